@@ -1,10 +1,13 @@
 //! CLI argument definitions and parsing structures.
 
 use super::types::{
-    filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter},
+    filters::{
+        ComparisonSort, EstimateSort, ExportFormat, ExportSite, FantasyTeamFilter, GroupBy,
+        InjuryStatusFilter, NflTeam, PlayerDataGroupBy, RosterStatusFilter, WeekOverWeekMetric,
+    },
     ids::LeagueId,
-    position::Position,
-    time::{Season, Week},
+    position::{Position, PositionSelector},
+    time::{Season, UtcTimestamp, Week, WeekRange},
 };
 use clap::{Args, Parser, Subcommand};
 
@@ -19,11 +22,12 @@ pub struct CommonFilters {
     #[clap(long, short = 'n')]
     pub player_name: Option<Vec<String>>,
 
-    /// Filter by position (repeatable): `-p QB -p RB`.
-    #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(Position))]
-    pub positions: Option<Vec<Position>>,
+    /// Filter by position (repeatable): `-p QB -p RB`. Groups: SKILL (RB/WR/TE),
+    /// OFFENSE (QB/RB/WR/TE), KICKERS-AND-DEFENSE (K/D/ST); mixed values combine.
+    #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(PositionSelector))]
+    pub positions: Option<Vec<PositionSelector>>,
 
-    /// Season year (e.g. 2025).
+    /// Season year (e.g. 2025); defaults to the current season.
     #[clap(long, short, default_value_t = Season::default())]
     pub season: Season,
 
@@ -46,6 +50,10 @@ pub struct CommonFilters {
     /// Filter by exact fantasy team ID.
     #[clap(long)]
     pub team_id: Option<u32>,
+
+    /// Filter by NFL team abbreviation (e.g. `BUF`); includes the team's D/ST.
+    #[clap(long)]
+    pub nfl_team: Option<NflTeam>,
 }
 
 impl CommonFilters {
@@ -57,15 +65,69 @@ impl CommonFilters {
             .or_else(|| self.team_id.map(FantasyTeamFilter::Id))
             .or(None)
     }
+
+    /// Positions to filter by, with groups expanded
+    pub fn get_positions(&self) -> Option<Vec<Position>> {
+        self.positions.as_deref().map(PositionSelector::expand)
+    }
 }
 
 #[derive(Debug, Parser)]
 #[clap(name = "espn-ffl", about = "ESPN Fantasy Football CLI")]
 pub struct ESPN {
+    /// Never write cache files (or set `ESPN_FFL_NO_CACHE_WRITE=1`); existing caches
+    /// are still read and in-memory caching is unaffected.
+    #[clap(long, global = true)]
+    pub no_cache_write: bool,
+
+    /// Ignore the local scoring overrides file and score with league settings only
+    #[clap(long, global = true)]
+    pub no_overrides: bool,
+
+    /// Leave players whose position can't be determined out of point computation
+    /// instead of scoring them with base points only
+    #[clap(long, global = true)]
+    pub strict_positions: bool,
+
+    /// Write unknown optional fields in JSON output as `null` instead of leaving them
+    /// out (kept for one release while scripts migrate)
+    #[clap(long, global = true)]
+    pub legacy_json: bool,
+
+    /// Fit table output to this many columns instead of the terminal's width
+    #[clap(long, global = true, value_parser = clap::value_parser!(u16).range(20..))]
+    pub width: Option<u16>,
+
+    /// Print HTTP, cache, database and per-stage timing counters when the run ends
+    /// (also on with `--verbose`)
+    #[clap(long, global = true)]
+    pub timings: bool,
+
+    /// Cap the worker threads used to parse and score players (default: one per CPU)
+    #[clap(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    pub max_threads: Option<u16>,
+
+    /// Use this database file instead of `players.db` in the cache directory (or set
+    /// `ESPN_FFL_DB_PATH`), e.g. to keep a separate database per league
+    #[clap(long, global = true)]
+    pub db_path: Option<std::path::PathBuf>,
+
     #[clap(subcommand)]
     pub command: Commands,
 }
 
+impl ESPN {
+    /// Whether to collect and print run metrics: `--timings`, or a command's `--verbose`
+    pub fn wants_timings(&self) -> bool {
+        self.timings
+            || matches!(
+                self.command,
+                Commands::LeagueData { verbose: true, .. }
+                    | Commands::UpdateAllData { verbose: true, .. }
+            )
+    }
+}
+
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     /// Fetch and optionally refresh cached league settings for a season + league
@@ -78,7 +140,7 @@ pub enum Commands {
         #[clap(long)]
         refresh: bool,
 
-        /// Season year (e.g. 2025).
+        /// Season year (e.g. 2025); defaults to the current season.
         #[clap(long, short, default_value_t = Season::default())]
         season: Season,
 
@@ -95,6 +157,11 @@ pub enum Commands {
         #[clap(flatten)]
         filters: CommonFilters,
 
+        /// Several weeks instead of `--week`: a range (`1-8`), a list (`1,3,5`), both (`1-4,9`)
+        /// or `all`. Rows keep their week, sorted by points within each week.
+        #[clap(long, visible_alias = "week-range", conflicts_with = "week")]
+        weeks: Option<WeekRange>,
+
         /// Print request URL and headers for debugging.
         #[clap(long)]
         debug: bool,
@@ -103,21 +170,102 @@ pub enum Commands {
         #[clap(long)]
         json: bool,
 
+        /// Output newline-delimited JSON: one compact object per player, same fields as `--json`.
+        #[clap(long, conflicts_with = "json")]
+        json_lines: bool,
+
+        /// Output CSV with a header row (id, name, position, week, projected, points,
+        /// injury_status, is_rostered, team_name) for spreadsheets.
+        #[clap(
+            long,
+            conflicts_with_all = ["json", "json_lines", "both", "group_by", "sparkline", "explain", "show_notes", "with_news"]
+        )]
+        csv: bool,
+
         /// Use projected points instead of actual (statSourceId == 1)
         #[clap(long = "proj")]
         projected: bool,
 
-        /// Force refresh player positions in database (useful after position mapping updates)
+        /// Force refresh player positions in database (useful after position mapping updates).
+        /// Also names team defenses stored as "Player -16002" after their NFL team.
         #[clap(long)]
         refresh_positions: bool,
 
-        /// Clear all data from the database before fetching (useful for starting fresh)
+        /// Clear all data from the database before fetching (useful for starting fresh).
+        /// Player notes are kept unless `--include-notes` is also passed.
         #[clap(long)]
         clear_db: bool,
 
+        /// Also delete player notes when clearing the database.
+        #[clap(long, requires = "clear_db")]
+        include_notes: bool,
+
         /// Force refresh from ESPN API even if cached data exists
         #[clap(long)]
         refresh: bool,
+
+        /// Show each player's recent actual points as a sparkline (raw weekly array in JSON).
+        #[clap(long, conflicts_with = "weeks")]
+        sparkline: bool,
+
+        /// Render sparklines with ASCII characters for terminals without unicode support.
+        #[clap(long, requires = "sparkline")]
+        ascii: bool,
+
+        /// Show projected and actual points side by side with the difference.
+        #[clap(long, conflicts_with_all = ["projected", "sparkline", "weeks"])]
+        both: bool,
+
+        /// Order players by points, name, position, fantasy team, or (with `--both`) delta
+        /// (alias projection-diff). Players that tie stay in points order.
+        #[clap(long, value_enum, default_value_t = ComparisonSort::Points)]
+        sort: ComparisonSort,
+
+        /// Reverse the `--sort` order; players without a value still come last.
+        #[clap(long)]
+        reverse: bool,
+
+        /// Append each player's latest note (all notes in JSON).
+        #[clap(long)]
+        show_notes: bool,
+
+        /// Show each player's latest ESPN news headline, with the date it was published.
+        #[clap(long)]
+        with_news: bool,
+
+        /// Show only the first N players in `--sort` order (the N highest-scoring by
+        /// default). With `--group-by team` the limit applies within each team, and with
+        /// `--weeks` within each week.
+        #[clap(long, value_name = "N")]
+        top: Option<usize>,
+
+        /// Section output by fantasy team, free agents last, with per-team point subtotals
+        /// (projected and actual with `--both`).
+        #[clap(
+            long,
+            value_enum,
+            conflicts_with_all = ["json_lines", "sparkline", "explain", "show_notes", "with_news", "weeks"]
+        )]
+        group_by: Option<PlayerDataGroupBy>,
+
+        /// Break each player's actual points down by stat (e.g. "FG 50+: 1 x 5.00").
+        #[clap(long, conflicts_with_all = ["projected", "both", "sparkline", "weeks"])]
+        explain: bool,
+
+        /// Show only players whose injury status changed since this UTC time
+        /// (e.g. 2025-09-14T12:00Z) or in the last N hours (e.g. `12h`), as seen by
+        /// earlier fetches of the week.
+        #[clap(
+            long,
+            value_name = "TIME",
+            value_parser = UtcTimestamp::parse_since,
+            conflicts_with = "both"
+        )]
+        status_changed_since: Option<UtcTimestamp>,
+
+        /// Don't warn when actual points are requested for a week that hasn't been played yet.
+        #[clap(long)]
+        quiet: bool,
     },
 
     /// Analyze projection accuracy and generate predictions for players.
@@ -131,6 +279,14 @@ pub enum Commands {
         #[clap(long)]
         json: bool,
 
+        /// Output newline-delimited JSON: one compact object per player, same fields as `--json`.
+        #[clap(long, conflicts_with_all = ["json", "group_by"])]
+        json_lines: bool,
+
+        /// Output CSV with a header row, one line per player, for spreadsheets.
+        #[clap(long, conflicts_with_all = ["json", "json_lines", "group_by"])]
+        csv: bool,
+
         /// Force refresh from ESPN API even if cached data exists
         #[clap(long)]
         refresh: bool,
@@ -138,28 +294,835 @@ pub enum Commands {
         /// Bias adjustment strength (0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified correction)
         #[clap(long)]
         bias_strength: Option<f64>,
+
+        /// Weight recent weeks more in the bias: each earlier week's error counts
+        /// `D^weeks_ago` (e.g. 0.8); 1.0 weighs every week equally.
+        #[clap(long, value_name = "D")]
+        bias_decay: Option<f64>,
+
+        /// Section output by position with positional rank and replacement baseline.
+        #[clap(long, value_enum)]
+        group_by: Option<GroupBy>,
+
+        /// Show only the N players with the highest estimates, after all filters. With
+        /// `--group-by position` the limit applies within each position.
+        #[clap(long, value_name = "N")]
+        top: Option<usize>,
+
+        /// Order players by estimated points, or by value over their position's
+        /// replacement level (`vorp`) to weigh scarce positions fairly.
+        #[clap(long, value_enum, default_value_t = EstimateSort::Points)]
+        sort: EstimateSort,
+
+        /// Include weeks a player did not play (DNP) as zero-point games in bias history.
+        #[clap(long)]
+        include_dnp: bool,
+
+        /// Blend in recent form (recency-weighted average of the last 4 actual games)
+        /// with this weight: 0 = bias-adjusted ESPN only, 1 = recent form only.
+        #[clap(long, value_name = "W")]
+        blend_recent: Option<f64>,
+
+        /// Append each player's latest note (all notes in JSON).
+        #[clap(long)]
+        show_notes: bool,
+
+        /// List the weeks behind each player's bias (best with a name filter).
+        #[clap(long)]
+        show_history: bool,
+
+        /// Also estimate free agents with no ESPN projection for the week from recent
+        /// form (rostered players without one are always included).
+        #[clap(long)]
+        include_unprojected: bool,
+
+        /// Only show players the adjusted projection ranks well above their
+        /// position rank by ESPN start rate (the "crowd" column).
+        #[clap(long)]
+        contrarian: bool,
+
+        /// List players per position as must start / solid start / flex consideration /
+        /// sit against the position's replacement level (a `tier` field in JSON).
+        #[clap(long, conflicts_with = "group_by")]
+        tiers: bool,
+
+        /// Judge lineup locks at this UTC time instead of now (e.g. 2025-09-14T18:30Z).
+        #[clap(long, value_name = "TIME")]
+        as_of: Option<UtcTimestamp>,
+
+        /// Also write the shown players' projections, floors and ceilings to this CSV file
+        /// for third-party lineup optimizers.
+        #[clap(long, value_name = "PATH")]
+        export_projections: Option<std::path::PathBuf>,
+
+        /// Site whose name, position and team conventions the CSV follows.
+        #[clap(long, value_enum, default_value_t = ExportSite::Espn, requires = "export_projections")]
+        site: ExportSite,
+
+        /// Refuse to export when more than this share of players (0 to 1) have no
+        /// projection (default: 0.25).
+        #[clap(long, value_name = "SHARE", requires = "export_projections")]
+        max_missing: Option<f64>,
     },
 
     /// Update all player data (actual and projected) for multiple weeks.
     ///
     /// Efficiently populates the database with complete historical data needed
     /// for accurate projection analysis by fetching both actual and projected
-    /// points for all players from week 1 through the specified week.
+    /// points for all players in the selected weeks.
     UpdateAllData {
         /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
         #[clap(long, short)]
         league_id: Option<LeagueId>,
 
-        /// Season year (e.g. 2025).
+        /// Season year (e.g. 2025); defaults to the current season.
         #[clap(long, short, default_value_t = Season::default())]
         season: Season,
 
-        /// Update data through this week (inclusive) - e.g., 4 means weeks 1,2,3,4.
-        #[clap(long)]
-        through_week: Week,
+        /// Weeks to update: a week (`4`), a range (`1-8`), a list (`1,3,5`) or `all`.
+        #[clap(long, required_unless_present = "through_week")]
+        weeks: Option<WeekRange>,
+
+        /// Update weeks 1 through this week (inclusive); same as `--weeks 1-N`.
+        #[clap(long, conflicts_with = "weeks")]
+        through_week: Option<Week>,
 
         /// Show detailed progress information.
         #[clap(long)]
         verbose: bool,
+
+        /// Stop at the first failed week instead of continuing and summarizing failures.
+        #[clap(long)]
+        fail_fast: bool,
+
+        /// Start without asking for confirmation after the pre-flight summary.
+        #[clap(long, short)]
+        yes: bool,
+    },
+    /// Show season records: high/low scores, streaks, blowouts and per-team bests.
+    ///
+    /// Built from the league schedule (mMatchupScore). Weeks with undecided
+    /// matchups are excluded so partial scores never show up as records.
+    SeasonRecords {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of the league schedule from ESPN.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Forecast your matchup for a week from bias-adjusted projections.
+    ///
+    /// Finds your opponent from the league schedule, builds each team's best
+    /// available lineup, and reports floor/ceiling ranges, the projected margin
+    /// and a win probability (normal approximation of the combined variance).
+    #[clap(visible_alias = "matchup")]
+    MatchupForecast {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Week to forecast.
+        #[clap(long, short, default_value_t = Week::default())]
+        week: Week,
+
+        /// Your fantasy team name (partial matching).
+        #[clap(long, required_unless_present = "team_id")]
+        team: Option<String>,
+
+        /// Your fantasy team ID.
+        #[clap(long, conflicts_with = "team")]
+        team_id: Option<u32>,
+
+        /// Bias adjustment strength (0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified correction)
+        #[clap(long)]
+        bias_strength: Option<f64>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of schedule, roster and projection data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+
+        /// Forecast as if this player were on your roster (ID or exact name; repeatable).
+        #[clap(long, value_name = "PLAYER")]
+        add: Vec<String>,
+
+        /// Forecast as if this player were off your roster (ID or exact name; repeatable).
+        #[clap(long, value_name = "PLAYER")]
+        remove: Vec<String>,
+
+        /// Judge lineup locks at this UTC time instead of now (e.g. 2025-09-14T18:30Z).
+        #[clap(long, value_name = "TIME")]
+        as_of: Option<UtcTimestamp>,
+    },
+    /// Pick a fantasy team's best starting lineup for a week.
+    ///
+    /// Fills the league's lineup slots (flex slots last) with the highest
+    /// bias-adjusted projections on the roster, and flags slots where an injured
+    /// player is, or would be, the best option.
+    OptimalLineup {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Week to pick the lineup for.
+        #[clap(long, short, default_value_t = Week::default())]
+        week: Week,
+
+        /// Fantasy team name (partial matching).
+        #[clap(long, required_unless_present = "team_id")]
+        team: Option<String>,
+
+        /// Fantasy team ID.
+        #[clap(long, conflicts_with = "team")]
+        team_id: Option<u32>,
+
+        /// Bias adjustment strength (0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified correction)
+        #[clap(long)]
+        bias_strength: Option<f64>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of roster and projection data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Compare two or more players for a week and recommend the one to start.
+    ///
+    /// Shows each player's ESPN projection, bias-adjusted estimate and confidence,
+    /// recent actual average and injury status side by side; the highest estimate
+    /// wins, ties going to the higher confidence.
+    Compare {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Week to compare.
+        #[clap(long, short, default_value_t = Week::default())]
+        week: Week,
+
+        /// Player name or ESPN player ID (repeatable, at least two): `-n Pollard -n Gibbs`.
+        /// A partial name works when only one player matches.
+        #[clap(
+            long = "player-name",
+            short = 'n',
+            value_name = "PLAYER",
+            required = true
+        )]
+        players: Vec<String>,
+
+        /// Bias adjustment strength (0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified correction)
+        #[clap(long)]
+        bias_strength: Option<f64>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of projection data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Estimate playoff odds by simulating the rest of the regular season.
+    ///
+    /// Standings and remaining matchups come from the league schedule; each team's
+    /// weekly scores are drawn from its season mean and spread so far.
+    PlayoffOdds {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Number of simulated seasons.
+        #[clap(long, default_value_t = 10_000, value_parser = clap::value_parser!(u32).range(1..))]
+        iterations: u32,
+
+        /// Playoff spots (defaults to the league setting).
+        #[clap(long)]
+        playoff_teams: Option<usize>,
+
+        /// RNG seed for reproducible results.
+        #[clap(long)]
+        seed: Option<u64>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of the league schedule from ESPN.
+        #[clap(long)]
+        refresh: bool,
+
+        /// Also write the report as a self-contained HTML page to share (e.g. `week3.html`).
+        #[clap(long, value_name = "PATH")]
+        share: Option<std::path::PathBuf>,
+    },
+    /// Prefetch everything needed for the current week so later queries use the cache.
+    ///
+    /// Warms league settings, week and current rosters, projections and the league and
+    /// NFL schedules, skipping anything still fresh, and reports what was fetched.
+    WarmCache {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Check stored data for earlier weeks and print commands that fill any gaps.
+        #[clap(long)]
+        check_coverage: bool,
+
+        /// Output per-item status as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Refetch every item even when its cache is fresh.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Show fantasy team rosters for a week.
+    ///
+    /// Lists each rostered player's lineup slot (starters first, then bench and
+    /// IR), position and the week's stored projected and actual points. Without
+    /// a team filter every team is shown, ordered by name.
+    Roster {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Week whose rosters to show.
+        #[clap(long, short)]
+        week: Week,
+
+        /// Only show teams matching this name (partial matching).
+        #[clap(long)]
+        team: Option<String>,
+
+        /// Only show the team with this exact ID.
+        #[clap(long, conflicts_with = "team")]
+        team_id: Option<u32>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of roster data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Show each team's roster changes between two weeks.
+    ///
+    /// Lists players added, dropped and moved between starter/bench/IR slots,
+    /// with the points each scored in the weeks after `--from` through `--to`.
+    #[clap(visible_alias = "roster-changes")]
+    RosterDiff {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Earlier week to compare from.
+        #[clap(long, visible_alias = "from-week")]
+        from: Week,
+
+        /// Later week to compare to.
+        #[clap(long, visible_alias = "to-week")]
+        to: Week,
+
+        /// Only show teams matching this name (partial matching).
+        #[clap(long)]
+        team: Option<String>,
+
+        /// Only show the team with this exact ID.
+        #[clap(long, conflicts_with = "team")]
+        team_id: Option<u32>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of roster data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+    },
+    /// Show each team's starter and bench points for a completed week.
+    ///
+    /// Totals come from the actual points stored by `player-data` and
+    /// `update-all-data` plus the week's lineup; teams with unscored starters
+    /// are flagged as partial.
+    TeamTotals {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Week to total.
+        #[clap(long, short)]
+        week: Week,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+
+        /// Force refresh of roster data from ESPN.
+        #[clap(long)]
+        refresh: bool,
+
+        /// Also write the report as a self-contained HTML page to share (e.g. `week3.html`).
+        #[clap(long, value_name = "PATH")]
+        share: Option<std::path::PathBuf>,
+    },
+    /// Look back at a team's kicker and D/ST starts against streaming and holding.
+    ///
+    /// For every week with a stored roster snapshot, compares the points the team's
+    /// K/DEF starters scored with the best free agent that week ("perfect streaming")
+    /// and with holding the position's season-long top scorer, then gives a verdict.
+    StreamingRecap {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Fantasy team name (partial matching).
+        #[clap(long, required_unless_present = "team_id")]
+        team: Option<String>,
+
+        /// Fantasy team ID.
+        #[clap(long, conflicts_with = "team")]
+        team_id: Option<u32>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Rank players by a single raw stat (passing yards, rushing TDs, receptions, ...).
+    ///
+    /// Reads the raw stats stored by `player-data` and `update-all-data`; fetch the
+    /// weeks first. Fantasy points for the same week(s) are shown alongside.
+    CategoryLeaders {
+        /// Stat category, e.g. `passing-yards`, `rushing-touchdowns`, `receptions`.
+        category: String,
+
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Single week.
+        #[clap(long, short, required_unless_present = "season_totals")]
+        week: Option<Week>,
+
+        /// Sum the category over every stored week of the season instead.
+        #[clap(long, conflicts_with = "week")]
+        season_totals: bool,
+
+        /// Filter by position (repeatable): `-p QB -p RB`. Groups: SKILL (RB/WR/TE),
+        /// OFFENSE (QB/RB/WR/TE), KICKERS-AND-DEFENSE (K/D/ST); mixed values combine.
+        #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(PositionSelector))]
+        positions: Option<Vec<PositionSelector>>,
+
+        /// Filter by roster status.
+        #[clap(long)]
+        roster_status: Option<RosterStatusFilter>,
+
+        /// Number of players to show.
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show each player's weekly finishes at their position over a season.
+    ///
+    /// Ranks players within their position for every stored week from actual points,
+    /// then counts top-12 and top-24 finishes with the median and spread of the ranks.
+    PositionalRanks {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Filter by position (repeatable): `-p QB -p RB`. Groups: SKILL (RB/WR/TE),
+        /// OFFENSE (QB/RB/WR/TE), KICKERS-AND-DEFENSE (K/D/ST); mixed values combine.
+        #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(PositionSelector))]
+        positions: Option<Vec<PositionSelector>>,
+
+        /// Only list players with at least this many ranked weeks.
+        #[clap(long, default_value_t = 1)]
+        min_games: u32,
+
+        /// Output results as JSON (with every weekly rank) instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Report how accurate ESPN's projections and the bias-adjusted ones were, by position.
+    ///
+    /// Computes the mean absolute and root mean squared error of stored projections
+    /// against actual points; each week's adjusted projection is rebuilt from the
+    /// weeks before it only.
+    AccuracyReport {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Last week to score; defaults to every stored week.
+        #[clap(long)]
+        through_week: Option<Week>,
+
+        /// Bias adjustment strength (0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified correction)
+        #[clap(long)]
+        bias_strength: Option<f64>,
+
+        /// Weight recent weeks more in the bias: each earlier week's error counts
+        /// `D^weeks_ago` (e.g. 0.8); 1.0 weighs every week equally.
+        #[clap(long, value_name = "D")]
+        bias_decay: Option<f64>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show each player's full-season point pace against a preseason expectation.
+    ///
+    /// Extrapolates stored actual points over the team's remaining games (byes and
+    /// weeks the player didn't play are left out) and lists the biggest over- and
+    /// under-achievers against the earliest stored ESPN weekly projection first.
+    Pace {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Filter by position (repeatable): `-p QB -p RB`. Groups: SKILL (RB/WR/TE),
+        /// OFFENSE (QB/RB/WR/TE), KICKERS-AND-DEFENSE (K/D/ST); mixed values combine.
+        #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(PositionSelector))]
+        positions: Option<Vec<PositionSelector>>,
+
+        /// Only list players with at least this many games played.
+        #[clap(long, default_value_t = 1)]
+        min_games: u32,
+
+        /// Number of players to show.
+        #[clap(long, default_value_t = 20)]
+        top: usize,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show one player's stored stats week by week for a season.
+    ///
+    /// Lists ESPN's projection, the actual points, their difference, a running
+    /// average and the injury and roster status for every stored week, followed by
+    /// a sparkline of the weekly points.
+    StatsHistory {
+        /// Player name or ESPN player ID; a partial name works when only one player matches.
+        #[clap(long, short = 'n')]
+        player_name: String,
+
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Render the sparkline with ASCII characters for terminals without unicode support.
+        #[clap(long)]
+        ascii: bool,
+
+        /// Output the stored weekly rows as JSON instead of a table.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Show which players rose and fell most between two weeks.
+    ///
+    /// Compares the stored actual points, ESPN weekly projections, ownership and
+    /// injury designations of every player between two weeks, ranking risers and
+    /// fallers by one of them and listing injury status changes.
+    WeekOverWeek {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Later week to compare to.
+        #[clap(long, short)]
+        week: Week,
+
+        /// Earlier week to compare from; defaults to the week before `--week`.
+        #[clap(long)]
+        from: Option<Week>,
+
+        /// Filter by position (repeatable): `-p QB -p RB`. Groups: SKILL (RB/WR/TE),
+        /// OFFENSE (QB/RB/WR/TE), KICKERS-AND-DEFENSE (K/D/ST); mixed values combine.
+        #[clap(short = 'p', long = "position", value_parser = clap::value_parser!(PositionSelector))]
+        positions: Option<Vec<PositionSelector>>,
+
+        /// Filter by roster status.
+        #[clap(long)]
+        roster_status: Option<RosterStatusFilter>,
+
+        /// Change that ranks risers and fallers.
+        #[clap(long, value_enum, default_value_t = WeekOverWeekMetric::Actual)]
+        by: WeekOverWeekMetric,
+
+        /// Number of risers and of fallers to show.
+        #[clap(long, default_value_t = 10)]
+        top: usize,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+    /// Keep local notes on players ("handcuff for X", "sell high after week 9").
+    Note {
+        #[clap(subcommand)]
+        command: NoteCommands,
+    },
+    /// Local database maintenance.
+    Db {
+        #[clap(subcommand)]
+        command: DbCommands,
+    },
+    /// Manage the ESPN cookies used to read private leagues.
+    Auth {
+        #[clap(subcommand)]
+        command: AuthCommands,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuthCommands {
+    /// Prompt for the SWID and espn_s2 cookies (input hidden) and save them to the
+    /// credentials file, readable only by you.
+    Set,
+
+    /// Show which credentials requests use and check them with a league request.
+    Status {
+        /// League ID to test against (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum DbCommands {
+    /// Scan stored weekly stats for inconsistent rows.
+    ///
+    /// Reports orphaned rows, points recorded for future weeks and negative
+    /// points, with example rows for each. `--fix` applies the safe repairs.
+    Check {
+        /// League ID used to look up the current week (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Current week of the season (fetched from ESPN if omitted).
+        #[clap(long)]
+        current_week: Option<Week>,
+
+        /// Delete orphaned rows and clear actual points recorded for future weeks.
+        #[clap(long)]
+        fix: bool,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Show a week-by-week grid of the data stored for a season.
+    ///
+    /// Rows are actual points, projected points, roster snapshots and ownership;
+    /// each week is marked complete, partial or missing.
+    Coverage {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Number of weeks to show.
+        #[clap(long, default_value_t = 18)]
+        weeks: u16,
+
+        /// Weeks with fewer than this share of the expected rows are marked partial.
+        #[clap(long, default_value_t = 0.9)]
+        partial_below: f64,
+
+        /// Output the counts as JSON instead of a grid.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Export every stored player-week for a season.
+    ///
+    /// Columns: player_id, name, season, week, projected, actual, position,
+    /// team, injury_status, is_rostered.
+    Export {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Output format (parquet requires building with `--features parquet`).
+        #[clap(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// File to write (stdout when omitted).
+        #[clap(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+
+    /// List the names each fantasy team has gone by in a season.
+    ///
+    /// Names are recorded from the roster data fetched by player-data,
+    /// projection-analysis and roster-diff, with the weeks each was seen.
+    TeamNames {
+        /// League ID (or set `ESPN_FFL_LEAGUE_ID` env var).
+        #[clap(long, short)]
+        league_id: Option<LeagueId>,
+
+        /// Season year (e.g. 2025); defaults to the current season.
+        #[clap(long, short, default_value_t = Season::default())]
+        season: Season,
+
+        /// Only show this team.
+        #[clap(long)]
+        team_id: Option<u32>,
+
+        /// Output the history as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// List the earlier names of players ESPN renamed (suffixes added, name changes).
+    ///
+    /// Old names are recorded when player-data stores a player under a new name;
+    /// name filters and player lookups still match them.
+    Aliases {
+        /// Only show this player (ID, or current or old exact name).
+        #[clap(long)]
+        player: Option<String>,
+
+        /// Output the aliases as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Merge players stored under both the positive and negative form of an ID.
+    ///
+    /// Older versions stored some D/ST entries under the wrong sign; pairs with the
+    /// same name and position are merged into the ID ESPN uses today.
+    Dedupe {
+        /// List the pairs that would be merged without changing anything.
+        #[clap(long)]
+        dry_run: bool,
+
+        /// Output the merged pairs as JSON.
+        #[clap(long)]
+        json: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum NoteCommands {
+    /// Attach a note to a player.
+    Add {
+        /// Player ID or exact name (ambiguous names must use the ID).
+        player: String,
+
+        /// Note text.
+        text: String,
+    },
+
+    /// List notes, newest first.
+    List {
+        /// Only show notes for this player (ID or exact name).
+        player: Option<String>,
+
+        /// Output results as JSON instead of text lines.
+        #[clap(long)]
+        json: bool,
+    },
+
+    /// Delete a note by its ID.
+    Rm {
+        /// Note ID as shown by `note list`.
+        id: i64,
     },
 }
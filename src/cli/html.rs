@@ -0,0 +1,211 @@
+//! Self-contained HTML reports for `--share`.
+//!
+//! A report is a title, a season/week line and a list of tables, rendered as one
+//! static page with inline CSS and no scripts or external requests, so it can be
+//! dropped into a league chat as is. Every string is escaped on the way out.
+
+use std::{fmt::Write as _, path::Path};
+
+use super::render::Align;
+use crate::{
+    espn::http::{format_utc_date, format_utc_timestamp},
+    Result,
+};
+
+const STYLE: &str = "body{font-family:system-ui,-apple-system,sans-serif;margin:2em auto;\
+max-width:60em;padding:0 1em;color:#1d2733}\
+h1{margin-bottom:0}.meta{color:#5b6773;margin-top:.25em}\
+table{border-collapse:collapse;margin:1em 0;width:100%}\
+th,td{padding:.35em .6em;border-bottom:1px solid #dde3e9;text-align:left}\
+th{background:#f1f4f7}td.num,th.num{text-align:right;font-variant-numeric:tabular-nums}\
+p.note{color:#5b6773;font-size:.9em}";
+
+/// Escape text for use in HTML element content and attribute values
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// One titled table in a report
+#[derive(Debug, Clone)]
+pub struct HtmlSection {
+    heading: String,
+    columns: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+    notes: Vec<String>,
+}
+
+impl HtmlSection {
+    pub fn new(heading: impl Into<String>, columns: &[(&str, Align)]) -> Self {
+        Self {
+            heading: heading.into(),
+            columns: columns
+                .iter()
+                .map(|(header, align)| (header.to_string(), *align))
+                .collect(),
+            rows: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    /// Add a row; cells beyond the column count are dropped, missing ones left empty
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        self.rows.push(cells);
+    }
+
+    /// Add a line of text under the table
+    pub fn add_note(&mut self, note: impl Into<String>) {
+        self.notes.push(note.into());
+    }
+}
+
+/// A shareable report page
+#[derive(Debug, Clone)]
+pub struct HtmlReport {
+    title: String,
+    /// Season/week line under the title
+    subtitle: String,
+    /// Unix seconds, shown as the generation time
+    generated_at: u64,
+    sections: Vec<HtmlSection>,
+}
+
+impl HtmlReport {
+    /// A report generated now
+    pub fn new(title: impl Into<String>, subtitle: impl Into<String>) -> Self {
+        let generated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::generated_at(title, subtitle, generated_at)
+    }
+
+    /// A report stamped with a fixed generation time
+    pub fn generated_at(
+        title: impl Into<String>,
+        subtitle: impl Into<String>,
+        generated_at: u64,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            subtitle: subtitle.into(),
+            generated_at,
+            sections: Vec::new(),
+        }
+    }
+
+    pub fn add_section(&mut self, section: HtmlSection) {
+        self.sections.push(section);
+    }
+
+    /// The whole page
+    pub fn render(&self) -> String {
+        let mut html = String::new();
+        let title = escape_html(&self.title);
+        // Writing to a String can't fail
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n\
+             <h1>{title}</h1>\n<p class=\"meta\">{} &middot; generated {} ({})</p>\n",
+            escape_html(&self.subtitle),
+            format_utc_date(self.generated_at),
+            format_utc_timestamp(self.generated_at),
+        );
+
+        for section in &self.sections {
+            let _ = writeln!(html, "<h2>{}</h2>", escape_html(&section.heading));
+            html.push_str("<table>\n<thead><tr>");
+            for (header, align) in &section.columns {
+                let _ = write!(html, "<th{}>{}</th>", class(*align), escape_html(header));
+            }
+            html.push_str("</tr></thead>\n<tbody>\n");
+            for row in &section.rows {
+                html.push_str("<tr>");
+                for (i, (_, align)) in section.columns.iter().enumerate() {
+                    let cell = row.get(i).map(String::as_str).unwrap_or_default();
+                    let _ = write!(html, "<td{}>{}</td>", class(*align), escape_html(cell));
+                }
+                html.push_str("</tr>\n");
+            }
+            html.push_str("</tbody>\n</table>\n");
+            for note in &section.notes {
+                let _ = writeln!(html, "<p class=\"note\">{}</p>", escape_html(note));
+            }
+        }
+
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+
+    /// Write the page to `path`, replacing any existing file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+}
+
+fn class(align: Align) -> &'static str {
+    match align {
+        Align::Left => "",
+        Align::Right => " class=\"num\"",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_escapes_strings_and_is_well_formed() {
+        let mut section = HtmlSection::new(
+            "Team Totals",
+            &[("Team", Align::Left), ("Starters", Align::Right)],
+        );
+        section.add_row(vec![
+            "<script>alert('pwned')</script> & Co".to_string(),
+            "101.5".to_string(),
+        ]);
+        section.add_row(vec!["Short Row".to_string()]);
+        section.add_note("\"Partial\" totals");
+        let mut report = HtmlReport::generated_at("Weekly <Recap>", "Season 2025 Week 3", 0);
+        report.add_section(section);
+
+        let html = report.render();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.ends_with("</html>\n"));
+        assert!(!html.contains("<script"));
+        assert!(html.contains("&lt;script&gt;alert(&#39;pwned&#39;)&lt;/script&gt; &amp; Co"));
+        assert!(html.contains("<title>Weekly &lt;Recap&gt;</title>"));
+        assert!(html.contains("&quot;Partial&quot; totals"));
+        assert!(html.contains("generated 1970-01-01 (Thu 00:00 UTC)"));
+        assert!(!html.contains("http://") && !html.contains("https://"));
+
+        // Every opened element is closed, and short rows are padded to the columns
+        for tag in [
+            "html", "head", "body", "table", "thead", "tbody", "tr", "th", "td", "p",
+        ] {
+            let opened = html.matches(&format!("<{}>", tag)).count()
+                + html.matches(&format!("<{} ", tag)).count();
+            assert_eq!(
+                opened,
+                html.matches(&format!("</{}>", tag)).count(),
+                "{}",
+                tag
+            );
+        }
+        assert_eq!(html.matches("<td").count(), 4);
+        assert_eq!(html.matches("<td class=\"num\">101.5</td>").count(), 1);
+    }
+}
@@ -1,6 +1,8 @@
 //! CLI argument definitions and parsing.
 
 pub mod args;
+pub mod html;
+pub mod render;
 pub mod types;
 
-pub use args::{Commands, CommonFilters, ESPN};
+pub use args::{AuthCommands, Commands, CommonFilters, DbCommands, NoteCommands, ESPN};
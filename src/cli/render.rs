@@ -0,0 +1,665 @@
+//! Text rendering helpers shared by command output.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Unicode sparkline levels, lowest to highest
+const SPARK_LEVELS: [char; 5] = ['▁', '▂', '▃', '▅', '▇'];
+
+/// ASCII fallback levels for terminals without unicode support
+const ASCII_LEVELS: [char; 5] = ['_', '-', '=', '+', '#'];
+
+/// Render weekly values as a sparkline normalized to `[min, max]`.
+///
+/// Missing weeks (`None`) render as a gap marker (`·`, or `.` in ASCII mode).
+/// Values outside the bounds are clamped; a flat range renders at the middle level.
+pub fn render_sparkline(values: &[Option<f64>], min: f64, max: f64, ascii: bool) -> String {
+    let (levels, gap) = if ascii {
+        (&ASCII_LEVELS, '.')
+    } else {
+        (&SPARK_LEVELS, '·')
+    };
+    let top = levels.len() - 1;
+
+    values
+        .iter()
+        .map(|value| match value {
+            None => gap,
+            Some(v) => {
+                if max <= min {
+                    return levels[top / 2];
+                }
+                let ratio = ((v - min) / (max - min)).clamp(0.0, 1.0);
+                levels[(ratio * top as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
+static LEGACY_JSON: AtomicBool = AtomicBool::new(false);
+
+/// Write unknown optional fields as `null` again, as before they were left out
+pub fn enable_legacy_json() {
+    LEGACY_JSON.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--legacy-json` is in effect
+pub fn legacy_json() -> bool {
+    LEGACY_JSON.load(Ordering::Relaxed)
+}
+
+/// `skip_serializing_if` for optional status fields in JSON output: left out when
+/// unknown, unless `--legacy-json` asks for explicit nulls
+pub fn skip_none<T>(value: &Option<T>) -> bool {
+    value.is_none() && !legacy_json()
+}
+
+/// Normalize a player name for collision checks: lowercase, punctuation dropped,
+/// whitespace collapsed ("D.J. Moore" and "DJ Moore" both become "dj moore").
+pub fn normalize_player_name(name: &str) -> String {
+    name.split_whitespace()
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// A player as it appears in one result set, for [`disambiguate_names`]
+#[derive(Debug, Clone, Copy)]
+pub struct NameEntry<'a, K> {
+    pub id: K,
+    pub name: &'a str,
+    pub position: &'a str,
+    /// NFL team abbreviation, when known
+    pub team: Option<&'a str>,
+}
+
+/// Display names for a result set, suffixed only where names collide.
+///
+/// Players whose normalized names are unique keep their plain name. Colliding
+/// players get "(POS, TEAM)" — or "(POS)" without a known team — and the ID is
+/// appended when that still isn't enough to tell them apart.
+pub fn disambiguate_names<K>(entries: &[NameEntry<'_, K>]) -> HashMap<K, String>
+where
+    K: Copy + Eq + Hash + Display,
+{
+    let mut by_name: HashMap<String, Vec<&NameEntry<'_, K>>> = HashMap::new();
+    for entry in entries {
+        let group = by_name
+            .entry(normalize_player_name(entry.name))
+            .or_default();
+        if !group.iter().any(|e| e.id == entry.id) {
+            group.push(entry);
+        }
+    }
+
+    let mut names = HashMap::new();
+    for group in by_name.values() {
+        if group.len() == 1 {
+            names.insert(group[0].id, group[0].name.to_string());
+            continue;
+        }
+
+        let suffix = |entry: &NameEntry<'_, K>| match entry.team {
+            Some(team) => format!("{}, {}", entry.position, team),
+            None => entry.position.to_string(),
+        };
+        for entry in group {
+            let ambiguous = group
+                .iter()
+                .filter(|other| suffix(other) == suffix(entry))
+                .count()
+                > 1;
+            let label = if ambiguous {
+                format!("{} ({}, #{})", entry.name, suffix(entry), entry.id)
+            } else {
+                format!("{} ({})", entry.name, suffix(entry))
+            };
+            names.insert(entry.id, label);
+        }
+    }
+    names
+}
+
+/// Output width set with `--width`; 0 means use the terminal's width
+static OUTPUT_WIDTH: AtomicUsize = AtomicUsize::new(0);
+
+/// Space between table columns
+const COLUMN_GAP: &str = " ";
+
+/// Indent for the second line of the narrow layout
+const NARROW_INDENT: &str = "    ";
+
+/// Width a trailing free-text column is guaranteed before the flex column gives up space
+const REST_MIN_WIDTH: usize = 10;
+
+/// Narrowest a player name column gets before a table switches to two lines per player
+pub const NAME_MIN_WIDTH: usize = 12;
+
+/// Widest a player name column grows; longer names are truncated
+pub const NAME_MAX_WIDTH: usize = 32;
+
+/// Fit table output to `width` columns instead of the terminal's width
+pub fn set_output_width(width: usize) {
+    OUTPUT_WIDTH.store(width, Ordering::Relaxed);
+}
+
+/// Width tables are fitted to: the `--width` override, else the terminal's width.
+///
+/// `None` when stdout isn't a terminal, so piped output is never truncated.
+pub fn output_width() -> Option<usize> {
+    match OUTPUT_WIDTH.load(Ordering::Relaxed) {
+        0 => terminal_size::terminal_size().map(|(width, _)| width.0 as usize),
+        width => Some(width),
+    }
+}
+
+/// Shorten `text` to at most `width` terminal columns, ending in "…" when cut
+pub fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+
+    let mut out = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w > width - 1 {
+            break;
+        }
+        out.push(c);
+        used += w;
+    }
+    out.push('…');
+    out
+}
+
+/// Cell alignment within its column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnWidth {
+    Fixed,
+    Flex { min: usize, max: usize },
+    Rest,
+}
+
+/// A [`Table`] column
+#[derive(Debug, Clone)]
+pub struct Column {
+    header: String,
+    align: Align,
+    width: ColumnWidth,
+}
+
+impl Column {
+    /// Sized to its widest cell and never truncated (IDs, points, percentages)
+    pub fn fixed(header: impl Into<String>, align: Align) -> Self {
+        Self {
+            header: header.into(),
+            align,
+            width: ColumnWidth::Fixed,
+        }
+    }
+
+    /// Left-aligned column that takes the space the fixed columns leave, up to `max`.
+    ///
+    /// Cells are truncated to fit; with less than `min` left the table switches to
+    /// its two-line layout. A table has at most one flex column (the name).
+    pub fn flex(header: impl Into<String>, min: usize, max: usize) -> Self {
+        Self {
+            header: header.into(),
+            align: Align::Left,
+            width: ColumnWidth::Flex { min, max },
+        }
+    }
+
+    /// Free text at the end of the row (reasoning, notes), truncated to whatever
+    /// width is left. Must be the last column.
+    pub fn rest(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            align: Align::Left,
+            width: ColumnWidth::Rest,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Row {
+    cells: Vec<String>,
+    details: Vec<String>,
+}
+
+/// Console table fitted to the output width.
+///
+/// Fixed columns keep their natural width, the flex column absorbs the difference
+/// and a trailing rest column gets what's left. Below the flex column's minimum,
+/// each row prints as two lines: the flex cell, then the other cells indented.
+#[derive(Debug, Clone)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Row>,
+}
+
+impl Table {
+    pub fn new(columns: Vec<Column>) -> Self {
+        debug_assert!(
+            columns
+                .iter()
+                .filter(|c| matches!(c.width, ColumnWidth::Flex { .. }))
+                .count()
+                <= 1
+        );
+        debug_assert!(columns
+            .iter()
+            .rev()
+            .skip(1)
+            .all(|c| c.width != ColumnWidth::Rest));
+        Self {
+            columns,
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, cells: Vec<String>) {
+        debug_assert_eq!(cells.len(), self.columns.len());
+        self.rows.push(Row {
+            cells,
+            details: Vec::new(),
+        });
+    }
+
+    /// Add a free-form line under the last row (breakdowns, history)
+    pub fn add_detail(&mut self, line: String) {
+        if let Some(row) = self.rows.last_mut() {
+            row.details.push(line);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Print the table fitted to [`output_width`]
+    pub fn print(&self) {
+        for line in self.render(output_width()) {
+            println!("{}", line);
+        }
+    }
+
+    /// Header, rule and rows fitted to `width`; `None` renders every cell in full
+    pub fn render(&self, width: Option<usize>) -> Vec<String> {
+        let natural: Vec<usize> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                self.rows
+                    .iter()
+                    .map(|row| row.cells[i].width())
+                    .chain([column.header.width()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let Some(total) = width else {
+            return self.render_wide(&natural, None);
+        };
+
+        let mut widths = natural.clone();
+        let gaps = self.columns.len().saturating_sub(1) * COLUMN_GAP.len();
+        let fixed: usize = self
+            .columns
+            .iter()
+            .zip(&natural)
+            .filter(|(column, _)| column.width == ColumnWidth::Fixed)
+            .map(|(_, width)| width)
+            .sum();
+        let rest_min = match self.columns.last() {
+            Some(column) if column.width == ColumnWidth::Rest => REST_MIN_WIDTH,
+            _ => 0,
+        };
+        let available = total.saturating_sub(fixed + gaps + rest_min);
+
+        if let Some(flex) = self
+            .columns
+            .iter()
+            .position(|c| matches!(c.width, ColumnWidth::Flex { .. }))
+        {
+            let ColumnWidth::Flex { min, max } = self.columns[flex].width else {
+                unreachable!()
+            };
+            if available < min.min(natural[flex]) {
+                return self.render_narrow(flex, &natural, total);
+            }
+            widths[flex] = natural[flex].min(max).min(available);
+        }
+        if let Some(last) = widths.len().checked_sub(1) {
+            if self.columns[last].width == ColumnWidth::Rest {
+                let used: usize = widths[..last].iter().sum::<usize>() + gaps;
+                widths[last] = total.saturating_sub(used);
+            }
+        }
+
+        self.render_wide(&widths, Some(total))
+    }
+
+    fn render_wide(&self, widths: &[usize], total: Option<usize>) -> Vec<String> {
+        let line = |cells: &[&str]| {
+            let joined = cells
+                .iter()
+                .zip(&self.columns)
+                .zip(widths)
+                .map(|((cell, column), &width)| match (column.width, total) {
+                    (ColumnWidth::Rest, None) => cell.to_string(),
+                    _ => pad(&truncate(cell, width), width, column.align),
+                })
+                .collect::<Vec<_>>()
+                .join(COLUMN_GAP);
+            let joined = joined.trim_end();
+            match total {
+                Some(total) => truncate(joined, total),
+                None => joined.to_string(),
+            }
+        };
+
+        let headers: Vec<&str> = self.columns.iter().map(|c| c.header.as_str()).collect();
+        let rule: Vec<String> = self
+            .columns
+            .iter()
+            .zip(widths)
+            .map(|(column, &width)| match column.width {
+                ColumnWidth::Rest => "-".repeat(column.header.width().min(width)),
+                _ => "-".repeat(width),
+            })
+            .collect();
+        let rule: Vec<&str> = rule.iter().map(String::as_str).collect();
+
+        let mut lines = vec![line(&headers), line(&rule)];
+        for row in &self.rows {
+            let cells: Vec<&str> = row.cells.iter().map(String::as_str).collect();
+            lines.push(line(&cells));
+            lines.extend(row.details.iter().map(|detail| match total {
+                Some(total) => truncate(detail, total),
+                None => detail.clone(),
+            }));
+        }
+        lines
+    }
+
+    fn render_narrow(&self, flex: usize, natural: &[usize], total: usize) -> Vec<String> {
+        let second_line = |cells: &[&str]| {
+            let joined = cells
+                .iter()
+                .zip(&self.columns)
+                .zip(natural)
+                .enumerate()
+                .filter(|(i, _)| *i != flex)
+                .map(|(_, ((cell, column), &width))| match column.width {
+                    ColumnWidth::Rest => cell.to_string(),
+                    _ => pad(cell, width, column.align),
+                })
+                .collect::<Vec<_>>()
+                .join(COLUMN_GAP);
+            truncate(format!("{}{}", NARROW_INDENT, joined).trim_end(), total)
+        };
+
+        let headers: Vec<&str> = self.columns.iter().map(|c| c.header.as_str()).collect();
+        let header = second_line(&headers);
+        let mut lines = vec![
+            truncate(&self.columns[flex].header, total),
+            header.clone(),
+            "-".repeat(header.width()),
+        ];
+        for row in &self.rows {
+            let cells: Vec<&str> = row.cells.iter().map(String::as_str).collect();
+            lines.push(truncate(&row.cells[flex], total));
+            lines.push(second_line(&cells));
+            lines.extend(row.details.iter().map(|detail| truncate(detail, total)));
+        }
+        lines
+    }
+}
+
+/// Pad `text` to `width` terminal columns
+fn pad(text: &str, width: usize, align: Align) -> String {
+    let fill = " ".repeat(width.saturating_sub(text.width()));
+    match align {
+        Align::Left => format!("{}{}", text, fill),
+        Align::Right => format!("{}{}", fill, text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_sparkline_unicode() {
+        let values = [Some(0.0), Some(5.0), Some(10.0), Some(20.0), Some(40.0)];
+        assert_eq!(render_sparkline(&values, 0.0, 40.0, false), "▁▂▂▃▇");
+    }
+
+    #[test]
+    fn test_render_sparkline_ascii_and_gaps() {
+        let values = [Some(0.0), None, Some(40.0)];
+        assert_eq!(render_sparkline(&values, 0.0, 40.0, true), "_.#");
+        assert_eq!(render_sparkline(&values, 0.0, 40.0, false), "▁·▇");
+    }
+
+    #[test]
+    fn test_render_sparkline_clamps_and_flat_range() {
+        assert_eq!(
+            render_sparkline(&[Some(-5.0), Some(99.0)], 0.0, 10.0, true),
+            "_#"
+        );
+        assert_eq!(render_sparkline(&[Some(7.0)], 7.0, 7.0, true), "=");
+        assert_eq!(render_sparkline(&[], 0.0, 10.0, false), "");
+    }
+
+    fn entry<'a>(
+        id: u32,
+        name: &'a str,
+        position: &'a str,
+        team: Option<&'a str>,
+    ) -> NameEntry<'a, u32> {
+        NameEntry {
+            id,
+            name,
+            position,
+            team,
+        }
+    }
+
+    #[test]
+    fn test_normalize_player_name() {
+        assert_eq!(normalize_player_name("D.J. Moore"), "dj moore");
+        assert_eq!(normalize_player_name("  DJ   Moore "), "dj moore");
+        assert_eq!(normalize_player_name("Ja'Marr Chase"), "jamarr chase");
+    }
+
+    #[test]
+    fn test_disambiguate_names_only_suffixes_collisions() {
+        let entries = [
+            entry(1, "Josh Allen", "QB", Some("BUF")),
+            entry(2, "Josh Allen", "LB", Some("JAX")),
+            entry(3, "Patrick Mahomes", "QB", Some("KC")),
+            // Same player listed twice is not a collision
+            entry(3, "Patrick Mahomes", "QB", Some("KC")),
+        ];
+        let names = disambiguate_names(&entries);
+
+        assert_eq!(names[&1], "Josh Allen (QB, BUF)");
+        assert_eq!(names[&2], "Josh Allen (LB, JAX)");
+        assert_eq!(names[&3], "Patrick Mahomes");
+    }
+
+    #[test]
+    fn test_disambiguate_names_normalizes_and_falls_back_to_id() {
+        let entries = [
+            entry(10, "D.J. Moore", "WR", Some("CHI")),
+            entry(11, "DJ Moore", "WR", None),
+            entry(20, "Mike Williams", "WR", None),
+            entry(21, "Mike Williams", "WR", None),
+        ];
+        let names = disambiguate_names(&entries);
+
+        assert_eq!(names[&10], "D.J. Moore (WR, CHI)");
+        assert_eq!(names[&11], "DJ Moore (WR)");
+        assert_eq!(names[&20], "Mike Williams (WR, #20)");
+        assert_eq!(names[&21], "Mike Williams (WR, #21)");
+    }
+
+    fn sample_table() -> Table {
+        let mut table = Table::new(vec![
+            Column::fixed("ID", Align::Right),
+            Column::flex("Name", 14, 28),
+            Column::fixed("Pos", Align::Left),
+            Column::fixed("Status", Align::Left),
+            Column::fixed("Roster", Align::Left),
+            Column::fixed("Proj", Align::Right),
+            Column::fixed("Conf%", Align::Right),
+            Column::rest("Reasoning"),
+        ]);
+        let rows = [
+            [
+                "3117251",
+                "Christian McCaffrey (RB, SF)",
+                "RB",
+                "Questionable",
+                "Team Alpha",
+                "24.3",
+                "85%",
+                "Outscored ESPN by 2.1 pts/week over 5 weeks",
+            ],
+            [
+                "4362628",
+                "Ja'Marr Chase",
+                "WR",
+                "Active",
+                "FA",
+                "18.7",
+                "72%",
+                "In line with ESPN",
+            ],
+            [
+                "15847",
+                "Travis Kelce",
+                "TE",
+                "Active",
+                "Team Bravo",
+                "11.2",
+                "64%",
+                "Underperformed ESPN",
+            ],
+        ];
+        for row in rows {
+            table.add_row(row.map(String::from).to_vec());
+        }
+        table.add_detail("      Week    Proj  Actual   Error".to_string());
+        table
+    }
+
+    fn assert_fits(lines: &[String], width: usize) {
+        for line in lines {
+            assert!(line.width() <= width, "{:?} is wider than {}", line, width);
+        }
+    }
+
+    #[test]
+    fn test_truncate_adds_ellipsis_by_display_width() {
+        assert_eq!(truncate("Ja'Marr Chase", 20), "Ja'Marr Chase");
+        assert_eq!(truncate("Ja'Marr Chase", 8), "Ja'Marr…");
+        assert_eq!(truncate("📝 note", 3), "📝…");
+        assert_eq!(truncate("anything", 0), "");
+    }
+
+    #[test]
+    fn test_table_width_120_renders_in_full() {
+        let lines = sample_table().render(Some(120));
+        assert_eq!(
+            lines,
+            [
+                "     ID Name                         Pos Status       Roster     Proj Conf% Reasoning",
+                "------- ---------------------------- --- ------------ ---------- ---- ----- ---------",
+                "3117251 Christian McCaffrey (RB, SF) RB  Questionable Team Alpha 24.3   85% Outscored ESPN by 2.1 pts/week over 5 weeks",
+                "4362628 Ja'Marr Chase                WR  Active       FA         18.7   72% In line with ESPN",
+                "  15847 Travis Kelce                 TE  Active       Team Bravo 11.2   64% Underperformed ESPN",
+                "      Week    Proj  Actual   Error",
+            ]
+        );
+        assert_eq!(lines, sample_table().render(None));
+    }
+
+    #[test]
+    fn test_table_width_80_flexes_name_and_keeps_numbers_aligned() {
+        let lines = sample_table().render(Some(80));
+        assert_eq!(
+            lines,
+            [
+                "     ID Name                   Pos Status       Roster     Proj Conf% Reasoning",
+                "------- ---------------------- --- ------------ ---------- ---- ----- ---------",
+                "3117251 Christian McCaffrey (… RB  Questionable Team Alpha 24.3   85% Outscored…",
+                "4362628 Ja'Marr Chase          WR  Active       FA         18.7   72% In line w…",
+                "  15847 Travis Kelce           TE  Active       Team Bravo 11.2   64% Underperf…",
+                "      Week    Proj  Actual   Error",
+            ]
+        );
+        assert_fits(&lines, 80);
+        // Projections sit under their header even on rows with a truncated name
+        let proj = lines[0].find("Proj").unwrap();
+        for line in &lines[2..5] {
+            let cell: String = line.chars().skip(proj).take(4).collect();
+            assert!(cell.parse::<f64>().is_ok(), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn test_table_width_60_uses_two_line_layout() {
+        let lines = sample_table().render(Some(60));
+        assert_eq!(
+            lines,
+            [
+                "Name",
+                "         ID Pos Status       Roster     Proj Conf% Reasoning",
+                "------------------------------------------------------------",
+                "Christian McCaffrey (RB, SF)",
+                "    3117251 RB  Questionable Team Alpha 24.3   85% Outscore…",
+                "Ja'Marr Chase",
+                "    4362628 WR  Active       FA         18.7   72% In line …",
+                "Travis Kelce",
+                "      15847 TE  Active       Team Bravo 11.2   64% Underper…",
+                "      Week    Proj  Actual   Error",
+            ]
+        );
+        assert_fits(&lines, 60);
+    }
+
+    #[test]
+    fn test_table_without_flex_column_truncates_lines() {
+        let mut table = Table::new(vec![
+            Column::fixed("Week", Align::Right),
+            Column::fixed("Points", Align::Right),
+        ]);
+        table.add_row(vec!["1".to_string(), "123.45".to_string()]);
+        assert_eq!(table.render(Some(8)), ["Week Po…", "---- --…", "   1 12…"]);
+    }
+}
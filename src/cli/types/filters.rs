@@ -1,6 +1,11 @@
 //! Filter types for ESPN Fantasy Football CLI commands.
 
-use std::fmt;
+use std::{fmt, str::FromStr};
+
+use crate::{
+    error::EspnError,
+    espn::teams::{pro_team_abbrev, pro_team_id_for_abbrev},
+};
 
 /// Filter for player injury status in CLI commands.
 ///
@@ -10,13 +15,14 @@ use std::fmt;
 ///
 /// # Server-side vs Client-side Filtering
 ///
-/// - **Server-side** (efficient): `Active`, `Injured`
-/// - **Client-side** (less efficient): Specific statuses like `Out`, `Doubtful`, etc.
+/// - **Server-side** (efficient): `Active`
+/// - **Client-side** (less efficient): `Injured` and specific statuses like `Out`,
+///   `Doubtful`, etc.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, clap::ValueEnum)]
 pub enum InjuryStatusFilter {
     /// Players who are active/healthy
     Active,
-    /// Players with any injury status (questionable, doubtful, out, etc.)
+    /// Players with any status other than active (questionable, out, suspension, PUP, etc.)
     Injured,
     /// Players listed as "Out"
     Out,
@@ -80,6 +86,22 @@ pub enum FantasyTeamFilter {
     Name(String),
     /// Filter by exact team ID
     Id(u32),
+    /// Any of these team IDs; a name filter resolved against the team name history
+    Ids(Vec<u32>),
+}
+
+impl FantasyTeamFilter {
+    /// Whether a team with this ID and name passes the filter
+    ///
+    /// Names match case-insensitively on any part of the team's name.
+    pub fn matches(&self, team_id: u32, team_name: Option<&str>) -> bool {
+        match self {
+            FantasyTeamFilter::Id(id) => team_id == *id,
+            FantasyTeamFilter::Ids(ids) => ids.contains(&team_id),
+            FantasyTeamFilter::Name(name) => team_name
+                .is_some_and(|team_name| team_name.to_lowercase().contains(&name.to_lowercase())),
+        }
+    }
 }
 
 impl fmt::Display for FantasyTeamFilter {
@@ -87,6 +109,193 @@ impl fmt::Display for FantasyTeamFilter {
         match self {
             FantasyTeamFilter::Name(name) => write!(f, "Team: {}", name),
             FantasyTeamFilter::Id(id) => write!(f, "Team ID: {}", id),
+            FantasyTeamFilter::Ids(ids) => {
+                let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                write!(f, "Team IDs: {}", ids.join(", "))
+            }
         }
     }
 }
+
+/// How to section list output in CLI commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum GroupBy {
+    /// One section per player position, with positional ranks
+    Position,
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            GroupBy::Position => "position",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Ordering for projection analysis output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum EstimateSort {
+    /// Estimated points, highest first
+    Points,
+    /// Value over the position's replacement level, highest first
+    Vorp,
+}
+
+impl fmt::Display for EstimateSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            EstimateSort::Points => "points",
+            EstimateSort::Vorp => "vorp",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// How to section `player-data` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum PlayerDataGroupBy {
+    /// One section per fantasy team, free agents last, with point subtotals
+    Team,
+}
+
+impl fmt::Display for PlayerDataGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PlayerDataGroupBy::Team => "team",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Which change ranks the `week-over-week` risers and fallers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum WeekOverWeekMetric {
+    /// Actual points scored
+    Actual,
+    /// ESPN's weekly projection
+    Projected,
+    /// ESPN ownership percentage
+    Owned,
+}
+
+impl fmt::Display for WeekOverWeekMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            WeekOverWeekMetric::Actual => "actual points",
+            WeekOverWeekMetric::Projected => "projected points",
+            WeekOverWeekMetric::Owned => "% owned",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// File format for data exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// Pretty-printed JSON array
+    Json,
+    /// Newline-delimited JSON, one player-week per line
+    JsonLines,
+    /// Columnar Parquet file (requires the `parquet` feature)
+    Parquet,
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ExportFormat::Json => "json",
+            ExportFormat::JsonLines => "json-lines",
+            ExportFormat::Parquet => "parquet",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Site whose naming conventions a projections CSV follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum ExportSite {
+    /// ESPN names, positions and team abbreviations unchanged
+    Espn,
+    /// FanDuel: no name suffixes, "D" for defenses, JAC/WAS abbreviations
+    Fanduel,
+    /// DraftKings: no periods in names, "DST" for defenses, WAS abbreviation
+    Draftkings,
+}
+
+impl fmt::Display for ExportSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ExportSite::Espn => "espn",
+            ExportSite::Fanduel => "fanduel",
+            ExportSite::Draftkings => "draftkings",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// An NFL team picked on the command line by abbreviation (`--nfl-team BUF`)
+///
+/// Holds ESPN's pro team ID and displays as ESPN's abbreviation, so `WAS`
+/// and `WSH` select the same team.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NflTeam(u32);
+
+impl NflTeam {
+    /// ESPN's pro team ID
+    pub fn id(self) -> u32 {
+        self.0
+    }
+
+    /// ESPN's abbreviation, e.g. "WSH"
+    pub fn abbrev(self) -> &'static str {
+        pro_team_abbrev(self.0).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for NflTeam {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.abbrev())
+    }
+}
+
+impl FromStr for NflTeam {
+    type Err = EspnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        pro_team_id_for_abbrev(s)
+            .map(Self)
+            .ok_or_else(|| EspnError::InvalidNflTeam {
+                team: s.to_string(),
+            })
+    }
+}
+
+/// Ordering for player-data output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, clap::ValueEnum)]
+pub enum ComparisonSort {
+    /// Points (actual, or projected when there is no actual), highest first
+    Points,
+    /// Player name, A to Z
+    Name,
+    /// Position: QB, RB, WR, TE, K, D/ST
+    Position,
+    /// Fantasy team name, A to Z, free agents last
+    Team,
+    /// Actual minus projected, biggest outperformance first (needs `--both`)
+    #[value(alias = "projection-diff")]
+    Delta,
+}
+
+impl fmt::Display for ComparisonSort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            ComparisonSort::Points => "points",
+            ComparisonSort::Name => "name",
+            ComparisonSort::Position => "position",
+            ComparisonSort::Team => "team",
+            ComparisonSort::Delta => "delta",
+        };
+        write!(f, "{}", s)
+    }
+}
@@ -68,6 +68,25 @@ impl Position {
         }
     }
 
+    /// ESPN position IDs this position covers in a league.
+    ///
+    /// Same as [`get_all_position_ids`](Self::get_all_position_ids), except that FLEX
+    /// covers the positions the league's flex-style lineup slots take.
+    pub fn league_position_ids(&self, flex: &FlexEligibility) -> Vec<u8> {
+        match self {
+            Position::FLEX => flex.position_ids(),
+            _ => self.get_all_position_ids(),
+        }
+    }
+
+    /// Whether a player at `position` passes this position as a filter
+    pub fn accepts(&self, position: Position, flex: &FlexEligibility) -> bool {
+        match self {
+            Position::FLEX => flex.contains(position),
+            _ => *self == position,
+        }
+    }
+
     /// Convert a single ESPN position ID to a Position enum.
     ///
     /// Returns the most specific position type for the given ID.
@@ -169,10 +188,172 @@ impl FromStr for Position {
     }
 }
 
+/// Positions a FLEX filter covers in a league
+///
+/// Built from the league's flex-style lineup slots (see
+/// [`LeagueSettings::flex_eligibility`](crate::LeagueSettings::flex_eligibility)), so
+/// superflex leagues include QBs and WR/TE-only flex leagues leave out RBs. The
+/// default is ESPN's standard RB/WR/TE FLEX.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlexEligibility {
+    positions: Vec<Position>,
+}
+
+impl FlexEligibility {
+    /// FLEX covering `positions`, or the standard RB/WR/TE when there are none
+    pub fn new(positions: impl IntoIterator<Item = Position>) -> Self {
+        let mut positions: Vec<Position> = positions.into_iter().collect();
+        if positions.is_empty() {
+            return Self::default();
+        }
+        positions.sort_by_key(Position::to_u8);
+        positions.dedup();
+        Self { positions }
+    }
+
+    /// Positions covered, in ESPN position ID order
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+
+    /// Whether a player at `position` counts as FLEX
+    pub fn contains(&self, position: Position) -> bool {
+        self.positions.contains(&position)
+    }
+
+    /// Every ESPN position ID covered, aliases included
+    pub fn position_ids(&self) -> Vec<u8> {
+        self.positions
+            .iter()
+            .flat_map(Position::get_all_position_ids)
+            .collect()
+    }
+}
+
+impl Default for FlexEligibility {
+    fn default() -> Self {
+        Self {
+            positions: vec![Position::RB, Position::WR, Position::TE],
+        }
+    }
+}
+
+/// Named position groups accepted wherever `--position` is
+pub const POSITION_GROUPS: &[(&str, &[Position])] = &[
+    ("SKILL", &[Position::RB, Position::WR, Position::TE]),
+    (
+        "OFFENSE",
+        &[Position::QB, Position::RB, Position::WR, Position::TE],
+    ),
+    ("KICKERS-AND-DEFENSE", &[Position::K, Position::DEF]),
+];
+
+/// A `--position` value: a single position or a named group of them.
+///
+/// Groups expand when parsed, so the ESPN slot filter and the client-side position
+/// filter both see the same plain list of positions.
+///
+/// ```rust
+/// use espn_ffl::cli::types::position::{Position, PositionSelector};
+///
+/// let skill: PositionSelector = "skill".parse().unwrap();
+/// assert_eq!(skill.positions(), [Position::RB, Position::WR, Position::TE]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionSelector(Vec<Position>);
+
+impl PositionSelector {
+    pub fn positions(&self) -> &[Position] {
+        &self.0
+    }
+
+    /// Union of several selectors in the order given, each position once
+    pub fn expand(selectors: &[PositionSelector]) -> Vec<Position> {
+        let mut positions = Vec::new();
+        for position in selectors.iter().flat_map(|s| s.positions()) {
+            if !positions.contains(position) {
+                positions.push(*position);
+            }
+        }
+        positions
+    }
+}
+
+impl FromStr for PositionSelector {
+    type Err = EspnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_uppercase().replace('_', "-");
+        // "ALL-OFFENSE" reads naturally next to the others
+        let name = name.strip_prefix("ALL-").unwrap_or(&name);
+        match POSITION_GROUPS.iter().find(|(group, _)| *group == name) {
+            Some((_, positions)) => Ok(Self(positions.to_vec())),
+            None => s.parse::<Position>().map(|position| Self(vec![position])),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn ids(positions: &[Position]) -> Vec<u8> {
+        let mut ids: Vec<u8> = positions
+            .iter()
+            .flat_map(|p| p.get_all_position_ids())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    }
+
+    #[test]
+    fn test_position_groups_expand() {
+        let expand = |s: &str| s.parse::<PositionSelector>().unwrap().positions().to_vec();
+
+        assert_eq!(
+            expand("SKILL"),
+            vec![Position::RB, Position::WR, Position::TE]
+        );
+        assert_eq!(
+            expand("offense"),
+            vec![Position::QB, Position::RB, Position::WR, Position::TE]
+        );
+        assert_eq!(expand("all-offense"), expand("OFFENSE"));
+        assert_eq!(
+            expand("kickers_and_defense"),
+            vec![Position::K, Position::DEF]
+        );
+        assert_eq!(expand("d/st"), vec![Position::DEF]);
+        assert!("SPECIAL".parse::<PositionSelector>().is_err());
+    }
+
+    #[test]
+    fn test_position_selectors_union() {
+        let selectors: Vec<PositionSelector> = ["WR", "SKILL", "QB", "TE"]
+            .iter()
+            .map(|s| s.parse().unwrap())
+            .collect();
+        assert_eq!(
+            PositionSelector::expand(&selectors),
+            vec![Position::WR, Position::RB, Position::TE, Position::QB]
+        );
+    }
+
+    #[test]
+    fn test_position_groups_match_individual_slot_ids() {
+        // A group asks ESPN for exactly the slots its positions would one by one
+        let skill: PositionSelector = "SKILL".parse().unwrap();
+        assert_eq!(
+            ids(skill.positions()),
+            ids(&[Position::RB, Position::WR, Position::TE])
+        );
+        assert_eq!(ids(skill.positions()), ids(&[Position::FLEX]));
+
+        let special: PositionSelector = "KICKERS-AND-DEFENSE".parse().unwrap();
+        assert_eq!(ids(special.positions()), vec![5, 16, 17]);
+    }
+
     #[test]
     fn test_all_position_id_mappings() {
         // Test that all ESPN position IDs map correctly to Position enums
@@ -210,6 +391,32 @@ mod tests {
         assert!(!flex_ids.contains(&5)); // Not K
     }
 
+    #[test]
+    fn test_flex_covers_the_leagues_flex_positions() {
+        let standard = FlexEligibility::default();
+        let superflex =
+            FlexEligibility::new([Position::TE, Position::QB, Position::RB, Position::WR]);
+        let wr_te = FlexEligibility::new([Position::WR, Position::TE, Position::WR]);
+        assert_eq!(
+            Position::FLEX.league_position_ids(&standard),
+            vec![2, 3, 4, 6]
+        );
+        assert_eq!(
+            Position::FLEX.league_position_ids(&superflex),
+            vec![0, 1, 2, 3, 4, 6]
+        );
+        assert_eq!(Position::FLEX.league_position_ids(&wr_te), vec![3, 4, 6]);
+        assert_eq!(Position::QB.league_position_ids(&superflex), vec![0, 1]);
+        assert_eq!(FlexEligibility::new([]), standard);
+
+        assert!(!Position::FLEX.accepts(Position::QB, &standard));
+        assert!(Position::FLEX.accepts(Position::QB, &superflex));
+        assert!(Position::FLEX.accepts(Position::TE, &standard));
+        assert!(!Position::FLEX.accepts(Position::RB, &wr_te));
+        assert!(!Position::FLEX.accepts(Position::K, &superflex));
+        assert!(!Position::RB.accepts(Position::QB, &superflex));
+    }
+
     #[test]
     fn test_position_string_conversion() {
         // Test that position enums convert to correct strings
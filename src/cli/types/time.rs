@@ -4,6 +4,155 @@ use crate::error::{EspnError, Result};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// First month (1-based) that belongs to the new NFL season.
+///
+/// The regular season ends in early January and the Super Bowl is in February, so
+/// January and February still belong to the previous year's season. March starts
+/// the new league year (free agency), after which the upcoming season is the one
+/// worth querying by default.
+pub const SEASON_START_MONTH: u32 = 3;
+
+/// Build-time override for the default season, e.g. for reproducible test builds:
+/// `ESPN_FFL_DEFAULT_SEASON=2024 cargo build`
+const DEFAULT_SEASON_OVERRIDE: Option<&str> = option_env!("ESPN_FFL_DEFAULT_SEASON");
+
+/// UTC calendar date `(year, month, day)` of a Unix timestamp in seconds
+pub fn utc_date(secs: u64) -> (i64, u32, u32) {
+    // Civil-from-days (proleptic Gregorian), counting eras of 400 years from 0000-03-01
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since the Unix epoch of a UTC calendar date (proleptic Gregorian)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = i64::from((month + 9) % 12);
+    let day_of_year = (153 * month_index + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// A point in time in whole seconds since the Unix epoch (UTC)
+///
+/// Parses `2025-09-14T17:00:00Z`, `2025-09-14T17:00Z`, `2025-09-14 17:00` (always
+/// UTC) or a plain Unix timestamp in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct UtcTimestamp(pub u64);
+
+impl UtcTimestamp {
+    pub fn new(secs: u64) -> Self {
+        Self(secs)
+    }
+
+    pub fn as_secs(&self) -> u64 {
+        self.0
+    }
+
+    /// The current time
+    pub fn now() -> Self {
+        Self(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        )
+    }
+
+    /// Parse a point in time given as hours ago (`6h`) or in any [`FromStr`] form
+    pub fn parse_since(text: &str) -> Result<Self> {
+        if let Some(hours) = text.trim().strip_suffix(['h', 'H']) {
+            if let Ok(hours) = hours.trim().parse::<u64>() {
+                return Ok(Self(Self::now().0.saturating_sub(hours * 3600)));
+            }
+        }
+        text.parse()
+    }
+}
+
+impl fmt::Display for UtcTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = utc_date(self.0);
+        let time_of_day = self.0 % 86_400;
+        write!(
+            f,
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+            year,
+            month,
+            day,
+            time_of_day / 3600,
+            time_of_day % 3600 / 60,
+            time_of_day % 60
+        )
+    }
+}
+
+impl FromStr for UtcTimestamp {
+    type Err = EspnError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || EspnError::InvalidTimestamp {
+            value: s.to_string(),
+        };
+        let text = s.trim();
+        if let Ok(secs) = text.parse::<u64>() {
+            return Ok(Self(secs));
+        }
+
+        let text = text.strip_suffix(['Z', 'z']).unwrap_or(text);
+        let (date, time) = text.split_once(['T', 't', ' ']).ok_or_else(invalid)?;
+        let date: Vec<&str> = date.split('-').collect();
+        let time: Vec<&str> = time.split(':').collect();
+        let [year, month, day] = date.as_slice() else {
+            return Err(invalid());
+        };
+        let (hour, minute, second) = match time.as_slice() {
+            [hour, minute] => (hour, minute, &"0"),
+            [hour, minute, second] => (hour, minute, second),
+            _ => return Err(invalid()),
+        };
+
+        let year: i64 = year.parse().map_err(|_| invalid())?;
+        let month: u32 = month.parse().map_err(|_| invalid())?;
+        let day: u32 = day.parse().map_err(|_| invalid())?;
+        let hour: u64 = hour.parse().map_err(|_| invalid())?;
+        let minute: u64 = minute.parse().map_err(|_| invalid())?;
+        let second: u64 = second.parse().map_err(|_| invalid())?;
+        if !(1..=12).contains(&month)
+            || !(1..=31).contains(&day)
+            || hour > 23
+            || minute > 59
+            || second > 59
+        {
+            return Err(invalid());
+        }
+
+        let days = days_from_civil(year, month, day);
+        // Reject dates like Feb 30 that roll over into the next month
+        if days < 0 || utc_date(days as u64 * 86_400) != (year, month, day) {
+            return Err(invalid());
+        }
+        Ok(Self(
+            days as u64 * 86_400 + hour * 3600 + minute * 60 + second,
+        ))
+    }
+}
 
 /// Type-safe wrapper for Season years
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -17,11 +166,51 @@ impl Season {
     pub fn as_u16(&self) -> u16 {
         self.0
     }
+
+    /// The season before this one, if any
+    pub fn prev(&self) -> Option<Season> {
+        self.0.checked_sub(1).map(Self)
+    }
+
+    /// The season after this one, if representable
+    pub fn next(&self) -> Option<Season> {
+        self.0.checked_add(1).map(Self)
+    }
+
+    /// Season in progress (or coming up) on a calendar date; see [`SEASON_START_MONTH`]
+    pub fn for_date(year: i64, month: u32) -> Self {
+        let season = if month >= SEASON_START_MONTH {
+            year
+        } else {
+            year - 1
+        };
+        Self(season.clamp(0, u16::MAX as i64) as u16)
+    }
+
+    /// Season for a Unix timestamp in seconds (UTC)
+    pub fn at_unix_time(secs: u64) -> Self {
+        let (year, month, _) = utc_date(secs);
+        Self::for_date(year, month)
+    }
+
+    /// Season for today's date, ignoring the build-time override
+    pub fn current() -> Self {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self::at_unix_time(now)
+    }
 }
 
+/// The current season from today's date, unless `ESPN_FFL_DEFAULT_SEASON` was set
+/// when the binary was built
 impl Default for Season {
     fn default() -> Self {
-        Self(2025)
+        DEFAULT_SEASON_OVERRIDE
+            .and_then(|year| year.trim().parse().ok())
+            .map(Self)
+            .unwrap_or_else(Self::current)
     }
 }
 
@@ -44,6 +233,9 @@ impl FromStr for Season {
 pub struct Week(pub u16);
 
 impl Week {
+    /// Last scoring period of an ESPN fantasy season
+    pub const MAX: u16 = 18;
+
     pub fn new(week: u16) -> Self {
         Self(week)
     }
@@ -51,6 +243,21 @@ impl Week {
     pub fn as_u16(&self) -> u16 {
         self.0
     }
+
+    /// The previous week, or `None` for week 1 (and week 0)
+    pub fn prev(&self) -> Option<Week> {
+        self.0.checked_sub(1).filter(|&w| w >= 1).map(Self)
+    }
+
+    /// The following week, or `None` past the last week of the season
+    pub fn next(&self) -> Option<Week> {
+        self.0.checked_add(1).filter(|&w| w <= Self::MAX).map(Self)
+    }
+
+    /// Iterate from this week through `end` (inclusive); empty if `end` is earlier
+    pub fn range_to(&self, end: Week) -> impl Iterator<Item = Week> {
+        (self.0..=end.0).map(Self)
+    }
 }
 
 impl Default for Week {
@@ -72,3 +279,372 @@ impl FromStr for Week {
         Ok(Self(s.parse()?))
     }
 }
+
+/// A set of weeks given on the command line: `3`, `1-8`, `1,3,5`, `1-4,9` or `all`
+///
+/// Weeks are kept sorted with duplicates removed, so iterating visits each week
+/// once in order. Serializes as its canonical spec string (e.g. `"1-4,9"`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WeekRange(Vec<Week>);
+
+impl WeekRange {
+    /// Every week of the season
+    pub fn all() -> Self {
+        Self::through(Week::new(Week::MAX))
+    }
+
+    /// Weeks 1 through `end` (inclusive)
+    pub fn through(end: Week) -> Self {
+        Self(Week::new(1).range_to(end).collect())
+    }
+
+    /// Parse a spec, rejecting weeks past `max_week` (e.g. a 17-week season)
+    pub fn parse_with_max(spec: &str, max_week: u16) -> Result<Self> {
+        let invalid = |reason: String| EspnError::InvalidWeeks {
+            spec: spec.to_string(),
+            reason,
+        };
+        let trimmed = spec.trim();
+        if trimmed.eq_ignore_ascii_case("all") {
+            return Ok(Self::through(Week::new(max_week)));
+        }
+
+        let parse_week = |part: &str| -> Result<u16> {
+            let week: u16 = part
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("'{}' is not a week number", part.trim())))?;
+            if week == 0 || week > max_week {
+                return Err(invalid(format!("week {} is outside 1-{}", week, max_week)));
+            }
+            Ok(week)
+        };
+
+        let mut weeks = Vec::new();
+        for part in trimmed.split(',') {
+            match part.split_once('-') {
+                Some((from, to)) => {
+                    let (from, to) = (parse_week(from)?, parse_week(to)?);
+                    if from > to {
+                        return Err(EspnError::InvalidWeekRange { from, to });
+                    }
+                    weeks.extend((from..=to).map(Week));
+                }
+                None => weeks.push(Week(parse_week(part)?)),
+            }
+        }
+        weeks.sort_unstable_by_key(Week::as_u16);
+        weeks.dedup();
+        Ok(Self(weeks))
+    }
+
+    /// The weeks in order
+    pub fn weeks(&self) -> &[Week] {
+        &self.0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Week> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, week: Week) -> bool {
+        self.0.binary_search_by_key(&week.0, Week::as_u16).is_ok()
+    }
+
+    pub fn first(&self) -> Option<Week> {
+        self.0.first().copied()
+    }
+
+    pub fn last(&self) -> Option<Week> {
+        self.0.last().copied()
+    }
+}
+
+/// Collects weeks into a range, sorting them and dropping duplicates
+impl FromIterator<Week> for WeekRange {
+    fn from_iter<I: IntoIterator<Item = Week>>(iter: I) -> Self {
+        let mut weeks: Vec<Week> = iter.into_iter().collect();
+        weeks.sort_unstable_by_key(Week::as_u16);
+        weeks.dedup();
+        Self(weeks)
+    }
+}
+
+impl Default for WeekRange {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl IntoIterator for WeekRange {
+    type Item = Week;
+    type IntoIter = std::vec::IntoIter<Week>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a WeekRange {
+    type Item = Week;
+    type IntoIter = std::iter::Copied<std::slice::Iter<'a, Week>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter().copied()
+    }
+}
+
+/// Canonical spec with consecutive weeks collapsed, e.g. `1-4,9`
+impl fmt::Display for WeekRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut weeks = self.0.iter().map(Week::as_u16).peekable();
+        let mut first = true;
+        while let Some(start) = weeks.next() {
+            let mut end = start;
+            while weeks.peek() == Some(&(end + 1)) {
+                end = weeks.next().unwrap_or(end);
+            }
+            if !first {
+                write!(f, ",")?;
+            }
+            first = false;
+            if start == end {
+                write!(f, "{}", start)?;
+            } else {
+                write!(f, "{}-{}", start, end)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for WeekRange {
+    type Err = EspnError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse_with_max(s, Week::MAX)
+    }
+}
+
+impl TryFrom<String> for WeekRange {
+    type Error = EspnError;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<WeekRange> for String {
+    fn from(range: WeekRange) -> Self {
+        range.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_prev_boundaries() {
+        assert_eq!(Week::new(1).prev(), None);
+        assert_eq!(Week::new(0).prev(), None);
+        assert_eq!(Week::new(2).prev(), Some(Week::new(1)));
+        assert_eq!(Week::new(Week::MAX).prev(), Some(Week::new(Week::MAX - 1)));
+    }
+
+    #[test]
+    fn test_week_next_boundaries() {
+        assert_eq!(Week::new(1).next(), Some(Week::new(2)));
+        assert_eq!(Week::new(Week::MAX - 1).next(), Some(Week::new(Week::MAX)));
+        assert_eq!(Week::new(Week::MAX).next(), None);
+        assert_eq!(Week::new(u16::MAX).next(), None);
+    }
+
+    #[test]
+    fn test_week_range_to() {
+        let weeks: Vec<u16> = Week::new(1)
+            .range_to(Week::new(4))
+            .map(|w| w.as_u16())
+            .collect();
+        assert_eq!(weeks, vec![1, 2, 3, 4]);
+
+        assert_eq!(Week::new(3).range_to(Week::new(3)).count(), 1);
+        assert_eq!(Week::new(5).range_to(Week::new(4)).count(), 0);
+        assert_eq!(
+            Week::new(1).range_to(Week::new(Week::MAX)).last(),
+            Some(Week::new(Week::MAX))
+        );
+    }
+
+    #[test]
+    fn test_utc_date() {
+        assert_eq!(utc_date(0), (1970, 1, 1));
+        assert_eq!(utc_date(1_709_208_000), (2024, 2, 29));
+        assert_eq!(utc_date(1_767_225_599), (2025, 12, 31));
+    }
+
+    #[test]
+    fn test_utc_timestamp_parse_and_display() {
+        // 2025-09-14 17:00 UTC, the Sunday 1 PM ET kickoff
+        let kickoff = UtcTimestamp::new(1_757_869_200);
+        for text in [
+            "2025-09-14T17:00:00Z",
+            "2025-09-14T17:00Z",
+            "2025-09-14 17:00",
+            " 1757869200 ",
+        ] {
+            assert_eq!(text.parse::<UtcTimestamp>().unwrap(), kickoff, "{}", text);
+        }
+        assert_eq!(kickoff.to_string(), "2025-09-14T17:00:00Z");
+        assert_eq!(
+            "2024-02-29T23:59:59Z".parse::<UtcTimestamp>().unwrap(),
+            UtcTimestamp::new(1_709_251_199)
+        );
+        assert_eq!(
+            "1970-01-01T00:00Z".parse::<UtcTimestamp>().unwrap(),
+            UtcTimestamp::new(0)
+        );
+
+        for text in [
+            "2025-09-14",
+            "2025-02-30T12:00Z",
+            "2025-09-14T24:00Z",
+            "2025-13-01T00:00Z",
+            "Sunday",
+        ] {
+            assert!(text.parse::<UtcTimestamp>().is_err(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_utc_timestamp_parse_since() {
+        let now = UtcTimestamp::now().as_secs();
+        let six_hours_ago = UtcTimestamp::parse_since("6h").unwrap().as_secs();
+        assert!((now - 6 * 3600..=now - 6 * 3600 + 5).contains(&six_hours_ago));
+        assert_eq!(
+            UtcTimestamp::parse_since("2025-09-14T17:00Z").unwrap(),
+            UtcTimestamp::new(1_757_869_200)
+        );
+        assert!(UtcTimestamp::parse_since("h").is_err());
+        assert!(UtcTimestamp::parse_since("-3h").is_err());
+    }
+
+    #[test]
+    fn test_season_for_date_cutoff() {
+        assert_eq!(Season::for_date(2026, 1), Season::new(2025));
+        assert_eq!(Season::for_date(2026, 2), Season::new(2025));
+        assert_eq!(Season::for_date(2026, 3), Season::new(2026));
+        assert_eq!(Season::for_date(2026, 12), Season::new(2026));
+    }
+
+    #[test]
+    fn test_season_at_unix_time() {
+        // 2026-01-15: playoffs of the 2025 season
+        assert_eq!(Season::at_unix_time(1_768_435_200), Season::new(2025));
+        // 2026-07-01: offseason ahead of the 2026 season
+        assert_eq!(Season::at_unix_time(1_782_864_000), Season::new(2026));
+        // 2026-10-11: mid-season
+        assert_eq!(Season::at_unix_time(1_791_676_800), Season::new(2026));
+        // 2026-02-28 23:59:59 vs 2026-03-01 00:00:00 UTC
+        assert_eq!(Season::at_unix_time(1_772_323_199), Season::new(2025));
+        assert_eq!(Season::at_unix_time(1_772_323_200), Season::new(2026));
+    }
+
+    #[test]
+    fn test_season_prev_next() {
+        assert_eq!(Season::new(2025).prev(), Some(Season::new(2024)));
+        assert_eq!(Season::new(2025).next(), Some(Season::new(2026)));
+        assert_eq!(Season::new(0).prev(), None);
+        assert_eq!(Season::new(u16::MAX).next(), None);
+    }
+
+    fn week_list(range: &WeekRange) -> Vec<u16> {
+        range.iter().map(|w| w.as_u16()).collect()
+    }
+
+    #[test]
+    fn test_week_range_parse_forms() {
+        assert_eq!(week_list(&"3".parse().unwrap()), vec![3]);
+        assert_eq!(week_list(&"1-4".parse().unwrap()), vec![1, 2, 3, 4]);
+        assert_eq!(week_list(&"1,3,5".parse().unwrap()), vec![1, 3, 5]);
+        assert_eq!(week_list(&" 1-2, 7 ".parse().unwrap()), vec![1, 2, 7]);
+        assert_eq!(week_list(&"5-5".parse().unwrap()), vec![5]);
+
+        let all: WeekRange = "all".parse().unwrap();
+        assert_eq!(all, WeekRange::all());
+        assert_eq!(all.len(), Week::MAX as usize);
+        assert_eq!("ALL".parse::<WeekRange>().unwrap(), all);
+    }
+
+    #[test]
+    fn test_week_range_sorts_and_dedups() {
+        let range: WeekRange = "5,1-3,2,5".parse().unwrap();
+        assert_eq!(week_list(&range), vec![1, 2, 3, 5]);
+        assert_eq!(range.first(), Some(Week::new(1)));
+        assert_eq!(range.last(), Some(Week::new(5)));
+        assert!(range.contains(Week::new(2)));
+        assert!(!range.contains(Week::new(4)));
+
+        let collected: WeekRange = [5, 1, 3, 1].into_iter().map(Week::new).collect();
+        assert_eq!(collected.to_string(), "1,3,5");
+    }
+
+    #[test]
+    fn test_week_range_rejects_reversed_range() {
+        assert!(matches!(
+            "8-3".parse::<WeekRange>(),
+            Err(EspnError::InvalidWeekRange { from: 8, to: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_week_range_rejects_out_of_range_weeks() {
+        for spec in ["0", "19", "1-19", "0-3", "2,40"] {
+            assert!(
+                matches!(
+                    spec.parse::<WeekRange>(),
+                    Err(EspnError::InvalidWeeks { .. })
+                ),
+                "{spec} should be rejected"
+            );
+        }
+        // A shorter season caps the range
+        assert!(WeekRange::parse_with_max("18", 17).is_err());
+        assert_eq!(
+            WeekRange::parse_with_max("all", 17).unwrap().last(),
+            Some(Week::new(17))
+        );
+    }
+
+    #[test]
+    fn test_week_range_rejects_malformed_specs() {
+        for spec in ["", "x", "1-", "-3", "1,,2", "1-2-3", "1..3", "-1"] {
+            assert!(
+                spec.parse::<WeekRange>().is_err(),
+                "{spec} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_week_range_display_and_serde() {
+        let range: WeekRange = "9,1,2,3,4,11,12".parse().unwrap();
+        assert_eq!(range.to_string(), "1-4,9,11-12");
+        assert_eq!(range.to_string().parse::<WeekRange>().unwrap(), range);
+        assert_eq!(WeekRange::through(Week::new(3)).to_string(), "1-3");
+
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(json, "\"1-4,9,11-12\"");
+        assert_eq!(serde_json::from_str::<WeekRange>(&json).unwrap(), range);
+        assert!(serde_json::from_str::<WeekRange>("\"3-1\"").is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! Accuracy report command implementation
+//!
+//! Measures how close ESPN's stored weekly projections, and the bias-adjusted
+//! projections built from them, came to the actual points at each position.
+
+use serde::Serialize;
+
+use crate::{
+    cli::render::{Align, Column, Table},
+    storage::{analysis::BiasWeighting, PlayerDatabase, PositionAccuracy},
+    LeagueId, Result, Season, Week,
+};
+
+use super::{
+    league_data::resolve_league_id,
+    projection_analysis::{validate_bias_decay, validate_bias_strength},
+};
+
+/// Configuration for the accuracy report
+#[derive(Debug)]
+pub struct AccuracyReportParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    /// Last week to score; every stored week when `None`
+    pub through_week: Option<Week>,
+    pub bias_strength: f64,
+    pub bias_decay: f64,
+    pub as_json: bool,
+}
+
+/// JSON output for the accuracy report
+#[derive(Debug, Serialize)]
+struct AccuracyOutput<'a> {
+    season: Season,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    through_week: Option<Week>,
+    positions: &'a [PositionAccuracy],
+}
+
+/// Handle the accuracy report command
+pub fn handle_accuracy_report(params: AccuracyReportParams) -> Result<()> {
+    let bias = BiasWeighting {
+        strength: validate_bias_strength(params.bias_strength)?,
+        decay: validate_bias_decay(params.bias_decay)?,
+    };
+    let league_id = resolve_league_id(params.league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let positions = db.projection_accuracy(league_id, params.season, params.through_week, bias)?;
+
+    if params.as_json {
+        let output = AccuracyOutput {
+            season: params.season,
+            through_week: params.through_week,
+            positions: &positions,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_accuracy(params.season, params.through_week, &positions);
+    Ok(())
+}
+
+fn print_accuracy(season: Season, through_week: Option<Week>, positions: &[PositionAccuracy]) {
+    match through_week {
+        Some(week) => println!(
+            "Projection accuracy - Season {} through week {}",
+            season, week
+        ),
+        None => println!("Projection accuracy - Season {}", season),
+    }
+    if positions.is_empty() {
+        println!(
+            "No stored projected and actual points for these weeks; run update-all-data first."
+        );
+        return;
+    }
+
+    let mut table = Table::new(vec![
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Games", Align::Right),
+        Column::fixed("ESPN MAE", Align::Right),
+        Column::fixed("Adj MAE", Align::Right),
+        Column::fixed("ESPN RMSE", Align::Right),
+        Column::fixed("Adj RMSE", Align::Right),
+        Column::fixed("Improvement", Align::Right),
+    ]);
+    for row in positions {
+        table.add_row(vec![
+            row.position.clone(),
+            row.games.to_string(),
+            format!("{:.2}", row.espn_mae),
+            format!("{:.2}", row.adjusted_mae),
+            format!("{:.2}", row.espn_rmse),
+            format!("{:.2}", row.adjusted_rmse),
+            row.improvement_pct
+                .map_or_else(|| "--".to_string(), |pct| format!("{:+.1}%", pct)),
+        ]);
+    }
+    table.print();
+    println!();
+    println!("Adjusted projections use only the weeks before each game; byes and DNP weeks are left out.");
+}
@@ -0,0 +1,74 @@
+//! ESPN credentials commands
+//!
+//! `auth set` saves the SWID and espn_s2 cookies to the credentials file so they
+//! don't have to live in environment variables; `auth status` reports which
+//! credentials requests will use and checks them against a league.
+
+use dialoguer::Password;
+use reqwest::StatusCode;
+
+use crate::{
+    espn::{
+        credentials::{credentials_path, resolve_credentials, Credentials},
+        http::get_league_settings,
+    },
+    LeagueId, Result, Season,
+};
+
+use super::league_data::resolve_league_id;
+
+/// Handle `auth set`: prompt for both cookies without echoing them and save them
+pub fn handle_auth_set() -> Result<()> {
+    let path = credentials_path();
+    let prompt = |name: &str| -> Result<String> {
+        let value = Password::new()
+            .with_prompt(name)
+            .interact()
+            .map_err(|dialoguer::Error::IO(e)| e)?;
+        Ok(value.trim().to_string())
+    };
+    let credentials = Credentials {
+        swid: prompt("SWID")?,
+        espn_s2: prompt("espn_s2")?,
+    };
+    credentials.write(&path)?;
+
+    println!("✓ Saved credentials to {}", path.display());
+    if Credentials::from_env().is_some() {
+        println!("Note: ESPN_SWID and ESPN_S2 are set and take precedence over the file.");
+    }
+    Ok(())
+}
+
+/// Handle `auth status`: show the credential source and try a league request with it
+pub async fn handle_auth_status(league_id: Option<LeagueId>, season: Season) -> Result<()> {
+    match resolve_credentials()? {
+        Some((_, source)) => println!("Credentials: {}", source),
+        None => println!(
+            "Credentials: none (set ESPN_SWID and ESPN_S2 or run `espn-ffl auth set`); \
+             only public leagues can be read"
+        ),
+    }
+
+    let Ok(league_id) = resolve_league_id(league_id) else {
+        println!("No league ID given; pass --league-id to check the credentials with ESPN.");
+        return Ok(());
+    };
+    match get_league_settings(league_id, season).await {
+        Ok(_) => println!(
+            "✓ ESPN returned league {} for season {}",
+            league_id.as_u32(),
+            season
+        ),
+        Err(e) => match e.http_error().and_then(|e| e.status()) {
+            Some(status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)) => println!(
+                "✗ ESPN refused league {} (HTTP {}); the credentials are missing, expired \
+                 or don't belong to a member of the league",
+                league_id.as_u32(),
+                status.as_u16()
+            ),
+            _ => return Err(e),
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,147 @@
+//! Category leaders command implementation
+//!
+//! Ranks players by a single raw stat (passing yards, receptions, long field
+//! goals, ...) from the stats stored by `player-data`/`update-all-data`, with
+//! fantasy points alongside for context.
+
+use crate::{
+    cli::{
+        render::{
+            disambiguate_names, Align, Column, NameEntry, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH,
+        },
+        types::{filters::RosterStatusFilter, position::Position},
+    },
+    espn::stat_categories::{parse_stat_category, StatCategory},
+    storage::{CategoryLeader, CategoryLeadersQuery, PlayerDatabase},
+    LeagueId, Result, Season, Week,
+};
+
+use serde::Serialize;
+
+use super::league_data::resolve_league_id;
+
+/// Configuration for the category leaders query
+#[derive(Debug)]
+pub struct CategoryLeadersParams {
+    pub category: String,
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    /// Single week, or `None` for season totals
+    pub week: Option<Week>,
+    pub positions: Option<Vec<Position>>,
+    pub roster_status: Option<RosterStatusFilter>,
+    pub top: usize,
+    pub as_json: bool,
+}
+
+/// JSON output for the category leaders command
+#[derive(Debug, Serialize)]
+struct CategoryLeadersOutput<'a> {
+    category: &'static str,
+    stat_id: u16,
+    season: Season,
+    /// `null` for season totals
+    week: Option<Week>,
+    leaders: &'a [CategoryLeader],
+}
+
+/// Handle the category leaders command
+pub fn handle_category_leaders(params: CategoryLeadersParams) -> Result<()> {
+    let category = parse_stat_category(&params.category)?;
+    let league_id = resolve_league_id(params.league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let leaders = db.get_category_leaders(
+        league_id,
+        &CategoryLeadersQuery {
+            stat_id: category.stat_id,
+            season: params.season,
+            week: params.week,
+            positions: params.positions.as_deref(),
+            roster_status: params.roster_status.as_ref(),
+            limit: params.top,
+        },
+    )?;
+
+    if params.as_json {
+        let output = CategoryLeadersOutput {
+            category: category.name,
+            stat_id: category.stat_id,
+            season: params.season,
+            week: params.week,
+            leaders: &leaders,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_leaders(category, params.season, params.week, &leaders);
+    Ok(())
+}
+
+fn print_leaders(
+    category: &StatCategory,
+    season: Season,
+    week: Option<Week>,
+    leaders: &[CategoryLeader],
+) {
+    match week {
+        Some(week) => println!(
+            "{} leaders - Season {} Week {}",
+            category.label, season, week
+        ),
+        None => println!("{} leaders - Season {} totals", category.label, season),
+    }
+    if leaders.is_empty() {
+        println!("No stored stats for this category; run player-data or update-all-data first.");
+        return;
+    }
+
+    let entries: Vec<NameEntry<'_, crate::PlayerId>> = leaders
+        .iter()
+        .map(|l| NameEntry {
+            id: l.player_id,
+            name: &l.name,
+            position: &l.position,
+            team: l.team.as_deref(),
+        })
+        .collect();
+    let names = disambiguate_names(&entries);
+
+    let mut table = Table::new(vec![
+        Column::fixed("Rank", Align::Right),
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Team", Align::Left),
+        Column::fixed(category.label, Align::Right),
+        Column::fixed("FPts", Align::Right),
+        Column::fixed("Games", Align::Right),
+    ]);
+    for (rank, leader) in leaders.iter().enumerate() {
+        table.add_row(vec![
+            (rank + 1).to_string(),
+            names
+                .get(&leader.player_id)
+                .unwrap_or(&leader.name)
+                .to_string(),
+            leader.position.clone(),
+            leader.team.as_deref().unwrap_or("--").to_string(),
+            format_stat(leader.value),
+            leader
+                .fantasy_points
+                .map_or_else(|| "--".to_string(), |p| format!("{:.1}", p)),
+            leader.games.to_string(),
+        ]);
+    }
+    println!();
+    table.print();
+}
+
+/// Counting stats print as whole numbers; anything fractional keeps one decimal
+fn format_stat(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
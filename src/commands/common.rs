@@ -3,26 +3,109 @@
 //! This module contains shared functionality that would otherwise be duplicated
 //! across different command implementations.
 
-use std::collections::BTreeMap;
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    io::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
 
 use crate::{
     cli::types::{
-        filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter},
+        filters::{FantasyTeamFilter, InjuryStatusFilter, NflTeam, RosterStatusFilter},
         position::Position,
     },
     espn::{
         cache_settings::load_or_fetch_league_settings,
-        compute::build_scoring_index,
+        compute::{build_scoring_index, strict_positions},
         http::{get_league_roster_data, PlayerDataRequest},
+        scoring_overrides::ScoringOverrides,
         types::{LeagueData, LeagueSettings},
     },
     storage::PlayerDatabase,
     LeagueId, Result, Season, Week,
 };
 
+use super::player_filters::FilteredPlayer;
+
+/// Write rows as newline-delimited JSON: one compact object per line
+///
+/// Each line is flushed as it is written so a downstream reader can start before
+/// the last row. Returns the number of rows written.
+pub fn write_json_lines<T: Serialize>(
+    mut writer: impl Write,
+    rows: impl IntoIterator<Item = T>,
+) -> Result<usize> {
+    let mut written = 0;
+    for row in rows {
+        serde_json::to_writer(&mut writer, &row)?;
+        writeln!(writer)?;
+        writer.flush()?;
+        written += 1;
+    }
+    Ok(written)
+}
+
+/// Quote a CSV field when it holds a comma, quote or line break
+pub fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
+/// Write a CSV header row and then one line per row, quoting fields as needed
+///
+/// `fields` turns a row into its cells in `header` order. Returns the number of rows written.
+pub fn write_csv_rows<T>(
+    mut writer: impl Write,
+    header: &[&str],
+    rows: impl IntoIterator<Item = T>,
+    fields: impl Fn(T) -> Vec<String>,
+) -> Result<usize> {
+    writeln!(writer, "{}", header.join(","))?;
+    let mut written = 0;
+    for row in rows {
+        let cells = fields(row);
+        let line: Vec<Cow<'_, str>> = cells.iter().map(|cell| csv_field(cell)).collect();
+        writeln!(writer, "{}", line.join(","))?;
+        written += 1;
+    }
+    writer.flush()?;
+    Ok(written)
+}
+
 /// Type alias for scoring index
 pub type ScoringIndex = BTreeMap<u16, (f64, BTreeMap<u8, f64>)>;
 
+/// Set once the overrides notice has been printed, so multi-week runs show it once
+static OVERRIDES_NOTICE_SHOWN: AtomicBool = AtomicBool::new(false);
+
+/// League scoring index with any local scoring overrides folded in
+///
+/// The overrides are returned too, so `--explain` can say which stats they changed.
+pub fn league_scoring_index(
+    settings: &LeagueSettings,
+) -> Result<(ScoringIndex, Option<ScoringOverrides>)> {
+    let mut scoring_index = build_scoring_index(&settings.scoring_settings.scoring_items);
+    let overrides = ScoringOverrides::load()?;
+    if let Some(overrides) = &overrides {
+        overrides.validate(&scoring_index)?;
+        overrides.apply(&mut scoring_index);
+        if !OVERRIDES_NOTICE_SHOWN.swap(true, Ordering::Relaxed) {
+            eprintln!(
+                "⚠ Scoring with {} local adjustment(s) from {}; pass --no-overrides to use league scoring only",
+                overrides.adjustments.len(),
+                overrides.path.display()
+            );
+        }
+    }
+    Ok((scoring_index, overrides))
+}
+
 /// Shared command parameters that are common across multiple commands
 #[derive(Debug, Clone)]
 pub struct CommandParams {
@@ -30,12 +113,18 @@ pub struct CommandParams {
     pub season: Season,
     pub week: Week,
     pub as_json: bool,
+    /// Newline-delimited JSON instead of a pretty array; set together with `as_json`
+    pub json_lines: bool,
+    /// CSV with a header row instead of JSON; set together with `as_json`
+    pub csv: bool,
     pub refresh: bool,
     pub player_names: Option<Vec<String>>,
     pub positions: Option<Vec<Position>>,
     pub injury_status: Option<InjuryStatusFilter>,
     pub roster_status: Option<RosterStatusFilter>,
     pub fantasy_team_filter: Option<FantasyTeamFilter>,
+    /// Only players on this NFL team, team defenses included
+    pub nfl_team: Option<NflTeam>,
 }
 
 impl CommandParams {
@@ -46,12 +135,15 @@ impl CommandParams {
             season,
             week,
             as_json: false,
+            json_lines: false,
+            csv: false,
             refresh: false,
             player_names: None,
             positions: None,
             injury_status: None,
             roster_status: None,
             fantasy_team_filter: None,
+            nfl_team: None,
         }
     }
 
@@ -102,6 +194,12 @@ impl CommandParams {
         self.fantasy_team_filter = Some(filter);
         self
     }
+
+    /// Filter by NFL team
+    pub fn with_nfl_team(mut self, team: NflTeam) -> Self {
+        self.nfl_team = Some(team);
+        self
+    }
 }
 
 /// Trait for common command parameter building patterns
@@ -250,6 +348,17 @@ pub trait CommandParamsBuilder {
         self
     }
 
+    /// Filter by NFL team if provided
+    fn with_optional_nfl_team(mut self, team: Option<NflTeam>) -> Self
+    where
+        Self: Sized,
+    {
+        if let Some(team) = team {
+            self.base_mut().nfl_team = Some(team);
+        }
+        self
+    }
+
     /// Set JSON output conditionally
     fn with_json_output_if(mut self, json: bool) -> Self
     where
@@ -261,6 +370,30 @@ pub trait CommandParamsBuilder {
         self
     }
 
+    /// Set newline-delimited JSON output conditionally
+    fn with_json_lines_if(mut self, json_lines: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if json_lines {
+            self.base_mut().as_json = true;
+            self.base_mut().json_lines = true;
+        }
+        self
+    }
+
+    /// Set CSV output conditionally
+    fn with_csv_if(mut self, csv: bool) -> Self
+    where
+        Self: Sized,
+    {
+        if csv {
+            self.base_mut().as_json = true;
+            self.base_mut().csv = true;
+        }
+        self
+    }
+
     /// Set refresh conditionally
     fn with_refresh_if(mut self, refresh: bool) -> Self
     where
@@ -274,34 +407,48 @@ pub trait CommandParamsBuilder {
 }
 
 /// Context containing common resources needed by most commands
+///
+/// The database is opened on first use, so commands that finish without it never
+/// touch the database file.
 pub struct CommandContext {
     pub league_id: LeagueId,
-    pub db: PlayerDatabase,
+    db: Option<PlayerDatabase>,
     pub settings: LeagueSettings,
     pub scoring_index: ScoringIndex,
+    verbose: bool,
 }
 
 impl CommandContext {
-    /// Initialize common command context with database and league settings
+    /// Initialize common command context with league settings
     pub async fn new(league_id: LeagueId, season: Season, verbose: bool) -> Result<Self> {
-        if verbose {
-            println!("Connecting to database...");
-        }
-        let db = PlayerDatabase::new()?;
-
         if verbose {
             println!("Loading league scoring settings...");
         }
         let settings = load_or_fetch_league_settings(league_id, false, season).await?;
-        let scoring_index = build_scoring_index(&settings.scoring_settings.scoring_items);
+        let (scoring_index, _) = league_scoring_index(&settings)?;
 
         Ok(Self {
             league_id,
-            db,
+            db: None,
             settings,
             scoring_index,
+            verbose,
         })
     }
+
+    /// The database, connecting on first call
+    pub fn db(&mut self) -> Result<&mut PlayerDatabase> {
+        let db = match self.db.take() {
+            Some(db) => db,
+            None => {
+                if self.verbose {
+                    println!("Connecting to database...");
+                }
+                PlayerDatabase::new()?
+            }
+        };
+        Ok(self.db.insert(db))
+    }
 }
 
 /// Fetch week-specific roster data and display appropriate message
@@ -315,29 +462,7 @@ pub async fn fetch_roster_data_with_message(
     match get_league_roster_data(false, league_id, season, week, refresh).await {
         Ok((data, cache_status)) => {
             if verbose {
-                match cache_status {
-                    crate::espn::http::CacheStatus::Hit => {
-                        if let Some(w) = week {
-                            println!("✓ Week {} roster status loaded (from cache)", w.as_u16());
-                        } else {
-                            println!("✓ Current roster status loaded (from cache)");
-                        }
-                    }
-                    crate::espn::http::CacheStatus::Miss => {
-                        if let Some(w) = week {
-                            println!("✓ Week {} roster status fetched (cache miss)", w.as_u16());
-                        } else {
-                            println!("✓ Current roster status fetched (cache miss)");
-                        }
-                    }
-                    crate::espn::http::CacheStatus::Refreshed => {
-                        if let Some(w) = week {
-                            println!("✓ Week {} roster status fetched (refreshed)", w.as_u16());
-                        } else {
-                            println!("✓ Current roster status fetched (refreshed)");
-                        }
-                    }
-                }
+                println!("✓ {}", cache_status.message(week));
             }
             Ok(Some(data))
         }
@@ -354,13 +479,47 @@ pub async fn fetch_roster_data_with_message(
     }
 }
 
-/// Convert player's default_position_id to a safe position_id for scoring calculations
-pub fn normalize_position_id(default_position_id: i32) -> u8 {
-    if default_position_id < 0 {
-        0u8 // Default to QB position for scoring purposes
-    } else {
-        default_position_id as u8
+/// Most unknown-position players named in the end-of-run warning
+const UNKNOWN_POSITION_NAMES_SHOWN: usize = 10;
+
+/// Names of the players whose position couldn't be determined, for [`report_unknown_positions`]
+pub fn unknown_position_names(players: &[FilteredPlayer]) -> Vec<String> {
+    players
+        .iter()
+        .map(|fp| &fp.original_player)
+        .filter(|player| player.position().is_none())
+        .map(|player| player.display_name())
+        .collect()
+}
+
+/// Warn about players whose position couldn't be determined and how they were scored
+pub fn report_unknown_positions(names: &[String]) {
+    if names.is_empty() {
+        return;
     }
+    let handling = if strict_positions() {
+        "left out of point totals (--strict-positions)"
+    } else {
+        "scored with base points only"
+    };
+    let mut shown = names
+        .iter()
+        .take(UNKNOWN_POSITION_NAMES_SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if names.len() > UNKNOWN_POSITION_NAMES_SHOWN {
+        shown.push_str(&format!(
+            " and {} more",
+            names.len() - UNKNOWN_POSITION_NAMES_SHOWN
+        ));
+    }
+    println!(
+        "⚠ {} player(s) with unknown position {}: {}",
+        names.len(),
+        handling,
+        shown
+    );
 }
 
 /// Convert player's default_position_id to a position string
@@ -423,11 +582,35 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_normalize_position_id() {
-        assert_eq!(normalize_position_id(-1), 0);
-        assert_eq!(normalize_position_id(0), 0);
-        assert_eq!(normalize_position_id(1), 1);
-        assert_eq!(normalize_position_id(2), 2);
+    fn test_write_json_lines() {
+        let mut out = Vec::new();
+        let rows = [
+            serde_json::json!({"id": 1, "name": "A"}),
+            serde_json::json!({"id": 2}),
+        ];
+        assert_eq!(write_json_lines(&mut out, &rows).unwrap(), 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"id\":1,\"name\":\"A\"}\n{\"id\":2}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_csv_rows_quotes_fields() {
+        let mut out = Vec::new();
+        let rows = [
+            ("Kenny Rogers' Toasters, Inc.", 1),
+            ("The \"Real\" Deal", 2),
+        ];
+        let written = write_csv_rows(&mut out, &["team", "rank"], rows, |(team, rank)| {
+            vec![team.to_string(), rank.to_string()]
+        })
+        .unwrap();
+        assert_eq!(written, 2);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "team,rank\n\"Kenny Rogers' Toasters, Inc.\",1\n\"The \"\"Real\"\" Deal\",2\n"
+        );
     }
 
     #[test]
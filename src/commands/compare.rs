@@ -0,0 +1,268 @@
+//! Compare command implementation
+//!
+//! Puts two or more players side by side for one week (ESPN's projection, the
+//! bias-adjusted estimate and its confidence, recent form and injury status) and
+//! recommends the one to start.
+
+use crate::{
+    cli::render::{Align, Column, Table},
+    error::EspnError,
+    espn::{
+        cache_settings::load_or_fetch_league_settings,
+        compute::{compute_points_for_week, scoring_slot_for_player, select_weekly_stats},
+        http::{get_player_data, PlayerDataRequest},
+        teams::pro_team_abbrev,
+        types::{InjuryStatus, Player},
+    },
+    storage::{self, analysis::BiasWeighting, PerformanceEstimate, PlayerDatabase},
+    LeagueId, PlayerId, Result, Season, Week,
+};
+
+use super::{
+    common::league_scoring_index,
+    league_data::{resolve_league_id, validate_season},
+    notes::{resolve_player_among, resolve_player_containing},
+    projection_analysis::{bias_strength_soft_cap, bias_strength_warning, validate_bias_strength},
+};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Games behind the recent actual average
+pub const RECENT_GAMES: u32 = 3;
+
+/// Configuration for the compare command.
+#[derive(Debug)]
+pub struct CompareParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub week: Week,
+    /// Player IDs or names; a partial name works when only one player matches
+    pub players: Vec<String>,
+    pub bias_strength: f64,
+    pub refresh: bool,
+    pub as_json: bool,
+}
+
+/// One player's side of a start/sit comparison
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerComparison {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    pub team: Option<String>,
+    /// ESPN's projection for the week; `None` when ESPN has none
+    pub espn_projection: Option<f64>,
+    pub estimated_points: f64,
+    pub confidence: f64,
+    /// Average actual points over the last [`RECENT_GAMES`] games played before the week
+    pub recent_average: Option<f64>,
+    pub injury_status: Option<InjuryStatus>,
+}
+
+/// Players compared for one week and the one to start
+#[derive(Debug, Clone, Serialize)]
+pub struct Comparison {
+    pub season: Season,
+    pub week: Week,
+    pub players: Vec<PlayerComparison>,
+    pub recommended_player_id: Option<PlayerId>,
+}
+
+/// Handle the compare command
+pub async fn handle_compare(params: CompareParams) -> Result<()> {
+    if params.players.len() < 2 {
+        return Err(EspnError::TooFewPlayers {
+            count: params.players.len(),
+        });
+    }
+    let league_id = resolve_league_id(params.league_id)?;
+    validate_season(league_id, params.season).await?;
+    let (season, week) = (params.season, params.week);
+    let bias_strength = validate_bias_strength(params.bias_strength)?;
+    if let Some(warning) = bias_strength_warning(bias_strength, bias_strength_soft_cap()) {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+
+    let settings = load_or_fetch_league_settings(league_id, false, season).await?;
+    let (scoring_index, _) = league_scoring_index(&settings)?;
+    let players_val = get_player_data(PlayerDataRequest {
+        refresh: params.refresh,
+        flex: settings.flex_eligibility(),
+        ..PlayerDataRequest::new(league_id, season, week)
+    })
+    .await?;
+    let players: Vec<Player> = serde_json::from_value(players_val)?;
+    let compared = resolve_compared_players(&players, &params.players)?;
+
+    // Estimates need the players stored
+    let mut db = PlayerDatabase::new()?;
+    db.update_players_from_espn(compared.iter().copied())?;
+
+    let mut projected: Vec<(PlayerId, f64)> = Vec::new();
+    let mut unprojected: Vec<PlayerId> = Vec::new();
+    let mut espn_projections: HashMap<PlayerId, f64> = HashMap::new();
+    for &player in &compared {
+        let player_id = PlayerId::new(player.id);
+        let projection = scoring_slot_for_player(player).and_then(|slot| {
+            let stats = select_weekly_stats(player, season.as_u16(), week.as_u16(), 1)?;
+            Some(compute_points_for_week(stats, slot, &scoring_index))
+        });
+        match projection {
+            Some(points) => {
+                projected.push((player_id, points));
+                espn_projections.insert(player_id, points);
+            }
+            None => unprojected.push(player_id),
+        }
+    }
+    let mut estimates = db.estimate_week_performance(
+        league_id,
+        season,
+        week,
+        &projected,
+        BiasWeighting::new(bias_strength),
+        false,
+    )?;
+    db.add_unprojected_estimates(league_id, &mut estimates, season, week, &unprojected, false)?;
+    let estimates: HashMap<PlayerId, PerformanceEstimate> =
+        estimates.into_iter().map(|e| (e.player_id, e)).collect();
+
+    let mut rows = Vec::new();
+    for player in compared {
+        let player_id = PlayerId::new(player.id);
+        let stored = storage::Player::from_espn(player);
+        let estimate = estimates.get(&player_id);
+        let recent =
+            db.get_recent_actuals(league_id, player_id, season, week, RECENT_GAMES, false)?;
+        rows.push(PlayerComparison {
+            player_id,
+            name: stored.name,
+            position: stored.position,
+            team: player
+                .nfl_team_id()
+                .and_then(pro_team_abbrev)
+                .map(str::to_string),
+            espn_projection: espn_projections.get(&player_id).copied(),
+            estimated_points: estimate.map_or(0.0, |e| e.estimated_points),
+            confidence: estimate.map_or(0.0, |e| e.confidence),
+            recent_average: (!recent.is_empty())
+                .then(|| recent.iter().sum::<f64>() / recent.len() as f64),
+            injury_status: player.injury_status.clone(),
+        });
+    }
+    let comparison = Comparison {
+        season,
+        week,
+        recommended_player_id: recommend_player(&rows),
+        players: rows,
+    };
+
+    if params.as_json {
+        println!("{}", serde_json::to_string_pretty(&comparison)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_comparison(&comparison);
+    Ok(())
+}
+
+/// Resolve each query to one of this week's ESPN players, in order
+///
+/// A query is an ID or exact name, or else part of exactly one player's name; a
+/// player named twice is compared once.
+fn resolve_compared_players<'a>(
+    players: &'a [Player],
+    queries: &[String],
+) -> Result<Vec<&'a Player>> {
+    let known: Vec<storage::Player> = players.iter().map(storage::Player::from_espn).collect();
+    let mut compared: Vec<&Player> = Vec::new();
+    for query in queries {
+        let player_id = match resolve_player_among(&known, query) {
+            Err(EspnError::PlayerNotFound { .. }) => resolve_player_containing(&known, query)?,
+            resolved => resolved?,
+        };
+        if compared.iter().any(|p| PlayerId::new(p.id) == player_id) {
+            continue;
+        }
+        if let Some(player) = players.iter().find(|p| PlayerId::new(p.id) == player_id) {
+            compared.push(player);
+        }
+    }
+    if compared.len() < 2 {
+        return Err(EspnError::TooFewPlayers {
+            count: compared.len(),
+        });
+    }
+    Ok(compared)
+}
+
+/// The player with the highest estimate, ties going to the higher confidence
+///
+/// Earlier players win exact ties; `None` only for an empty comparison.
+pub fn recommend_player(players: &[PlayerComparison]) -> Option<PlayerId> {
+    players
+        .iter()
+        .rev()
+        .max_by(|a, b| {
+            a.estimated_points
+                .total_cmp(&b.estimated_points)
+                .then(a.confidence.total_cmp(&b.confidence))
+        })
+        .map(|p| p.player_id)
+}
+
+fn print_comparison(comparison: &Comparison) {
+    println!(
+        "Start/Sit - Season {} Week {}",
+        comparison.season, comparison.week
+    );
+    println!();
+
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.1}", points),
+        None => "--".to_string(),
+    };
+    let mut columns = vec![Column::fixed("", Align::Left)];
+    columns.extend(
+        comparison
+            .players
+            .iter()
+            .map(|p| Column::fixed(p.name.clone(), Align::Right)),
+    );
+    let mut table = Table::new(columns);
+    let mut add_row = |label: &str, cell: &dyn Fn(&PlayerComparison) -> String| {
+        let mut row = vec![label.to_string()];
+        row.extend(comparison.players.iter().map(cell));
+        table.add_row(row);
+    };
+    add_row("Position", &|p| {
+        format!("{} {}", p.position, p.team.as_deref().unwrap_or("FA"))
+    });
+    add_row("ESPN proj", &|p| format_points(p.espn_projection));
+    add_row("Adjusted", &|p| format!("{:.1}", p.estimated_points));
+    add_row("Confidence", &|p| format!("{:.0}%", p.confidence * 100.0));
+    add_row(&format!("Last {} avg", RECENT_GAMES), &|p| {
+        format_points(p.recent_average)
+    });
+    add_row("Status", &|p| {
+        p.injury_status
+            .as_ref()
+            .map_or_else(|| "Active".to_string(), |s| s.to_string())
+    });
+    table.print();
+
+    println!();
+    let recommended = comparison
+        .players
+        .iter()
+        .find(|p| Some(p.player_id) == comparison.recommended_player_id);
+    if let Some(player) = recommended {
+        println!(
+            "Start {} ({:.1} pts, {:.0}% confidence)",
+            player.name,
+            player.estimated_points,
+            player.confidence * 100.0
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Local database maintenance commands
+
+use crate::{
+    cli::{
+        render::{Align, Column, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH},
+        types::filters::ExportFormat,
+    },
+    core::ProcessLock,
+    error::EspnError,
+    espn::http::{format_utc_date, get_league_status},
+    storage::{
+        dedupe::{DedupeResult, SignedIdPair},
+        integrity::IntegrityReport,
+        CoverageLevel, DataCoverage, FantasyTeamName, PlayerAlias, PlayerDatabase,
+    },
+    LeagueId, Result, Season, Week,
+};
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use super::{common::write_json_lines, league_data::resolve_league_id, notes::resolve_player};
+
+/// Handle `db check`: report anomalies in stored weekly stats and optionally repair them
+pub async fn handle_db_check(
+    league_id: Option<LeagueId>,
+    season: Season,
+    current_week: Option<Week>,
+    fix: bool,
+    as_json: bool,
+) -> Result<()> {
+    let current_week = match current_week {
+        Some(week) => Some(week),
+        None => resolve_current_week(league_id, season, as_json).await,
+    };
+
+    let mut db = PlayerDatabase::open_existing()?;
+    let report = db.check_integrity(season, current_week)?;
+
+    let fix_summary = if fix {
+        let _lock = ProcessLock::acquire_database()?;
+        Some(db.fix_integrity(season, current_week)?)
+    } else {
+        None
+    };
+
+    if as_json {
+        let output = serde_json::json!({
+            "report": report,
+            "fixed": fix_summary,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_report(&report);
+
+    if let Some(summary) = fix_summary {
+        println!();
+        println!("✓ Deleted {} orphaned rows", summary.orphaned_rows_deleted);
+        if current_week.is_some() {
+            println!(
+                "✓ Cleared actual points on {} future-week rows",
+                summary.future_actuals_cleared
+            );
+        }
+    } else if report
+        .issues
+        .iter()
+        .any(|issue| issue.count > 0 && issue.check.is_fixable())
+    {
+        println!();
+        println!("Run with --fix to delete orphaned rows and clear future-week actuals.");
+    }
+
+    Ok(())
+}
+
+/// Handle `db coverage`: print a week-by-week grid of stored data for a season
+pub fn handle_db_coverage(
+    league_id: Option<LeagueId>,
+    season: Season,
+    weeks: u16,
+    partial_below: f64,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let coverage = db.get_data_coverage(league_id, season, weeks)?;
+
+    if as_json {
+        let output = serde_json::json!({
+            "coverage": coverage,
+            "partial_below": partial_below,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_coverage(&coverage, partial_below);
+    Ok(())
+}
+
+/// Handle `db export`: write a league's stored player-weeks for a season as JSON, JSON lines or Parquet
+///
+/// Output goes to `output` when given, otherwise to stdout.
+pub fn handle_db_export(
+    league_id: Option<LeagueId>,
+    season: Season,
+    format: ExportFormat,
+    output: Option<&Path>,
+) -> Result<()> {
+    if format == ExportFormat::Parquet && !cfg!(feature = "parquet") {
+        return Err(EspnError::FeatureDisabled {
+            feature: "parquet".to_string(),
+        });
+    }
+
+    let league_id = resolve_league_id(league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let rows = db.get_weekly_export(league_id, season)?;
+
+    let mut writer: Box<dyn Write + Send> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout()),
+    };
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, &rows)?;
+            writeln!(writer)?;
+            writer.flush()?;
+        }
+        ExportFormat::JsonLines => {
+            write_json_lines(&mut writer, &rows)?;
+        }
+        ExportFormat::Parquet => {
+            #[cfg(feature = "parquet")]
+            crate::storage::export::write_weekly_parquet(&rows, writer)?;
+        }
+    }
+
+    if let Some(path) = output {
+        println!(
+            "✓ Exported {} player-weeks for season {} to {}",
+            rows.len(),
+            season,
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Handle `db team-names`: list every name each fantasy team has used in a season
+pub fn handle_db_team_names(
+    league_id: Option<LeagueId>,
+    season: Season,
+    team_id: Option<u32>,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let history = db.get_team_name_history(league_id, season, team_id)?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&history)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_team_names(season, &history);
+    Ok(())
+}
+
+/// Handle `db aliases`: list the old names of players ESPN renamed
+pub fn handle_db_aliases(player: Option<&str>, as_json: bool) -> Result<()> {
+    let db = PlayerDatabase::open_existing()?;
+    let player_id = player.map(|query| resolve_player(&db, query)).transpose()?;
+    let aliases = db.list_player_aliases(player_id)?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&aliases)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_aliases(&aliases);
+    Ok(())
+}
+
+/// Handle `db dedupe`: merge players stored under both signs of the same ID
+pub fn handle_db_dedupe(dry_run: bool, as_json: bool) -> Result<()> {
+    let mut db = PlayerDatabase::open_existing()?;
+
+    if dry_run {
+        let pairs = db.find_signed_id_duplicates()?;
+        if as_json {
+            println!("{}", serde_json::to_string_pretty(&pairs)?); // tarpaulin::skip
+        } else {
+            // tarpaulin::skip - console output
+            print_dedupe_pairs(&pairs);
+        }
+        return Ok(());
+    }
+
+    let _lock = ProcessLock::acquire_database()?;
+    let merged = db.dedupe_signed_ids()?;
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&merged)?); // tarpaulin::skip
+    } else {
+        // tarpaulin::skip - console output
+        print_dedupe_results(&merged);
+    }
+    Ok(())
+}
+
+/// Look up the league's current week, warning (not failing) when it can't be determined
+async fn resolve_current_week(
+    league_id: Option<LeagueId>,
+    season: Season,
+    quiet: bool,
+) -> Option<Week> {
+    let lookup = async {
+        let league_id = resolve_league_id(league_id)?;
+        get_league_status(league_id, season).await
+    };
+
+    match lookup.await {
+        Ok(status) => status.current_week(),
+        Err(e) => {
+            if !quiet {
+                println!(
+                    "⚠ Could not determine the current week ({}); skipping future-week checks",
+                    e
+                );
+            }
+            None
+        }
+    }
+}
+
+fn print_report(report: &IntegrityReport) {
+    println!("Database Check - Season {}", report.season);
+    match report.current_week {
+        Some(week) => println!("Current week: {}", week),
+        None => println!("Current week: unknown (future-week checks skipped)"),
+    }
+    println!();
+
+    for issue in &report.issues {
+        let marker = if issue.count == 0 { "✓" } else { "⚠" };
+        println!("{} {}: {}", marker, issue.check.description(), issue.count);
+        for example in &issue.examples {
+            println!(
+                "    player {} season {} week {} (proj {}, actual {})",
+                example.player_id,
+                example.season,
+                example.week,
+                format_points(example.projected_points),
+                format_points(example.actual_points)
+            );
+        }
+        if issue.count > issue.examples.len() {
+            println!("    ... and {} more", issue.count - issue.examples.len());
+        }
+    }
+
+    if !report.duplicate_names.is_empty() {
+        println!();
+        println!(
+            "⚠ Players sharing a name and position: {}",
+            report.duplicate_names.len()
+        );
+        for duplicate in &report.duplicate_names {
+            let ids: Vec<String> = duplicate
+                .player_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect();
+            println!(
+                "    {} ({}): players {}",
+                duplicate.name,
+                duplicate.position,
+                ids.join(", ")
+            );
+        }
+    }
+
+    println!();
+    println!("Total anomalies: {}", report.total_anomalies());
+}
+
+fn print_dedupe_pairs(pairs: &[SignedIdPair]) {
+    if pairs.is_empty() {
+        println!("✓ No players stored under both signs of an ID");
+        return;
+    }
+    println!("Players stored under both signs of an ID: {}", pairs.len());
+    for pair in pairs {
+        println!(
+            "    {} ({}): would keep {}, merge in {}",
+            pair.name, pair.position, pair.kept, pair.removed
+        );
+    }
+    println!();
+    println!("Run without --dry-run to merge them.");
+}
+
+fn print_dedupe_results(results: &[DedupeResult]) {
+    if results.is_empty() {
+        println!("✓ No players stored under both signs of an ID");
+        return;
+    }
+    for result in results {
+        println!(
+            "✓ {} ({}): merged {} into {} ({} weeks combined, {} moved)",
+            result.pair.name,
+            result.pair.position,
+            result.pair.removed,
+            result.pair.kept,
+            result.weeks_merged,
+            result.weeks_moved
+        );
+    }
+}
+
+fn format_points(points: Option<f64>) -> String {
+    points
+        .map(|p| format!("{:.2}", p))
+        .unwrap_or_else(|| "--".to_string())
+}
+
+fn print_coverage(coverage: &DataCoverage, partial_below: f64) {
+    println!("Data Coverage - Season {}", coverage.season);
+    println!();
+
+    let mut header = format!("{:<18}", "");
+    for week in 1..=coverage.weeks {
+        header.push_str(&format!("{:>3}", week));
+    }
+    println!("{}  {:>8}", header, "Total");
+
+    for row in &coverage.rows {
+        let mut line = format!("{:<18}", row.data_type.label());
+        for week in 1..=coverage.weeks {
+            line.push_str(&format!("{:>3}", row.level(week, partial_below).symbol()));
+        }
+        println!("{}  {:>8}", line, row.total);
+    }
+
+    println!();
+    println!(
+        "{} at least {:.0}% of expected rows  {} partial  {} no rows",
+        CoverageLevel::Full.symbol(),
+        partial_below * 100.0,
+        CoverageLevel::Partial.symbol(),
+        CoverageLevel::Missing.symbol()
+    );
+    println!("Expected rows for each data type = its best-covered week; change the partial threshold with --partial-below.");
+    println!("Ownership counts players with a recorded roster status (rostered or free agent).");
+}
+
+fn print_aliases(aliases: &[PlayerAlias]) {
+    println!("Player Aliases");
+    if aliases.is_empty() {
+        println!("No renamed players recorded.");
+        return;
+    }
+
+    let mut table = Table::new(vec![
+        Column::fixed("ID", Align::Right),
+        Column::fixed("Renamed", Align::Left),
+        Column::flex("Old name", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::rest("Current name"),
+    ]);
+    for alias in aliases {
+        table.add_row(vec![
+            alias.player_id.to_string(),
+            format_utc_date(alias.replaced_at),
+            alias.alias.clone(),
+            alias.name.clone().unwrap_or_else(|| "--".to_string()),
+        ]);
+    }
+    println!();
+    table.print();
+}
+
+fn print_team_names(season: Season, history: &[FantasyTeamName]) {
+    println!("Team Names - Season {}", season);
+    if history.is_empty() {
+        println!("No team names recorded; run player-data or roster-diff first.");
+        return;
+    }
+
+    let mut current_team = None;
+    for entry in history {
+        if current_team != Some(entry.team_id) {
+            println!();
+            println!("Team {}", entry.team_id);
+            current_team = Some(entry.team_id);
+        }
+        let weeks = if entry.first_seen_week == entry.last_seen_week {
+            format!("Week {}", entry.first_seen_week)
+        } else {
+            format!("Weeks {}-{}", entry.first_seen_week, entry.last_seen_week)
+        };
+        let abbrev = entry
+            .abbrev
+            .as_ref()
+            .map(|abbrev| format!(" ({})", abbrev))
+            .unwrap_or_default();
+        println!("  {:<12} {}{}", weeks, entry.name, abbrev);
+    }
+}
@@ -1,10 +1,24 @@
 //! League data command implementation
 
 use crate::{
-    core::league_settings_path, error::EspnError,
-    espn::cache_settings::load_or_fetch_league_settings, LeagueId, Result, Season,
-    LEAGUE_ID_ENV_VAR,
+    core::league_settings_path,
+    error::EspnError,
+    espn::{
+        cache_settings::load_or_fetch_league_settings,
+        http::{get_game_metadata, get_league_status},
+    },
+    storage::PlayerDatabase,
+    LeagueId, Result, Season, LEAGUE_ID_ENV_VAR,
 };
+use std::{
+    collections::HashSet,
+    sync::{LazyLock, Mutex},
+};
+
+/// League/season pairs already checked in this process, so repeated handler calls
+/// (e.g. `update-all-data`) only hit the metadata endpoints once
+static CHECKED_SEASONS: LazyLock<Mutex<HashSet<(LeagueId, Season)>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
 
 /// Resolve league ID from option or environment variable
 pub fn resolve_league_id(league_id: Option<LeagueId>) -> Result<LeagueId> {
@@ -31,6 +45,69 @@ pub fn resolve_league_id(league_id: Option<LeagueId>) -> Result<LeagueId> {
     }
 }
 
+/// Reject a season ESPN has no data for before any multi-week work starts.
+///
+/// Seasons after ESPN's latest season are invalid outright; earlier seasons must be
+/// one the league existed in (its `previousSeasons` as of the latest season). Checks
+/// are skipped when the metadata can't be fetched (offline, private league without
+/// cookies), so cached data stays usable.
+pub async fn validate_season(league_id: LeagueId, season: Season) -> Result<()> {
+    if CHECKED_SEASONS
+        .lock()
+        .unwrap()
+        .contains(&(league_id, season))
+    {
+        return Ok(());
+    }
+
+    // tarpaulin::skip - HTTP calls, tested via integration tests
+    if let Some(latest) = get_game_metadata()
+        .await
+        .ok()
+        .and_then(|m| m.current_season_id)
+    {
+        let league_seasons = if season.as_u16() < latest {
+            get_league_status(league_id, Season::new(latest))
+                .await
+                .ok()
+                .map(|status| status.league_seasons())
+        } else {
+            None
+        };
+        check_season(league_id, season, latest, league_seasons.as_deref())?;
+    }
+
+    CHECKED_SEASONS.lock().unwrap().insert((league_id, season));
+    Ok(())
+}
+
+/// Check a requested season against ESPN's latest season and the league's history
+pub fn check_season(
+    league_id: LeagueId,
+    season: Season,
+    latest_available: u16,
+    league_seasons: Option<&[u16]>,
+) -> Result<()> {
+    let requested = season.as_u16();
+    if requested > latest_available {
+        return Err(EspnError::InvalidSeason {
+            requested,
+            latest_available,
+        });
+    }
+
+    match league_seasons {
+        Some(seasons) if !seasons.is_empty() && !seasons.contains(&requested) => {
+            Err(EspnError::LeagueSeasonNotFound {
+                league_id: league_id.as_u32(),
+                season: requested,
+                first_season: seasons[0],
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Handle the league data command
 pub async fn handle_league_data(
     league_id: Option<LeagueId>,
@@ -39,6 +116,7 @@ pub async fn handle_league_data(
     verbose: bool,
 ) -> Result<()> {
     let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
 
     if refresh {
         println!("Fetching fresh league settings from ESPN...");
@@ -52,13 +130,22 @@ pub async fn handle_league_data(
     println!("✓ League settings loaded successfully");
 
     if verbose {
-        let path = league_settings_path(season.as_u16(), league_id.as_u32());
-        println!("League settings cached at: {}", path.display()); // tarpaulin::skip
+        let path = PlayerDatabase::database_path()?;
+        let path = if path.exists() {
+            path
+        } else {
+            league_settings_path(season.as_u16(), league_id.as_u32())
+        };
+        println!("League settings stored in: {}", path.display()); // tarpaulin::skip
         println!("League ID: {}, Season: {}", league_id, season); // tarpaulin::skip
+        println!("Default season: {}", Season::default()); // tarpaulin::skip
         println!(
             "Scoring settings: {} items",
             settings.scoring_settings.scoring_items.len()
         ); // tarpaulin::skip
+        for warning in settings.scoring_settings.override_warnings() {
+            println!("⚠ Warning: {}", warning); // tarpaulin::skip
+        }
     }
 
     Ok(())
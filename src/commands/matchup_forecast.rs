@@ -0,0 +1,755 @@
+//! Matchup forecast command implementation
+//!
+//! Projects both sides of a fantasy matchup from bias-adjusted estimates: the
+//! best available starting lineup for each roster, floor/ceiling ranges, the
+//! projected margin and a win probability. `--add`/`--remove` forecast a
+//! hypothetical roster (e.g. a trade) and compare it with the real one.
+//!
+//! Matchup periods spanning several weeks (e.g. a two-week championship) are
+//! forecast as the sum of each week's best lineup. Once a player's NFL game has
+//! kicked off they stay in their current slot.
+
+use crate::{
+    cli::{
+        render::truncate,
+        types::{filters::FantasyTeamFilter, position::FlexEligibility},
+    },
+    error::EspnError,
+    espn::{
+        cache_settings::load_or_fetch_league_settings,
+        compute::{compute_points_for_week, scoring_slot_for_player, select_weekly_stats},
+        http::{
+            get_league_roster_data, get_league_schedule, get_player_data, get_pro_schedule,
+            PlayerDataRequest,
+        },
+        lineup::{
+            is_ir_slot, optimal_lineup, win_probability, LineupCandidate, OptimalLineup,
+            DEFAULT_STD_FRACTION, FLOOR_CEILING_Z,
+        },
+        types::{
+            team_display_name, InjuryStatus, LeagueData, LeagueSchedule, Player, RosterEntry, Team,
+        },
+    },
+    storage::{self, analysis::BiasWeighting, PerformanceEstimate, PlayerDatabase},
+    LeagueId, PlayerId, Result, Season, UtcTimestamp, Week,
+};
+
+use super::{
+    common::{league_scoring_index, ScoringIndex},
+    league_data::{resolve_league_id, validate_season},
+    notes::resolve_player_among,
+    player_filters::{filter_and_convert_players, resolve_fantasy_team_filter},
+    projection_analysis::{bias_strength_soft_cap, bias_strength_warning, validate_bias_strength},
+    season_records::format_weeks,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Configuration for the matchup forecast.
+#[derive(Debug)]
+pub struct MatchupForecastParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub week: Week,
+    /// Your fantasy team, by name (partial matching) or ID
+    pub team: FantasyTeamFilter,
+    pub bias_strength: f64,
+    pub refresh: bool,
+    pub as_json: bool,
+    /// Players to treat as on your roster (ID or exact name)
+    pub add: Vec<String>,
+    /// Players to take off your roster (ID or exact name)
+    pub remove: Vec<String>,
+    /// Time lineup locks are judged at (defaults to now)
+    pub as_of: Option<UtcTimestamp>,
+}
+
+/// Lineup slot hypothetical additions are placed in (bench)
+const BENCH_SLOT: u8 = 20;
+
+/// Best lineup for one of the other weeks of a multi-week matchup period
+#[derive(Debug, Clone, Serialize)]
+pub struct WeekLineup {
+    pub week: Week,
+    pub lineup: OptimalLineup,
+}
+
+/// Projection for one side of the matchup
+///
+/// Totals cover every week of the matchup period; `lineup` is the forecast week's.
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamForecast {
+    pub team_id: u32,
+    pub team_name: String,
+    pub projected_points: f64,
+    pub floor: f64,
+    pub ceiling: f64,
+    pub lineup: OptimalLineup,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub other_weeks: Vec<WeekLineup>,
+}
+
+impl TeamForecast {
+    pub fn new(
+        team_id: u32,
+        team_name: String,
+        lineup: OptimalLineup,
+        other_weeks: Vec<WeekLineup>,
+    ) -> Self {
+        let lineups = || std::iter::once(&lineup).chain(other_weeks.iter().map(|w| &w.lineup));
+        let projected_points: f64 = lineups().map(OptimalLineup::projected_points).sum();
+        let spread = FLOOR_CEILING_Z * lineups().map(OptimalLineup::variance).sum::<f64>().sqrt();
+        Self {
+            team_id,
+            team_name,
+            projected_points,
+            floor: (projected_points - spread).max(0.0),
+            ceiling: projected_points + spread,
+            lineup,
+            other_weeks,
+        }
+    }
+
+    /// Variance of the total across every week of the matchup period
+    pub fn variance(&self) -> f64 {
+        self.lineup.variance()
+            + self
+                .other_weeks
+                .iter()
+                .map(|w| w.lineup.variance())
+                .sum::<f64>()
+    }
+}
+
+/// Forecast for your team against this week's opponent
+#[derive(Debug, Clone, Serialize)]
+pub struct MatchupForecast {
+    pub season: Season,
+    pub week: Week,
+    pub matchup_period: u16,
+    /// Every week the matchup period is played over, in order
+    pub scoring_periods: Vec<Week>,
+    pub team: TeamForecast,
+    pub opponent: TeamForecast,
+    /// Your projected points minus your opponent's
+    pub projected_margin: f64,
+    /// Normal approximation using both lineups' combined variance
+    pub win_probability: f64,
+    /// Set when the forecast uses a hypothetical roster
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scenario: Option<RosterScenario>,
+    pub warnings: Vec<String>,
+}
+
+/// A hypothetical roster and how it compares with the real one
+#[derive(Debug, Clone, Serialize)]
+pub struct RosterScenario {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Your projection with the real roster
+    pub actual_projected_points: f64,
+    pub actual_win_probability: f64,
+    /// Hypothetical minus real projection
+    pub projected_delta: f64,
+}
+
+impl RosterScenario {
+    /// e.g. "hypothetical: +CeeDee Lamb, -Jaylen Waddle"
+    pub fn label(&self) -> String {
+        let changes: Vec<String> = self
+            .added
+            .iter()
+            .map(|name| format!("+{}", name))
+            .chain(self.removed.iter().map(|name| format!("-{}", name)))
+            .collect();
+        format!("hypothetical: {}", changes.join(", "))
+    }
+}
+
+/// `--add`/`--remove` resolved to players (ID and display name)
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedChanges {
+    pub added: Vec<(PlayerId, String)>,
+    pub removed: Vec<(PlayerId, String)>,
+}
+
+/// Handle the matchup forecast command
+pub async fn handle_matchup_forecast(params: MatchupForecastParams) -> Result<()> {
+    let league_id = resolve_league_id(params.league_id)?;
+    validate_season(league_id, params.season).await?;
+    let season = params.season;
+    let week = params.week;
+    let bias_strength = validate_bias_strength(params.bias_strength)?;
+    if let Some(warning) = bias_strength_warning(bias_strength, bias_strength_soft_cap()) {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+
+    if !params.as_json {
+        println!("Loading league schedule...");
+    }
+    let schedule = get_league_schedule(league_id, season, params.refresh).await?;
+    let team_names = schedule.team_names();
+    let settings = load_or_fetch_league_settings(league_id, false, season).await?;
+    let (scoring_index, _) = league_scoring_index(&settings)?;
+    let schedule_settings = &settings.schedule_settings;
+    let matchup_period = schedule_settings.matchup_period_for(week);
+    let scoring_periods = schedule_settings.scoring_periods(matchup_period);
+    let db = PlayerDatabase::new()?;
+    let team_filter = resolve_fantasy_team_filter(&db, league_id, season, &params.team)?;
+    let team_id = resolve_team_id(&team_names, &team_filter)?;
+    let opponent_id =
+        find_opponent(&schedule, team_id, matchup_period).ok_or(EspnError::NoOpponent {
+            team_id,
+            week: week.as_u16(),
+        })?;
+
+    // The forecast week first, then the rest of its matchup period
+    let mut weeks = Vec::new();
+    for forecast_week in std::iter::once(week).chain(
+        scoring_periods
+            .iter()
+            .copied()
+            .filter(|&other| other != week),
+    ) {
+        if !params.as_json {
+            println!("Loading week {} rosters and projections...", forecast_week);
+        }
+        let (roster_data, _) = get_league_roster_data(
+            false,
+            league_id,
+            season,
+            Some(forecast_week),
+            params.refresh,
+        )
+        .await?;
+        let players_val = get_player_data(PlayerDataRequest {
+            refresh: params.refresh,
+            ..PlayerDataRequest::new(league_id, season, forecast_week)
+        })
+        .await?;
+        let players: Vec<Player> = serde_json::from_value(players_val)?;
+        weeks.push((forecast_week, roster_data, players));
+    }
+
+    let mut warnings = Vec::new();
+    let changes = resolve_changes(&weeks[0].2, &params.add, &params.remove)?;
+    // Without the NFL schedule nobody is treated as locked
+    let as_of = params.as_of.unwrap_or_else(UtcTimestamp::now);
+    let pro_schedule = get_pro_schedule(season).await.ok();
+
+    // Each week's lineups for (you, opponent), plus your real roster's with a scenario
+    let mut week_lineups: Vec<(Week, OptimalLineup, OptimalLineup, Option<OptimalLineup>)> =
+        Vec::new();
+    for (forecast_week, roster_data, players) in weeks {
+        // Scenario warnings are the same every week, so only the first week's are kept
+        let mut scenario_warnings = Vec::new();
+        let scenario_data = changes.as_ref().map(|changes| {
+            apply_roster_changes(&roster_data, team_id, changes, &mut scenario_warnings)
+        });
+        if forecast_week == week {
+            warnings.append(&mut scenario_warnings);
+        }
+
+        let leagues: Vec<&LeagueData> = [Some(&roster_data), scenario_data.as_ref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        let roster_players = roster_projections(
+            players,
+            &leagues,
+            &[team_id, opponent_id],
+            season,
+            forecast_week,
+            &scoring_index,
+        );
+
+        let projected: Vec<(PlayerId, f64)> = roster_players
+            .iter()
+            .map(|(id, (_, projection))| (*id, *projection))
+            .collect();
+        let estimates: HashMap<PlayerId, PerformanceEstimate> = db
+            .estimate_week_performance(
+                league_id,
+                season,
+                forecast_week,
+                &projected,
+                BiasWeighting::new(bias_strength),
+                false,
+            )?
+            .into_iter()
+            .map(|e| (e.player_id, e))
+            .collect();
+
+        let locked_teams = pro_schedule
+            .as_ref()
+            .and_then(|schedule| schedule.locked_teams(forecast_week, as_of.as_secs()));
+        let lineup_for = |league: &LeagueData, id: u32| {
+            let candidates = league
+                .teams
+                .iter()
+                .find(|t| t.id == id)
+                .map(|team| {
+                    build_candidates(team, &roster_players, &estimates, locked_teams.as_ref())
+                })
+                .unwrap_or_default();
+            optimal_lineup(candidates, &settings.roster_settings.lineup_slot_counts)
+        };
+        let league = scenario_data.as_ref().unwrap_or(&roster_data);
+        week_lineups.push((
+            forecast_week,
+            lineup_for(league, team_id),
+            lineup_for(league, opponent_id),
+            scenario_data
+                .is_some()
+                .then(|| lineup_for(&roster_data, team_id)),
+        ));
+    }
+
+    let forecast_team = |id: u32, lineups: Vec<(Week, OptimalLineup)>| {
+        let name = team_display_name(id, team_names.get(&id).map(String::as_str));
+        let mut lineups = lineups.into_iter();
+        let (_, lineup) = lineups.next().expect("the forecast week is always loaded");
+        let other_weeks = lineups
+            .map(|(week, lineup)| WeekLineup { week, lineup })
+            .collect();
+        TeamForecast::new(id, name, lineup, other_weeks)
+    };
+    let team = forecast_team(
+        team_id,
+        week_lineups
+            .iter()
+            .map(|(w, ours, _, _)| (*w, ours.clone()))
+            .collect(),
+    );
+    let opponent = forecast_team(
+        opponent_id,
+        week_lineups
+            .iter()
+            .map(|(w, _, theirs, _)| (*w, theirs.clone()))
+            .collect(),
+    );
+    let projected_margin = team.projected_points - opponent.projected_points;
+    let probability = win_probability(projected_margin, team.variance() + opponent.variance());
+
+    for side in [&team, &opponent] {
+        for hole in side.lineup.holes() {
+            warnings.push(format!(
+                "{}: no healthy player available for {} (bye or injury)",
+                side.team_name, hole.slot
+            ));
+        }
+        for other in &side.other_weeks {
+            for hole in other.lineup.holes() {
+                warnings.push(format!(
+                    "{}: no healthy player available for {} in week {} (bye or injury)",
+                    side.team_name, hole.slot, other.week
+                ));
+            }
+        }
+    }
+    let scenario = changes.map(|changes| {
+        let actual = forecast_team(
+            team_id,
+            week_lineups
+                .into_iter()
+                .filter_map(|(w, _, _, actual)| Some((w, actual?)))
+                .collect(),
+        );
+        let actual_probability = win_probability(
+            actual.projected_points - opponent.projected_points,
+            actual.variance() + opponent.variance(),
+        );
+        RosterScenario {
+            added: changes.added.into_iter().map(|(_, name)| name).collect(),
+            removed: changes.removed.into_iter().map(|(_, name)| name).collect(),
+            actual_projected_points: actual.projected_points,
+            actual_win_probability: actual_probability,
+            projected_delta: team.projected_points - actual.projected_points,
+        }
+    });
+
+    let forecast = MatchupForecast {
+        season,
+        week,
+        matchup_period,
+        scoring_periods,
+        win_probability: probability,
+        projected_margin,
+        team,
+        opponent,
+        scenario,
+        warnings,
+    };
+
+    if params.as_json {
+        println!("{}", serde_json::to_string_pretty(&forecast)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_forecast(&forecast);
+    Ok(())
+}
+
+/// Resolve `--add`/`--remove` against this week's ESPN players; `None` without changes
+fn resolve_changes(
+    players: &[Player],
+    add: &[String],
+    remove: &[String],
+) -> Result<Option<ResolvedChanges>> {
+    if add.is_empty() && remove.is_empty() {
+        return Ok(None);
+    }
+    let known: Vec<storage::Player> = players.iter().map(storage::Player::from_espn).collect();
+    let resolve = |queries: &[String]| -> Result<Vec<(PlayerId, String)>> {
+        let mut resolved: Vec<(PlayerId, String)> = Vec::new();
+        for query in queries {
+            let player_id = resolve_player_among(&known, query)?;
+            if resolved.iter().any(|(id, _)| *id == player_id) {
+                continue;
+            }
+            let name = known
+                .iter()
+                .find(|p| p.player_id == player_id)
+                .map_or_else(|| query.clone(), |p| p.name.clone());
+            resolved.push((player_id, name));
+        }
+        Ok(resolved)
+    };
+    Ok(Some(ResolvedChanges {
+        added: resolve(add)?,
+        removed: resolve(remove)?,
+    }))
+}
+
+/// Copy of the league's rosters with the changes applied to `team_id`
+///
+/// Removed players leave your roster; added players join your bench and leave any
+/// other roster they were on. Changes that don't apply (adding a player you already
+/// have, removing one you don't) are skipped with a warning.
+pub fn apply_roster_changes(
+    league: &LeagueData,
+    team_id: u32,
+    changes: &ResolvedChanges,
+    warnings: &mut Vec<String>,
+) -> LeagueData {
+    let mut scenario = league.clone();
+    let owner = |player_id: PlayerId| {
+        scenario.teams.iter().find(|team| {
+            team.roster.as_ref().is_some_and(|roster| {
+                roster
+                    .entries
+                    .iter()
+                    .any(|e| e.player_id == player_id.as_i64())
+            })
+        })
+    };
+
+    let mut additions = Vec::new();
+    for (player_id, name) in &changes.added {
+        match owner(*player_id) {
+            Some(team) if team.id == team_id => {
+                warnings.push(format!("{} is already on your roster; not added", name))
+            }
+            Some(team) => {
+                warnings.push(format!(
+                    "{} is rostered by {}; moved to your roster for this scenario",
+                    name,
+                    team.name.as_deref().unwrap_or("another team")
+                ));
+                additions.push(*player_id);
+            }
+            None => additions.push(*player_id),
+        }
+    }
+    let mut removals = Vec::new();
+    for (player_id, name) in &changes.removed {
+        match owner(*player_id) {
+            Some(team) if team.id == team_id => removals.push(*player_id),
+            _ => warnings.push(format!("{} is not on your roster; not removed", name)),
+        }
+    }
+
+    for team in &mut scenario.teams {
+        let Some(roster) = &mut team.roster else {
+            continue;
+        };
+        // Removals were only kept for players on your roster
+        roster.entries.retain(|entry| {
+            let player_id = PlayerId::new(entry.player_id);
+            !additions.contains(&player_id) && !removals.contains(&player_id)
+        });
+        if team.id == team_id {
+            roster
+                .entries
+                .extend(additions.iter().map(|player_id| RosterEntry {
+                    player_id: player_id.as_i64(),
+                    lineup_slot_id: BENCH_SLOT,
+                    injury_status: None,
+                }));
+        }
+    }
+    scenario
+}
+
+/// Find the team ID matching a name or ID filter
+pub(crate) fn resolve_team_id(
+    team_names: &HashMap<u32, String>,
+    filter: &FantasyTeamFilter,
+) -> Result<u32> {
+    let found = match filter {
+        FantasyTeamFilter::Id(id) => team_names.contains_key(id).then_some(*id),
+        FantasyTeamFilter::Ids(ids) => {
+            let mut matches: Vec<u32> = ids
+                .iter()
+                .copied()
+                .filter(|id| team_names.contains_key(id))
+                .collect();
+            matches.sort_unstable();
+            matches.first().copied()
+        }
+        FantasyTeamFilter::Name(name) => {
+            let name = name.to_lowercase();
+            let mut matches: Vec<u32> = team_names
+                .iter()
+                .filter(|(_, team_name)| team_name.to_lowercase().contains(&name))
+                .map(|(id, _)| *id)
+                .collect();
+            matches.sort_unstable();
+            matches.first().copied()
+        }
+    };
+
+    found.ok_or_else(|| EspnError::TeamNotFound {
+        team: filter.to_string(),
+    })
+}
+
+/// Opponent of `team_id` in the given matchup period, if they aren't on a bye
+///
+/// Matchup periods aren't always weeks; map a week with
+/// [`ScheduleSettings::matchup_period_for`](crate::espn::types::ScheduleSettings::matchup_period_for).
+pub fn find_opponent(schedule: &LeagueSchedule, team_id: u32, matchup_period: u16) -> Option<u32> {
+    schedule
+        .schedule
+        .iter()
+        .filter(|m| m.matchup_period_id == matchup_period)
+        .find_map(|m| {
+            let home = m.home.as_ref()?.team_id;
+            let away = m.away.as_ref()?.team_id;
+            if home == team_id {
+                Some(away)
+            } else if away == team_id {
+                Some(home)
+            } else {
+                None
+            }
+        })
+}
+
+/// ESPN projections for one week, for players on the given teams' rosters only
+pub(crate) fn roster_projections(
+    players: Vec<Player>,
+    leagues: &[&LeagueData],
+    team_ids: &[u32],
+    season: Season,
+    week: Week,
+    scoring_index: &ScoringIndex,
+) -> HashMap<PlayerId, (Player, f64)> {
+    let rostered_ids: Vec<i64> = leagues
+        .iter()
+        .flat_map(|league| &league.teams)
+        .filter(|t| team_ids.contains(&t.id))
+        .filter_map(|t| t.roster.as_ref())
+        .flat_map(|r| r.entries.iter().map(|e| e.player_id))
+        .collect();
+
+    let mut roster_players = HashMap::new();
+    for filtered in
+        filter_and_convert_players(players, None, None, None, &FlexEligibility::default())
+    {
+        if !rostered_ids.contains(&filtered.player_id.as_i64()) {
+            continue;
+        }
+        let player = filtered.original_player;
+        let Some(scoring_slot) = scoring_slot_for_player(&player) else {
+            continue;
+        };
+        let projection = select_weekly_stats(&player, season.as_u16(), week.as_u16(), 1)
+            .map_or(0.0, |stats| {
+                compute_points_for_week(stats, scoring_slot, scoring_index)
+            });
+        roster_players.insert(filtered.player_id, (player, projection));
+    }
+    roster_players
+}
+
+/// Turn a team's roster into lineup candidates with bias-adjusted projections
+///
+/// Players on an NFL team in `locked_teams` (their game has started) are locked.
+pub(crate) fn build_candidates(
+    team: &Team,
+    roster_players: &HashMap<PlayerId, (Player, f64)>,
+    estimates: &HashMap<PlayerId, PerformanceEstimate>,
+    locked_teams: Option<&HashSet<u32>>,
+) -> Vec<LineupCandidate> {
+    let Some(roster) = &team.roster else {
+        return Vec::new();
+    };
+
+    roster
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let player_id = PlayerId::new(entry.player_id);
+            let (player, espn_projection) = roster_players.get(&player_id)?;
+            let position = player.position()?.to_string();
+
+            let estimate = estimates.get(&player_id);
+            let projection = estimate.map_or(*espn_projection, |e| e.estimated_points);
+            let std_dev = estimate
+                .and_then(|e| e.error_std)
+                .unwrap_or(DEFAULT_STD_FRACTION * projection);
+            let ruled_out = matches!(
+                player.injury_status,
+                Some(InjuryStatus::Out) | Some(InjuryStatus::InjuryReserve)
+            );
+
+            Some(LineupCandidate {
+                player_id,
+                name: player.display_name(),
+                position,
+                projection,
+                std_dev,
+                available: !ruled_out && !is_ir_slot(entry.lineup_slot_id) && projection > 0.0,
+                roster_slot_id: Some(entry.lineup_slot_id),
+                locked: locked_teams.is_some_and(|teams| {
+                    player
+                        .nfl_team_id()
+                        .is_some_and(|pro_team| teams.contains(&pro_team))
+                }),
+            })
+        })
+        .collect()
+}
+
+fn print_forecast(forecast: &MatchupForecast) {
+    let team = &forecast.team;
+    let opponent = &forecast.opponent;
+
+    println!(
+        "Matchup Forecast - Season {} Week {}",
+        forecast.season, forecast.week
+    );
+    if forecast.scoring_periods.len() > 1 {
+        println!(
+            "Matchup period {} is played over weeks {}; lineups are week {}'s, totals cover every week",
+            forecast.matchup_period,
+            format_weeks(&forecast.scoring_periods),
+            forecast.week
+        );
+    }
+    if let Some(scenario) = &forecast.scenario {
+        println!("{}", scenario.label());
+    }
+    println!();
+    println!(
+        "{:<6} {:<36} {:<36}",
+        "Slot",
+        truncate(&team.team_name, 36),
+        truncate(&opponent.team_name, 36)
+    );
+    println!("{:<6} {:<36} {:<36}", "----", "----", "----");
+
+    let cell = |slot: Option<&crate::espn::lineup::LineupSlot>| match slot
+        .and_then(|s| s.player.as_ref())
+    {
+        Some(p) => format!(
+            "{}{} {:.1} ({:.1}-{:.1})",
+            truncate(&p.name, 18),
+            if p.locked { "*" } else { "" },
+            p.projection,
+            p.floor(),
+            p.ceiling()
+        ),
+        None => "-- empty --".to_string(),
+    };
+    let rows = team
+        .lineup
+        .starters
+        .len()
+        .max(opponent.lineup.starters.len());
+    for i in 0..rows {
+        let ours = team.lineup.starters.get(i);
+        let theirs = opponent.lineup.starters.get(i);
+        let slot = ours.or(theirs).map(|s| s.slot.as_str()).unwrap_or("");
+        println!("{:<6} {:<36} {:<36}", slot, cell(ours), cell(theirs));
+    }
+    let any_locked = [team, opponent]
+        .iter()
+        .flat_map(|side| &side.lineup.starters)
+        .any(|s| s.player.as_ref().is_some_and(|p| p.locked));
+    if any_locked {
+        println!("* game has started; locked in this slot");
+    }
+
+    let total = |t: &TeamForecast| {
+        format!(
+            "{:.1} ({:.1}-{:.1})",
+            t.projected_points, t.floor, t.ceiling
+        )
+    };
+    println!();
+    if !team.other_weeks.is_empty() {
+        let week_total = |lineup: &OptimalLineup| {
+            format!(
+                "{:.1} ({:.1}-{:.1})",
+                lineup.projected_points(),
+                lineup.floor(),
+                lineup.ceiling()
+            )
+        };
+        let weeks = std::iter::once((forecast.week, &team.lineup, &opponent.lineup)).chain(
+            team.other_weeks
+                .iter()
+                .zip(&opponent.other_weeks)
+                .map(|(ours, theirs)| (ours.week, &ours.lineup, &theirs.lineup)),
+        );
+        for (week, ours, theirs) in weeks {
+            println!(
+                "{:<6} {:<36} {:<36}",
+                format!("Wk {}", week),
+                week_total(ours),
+                week_total(theirs)
+            );
+        }
+    }
+    println!("{:<6} {:<36} {:<36}", "Total", total(team), total(opponent));
+    println!();
+    println!(
+        "Projected margin: {:+.1} ({})",
+        forecast.projected_margin,
+        if forecast.projected_margin >= 0.0 {
+            &team.team_name
+        } else {
+            &opponent.team_name
+        }
+    );
+    println!(
+        "Win probability:  {:.0}% (normal approximation, players treated as independent)",
+        forecast.win_probability * 100.0
+    );
+    if let Some(scenario) = &forecast.scenario {
+        println!(
+            "Versus your real roster: {:.1} projected ({:+.1}), win probability {:.0}% -> {:.0}%",
+            scenario.actual_projected_points,
+            scenario.projected_delta,
+            scenario.actual_win_probability * 100.0,
+            forecast.win_probability * 100.0
+        );
+    }
+
+    for warning in &forecast.warnings {
+        println!("⚠ {}", warning);
+    }
+}
@@ -1,8 +1,28 @@
 //! Command implementations for ESPN Fantasy Football CLI
 
+pub mod accuracy_report;
+pub mod auth;
+pub mod category_leaders;
 pub mod common;
+pub mod compare;
+pub mod db;
 pub mod league_data;
+pub mod matchup_forecast;
+pub mod notes;
+pub mod optimal_lineup;
+pub mod pace;
 pub mod player_data;
 pub mod player_filters;
+pub mod playoff_odds;
+pub mod positional_ranks;
 pub mod projection_analysis;
+pub mod projection_export;
+pub mod roster;
+pub mod roster_diff;
+pub mod season_records;
+pub mod stats_history;
+pub mod streaming_recap;
+pub mod team_totals;
 pub mod update_all_data;
+pub mod warm_cache;
+pub mod week_over_week;
@@ -0,0 +1,260 @@
+//! Local player notes: add, list and remove annotations, and attach them to output
+//!
+//! Notes live in the `player_notes` table and are keyed by player ID. Players are
+//! looked up by exact (normalized) name or by ID; a name shared by several players
+//! is rejected as ambiguous rather than guessed.
+
+use super::common::write_json_lines;
+use crate::{
+    cli::render::{disambiguate_names, normalize_player_name, NameEntry},
+    error::EspnError,
+    espn::http::format_utc_date,
+    storage::{Player, PlayerDatabase, PlayerNote},
+    PlayerId, Result,
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Notes for a set of players, newest first per player
+pub type NotesByPlayer = HashMap<PlayerId, Vec<PlayerNote>>;
+
+/// An output row with the player's notes appended (for `--show-notes` JSON)
+#[derive(Debug, Serialize)]
+pub struct WithNotes<'a, T: Serialize> {
+    #[serde(flatten)]
+    pub row: &'a T,
+    pub notes: &'a [PlayerNote],
+}
+
+impl<'a, T: Serialize> WithNotes<'a, T> {
+    pub fn new(row: &'a T, player_id: PlayerId, notes: &'a NotesByPlayer) -> Self {
+        Self {
+            row,
+            notes: notes
+                .get(&player_id)
+                .map(|notes| notes.as_slice())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Pretty JSON for output rows, with a `notes` array on each row when `notes` is given
+pub fn rows_to_json<T: Serialize>(
+    rows: &[T],
+    player_id: impl Fn(&T) -> PlayerId,
+    notes: Option<&NotesByPlayer>,
+) -> Result<String> {
+    let json = match notes {
+        Some(notes) => {
+            let rows: Vec<WithNotes<'_, T>> = rows
+                .iter()
+                .map(|row| WithNotes::new(row, player_id(row), notes))
+                .collect();
+            serde_json::to_string_pretty(&rows)?
+        }
+        None => serde_json::to_string_pretty(rows)?,
+    };
+    Ok(json)
+}
+
+/// Print output rows as JSON: a pretty array, or one object per line with `json_lines`
+///
+/// Either way each row gets a `notes` array when `notes` is given.
+pub fn print_json_rows<T: Serialize>(
+    rows: &[T],
+    player_id: impl Fn(&T) -> PlayerId,
+    notes: Option<&NotesByPlayer>,
+    json_lines: bool,
+) -> Result<()> {
+    if !json_lines {
+        println!("{}", rows_to_json(rows, player_id, notes)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    let stdout = std::io::stdout().lock();
+    match notes {
+        Some(notes) => write_json_lines(
+            stdout,
+            rows.iter()
+                .map(|row| WithNotes::new(row, player_id(row), notes)),
+        )?,
+        None => write_json_lines(stdout, rows)?,
+    };
+    Ok(())
+}
+
+/// Text appended to a player's output line: the latest note, if any
+pub fn latest_note_suffix(notes: &NotesByPlayer, player_id: PlayerId) -> String {
+    notes
+        .get(&player_id)
+        .and_then(|notes| notes.first())
+        .map(|note| format!(" 📝 {}", note.note))
+        .unwrap_or_default()
+}
+
+/// Resolve a player by ID or exact name (case and punctuation insensitive)
+///
+/// A name no stored player has now is looked up among the names ESPN used
+/// before renaming a player (see [`PlayerDatabase::list_player_aliases`]).
+pub fn resolve_player(db: &PlayerDatabase, query: &str) -> Result<PlayerId> {
+    let players = db.get_all_players()?;
+    match resolve_player_among(&players, query) {
+        Err(EspnError::PlayerNotFound { .. }) => {
+            let wanted = normalize_player_name(query);
+            let renamed_ids: HashSet<PlayerId> = db
+                .list_player_aliases(None)?
+                .into_iter()
+                .filter(|alias| normalize_player_name(&alias.alias) == wanted)
+                .map(|alias| alias.player_id)
+                .collect();
+            let renamed: Vec<&Player> = players
+                .iter()
+                .filter(|p| renamed_ids.contains(&p.player_id))
+                .collect();
+            match renamed.as_slice() {
+                [] => Err(EspnError::PlayerNotFound {
+                    name: query.to_string(),
+                }),
+                [player] => Ok(player.player_id),
+                _ => Err(EspnError::AmbiguousPlayerName {
+                    name: query.to_string(),
+                    candidates: renamed
+                        .iter()
+                        .map(|p| format!("{} = {}", p.name, p.player_id))
+                        .collect(),
+                }),
+            }
+        }
+        resolved => resolved,
+    }
+}
+
+/// Resolve a player by ID or exact name among the given players
+///
+/// A name shared by several players is an [`EspnError::AmbiguousPlayerName`]
+/// listing each one with its ID.
+pub fn resolve_player_among(players: &[Player], query: &str) -> Result<PlayerId> {
+    if let Ok(id) = query.trim().parse::<i64>() {
+        if players.iter().any(|p| p.player_id.as_i64() == id) {
+            return Ok(PlayerId::new(id));
+        }
+    }
+
+    let wanted = normalize_player_name(query);
+    let matches: Vec<_> = players
+        .iter()
+        .filter(|p| normalize_player_name(&p.name) == wanted)
+        .collect();
+
+    match matches.as_slice() {
+        [] => Err(EspnError::PlayerNotFound {
+            name: query.to_string(),
+        }),
+        [player] => Ok(player.player_id),
+        _ => {
+            let entries: Vec<NameEntry<'_, PlayerId>> = matches
+                .iter()
+                .map(|p| NameEntry {
+                    id: p.player_id,
+                    name: &p.name,
+                    position: &p.position,
+                    team: p.team.as_deref(),
+                })
+                .collect();
+            let names = disambiguate_names(&entries);
+            let candidates = matches
+                .iter()
+                .map(|p| format!("{} = {}", names[&p.player_id], p.player_id))
+                .collect();
+            Err(EspnError::AmbiguousPlayerName {
+                name: query.to_string(),
+                candidates,
+            })
+        }
+    }
+}
+
+/// Resolve a player whose name contains the query among the given players
+///
+/// Several players containing the query are an [`EspnError::AmbiguousPlayerName`]
+/// listing each one with its ID.
+pub fn resolve_player_containing(players: &[Player], query: &str) -> Result<PlayerId> {
+    let wanted = normalize_player_name(query);
+    let matches: Vec<&Player> = players
+        .iter()
+        .filter(|p| !wanted.is_empty() && normalize_player_name(&p.name).contains(&wanted))
+        .collect();
+
+    match matches.as_slice() {
+        [] => Err(EspnError::PlayerNotFound {
+            name: query.to_string(),
+        }),
+        [player] => Ok(player.player_id),
+        _ => {
+            let entries: Vec<NameEntry<'_, PlayerId>> = matches
+                .iter()
+                .map(|p| NameEntry {
+                    id: p.player_id,
+                    name: &p.name,
+                    position: &p.position,
+                    team: p.team.as_deref(),
+                })
+                .collect();
+            let names = disambiguate_names(&entries);
+            Err(EspnError::AmbiguousPlayerName {
+                name: query.to_string(),
+                candidates: matches
+                    .iter()
+                    .map(|p| format!("{} = {}", names[&p.player_id], p.player_id))
+                    .collect(),
+            })
+        }
+    }
+}
+
+/// Handle `note add`: attach a note to a player
+pub fn handle_note_add(player: &str, text: &str) -> Result<()> {
+    let mut db = PlayerDatabase::new()?;
+    let player_id = resolve_player(&db, player)?;
+    let note_id = db.add_player_note(player_id, text)?;
+    println!("✓ Added note {} for player {}", note_id, player_id);
+    Ok(())
+}
+
+/// Handle `note list`: show notes, optionally for one player
+pub fn handle_note_list(player: Option<&str>, as_json: bool) -> Result<()> {
+    let db = PlayerDatabase::open_existing()?;
+    let player_id = player.map(|p| resolve_player(&db, p)).transpose()?;
+    let notes = db.list_player_notes(player_id)?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&notes)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    if notes.is_empty() {
+        println!("No notes found.");
+    }
+    for note in notes {
+        println!(
+            "#{} {} ({}) {}: {}",
+            note.note_id,
+            note.player_name.as_deref().unwrap_or("Unknown player"),
+            note.player_id,
+            format_utc_date(note.created_at),
+            note.note
+        );
+    }
+    Ok(())
+}
+
+/// Handle `note rm`: delete a note by ID
+pub fn handle_note_remove(note_id: i64) -> Result<()> {
+    let mut db = PlayerDatabase::new()?;
+    if !db.delete_player_note(note_id)? {
+        return Err(EspnError::NoteNotFound { note_id });
+    }
+    println!("✓ Removed note {}", note_id);
+    Ok(())
+}
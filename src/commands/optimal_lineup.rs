@@ -0,0 +1,290 @@
+//! Optimal lineup command implementation
+//!
+//! Picks the highest-projected starting lineup for one fantasy team from the
+//! league's lineup slots and bias-adjusted projections, and flags slots where an
+//! injured player is, or would have been, the best option.
+
+use crate::{
+    cli::{
+        render::{Align, Column, Table},
+        types::filters::FantasyTeamFilter,
+    },
+    espn::{
+        cache_settings::load_or_fetch_league_settings,
+        http::{get_league_roster_data, get_player_data, get_pro_schedule, PlayerDataRequest},
+        lineup::{optimal_lineup, slot_eligible_positions, OptimalLineup},
+        types::{team_display_name, InjuryStatus, Player},
+    },
+    storage::{analysis::BiasWeighting, PerformanceEstimate, PlayerDatabase},
+    LeagueId, PlayerId, Result, Season, UtcTimestamp, Week,
+};
+
+use super::{
+    common::league_scoring_index,
+    league_data::{resolve_league_id, validate_season},
+    matchup_forecast::{build_candidates, resolve_team_id, roster_projections},
+    player_filters::resolve_fantasy_team_filter,
+    projection_analysis::{bias_strength_soft_cap, bias_strength_warning, validate_bias_strength},
+};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+/// Configuration for the optimal lineup command.
+#[derive(Debug)]
+pub struct OptimalLineupParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub week: Week,
+    /// The fantasy team, by name (partial matching) or ID
+    pub team: FantasyTeamFilter,
+    pub bias_strength: f64,
+    pub refresh: bool,
+    pub as_json: bool,
+}
+
+/// Best lineup for one fantasy team in one week
+#[derive(Debug, Clone, Serialize)]
+pub struct OptimalLineupReport {
+    pub season: Season,
+    pub week: Week,
+    pub team_id: u32,
+    pub team_name: String,
+    pub projected_points: f64,
+    pub floor: f64,
+    pub ceiling: f64,
+    pub lineup: OptimalLineup,
+    /// Empty slots and slots where an injured player is, or would be, the best option
+    pub warnings: Vec<String>,
+}
+
+/// Handle the optimal lineup command
+pub async fn handle_optimal_lineup(params: OptimalLineupParams) -> Result<()> {
+    let league_id = resolve_league_id(params.league_id)?;
+    validate_season(league_id, params.season).await?;
+    let (season, week) = (params.season, params.week);
+    let bias_strength = validate_bias_strength(params.bias_strength)?;
+    if let Some(warning) = bias_strength_warning(bias_strength, bias_strength_soft_cap()) {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+
+    if !params.as_json {
+        println!("Loading week {} rosters and projections...", week);
+    }
+    let settings = load_or_fetch_league_settings(league_id, false, season).await?;
+    let (scoring_index, _) = league_scoring_index(&settings)?;
+    let (roster_data, _) =
+        get_league_roster_data(false, league_id, season, Some(week), params.refresh).await?;
+    let team_names: HashMap<u32, String> = roster_data
+        .teams
+        .iter()
+        .map(|team| {
+            let name = team_display_name(team.id, team.name.as_deref().or(team.abbrev.as_deref()));
+            (team.id, name)
+        })
+        .collect();
+    let db = PlayerDatabase::new()?;
+    let team_filter = resolve_fantasy_team_filter(&db, league_id, season, &params.team)?;
+    let team_id = resolve_team_id(&team_names, &team_filter)?;
+
+    let players_val = get_player_data(PlayerDataRequest {
+        refresh: params.refresh,
+        ..PlayerDataRequest::new(league_id, season, week)
+    })
+    .await?;
+    let players: Vec<Player> = serde_json::from_value(players_val)?;
+    let roster_players = roster_projections(
+        players,
+        &[&roster_data],
+        &[team_id],
+        season,
+        week,
+        &scoring_index,
+    );
+    let projected: Vec<(PlayerId, f64)> = roster_players
+        .iter()
+        .map(|(id, (_, projection))| (*id, *projection))
+        .collect();
+    let estimates: HashMap<PlayerId, PerformanceEstimate> = db
+        .estimate_week_performance(
+            league_id,
+            season,
+            week,
+            &projected,
+            BiasWeighting::new(bias_strength),
+            false,
+        )?
+        .into_iter()
+        .map(|e| (e.player_id, e))
+        .collect();
+
+    // Players whose game has started stay where they are
+    let locked_teams = get_pro_schedule(season)
+        .await
+        .ok()
+        .and_then(|schedule| schedule.locked_teams(week, UtcTimestamp::now().as_secs()));
+    let candidates = roster_data
+        .teams
+        .iter()
+        .find(|team| team.id == team_id)
+        .map(|team| build_candidates(team, &roster_players, &estimates, locked_teams.as_ref()))
+        .unwrap_or_default();
+    let statuses: HashMap<PlayerId, InjuryStatus> = roster_players
+        .iter()
+        .filter_map(|(id, (player, _))| Some((*id, player.injury_status.clone()?)))
+        .collect();
+
+    let lineup = optimal_lineup(candidates, &settings.roster_settings.lineup_slot_counts);
+    let report = OptimalLineupReport {
+        season,
+        week,
+        team_id,
+        team_name: team_display_name(team_id, team_names.get(&team_id).map(String::as_str)),
+        projected_points: lineup.projected_points(),
+        floor: lineup.floor(),
+        ceiling: lineup.ceiling(),
+        warnings: injury_warnings(&lineup, &statuses),
+        lineup,
+    };
+
+    if params.as_json {
+        println!("{}", serde_json::to_string_pretty(&report)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_report(&report, &statuses);
+    Ok(())
+}
+
+/// Whether a status puts a player's availability in doubt
+fn is_injured(status: &InjuryStatus) -> bool {
+    !matches!(
+        status,
+        InjuryStatus::Active | InjuryStatus::Probable | InjuryStatus::Unknown
+    )
+}
+
+/// Warnings for the lineup's starting slots
+///
+/// Flags empty slots, starters listed as injured (e.g. Questionable), and slots
+/// where a ruled-out player on the bench projects more than the player starting
+/// there. Each benched player is mentioned once, for the first slot they'd fill.
+pub fn injury_warnings(
+    lineup: &OptimalLineup,
+    statuses: &HashMap<PlayerId, InjuryStatus>,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut flagged: HashSet<PlayerId> = HashSet::new();
+    for slot in &lineup.starters {
+        let starter_projection = slot.player.as_ref().map_or(0.0, |p| p.projection);
+        match &slot.player {
+            Some(starter) => {
+                if let Some(status) = statuses.get(&starter.player_id).filter(|s| is_injured(s)) {
+                    warnings.push(format!(
+                        "{}: {} is {}; check their status before kickoff",
+                        slot.slot, starter.name, status
+                    ));
+                }
+            }
+            None => warnings.push(format!(
+                "{}: no healthy player available (bye or injury)",
+                slot.slot
+            )),
+        }
+
+        let positions = slot_eligible_positions(slot.slot_id).unwrap_or_default();
+        let better_injured = lineup
+            .bench
+            .iter()
+            .filter(|c| !c.available && positions.contains(&c.position.as_str()))
+            .filter(|c| c.projection > starter_projection && !flagged.contains(&c.player_id))
+            .filter_map(|c| Some((c, statuses.get(&c.player_id).filter(|s| is_injured(s))?)))
+            .max_by(|(a, _), (b, _)| {
+                a.projection
+                    .partial_cmp(&b.projection)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            });
+        if let Some((benched, status)) = better_injured {
+            flagged.insert(benched.player_id);
+            warnings.push(format!(
+                "{}: {} ({}) would be the best option at {:.1} pts but can't start",
+                slot.slot, benched.name, status, benched.projection
+            ));
+        }
+    }
+    warnings
+}
+
+fn print_report(report: &OptimalLineupReport, statuses: &HashMap<PlayerId, InjuryStatus>) {
+    println!(
+        "Optimal Lineup - {} - Season {} Week {}",
+        report.team_name, report.season, report.week
+    );
+    println!();
+
+    let status = |player_id: &PlayerId| {
+        statuses
+            .get(player_id)
+            .filter(|s| is_injured(s))
+            .map(|s| s.to_string())
+            .unwrap_or_default()
+    };
+    let mut table = Table::new(vec![
+        Column::fixed("Slot", Align::Left),
+        Column::flex("Player", 12, 30),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Proj", Align::Right),
+        Column::fixed("Range", Align::Right),
+        Column::rest("Status"),
+    ]);
+    for slot in &report.lineup.starters {
+        match &slot.player {
+            Some(p) => table.add_row(vec![
+                slot.slot.clone(),
+                format!("{}{}", p.name, if p.locked { "*" } else { "" }),
+                p.position.clone(),
+                format!("{:.1}", p.projection),
+                format!("{:.1}-{:.1}", p.floor(), p.ceiling()),
+                status(&p.player_id),
+            ]),
+            None => table.add_row(vec![
+                slot.slot.clone(),
+                "-- empty --".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ]),
+        }
+    }
+    for p in &report.lineup.bench {
+        table.add_row(vec![
+            "BE".to_string(),
+            format!("{}{}", p.name, if p.locked { "*" } else { "" }),
+            p.position.clone(),
+            format!("{:.1}", p.projection),
+            format!("{:.1}-{:.1}", p.floor(), p.ceiling()),
+            status(&p.player_id),
+        ]);
+    }
+    table.print();
+    let any_locked = report
+        .lineup
+        .starters
+        .iter()
+        .filter_map(|s| s.player.as_ref())
+        .chain(&report.lineup.bench)
+        .any(|p| p.locked);
+    if any_locked {
+        println!("* game has started; locked in this slot");
+    }
+
+    println!();
+    println!(
+        "Projected total: {:.1} ({:.1}-{:.1})",
+        report.projected_points, report.floor, report.ceiling
+    );
+    for warning in &report.warnings {
+        println!("⚠ {}", warning);
+    }
+}
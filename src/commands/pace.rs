@@ -0,0 +1,130 @@
+//! Season pace command implementation
+//!
+//! Extrapolates each player's stored actual points to a full season over their
+//! team's remaining games and compares the pace with a preseason expectation,
+//! listing the biggest over- and under-achievers first.
+
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        render::{
+            disambiguate_names, Align, Column, NameEntry, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH,
+        },
+        types::position::Position,
+    },
+    storage::{analysis::SEASON_GAMES, PlayerDatabase, SeasonPace},
+    LeagueId, PlayerId, Result, Season,
+};
+
+use super::league_data::resolve_league_id;
+
+/// Configuration for the season pace report
+#[derive(Debug)]
+pub struct PaceParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub positions: Option<Vec<Position>>,
+    /// Fewest games played a player needs to be listed
+    pub min_games: u32,
+    /// Number of players to show
+    pub top: usize,
+    pub as_json: bool,
+}
+
+/// JSON output for the season pace command
+#[derive(Debug, Serialize)]
+struct PaceOutput<'a> {
+    season: Season,
+    players: &'a [SeasonPace],
+}
+
+/// Handle the season pace command
+pub fn handle_pace(params: PaceParams) -> Result<()> {
+    let league_id = resolve_league_id(params.league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let mut players = db.season_pace(
+        league_id,
+        params.season,
+        params.positions.as_deref(),
+        params.min_games,
+    )?;
+    players.truncate(params.top);
+
+    if params.as_json {
+        let output = PaceOutput {
+            season: params.season,
+            players: &players,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_pace(params.season, &players);
+    Ok(())
+}
+
+fn print_pace(season: Season, players: &[SeasonPace]) {
+    println!(
+        "Season point pace vs expectation - Season {} ({} games)",
+        season, SEASON_GAMES
+    );
+    if players.is_empty() {
+        println!("No stored actual points for this season; run update-all-data first.");
+        return;
+    }
+
+    let entries: Vec<NameEntry<'_, PlayerId>> = players
+        .iter()
+        .map(|p| NameEntry {
+            id: p.player_id,
+            name: &p.name,
+            position: &p.position,
+            team: p.team.as_deref(),
+        })
+        .collect();
+    let names = disambiguate_names(&entries);
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.1}", points),
+        None => "--".to_string(),
+    };
+
+    let mut table = Table::new(vec![
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Team", Align::Left),
+        Column::fixed("GP", Align::Right),
+        Column::fixed("Left", Align::Right),
+        Column::fixed("Points", Align::Right),
+        Column::fixed("Pace", Align::Right),
+        Column::fixed("Expected", Align::Right),
+        Column::fixed("vs Exp", Align::Right),
+    ]);
+    for player in players {
+        table.add_row(vec![
+            names
+                .get(&player.player_id)
+                .unwrap_or(&player.name)
+                .to_string(),
+            player.position.clone(),
+            player.team.as_deref().unwrap_or("--").to_string(),
+            player.games.to_string(),
+            player.remaining_games.to_string(),
+            format!("{:.1}", player.points),
+            format!("{:.1}", player.pace),
+            format_points(player.expectation),
+            player
+                .vs_expectation
+                .map(|gap| format!("{:+.1}", gap))
+                .unwrap_or_else(|| "--".to_string()),
+        ]);
+    }
+    println!();
+    table.print();
+    println!();
+    println!(
+        "Expected: earliest stored ESPN weekly projection x {} games",
+        SEASON_GAMES
+    );
+}
@@ -15,27 +15,65 @@
 //! # Usage
 //!
 //! The main entry point is [`handle_player_data`] which accepts a [`PlayerDataParams`]
-//! struct containing all configuration options.
+//! struct containing all configuration options. Library callers who want the rows
+//! rather than printed output use [`fetch_player_data`] or [`fetch_points_comparison`].
 
 use crate::{
-    cli::types::position::Position,
+    cli::{
+        render::{
+            disambiguate_names, normalize_player_name, render_sparkline, skip_none, Align, Column,
+            NameEntry, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH,
+        },
+        types::{
+            filters::{ComparisonSort, PlayerDataGroupBy},
+            position::{FlexEligibility, Position},
+        },
+    },
+    core::{metrics::stage, ProcessLock},
+    error::EspnError,
+    espn::http::format_utc_date,
     espn::{
         cache_settings::load_or_fetch_league_settings,
-        compute::{build_scoring_index, compute_points_for_week, select_weekly_stats},
-        http::{get_player_data, update_player_points_with_roster_data, PlayerDataRequest},
-        types::PlayerPoints,
+        compute::{
+            compute_points_for_week, explain_points_for_week, infer_played,
+            scoring_slot_for_player, scoring_slot_for_position, select_weekly_stats,
+            PointsContribution, BASE_SCORING_SLOT,
+        },
+        http::{
+            get_player_data, get_pro_schedule, player_query_limit,
+            update_player_points_with_roster_data, PlayerDataRequest, PLAYERS_PAGE_SIZE,
+        },
+        scoring_overrides::ScoringOverrides,
+        teams::pro_team_abbrev,
+        types::{InjuryStatus, LeagueData, PlayerPoints, ProSchedule},
     },
-    storage::{PlayerDatabase, PlayerWeeklyStats},
-    Result, Season, Week,
+    storage::{analysis::POSITION_GROUP_ORDER, PlayerDatabase, PlayerHeadline, PlayerWeeklyStats},
+    LeagueId, PlayerId, Result, Season, UtcTimestamp, Week, WeekRange,
 };
 
 use super::{
-    common::{CommandParams, CommandParamsBuilder},
-    league_data::resolve_league_id,
-    player_filters::{apply_status_filters, filter_and_convert_players},
+    common::{
+        league_scoring_index, report_unknown_positions, unknown_position_names, write_csv_rows,
+        CommandParams, CommandParamsBuilder, ScoringIndex,
+    },
+    league_data::{resolve_league_id, validate_season},
+    notes::{latest_note_suffix, print_json_rows, NotesByPlayer},
+    player_filters::{
+        apply_status_filters, filter_and_convert_players, resolve_fantasy_team_filter,
+        FilteredPlayer,
+    },
 };
 use crate::espn::types::CachedPlayerData;
 use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+};
+
+/// Number of weeks (ending at the queried week) shown in `--sparkline` trends
+pub const SPARKLINE_WEEKS: u16 = 8;
 
 /// Configuration for player data retrieval.
 #[derive(Debug)]
@@ -44,7 +82,35 @@ pub struct PlayerDataParams {
     pub projected: bool,
     pub debug: bool,
     pub clear_db: bool,
+    /// Also delete player notes when `clear_db` is set
+    pub include_notes: bool,
     pub refresh_positions: bool,
+    /// Show recent actual points as a sparkline (text) or weekly array (JSON)
+    pub sparkline: bool,
+    /// Render sparklines with ASCII characters instead of unicode blocks
+    pub ascii: bool,
+    /// Show projected and actual points side by side with their difference
+    pub both: bool,
+    /// Output ordering; `Delta` needs `both`
+    pub sort: ComparisonSort,
+    /// Reverse `sort`
+    pub reverse: bool,
+    /// Append each player's latest note (all notes in JSON)
+    pub show_notes: bool,
+    /// Add each player's latest stored ESPN news headline
+    pub with_news: bool,
+    /// Number of players to show; per team when grouping by team
+    pub top: Option<usize>,
+    /// Section output by fantasy team
+    pub group_by: Option<PlayerDataGroupBy>,
+    /// Break actual points down by stat
+    pub explain: bool,
+    /// Only players whose injury status changed at or after this time
+    pub status_changed_since: Option<UtcTimestamp>,
+    /// Several weeks instead of `base.week`, combined into one result set
+    pub weeks: Option<WeekRange>,
+    /// Skip the warning for actual points of a week that hasn't been played
+    pub quiet: bool,
 }
 
 impl PlayerDataParams {
@@ -55,7 +121,21 @@ impl PlayerDataParams {
             projected,
             debug: false,
             clear_db: false,
+            include_notes: false,
             refresh_positions: false,
+            sparkline: false,
+            ascii: false,
+            both: false,
+            sort: ComparisonSort::Points,
+            reverse: false,
+            show_notes: false,
+            with_news: false,
+            top: None,
+            group_by: None,
+            explain: false,
+            status_changed_since: None,
+            weeks: None,
+            quiet: false,
         }
     }
 
@@ -76,72 +156,732 @@ impl CommandParamsBuilder for PlayerDataParams {
     }
 }
 
-/// Retrieve and process player fantasy data for a given week.
+/// League, settings and rosters a player-data load works from
+struct PreparedLoad {
+    db: PlayerDatabase,
+    league_id: LeagueId,
+    weeks: Vec<Week>,
+    /// Each of `weeks`' rosters; `None` where ESPN couldn't be reached
+    rosters: Vec<Option<LeagueData>>,
+    scoring_index: ScoringIndex,
+    overrides: Option<ScoringOverrides>,
+    flex: FlexEligibility,
+}
+
+/// Rows loaded for output, with the database and scoring behind them
+struct LoadedRows<T> {
+    db: PlayerDatabase,
+    league_id: LeagueId,
+    scoring_index: ScoringIndex,
+    overrides: Option<ScoringOverrides>,
+    rows: Vec<T>,
+}
+
+/// Retrieve player fantasy points for a given week (or each of `weeks`), filtered and
+/// sorted the way `player-data` shows them.
+///
+/// Fetches player stats from ESPN API (or the local database when the week is stored),
+/// calculates fantasy points using league settings and applies every injury, roster,
+/// fantasy team and status-change filter. Rows keep their week, ordered by `sort` within
+/// each week and cut to `top` unless grouping. Nothing is printed to stdout. `both` is
+/// ignored here; see [`fetch_points_comparison`].
+pub async fn fetch_player_data(mut params: PlayerDataParams) -> Result<Vec<PlayerPoints>> {
+    Ok(load_player_points(&mut params, false).await?.rows)
+}
+
+/// Retrieve projected and actual points side by side for `base.week`, as `--both` shows them.
+///
+/// Takes the same filters as [`fetch_player_data`]; rows are ordered by `sort` and cut
+/// to `top` unless grouping. Nothing is printed to stdout.
+pub async fn fetch_points_comparison(
+    mut params: PlayerDataParams,
+) -> Result<Vec<PointsComparison>> {
+    Ok(load_points_comparisons(&mut params, false).await?.rows)
+}
+
+/// Retrieve, process and print player fantasy data for a given week (or each of `weeks`).
 ///
-/// Fetches player stats from ESPN API, calculates fantasy points using league settings,
-/// and caches results in local database for performance.
-pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
+/// Loads rows as [`fetch_player_data`] (or [`fetch_points_comparison`] with `both`) does,
+/// then prints them in the requested format. League settings are loaded once for a range
+/// of weeks; rows keep their week, sorted by points within each week.
+pub async fn handle_player_data(mut params: PlayerDataParams) -> Result<()> {
+    // Progress goes to stdout, so it is left out when stdout carries JSON
+    let verbose = !params.base.as_json;
+    if params.both {
+        let loaded = load_points_comparisons(&mut params, verbose).await?;
+        return print_points_comparisons(&loaded.db, &params, loaded.rows);
+    }
+
+    let LoadedRows {
+        db,
+        league_id,
+        scoring_index,
+        overrides,
+        rows: player_points,
+    } = load_player_points(&mut params, verbose).await?;
+
+    if let Some(PlayerDataGroupBy::Team) = params.group_by {
+        let groups = group_by_team(
+            player_points,
+            |p| (p.team_id, p.team_name.as_deref()),
+            params.top,
+        );
+        return print_team_points(&db, &groups, params.base.as_json);
+    }
+
+    // Recent weekly actuals for trend display, normalized per position
+    let (recent_points, position_bounds) = if params.sparkline {
+        let player_ids: Vec<_> = player_points.iter().map(|p| p.id).collect();
+        (
+            db.get_recent_actual_points(
+                league_id,
+                &player_ids,
+                params.base.season,
+                params.base.week,
+                SPARKLINE_WEEKS,
+            )?,
+            db.get_position_point_bounds(league_id, params.base.season)?,
+        )
+    } else {
+        (HashMap::new(), HashMap::new())
+    };
+
+    let notes = if params.show_notes {
+        let player_ids: Vec<_> = player_points.iter().map(|p| p.id).collect();
+        Some(db.get_notes_for_players(&player_ids)?)
+    } else {
+        None
+    };
+    let news = if params.with_news {
+        let player_ids: Vec<_> = player_points.iter().map(|p| p.id).collect();
+        Some(db.get_player_news(&player_ids)?)
+    } else {
+        None
+    };
+
+    // Per-stat breakdown of actual points, from the raw stats stored for the week
+    let breakdowns: HashMap<PlayerId, Vec<PointsContribution>> = if params.explain {
+        let player_ids: Vec<_> = player_points.iter().map(|p| p.id).collect();
+        let raw_stats =
+            db.get_raw_stats(league_id, &player_ids, params.base.season, params.base.week)?;
+        player_points
+            .iter()
+            .filter_map(|player| {
+                let stats = raw_stats.get(&player.id)?;
+                let scoring_slot = player
+                    .position
+                    .parse::<Position>()
+                    .map_or(BASE_SCORING_SLOT, |p| scoring_slot_for_position(p.to_u8()));
+                let mut breakdown = explain_points_for_week(stats, scoring_slot, &scoring_index);
+                if let Some(overrides) = &overrides {
+                    overrides.annotate(&mut breakdown, scoring_slot);
+                }
+                Some((player.id, breakdown))
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
+
+    if params.base.as_json && params.explain {
+        let with_breakdown: Vec<PlayerPointsWithBreakdown> = player_points
+            .iter()
+            .map(|player| PlayerPointsWithBreakdown {
+                player,
+                breakdown: breakdowns
+                    .get(&player.id)
+                    .map(|b| b.as_slice())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        print_player_rows(
+            &with_breakdown,
+            |row| row.player.id,
+            notes.as_ref(),
+            news.as_ref(),
+            params.base.json_lines,
+        )?;
+    } else if params.base.as_json && params.sparkline {
+        let with_trend: Vec<PlayerPointsWithTrend> = player_points
+            .iter()
+            .map(|player| PlayerPointsWithTrend {
+                player,
+                recent_points: recent_points
+                    .get(&player.id)
+                    .map(|points| points.as_slice())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        print_player_rows(
+            &with_trend,
+            |row| row.player.id,
+            notes.as_ref(),
+            news.as_ref(),
+            params.base.json_lines,
+        )?;
+    } else if params.base.csv {
+        write_player_points_csv(std::io::stdout().lock(), &player_points)?;
+    } else if params.base.as_json {
+        print_player_rows(
+            &player_points,
+            |row| row.id,
+            notes.as_ref(),
+            news.as_ref(),
+            params.base.json_lines,
+        )?;
+    } else {
+        let names = display_names(
+            &db,
+            player_points
+                .iter()
+                .map(|p| (p.id, p.name.as_str(), p.position.as_str())),
+        );
+        // Omit the opponent column for weeks fetched before matchups were stored
+        let show_opponent = player_points.iter().any(|p| p.opponent_team.is_some());
+        // tarpaulin::skip - console output
+        let mut columns = player_columns();
+        if show_opponent {
+            columns.push(Column::fixed("Opp", Align::Left));
+        }
+        columns.push(Column::fixed("Points", Align::Right));
+        if params.sparkline {
+            columns.push(Column::fixed("Trend", Align::Left));
+        }
+        columns.extend(annotation_column(notes.as_ref(), news.as_ref()));
+        let mut table = Table::new(columns);
+
+        for player in player_points {
+            let mut cells = player_cells(
+                player.id,
+                names.get(&player.id).unwrap_or(&player.name),
+                &player.position,
+                player.week,
+                status_label(player.injury_status.as_ref(), player.injured),
+                roster_label(player.is_rostered, player.team_name.as_deref()),
+            );
+            if show_opponent {
+                cells.push(opponent_label(
+                    player.opponent_team.as_deref(),
+                    player.home_game,
+                ));
+            }
+            cells.push(if player.is_dnp() {
+                "DNP".to_string()
+            } else {
+                format!("{:.2}", player.points)
+            });
+            if params.sparkline {
+                let (min, max) = position_bounds
+                    .get(&player.position)
+                    .copied()
+                    .unwrap_or((0.0, 0.0));
+                cells.push(
+                    recent_points
+                        .get(&player.id)
+                        .map(|points| render_sparkline(points, min, max, params.ascii))
+                        .unwrap_or_default(),
+                );
+            }
+            cells.extend(annotation_cell(notes.as_ref(), news.as_ref(), player.id));
+            table.add_row(cells);
+
+            for contribution in breakdowns.get(&player.id).into_iter().flatten() {
+                let adjustment = contribution
+                    .adjustment
+                    .as_ref()
+                    .map(|adjustment| format!("  [override: {}]", adjustment))
+                    .unwrap_or_default();
+                table.add_detail(format!(
+                    "    {}: {} × {:.2} = {:+.2}{}",
+                    contribution.label,
+                    contribution.raw_value,
+                    contribution.points_per_unit,
+                    contribution.points,
+                    adjustment
+                ));
+            }
+        }
+        table.print();
+    }
+
+    Ok(())
+}
+
+/// Open the database and load what every week of the request needs
+///
+/// Runs the `clear_db` and `refresh_positions` maintenance first, fetches and stores each
+/// week's rosters, and resolves player and fantasy team filters to every name used.
+async fn prepare_load(params: &mut PlayerDataParams, verbose: bool) -> Result<PreparedLoad> {
+    if params.sort == ComparisonSort::Delta && !params.both {
+        return Err(EspnError::SortRequiresBoth {
+            sort: params.sort.to_string(),
+        });
+    }
     let league_id = resolve_league_id(params.base.league_id)?;
-    println!("Connecting to database...");
+    validate_season(league_id, params.base.season).await?;
+    if verbose {
+        println!("Connecting to database...");
+    }
+    if params.debug {
+        eprintln!("Database => {}", PlayerDatabase::database_path()?.display());
+        // tarpaulin::skip
+    }
     let mut db = PlayerDatabase::new()?;
 
+    // A filter for a player's old name also finds them under their current one
+    if let Some(names) = &params.base.player_names {
+        params.base.player_names = Some(db.expand_player_names_with_aliases(names)?);
+    }
+
+    // If clear_db flag is set, clear all database data first
+    if params.clear_db {
+        let _lock = ProcessLock::acquire_database()?;
+        if verbose {
+            println!("Clearing all database data..."); // tarpaulin::skip
+        }
+        db.clear_all_data()?;
+        if params.include_notes {
+            db.clear_player_notes()?;
+        }
+        if verbose {
+            println!("✓ Database cleared successfully!"); // tarpaulin::skip
+        }
+    }
+
+    // Defenses stored before they were named after their NFL team
+    if params.refresh_positions {
+        let named = db.backfill_defense_names()?;
+        if verbose {
+            println!(
+                "✓ Named {} team defenses stored without a name or team",
+                named
+            );
+        }
+    }
+
+    let weeks: Vec<Week> = match &params.weeks {
+        Some(weeks) => weeks.weeks().to_vec(),
+        None => vec![params.base.week],
+    };
+    let mut rosters = Vec::with_capacity(weeks.len());
+    for &week in &weeks {
+        rosters.push(load_week_roster(&mut db, league_id, params, week, verbose).await);
+    }
+
+    // Match --team against every name a team has used this season
+    if let Some(filter) = &params.base.fantasy_team_filter {
+        params.base.fantasy_team_filter = Some(resolve_fantasy_team_filter(
+            &db,
+            league_id,
+            params.base.season,
+            filter,
+        )?);
+    }
+
+    // Load or fetch league settings to compute points; cached for future runs.
+    if verbose {
+        println!("Loading league scoring settings...");
+    }
+    let settings = load_or_fetch_league_settings(league_id, false, params.base.season).await?;
+    let (scoring_index, overrides) = league_scoring_index(&settings)?;
+
+    Ok(PreparedLoad {
+        db,
+        league_id,
+        weeks,
+        rosters,
+        scoring_index,
+        overrides,
+        flex: settings.flex_eligibility(),
+    })
+}
+
+/// Load the requested side's points for every week, filtered and sorted for output
+///
+/// Leaves `base.week` at the last week loaded.
+async fn load_player_points(
+    params: &mut PlayerDataParams,
+    verbose: bool,
+) -> Result<LoadedRows<PlayerPoints>> {
+    let PreparedLoad {
+        mut db,
+        league_id,
+        weeks,
+        rosters,
+        scoring_index,
+        overrides,
+        flex,
+    } = prepare_load(params, verbose).await?;
+    let source = PointsSource {
+        league_id,
+        scoring_index: &scoring_index,
+        flex: &flex,
+        verbose,
+    };
+
+    let mut player_points = Vec::new();
+    for (week, roster_data) in weeks.into_iter().zip(&rosters) {
+        params.base.week = week;
+        let mut week_points = load_week_points(
+            &mut db,
+            &source,
+            params,
+            params.projected,
+            roster_data.as_ref(),
+        )
+        .await?;
+
+        // Apply client-side filtering for specific injury statuses, roster status, and fantasy team
+        if params.base.injury_status.is_some()
+            || params.base.roster_status.is_some()
+            || params.base.fantasy_team_filter.is_some()
+        {
+            apply_status_filters(
+                &mut week_points,
+                params.base.injury_status.as_ref(),
+                params.base.roster_status.as_ref(),
+                params.base.fantasy_team_filter.as_ref(),
+            );
+        }
+        if let Some(since) = params.status_changed_since {
+            let changed: HashSet<PlayerId> = db
+                .get_status_changes(params.base.season, week, since.as_secs())?
+                .into_iter()
+                .map(|change| change.player_id)
+                .collect();
+            week_points.retain(|p| changed.contains(&p.id));
+        }
+        use_current_team_names(&db, league_id, params, &mut week_points)?;
+
+        // Points first, so players tied on another sort stay in points order
+        week_points.sort_by(|a, b| {
+            b.points
+                .partial_cmp(&a.points)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        sort_player_rows(&mut week_points, params.sort, params.reverse, |p| {
+            SortFields {
+                points: Some(p.points),
+                delta: None,
+                name: &p.name,
+                position: &p.position,
+                team_name: p.team_name.as_deref(),
+            }
+        });
+        // --top limits each team instead when grouping
+        if let (Some(top), None) = (params.top, params.group_by) {
+            week_points.truncate(top);
+        }
+        player_points.extend(week_points);
+    }
+
+    Ok(LoadedRows {
+        db,
+        league_id,
+        scoring_index,
+        overrides,
+        rows: player_points,
+    })
+}
+
+/// `--both`: load actual and projected points for the week and join them by player
+async fn load_points_comparisons(
+    params: &mut PlayerDataParams,
+    verbose: bool,
+) -> Result<LoadedRows<PointsComparison>> {
+    let PreparedLoad {
+        mut db,
+        league_id,
+        mut rosters,
+        scoring_index,
+        overrides,
+        flex,
+        ..
+    } = prepare_load(params, verbose).await?;
+    let source = PointsSource {
+        league_id,
+        scoring_index: &scoring_index,
+        flex: &flex,
+        verbose,
+    };
+    let roster_data = rosters.pop().flatten();
+
+    let mut sources = Vec::with_capacity(2);
+    for projected in [false, true] {
+        let mut points =
+            load_week_points(&mut db, &source, params, projected, roster_data.as_ref()).await?;
+        apply_status_filters(
+            &mut points,
+            params.base.injury_status.as_ref(),
+            params.base.roster_status.as_ref(),
+            params.base.fantasy_team_filter.as_ref(),
+        );
+        use_current_team_names(&db, league_id, params, &mut points)?;
+        sources.push(points);
+    }
+    let projected = sources.pop().unwrap_or_default();
+    let actual = sources.pop().unwrap_or_default();
+
+    let mut rows = combine_projected_actual(actual, projected);
+    sort_comparisons(&mut rows, params.sort, params.reverse);
+    // --top limits each team instead when grouping
+    if let (Some(top), None) = (params.top, params.group_by) {
+        rows.truncate(top);
+    }
+
+    Ok(LoadedRows {
+        db,
+        league_id,
+        scoring_index,
+        overrides,
+        rows,
+    })
+}
+
+/// Fetch the week's rosters and store them as that week's snapshot and team names
+///
+/// `None` when ESPN can't be reached; players then show an unknown roster status.
+async fn load_week_roster(
+    db: &mut PlayerDatabase,
+    league_id: LeagueId,
+    params: &PlayerDataParams,
+    week: Week,
+    verbose: bool,
+) -> Option<LeagueData> {
     // Fetch week-specific roster data to match the player data we're querying
     let roster_data = match crate::espn::http::get_league_roster_data(
         false,
         league_id,
         params.base.season,
-        Some(params.base.week),
+        Some(week),
         params.base.refresh,
     )
     .await
     {
         Ok((data, cache_status)) => {
-            match cache_status {
-                crate::espn::http::CacheStatus::Hit => {
-                    println!(
-                        "✓ Week {} roster status loaded (from cache)",
-                        params.base.week.as_u16()
-                    );
-                }
-                crate::espn::http::CacheStatus::Miss => {
-                    println!(
-                        "✓ Week {} roster status fetched (cache miss)",
-                        params.base.week.as_u16()
-                    );
-                }
-                crate::espn::http::CacheStatus::Refreshed => {
-                    println!(
-                        "✓ Week {} roster status fetched (refreshed)",
-                        params.base.week.as_u16()
-                    );
-                }
+            if verbose {
+                println!("✓ {}", cache_status.message(Some(week)));
             }
-            Some(data)
+            data
         }
         Err(e) => {
-            println!(
-                "⚠ Could not fetch week {} roster data: {}",
-                params.base.week.as_u16(),
-                e
-            );
-            None
+            if verbose {
+                println!(
+                    "⚠ Could not fetch week {} roster data: {}",
+                    week.as_u16(),
+                    e
+                );
+            }
+            return None;
         }
     };
 
-    // If clear_db flag is set, clear all database data first
-    if params.clear_db {
-        println!("Clearing all database data..."); // tarpaulin::skip
-        db.clear_all_data()?;
-        println!("✓ Database cleared successfully!"); // tarpaulin::skip
+    // Keep a per-week roster snapshot for roster-diff, and the team names seen that week
+    if let Err(e) = db.save_roster_snapshot(league_id, params.base.season, week, &roster_data) {
+        if verbose {
+            println!("⚠ Warning: Could not save roster snapshot: {}", e);
+        }
+    }
+    if let Err(e) = db.record_team_names(league_id, params.base.season, week, &roster_data) {
+        if verbose {
+            println!("⚠ Warning: Could not record team names: {}", e);
+        }
     }
+    Some(roster_data)
+}
 
-    // Load or fetch league settings to compute points; cached for future runs.
-    println!("Loading league scoring settings...");
-    let settings = load_or_fetch_league_settings(league_id, false, params.base.season).await?;
-    let scoring_index = build_scoring_index(&settings.scoring_settings.scoring_items);
+/// How a week's points are scored and where they're stored
+struct PointsSource<'a> {
+    league_id: LeagueId,
+    scoring_index: &'a ScoringIndex,
+    flex: &'a FlexEligibility,
+    /// Print progress to stdout
+    verbose: bool,
+}
+
+/// Players scored and stored per chunk on a fresh fetch; bounds how much parsed stat
+/// data is held at once on a full-league pull
+pub const SCORING_CHUNK_SIZE: usize = 200;
+
+/// A scored player: the row to store, the requested side's points (if any) and the
+/// raw actual stats behind the per-category leader queries
+type ScoredPlayer = (PlayerWeeklyStats, Option<PlayerPoints>, Option<Value>);
 
+/// Score both stat sources for one player from the fetched payload
+///
+/// Both sides are stored so the other view is served from the database; only the
+/// requested side becomes [`PlayerPoints`]. `None` when the player has neither side.
+fn score_player(
+    filtered_player: &FilteredPlayer,
+    params: &PlayerDataParams,
+    projected: bool,
+    scoring_index: &ScoringIndex,
+    pro_schedule: Option<&ProSchedule>,
+) -> Option<ScoredPlayer> {
+    let player = &filtered_player.original_player;
+    let player_id = filtered_player.player_id;
+
+    let position = player
+        .position()
+        .map_or_else(|| "UNKNOWN".to_string(), |p| p.to_string());
+    let scoring_slot = scoring_slot_for_player(player)?;
+
+    let score = |stat_source: u8| {
+        select_weekly_stats(
+            player,
+            params.base.season.as_u16(),
+            params.base.week.as_u16(),
+            stat_source,
+        )
+        .map(|stats| {
+            (
+                stats,
+                compute_points_for_week(stats, scoring_slot, scoring_index),
+            )
+        })
+    };
+    let actual = score(0);
+    let projection = score(1);
+    if actual.is_none() && projection.is_none() {
+        return None;
+    }
+
+    // Participation is only meaningful for actual results
+    let played =
+        actual.map(|(stats, _)| infer_played(stats, player.active, player.injury_status.as_ref()));
+
+    // The week's NFL matchup for the player's team (free agents have team 0)
+    let (opponent_team, home_game) =
+        match (pro_schedule, player.pro_team_id.filter(|&team| team != 0)) {
+            (Some(schedule), Some(team)) => (
+                schedule
+                    .opponent(team, params.base.week)
+                    .and_then(pro_team_abbrev)
+                    .map(str::to_string),
+                schedule.is_home(team, params.base.week),
+            ),
+            _ => (None, None),
+        };
+
+    let weekly_db_stats = PlayerWeeklyStats {
+        player_id,
+        season: params.base.season,
+        week: params.base.week,
+        league_id: None, // Set along with roster status before the row is stored
+        projected_points: projection.map(|(_, points)| points),
+        actual_points: actual.map(|(_, points)| points),
+        active: player.active,
+        injured: player.injured,
+        injury_status: player.injury_status.clone(),
+        is_rostered: None, // Filled in from roster data before the row is stored
+        fantasy_team_id: None,
+        fantasy_team_name: None,
+        played,
+        percent_owned: player.ownership.and_then(|o| o.percent_owned),
+        percent_started: player.ownership.and_then(|o| o.percent_started),
+        opponent_team: opponent_team.clone(),
+        home_game,
+        created_at: 0, // Will be set by database
+        updated_at: 0, // Will be set by database
+    };
+
+    let requested = if projected { projection } else { actual };
+    let player_point = requested.map(|(_, points)| {
+        let mut player_point = PlayerPoints::from_espn_player(
+            player_id,
+            player,
+            position,
+            points,
+            params.base.week,
+            projected,
+        );
+        player_point.played = if projected { None } else { played };
+        player_point.opponent_team = opponent_team;
+        player_point.home_game = home_game;
+        player_point
+    });
+
+    // Raw actual stats back the per-category leader queries
+    let raw_actual = actual.and_then(|(stats, _)| serde_json::to_value(stats).ok());
+
+    Some((weekly_db_stats, player_point, raw_actual))
+}
+
+/// Apply roster status to one chunk of scored players and store it
+///
+/// Returns the requested side's points, with roster status set.
+fn store_scored_players(
+    db: &mut PlayerDatabase,
+    league_id: LeagueId,
+    params: &PlayerDataParams,
+    scored: Vec<ScoredPlayer>,
+    roster_data: Option<&LeagueData>,
+    verbose: bool,
+) -> Vec<PlayerPoints> {
+    let mut weekly_stats = Vec::with_capacity(scored.len());
+    let mut player_points = Vec::new();
+    let mut raw_stats = Vec::new();
+    for (weekly_db_stats, player_point, raw_actual) in scored {
+        if let Some(raw_actual) = raw_actual {
+            raw_stats.push((weekly_db_stats.player_id, raw_actual));
+        }
+        player_points.extend(player_point);
+        weekly_stats.push(weekly_db_stats);
+    }
+
+    update_player_points_with_roster_data(&mut player_points, roster_data, false);
+    let rostered: HashMap<PlayerId, &PlayerPoints> =
+        player_points.iter().map(|p| (p.id, p)).collect();
+    for weekly_db_stats in &mut weekly_stats {
+        weekly_db_stats.league_id = Some(league_id);
+        if let Some(player) = rostered.get(&weekly_db_stats.player_id) {
+            weekly_db_stats.is_rostered = player.is_rostered;
+            weekly_db_stats.fantasy_team_id = player.team_id;
+            weekly_db_stats.fantasy_team_name = player.team_name.clone();
+        }
+    }
+
+    if let Err(e) = db.merge_weekly_stats_batch(&weekly_stats) {
+        if verbose {
+            println!("⚠ Warning: Could not store weekly stats: {}", e);
+        }
+    }
+    if let Err(e) = db.record_injury_statuses(&weekly_stats, UtcTimestamp::now().as_secs()) {
+        if verbose {
+            println!("⚠ Warning: Could not record injury statuses: {}", e);
+        }
+    }
+    let raw_refs: Vec<(PlayerId, &Value)> =
+        raw_stats.iter().map(|(id, stats)| (*id, stats)).collect();
+    if let Err(e) = db.save_raw_stats(league_id, params.base.season, params.base.week, &raw_refs) {
+        if verbose {
+            println!("⚠ Warning: Could not store raw stats: {}", e);
+        }
+    }
+    player_points
+}
+
+/// Load one source (actual or projected) of player points for the week
+///
+/// Serves from the database when the week is already stored for that source, otherwise
+/// fetches from ESPN. The payload carries both actual and projected stats, so both are
+/// scored and merged into the stored rows in one pass; a side missing from the payload
+/// leaves the stored value alone.
+async fn load_week_points(
+    db: &mut PlayerDatabase,
+    source: &PointsSource<'_>,
+    params: &PlayerDataParams,
+    projected: bool,
+    roster_data: Option<&LeagueData>,
+) -> Result<Vec<PlayerPoints>> {
+    let PointsSource {
+        league_id,
+        scoring_index,
+        flex,
+        verbose,
+    } = *source;
     let mut player_points: Vec<PlayerPoints> = Vec::new();
-    let mut stats_to_save: Vec<(PlayerWeeklyStats, PlayerPoints)> = Vec::new();
+    let mut unknown_positions: Vec<String> = Vec::new();
 
     // Check if we should use cached data (only if not forcing refresh)
     let use_cached = !params.base.refresh
@@ -150,20 +890,24 @@ pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
         && db.has_data_for_week(
             params.base.season,
             params.base.week,
+            Some(league_id),
             params.base.player_names.as_ref(),
             None,
-            Some(params.projected),
+            Some(projected),
         )?;
 
-    if use_cached {
+    let side = if projected { "projected" } else { "actual" };
+    if use_cached && verbose {
         println!(
-            "Using cached player data for Season {} Week {}...",
+            "Using cached {} player data for Season {} Week {}...",
+            side,
             params.base.season.as_u16(),
             params.base.week.as_u16()
         );
-
+    }
+    if use_cached {
         // Get cached data directly from database
-        let cached_data = db.get_cached_player_data(&params.base, params.projected)?;
+        let cached_data = db.get_cached_player_data(&params.base, league_id, projected)?;
 
         // Convert cached data to PlayerPoints format with status info in parallel
         let cached_player_points: Vec<PlayerPoints> = cached_data
@@ -180,6 +924,8 @@ pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
                     is_rostered,
                     team_id,
                     team_name,
+                    played,
+                    (opponent_team, home_game),
                 )| {
                     PlayerPoints::from_cached_data(CachedPlayerData {
                         player_id,
@@ -187,13 +933,16 @@ pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
                         position,
                         points,
                         week: params.base.week,
-                        projected: params.projected,
+                        projected,
                         active,
                         injured,
                         injury_status,
                         is_rostered,
                         team_id,
                         team_name,
+                        played,
+                        opponent_team,
+                        home_game,
                     })
                 },
             )
@@ -201,14 +950,18 @@ pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
 
         player_points.extend(cached_player_points);
     } else {
-        println!(
-            "Fetching fresh player data from ESPN for Season {} Week {}...",
-            params.base.season.as_u16(),
-            params.base.week.as_u16()
-        );
+        if verbose {
+            println!(
+                "Fetching fresh {} player data from ESPN for Season {} Week {}...",
+                side,
+                params.base.season.as_u16(),
+                params.base.week.as_u16()
+            );
+        }
 
         // tarpaulin::skip - HTTP call, tested via integration tests
         let positions_clone = params.base.positions.clone();
+        let fetch_stage = stage("fetch players");
         let players_val = get_player_data(PlayerDataRequest {
             debug: params.debug,
             refresh: params.base.refresh,
@@ -219,201 +972,902 @@ pub async fn handle_player_data(params: PlayerDataParams) -> Result<()> {
             week: params.base.week,
             injury_status_filter: params.base.injury_status.clone(),
             roster_status_filter: params.base.roster_status.clone(),
+            flex: flex.clone(),
+            limit: player_query_limit(params.base.player_names.as_deref()),
+            page_size: PLAYERS_PAGE_SIZE,
         })
         .await?;
+        drop(fetch_stage);
 
-        // Deserialize directly into Vec<Player>
+        // `from_value` consumes the raw payload, so only the typed players are held from here
+        let parse_stage = stage("parse and score");
         let players: Vec<crate::espn::types::Player> = serde_json::from_value(players_val)?;
-        println!(
-            "Processing {} players and calculating fantasy points...",
-            players.len()
+        if verbose {
+            println!(
+                "Processing {} players and calculating fantasy points...",
+                players.len()
+            );
+        }
+        let has_actuals = players.iter().any(|player| {
+            select_weekly_stats(
+                player,
+                params.base.season.as_u16(),
+                params.base.week.as_u16(),
+                0,
+            )
+            .is_some()
+        });
+
+        let filtered_players = filter_and_convert_players(
+            players,
+            params.base.player_names.clone(),
+            positions_clone,
+            params.base.nfl_team,
+            flex,
         );
-        let stat_source = if params.projected { 1 } else { 0 };
+        drop(parse_stage);
 
-        // Phase 1: Store ALL players and process stats separately
-        let filtered_players =
-            filter_and_convert_players(players, params.base.player_names.clone(), positions_clone);
+        unknown_positions = unknown_position_names(&filtered_players);
+        let fetched_ids: HashSet<PlayerId> =
+            filtered_players.iter().map(|fp| fp.player_id).collect();
 
-        // First, store all players regardless of whether they have stats
-        let espn_players: Vec<crate::espn::types::Player> = filtered_players
-            .iter()
-            .map(|fp| fp.original_player.clone())
-            .collect();
-        let _ = db.update_players_from_espn(&espn_players);
+        // Opponents and home/away come from the NFL schedule, fetched once per season;
+        // without it the matchup is just left unknown
+        let pro_schedule = get_pro_schedule(params.base.season).await.ok();
+        if !projected
+            && !params.both
+            && !params.quiet
+            && week_not_played(
+                pro_schedule.as_ref(),
+                params.base.week,
+                has_actuals,
+                UtcTimestamp::now().as_secs(),
+            )
+        {
+            eprintln!(
+                "⚠ Week {} has not been played yet; use --proj for projections",
+                params.base.week
+            );
+        }
 
-        // Phase 2: Process stats for players who have them
-        let processed_data: Vec<(PlayerWeeklyStats, PlayerPoints)> = filtered_players
-            .into_par_iter()
-            .filter_map(|filtered_player| {
-                let player = filtered_player.original_player;
-                let player_id = filtered_player.player_id;
-
-                let position = if player.default_position_id < 0 {
-                    "UNKNOWN".to_string()
-                } else {
-                    Position::try_from(player.default_position_id as u8)
-                        .map(|p| p.to_string())
-                        .unwrap_or_else(|_| "UNKNOWN".to_string())
-                };
-
-                // Compute weekly stats and fantasy points only if player has stats
-                if let Ok(player_value) = serde_json::to_value(&player) {
-                    if let Some(weekly_stats) = select_weekly_stats(
-                        &player_value,
-                        params.base.season.as_u16(),
-                        params.base.week.as_u16(),
-                        stat_source,
-                    ) {
-                        let position_id = if player.default_position_id < 0 {
-                            0u8 // Default to QB position for scoring purposes
-                        } else {
-                            player.default_position_id as u8
-                        };
-                        let points =
-                            compute_points_for_week(weekly_stats, position_id, &scoring_index);
-
-                        let weekly_db_stats = PlayerWeeklyStats {
-                            player_id,
-                            season: params.base.season,
-                            week: params.base.week,
-                            projected_points: if params.projected { Some(points) } else { None },
-                            actual_points: if !params.projected {
-                                Some(points)
-                            } else {
-                                None
-                            },
-                            active: player.active,
-                            injured: player.injured,
-                            injury_status: player.injury_status.clone(),
-                            is_rostered: None, // Will be updated later when roster data is applied
-                            fantasy_team_id: None, // Will be updated later when roster data is applied
-                            fantasy_team_name: None, // Will be updated later when roster data is applied
-                            created_at: 0,           // Will be set by database
-                            updated_at: 0,           // Will be set by database
-                        };
-
-                        let player_point = PlayerPoints::from_espn_player(
-                            player_id,
-                            &player,
-                            position.clone(),
-                            points,
-                            params.base.week,
-                            params.projected,
-                        );
-
-                        Some((weekly_db_stats, player_point))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
+        // Store, score and save a chunk at a time, so a full-league pull only holds
+        // one chunk's stats and scored rows at once
+        let mut remaining = filtered_players.into_iter();
+        loop {
+            let chunk: Vec<FilteredPlayer> = remaining.by_ref().take(SCORING_CHUNK_SIZE).collect();
+            if chunk.is_empty() {
+                break;
+            }
+
+            let score_stage = stage("parse and score");
+            // Store every player, whether or not they have stats this week
+            if let Ok(aliases) =
+                db.update_players_from_espn(chunk.iter().map(|fp| &fp.original_player))
+            {
+                for alias in aliases {
+                    eprintln!(
+                        "Note: ESPN renamed player {} from {} to {}; the old name is kept as an alias",
+                        alias.player_id,
+                        alias.alias,
+                        alias.name.unwrap_or_default()
+                    );
                 }
-            })
-            .collect();
+            }
+            let scored: Vec<ScoredPlayer> = chunk
+                .par_iter()
+                .filter_map(|filtered_player| {
+                    score_player(
+                        filtered_player,
+                        params,
+                        projected,
+                        scoring_index,
+                        pro_schedule.as_ref(),
+                    )
+                })
+                .collect();
+            drop(chunk);
+            drop(score_stage);
 
-        // Phase 3: Collect PlayerPoints first
-        for (_weekly_db_stats, player_point) in &processed_data {
-            player_points.push(player_point.clone());
+            let _store_stage = stage("store");
+            player_points.extend(store_scored_players(
+                db,
+                league_id,
+                params,
+                scored,
+                roster_data,
+                verbose,
+            ));
         }
 
-        // Store processed_data for use outside the else block
-        stats_to_save = processed_data;
+        if verbose {
+            warn_duplicate_player_names(db, &fetched_ids);
+        }
     }
 
-    println!(
-        "✓ Found {} players with fantasy points",
-        player_points.len()
-    );
-
-    // Check roster status for players using pre-fetched data BEFORE saving to database
-    update_player_points_with_roster_data(
-        &mut player_points,
-        roster_data.as_ref(),
-        true, // verbose
-    );
+    if verbose {
+        println!(
+            "✓ Found {} players with fantasy points",
+            player_points.len()
+        );
+        report_unknown_positions(&unknown_positions);
+    }
 
-    // Now save to database with correct roster information
-    if !use_cached {
-        for (mut weekly_db_stats, _player_point) in stats_to_save {
-            // Find the corresponding updated player_points to get roster info
-            if let Some(updated_player) = player_points
-                .iter()
-                .find(|p| p.id == weekly_db_stats.player_id)
-            {
-                weekly_db_stats.is_rostered = updated_player.is_rostered;
-                weekly_db_stats.fantasy_team_id = updated_player.team_id;
-                weekly_db_stats.fantasy_team_name = updated_player.team_name.clone();
-            }
-            let _ = db.merge_weekly_stats(&weekly_db_stats);
+    if use_cached {
+        update_player_points_with_roster_data(&mut player_points, roster_data, verbose);
+    } else if verbose && !player_points.is_empty() {
+        // Fresh rows had roster status applied chunk by chunk before they were stored
+        match roster_data {
+            Some(_) => println!("✓ Roster status updated"),
+            None => println!("⚠ No roster data available"),
         }
     }
 
     // Update database with roster information for ALL players (not just those with points)
     // Only do this when not using cached data, since cached data already has current roster info
+    let _store_stage = stage("store");
     if !use_cached {
-        if let Some(ref league_data) = roster_data {
+        if let Some(league_data) = roster_data {
             match db.update_all_players_roster_info(
                 league_data,
+                league_id,
                 params.base.season,
                 params.base.week,
             ) {
-                Ok(count) => println!("✓ Updated roster info for {} players", count),
-                Err(e) => println!("⚠ Warning: Could not update roster info: {}", e),
+                Ok(count) if verbose => {
+                    println!("✓ Updated roster info for {} players", count)
+                }
+                Err(e) if verbose => println!("⚠ Warning: Could not update roster info: {}", e),
+                _ => {}
             }
         }
     }
 
-    // Apply client-side filtering for specific injury statuses, roster status, and fantasy team
-    if params.base.injury_status.is_some()
-        || params.base.roster_status.is_some()
-        || params.base.fantasy_team_filter.is_some()
-    {
-        apply_status_filters(
-            &mut player_points,
-            params.base.injury_status.as_ref(),
-            params.base.roster_status.as_ref(),
-            params.base.fantasy_team_filter.as_ref(),
+    Ok(player_points)
+}
+
+/// Whether `week` hasn't been played yet as of `now` (seconds)
+///
+/// Goes by the NFL schedule when it has the week's games; otherwise by whether
+/// ESPN's payload had actual stats (`has_actuals`) for anyone that week.
+pub fn week_not_played(
+    pro_schedule: Option<&ProSchedule>,
+    week: Week,
+    has_actuals: bool,
+    now: u64,
+) -> bool {
+    match pro_schedule.and_then(|schedule| schedule.first_kickoff(week)) {
+        Some(kickoff) => now < kickoff,
+        None => !has_actuals,
+    }
+}
+
+/// Show each fantasy team under its latest recorded name, so rows from weeks before
+/// a rename don't carry the old one
+fn use_current_team_names(
+    db: &PlayerDatabase,
+    league_id: LeagueId,
+    params: &PlayerDataParams,
+    players: &mut [PlayerPoints],
+) -> Result<()> {
+    let current_names = db.get_current_team_names(league_id, params.base.season)?;
+    for player in players {
+        if let Some(name) = player.team_id.and_then(|id| current_names.get(&id)) {
+            player.team_name = Some(name.clone());
+        }
+    }
+    Ok(())
+}
+
+/// JSON output row carrying the per-stat breakdown used for `--explain`
+#[derive(Serialize)]
+struct PlayerPointsWithBreakdown<'a> {
+    #[serde(flatten)]
+    player: &'a PlayerPoints,
+    breakdown: &'a [PointsContribution],
+}
+
+/// Stored ESPN news headlines for a set of players
+type NewsByPlayer = HashMap<PlayerId, PlayerHeadline>;
+
+/// JSON output row with the player's stored news headline (for `--with-news`)
+#[derive(Serialize)]
+struct WithNews<'a, T: Serialize> {
+    #[serde(flatten)]
+    row: &'a T,
+    news: Option<&'a PlayerHeadline>,
+}
+
+/// Print output rows as JSON, adding a `news` field to each row when `news` is given
+fn print_player_rows<T: Serialize>(
+    rows: &[T],
+    player_id: impl Fn(&T) -> PlayerId,
+    notes: Option<&NotesByPlayer>,
+    news: Option<&NewsByPlayer>,
+    json_lines: bool,
+) -> Result<()> {
+    let Some(news) = news else {
+        return print_json_rows(rows, player_id, notes, json_lines);
+    };
+    let rows: Vec<WithNews<'_, T>> = rows
+        .iter()
+        .map(|row| WithNews {
+            row,
+            news: news.get(&player_id(row)),
+        })
+        .collect();
+    print_json_rows(&rows, |row| player_id(row.row), notes, json_lines)
+}
+
+/// Column names of the `--csv` header row
+const PLAYER_POINTS_CSV_HEADER: [&str; 9] = [
+    "id",
+    "name",
+    "position",
+    "week",
+    "projected",
+    "points",
+    "injury_status",
+    "is_rostered",
+    "team_name",
+];
+
+/// Write players as CSV with a header row; unknown values are left empty
+fn write_player_points_csv(writer: impl Write, rows: &[PlayerPoints]) -> Result<usize> {
+    write_csv_rows(writer, &PLAYER_POINTS_CSV_HEADER, rows, |player| {
+        vec![
+            player.id.to_string(),
+            player.name.clone(),
+            player.position.clone(),
+            player.week.to_string(),
+            player.projected.to_string(),
+            format!("{:.2}", player.points),
+            player
+                .injury_status
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            player
+                .is_rostered
+                .map(|rostered| rostered.to_string())
+                .unwrap_or_default(),
+            player.team_name.clone().unwrap_or_default(),
+        ]
+    })
+}
+
+/// Trailing free-text column for `--show-notes` and `--with-news`, if either is on
+fn annotation_column(notes: Option<&NotesByPlayer>, news: Option<&NewsByPlayer>) -> Option<Column> {
+    match (notes.is_some(), news.is_some()) {
+        (true, true) => Some(Column::rest("Note / News")),
+        (true, false) => Some(Column::rest("Note")),
+        (false, true) => Some(Column::rest("News")),
+        (false, false) => None,
+    }
+}
+
+/// The player's latest note and news headline, for the [`annotation_column`]
+fn annotation_cell(
+    notes: Option<&NotesByPlayer>,
+    news: Option<&NewsByPlayer>,
+    player_id: PlayerId,
+) -> Option<String> {
+    if notes.is_none() && news.is_none() {
+        return None;
+    }
+    let note = notes
+        .map(|notes| {
+            latest_note_suffix(notes, player_id)
+                .trim_start()
+                .to_string()
+        })
+        .filter(|note| !note.is_empty());
+    let headline = news
+        .and_then(|news| news.get(&player_id))
+        .map(|news| match news.published {
+            Some(published) => format!("📰 {} ({})", news.headline, format_utc_date(published)),
+            None => format!("📰 {}", news.headline),
+        });
+    Some(
+        note.into_iter()
+            .chain(headline)
+            .collect::<Vec<_>>()
+            .join(" | "),
+    )
+}
+
+/// JSON output row carrying the raw weekly actuals used for `--sparkline`
+#[derive(Serialize)]
+struct PlayerPointsWithTrend<'a> {
+    #[serde(flatten)]
+    player: &'a PlayerPoints,
+    recent_points: &'a [Option<f64>],
+}
+
+/// Projected and actual points for one player in the same week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct PointsComparison {
+    pub id: PlayerId,
+    pub name: String,
+    pub position: String,
+    pub week: Week,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub injured: Option<bool>,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub injury_status: Option<InjuryStatus>,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub is_rostered: Option<bool>,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub team_id: Option<u32>,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub team_name: Option<String>,
+    #[serde(skip_serializing_if = "skip_none")]
+    pub played: Option<bool>,
+    pub projected_points: Option<f64>,
+    pub actual_points: Option<f64>,
+    /// Actual minus projected; `None` unless both are known
+    pub delta: Option<f64>,
+}
+
+impl PointsComparison {
+    fn sort_fields(&self) -> SortFields<'_> {
+        SortFields {
+            points: self.actual_points.or(self.projected_points),
+            delta: self.delta,
+            name: &self.name,
+            position: &self.position,
+            team_name: self.team_name.as_deref(),
+        }
+    }
+
+    fn from_player(player: &PlayerPoints) -> Self {
+        Self {
+            id: player.id,
+            name: player.name.clone(),
+            position: player.position.clone(),
+            week: player.week,
+            injured: player.injured,
+            injury_status: player.injury_status.clone(),
+            is_rostered: player.is_rostered,
+            team_id: player.team_id,
+            team_name: player.team_name.clone(),
+            played: player.played,
+            projected_points: None,
+            actual_points: None,
+            delta: None,
+        }
+    }
+}
+
+/// Join actual and projected points by player
+///
+/// Status and roster details come from the actual row when a player has both.
+pub fn combine_projected_actual(
+    actual: Vec<PlayerPoints>,
+    projected: Vec<PlayerPoints>,
+) -> Vec<PointsComparison> {
+    let mut rows: Vec<PointsComparison> = Vec::with_capacity(actual.len().max(projected.len()));
+    let mut index: HashMap<PlayerId, usize> = HashMap::new();
+
+    for player in &actual {
+        index.insert(player.id, rows.len());
+        rows.push(PointsComparison {
+            actual_points: Some(player.points),
+            ..PointsComparison::from_player(player)
+        });
+    }
+    for player in &projected {
+        match index.get(&player.id) {
+            Some(&i) => rows[i].projected_points = Some(player.points),
+            None => rows.push(PointsComparison {
+                projected_points: Some(player.points),
+                ..PointsComparison::from_player(player)
+            }),
+        }
+    }
+
+    for row in &mut rows {
+        row.delta = row
+            .actual_points
+            .zip(row.projected_points)
+            .map(|(actual, projected)| actual - projected);
+    }
+    rows
+}
+
+/// Order comparison rows, by points first so rows tied on `sort` stay in points order
+pub fn sort_comparisons(rows: &mut [PointsComparison], sort: ComparisonSort, reverse: bool) {
+    sort_player_rows(
+        rows,
+        ComparisonSort::Points,
+        false,
+        PointsComparison::sort_fields,
+    );
+    sort_player_rows(rows, sort, reverse, PointsComparison::sort_fields);
+}
+
+/// What a player-data row is sorted on
+pub struct SortFields<'a> {
+    pub points: Option<f64>,
+    pub delta: Option<f64>,
+    pub name: &'a str,
+    pub position: &'a str,
+    pub team_name: Option<&'a str>,
+}
+
+/// Stable sort of player rows for output
+///
+/// Points and deltas run highest first, names and teams A to Z and positions QB,
+/// RB, WR, TE, K, D/ST; `reverse` flips that. Rows missing the value (a free
+/// agent's team, a delta without both sides) come last either way.
+pub fn sort_player_rows<T>(
+    rows: &mut [T],
+    sort: ComparisonSort,
+    reverse: bool,
+    fields: impl Fn(&T) -> SortFields<'_>,
+) {
+    use std::cmp::Ordering;
+
+    // Rows without a value go after those with one, whichever way the rest runs
+    fn missing_last<V>(a: Option<V>, b: Option<V>, order: impl Fn(V, V) -> Ordering) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => order(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+    let position_rank = |position: &str| {
+        POSITION_GROUP_ORDER
+            .iter()
+            .position(|p| *p == position)
+            .unwrap_or(POSITION_GROUP_ORDER.len())
+    };
+    let directed = |order: Ordering| if reverse { order.reverse() } else { order };
+    let highest_first = |a: f64, b: f64| directed(b.partial_cmp(&a).unwrap_or(Ordering::Equal));
+    let compare = |a: SortFields, b: SortFields| match sort {
+        ComparisonSort::Points => missing_last(a.points, b.points, highest_first),
+        ComparisonSort::Delta => missing_last(a.delta, b.delta, highest_first),
+        ComparisonSort::Name => directed(a.name.to_lowercase().cmp(&b.name.to_lowercase())),
+        ComparisonSort::Position => {
+            directed(position_rank(a.position).cmp(&position_rank(b.position)))
+        }
+        ComparisonSort::Team => missing_last(a.team_name, b.team_name, |a, b| {
+            directed(a.to_lowercase().cmp(&b.to_lowercase()))
+        }),
+    };
+    rows.sort_by(|a, b| compare(fields(a), fields(b)));
+}
+
+/// `--both`: print projected and actual points side by side
+fn print_points_comparisons(
+    db: &PlayerDatabase,
+    params: &PlayerDataParams,
+    rows: Vec<PointsComparison>,
+) -> Result<()> {
+    if let Some(PlayerDataGroupBy::Team) = params.group_by {
+        let groups = group_by_team(rows, |r| (r.team_id, r.team_name.as_deref()), params.top);
+        return print_team_comparisons(db, &groups, params.base.as_json);
+    }
+
+    let notes = if params.show_notes {
+        let player_ids: Vec<_> = rows.iter().map(|r| r.id).collect();
+        Some(db.get_notes_for_players(&player_ids)?)
+    } else {
+        None
+    };
+    let news = if params.with_news {
+        let player_ids: Vec<_> = rows.iter().map(|r| r.id).collect();
+        Some(db.get_player_news(&player_ids)?)
+    } else {
+        None
+    };
+
+    if params.base.as_json {
+        print_player_rows(
+            &rows,
+            |row| row.id,
+            notes.as_ref(),
+            news.as_ref(),
+            params.base.json_lines,
+        )?;
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.2}", points),
+        None => "-".to_string(),
+    };
+    let names = display_names(
+        db,
+        rows.iter()
+            .map(|r| (r.id, r.name.as_str(), r.position.as_str())),
+    );
+    let mut columns = player_columns();
+    columns.extend([
+        Column::fixed("Proj", Align::Right),
+        Column::fixed("Actual", Align::Right),
+        Column::fixed("Delta", Align::Right),
+    ]);
+    columns.extend(annotation_column(notes.as_ref(), news.as_ref()));
+    let mut table = Table::new(columns);
+
+    for row in rows {
+        let mut cells = player_cells(
+            row.id,
+            names.get(&row.id).unwrap_or(&row.name),
+            &row.position,
+            row.week,
+            status_label(row.injury_status.as_ref(), row.injured),
+            roster_label(row.is_rostered, row.team_name.as_deref()),
+        );
+        cells.push(format_points(row.projected_points));
+        cells.push(if row.played == Some(false) {
+            "DNP".to_string()
+        } else {
+            format_points(row.actual_points)
+        });
+        cells.push(
+            row.delta
+                .map(|delta| format!("{:+.2}", delta))
+                .unwrap_or_else(|| "-".to_string()),
         );
+        cells.extend(annotation_cell(notes.as_ref(), news.as_ref(), row.id));
+        table.add_row(cells);
+    }
+    table.print();
+
+    Ok(())
+}
+
+/// One fantasy team's players, or the free agent pool, for `--group-by team`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TeamGroup<T> {
+    /// `None` for free agents
+    pub team_id: Option<u32>,
+    /// `None` for free agents
+    pub team_name: Option<String>,
+    pub players: Vec<T>,
+}
+
+impl<T> TeamGroup<T> {
+    /// Section heading: the team name, or "Free Agents"
+    pub fn label(&self) -> &str {
+        self.team_name.as_deref().unwrap_or("Free Agents")
+    }
+}
+
+/// Section rows by fantasy team name, keeping their order within each team
+///
+/// Teams are ordered by name with the free agents (rows without a team name)
+/// last, and each team is cut to its first `top` rows.
+pub fn group_by_team<T>(
+    rows: Vec<T>,
+    team: impl Fn(&T) -> (Option<u32>, Option<&str>),
+    top: Option<usize>,
+) -> Vec<TeamGroup<T>> {
+    let mut groups: Vec<TeamGroup<T>> = Vec::new();
+    for row in rows {
+        let (team_id, team_name) = team(&row);
+        let team_name = team_name.map(str::to_string);
+        let index = match groups.iter().position(|g| g.team_name == team_name) {
+            Some(index) => index,
+            None => {
+                groups.push(TeamGroup {
+                    team_id,
+                    team_name,
+                    players: Vec::new(),
+                });
+                groups.len() - 1
+            }
+        };
+        groups[index].players.push(row);
     }
 
-    // Sort descending by points
-    player_points.sort_by(|a, b| {
-        b.points
-            .partial_cmp(&a.points)
-            .unwrap_or(std::cmp::Ordering::Equal)
+    groups.sort_by(|a, b| match (&a.team_name, &b.team_name) {
+        (Some(a), Some(b)) => a.to_lowercase().cmp(&b.to_lowercase()),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
     });
+    if let Some(top) = top {
+        for group in &mut groups {
+            group.players.truncate(top);
+        }
+    }
+    groups
+}
 
-    if params.base.as_json {
-        println!("{}", serde_json::to_string_pretty(&player_points)?); // tarpaulin::skip
-    } else {
-        for player in player_points {
-            // tarpaulin::skip - console output
-            let status_str = match (&player.injury_status, player.injured) {
-                (Some(status), _) => format!("[{}]", status),
-                (None, Some(true)) => "[Injured]".to_string(),
-                (None, Some(false)) => "[Active]".to_string(),
-                (None, None) => "[Active]".to_string(),
-            };
-
-            let roster_str = match (player.is_rostered, &player.team_name) {
-                (Some(true), Some(team_name)) => format!(" ({})", team_name),
-                (Some(true), None) => "(Rostered)".to_string(),
-                (Some(false), _) => "(FA)".to_string(),
-                (None, _) => "".to_string(),
-            };
+/// `--group-by team` JSON: a team's players with their point subtotal
+#[derive(Serialize)]
+struct TeamPointsOutput<'a> {
+    team_id: Option<u32>,
+    team_name: Option<&'a str>,
+    points: f64,
+    players: &'a [PlayerPoints],
+}
 
-            println!(
-                "{} {} ({}) [week {}] {} {} {:.2}",
-                player.id.as_i64(),
-                player.name,
-                player.position,
-                player.week.as_u16(),
-                status_str,
-                roster_str,
-                player.points,
+/// `--group-by team --both` JSON: a team's players with projected and actual subtotals
+#[derive(Serialize)]
+struct TeamComparisonOutput<'a> {
+    team_id: Option<u32>,
+    team_name: Option<&'a str>,
+    projected_points: f64,
+    actual_points: f64,
+    players: &'a [PointsComparison],
+}
+
+/// Print player points sectioned by fantasy team, each with its subtotal
+fn print_team_points(
+    db: &PlayerDatabase,
+    groups: &[TeamGroup<PlayerPoints>],
+    as_json: bool,
+) -> Result<()> {
+    let subtotal = |group: &TeamGroup<PlayerPoints>| group.players.iter().map(|p| p.points).sum();
+
+    if as_json {
+        let output: Vec<TeamPointsOutput> = groups
+            .iter()
+            .map(|group| TeamPointsOutput {
+                team_id: group.team_id,
+                team_name: group.team_name.as_deref(),
+                points: subtotal(group),
+                players: &group.players,
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    let names = display_names(
+        db,
+        groups
+            .iter()
+            .flat_map(|g| &g.players)
+            .map(|p| (p.id, p.name.as_str(), p.position.as_str())),
+    );
+    for group in groups {
+        let points: f64 = subtotal(group);
+        println!(
+            "== {} ({} players, {:.2} pts) ==",
+            group.label(),
+            group.players.len(),
+            points
+        );
+        let mut columns = player_columns();
+        columns.push(Column::fixed("Points", Align::Right));
+        let mut table = Table::new(columns);
+        for player in &group.players {
+            let mut cells = player_cells(
+                player.id,
+                names.get(&player.id).unwrap_or(&player.name),
+                &player.position,
+                player.week,
+                status_label(player.injury_status.as_ref(), player.injured),
+                roster_label(player.is_rostered, player.team_name.as_deref()),
             );
+            cells.push(if player.is_dnp() {
+                "DNP".to_string()
+            } else {
+                format!("{:.2}", player.points)
+            });
+            table.add_row(cells);
         }
+        table.print();
+        println!();
     }
+    Ok(())
+}
 
+/// Print projected and actual points sectioned by fantasy team, each with both subtotals
+fn print_team_comparisons(
+    db: &PlayerDatabase,
+    groups: &[TeamGroup<PointsComparison>],
+    as_json: bool,
+) -> Result<()> {
+    let subtotals = |group: &TeamGroup<PointsComparison>| {
+        group
+            .players
+            .iter()
+            .fold((0.0, 0.0), |(projected, actual), row| {
+                (
+                    projected + row.projected_points.unwrap_or(0.0),
+                    actual + row.actual_points.unwrap_or(0.0),
+                )
+            })
+    };
+
+    if as_json {
+        let output: Vec<TeamComparisonOutput> = groups
+            .iter()
+            .map(|group| {
+                let (projected_points, actual_points) = subtotals(group);
+                TeamComparisonOutput {
+                    team_id: group.team_id,
+                    team_name: group.team_name.as_deref(),
+                    projected_points,
+                    actual_points,
+                    players: &group.players,
+                }
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.2}", points),
+        None => "-".to_string(),
+    };
+    let names = display_names(
+        db,
+        groups
+            .iter()
+            .flat_map(|g| &g.players)
+            .map(|r| (r.id, r.name.as_str(), r.position.as_str())),
+    );
+    for group in groups {
+        let (projected, actual) = subtotals(group);
+        println!(
+            "== {} ({} players, proj {:.2}, actual {:.2}, delta {:+.2}) ==",
+            group.label(),
+            group.players.len(),
+            projected,
+            actual,
+            actual - projected
+        );
+        let mut columns = player_columns();
+        columns.extend([
+            Column::fixed("Proj", Align::Right),
+            Column::fixed("Actual", Align::Right),
+            Column::fixed("Delta", Align::Right),
+        ]);
+        let mut table = Table::new(columns);
+        for row in &group.players {
+            let mut cells = player_cells(
+                row.id,
+                names.get(&row.id).unwrap_or(&row.name),
+                &row.position,
+                row.week,
+                status_label(row.injury_status.as_ref(), row.injured),
+                roster_label(row.is_rostered, row.team_name.as_deref()),
+            );
+            cells.push(format_points(row.projected_points));
+            cells.push(if row.played == Some(false) {
+                "DNP".to_string()
+            } else {
+                format_points(row.actual_points)
+            });
+            cells.push(
+                row.delta
+                    .map(|delta| format!("{:+.2}", delta))
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+            table.add_row(cells);
+        }
+        table.print();
+        println!();
+    }
     Ok(())
 }
+
+/// Leading columns shared by the player tables
+fn player_columns() -> Vec<Column> {
+    vec![
+        Column::fixed("ID", Align::Right),
+        Column::flex("Name", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Week", Align::Right),
+        Column::fixed("Status", Align::Left),
+        Column::fixed("Roster", Align::Left),
+    ]
+}
+
+/// Cells for [`player_columns`]
+fn player_cells(
+    id: PlayerId,
+    name: &str,
+    position: &str,
+    week: Week,
+    status: String,
+    roster: String,
+) -> Vec<String> {
+    vec![
+        id.as_i64().to_string(),
+        name.to_string(),
+        position.to_string(),
+        week.as_u16().to_string(),
+        status,
+        roster,
+    ]
+}
+
+/// Opponent cell: "KC" at home, "@KC" away, "--" on a bye or when unknown
+fn opponent_label(opponent: Option<&str>, home_game: Option<bool>) -> String {
+    match (opponent, home_game) {
+        (Some(opponent), Some(false)) => format!("@{}", opponent),
+        (Some(opponent), _) => opponent.to_string(),
+        (None, _) => "--".to_string(),
+    }
+}
+
+/// Names for text output, with a "(POS, TEAM)" suffix where two players in the
+/// result set share a normalized name
+fn display_names<'a>(
+    db: &PlayerDatabase,
+    players: impl Iterator<Item = (PlayerId, &'a str, &'a str)>,
+) -> HashMap<PlayerId, String> {
+    let players: Vec<(PlayerId, &str, &str)> = players.collect();
+
+    let mut name_counts: HashMap<String, usize> = HashMap::new();
+    for (_, name, _) in &players {
+        *name_counts.entry(normalize_player_name(name)).or_default() += 1;
+    }
+    let colliding: Vec<PlayerId> = players
+        .iter()
+        .filter(|(_, name, _)| name_counts[&normalize_player_name(name)] > 1)
+        .map(|(id, _, _)| *id)
+        .collect();
+    let teams = if colliding.is_empty() {
+        HashMap::new()
+    } else {
+        db.get_player_teams(&colliding).unwrap_or_default()
+    };
+
+    let entries: Vec<NameEntry<'_, PlayerId>> = players
+        .iter()
+        .map(|(id, name, position)| NameEntry {
+            id: *id,
+            name,
+            position,
+            team: teams.get(id).map(String::as_str),
+        })
+        .collect();
+    disambiguate_names(&entries)
+}
+
+/// Warn when freshly stored players share a name and position with another player ID
+fn warn_duplicate_player_names(db: &PlayerDatabase, player_ids: &HashSet<PlayerId>) {
+    let Ok(duplicates) = db.find_duplicate_player_names() else {
+        return;
+    };
+    for duplicate in duplicates
+        .iter()
+        .filter(|d| d.player_ids.iter().any(|id| player_ids.contains(id)))
+    {
+        let ids: Vec<String> = duplicate
+            .player_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        println!(
+            "⚠ Warning: players {} share the name {} ({})",
+            ids.join(", "),
+            duplicate.name,
+            duplicate.position
+        );
+    }
+}
+
+/// Injury status shown in text output, e.g. "Questionable"
+pub(crate) fn status_label(injury_status: Option<&InjuryStatus>, injured: Option<bool>) -> String {
+    match (injury_status, injured) {
+        (Some(status), _) => status.to_string(),
+        (None, Some(true)) => "Injured".to_string(),
+        (None, Some(false)) => "Active".to_string(),
+        (None, None) => "Active".to_string(),
+    }
+}
+
+/// Fantasy roster shown in text output, e.g. "Team Alpha" or "FA"
+pub(crate) fn roster_label(is_rostered: Option<bool>, team_name: Option<&str>) -> String {
+    match (is_rostered, team_name) {
+        (Some(true), Some(team_name)) => team_name.to_string(),
+        (Some(true), None) => "Rostered".to_string(),
+        (Some(false), _) => "FA".to_string(),
+        (None, _) => "".to_string(),
+    }
+}
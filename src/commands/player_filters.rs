@@ -2,11 +2,12 @@
 
 use crate::{
     cli::types::{
-        filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter},
-        position::Position,
+        filters::{FantasyTeamFilter, InjuryStatusFilter, NflTeam, RosterStatusFilter},
+        position::{FlexEligibility, Position},
     },
     espn::types::{InjuryStatus, Player, PlayerPoints},
-    PlayerId,
+    storage::PlayerDatabase,
+    LeagueId, PlayerId, Result, Season,
 };
 use rayon::prelude::*;
 
@@ -17,10 +18,15 @@ pub struct FilteredPlayer {
 }
 
 /// Shared player filtering logic used by both player-data and projection-analysis commands
+///
+/// `flex` is what a FLEX filter covers in the league (see [`LeagueSettings::flex_eligibility`]).
+/// `nfl_team` keeps team defenses too, matched through [`Player::nfl_team_id`].
 pub fn filter_and_convert_players(
     players: Vec<Player>,
     player_names: Option<Vec<String>>,
     position_filter: Option<Vec<Position>>,
+    nfl_team: Option<NflTeam>,
+    flex: &FlexEligibility,
 ) -> Vec<FilteredPlayer> {
     players
         .into_par_iter()
@@ -49,27 +55,25 @@ pub fn filter_and_convert_players(
 
             // Apply position filtering on the client side to ensure accuracy
             if let Some(positions) = &position_filter {
-                let player_position = (player.default_position_id >= 0)
-                    .then(|| Position::try_from(player.default_position_id as u8).ok())
-                    .flatten();
-
-                let Some(pos) = player_position else {
+                let Some(pos) = player.position() else {
                     return None; // Player has no valid position, exclude it
                 };
 
-                let matches = positions.iter().any(|filter_pos| {
-                    if *filter_pos == Position::FLEX {
-                        filter_pos.get_all_position_ids().contains(&pos.to_u8())
-                    } else {
-                        *filter_pos == pos
-                    }
-                });
+                let matches = positions
+                    .iter()
+                    .any(|filter_pos| filter_pos.accepts(pos, flex));
 
                 if !matches {
                     return None;
                 }
             }
 
+            if let Some(team) = nfl_team {
+                if player.nfl_team_id() != Some(team.id()) {
+                    return None;
+                }
+            }
+
             // Preserve original ESPN player IDs (including negative IDs for D/ST teams)
             let player_id = PlayerId::new(player.id);
 
@@ -132,10 +136,14 @@ pub fn matches_roster_filter(player: &PlayerPoints, filter: &RosterStatusFilter)
 ///
 /// This function provides consistent fantasy team filtering logic across commands.
 /// For team name filtering, it performs case-insensitive partial matching against
-/// both the full team name and the 3-letter team abbreviation stored by ESPN.
+/// the player's current team name. Abbreviations and earlier team names are matched
+/// by resolving the filter first with [`resolve_fantasy_team_filter`].
 pub fn matches_fantasy_team_filter(player: &PlayerPoints, filter: &FantasyTeamFilter) -> bool {
     match filter {
         FantasyTeamFilter::Id(team_id) => player.team_id == Some(*team_id),
+        FantasyTeamFilter::Ids(team_ids) => player
+            .team_id
+            .is_some_and(|team_id| team_ids.contains(&team_id)),
         FantasyTeamFilter::Name(filter_name) => {
             let filter_lower = filter_name.to_lowercase();
 
@@ -146,13 +154,31 @@ pub fn matches_fantasy_team_filter(player: &PlayerPoints, filter: &FantasyTeamFi
                 }
             }
 
-            // Note: ESPN's 3-letter abbreviations would need to be stored separately
-            // For now, we only match against the full team name
             false
         }
     }
 }
 
+/// Turn a team name filter into the IDs of every team that has gone by a matching
+/// name (or abbreviation) this season, so renames don't hide older weeks
+///
+/// Name filters that match nothing in the history, e.g. before any rosters have been
+/// stored, and ID filters are returned unchanged.
+pub fn resolve_fantasy_team_filter(
+    db: &PlayerDatabase,
+    league_id: LeagueId,
+    season: Season,
+    filter: &FantasyTeamFilter,
+) -> Result<FantasyTeamFilter> {
+    if let FantasyTeamFilter::Name(name) = filter {
+        let team_ids = db.find_team_ids_by_name(league_id, season, name)?;
+        if !team_ids.is_empty() {
+            return Ok(FantasyTeamFilter::Ids(team_ids));
+        }
+    }
+    Ok(filter.clone())
+}
+
 /// Apply injury status filter to a collection of PlayerPoints
 ///
 /// # Examples
@@ -244,6 +270,9 @@ mod tests {
             is_rostered,
             team_id: None,
             team_name: None,
+            played: None,
+            opponent_team: None,
+            home_game: None,
         }
     }
 
@@ -311,6 +340,20 @@ mod tests {
         assert!(matches_injury_filter(&out_player, &InjuryStatusFilter::Out));
     }
 
+    #[test]
+    fn test_unavailable_non_injury_statuses_count_as_injured() {
+        for status in [
+            InjuryStatus::Suspension,
+            InjuryStatus::PUP,
+            InjuryStatus::NonFootballInjury,
+            InjuryStatus::Unknown,
+        ] {
+            let player = create_test_player("Player", Some(false), Some(status), None);
+            assert!(matches_injury_filter(&player, &InjuryStatusFilter::Injured));
+            assert!(!matches_injury_filter(&player, &InjuryStatusFilter::Active));
+        }
+    }
+
     #[test]
     fn test_matches_roster_filter() {
         let rostered_player = create_test_player("Rostered Player", None, None, Some(true));
@@ -387,6 +430,9 @@ mod tests {
             is_rostered: Some(true),
             team_id: Some(1),
             team_name: Some("Kenny Rogers' Toasters".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         };
 
         let player_on_team_2 = PlayerPoints {
@@ -402,6 +448,9 @@ mod tests {
             is_rostered: Some(true),
             team_id: Some(2),
             team_name: Some("Other Team".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         };
 
         let team_1_filter = FantasyTeamFilter::Id(1);
@@ -433,6 +482,17 @@ mod tests {
             &player_on_team_2,
             &team_3_filter
         ));
+
+        // A resolved name filter matches any of its teams
+        let resolved_filter = FantasyTeamFilter::Ids(vec![2, 3]);
+        assert!(!matches_fantasy_team_filter(
+            &player_on_team_1,
+            &resolved_filter
+        ));
+        assert!(matches_fantasy_team_filter(
+            &player_on_team_2,
+            &resolved_filter
+        ));
     }
 
     #[test]
@@ -450,6 +510,9 @@ mod tests {
             is_rostered: Some(true),
             team_id: Some(1),
             team_name: Some("Kenny Rogers' Toasters".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         };
 
         let player_other_team = PlayerPoints {
@@ -465,6 +528,9 @@ mod tests {
             is_rostered: Some(true),
             team_id: Some(2),
             team_name: Some("Different Team Name".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         };
 
         // Test partial matching (case-insensitive)
@@ -0,0 +1,233 @@
+//! Playoff odds command implementation
+//!
+//! Reads the league schedule for current standings and the remaining
+//! regular-season matchups, then simulates the rest of the season to estimate
+//! each team's playoff probability and seed distribution.
+
+use crate::{
+    cli::{
+        html::{HtmlReport, HtmlSection},
+        render::{Align, Column, Table},
+    },
+    espn::{
+        cache_settings::load_or_fetch_league_settings,
+        http::get_league_schedule,
+        playoffs::{simulate_playoff_odds, PlayoffOdds, PlayoffSimInput, DEFAULT_PLAYOFF_TEAMS},
+        types::team_display_name,
+    },
+    LeagueId, Result, Season,
+};
+
+use super::league_data::{resolve_league_id, validate_season};
+use rand::{rngs::StdRng, SeedableRng};
+use serde::Serialize;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Team name column limits for the odds table
+const TEAM_MIN_WIDTH: usize = 10;
+const TEAM_MAX_WIDTH: usize = 24;
+
+/// Configuration for the playoff odds simulation
+#[derive(Debug)]
+pub struct PlayoffOddsParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub iterations: u32,
+    /// Overrides the league's playoff team count
+    pub playoff_teams: Option<usize>,
+    /// Fixed RNG seed for reproducible results
+    pub seed: Option<u64>,
+    pub refresh: bool,
+    pub as_json: bool,
+    /// Also write the standings and odds as an HTML page to share
+    pub share: Option<PathBuf>,
+}
+
+/// Playoff odds for one team, with its display name
+#[derive(Debug, Serialize)]
+struct NamedOdds<'a> {
+    team_name: String,
+    #[serde(flatten)]
+    odds: &'a PlayoffOdds,
+}
+
+/// JSON output for the playoff odds command
+#[derive(Debug, Serialize)]
+struct PlayoffOddsOutput<'a> {
+    season: Season,
+    iterations: u32,
+    playoff_teams: usize,
+    remaining_games: usize,
+    teams: Vec<NamedOdds<'a>>,
+}
+
+/// Handle the playoff odds command
+pub async fn handle_playoff_odds(params: PlayoffOddsParams) -> Result<()> {
+    let league_id = resolve_league_id(params.league_id)?;
+    validate_season(league_id, params.season).await?;
+
+    if !params.as_json {
+        println!("Loading league schedule...");
+    }
+    let schedule = get_league_schedule(league_id, params.season, params.refresh).await?;
+    let settings = load_or_fetch_league_settings(league_id, false, params.season).await?;
+    let schedule_settings = &settings.schedule_settings;
+    let playoff_teams = params
+        .playoff_teams
+        .or(schedule_settings.playoff_team_count.map(|n| n as usize))
+        .unwrap_or(DEFAULT_PLAYOFF_TEAMS);
+
+    let input = PlayoffSimInput::from_schedule(
+        &schedule,
+        schedule_settings.matchup_period_count,
+        playoff_teams,
+    );
+    let mut rng = match params.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_os_rng(),
+    };
+    let odds = simulate_playoff_odds(&input, params.iterations, &mut rng);
+
+    let team_names = schedule.team_names();
+
+    if let Some(path) = &params.share {
+        share_report(&params, &input, &odds, &team_names).write(path)?;
+    }
+
+    if params.as_json {
+        let output = PlayoffOddsOutput {
+            season: params.season,
+            iterations: params.iterations,
+            playoff_teams: input.playoff_teams,
+            remaining_games: input.remaining.len(),
+            teams: odds
+                .iter()
+                .map(|odds| NamedOdds {
+                    team_name: team_name(&team_names, odds.team_id),
+                    odds,
+                })
+                .collect(),
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_odds(&params, &input, &odds, &team_names);
+    if let Some(path) = &params.share {
+        println!();
+        println!("✓ Wrote share report to {}", path.display());
+    }
+    Ok(())
+}
+
+fn team_name(team_names: &HashMap<u32, String>, team_id: u32) -> String {
+    team_display_name(team_id, team_names.get(&team_id).map(String::as_str))
+}
+
+/// Standings, record and odds cells for one team, in column order
+fn odds_cells(team: &PlayoffOdds, team_names: &HashMap<u32, String>) -> Vec<String> {
+    let mut cells = vec![
+        team_name(team_names, team.team_id),
+        format!(
+            "{}-{}-{}",
+            team.record.wins, team.record.losses, team.record.ties
+        ),
+        format!("{:.1}", team.strength.mean),
+        format!("{:.1}", team.projected_wins),
+        format_percent(team.playoff_probability),
+    ];
+    cells.extend(team.seed_probabilities.iter().map(|p| format_percent(*p)));
+    cells
+}
+
+/// Seed column headers ("#1", "#2", ...) for the odds table
+fn seed_headers(odds: &[PlayoffOdds]) -> Vec<String> {
+    let seeds = odds.first().map_or(0, |o| o.seed_probabilities.len());
+    (1..=seeds).map(|s| format!("#{}", s)).collect()
+}
+
+fn summary_line(params: &PlayoffOddsParams, input: &PlayoffSimInput) -> String {
+    format!(
+        "Season {} ({} simulations, {} playoff spots, {} games left)",
+        params.season,
+        params.iterations,
+        input.playoff_teams,
+        input.remaining.len()
+    )
+}
+
+/// The standings and odds as a page to share with the league
+fn share_report(
+    params: &PlayoffOddsParams,
+    input: &PlayoffSimInput,
+    odds: &[PlayoffOdds],
+    team_names: &HashMap<u32, String>,
+) -> HtmlReport {
+    let seeds = seed_headers(odds);
+    let mut columns = vec![
+        ("Team", Align::Left),
+        ("W-L-T", Align::Left),
+        ("Avg", Align::Right),
+        ("Proj W", Align::Right),
+        ("Playoff", Align::Right),
+    ];
+    columns.extend(seeds.iter().map(|s| (s.as_str(), Align::Right)));
+    let mut section = HtmlSection::new("Standings and Playoff Odds", &columns);
+    for team in odds {
+        section.add_row(odds_cells(team, team_names));
+    }
+    if input.remaining.is_empty() {
+        section.add_note("The regular season is complete; seeds are final.");
+    }
+    section.add_note("Weekly scores are drawn from each team's season mean and spread so far.");
+
+    let mut report = HtmlReport::new("Playoff Odds", summary_line(params, input));
+    report.add_section(section);
+    report
+}
+
+fn print_odds(
+    params: &PlayoffOddsParams,
+    input: &PlayoffSimInput,
+    odds: &[PlayoffOdds],
+    team_names: &HashMap<u32, String>,
+) {
+    println!("Playoff Odds - {}", summary_line(params, input));
+    if input.remaining.is_empty() {
+        println!("The regular season is complete; seeds are final.");
+    }
+    println!();
+
+    let mut columns = vec![
+        Column::flex("Team", TEAM_MIN_WIDTH, TEAM_MAX_WIDTH),
+        Column::fixed("W-L-T", Align::Left),
+        Column::fixed("Avg", Align::Right),
+        Column::fixed("Proj W", Align::Right),
+        Column::fixed("Playoff", Align::Right),
+    ];
+    columns.extend(
+        seed_headers(odds)
+            .into_iter()
+            .map(|s| Column::fixed(s, Align::Right)),
+    );
+    let mut table = Table::new(columns);
+
+    for team in odds {
+        table.add_row(odds_cells(team, team_names));
+    }
+    table.print();
+    println!();
+    println!("Weekly scores are drawn from each team's season mean and spread so far.");
+}
+
+/// Whole percentages, with "<1%" / ">99%" so near-certainties aren't shown as 0 or 100
+fn format_percent(p: f64) -> String {
+    if p > 0.0 && p < 0.005 {
+        "<1%".to_string()
+    } else if p < 1.0 && p > 0.995 {
+        ">99%".to_string()
+    } else {
+        format!("{:.0}%", p * 100.0)
+    }
+}
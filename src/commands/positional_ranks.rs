@@ -0,0 +1,110 @@
+//! Positional ranks command implementation
+//!
+//! Ranks every player against their position for each stored week from actual
+//! points, then summarizes how often they finished top-12 and top-24, their
+//! median weekly rank and how much it moved around.
+
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        render::{
+            disambiguate_names, Align, Column, NameEntry, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH,
+        },
+        types::position::Position,
+    },
+    storage::{PlayerDatabase, PositionalRankHistory},
+    LeagueId, PlayerId, Result, Season,
+};
+
+use super::league_data::resolve_league_id;
+
+/// Configuration for the positional ranks query
+#[derive(Debug)]
+pub struct PositionalRanksParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    pub positions: Option<Vec<Position>>,
+    /// Fewest ranked weeks a player needs to be listed
+    pub min_games: u32,
+    pub as_json: bool,
+}
+
+/// JSON output for the positional ranks command
+#[derive(Debug, Serialize)]
+struct PositionalRanksOutput<'a> {
+    season: Season,
+    players: &'a [PositionalRankHistory],
+}
+
+/// Handle the positional ranks command
+pub fn handle_positional_ranks(params: PositionalRanksParams) -> Result<()> {
+    let league_id = resolve_league_id(params.league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let players = db.positional_rank_history(
+        league_id,
+        params.season,
+        params.positions.as_deref(),
+        params.min_games,
+    )?;
+
+    if params.as_json {
+        let output = PositionalRanksOutput {
+            season: params.season,
+            players: &players,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    print_ranks(params.season, &players);
+    Ok(())
+}
+
+fn print_ranks(season: Season, players: &[PositionalRankHistory]) {
+    println!("Weekly positional finishes - Season {}", season);
+    if players.is_empty() {
+        println!("No stored actual points for this season; run update-all-data first.");
+        return;
+    }
+
+    let entries: Vec<NameEntry<'_, PlayerId>> = players
+        .iter()
+        .map(|p| NameEntry {
+            id: p.player_id,
+            name: &p.name,
+            position: &p.position,
+            team: p.team.as_deref(),
+        })
+        .collect();
+    let names = disambiguate_names(&entries);
+
+    let mut table = Table::new(vec![
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Team", Align::Left),
+        Column::fixed("Games", Align::Right),
+        Column::fixed("Top 12", Align::Right),
+        Column::fixed("Top 24", Align::Right),
+        Column::fixed("Median", Align::Right),
+        Column::fixed("Std Dev", Align::Right),
+    ]);
+    for player in players {
+        table.add_row(vec![
+            names
+                .get(&player.player_id)
+                .unwrap_or(&player.name)
+                .to_string(),
+            player.position.clone(),
+            player.team.as_deref().unwrap_or("--").to_string(),
+            player.games.to_string(),
+            player.top_12.to_string(),
+            player.top_24.to_string(),
+            format!("{:.1}", player.median_rank),
+            format!("{:.1}", player.rank_variance.sqrt()),
+        ]);
+    }
+    println!();
+    table.print();
+}
@@ -1,42 +1,182 @@
 //! Projection analysis command implementation
+//!
+//! [`analyze_projections`] returns the estimates; [`handle_projection_analysis`] prints them.
 
 use crate::{
-    cli::types::position::Position,
+    cli::{
+        render::{
+            disambiguate_names, output_width, Align, Column, NameEntry, Table, NAME_MAX_WIDTH,
+            NAME_MIN_WIDTH,
+        },
+        types::{
+            filters::{EstimateSort, GroupBy, RosterStatusFilter},
+            position::Position,
+        },
+    },
+    error::EspnError,
     espn::{
         cache_settings::load_or_fetch_league_settings,
-        compute::{build_scoring_index, compute_points_for_week, select_weekly_stats},
-        http::{get_player_data, update_player_points_with_roster_data, PlayerDataRequest},
-        types::PlayerPoints,
+        compute::{compute_points_for_week, scoring_slot_for_player, select_weekly_stats},
+        http::{
+            get_player_data, get_pro_schedule, player_query_limit,
+            update_player_points_with_roster_data, PlayerDataRequest, PLAYERS_PAGE_SIZE,
+        },
+        teams::pro_team_abbrev,
+        types::{PlayerPoints, ProSchedule},
+    },
+    storage::{
+        analysis::{
+            assign_start_tiers, assign_vorp, contrarian_estimates, group_estimates_by_position,
+            replacement_levels, sort_by_vorp, BiasWeighting, TierThresholds, CONTRARIAN_RANK_GAP,
+            POSITION_GROUP_ORDER,
+        },
+        PerformanceEstimate, PlayerDatabase, StartTier,
     },
-    storage::PlayerDatabase,
-    Result, Season, Week,
+    PlayerId, Result, Season, UtcTimestamp, Week,
 };
 
 use super::{
-    common::{CommandParams, CommandParamsBuilder},
-    league_data::resolve_league_id,
+    common::{
+        league_scoring_index, report_unknown_positions, unknown_position_names, write_csv_rows,
+        CommandParams, CommandParamsBuilder,
+    },
+    league_data::{resolve_league_id, validate_season},
+    notes::{latest_note_suffix, print_json_rows},
     player_filters::{
         filter_and_convert_players, matches_fantasy_team_filter, matches_injury_filter,
-        matches_roster_filter,
+        matches_roster_filter, resolve_fantasy_team_filter,
     },
+    projection_export::{write_projections_csv, ExportRow, ProjectionExport},
 };
 use rayon::prelude::*;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+};
 
 /// Configuration for projection analysis.
 #[derive(Debug)]
 pub struct ProjectionAnalysisParams {
     pub base: CommandParams,
     pub bias_strength: f64,
+    /// Weight of each earlier week's bias relative to the week after it (1.0 = uniform)
+    pub bias_decay: f64,
+    pub group_by: Option<GroupBy>,
+    /// Show only the highest estimates (within each position when grouping)
+    pub top: Option<usize>,
+    /// Order by estimated points or by value over replacement
+    pub sort: EstimateSort,
+    /// Count "did not play" weeks as zero-point games in the bias history
+    pub include_dnp: bool,
+    /// Append each player's latest note (all notes in JSON)
+    pub show_notes: bool,
+    /// Weight on recent form when blending with the bias-adjusted projection
+    pub blend_recent: Option<f64>,
+    /// Attach the weeks behind each displayed player's bias
+    pub show_history: bool,
+    /// Also estimate free agents ESPN didn't project (rostered players always are)
+    pub include_unprojected: bool,
+    /// Only show players ranked well above their start rate (see [`CONTRARIAN_RANK_GAP`])
+    pub contrarian: bool,
+    /// Tier players per position against replacement level (see [`TierThresholds`])
+    pub tiers: bool,
+    /// Time lineup locks are judged at (defaults to now)
+    pub as_of: Option<UtcTimestamp>,
+    /// Also write the shown players to a CSV for lineup optimizers
+    pub export: Option<ProjectionExport>,
 }
 
+/// Bias strengths above this print a warning unless overridden by `ESPN_FFL_BIAS_STRENGTH_SOFT_CAP`
+pub const DEFAULT_BIAS_STRENGTH_SOFT_CAP: f64 = 3.0;
+
+/// Environment variable that overrides [`DEFAULT_BIAS_STRENGTH_SOFT_CAP`].
+pub const BIAS_STRENGTH_SOFT_CAP_ENV_VAR: &str = "ESPN_FFL_BIAS_STRENGTH_SOFT_CAP";
+
 impl ProjectionAnalysisParams {
     /// Create new parameters with required fields.
-    pub fn new(season: Season, week: Week, bias_strength: f64) -> Self {
-        Self {
+    ///
+    /// Rejects negative (or non-finite) bias strengths.
+    pub fn new(season: Season, week: Week, bias_strength: f64) -> Result<Self> {
+        Ok(Self {
             base: CommandParams::new(season, week),
-            bias_strength,
-        }
+            bias_strength: validate_bias_strength(bias_strength)?,
+            bias_decay: 1.0,
+            group_by: None,
+            top: None,
+            sort: EstimateSort::Points,
+            include_dnp: false,
+            show_notes: false,
+            blend_recent: None,
+            show_history: false,
+            include_unprojected: false,
+            contrarian: false,
+            tiers: false,
+            as_of: None,
+            export: None,
+        })
+    }
+
+    /// Blend estimates with recent form at the given weight (0 to 1).
+    pub fn with_blend_recent(mut self, weight: f64) -> Result<Self> {
+        self.blend_recent = Some(validate_blend_weight(weight)?);
+        Ok(self)
     }
+
+    /// Weight recent weeks' bias more heavily, by `decay^weeks_ago` (above 0, up to 1).
+    pub fn with_bias_decay(mut self, decay: f64) -> Result<Self> {
+        self.bias_decay = validate_bias_decay(decay)?;
+        Ok(self)
+    }
+}
+
+/// Check a `--bias-decay` factor, which must be above 0 and at most 1
+pub fn validate_bias_decay(decay: f64) -> Result<f64> {
+    if decay > 0.0 && decay <= 1.0 {
+        Ok(decay)
+    } else {
+        Err(EspnError::InvalidBiasDecay { value: decay })
+    }
+}
+
+/// Check a `--blend-recent` weight, which must be between 0 and 1
+pub fn validate_blend_weight(weight: f64) -> Result<f64> {
+    if (0.0..=1.0).contains(&weight) {
+        Ok(weight)
+    } else {
+        Err(EspnError::InvalidBlendWeight { value: weight })
+    }
+}
+
+/// Check a `--bias-strength` value, which must be a non-negative number
+pub fn validate_bias_strength(bias_strength: f64) -> Result<f64> {
+    if bias_strength.is_finite() && bias_strength >= 0.0 {
+        Ok(bias_strength)
+    } else {
+        Err(EspnError::InvalidBiasStrength {
+            value: bias_strength,
+        })
+    }
+}
+
+/// Soft cap for `--bias-strength`: `ESPN_FFL_BIAS_STRENGTH_SOFT_CAP` if set, otherwise 3.0
+pub fn bias_strength_soft_cap() -> f64 {
+    std::env::var(BIAS_STRENGTH_SOFT_CAP_ENV_VAR)
+        .ok()
+        .and_then(|cap| cap.trim().parse::<f64>().ok())
+        .filter(|cap| cap.is_finite() && *cap >= 0.0)
+        .unwrap_or(DEFAULT_BIAS_STRENGTH_SOFT_CAP)
+}
+
+/// Warning for bias strengths above the soft cap, which amplify corrections heavily
+pub fn bias_strength_warning(bias_strength: f64, soft_cap: f64) -> Option<String> {
+    (bias_strength > soft_cap).then(|| {
+        format!(
+            "bias strength {} is above {} and amplifies historical corrections {}x; \
+             adjusted projections are capped by position history",
+            bias_strength, soft_cap, bias_strength
+        )
+    })
 }
 
 impl CommandParamsBuilder for ProjectionAnalysisParams {
@@ -49,13 +189,311 @@ impl CommandParamsBuilder for ProjectionAnalysisParams {
     }
 }
 
+/// Estimates for the analyzed week with what the output needs to show them
+struct WeekAnalysis {
+    db: PlayerDatabase,
+    /// Filtered and ordered for output
+    estimates: Vec<PerformanceEstimate>,
+    starters: HashMap<String, u32>,
+    replacement: HashMap<String, f64>,
+    pro_teams: HashMap<PlayerId, u32>,
+    pro_schedule: Option<ProSchedule>,
+}
+
+/// Bias-adjusted estimates for the week, filtered and ordered the way
+/// `projection-analysis` shows them.
+///
+/// Fetches ESPN's projections, adjusts them by each player's history and applies every
+/// position, injury, roster and fantasy team filter along with `contrarian`, `sort` and
+/// `top` (unless grouping). Empty when there's nothing to estimate. Nothing is printed
+/// to stdout, and `export` is only checked, not written.
+pub async fn analyze_projections(
+    mut params: ProjectionAnalysisParams,
+) -> Result<Vec<PerformanceEstimate>> {
+    let analysis = run_analysis(&mut params, false).await?;
+    Ok(analysis.map(|a| a.estimates).unwrap_or_default())
+}
+
 /// Handle the projection analysis command.
-pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Result<()> {
+pub async fn handle_projection_analysis(mut params: ProjectionAnalysisParams) -> Result<()> {
+    if let Some(warning) = bias_strength_warning(params.bias_strength, bias_strength_soft_cap()) {
+        eprintln!("⚠ Warning: {}", warning);
+    }
+    let verbose = !params.base.as_json;
+    let Some(WeekAnalysis {
+        db,
+        estimates: filtered_estimates,
+        starters,
+        replacement,
+        pro_teams,
+        pro_schedule,
+    }) = run_analysis(&mut params, verbose).await?
+    else {
+        if verbose {
+            println!(
+                "No projection data available for week {}.",
+                params.base.week.as_u16()
+            ); // tarpaulin::skip
+            println!("Make sure to fetch historical data for previous weeks first.");
+            // tarpaulin::skip
+        }
+        return Ok(());
+    };
+
+    if verbose {
+        println!(
+            "✓ Generated predictions for {} players",
+            filtered_estimates.len()
+        );
+    }
+    // Omit the crowd column entirely for weeks without start rates
+    let show_crowd = filtered_estimates
+        .iter()
+        .any(|e| e.percent_started.is_some());
+
+    if let Some(export) = &params.export {
+        let rows: Vec<ExportRow> = filtered_estimates
+            .iter()
+            .map(|estimate| {
+                let team = pro_teams.get(&estimate.player_id).copied();
+                let opponent =
+                    team.and_then(|team| pro_schedule.as_ref()?.opponent(team, params.base.week));
+                ExportRow::new(
+                    estimate,
+                    team.and_then(pro_team_abbrev),
+                    opponent.and_then(pro_team_abbrev),
+                    export.site,
+                )
+            })
+            .collect();
+        let written = write_projections_csv(BufWriter::new(File::create(&export.path)?), &rows)?;
+        if verbose {
+            println!(
+                "✓ Wrote {} {} projections to {}",
+                written,
+                export.site,
+                export.path.display()
+            );
+        }
+    }
+
+    let names = {
+        let entries: Vec<NameEntry<'_, PlayerId>> = filtered_estimates
+            .iter()
+            .map(|e| NameEntry {
+                id: e.player_id,
+                name: &e.name,
+                position: &e.position,
+                team: e.team.as_deref(),
+            })
+            .collect();
+        disambiguate_names(&entries)
+    };
+    let name_cell = |estimate: &PerformanceEstimate| {
+        let name = names
+            .get(&estimate.player_id)
+            .cloned()
+            .unwrap_or_else(|| estimate.name.clone());
+        if estimate.locked == Some(true) {
+            format!("{} 🔒", name)
+        } else {
+            name
+        }
+    };
+
+    let notes = if params.show_notes {
+        let player_ids: Vec<_> = filtered_estimates.iter().map(|e| e.player_id).collect();
+        Some(db.get_notes_for_players(&player_ids)?)
+    } else {
+        None
+    };
+    let reasoning_cell = |estimate: &PerformanceEstimate| {
+        let note = notes
+            .as_ref()
+            .map(|notes| latest_note_suffix(notes, estimate.player_id))
+            .unwrap_or_default();
+        format!("{}{}", estimate.reasoning, note)
+    };
+
+    if let Some(GroupBy::Position) = params.group_by {
+        let mut groups = group_estimates_by_position(filtered_estimates, &starters);
+        if let Some(top) = params.top {
+            for group in &mut groups {
+                group.players.truncate(top);
+            }
+        }
+
+        if params.base.as_json {
+            let nested: serde_json::Map<String, serde_json::Value> = groups
+                .into_iter()
+                .map(|group| {
+                    let mut value = serde_json::to_value(&group)?;
+                    if let (Some(notes), Some(players)) = (&notes, value["players"].as_array_mut())
+                    {
+                        for (player, ranked) in players.iter_mut().zip(&group.players) {
+                            let player_notes = notes
+                                .get(&ranked.estimate.player_id)
+                                .cloned()
+                                .unwrap_or_default();
+                            player["notes"] = serde_json::to_value(player_notes)?;
+                        }
+                    }
+                    Ok((group.position.clone(), value))
+                })
+                .collect::<Result<_>>()?;
+            println!("{}", serde_json::to_string_pretty(&nested)?); // tarpaulin::skip
+        } else {
+            // tarpaulin::skip - console output
+            print_analysis_header(params.base.season, params.base.week);
+            for group in groups {
+                println!(
+                    "== {} ({} players, avg {:.1}, replacement {:.1}) ==",
+                    group.position,
+                    group.players.len(),
+                    group.average_estimate,
+                    group.replacement_level
+                );
+                let mut columns = vec![
+                    Column::fixed("Rank", Align::Right),
+                    Column::flex("Name", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+                    Column::fixed("ESPN", Align::Right),
+                    Column::fixed("Adj", Align::Right),
+                    Column::fixed("Final", Align::Right),
+                    Column::fixed("+Repl", Align::Right),
+                    Column::fixed("Conf%", Align::Right),
+                ];
+                if show_crowd {
+                    columns.push(Column::fixed("Crowd", Align::Right));
+                }
+                columns.push(Column::rest("Reasoning"));
+                let mut table = Table::new(columns);
+                for ranked in group.players {
+                    let estimate = &ranked.estimate;
+                    let mut cells = vec![
+                        ranked.positional_rank.to_string(),
+                        name_cell(estimate),
+                        format!("{:.1}", estimate.espn_projection),
+                        format_adjustment(estimate),
+                        format!("{:.1}", estimate.estimated_points),
+                        format!("{:+.1}", ranked.points_above_replacement),
+                        format_confidence(estimate),
+                    ];
+                    if show_crowd {
+                        cells.push(format_crowd(estimate));
+                    }
+                    cells.push(reasoning_cell(estimate));
+                    table.add_row(cells);
+                    add_history(&mut table, estimate);
+                }
+                table.print();
+                println!();
+            }
+        }
+    } else if params.base.csv {
+        write_estimates_csv(std::io::stdout().lock(), &filtered_estimates)?;
+    } else if params.base.as_json {
+        print_json_rows(
+            &filtered_estimates,
+            |e| e.player_id,
+            notes.as_ref(),
+            params.base.json_lines,
+        )?;
+    } else if params.tiers {
+        // tarpaulin::skip - console output
+        print_analysis_header(params.base.season, params.base.week);
+        for group in group_estimates_by_position(filtered_estimates, &starters) {
+            match replacement.get(&group.position) {
+                Some(level) => println!("== {} (replacement {:.1}) ==", group.position, level),
+                None => println!("== {} ==", group.position),
+            }
+            for tier in StartTier::ALL {
+                let players: Vec<String> = group
+                    .players
+                    .iter()
+                    .filter(|ranked| ranked.estimate.tier == Some(tier))
+                    .map(|ranked| {
+                        format!(
+                            "{} {:.1}",
+                            name_cell(&ranked.estimate),
+                            ranked.estimate.estimated_points
+                        )
+                    })
+                    .collect();
+                if !players.is_empty() {
+                    print_tier_line(tier.label(), &players, output_width());
+                }
+            }
+            println!();
+        }
+    } else {
+        // tarpaulin::skip - console output
+        print_analysis_header(params.base.season, params.base.week);
+        let show_vorp = params.sort == EstimateSort::Vorp;
+        if show_vorp {
+            print_replacement_baselines(&replacement, &starters);
+        }
+
+        let mut columns = vec![
+            Column::flex("Name", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+            Column::fixed("Pos", Align::Left),
+            Column::fixed("ESPN", Align::Right),
+            Column::fixed("Adj", Align::Right),
+            Column::fixed("Final", Align::Right),
+        ];
+        if show_vorp {
+            columns.push(Column::fixed("VORP", Align::Right));
+        }
+        columns.push(Column::fixed("Conf%", Align::Right));
+        if show_crowd {
+            columns.push(Column::fixed("Crowd", Align::Right));
+        }
+        columns.push(Column::rest("Reasoning"));
+        let mut table = Table::new(columns);
+        for estimate in &filtered_estimates {
+            let mut cells = vec![
+                name_cell(estimate),
+                estimate.position.clone(),
+                format!("{:.1}", estimate.espn_projection),
+                format_adjustment(estimate),
+                format!("{:.1}", estimate.estimated_points),
+            ];
+            if show_vorp {
+                cells.push(
+                    estimate
+                        .vorp
+                        .map_or_else(|| "--".to_string(), |vorp| format!("{:+.1}", vorp)),
+                );
+            }
+            cells.push(format_confidence(estimate));
+            if show_crowd {
+                cells.push(format_crowd(estimate));
+            }
+            cells.push(reasoning_cell(estimate));
+            table.add_row(cells);
+            add_history(&mut table, estimate);
+        }
+        table.print();
+    }
+
+    Ok(())
+}
+
+/// Fetch, estimate and filter the week's players; `None` when nothing can be estimated
+async fn run_analysis(
+    params: &mut ProjectionAnalysisParams,
+    verbose: bool,
+) -> Result<Option<WeekAnalysis>> {
     let league_id = resolve_league_id(params.base.league_id)?;
-    if !params.base.as_json {
+    validate_season(league_id, params.base.season).await?;
+    if verbose {
         println!("Connecting to database...");
     }
-    let db = PlayerDatabase::new()?;
+    let mut db = PlayerDatabase::new()?;
+
+    // A filter for a player's old name also finds them under their current one
+    if let Some(names) = &params.base.player_names {
+        params.base.player_names = Some(db.expand_player_names_with_aliases(names)?);
+    }
 
     // Fetch week-specific roster data to match the week being analyzed
     let roster_data = match crate::espn::http::get_league_roster_data(
@@ -68,32 +506,13 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
     .await
     {
         Ok((data, cache_status)) => {
-            if !params.base.as_json {
-                match cache_status {
-                    crate::espn::http::CacheStatus::Hit => {
-                        println!(
-                            "✓ Week {} roster status loaded (from cache)",
-                            params.base.week.as_u16()
-                        );
-                    }
-                    crate::espn::http::CacheStatus::Miss => {
-                        println!(
-                            "✓ Week {} roster status fetched (cache miss)",
-                            params.base.week.as_u16()
-                        );
-                    }
-                    crate::espn::http::CacheStatus::Refreshed => {
-                        println!(
-                            "✓ Week {} roster status fetched (refreshed)",
-                            params.base.week.as_u16()
-                        );
-                    }
-                }
+            if verbose {
+                println!("✓ {}", cache_status.message(Some(params.base.week)));
             }
             Some(data)
         }
         Err(e) => {
-            if !params.base.as_json {
+            if verbose {
                 println!(
                     "⚠ Could not fetch week {} roster data: {}",
                     params.base.week.as_u16(),
@@ -104,6 +523,27 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
         }
     };
 
+    if let Some(ref league_data) = roster_data {
+        let _ = db.record_team_names(league_id, params.base.season, params.base.week, league_data);
+    }
+    // Match --team against every name a team has used this season
+    if let Some(filter) = &params.base.fantasy_team_filter {
+        params.base.fantasy_team_filter = Some(resolve_fantasy_team_filter(
+            &db,
+            league_id,
+            params.base.season,
+            filter,
+        )?);
+    }
+
+    // Load league settings to compute ESPN projections
+    if verbose {
+        println!("Loading league scoring settings...");
+    }
+    let settings = load_or_fetch_league_settings(league_id, false, params.base.season).await?;
+    let (scoring_index, _) = league_scoring_index(&settings)?;
+    let flex = settings.flex_eligibility();
+
     // Fetch ESPN projections for the target week (get_player_data handles caching internally)
     let players_val = get_player_data(PlayerDataRequest {
         debug: false,
@@ -115,6 +555,9 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
         week: params.base.week,
         injury_status_filter: params.base.injury_status.clone(),
         roster_status_filter: params.base.roster_status.clone(),
+        flex: flex.clone(),
+        limit: player_query_limit(params.base.player_names.as_deref()),
+        page_size: PLAYERS_PAGE_SIZE,
     })
     .await?;
 
@@ -123,78 +566,170 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
     // Note: No need to update players table since projection analysis works directly
     // with ESPN API data and doesn't rely on the database players table
 
-    // Load league settings to compute ESPN projections
-    if !params.base.as_json {
-        println!("Loading league scoring settings...");
-    }
-    let settings = load_or_fetch_league_settings(league_id, false, params.base.season).await?;
-    let scoring_index = build_scoring_index(&settings.scoring_settings.scoring_items);
-
-    if !players.is_empty() && !params.base.as_json {
+    if !players.is_empty() && verbose {
         println!(
             "Computing ESPN projections for {} players...",
             players.len()
         );
     }
 
-    // Calculate ESPN projections for each player in parallel
-    let projected_points_data: Vec<(crate::PlayerId, f64)> = filter_and_convert_players(
+    let filtered_players = filter_and_convert_players(
         players,
         params.base.player_names.clone(),
         params.base.positions.clone(),
-    )
-    .into_par_iter()
-    .filter_map(|filtered_player| {
-        let player = filtered_player.original_player;
-        let player_id = filtered_player.player_id;
-
-        if let Ok(player_value) = serde_json::to_value(&player) {
-            if let Some(weekly_stats) = select_weekly_stats(
-                &player_value,
+        params.base.nfl_team,
+        &flex,
+    );
+    if verbose {
+        report_unknown_positions(&unknown_position_names(&filtered_players));
+    }
+
+    // Start rates for the crowd column; ESPN leaves ownership out of some responses
+    let percent_started: HashMap<PlayerId, f64> = filtered_players
+        .iter()
+        .filter_map(|fp| {
+            let started = fp.original_player.ownership?.percent_started?;
+            Some((fp.player_id, started))
+        })
+        .collect();
+    let pro_teams: HashMap<PlayerId, u32> = filtered_players
+        .iter()
+        .filter_map(|fp| Some((fp.player_id, fp.original_player.nfl_team_id()?)))
+        .collect();
+
+    // Calculate ESPN projections for each player in parallel; `None` when ESPN has
+    // no projection for the week
+    let computed: Vec<(PlayerId, i64, Option<f64>)> = filtered_players
+        .into_par_iter()
+        .filter_map(|filtered_player| {
+            let player = filtered_player.original_player;
+            let scoring_slot = scoring_slot_for_player(&player)?;
+            let espn_projection = select_weekly_stats(
+                &player,
                 params.base.season.as_u16(),
                 params.base.week.as_u16(),
                 1, // stat_source = 1 for projected
-            ) {
-                let position_id = if player.default_position_id < 0 {
-                    0u8
-                } else {
-                    player.default_position_id as u8
-                };
-                let espn_projection =
-                    compute_points_for_week(weekly_stats, position_id, &scoring_index);
+            )
+            .map(|weekly_stats| {
+                compute_points_for_week(weekly_stats, scoring_slot, &scoring_index)
+            });
+            Some((filtered_player.player_id, player.id, espn_projection))
+        })
+        .collect();
+
+    // NFL schedule for lineup locks and export opponents; `None` if ESPN has no games
+    // for the week
+    let pro_schedule = get_pro_schedule(params.base.season)
+        .await
+        .ok()
+        .filter(|schedule| schedule.last_kickoff(params.base.week).is_some());
+
+    // Players on bye have no projection to miss
+    if let Some(export) = &params.export {
+        let playing: Vec<bool> = computed
+            .iter()
+            .map(
+                |(player_id, _, _)| match (&pro_schedule, pro_teams.get(player_id)) {
+                    (Some(schedule), Some(&team)) => {
+                        schedule.kickoff(team, params.base.week).is_some()
+                    }
+                    _ => true,
+                },
+            )
+            .collect();
+        let total = playing.iter().filter(|&&playing| playing).count();
+        let missing = computed
+            .iter()
+            .zip(&playing)
+            .filter(|((_, _, projection), &playing)| playing && projection.is_none())
+            .count();
+        export.check_missing(missing, total, params.base.week)?;
+    }
 
-                Some((player_id, espn_projection))
-            } else {
-                None
+    // Rostered players without a projection are always kept; free agents only on request
+    let rostered = roster_data
+        .as_ref()
+        .map(|league_data| league_data.create_player_roster_map())
+        .unwrap_or_default();
+    let mut projected_points_data: Vec<(PlayerId, f64)> = Vec::new();
+    let mut unprojected: Vec<PlayerId> = Vec::new();
+    for (player_id, espn_id, espn_projection) in computed {
+        match espn_projection {
+            Some(points) => projected_points_data.push((player_id, points)),
+            None if params.include_unprojected || rostered.contains_key(&espn_id) => {
+                unprojected.push(player_id)
             }
-        } else {
-            None
+            None => {}
         }
-    })
-    .collect();
+    }
 
     // Get performance estimates using historical data
-    if !params.base.as_json {
+    if verbose {
         println!("Analyzing historical performance bias and generating predictions...");
     }
-    let estimates = db.estimate_week_performance(
+    let mut estimates = db.estimate_week_performance(
+        league_id,
         params.base.season,
         params.base.week,
         &projected_points_data,
-        None,
-        params.bias_strength,
+        BiasWeighting {
+            strength: params.bias_strength,
+            decay: params.bias_decay,
+        },
+        params.include_dnp,
+    )?;
+    db.add_unprojected_estimates(
+        league_id,
+        &mut estimates,
+        params.base.season,
+        params.base.week,
+        &unprojected,
+        params.include_dnp,
     )?;
+    if let Some(weight) = params.blend_recent {
+        db.apply_recent_form_blend(
+            league_id,
+            &mut estimates,
+            params.base.season,
+            params.base.week,
+            weight,
+            params.include_dnp,
+        )?;
+    }
 
-    if estimates.is_empty() {
-        if !params.base.as_json {
-            println!(
-                "No projection data available for week {}.",
-                params.base.week.as_u16()
-            ); // tarpaulin::skip
-            println!("Make sure to fetch historical data for previous weeks first.");
-            // tarpaulin::skip
+    for estimate in &mut estimates {
+        estimate.percent_started = percent_started.get(&estimate.player_id).copied();
+    }
+
+    // Mark players whose games have kicked off, while the week is being played
+    let as_of = params.as_of.unwrap_or_else(UtcTimestamp::now);
+    let locked_teams = pro_schedule
+        .as_ref()
+        .and_then(|schedule| schedule.locked_teams(params.base.week, as_of.as_secs()));
+    if let Some(locked_teams) = &locked_teams {
+        for estimate in &mut estimates {
+            estimate.locked = Some(
+                pro_teams
+                    .get(&estimate.player_id)
+                    .is_some_and(|team| locked_teams.contains(team)),
+            );
         }
-        return Ok(());
+    }
+
+    let team_count = roster_data
+        .as_ref()
+        .map(|data| data.teams.len() as u32)
+        .unwrap_or(0);
+    let starters = settings.starters_per_position(team_count);
+    // Replacement levels come from every estimate, before display filters narrow the list
+    let replacement = replacement_levels(&estimates, &starters);
+    assign_vorp(&mut estimates, &replacement);
+    if params.tiers {
+        assign_start_tiers(&mut estimates, &replacement, &TierThresholds::default());
+    }
+
+    if estimates.is_empty() {
+        return Ok(None);
     }
 
     // Get current injury/roster status and team data for filtering if needed
@@ -204,7 +739,7 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
         || params.base.roster_status.is_some()
         || params.base.fantasy_team_filter.is_some()
     {
-        if !params.base.as_json {
+        if verbose {
             println!("Getting current player status and team data for filtering...");
         }
 
@@ -223,7 +758,7 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
 
         // Build status map for filtering
         for player in temp_player_points {
-            current_status_map.insert(player.name.clone(), player);
+            current_status_map.insert(player.id, player);
         }
     }
 
@@ -231,7 +766,7 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
     let allowed_position_ids = settings.get_allowed_position_ids();
 
     // Apply filters in parallel (position, injury status, roster status, team)
-    let filtered_estimates: Vec<_> = estimates
+    let mut filtered_estimates: Vec<_> = estimates
         .into_par_iter()
         .filter(|estimate| {
             // First, check if this player's position is allowed in the league
@@ -244,23 +779,17 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
 
             // Apply user-specified position filter
             if let Some(pos_filters) = &params.base.positions {
-                let position_matches = pos_filters.iter().any(|p| {
-                    match p {
-                        // For flexible params.base.positions, check if player position is eligible
-                        Position::FLEX => {
-                            matches!(estimate.position.as_str(), "RB" | "WR" | "TE")
-                        }
-                        // For individual params.base.positions, compare directly
-                        _ => estimate.position == p.to_string(),
-                    }
-                });
+                let position_matches = match estimate.position.parse::<Position>() {
+                    Ok(position) => pos_filters.iter().any(|p| p.accepts(position, &flex)),
+                    Err(_) => false,
+                };
                 if !position_matches {
                     return false;
                 }
             }
 
             // Apply injury/roster status filters using current status
-            if let Some(player_status) = current_status_map.get(&estimate.name) {
+            if let Some(player_status) = current_status_map.get(&estimate.player_id) {
                 // Apply injury status filter if specified
                 if let Some(injury_filter) = &params.base.injury_status {
                     if !matches_injury_filter(player_status, injury_filter) {
@@ -293,55 +822,204 @@ pub async fn handle_projection_analysis(params: ProjectionAnalysisParams) -> Res
         })
         .collect();
 
-    if !params.base.as_json {
-        println!(
-            "✓ Generated predictions for {} players",
-            filtered_estimates.len()
-        );
+    // A free agent whose game has started can't be picked up and played this week
+    if matches!(params.base.roster_status, Some(RosterStatusFilter::FA)) {
+        let before = filtered_estimates.len();
+        filtered_estimates.retain(|e| e.locked != Some(true));
+        let hidden = before - filtered_estimates.len();
+        if hidden > 0 && verbose {
+            println!(
+                "Skipping {} free agent(s) whose games have already started",
+                hidden
+            );
+        }
     }
 
-    if params.base.as_json {
-        println!("{}", serde_json::to_string_pretty(&filtered_estimates)?); // tarpaulin::skip
-    } else {
-        // tarpaulin::skip - console output
-        println!(
-            "Projection Analysis & Predictions for Week {}",
-            params.base.week.as_u16()
-        );
-        println!("Season: {}", params.base.season.as_u16());
-        println!();
+    if params.contrarian {
+        if percent_started.is_empty() && verbose {
+            println!(
+                "⚠ ESPN didn't supply start rates for week {}; --contrarian has nothing to compare",
+                params.base.week.as_u16()
+            );
+        }
+        filtered_estimates = contrarian_estimates(filtered_estimates, CONTRARIAN_RANK_GAP);
+    }
 
-        // Print column headers
-        println!(
-            "{:<20} {:<8} {:<8} {:<8} {:<8} {:<8} Reasoning",
-            "Name", "Pos", "ESPN", "Adj", "Final", "Conf%"
-        );
+    if params.sort == EstimateSort::Vorp {
+        sort_by_vorp(&mut filtered_estimates);
+    }
+
+    // --top limits each position instead when grouping
+    if let (Some(top), None) = (params.top, params.group_by) {
+        filtered_estimates.truncate(top);
+    }
+
+    // Only for the players being shown, to keep normal payloads small
+    if params.show_history {
+        db.attach_bias_history(
+            league_id,
+            &mut filtered_estimates,
+            params.base.season,
+            params.base.week,
+            params.include_dnp,
+        )?;
+    }
+
+    Ok(Some(WeekAnalysis {
+        db,
+        estimates: filtered_estimates,
+        starters,
+        replacement,
+        pro_teams,
+        pro_schedule,
+    }))
+}
+
+fn print_analysis_header(season: Season, week: Week) {
+    println!(
+        "Projection Analysis & Predictions for Week {}",
+        week.as_u16()
+    );
+    println!("Season: {}", season.as_u16());
+    println!();
+}
+
+/// Replacement level behind each position's VORP, with the league's starter count
+fn print_replacement_baselines(
+    replacement: &HashMap<String, f64>,
+    starters: &HashMap<String, u32>,
+) {
+    let mut positions: Vec<&String> = replacement.keys().collect();
+    positions.sort_by_key(|position| {
+        let rank = POSITION_GROUP_ORDER
+            .iter()
+            .position(|p| p == position)
+            .unwrap_or(POSITION_GROUP_ORDER.len());
+        (rank, position.to_string())
+    });
+    println!("Replacement baselines:");
+    for position in positions {
+        let source = match starters.get(position) {
+            Some(count) => format!("first player past {} starters", count),
+            None => "lowest estimate, no starting slots".to_string(),
+        };
         println!(
-            "{:<20} {:<8} {:<8} {:<8} {:<8} {:<8} ---------",
-            "----", "---", "----", "---", "-----", "----"
+            "  {:<5} {:>5.1} ({})",
+            position, replacement[position], source
         );
+    }
+    println!();
+}
 
-        for estimate in filtered_estimates {
-            let adj_str = if estimate.bias_adjustment.abs() < 0.1 {
-                "--".to_string()
-            } else if estimate.bias_adjustment > 0.0 {
-                format!("+{:.1}", estimate.bias_adjustment)
-            } else {
-                format!("{:.1}", estimate.bias_adjustment)
-            };
+/// Column names of the `--csv` header row
+const ESTIMATE_CSV_HEADER: [&str; 12] = [
+    "player_id",
+    "name",
+    "position",
+    "team",
+    "espn_projection",
+    "bias_adjustment",
+    "estimated_points",
+    "confidence",
+    "error_std",
+    "percent_started",
+    "tier",
+    "reasoning",
+];
 
-            println!(
-                "{:<20} {:<8} {:<8.1} {:<8} {:<8.1} {:<8}% {}",
-                estimate.name.chars().take(20).collect::<String>(),
-                estimate.position,
-                estimate.espn_projection,
-                adj_str,
-                estimate.estimated_points,
-                (estimate.confidence * 100.0) as u8,
-                estimate.reasoning
-            );
+/// Write estimates as CSV with a header row; unknown values are left empty
+fn write_estimates_csv(writer: impl Write, estimates: &[PerformanceEstimate]) -> Result<usize> {
+    let optional = |value: Option<f64>| value.map(|v| format!("{:.2}", v)).unwrap_or_default();
+    write_csv_rows(writer, &ESTIMATE_CSV_HEADER, estimates, |estimate| {
+        vec![
+            estimate.player_id.to_string(),
+            estimate.name.clone(),
+            estimate.position.clone(),
+            estimate.team.clone().unwrap_or_default(),
+            format!("{:.2}", estimate.espn_projection),
+            format!("{:.2}", estimate.bias_adjustment),
+            format!("{:.2}", estimate.estimated_points),
+            format!("{:.2}", estimate.confidence),
+            optional(estimate.error_std),
+            optional(estimate.percent_started),
+            estimate
+                .tier
+                .map(|tier| tier.label().to_string())
+                .unwrap_or_default(),
+            estimate.reasoning.clone(),
+        ]
+    })
+}
+
+/// One tier's players on as few lines as fit, continuation lines indented under the first
+fn print_tier_line(label: &str, players: &[String], width: Option<usize>) {
+    let prefix = format!("{:<20}", format!("{}:", label));
+    let mut line = prefix.clone();
+    let mut line_has_player = false;
+    for (i, player) in players.iter().enumerate() {
+        let entry = if i + 1 < players.len() {
+            format!("{},", player)
+        } else {
+            player.clone()
+        };
+        let needed = line.chars().count() + usize::from(line_has_player) + entry.chars().count();
+        if line_has_player && width.is_some_and(|width| needed > width) {
+            println!("{}", line);
+            line = " ".repeat(prefix.len());
+            line_has_player = false;
+        }
+        if line_has_player {
+            line.push(' ');
         }
+        line.push_str(&entry);
+        line_has_player = true;
     }
+    println!("{}", line);
+}
 
-    Ok(())
+/// Indented week-by-week history under a player's row, when attached
+fn add_history(table: &mut Table, estimate: &PerformanceEstimate) {
+    let Some(history) = &estimate.history else {
+        return;
+    };
+    if history.is_empty() {
+        table.add_detail("      (no weeks with both projected and actual points)".to_string());
+        return;
+    }
+    table.add_detail(format!(
+        "      {:>4} {:>7} {:>7} {:>7}",
+        "Week", "Proj", "Actual", "Error"
+    ));
+    for week in history {
+        let excluded = week
+            .excluded
+            .map(|reason| format!("  excluded ({})", reason.label()))
+            .unwrap_or_default();
+        table.add_detail(format!(
+            "      {:>4} {:>7.1} {:>7.1} {:>+7.1}{}",
+            week.week, week.projected, week.actual, week.error, excluded
+        ));
+    }
+}
+
+/// Share of ESPN leagues starting the player ("--" when ESPN didn't say)
+fn format_crowd(estimate: &PerformanceEstimate) -> String {
+    estimate
+        .percent_started
+        .map_or_else(|| "--".to_string(), |p| format!("{:.0}%", p))
+}
+
+fn format_confidence(estimate: &PerformanceEstimate) -> String {
+    format!("{}%", (estimate.confidence * 100.0) as u8)
+}
+
+/// Format a bias adjustment for display ("--" when negligible)
+fn format_adjustment(estimate: &PerformanceEstimate) -> String {
+    if estimate.bias_adjustment.abs() < 0.1 {
+        "--".to_string()
+    } else if estimate.bias_adjustment > 0.0 {
+        format!("+{:.1}", estimate.bias_adjustment)
+    } else {
+        format!("{:.1}", estimate.bias_adjustment)
+    }
 }
@@ -0,0 +1,270 @@
+//! CSV projections for third-party lineup optimizers (`projection-analysis --export-projections`)
+//!
+//! One header row, then one row per player:
+//! `name,position,team,opponent,projection,floor,ceiling,confidence`. Floor and
+//! ceiling are the 10th and 90th percentiles used by matchup forecasts and
+//! confidence is a share between 0 and 1. Team and opponent are empty for free
+//! agents and byes. Names, positions and team abbreviations follow the chosen
+//! site's conventions, and defenses are always named "Team Name D/ST".
+
+use super::common::csv_field;
+use crate::{
+    cli::types::filters::ExportSite,
+    error::EspnError,
+    espn::lineup::{DEFAULT_STD_FRACTION, FLOOR_CEILING_Z},
+    storage::PerformanceEstimate,
+    Result, Week,
+};
+use std::{io::Write, path::PathBuf};
+
+/// Column names of the CSV header row
+pub const CSV_HEADER: [&str; 8] = [
+    "name",
+    "position",
+    "team",
+    "opponent",
+    "projection",
+    "floor",
+    "ceiling",
+    "confidence",
+];
+
+/// Share of players allowed to lack a projection before an export is refused
+pub const DEFAULT_MAX_MISSING_PROJECTIONS: f64 = 0.25;
+
+/// Name suffixes FanDuel leaves off
+const NAME_SUFFIXES: [&str; 7] = ["Jr.", "Jr", "Sr.", "Sr", "II", "III", "IV"];
+
+/// Where and how to write a projections CSV
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectionExport {
+    pub path: PathBuf,
+    pub site: ExportSite,
+    /// Largest share of players without a projection that still exports
+    pub max_missing: f64,
+}
+
+impl ProjectionExport {
+    /// Rejects a `max_missing` share outside 0 to 1
+    pub fn new(path: PathBuf, site: ExportSite, max_missing: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&max_missing) {
+            return Err(EspnError::InvalidMissingShare { value: max_missing });
+        }
+        Ok(Self {
+            path,
+            site,
+            max_missing,
+        })
+    }
+
+    /// Refuse to export when more than `max_missing` of `total` players lack a projection
+    pub fn check_missing(&self, missing: usize, total: usize, week: Week) -> Result<()> {
+        if total > 0 && missing as f64 / total as f64 > self.max_missing {
+            return Err(EspnError::TooManyMissingProjections {
+                missing,
+                total,
+                week: week.as_u16(),
+                max_share: self.max_missing,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// One player's row, already in the site's conventions
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportRow {
+    pub name: String,
+    pub position: String,
+    pub team: Option<String>,
+    pub opponent: Option<String>,
+    pub projection: f64,
+    pub floor: f64,
+    pub ceiling: f64,
+    pub confidence: f64,
+}
+
+impl ExportRow {
+    /// Row for an estimate; `team` and `opponent` are ESPN abbreviations
+    pub fn new(
+        estimate: &PerformanceEstimate,
+        team: Option<&str>,
+        opponent: Option<&str>,
+        site: ExportSite,
+    ) -> Self {
+        let projection = estimate.estimated_points;
+        let spread = FLOOR_CEILING_Z
+            * estimate
+                .error_std
+                .unwrap_or(DEFAULT_STD_FRACTION * projection);
+        Self {
+            name: site_name(site, &estimate.name, &estimate.position),
+            position: site_position(site, &estimate.position).to_string(),
+            team: team.map(|abbrev| site_team(site, abbrev).to_string()),
+            opponent: opponent.map(|abbrev| site_team(site, abbrev).to_string()),
+            projection,
+            floor: (projection - spread).max(0.0),
+            ceiling: projection + spread,
+            confidence: estimate.confidence,
+        }
+    }
+}
+
+/// Player name as the site lists it; defenses become "Team Name D/ST"
+pub fn site_name(site: ExportSite, name: &str, position: &str) -> String {
+    if is_defense(position) {
+        let team = name
+            .trim()
+            .trim_end_matches("D/ST")
+            .trim_end_matches("DST")
+            .trim_end();
+        return format!("{} D/ST", team);
+    }
+    match site {
+        ExportSite::Espn => name.to_string(),
+        ExportSite::Fanduel => {
+            let mut words: Vec<&str> = name.split_whitespace().collect();
+            if words.len() > 2 && words.last().is_some_and(|w| NAME_SUFFIXES.contains(w)) {
+                words.pop();
+            }
+            words.join(" ")
+        }
+        ExportSite::Draftkings => name.replace('.', ""),
+    }
+}
+
+/// Position label the site uses
+pub fn site_position(site: ExportSite, position: &str) -> &str {
+    if !is_defense(position) {
+        return position;
+    }
+    match site {
+        ExportSite::Espn => "D/ST",
+        ExportSite::Fanduel => "D",
+        ExportSite::Draftkings => "DST",
+    }
+}
+
+/// NFL team abbreviation the site uses for an ESPN abbreviation
+pub fn site_team(site: ExportSite, abbrev: &str) -> &str {
+    match (site, abbrev) {
+        (ExportSite::Fanduel, "JAX") => "JAC",
+        (ExportSite::Fanduel | ExportSite::Draftkings, "WSH") => "WAS",
+        _ => abbrev,
+    }
+}
+
+fn is_defense(position: &str) -> bool {
+    matches!(position, "D/ST" | "DST" | "DEF")
+}
+
+/// Write the header and one line per row, returning the number of rows
+pub fn write_projections_csv<W: Write>(mut writer: W, rows: &[ExportRow]) -> Result<usize> {
+    writeln!(writer, "{}", CSV_HEADER.join(","))?;
+    for row in rows {
+        writeln!(
+            writer,
+            "{},{},{},{},{:.2},{:.2},{:.2},{:.2}",
+            csv_field(&row.name),
+            csv_field(&row.position),
+            csv_field(row.team.as_deref().unwrap_or_default()),
+            csv_field(row.opponent.as_deref().unwrap_or_default()),
+            row.projection,
+            row.floor,
+            row.ceiling,
+            row.confidence
+        )?;
+    }
+    writer.flush()?;
+    Ok(rows.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_espn_names_are_unchanged() {
+        let site = ExportSite::Espn;
+        assert_eq!(
+            site_name(site, "Marvin Harrison Jr.", "WR"),
+            "Marvin Harrison Jr."
+        );
+        assert_eq!(site_name(site, "A.J. Brown", "WR"), "A.J. Brown");
+        assert_eq!(site_name(site, "Bills D/ST", "D/ST"), "Bills D/ST");
+        assert_eq!(site_position(site, "D/ST"), "D/ST");
+        assert_eq!(site_team(site, "WSH"), "WSH");
+        assert_eq!(site_team(site, "JAX"), "JAX");
+    }
+
+    #[test]
+    fn test_fanduel_drops_suffixes() {
+        let site = ExportSite::Fanduel;
+        assert_eq!(
+            site_name(site, "Marvin Harrison Jr.", "WR"),
+            "Marvin Harrison"
+        );
+        assert_eq!(
+            site_name(site, "Michael Pittman Jr", "WR"),
+            "Michael Pittman"
+        );
+        assert_eq!(
+            site_name(site, "Patrick Mahomes II", "QB"),
+            "Patrick Mahomes"
+        );
+        assert_eq!(site_name(site, "A.J. Brown", "WR"), "A.J. Brown");
+        // A two-word name is never cut down to one
+        assert_eq!(site_name(site, "Mark II", "K"), "Mark II");
+        assert_eq!(site_name(site, "Bills D/ST", "D/ST"), "Bills D/ST");
+        assert_eq!(site_position(site, "D/ST"), "D");
+        assert_eq!(site_position(site, "WR"), "WR");
+        assert_eq!(site_team(site, "JAX"), "JAC");
+        assert_eq!(site_team(site, "WSH"), "WAS");
+    }
+
+    #[test]
+    fn test_draftkings_drops_periods() {
+        let site = ExportSite::Draftkings;
+        assert_eq!(site_name(site, "A.J. Brown", "WR"), "AJ Brown");
+        assert_eq!(
+            site_name(site, "Marvin Harrison Jr.", "WR"),
+            "Marvin Harrison Jr"
+        );
+        assert_eq!(site_name(site, "Bills DST", "D/ST"), "Bills D/ST");
+        assert_eq!(site_position(site, "D/ST"), "DST");
+        assert_eq!(site_team(site, "WSH"), "WAS");
+        assert_eq!(site_team(site, "JAX"), "JAX");
+    }
+
+    #[test]
+    fn test_check_missing() {
+        let export =
+            ProjectionExport::new(PathBuf::from("out.csv"), ExportSite::Espn, 0.25).unwrap();
+        assert!(export.check_missing(1, 4, Week::new(3)).is_ok());
+        assert!(export.check_missing(0, 0, Week::new(3)).is_ok());
+        let err = export.check_missing(2, 4, Week::new(3)).unwrap_err();
+        assert!(err.to_string().starts_with("2 of 4 players"), "{}", err);
+        assert!(ProjectionExport::new(PathBuf::from("out.csv"), ExportSite::Espn, 1.5).is_err());
+    }
+
+    #[test]
+    fn test_write_projections_csv() {
+        let rows = [ExportRow {
+            name: "Smith, \"Bo\"".to_string(),
+            position: "RB".to_string(),
+            team: Some("BUF".to_string()),
+            opponent: None,
+            projection: 12.346,
+            floor: 4.0,
+            ceiling: 20.5,
+            confidence: 0.7,
+        }];
+        let mut out = Vec::new();
+        assert_eq!(write_projections_csv(&mut out, &rows).unwrap(), 1);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "name,position,team,opponent,projection,floor,ceiling,confidence\n\
+             \"Smith, \"\"Bo\"\"\",RB,BUF,,12.35,4.00,20.50,0.70\n"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Roster command implementation
+//!
+//! Prints fantasy team rosters for a week: every player with their lineup slot,
+//! position and the week's stored projected and actual points.
+
+use crate::{
+    cli::{
+        render::{Align, Column, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH},
+        types::filters::FantasyTeamFilter,
+    },
+    espn::{http::get_league_roster_data, lineup::slot_label, types::team_display_name},
+    storage::{PlayerDatabase, RosterPlayer},
+    LeagueId, Result, Season, Week,
+};
+
+use super::{
+    league_data::{resolve_league_id, validate_season},
+    player_filters::resolve_fantasy_team_filter,
+};
+
+/// Handle the roster command
+pub async fn handle_roster(
+    league_id: Option<LeagueId>,
+    season: Season,
+    week: Week,
+    team_filter: Option<FantasyTeamFilter>,
+    refresh: bool,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
+    let mut db = PlayerDatabase::new()?;
+
+    // Snapshot the week's rosters; fall back to a stored snapshot if ESPN is unavailable
+    match get_league_roster_data(false, league_id, season, Some(week), refresh).await {
+        Ok((roster_data, _)) => {
+            db.save_roster_snapshot(league_id, season, week, &roster_data)?;
+            db.record_team_names(league_id, season, week, &roster_data)?;
+        }
+        Err(e) => {
+            if !as_json {
+                println!(
+                    "⚠ Could not fetch week {} rosters, using stored snapshot: {}",
+                    week.as_u16(),
+                    e
+                );
+            }
+        }
+    }
+
+    let mut rosters = db.team_rosters(league_id, season, week)?;
+    if let Some(filter) = &team_filter {
+        let filter = resolve_fantasy_team_filter(&db, league_id, season, filter)?;
+        rosters.retain(|roster| filter.matches(roster.team_id, roster.team_name.as_deref()));
+    }
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&rosters)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    println!("Rosters - Season {} Week {}", season, week);
+    if rosters.is_empty() {
+        println!();
+        println!("No roster snapshot found for this week.");
+        return Ok(());
+    }
+    for roster in &rosters {
+        println!();
+        println!(
+            "{}",
+            team_display_name(roster.team_id, roster.team_name.as_deref())
+        );
+        print_roster(&roster.players);
+    }
+    Ok(())
+}
+
+fn print_roster(players: &[RosterPlayer]) {
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.1}", points),
+        None => "--".to_string(),
+    };
+
+    let mut table = Table::new(vec![
+        Column::fixed("Slot", Align::Left),
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Proj", Align::Right),
+        Column::fixed("Pts", Align::Right),
+    ]);
+    for player in players {
+        table.add_row(vec![
+            slot_label(player.lineup_slot_id),
+            player
+                .player_name
+                .clone()
+                .unwrap_or_else(|| format!("Player {}", player.player_id.as_i64())),
+            player.position.clone().unwrap_or_default(),
+            format_points(player.projected_points),
+            format_points(player.points),
+        ]);
+    }
+    table.print();
+}
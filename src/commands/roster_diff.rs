@@ -0,0 +1,135 @@
+//! Roster diff command implementation
+//!
+//! Compares each fantasy team's roster between two weeks, listing adds, drops
+//! and starter/bench/IR moves with the points those players scored in between.
+
+use crate::{
+    cli::types::filters::FantasyTeamFilter,
+    error::EspnError,
+    espn::{http::get_league_roster_data, types::team_display_name},
+    storage::{PlayerDatabase, RosterChange},
+    LeagueId, Result, Season, Week,
+};
+
+use super::{
+    league_data::{resolve_league_id, validate_season},
+    player_filters::resolve_fantasy_team_filter,
+};
+
+/// Handle the roster diff command
+pub async fn handle_roster_diff(
+    league_id: Option<LeagueId>,
+    season: Season,
+    from_week: Week,
+    to_week: Week,
+    team_filter: Option<FantasyTeamFilter>,
+    refresh: bool,
+    as_json: bool,
+) -> Result<()> {
+    if from_week.as_u16() >= to_week.as_u16() {
+        return Err(EspnError::InvalidWeekRange {
+            from: from_week.as_u16(),
+            to: to_week.as_u16(),
+        });
+    }
+    let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
+    let mut db = PlayerDatabase::new()?;
+
+    // Snapshot both weeks; fall back to whatever is already stored if ESPN is unavailable
+    for week in [from_week, to_week] {
+        match get_league_roster_data(false, league_id, season, Some(week), refresh).await {
+            Ok((roster_data, _)) => {
+                db.save_roster_snapshot(league_id, season, week, &roster_data)?;
+                db.record_team_names(league_id, season, week, &roster_data)?;
+            }
+            Err(e) => {
+                if !as_json {
+                    println!(
+                        "⚠ Could not fetch week {} rosters, using stored snapshot: {}",
+                        week.as_u16(),
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    let mut diffs = db.get_roster_diff(league_id, season, from_week, to_week)?;
+    if let Some(filter) = &team_filter {
+        let filter = resolve_fantasy_team_filter(&db, league_id, season, filter)?;
+        diffs.retain(|diff| filter.matches(diff.team_id, diff.team_name.as_deref()));
+    }
+    let current_names = db.get_current_team_names(league_id, season)?;
+    for diff in &mut diffs {
+        if let Some(name) = current_names.get(&diff.team_id) {
+            diff.team_name = Some(name.clone());
+        }
+    }
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&diffs)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    println!(
+        "Roster changes - Season {} Week {} → Week {}",
+        season, from_week, to_week
+    );
+    if diffs.is_empty() {
+        println!("No roster snapshots found for these weeks.");
+        return Ok(());
+    }
+
+    for diff in &diffs {
+        println!();
+        println!(
+            "{}",
+            team_display_name(diff.team_id, diff.team_name.as_deref())
+        );
+        if diff.is_empty() {
+            println!("  No changes");
+            continue;
+        }
+        for change in &diff.added {
+            println!("  + {}", format_change(change));
+        }
+        for change in &diff.dropped {
+            println!("  - {}", format_change(change));
+        }
+        for change in &diff.moved {
+            println!("  ~ {}", format_change(change));
+        }
+    }
+
+    Ok(())
+}
+
+fn format_change(change: &RosterChange) -> String {
+    let name = change
+        .player_name
+        .clone()
+        .unwrap_or_else(|| format!("Player {}", change.player_id.as_i64()));
+    let slots = match (change.from_slot, change.to_slot) {
+        (Some(from), Some(to)) => format!("{} → {}", from, to),
+        (None, Some(to)) => to.to_string(),
+        (Some(from), None) => from.to_string(),
+        (None, None) => String::new(),
+    };
+    let slots = match &change.position {
+        Some(position) => format!("{}, {}", position, slots),
+        None => slots,
+    };
+    let weekly = change
+        .weekly_points
+        .iter()
+        .map(|p| p.map_or_else(|| "--".to_string(), |pts| format!("{:.1}", pts)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{} ({}) {:.1} pts [{}]",
+        name, slots, change.total_points, weekly
+    )
+}
@@ -0,0 +1,179 @@
+//! Season records command implementation
+//!
+//! Summarizes weekly high/low scores, streaks, blowouts and per-team records
+//! from the league schedule.
+
+use crate::{
+    espn::{
+        cache_settings::load_or_fetch_league_settings, http::get_league_schedule,
+        types::team_display_name,
+    },
+    storage::{MatchupMargin, PlayerDatabase, TeamWeekScore},
+    LeagueId, Result, Season, Week,
+};
+
+use super::league_data::{resolve_league_id, validate_season};
+
+/// Handle the season records command
+pub async fn handle_season_records(
+    league_id: Option<LeagueId>,
+    season: Season,
+    refresh: bool,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
+
+    if !as_json {
+        println!("Loading league schedule...");
+    }
+    let schedule = get_league_schedule(league_id, season, refresh).await?;
+    let settings = load_or_fetch_league_settings(league_id, false, season).await?;
+
+    let mut db = PlayerDatabase::new()?;
+    db.upsert_matchups(league_id, season, &schedule.schedule)?;
+    db.save_matchup_periods(
+        league_id,
+        season,
+        &settings.schedule_settings.matchup_periods,
+    )?;
+
+    let mut records = db.get_season_records(league_id, season)?;
+    records.apply_team_names(&schedule.team_names());
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&records)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    println!("Season Records - {}", season);
+    if records.included_weeks.is_empty() {
+        println!("No completed weeks found for this season.");
+        return Ok(());
+    }
+    println!("Weeks included: {}", format_weeks(&records.included_weeks));
+    if !records.excluded_weeks.is_empty() {
+        println!(
+            "Weeks excluded (incomplete): {}",
+            format_weeks(&records.excluded_weeks)
+        );
+    }
+    for (matchup_period, weeks) in &records.multi_week_periods {
+        println!(
+            "Week {} is a multi-week matchup (weeks {}); its scores are combined totals",
+            matchup_period,
+            format_weeks(weeks)
+        );
+    }
+    println!();
+
+    if let Some(score) = &records.highest_score {
+        println!("Highest score:  {}", format_score(score));
+    }
+    if let Some(score) = &records.lowest_score {
+        println!("Lowest score:   {}", format_score(score));
+    }
+    if let Some(streak) = &records.longest_win_streak {
+        println!(
+            "Longest streak: {} - {} wins (weeks {}-{})",
+            team_display_name(streak.team_id, streak.team_name.as_deref()),
+            streak.length,
+            streak.start_week,
+            streak.end_week
+        );
+    }
+    if let Some(margin) = &records.biggest_blowout {
+        println!("Biggest blowout: {}", format_margin(margin));
+    }
+    if let Some(margin) = &records.closest_game {
+        println!("Closest game:    {}", format_margin(margin));
+    }
+
+    println!();
+    println!(
+        "{:<24} {:<8} {:<18} {:<18}",
+        "Team", "W-L-T", "Best Week", "Worst Week"
+    );
+    println!(
+        "{:<24} {:<8} {:<18} {:<18}",
+        "----", "-----", "---------", "----------"
+    );
+    for team in &records.teams {
+        let week_str = |score: &Option<TeamWeekScore>| {
+            score
+                .as_ref()
+                .map(|s| format!("{:.1} (Wk {})", s.points, s.week))
+                .unwrap_or_else(|| "--".to_string())
+        };
+        println!(
+            "{:<24} {:<8} {:<18} {:<18}",
+            team_display_name(team.team_id, team.team_name.as_deref())
+                .chars()
+                .take(24)
+                .collect::<String>(),
+            format!("{}-{}-{}", team.wins, team.losses, team.ties),
+            week_str(&team.best_week),
+            week_str(&team.worst_week)
+        );
+    }
+
+    Ok(())
+}
+
+fn format_score(score: &TeamWeekScore) -> String {
+    format!(
+        "{} - {:.1} pts (Week {})",
+        team_display_name(score.team_id, score.team_name.as_deref()),
+        score.points,
+        score.week
+    )
+}
+
+fn format_margin(margin: &MatchupMargin) -> String {
+    format!(
+        "{} {:.1} def. {} {:.1} by {:.1} (Week {})",
+        team_display_name(margin.winner.team_id, margin.winner.team_name.as_deref()),
+        margin.winner.points,
+        team_display_name(margin.loser.team_id, margin.loser.team_name.as_deref()),
+        margin.loser.points,
+        margin.margin,
+        margin.week
+    )
+}
+
+/// Format a sorted list of weeks compactly, e.g. `1-4, 6`
+pub fn format_weeks(weeks: &[Week]) -> String {
+    let mut ranges: Vec<(Week, Week)> = Vec::new();
+    for &week in weeks {
+        match ranges.last_mut() {
+            Some((_, end)) if end.next() == Some(week) => *end = week,
+            _ => ranges.push((week, week)),
+        }
+    }
+
+    ranges
+        .into_iter()
+        .map(|(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_weeks_collapses_ranges() {
+        let weeks: Vec<Week> = [1, 2, 3, 5, 7, 8].into_iter().map(Week::new).collect();
+        assert_eq!(format_weeks(&weeks), "1-3, 5, 7-8");
+        assert_eq!(format_weeks(&[]), "");
+        assert_eq!(format_weeks(&[Week::new(4)]), "4");
+    }
+}
@@ -0,0 +1,141 @@
+//! Stats history command implementation
+//!
+//! Lists every stored week of one player's season: ESPN's projection, the actual
+//! points, how far apart they were, a running average and the player's injury and
+//! fantasy roster status that week, with a sparkline of the weekly points.
+
+use crate::{
+    cli::render::{render_sparkline, Align, Column, Table},
+    error::EspnError,
+    storage::{Player, PlayerDatabase, PlayerWeeklyStats},
+    LeagueId, PlayerId, Result, Season,
+};
+
+use super::{
+    league_data::resolve_league_id,
+    notes::{resolve_player, resolve_player_containing},
+    player_data::{roster_label, status_label},
+};
+
+/// Handle the stats history command
+pub fn handle_stats_history(
+    league_id: Option<LeagueId>,
+    query: &str,
+    season: Season,
+    ascii: bool,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let player_id = resolve_player_partial(&db, query)?;
+    let stats = db.get_player_season_stats(league_id, player_id, season)?;
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&stats)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    let player = db
+        .get_all_players()?
+        .into_iter()
+        .find(|p| p.player_id == player_id);
+    print_history(player.as_ref(), player_id, season, &stats, ascii);
+    Ok(())
+}
+
+/// Resolve a player by ID or exact name, then by a name containing the query
+fn resolve_player_partial(db: &PlayerDatabase, query: &str) -> Result<PlayerId> {
+    match resolve_player(db, query) {
+        Err(EspnError::PlayerNotFound { .. }) => {}
+        resolved => return resolved,
+    }
+    resolve_player_containing(&db.get_all_players()?, query)
+}
+
+/// Whether a week's actual points count toward the running average
+fn counts_toward_average(stats: &PlayerWeeklyStats) -> bool {
+    stats.played != Some(false)
+}
+
+fn print_history(
+    player: Option<&Player>,
+    player_id: PlayerId,
+    season: Season,
+    stats: &[PlayerWeeklyStats],
+    ascii: bool,
+) {
+    match player {
+        Some(player) => println!(
+            "{} ({}, {}) - Season {}",
+            player.name,
+            player.position,
+            player.team.as_deref().unwrap_or("FA"),
+            season
+        ),
+        None => println!("Player {} - Season {}", player_id, season),
+    }
+    if stats.is_empty() {
+        println!("No stored weeks for this season; run update-all-data first.");
+        return;
+    }
+
+    let format_points = |points: Option<f64>| match points {
+        Some(points) => format!("{:.1}", points),
+        None => "--".to_string(),
+    };
+
+    let mut table = Table::new(vec![
+        Column::fixed("Week", Align::Right),
+        Column::fixed("Proj", Align::Right),
+        Column::fixed("Pts", Align::Right),
+        Column::fixed("Delta", Align::Right),
+        Column::fixed("Avg", Align::Right),
+        Column::fixed("Status", Align::Left),
+        Column::rest("Roster"),
+    ]);
+    let (mut total, mut games) = (0.0, 0);
+    for week in stats {
+        if let Some(actual) = week.actual_points.filter(|_| counts_toward_average(week)) {
+            total += actual;
+            games += 1;
+        }
+        let delta = match (week.actual_points, week.projected_points) {
+            (Some(actual), Some(projected)) => format!("{:+.1}", actual - projected),
+            _ => "--".to_string(),
+        };
+        let status = if week.played == Some(false) {
+            "DNP".to_string()
+        } else {
+            status_label(week.injury_status.as_ref(), week.injured)
+        };
+        table.add_row(vec![
+            week.week.to_string(),
+            format_points(week.projected_points),
+            format_points(week.actual_points),
+            delta,
+            format_points((games > 0).then(|| total / games as f64)),
+            status,
+            roster_label(week.is_rostered, week.fantasy_team_name.as_deref()),
+        ]);
+    }
+    table.print();
+
+    let points: Vec<Option<f64>> = stats
+        .iter()
+        .map(|week| week.actual_points.filter(|_| counts_toward_average(week)))
+        .collect();
+    let (min, max) = points
+        .iter()
+        .flatten()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    if min.is_finite() {
+        println!();
+        println!(
+            "Trend: {}",
+            render_sparkline(&points, min.min(0.0), max, ascii)
+        );
+    }
+}
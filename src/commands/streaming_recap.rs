@@ -0,0 +1,190 @@
+//! Streaming recap command implementation
+//!
+//! Looks back over a fantasy team's season at kicker and D/ST: the points its
+//! actual starts earned, the best free agent each week ("perfect streaming") and
+//! holding the position's season-long top scorer, from stored actuals and roster
+//! snapshots.
+
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        render::{Align, Column, Table},
+        types::filters::FantasyTeamFilter,
+    },
+    error::EspnError,
+    espn::types::team_display_name,
+    storage::{PlayerDatabase, StreamingPick, StreamingRecap, StreamingVerdict, TeamWeekPlayer},
+    LeagueId, Result, Season,
+};
+
+use super::{league_data::resolve_league_id, player_filters::resolve_fantasy_team_filter};
+
+/// Positions the recap covers, as stored in the players table
+const STREAMING_POSITIONS: [&str; 2] = ["K", "D/ST"];
+
+const NAME_MAX_WIDTH: usize = 24;
+const NAME_MIN_WIDTH: usize = 10;
+
+/// Streaming recaps for one fantasy team
+#[derive(Debug, Serialize)]
+pub struct StreamingRecapReport {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub season: Season,
+    pub positions: Vec<StreamingRecap>,
+}
+
+/// Handle the streaming recap command
+pub fn handle_streaming_recap(
+    league_id: Option<LeagueId>,
+    season: Season,
+    team: FantasyTeamFilter,
+    as_json: bool,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    let db = PlayerDatabase::new()?;
+    let team_id = match resolve_fantasy_team_filter(&db, league_id, season, &team)? {
+        FantasyTeamFilter::Id(id) => id,
+        FantasyTeamFilter::Ids(ids) if !ids.is_empty() => ids[0],
+        _ => {
+            return Err(EspnError::TeamNotFound {
+                team: team.to_string(),
+            })
+        }
+    };
+
+    let report = StreamingRecapReport {
+        team_id,
+        team_name: db
+            .get_current_team_names(league_id, season)?
+            .remove(&team_id),
+        season,
+        positions: STREAMING_POSITIONS
+            .iter()
+            .map(|position| db.streaming_recap(league_id, season, team_id, position))
+            .collect::<anyhow::Result<_>>()?,
+    };
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&report)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    let team_name = team_display_name(team_id, report.team_name.as_deref());
+    println!("Streaming Recap - {} - Season {}", team_name, season);
+    if report.positions.iter().all(|r| r.weeks.is_empty()) {
+        println!();
+        println!("No roster snapshots stored for this team; run `espn-ffl update-all-data` first.");
+        return Ok(());
+    }
+    for recap in &report.positions {
+        println!();
+        print_recap(recap);
+    }
+    Ok(())
+}
+
+fn print_recap(recap: &StreamingRecap) {
+    match &recap.season_leader {
+        Some(leader) => println!(
+            "{} - season leader: {} ({:.1} pts)",
+            recap.position,
+            pick_name(leader),
+            leader.points
+        ),
+        None => println!("{} - no stored actuals", recap.position),
+    }
+
+    let mut table = Table::new(vec![
+        Column::fixed("Week", Align::Right),
+        Column::flex("Started", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pts", Align::Right),
+        Column::flex("Best Free Agent", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("FA Pts", Align::Right),
+        Column::fixed("Leader Pts", Align::Right),
+    ]);
+    for week in &recap.weeks {
+        let started = if week.started.is_empty() {
+            "(none)".to_string()
+        } else {
+            week.started
+                .iter()
+                .map(started_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        table.add_row(vec![
+            week.week.to_string(),
+            started,
+            format!("{:.1}", week.started_points),
+            week.best_free_agent
+                .as_ref()
+                .map_or_else(String::new, pick_name),
+            week.best_free_agent
+                .as_ref()
+                .map_or_else(String::new, |p| format!("{:.1}", p.points)),
+            format!("{:.1}", week.season_leader_points),
+        ]);
+    }
+    table.add_row(vec![
+        "Total".to_string(),
+        String::new(),
+        format!("{:.1}", recap.started_total),
+        String::new(),
+        format!("{:.1}", recap.perfect_streaming_total),
+        format!("{:.1}", recap.holding_total),
+    ]);
+    table.print();
+
+    if let Some(verdict) = verdict_line(recap) {
+        println!("{}", verdict);
+    }
+}
+
+/// One-line summary of whether holding the season leader would have paid off
+pub fn verdict_line(recap: &StreamingRecap) -> Option<String> {
+    let verdict = recap.verdict?;
+    let leader = recap
+        .season_leader
+        .as_ref()
+        .map_or_else(|| "the season leader".to_string(), pick_name);
+    let captured = if recap.perfect_streaming_total > 0.0 {
+        format!(
+            "; your starts captured {:.0}% of perfect streaming",
+            recap.started_total / recap.perfect_streaming_total * 100.0
+        )
+    } else {
+        String::new()
+    };
+    let diff = (recap.holding_total - recap.started_total).abs();
+    Some(match verdict {
+        StreamingVerdict::Hold => format!(
+            "Verdict: hold - {} would have scored {:.1} more than your starts{}",
+            leader, diff, captured
+        ),
+        StreamingVerdict::Stream => format!(
+            "Verdict: stream - your starts scored {:.1} more than holding {}{}",
+            diff, leader, captured
+        ),
+    })
+}
+
+fn pick_name(pick: &StreamingPick) -> String {
+    pick.player_name
+        .clone()
+        .unwrap_or_else(|| format!("Player {}", pick.player_id.as_i64()))
+}
+
+fn started_name(player: &TeamWeekPlayer) -> String {
+    let name = player
+        .player_name
+        .clone()
+        .unwrap_or_else(|| format!("Player {}", player.player_id.as_i64()));
+    if player.points.is_none() {
+        format!("{} (no stats)", name)
+    } else {
+        name
+    }
+}
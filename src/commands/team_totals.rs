@@ -0,0 +1,197 @@
+//! Team totals command implementation
+//!
+//! Prints each fantasy team's starter and bench points for a completed week, with
+//! its top and bottom scoring starter, from the stored actuals and roster snapshot.
+
+use std::path::Path;
+
+use crate::{
+    cli::{
+        html::{HtmlReport, HtmlSection},
+        render::{Align, Column, Table},
+    },
+    espn::{http::get_league_roster_data, lineup::slot_label, types::team_display_name},
+    storage::{PlayerDatabase, TeamWeekPlayer, TeamWeekTotal},
+    LeagueId, Result, Season, Week,
+};
+
+use super::league_data::{resolve_league_id, validate_season};
+
+/// Team names get no more room than this before they are cut
+const TEAM_MAX_WIDTH: usize = 24;
+const TEAM_MIN_WIDTH: usize = 10;
+
+/// Handle the team totals command
+pub async fn handle_team_totals(
+    league_id: Option<LeagueId>,
+    season: Season,
+    week: Week,
+    refresh: bool,
+    as_json: bool,
+    share: Option<&Path>,
+) -> Result<()> {
+    let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
+    let mut db = PlayerDatabase::new()?;
+
+    // Snapshot the week's lineups; fall back to a stored snapshot if ESPN is unavailable
+    match get_league_roster_data(false, league_id, season, Some(week), refresh).await {
+        Ok((roster_data, _)) => {
+            db.save_roster_snapshot(league_id, season, week, &roster_data)?;
+            db.record_team_names(league_id, season, week, &roster_data)?;
+        }
+        Err(e) => {
+            if !as_json {
+                println!(
+                    "⚠ Could not fetch week {} rosters, using stored snapshot: {}",
+                    week.as_u16(),
+                    e
+                );
+            }
+        }
+    }
+
+    let totals = db.team_week_totals(league_id, season, week)?;
+    if let Some(path) = share {
+        share_report(&totals, season, week).write(path)?;
+    }
+
+    if as_json {
+        println!("{}", serde_json::to_string_pretty(&totals)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    println!("Team Totals - Season {} Week {}", season, week);
+    println!();
+    if totals.is_empty() {
+        println!("No roster snapshot found for this week.");
+    } else {
+        print_totals(&totals, week);
+    }
+    if let Some(path) = share {
+        println!();
+        println!("✓ Wrote share report to {}", path.display());
+    }
+    Ok(())
+}
+
+/// The week's totals as a page to share with the league
+fn share_report(totals: &[TeamWeekTotal], season: Season, week: Week) -> HtmlReport {
+    let mut section = HtmlSection::new(
+        "Team Totals",
+        &[
+            ("Team", Align::Left),
+            ("Starters", Align::Right),
+            ("Bench", Align::Right),
+            ("Top Scorer", Align::Left),
+            ("Bottom Scorer", Align::Left),
+        ],
+    );
+    for total in totals {
+        section.add_row(vec![
+            team_name(total),
+            format!("{:.1}", total.starter_points),
+            format!("{:.1}", total.bench_points),
+            total
+                .top_scorer
+                .as_ref()
+                .map_or_else(String::new, scorer_cell),
+            total
+                .bottom_scorer
+                .as_ref()
+                .map_or_else(String::new, scorer_cell),
+        ]);
+        if !total.is_complete() {
+            section.add_note(format!(
+                "{}: partial total, no stored points for {}",
+                team_name(total),
+                pluralize_starters(total.missing_starters.len())
+            ));
+        }
+    }
+    if totals.is_empty() {
+        section.add_note("No roster snapshot found for this week.");
+    }
+
+    let mut report = HtmlReport::new(
+        format!("Week {} Recap", week),
+        format!("Season {} Week {}", season, week),
+    );
+    report.add_section(section);
+    report
+}
+
+fn team_name(total: &TeamWeekTotal) -> String {
+    team_display_name(total.team_id, total.team_name.as_deref())
+}
+
+fn print_totals(totals: &[TeamWeekTotal], week: Week) {
+    let mut table = Table::new(vec![
+        Column::flex("Team", TEAM_MIN_WIDTH, TEAM_MAX_WIDTH),
+        Column::fixed("Starters", Align::Right),
+        Column::fixed("Bench", Align::Right),
+        Column::fixed("Top Scorer", Align::Left),
+        Column::rest("Bottom Scorer"),
+    ]);
+    for total in totals {
+        table.add_row(vec![
+            team_name(total),
+            format!("{:.1}", total.starter_points),
+            format!("{:.1}", total.bench_points),
+            total
+                .top_scorer
+                .as_ref()
+                .map_or_else(String::new, scorer_cell),
+            total
+                .bottom_scorer
+                .as_ref()
+                .map_or_else(String::new, scorer_cell),
+        ]);
+        if !total.is_complete() {
+            let missing: Vec<String> = total.missing_starters.iter().map(player_name).collect();
+            table.add_detail(format!(
+                "⚠ incomplete: no stored points for {} ({})",
+                pluralize_starters(missing.len()),
+                missing.join(", ")
+            ));
+        }
+    }
+    table.print();
+
+    let incomplete = totals.iter().filter(|t| !t.is_complete()).count();
+    if incomplete > 0 {
+        println!();
+        println!(
+            "{} of {} teams have partial totals; store the week's actuals with \
+             `espn-ffl player-data --week {}` and rerun.",
+            incomplete,
+            totals.len(),
+            week
+        );
+    }
+}
+
+fn player_name(player: &TeamWeekPlayer) -> String {
+    player
+        .player_name
+        .clone()
+        .unwrap_or_else(|| format!("Player {}", player.player_id.as_i64()))
+}
+
+fn scorer_cell(player: &TeamWeekPlayer) -> String {
+    format!(
+        "{} ({}) {:.1}",
+        player_name(player),
+        slot_label(player.lineup_slot_id),
+        player.points.unwrap_or(0.0)
+    )
+}
+
+fn pluralize_starters(count: usize) -> String {
+    if count == 1 {
+        "1 starter".to_string()
+    } else {
+        format!("{} starters", count)
+    }
+}
@@ -1,48 +1,238 @@
 //! Update all player data command for bulk data population
 //!
 //! This command efficiently updates all player data (both actual and projected)
-//! for multiple weeks by reusing the existing player-data command logic.
+//! for multiple weeks by reusing the existing player-data command logic. A
+//! failed week is reported and skipped so one bad response doesn't throw away
+//! the rest of a long backfill; `--fail-fast` stops at the first failure instead.
+//!
+//! Before fetching anything it prints what the update will do and, at a
+//! terminal, asks for confirmation unless `--yes` was passed.
+
+use std::{io::IsTerminal, path::PathBuf};
+
+use dialoguer::Confirm;
 
-use crate::{LeagueId, Result, Season, Week};
+use crate::{
+    error::EspnError,
+    espn::{
+        cache_settings::has_stored_league_settings,
+        credentials::{resolve_credentials, CredentialSource},
+    },
+    storage::PlayerDatabase,
+    LeagueId, Result, Season, Week, WeekRange,
+};
 
 use super::{
     common::CommandParamsBuilder,
-    league_data::resolve_league_id,
+    league_data::{resolve_league_id, validate_season},
     player_data::{handle_player_data, PlayerDataParams},
 };
 
-/// Update all player data (actual and projected) for weeks 1 through the specified week
+/// Sources fetched for each week, with whether they are projections
+const SOURCES: [(&str, bool); 2] = [("actual", false), ("projected", true)];
+
+/// ESPN requests per week and source: the week's rosters, then its players
+pub const REQUESTS_PER_SOURCE: usize = 2;
+
+/// What an update is about to fetch, printed before it starts
+#[derive(Debug, Clone, PartialEq)]
+pub struct UpdatePlan {
+    pub season: Season,
+    pub league_id: LeagueId,
+    pub weeks: WeekRange,
+    /// Weeks that already have both actual and projected points stored
+    pub stored_weeks: WeekRange,
+    /// No stored league settings, so they are fetched once up front
+    pub fetch_settings: bool,
+    /// Where the ESPN cookies come from, `None` when requests go out without them
+    pub credentials: Option<CredentialSource>,
+    pub database: PathBuf,
+}
+
+impl UpdatePlan {
+    /// Look up stored weeks, cached settings and credentials for an update
+    ///
+    /// Reads the database without creating it, so declining the update leaves
+    /// nothing behind.
+    pub fn new(season: Season, league_id: LeagueId, weeks: WeekRange) -> Result<Self> {
+        let db = PlayerDatabase::open_existing()?;
+        let mut stored_weeks = Vec::new();
+        for week in &weeks {
+            let mut complete = true;
+            for (_, projected) in SOURCES {
+                complete &= db.has_data_for_week(
+                    season,
+                    week,
+                    Some(league_id),
+                    None,
+                    None,
+                    Some(projected),
+                )?;
+            }
+            if complete {
+                stored_weeks.push(week);
+            }
+        }
+        Ok(Self {
+            season,
+            league_id,
+            stored_weeks: stored_weeks.into_iter().collect(),
+            weeks,
+            fetch_settings: !has_stored_league_settings(league_id, season)?,
+            credentials: resolve_credentials()?.map(|(_, source)| source),
+            database: PlayerDatabase::database_path()?,
+        })
+    }
+
+    /// ESPN requests the update is expected to make
+    ///
+    /// Every week is fetched again, stored or not, so stored weeks still count;
+    /// they are listed separately in the summary.
+    pub fn estimated_requests(&self) -> usize {
+        self.weeks.len() * SOURCES.len() * REQUESTS_PER_SOURCE + usize::from(self.fetch_settings)
+    }
+
+    /// Summary lines shown before the update starts
+    pub fn summary(&self) -> Vec<String> {
+        let sources: Vec<&str> = SOURCES.iter().map(|(source, _)| *source).collect();
+        let stored = if self.stored_weeks.is_empty() {
+            "none".to_string()
+        } else {
+            format!(
+                "{} ({} of {}, fetched again to pick up stat corrections)",
+                self.stored_weeks,
+                self.stored_weeks.len(),
+                self.weeks.len()
+            )
+        };
+        let credentials = match &self.credentials {
+            Some(source) => format!("yes, from {}", source),
+            None => "no (public leagues only)".to_string(),
+        };
+        vec![
+            format!(
+                "Season {}, league {}",
+                self.season.as_u16(),
+                self.league_id.as_u32()
+            ),
+            format!("Weeks: {} ({} weeks)", self.weeks, self.weeks.len()),
+            format!("Sources: {}", sources.join(", ")),
+            format!("Already stored: {}", stored),
+            format!("Estimated ESPN requests: {}", self.estimated_requests()),
+            format!("Auth cookies: {}", credentials),
+            format!("Database: {}", self.database.display()),
+        ]
+    }
+}
+
+/// Ask before starting; without a terminal on stdin there is nobody to ask
+fn confirm_update() -> Result<bool> {
+    if !std::io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    // tarpaulin::skip - interactive prompt
+    Ok(Confirm::new()
+        .with_prompt("Start the update?")
+        .default(false)
+        .interact()
+        .map_err(|dialoguer::Error::IO(e)| e)?)
+}
+
+/// One week/source fetch that failed during an update
+#[derive(Debug)]
+pub struct UpdateFailure {
+    pub week: Week,
+    /// "actual" or "projected"
+    pub source: &'static str,
+    /// Short error category for the summary, e.g. "HTTP 500" or "database"
+    pub kind: String,
+    pub message: String,
+}
+
+impl UpdateFailure {
+    fn new(week: Week, source: &'static str, err: &EspnError) -> Self {
+        Self {
+            week,
+            source,
+            kind: error_kind(err),
+            message: err.to_string(),
+        }
+    }
+}
+
+/// Short category for an error, used to group failures in the summary
+fn error_kind(err: &EspnError) -> String {
+    if let Some(e) = err.http_error() {
+        return match e.status() {
+            Some(status) => format!("HTTP {}", status.as_u16()),
+            None if e.is_timeout() => "timeout".to_string(),
+            None if e.is_decode() => "bad response".to_string(),
+            None => "network".to_string(),
+        };
+    }
+    match err {
+        EspnError::Json(_) => "bad response".to_string(),
+        EspnError::Io(_) => "io".to_string(),
+        EspnError::Cache { .. } => "cache".to_string(),
+        EspnError::Database(_) => "database".to_string(),
+        EspnError::NoData => "no data".to_string(),
+        _ => "other".to_string(),
+    }
+}
+
+/// Update all player data (actual and projected) for the given weeks
 ///
 /// This command efficiently populates the database with complete historical data
 /// by calling the existing player-data command for both actual and projected data.
+/// Failures are collected and the remaining weeks still run; the summary at the end
+/// lists every failed week and source, and the command then returns
+/// [`EspnError::UpdateFailed`]. A week only counts as processed when both sources
+/// succeeded.
 ///
 /// # Arguments
 /// * `season` - The season year
-/// * `through_week` - Update data through this week (inclusive)
+/// * `weeks` - Weeks to update, in order
 /// * `league_id` - Optional league ID override
 /// * `verbose` - Show detailed progress information
+/// * `fail_fast` - Stop at the first failed fetch and return its error
+/// * `yes` - Skip the confirmation prompt after the summary
 pub async fn handle_update_all_data(
     season: Season,
-    through_week: Week,
+    weeks: WeekRange,
     league_id: Option<LeagueId>,
     verbose: bool,
+    fail_fast: bool,
+    yes: bool,
 ) -> Result<()> {
     let league_id = resolve_league_id(league_id)?;
+    validate_season(league_id, season).await?;
+
+    // tarpaulin::skip - console output
+    println!("About to update player data:");
+    for line in UpdatePlan::new(season, league_id, weeks.clone())?.summary() {
+        println!("  {}", line);
+    }
+    println!();
+    if !yes && !confirm_update()? {
+        println!("Update cancelled; nothing was fetched.");
+        return Ok(());
+    }
 
     if verbose {
         println!(
-            "Updating all player data for Season {} through Week {}",
+            "Updating all player data for Season {}, weeks {}",
             season.as_u16(),
-            through_week.as_u16()
+            weeks
         );
         println!("League ID: {}", league_id.as_u32());
+        println!("Default season: {}", Season::default());
     }
 
     let mut total_weeks_processed = 0;
+    let mut failures: Vec<UpdateFailure> = Vec::new();
 
-    // Process each week from 1 to through_week
-    for week_num in 1..=through_week.as_u16() {
-        let week = Week::new(week_num);
+    for week in &weeks {
+        let week_num = week.as_u16();
 
         if verbose {
             println!("\n--- Processing Week {} ---", week_num);
@@ -50,29 +240,36 @@ pub async fn handle_update_all_data(
             println!("Processing Week {}...", week_num);
         }
 
-        // Fetch actual data first
-        if verbose {
-            println!("Fetching actual player data...");
+        let mut week_complete = true;
+        for (source, projected) in SOURCES {
+            if verbose {
+                println!("Fetching {} player data...", source);
+            }
+            let params = PlayerDataParams::new(season, week, projected)
+                .with_league_id(league_id)
+                .with_refresh();
+            if let Err(err) = handle_player_data(params).await {
+                if fail_fast {
+                    return Err(err);
+                }
+                eprintln!("✗ Week {} {} data failed: {}", week_num, source, err);
+                failures.push(UpdateFailure::new(week, source, &err));
+                week_complete = false;
+            }
         }
-        let actual_params = PlayerDataParams::new(season, week, false)
-            .with_league_id(league_id)
-            .with_refresh();
-        handle_player_data(actual_params).await?;
 
-        // Fetch projected data
-        if verbose {
-            println!("Fetching projected player data...");
+        if week_complete {
+            total_weeks_processed += 1;
+            if verbose {
+                println!("✓ Week {} complete (actual + projected data)", week_num);
+            }
         }
-        let projected_params = PlayerDataParams::new(season, week, true)
-            .with_league_id(league_id)
-            .with_refresh();
-        handle_player_data(projected_params).await?;
-
-        total_weeks_processed += 1;
+    }
 
-        if verbose {
-            println!("✓ Week {} complete (actual + projected data)", week_num);
-        }
+    if !failures.is_empty() {
+        // tarpaulin::skip - console output
+        print_failure_summary(&failures, total_weeks_processed);
+        return Err(EspnError::UpdateFailed { failures });
     }
 
     println!("\n✓ Data update complete!");
@@ -80,11 +277,26 @@ pub async fn handle_update_all_data(
 
     if verbose {
         println!(
-            "\nDatabase now contains complete actual and projected data for weeks 1-{}",
-            through_week.as_u16()
+            "\nDatabase now contains complete actual and projected data for weeks {}",
+            weeks
         );
         println!("This data can be used for projection analysis and bias correction.");
     }
 
     Ok(())
 }
+
+fn print_failure_summary(failures: &[UpdateFailure], weeks_processed: usize) {
+    println!("\n✗ Data update finished with errors");
+    println!("Total weeks processed: {}", weeks_processed);
+    println!();
+    println!("{:>4}  {:<9}  {:<12}  Error", "Week", "Source", "Kind");
+    for failure in failures {
+        println!(
+            "{:>4}  {:<9}  {:<12}  {}",
+            failure.week, failure.source, failure.kind, failure.message
+        );
+    }
+    println!();
+    println!("Weeks that succeeded are stored; re-run update-all-data to retry the rest.");
+}
@@ -0,0 +1,418 @@
+//! Cache warming command implementation
+//!
+//! Prefetches everything in-game queries need for the current week (league
+//! settings, rosters, ownership, projections and both schedules) so they are
+//! served from the cache, and optionally checks stored coverage for the weeks
+//! already played.
+
+use crate::{
+    core::{
+        cache::{HttpPlayerDataCacheKey, ProScheduleCacheKey, ScheduleCacheKey, GLOBAL_CACHE},
+        is_fresh,
+    },
+    error::EspnError,
+    espn::{
+        cache_settings::{has_stored_league_settings, load_or_fetch_league_settings},
+        http::{
+            get_league_rosters_with_cache_status, get_league_schedule, get_league_status,
+            get_player_data, get_pro_schedule, roster_freshness_secs, CacheStatus,
+            PlayerDataRequest,
+        },
+    },
+    storage::{CoverageDataType, CoverageLevel, PlayerDatabase},
+    LeagueId, Result, Season, Week,
+};
+
+use super::league_data::{resolve_league_id, validate_season};
+use serde::Serialize;
+use std::time::Duration;
+
+/// Pause between network fetches so a warm-up doesn't burst requests at ESPN
+const FETCH_DELAY: Duration = Duration::from_millis(250);
+
+/// Share of a week's expected rows below which coverage counts as a gap (as in `db coverage`)
+const COVERAGE_PARTIAL_BELOW: f64 = 0.9;
+
+/// Configuration for the cache warming command
+#[derive(Debug)]
+pub struct WarmCacheParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    /// Refetch every item even when its cache is fresh
+    pub refresh: bool,
+    /// Check stored data for the weeks before the current one
+    pub check_coverage: bool,
+    pub as_json: bool,
+}
+
+/// Outcome for one cached item
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WarmStatus {
+    /// Already cached and within its freshness window; not fetched
+    Fresh,
+    /// Fetched from ESPN and written to the cache
+    Fetched,
+    /// The fetch failed; the cache was left as it was
+    Failed,
+}
+
+impl WarmStatus {
+    fn symbol(&self) -> &'static str {
+        match self {
+            WarmStatus::Fresh => "✓",
+            WarmStatus::Fetched => "↻",
+            WarmStatus::Failed => "✗",
+        }
+    }
+}
+
+/// One warmed cache item
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmItem {
+    pub item: &'static str,
+    pub status: WarmStatus,
+    /// Error message for failed items
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A prior week missing stored data
+#[derive(Debug, Clone, Serialize)]
+pub struct CoverageGap {
+    pub week: Week,
+    pub data_type: CoverageDataType,
+    pub level: CoverageLevel,
+}
+
+/// Everything the warm-up did
+#[derive(Debug, Clone, Serialize)]
+pub struct WarmCacheReport {
+    pub season: Season,
+    pub week: Week,
+    pub items: Vec<WarmItem>,
+    /// `None` unless coverage was checked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coverage_gaps: Option<Vec<CoverageGap>>,
+    /// Commands that fill the coverage gaps
+    pub follow_up: Vec<String>,
+}
+
+impl WarmCacheReport {
+    pub fn failed(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status == WarmStatus::Failed)
+            .count()
+    }
+}
+
+/// Sequential fetcher that records each item's outcome and spaces out network requests
+struct Warmer {
+    items: Vec<WarmItem>,
+    fetched_any: bool,
+}
+
+impl Warmer {
+    /// Record `item`, running `fetch` unless it is already fresh
+    ///
+    /// `fetch` reports whether it went to the network, since roster fetches decide
+    /// freshness themselves.
+    async fn warm<F, Fut>(&mut self, item: &'static str, fresh: bool, fetch: F)
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Result<bool>>,
+    {
+        let (status, error) = if fresh {
+            (WarmStatus::Fresh, None)
+        } else {
+            if self.fetched_any {
+                tokio::time::sleep(FETCH_DELAY).await;
+            }
+            match fetch().await {
+                Ok(true) => {
+                    self.fetched_any = true;
+                    (WarmStatus::Fetched, None)
+                }
+                Ok(false) => (WarmStatus::Fresh, None),
+                Err(e) => {
+                    self.fetched_any = true;
+                    (WarmStatus::Failed, Some(e.to_string()))
+                }
+            }
+        };
+        self.items.push(WarmItem {
+            item,
+            status,
+            error,
+        });
+    }
+}
+
+/// Whether a roster fetch was served from the cache
+fn roster_was_cached(status: &CacheStatus) -> bool {
+    matches!(
+        status,
+        CacheStatus::Fresh { .. } | CacheStatus::Locked { .. }
+    )
+}
+
+/// Warm every cache for the league's current week and report what happened
+///
+/// Settings and the NFL schedule are kept once cached; projections and the league
+/// schedule are refetched once older than the roster freshness window
+/// (`ESPN_FFL_ROSTER_FRESHNESS_MINUTES`); rosters use their own lock-aware check.
+pub async fn warm_cache(params: &WarmCacheParams) -> Result<WarmCacheReport> {
+    let league_id = resolve_league_id(params.league_id)?;
+    let season = params.season;
+    validate_season(league_id, season).await?;
+
+    let week = get_league_status(league_id, season)
+        .await?
+        .current_week()
+        .ok_or(EspnError::NoData)?;
+    let refresh = params.refresh;
+    let max_age = roster_freshness_secs();
+    let fresh_file = |age: Option<u64>, expires: bool| {
+        !refresh && age.is_some_and(|age| !expires || is_fresh(age, max_age))
+    };
+
+    let mut warmer = Warmer {
+        items: Vec::new(),
+        fetched_any: false,
+    };
+
+    let settings_stored = has_stored_league_settings(league_id, season)?;
+    warmer
+        .warm("league settings", !refresh && settings_stored, || async {
+            load_or_fetch_league_settings(league_id, true, season).await?;
+            Ok(true)
+        })
+        .await;
+
+    // Fetched before the weekly roster, whose lock check reads kickoff times
+    let pro_schedule_age = GLOBAL_CACHE
+        .pro_schedule
+        .disk_age_secs(&ProScheduleCacheKey { season });
+    warmer
+        .warm(
+            "NFL schedule",
+            fresh_file(pro_schedule_age, false),
+            || async {
+                // The NFL schedule has no refresh flag; drop the cached copy instead
+                let key = ProScheduleCacheKey { season };
+                GLOBAL_CACHE.pro_schedule.invalidate_disk_cache(&key)?;
+                GLOBAL_CACHE.pro_schedule.clear_memory();
+                get_pro_schedule(season).await?;
+                Ok(true)
+            },
+        )
+        .await;
+
+    let schedule_age = GLOBAL_CACHE
+        .schedule_data
+        .disk_age_secs(&ScheduleCacheKey { league_id, season });
+    warmer
+        .warm(
+            "league schedule",
+            fresh_file(schedule_age, true),
+            || async {
+                get_league_schedule(league_id, season, true).await?;
+                Ok(true)
+            },
+        )
+        .await;
+
+    warmer
+        .warm("week rosters", false, || async {
+            let (_, status) =
+                get_league_rosters_with_cache_status(false, league_id, season, Some(week), refresh)
+                    .await?;
+            Ok(!roster_was_cached(&status))
+        })
+        .await;
+
+    warmer
+        .warm("ownership", false, || async {
+            let (_, status) =
+                get_league_rosters_with_cache_status(false, league_id, season, None, refresh)
+                    .await?;
+            Ok(!roster_was_cached(&status))
+        })
+        .await;
+
+    let projections_age = GLOBAL_CACHE
+        .http_player_data
+        .disk_age_secs(&HttpPlayerDataCacheKey {
+            league_id,
+            season,
+            week,
+            player_names: None,
+            positions: None,
+            injury_status: None,
+            roster_status: None,
+            limit: None,
+        });
+    warmer
+        .warm("projections", fresh_file(projections_age, true), || async {
+            let mut request = PlayerDataRequest::new(league_id, season, week);
+            request.refresh = true;
+            get_player_data(request).await?;
+            Ok(true)
+        })
+        .await;
+
+    let (coverage_gaps, follow_up) = if params.check_coverage {
+        let gaps = find_coverage_gaps(league_id, season, week)?;
+        let follow_up = follow_up_commands(league_id, season, &gaps);
+        (Some(gaps), follow_up)
+    } else {
+        (None, Vec::new())
+    };
+
+    Ok(WarmCacheReport {
+        season,
+        week,
+        items: warmer.items,
+        coverage_gaps,
+        follow_up,
+    })
+}
+
+/// Weeks before `current_week` whose actual or projected points are partial or missing
+fn find_coverage_gaps(
+    league_id: LeagueId,
+    season: Season,
+    current_week: Week,
+) -> Result<Vec<CoverageGap>> {
+    let prior_weeks = current_week.as_u16().saturating_sub(1);
+    let db = PlayerDatabase::open_existing()?;
+    let coverage = db.get_data_coverage(league_id, season, prior_weeks)?;
+
+    let mut gaps = Vec::new();
+    for week in 1..=prior_weeks {
+        for row in &coverage.rows {
+            if !matches!(
+                row.data_type,
+                CoverageDataType::ActualPoints | CoverageDataType::ProjectedPoints
+            ) {
+                continue;
+            }
+            let level = row.level(week, COVERAGE_PARTIAL_BELOW);
+            if level != CoverageLevel::Full {
+                gaps.push(CoverageGap {
+                    week: Week::new(week),
+                    data_type: row.data_type,
+                    level,
+                });
+            }
+        }
+    }
+    Ok(gaps)
+}
+
+/// One `player-data --both --refresh` per week with a gap
+fn follow_up_commands(league_id: LeagueId, season: Season, gaps: &[CoverageGap]) -> Vec<String> {
+    let mut weeks: Vec<Week> = gaps.iter().map(|gap| gap.week).collect();
+    weeks.dedup();
+    weeks
+        .into_iter()
+        .map(|week| {
+            format!(
+                "espn-ffl player-data --league-id {} --season {} --week {} --both --refresh",
+                league_id, season, week
+            )
+        })
+        .collect()
+}
+
+/// Handle the cache warming command
+pub async fn handle_warm_cache(params: WarmCacheParams) -> Result<()> {
+    let report = warm_cache(&params).await?;
+
+    if params.as_json {
+        println!("{}", serde_json::to_string_pretty(&report)?); // tarpaulin::skip
+    } else {
+        // tarpaulin::skip - console output
+        print_report(&report);
+    }
+
+    match report.failed() {
+        0 => Ok(()),
+        failed => Err(EspnError::Cache {
+            message: format!(
+                "{} of {} items could not be fetched",
+                failed,
+                report.items.len()
+            ),
+        }),
+    }
+}
+
+fn print_report(report: &WarmCacheReport) {
+    println!(
+        "Cache Warm-up - Season {} Week {}",
+        report.season, report.week
+    );
+    println!();
+    for item in &report.items {
+        let status = match item.status {
+            WarmStatus::Fresh => "already fresh",
+            WarmStatus::Fetched => "fetched",
+            WarmStatus::Failed => "failed",
+        };
+        match &item.error {
+            Some(error) => println!(
+                "{} {:<16} {} ({})",
+                item.status.symbol(),
+                item.item,
+                status,
+                error
+            ),
+            None => println!("{} {:<16} {}", item.status.symbol(), item.item, status),
+        }
+    }
+
+    let fetched = report
+        .items
+        .iter()
+        .filter(|item| item.status == WarmStatus::Fetched)
+        .count();
+    println!();
+    println!(
+        "{} fetched, {} already fresh, {} failed",
+        fetched,
+        report.items.len() - fetched - report.failed(),
+        report.failed()
+    );
+
+    let Some(gaps) = &report.coverage_gaps else {
+        return;
+    };
+    println!();
+    match report.week.as_u16() {
+        1 => {
+            println!("No earlier weeks to check");
+            return;
+        }
+        week if gaps.is_empty() => {
+            println!("✓ Stored data covers weeks 1-{}", week - 1);
+            return;
+        }
+        _ => {}
+    }
+    println!("Coverage gaps before week {}:", report.week);
+    for gap in gaps {
+        println!(
+            "  {} Week {:>2} {}",
+            gap.level.symbol(),
+            gap.week,
+            gap.data_type.label()
+        );
+    }
+    println!();
+    println!("Fill them with:");
+    for command in &report.follow_up {
+        println!("  {}", command);
+    }
+}
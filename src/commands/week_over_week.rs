@@ -0,0 +1,232 @@
+//! Week-over-week command implementation
+//!
+//! Compares every player's stored numbers between two weeks and lists the
+//! biggest risers and fallers by actual points, ESPN's weekly projection or
+//! ownership, plus injury designations that changed in between.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+
+use crate::{
+    cli::{
+        render::{
+            disambiguate_names, Align, Column, NameEntry, Table, NAME_MAX_WIDTH, NAME_MIN_WIDTH,
+        },
+        types::{
+            filters::{RosterStatusFilter, WeekOverWeekMetric},
+            position::Position,
+        },
+    },
+    error::EspnError,
+    storage::{
+        analysis::{risers_and_fallers, week_over_week_delta},
+        PlayerDatabase, WeekOverWeekChange,
+    },
+    LeagueId, PlayerId, Result, Season, Week,
+};
+
+use super::league_data::resolve_league_id;
+
+/// Configuration for the week-over-week report
+#[derive(Debug)]
+pub struct WeekOverWeekParams {
+    pub league_id: Option<LeagueId>,
+    pub season: Season,
+    /// Earlier week; defaults to the week before `to`
+    pub from: Option<Week>,
+    pub to: Week,
+    pub positions: Option<Vec<Position>>,
+    pub roster_status: Option<RosterStatusFilter>,
+    /// Change that ranks risers and fallers
+    pub metric: WeekOverWeekMetric,
+    /// Number of risers and of fallers to show
+    pub top: usize,
+    pub as_json: bool,
+}
+
+/// JSON output for the week-over-week command
+#[derive(Debug, Serialize)]
+struct WeekOverWeekOutput<'a> {
+    season: Season,
+    from_week: Week,
+    to_week: Week,
+    ranked_by: String,
+    risers: Vec<&'a WeekOverWeekChange>,
+    fallers: Vec<&'a WeekOverWeekChange>,
+    status_changes: Vec<&'a WeekOverWeekChange>,
+}
+
+/// Handle the week-over-week command
+pub fn handle_week_over_week(params: WeekOverWeekParams) -> Result<()> {
+    let to = params.to;
+    let from = params
+        .from
+        .unwrap_or_else(|| Week::new(to.as_u16().saturating_sub(1)));
+    if from.as_u16() == 0 || from.as_u16() >= to.as_u16() {
+        return Err(EspnError::InvalidWeekRange {
+            from: from.as_u16(),
+            to: to.as_u16(),
+        });
+    }
+
+    let league_id = resolve_league_id(params.league_id)?;
+    let db = PlayerDatabase::open_existing()?;
+    let changes = db.week_over_week(
+        league_id,
+        params.season,
+        from,
+        to,
+        params.positions.as_deref(),
+        params.roster_status.as_ref(),
+    )?;
+    let (risers, fallers) = risers_and_fallers(&changes, params.metric, params.top);
+    let status_changes: Vec<&WeekOverWeekChange> =
+        changes.iter().filter(|c| c.status_changed()).collect();
+
+    if params.as_json {
+        let output = WeekOverWeekOutput {
+            season: params.season,
+            from_week: from,
+            to_week: to,
+            ranked_by: params.metric.to_string(),
+            risers,
+            fallers,
+            status_changes,
+        };
+        println!("{}", serde_json::to_string_pretty(&output)?); // tarpaulin::skip
+        return Ok(());
+    }
+
+    // tarpaulin::skip - console output
+    println!(
+        "Week-over-week changes - Season {} Week {} → Week {} (by {})",
+        params.season, from, to, params.metric
+    );
+    if changes.is_empty() {
+        println!(
+            "No stored stats for these weeks; run player-data or update-all-data for both first."
+        );
+        return Ok(());
+    }
+    let names = display_names(&changes);
+    print_ranked("Risers", &risers, params.metric, &names);
+    print_ranked("Fallers", &fallers, params.metric, &names);
+    print_status_changes(&status_changes, &names);
+    Ok(())
+}
+
+/// Player names, with position and team added where two players share one
+fn display_names(changes: &[WeekOverWeekChange]) -> HashMap<PlayerId, String> {
+    let entries: Vec<NameEntry<'_, PlayerId>> = changes
+        .iter()
+        .map(|c| NameEntry {
+            id: c.player_id,
+            name: &c.name,
+            position: &c.position,
+            team: c.team.as_deref(),
+        })
+        .collect();
+    disambiguate_names(&entries)
+}
+
+fn roster_label(change: &WeekOverWeekChange) -> String {
+    match (change.is_rostered, &change.fantasy_team_name) {
+        (Some(true), Some(team)) => team.clone(),
+        (Some(true), None) => "Rostered".to_string(),
+        (Some(false), _) => "FA".to_string(),
+        (None, _) => "--".to_string(),
+    }
+}
+
+fn status_label(change: &WeekOverWeekChange) -> String {
+    if change.status_changed() {
+        format!(
+            "{} → {}",
+            change.injury_status_from.as_deref().unwrap_or_default(),
+            change.injury_status_to.as_deref().unwrap_or_default()
+        )
+    } else {
+        String::new()
+    }
+}
+
+fn print_ranked(
+    title: &str,
+    changes: &[&WeekOverWeekChange],
+    metric: WeekOverWeekMetric,
+    names: &HashMap<PlayerId, String>,
+) {
+    println!();
+    println!("{}", title);
+    if changes.is_empty() {
+        println!("  (none)");
+        return;
+    }
+
+    let sides = |change: &WeekOverWeekChange| match metric {
+        WeekOverWeekMetric::Actual => (change.actual_from, change.actual_to),
+        WeekOverWeekMetric::Projected => (change.projected_from, change.projected_to),
+        WeekOverWeekMetric::Owned => (change.owned_from, change.owned_to),
+    };
+    let format_value = |value: Option<f64>| match value {
+        Some(value) => format!("{:.1}", value),
+        None => "--".to_string(),
+    };
+
+    let mut table = Table::new(vec![
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("From", Align::Right),
+        Column::fixed("To", Align::Right),
+        Column::fixed("Change", Align::Right),
+        Column::fixed("Status", Align::Left),
+        Column::rest("Roster"),
+    ]);
+    for change in changes {
+        let (from, to) = sides(change);
+        table.add_row(vec![
+            names
+                .get(&change.player_id)
+                .unwrap_or(&change.name)
+                .to_string(),
+            change.position.clone(),
+            format_value(from),
+            format_value(to),
+            week_over_week_delta(change, metric)
+                .map(|delta| format!("{:+.1}", delta))
+                .unwrap_or_default(),
+            status_label(change),
+            roster_label(change),
+        ]);
+    }
+    table.print();
+}
+
+fn print_status_changes(changes: &[&WeekOverWeekChange], names: &HashMap<PlayerId, String>) {
+    println!();
+    println!("Injury status changes");
+    if changes.is_empty() {
+        println!("  (none)");
+        return;
+    }
+
+    let mut table = Table::new(vec![
+        Column::flex("Player", NAME_MIN_WIDTH, NAME_MAX_WIDTH),
+        Column::fixed("Pos", Align::Left),
+        Column::fixed("Status", Align::Left),
+        Column::rest("Roster"),
+    ]);
+    for change in changes {
+        table.add_row(vec![
+            names
+                .get(&change.player_id)
+                .unwrap_or(&change.name)
+                .to_string(),
+            change.position.clone(),
+            status_label(change),
+            roster_label(change),
+        ]);
+    }
+    table.print();
+}
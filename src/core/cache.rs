@@ -6,35 +6,373 @@
 //!
 //! The system automatically promotes frequently accessed items to memory cache
 //! and provides fallback to disk storage for larger datasets.
+//!
+//! League-specific files live in a directory per league (`espn-ffl/<league_id>/`),
+//! so leagues never read each other's cached data; files shared by every league
+//! (the NFL schedule) stay at the top level.
 
 use dirs;
 use lru::LruCache;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs,
     hash::Hash,
     io::{Read, Write},
     num::NonZeroUsize,
     path::{Path, PathBuf},
-    sync::{Arc, Mutex},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, LazyLock, Mutex,
+    },
 };
 
-use crate::cli::types::filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter};
-use crate::{LeagueId, PlayerId, Position, Season, Week};
+use super::metrics::{metrics_enabled, record_cache, record_cache_error};
+use super::unix_now;
+use crate::cli::types::filters::{
+    FantasyTeamFilter, InjuryStatusFilter, NflTeam, RosterStatusFilter,
+};
+use crate::{
+    error::CacheError, LeagueId, PlayerId, Position, Season, Week, NO_CACHE_WRITE_ENV_VAR,
+};
 
-/// Path: ~/.cache/league_settings-{season}-{league_id}.json
-pub fn league_settings_path(season: u16, league_id: u32) -> PathBuf {
+/// Set by `--no-cache-write`; the env var is checked separately on each write
+static CACHE_WRITES_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Paths whose failed writes were already reported, so each is warned about once per run
+static WARNED_WRITE_PATHS: LazyLock<Mutex<HashSet<PathBuf>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+/// Stop writing cache files for the rest of the process; memory caches keep working
+pub fn disable_cache_writes() {
+    CACHE_WRITES_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether cache files may be written: not turned off by `--no-cache-write` or
+/// `ESPN_FFL_NO_CACHE_WRITE`
+pub fn cache_writes_enabled() -> bool {
+    if CACHE_WRITES_DISABLED.load(Ordering::Relaxed) {
+        return false;
+    }
+    match std::env::var(NO_CACHE_WRITE_ENV_VAR) {
+        Ok(value) => matches!(value.trim().to_lowercase().as_str(), "" | "0" | "false"),
+        Err(_) => true,
+    }
+}
+
+/// Write a file for the named cache unless cache writes are disabled
+///
+/// Failures (e.g. a read-only cache directory) never fail the command; see
+/// [`report_cache_error`].
+pub fn write_cache_file(cache: &'static str, path: &Path, contents: &str) {
+    if !cache_writes_enabled() {
+        return;
+    }
+    if let Err(source) = write_string(path, contents) {
+        report_cache_error(
+            cache,
+            CacheError::Write {
+                path: path.to_path_buf(),
+                source,
+            },
+        );
+    }
+}
+
+/// Read and parse a file of the named cache; `None` when it is missing or corrupt
+///
+/// A file that doesn't parse is deleted (unless cache writes are disabled) so the
+/// data is fetched again and rewritten, rather than missing on every run.
+pub fn read_cache_file<V: for<'de> Deserialize<'de>>(
+    cache: &'static str,
+    path: &Path,
+) -> Option<V> {
+    let content = try_read_to_string(path)?;
+    match serde_json::from_str(&content) {
+        Ok(value) => Some(value),
+        Err(source) => {
+            if cache_writes_enabled() {
+                let _ = fs::remove_file(path);
+            }
+            report_cache_error(
+                cache,
+                CacheError::Corrupt {
+                    path: path.to_path_buf(),
+                    source,
+                },
+            );
+            None
+        }
+    }
+}
+
+/// Count a cache error and tell the user about it without failing the command
+///
+/// Write failures are warned about once per path per run. Corrupt files are
+/// already handled, so they are only mentioned on `--verbose`/`--timings` runs.
+pub fn report_cache_error(cache: &'static str, error: CacheError) {
+    record_cache_error(cache);
+    match error {
+        CacheError::Corrupt { .. } => {
+            if metrics_enabled() {
+                eprintln!("Cache: {}; removed it so it is fetched again", error);
+            }
+        }
+        CacheError::Write { .. } | CacheError::Serialize { .. } => {
+            let first = WARNED_WRITE_PATHS
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(error.path().to_path_buf());
+            if first {
+                eprintln!(
+                    "⚠ Warning: {}; continuing without caching it on disk",
+                    error
+                );
+            }
+        }
+    }
+}
+
+/// Seconds since the cache file at `path` was last written; `None` when it doesn't exist
+pub fn cache_file_age_secs(path: &Path) -> Option<u64> {
+    let modified = fs::metadata(path).ok()?.modified().ok()?;
+    Some(
+        std::time::SystemTime::now()
+            .duration_since(modified)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    )
+}
+
+/// Whether data fetched `age_secs` ago is still inside a `max_age_secs` freshness window
+pub fn is_fresh(age_secs: u64, max_age_secs: u64) -> bool {
+    age_secs <= max_age_secs
+}
+
+/// Directory holding the cache files: ~/.cache/espn-ffl
+pub fn cache_files_dir() -> PathBuf {
     let base = dirs::cache_dir().unwrap_or_else(|| {
         let mut home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
         home.push(".cache");
         home
     });
     base.join("espn-ffl")
+}
+
+/// Cache directories already checked for flat-layout files during this process
+static MIGRATED_DIRS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Directory holding one league's cache files: ~/.cache/espn-ffl/{league_id}
+///
+/// The first call for a cache directory moves files left there by the older flat
+/// layout into their league's directory (see [`migrate_flat_cache_files`]).
+pub fn league_cache_dir(league_id: LeagueId) -> PathBuf {
+    let base = cache_files_dir();
+    {
+        let mut migrated = MIGRATED_DIRS.lock().unwrap_or_else(|e| e.into_inner());
+        if !migrated.contains(&base) {
+            if cache_writes_enabled() {
+                let _ = migrate_flat_cache_files(&base);
+            }
+            migrated.push(base.clone());
+        }
+    }
+    base.join(league_id.as_u32().to_string())
+}
+
+/// Path: ~/.cache/espn-ffl/{league_id}/league-settings_{season}_{league_id}.json
+pub fn league_settings_path(season: u16, league_id: u32) -> PathBuf {
+    league_cache_dir(LeagueId::new(league_id))
         .join(format!("league-settings_{}_{}.json", season, league_id))
 }
 
+/// Every league settings file left in the cache directories, with its season and league ID
+///
+/// Settings are kept in the database now; these files are only read to import them.
+pub fn league_settings_files() -> Vec<(u16, u32, PathBuf)> {
+    cache_file_dirs()
+        .into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| {
+            let stem = path.file_stem()?.to_str()?;
+            let (season, league_id) = league_settings_file_key(stem)?;
+            Some((season, league_id, path))
+        })
+        .collect()
+}
+
+/// Season and league ID in a `league-settings_{season}_{league_id}` file key
+fn league_settings_file_key(file_key: &str) -> Option<(u16, u32)> {
+    let (season, league_id) = file_key.strip_prefix("league-settings_")?.split_once('_')?;
+    Some((season.parse().ok()?, league_id.parse().ok()?))
+}
+
+/// Files moved and removed by [`migrate_flat_cache_files`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CacheMigration {
+    pub moved: usize,
+    pub discarded: usize,
+}
+
+/// How a file from the flat cache layout is handled
+#[derive(Debug, PartialEq, Eq)]
+enum FlatCacheFile {
+    /// Belongs to this league's directory
+    League(u32),
+    /// League-specific, but the name doesn't say which league
+    Ambiguous,
+    /// Not a league cache file (NFL schedule, user files); left alone
+    Shared,
+}
+
+/// Prefixes of flat-layout cache file names followed by `l{league_id}_`
+const LEAGUE_KEYED_PREFIXES: [&str; 4] = [
+    "league_settings_l",
+    "http_player_data_l",
+    "roster_data_l",
+    "schedule_data_l",
+];
+
+fn classify_flat_cache_file(stem: &str) -> FlatCacheFile {
+    if let Some(rest) = stem.strip_prefix("league-settings_") {
+        return match rest.rsplit_once('_').map(|(_, id)| id.parse()) {
+            Some(Ok(league_id)) => FlatCacheFile::League(league_id),
+            _ => FlatCacheFile::Ambiguous,
+        };
+    }
+    if let Some(rest) = LEAGUE_KEYED_PREFIXES
+        .iter()
+        .find_map(|prefix| stem.strip_prefix(prefix))
+    {
+        let id = rest.split('_').next().unwrap_or_default();
+        return match id.parse() {
+            Ok(league_id) => FlatCacheFile::League(league_id),
+            Err(_) => FlatCacheFile::Ambiguous,
+        };
+    }
+    // Database query caches were keyed without a league
+    if stem.starts_with("player_data_") || stem.starts_with("weekly_stats_") {
+        return FlatCacheFile::Ambiguous;
+    }
+    FlatCacheFile::Shared
+}
+
+/// Move cache files from the flat layout in `base` into per-league directories
+///
+/// Files whose name embeds a league ID move to `base/{league_id}/`, unless that
+/// directory already has a newer copy; query caches that never recorded a league
+/// can't be attributed and are deleted. Everything else (the NFL schedule, the
+/// database, `scoring_overrides.json`) stays where it is.
+pub fn migrate_flat_cache_files(base: &Path) -> std::io::Result<CacheMigration> {
+    let mut migration = CacheMigration::default();
+    let Ok(entries) = fs::read_dir(base) else {
+        return Ok(migration);
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if !path.is_file() || path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        match classify_flat_cache_file(stem) {
+            FlatCacheFile::League(league_id) => {
+                let dir = base.join(league_id.to_string());
+                let target = dir.join(path.file_name().unwrap_or_default());
+                if target.exists() {
+                    fs::remove_file(&path)?;
+                    migration.discarded += 1;
+                } else {
+                    fs::create_dir_all(&dir)?;
+                    fs::rename(&path, &target)?;
+                    migration.moved += 1;
+                }
+            }
+            FlatCacheFile::Ambiguous => {
+                fs::remove_file(&path)?;
+                migration.discarded += 1;
+            }
+            FlatCacheFile::Shared => {}
+        }
+    }
+    Ok(migration)
+}
+
+/// The top-level cache directory and every league directory in it
+fn cache_file_dirs() -> Vec<PathBuf> {
+    let base = cache_files_dir();
+    let mut dirs = vec![base.clone()];
+    if let Ok(entries) = fs::read_dir(&base) {
+        dirs.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+            path.is_dir()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.parse::<u32>().is_ok())
+        }));
+    }
+    dirs
+}
+
+/// Remove every cache file, in any league directory, whose file key matches
+///
+/// One pass over the cache directories. Returns the number of files removed;
+/// nothing is removed while cache writes are disabled.
+fn remove_cache_files(file_key_matches: impl Fn(&str) -> bool) -> usize {
+    if !cache_writes_enabled() {
+        return 0;
+    }
+    cache_file_dirs()
+        .into_iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flat_map(|entries| entries.flatten())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "json")
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(&file_key_matches)
+        })
+        .filter(|path| fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// Weeks whose cache files are due for deletion, each with the sequence number of
+/// its latest invalidation; see [`CacheManager::invalidate_week`]
+static PENDING_WEEKS: LazyLock<Mutex<HashMap<(Season, Week), u64>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Sequence for [`PENDING_WEEKS`], so a flush can tell a week queued again while it
+/// was deleting files from the one it set out to handle
+static INVALIDATION_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Whether a week's cache files are stale and waiting for the deferred deletion
+fn week_deletion_pending(week: (Season, Week)) -> bool {
+    PENDING_WEEKS.lock().unwrap().contains_key(&week)
+}
+
+/// Season and week in the file key of a cache built from stored rows
+///
+/// Matches `player_data_l{league}_s{season}_w{week}_...` and
+/// `weekly_stats_l{league}_p{player}_s{season}_w{week}`.
+fn stored_week_of_file_key(file_key: &str) -> Option<(u16, u16)> {
+    let rest = match file_key.strip_prefix("player_data_l") {
+        Some(rest) => rest.split_once('_')?.1,
+        None => {
+            let rest = file_key.strip_prefix("weekly_stats_l")?;
+            rest.split_once("_p")?.1.split_once('_')?.1
+        }
+    };
+    let (season, rest) = rest.strip_prefix('s')?.split_once("_w")?;
+    let week = rest.split('_').next()?;
+    Some((season.parse().ok()?, week.parse().ok()?))
+}
+
 /// Try to read a file into a String
 pub fn try_read_to_string(path: &Path) -> Option<String> {
     let mut f = fs::File::open(path).ok()?;
@@ -45,36 +383,87 @@ pub fn try_read_to_string(path: &Path) -> Option<String> {
     Some(s)
 }
 
-/// Write a string to file
+/// Write a string to file atomically
+///
+/// Contents go to a uniquely named temp file in the same directory which is then
+/// renamed over `path`, so concurrent readers (including other processes) see
+/// either the old file or the complete new one, never a partial write.
 pub fn write_string(path: &Path, contents: &str) -> std::io::Result<()> {
+    static TEMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)?;
     }
 
-    let mut f = fs::File::create(path)?;
-    f.write_all(contents.as_bytes())
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let temp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = (|| {
+        let mut f = fs::File::create(&temp_path)?;
+        f.write_all(contents.as_bytes())?;
+        f.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
 }
 
 /// Generic cache key that can be used for both memory and disk caching
 pub trait CacheKey: Hash + Eq + Clone + Send + Sync {
+    /// Name of the cache these keys belong to, for `--timings`
+    const NAME: &'static str;
+
     /// Generate a string representation for file system storage
     fn to_file_key(&self) -> String;
 
-    /// Generate the file path for this cache entry
+    /// League the cached data belongs to; `None` for data shared by every league
+    fn league_id(&self) -> Option<LeagueId> {
+        None
+    }
+
+    /// Generate the file path for this cache entry, in its league's directory
     fn to_file_path(&self) -> PathBuf {
-        let base = dirs::cache_dir().unwrap_or_else(|| {
-            let mut home = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-            home.push(".cache");
-            home
-        });
-        base.join("espn-ffl")
-            .join(format!("{}.json", self.to_file_key()))
+        let dir = self
+            .league_id()
+            .map_or_else(cache_files_dir, league_cache_dir);
+        dir.join(format!("{}.json", self.to_file_key()))
+    }
+
+    /// File keys this entry was stored under by earlier versions
+    ///
+    /// `UnifiedCache` reads these when the current file is missing and moves the
+    /// contents over, so a key format change doesn't throw away cached data.
+    fn legacy_file_keys(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Season and week of the stored database rows this entry was built from
+    ///
+    /// Such entries go stale when [`CacheManager::invalidate_week`] is called for
+    /// their week; until the deferred file deletion runs, their files aren't read.
+    fn stored_week(&self) -> Option<(Season, Week)> {
+        None
     }
 }
 
 /// Cache key for database player data queries
+///
+/// Every field maps onto a predicate in `get_cached_player_data`, so two keys only
+/// differ when the rows they hold do.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PlayerDataCacheKey {
+    pub league_id: LeagueId,
     pub season: Season,
     pub week: Week,
     pub player_names: Option<Vec<String>>,
@@ -83,9 +472,20 @@ pub struct PlayerDataCacheKey {
     pub injury_status: Option<InjuryStatusFilter>,
     pub roster_status: Option<RosterStatusFilter>,
     pub fantasy_team_filter: Option<FantasyTeamFilter>,
+    pub nfl_team: Option<NflTeam>,
 }
 
 impl CacheKey for PlayerDataCacheKey {
+    const NAME: &'static str = "player_data";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
+    fn stored_week(&self) -> Option<(Season, Week)> {
+        Some((self.season, self.week))
+    }
+
     fn to_file_key(&self) -> String {
         let names_hash = self
             .player_names
@@ -125,14 +525,24 @@ impl CacheKey for PlayerDataCacheKey {
             .as_ref()
             .map(|filter| match filter {
                 FantasyTeamFilter::Id(id) => format!("team_id_{}", id),
+                FantasyTeamFilter::Ids(ids) => {
+                    let ids: Vec<String> = ids.iter().map(|id| id.to_string()).collect();
+                    format!("team_ids_{}", ids.join("_"))
+                }
                 FantasyTeamFilter::Name(name) => {
                     format!("team_name_{}", name.to_lowercase().replace(' ', "_"))
                 }
             })
             .unwrap_or_else(|| "all_teams".to_string());
 
+        let nfl_team_hash = self
+            .nfl_team
+            .map(|team| format!("nfl_{}", team.abbrev().to_lowercase()))
+            .unwrap_or_else(|| "all_nfl".to_string());
+
         format!(
-            "player_data_s{}_w{}_{}_{}_{}_{}_{}_{}",
+            "player_data_l{}_s{}_w{}_{}_{}_{}_{}_{}_{}_{}",
+            self.league_id.as_u32(),
             self.season.as_u16(),
             self.week.as_u16(),
             names_hash,
@@ -140,6 +550,7 @@ impl CacheKey for PlayerDataCacheKey {
             injury_hash,
             roster_hash,
             team_hash,
+            nfl_team_hash,
             if self.projected { "proj" } else { "actual" }
         )
     }
@@ -148,15 +559,27 @@ impl CacheKey for PlayerDataCacheKey {
 /// Cache key for weekly stats queries
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WeeklyStatsCacheKey {
+    pub league_id: LeagueId,
     pub player_id: PlayerId,
     pub season: Season,
     pub week: Week,
 }
 
 impl CacheKey for WeeklyStatsCacheKey {
+    const NAME: &'static str = "weekly_stats";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
+    fn stored_week(&self) -> Option<(Season, Week)> {
+        Some((self.season, self.week))
+    }
+
     fn to_file_key(&self) -> String {
         format!(
-            "weekly_stats_p{}_s{}_w{}",
+            "weekly_stats_l{}_p{}_s{}_w{}",
+            self.league_id.as_u32(),
             self.player_id.as_i64(),
             self.season.as_u16(),
             self.week.as_u16()
@@ -172,6 +595,12 @@ pub struct LeagueSettingsCacheKey {
 }
 
 impl CacheKey for LeagueSettingsCacheKey {
+    const NAME: &'static str = "league_settings";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
     fn to_file_key(&self) -> String {
         format!(
             "league_settings_l{}_s{}",
@@ -182,6 +611,10 @@ impl CacheKey for LeagueSettingsCacheKey {
 }
 
 /// Cache key for HTTP player data requests
+///
+/// One payload carries both actual and projected stats, so the key covers only what
+/// shapes the request (league, season, week and the server-side filters); choosing
+/// a stat source happens when the payload is scored.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct HttpPlayerDataCacheKey {
     pub league_id: LeagueId,
@@ -189,11 +622,14 @@ pub struct HttpPlayerDataCacheKey {
     pub week: Week,
     pub player_names: Option<Vec<String>>,
     pub positions: Option<Vec<Position>>,
-    pub projected: bool,
+    pub injury_status: Option<InjuryStatusFilter>,
+    pub roster_status: Option<RosterStatusFilter>,
+    /// Most players fetched; `None` for every player
+    pub limit: Option<u32>,
 }
 
-impl CacheKey for HttpPlayerDataCacheKey {
-    fn to_file_key(&self) -> String {
+impl HttpPlayerDataCacheKey {
+    fn names_and_positions_hash(&self) -> (String, String) {
         let names_hash = self
             .player_names
             .as_ref()
@@ -215,16 +651,67 @@ impl CacheKey for HttpPlayerDataCacheKey {
             })
             .unwrap_or_else(|| "all_pos".to_string());
 
+        (names_hash, positions_hash)
+    }
+}
+
+impl CacheKey for HttpPlayerDataCacheKey {
+    const NAME: &'static str = "http_player_data";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
+    fn to_file_key(&self) -> String {
+        let (names_hash, positions_hash) = self.names_and_positions_hash();
+
+        let injury_hash = self
+            .injury_status
+            .as_ref()
+            .map(|status| format!("inj_{}", status.to_string().to_lowercase()))
+            .unwrap_or_else(|| "all_inj".to_string());
+
+        let roster_hash = self
+            .roster_status
+            .as_ref()
+            .map(|status| format!("ros_{}", status.to_string().to_lowercase()))
+            .unwrap_or_else(|| "all_ros".to_string());
+
+        let limit_hash = self
+            .limit
+            .map(|limit| format!("_lim{}", limit))
+            .unwrap_or_default();
+
         format!(
-            "http_player_data_l{}_s{}_w{}_{}_{}_{}",
+            "http_player_data_l{}_s{}_w{}_{}_{}_{}_{}{}",
             self.league_id.as_u32(),
             self.season.as_u16(),
             self.week.as_u16(),
             names_hash,
             positions_hash,
-            if self.projected { "proj" } else { "actual" }
+            injury_hash,
+            roster_hash,
+            limit_hash
         )
     }
+
+    /// Older versions keyed on the stat source and ignored the injury/roster filters,
+    /// always writing `_actual`; that file only matches an unfiltered, unlimited request.
+    fn legacy_file_keys(&self) -> Vec<String> {
+        if self.injury_status.is_some() || self.roster_status.is_some() || self.limit.is_some() {
+            return Vec::new();
+        }
+
+        let (names_hash, positions_hash) = self.names_and_positions_hash();
+        vec![format!(
+            "http_player_data_l{}_s{}_w{}_{}_{}_actual",
+            self.league_id.as_u32(),
+            self.season.as_u16(),
+            self.week.as_u16(),
+            names_hash,
+            positions_hash
+        )]
+    }
 }
 
 /// Cache key for HTTP roster data
@@ -236,6 +723,12 @@ pub struct RosterDataCacheKey {
 }
 
 impl CacheKey for RosterDataCacheKey {
+    const NAME: &'static str = "roster_data";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
     fn to_file_key(&self) -> String {
         let week_str = self
             .week
@@ -251,14 +744,106 @@ impl CacheKey for RosterDataCacheKey {
     }
 }
 
+/// Cache key for HTTP league schedule (matchup) data
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScheduleCacheKey {
+    pub league_id: LeagueId,
+    pub season: Season,
+}
+
+impl CacheKey for ScheduleCacheKey {
+    const NAME: &'static str = "schedule_data";
+
+    fn league_id(&self) -> Option<LeagueId> {
+        Some(self.league_id)
+    }
+
+    fn to_file_key(&self) -> String {
+        format!(
+            "schedule_data_l{}_s{}",
+            self.league_id.as_u32(),
+            self.season.as_u16()
+        )
+    }
+}
+
+/// Cache key for the NFL (pro team) schedule of a season
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProScheduleCacheKey {
+    pub season: Season,
+}
+
+impl CacheKey for ProScheduleCacheKey {
+    const NAME: &'static str = "pro_schedule";
+
+    fn to_file_key(&self) -> String {
+        format!("pro_schedule_s{}", self.season.as_u16())
+    }
+}
+
+/// How long cached roster data is kept before it expires
+pub const ROSTER_DATA_TTL_SECS: u64 = 6 * 60 * 60;
+
+/// How long a cached ESPN player payload is kept while its week's games can still change
+pub const LIVE_PLAYER_DATA_TTL_SECS: u64 = 60 * 60;
+
+/// Cached value together with when it was fetched (seconds since the Unix epoch)
+///
+/// This is the envelope `UnifiedCache` stores, in memory and on disk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Timestamped<V> {
+    pub fetched_at: u64,
+    /// Seconds after `fetched_at` the entry expires; `None` keeps it until invalidated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_secs: Option<u64>,
+    pub value: V,
+}
+
+impl<V> Timestamped<V> {
+    /// Wrap a value fetched just now, never expiring
+    pub fn now(value: V) -> Self {
+        Self {
+            fetched_at: unix_now(),
+            ttl_secs: None,
+            value,
+        }
+    }
+
+    /// Whether the entry's time to live has run out at `now` (seconds)
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.ttl_secs
+            .is_some_and(|ttl| !is_fresh(now.saturating_sub(self.fetched_at), ttl))
+    }
+}
+
+/// A cache file: the [`Timestamped`] envelope, or a bare value written by earlier versions
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DiskEntry<V> {
+    Timestamped(Timestamped<V>),
+    Bare(V),
+}
+
+/// Size and default time to live of one cache, for debugging
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CacheInfo {
+    pub name: &'static str,
+    pub entries: usize,
+    pub capacity: usize,
+    /// Default time to live in seconds; `None` when entries only go when invalidated
+    pub ttl_secs: Option<u64>,
+}
+
 /// Unified cache that combines LRU memory cache with file system persistence
 pub struct UnifiedCache<K, V>
 where
     K: CacheKey,
     V: Clone + Serialize + for<'de> Deserialize<'de>,
 {
-    memory_cache: Arc<Mutex<LruCache<K, V>>>,
+    memory_cache: Arc<Mutex<LruCache<K, Timestamped<V>>>>,
     memory_capacity: usize,
+    /// Time to live given to entries put without one; `None` never expires them
+    ttl_secs: Option<u64>,
 }
 
 impl<K, V> UnifiedCache<K, V>
@@ -273,54 +858,137 @@ where
                 NonZeroUsize::new(memory_capacity).unwrap(),
             ))),
             memory_capacity,
+            ttl_secs: None,
         }
     }
 
+    /// Expire entries `ttl_secs` after they were fetched, unless put with their own
+    /// time to live (see [`put_with_ttl`](Self::put_with_ttl))
+    pub fn with_ttl(mut self, ttl_secs: u64) -> Self {
+        self.ttl_secs = Some(ttl_secs);
+        self
+    }
+
     /// Get an item from cache (checks memory first, then disk)
     pub fn get(&self, key: &K) -> Option<V> {
+        self.get_timestamped(key).map(|entry| entry.value)
+    }
+
+    /// Get an item from cache along with when it was fetched
+    ///
+    /// Expired entries are misses: they are dropped from memory and their file is
+    /// deleted, so the caller fetches the data again.
+    pub fn get_timestamped(&self, key: &K) -> Option<Timestamped<V>> {
+        let now = unix_now();
+
         // First check memory cache
-        if let Some(value) = self.memory_cache.lock().unwrap().get(key) {
-            return Some(value.clone());
+        {
+            let mut memory = self.memory_cache.lock().unwrap();
+            if let Some(entry) = memory.get(key) {
+                if !entry.is_expired(now) {
+                    record_cache(K::NAME, true);
+                    return Some(entry.clone());
+                }
+                memory.pop(key);
+            }
         }
 
-        // Fall back to disk cache
-        if let Some(value) = self.get_from_disk(key) {
-            // Promote to memory cache
-            self.memory_cache
-                .lock()
-                .unwrap()
-                .put(key.clone(), value.clone());
-            return Some(value);
+        // Fall back to disk cache, unless the file is stale and waiting to be deleted
+        let stale_on_disk = key.stored_week().is_some_and(week_deletion_pending);
+        if let Some(entry) = (!stale_on_disk).then(|| self.get_from_disk(key)).flatten() {
+            if entry.is_expired(now) {
+                let _ = self.invalidate_disk_cache(key);
+            } else {
+                // Promote to memory cache
+                self.memory_cache
+                    .lock()
+                    .unwrap()
+                    .put(key.clone(), entry.clone());
+                record_cache(K::NAME, true);
+                return Some(entry);
+            }
         }
 
+        record_cache(K::NAME, false);
         None
     }
 
-    /// Put an item into cache (stores in both memory and disk)
+    /// Put an item into cache (stores in both memory and disk) with the cache's
+    /// time to live
     pub fn put(&self, key: K, value: V) {
-        // Store in memory cache
-        self.memory_cache
-            .lock()
-            .unwrap()
-            .put(key.clone(), value.clone());
+        self.put_with_ttl(key, value, self.ttl_secs);
+    }
+
+    /// Put an item into cache with its own time to live; `None` never expires it
+    ///
+    /// Entries for a week whose files are due for deletion stay in memory only,
+    /// since the pending flush would remove the file again.
+    pub fn put_with_ttl(&self, key: K, value: V, ttl_secs: Option<u64>) {
+        let entry = Timestamped {
+            fetched_at: unix_now(),
+            ttl_secs,
+            value,
+        };
 
         // Store in disk cache for persistence
-        let _ = self.put_to_disk(&key, &value);
+        if !key.stored_week().is_some_and(week_deletion_pending) {
+            self.put_to_disk(&key, &entry);
+        }
+
+        // Store in memory cache
+        self.memory_cache.lock().unwrap().put(key, entry);
     }
 
     /// Get item from disk cache only
-    fn get_from_disk(&self, key: &K) -> Option<V> {
+    ///
+    /// Falls back to the key's legacy file names; a hit there is rewritten under the
+    /// current name and the old file removed. Corrupt files are removed (see
+    /// [`read_cache_file`]).
+    fn get_from_disk(&self, key: &K) -> Option<Timestamped<V>> {
         let path = key.to_file_path();
-        let content = try_read_to_string(&path)?;
-        serde_json::from_str(&content).ok()
+        if path.exists() {
+            return self.read_entry(&path);
+        }
+
+        let dir = path.parent()?;
+        key.legacy_file_keys().into_iter().find_map(|legacy_key| {
+            let legacy_path = dir.join(format!("{}.json", legacy_key));
+            let entry = self.read_entry(&legacy_path)?;
+            if cache_writes_enabled() {
+                self.put_to_disk(key, &entry);
+                if key.to_file_path().exists() {
+                    let _ = fs::remove_file(&legacy_path);
+                }
+            }
+            Some(entry)
+        })
     }
 
-    /// Put item to disk cache only
-    fn put_to_disk(&self, key: &K, value: &V) -> std::io::Result<()> {
+    /// Read a cache file; a bare value from an earlier version counts as fetched
+    /// when the file was written, with the cache's time to live
+    fn read_entry(&self, path: &Path) -> Option<Timestamped<V>> {
+        match read_cache_file::<DiskEntry<V>>(K::NAME, path)? {
+            DiskEntry::Timestamped(entry) => Some(entry),
+            DiskEntry::Bare(value) => Some(Timestamped {
+                fetched_at: unix_now().saturating_sub(cache_file_age_secs(path).unwrap_or(0)),
+                ttl_secs: self.ttl_secs,
+                value,
+            }),
+        }
+    }
+
+    /// Put item to disk cache only (see [`write_cache_file`])
+    fn put_to_disk(&self, key: &K, entry: &Timestamped<V>) {
         let path = key.to_file_path();
-        let content = serde_json::to_string_pretty(value)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        write_string(&path, &content)
+        match serde_json::to_string_pretty(entry) {
+            Ok(content) => write_cache_file(K::NAME, &path, &content),
+            Err(source) => report_cache_error(K::NAME, CacheError::Serialize { path, source }),
+        }
+    }
+
+    /// Seconds since the disk entry for `key` was written; `None` when there isn't one
+    pub fn disk_age_secs(&self, key: &K) -> Option<u64> {
+        cache_file_age_secs(&key.to_file_path())
     }
 
     /// Clear memory cache only (keeps disk cache)
@@ -338,17 +1006,57 @@ where
     /// Clear disk cache for a specific key (used when underlying data changes)
     pub fn invalidate_disk_cache(&self, key: &K) -> std::io::Result<()> {
         let path = key.to_file_path();
-        if path.exists() {
+        if cache_writes_enabled() && path.exists() {
             std::fs::remove_file(path)?;
         }
         Ok(())
     }
 
+    /// Drop every entry whose key matches, from memory and disk
+    ///
+    /// Disk entries can't be turned back into keys, so they are matched on their
+    /// file key instead. Returns the number of files removed; like
+    /// [`invalidate_disk_cache`](Self::invalidate_disk_cache), files are left alone
+    /// while cache writes are disabled.
+    pub fn invalidate_matching(
+        &self,
+        key_matches: impl Fn(&K) -> bool,
+        file_key_matches: impl Fn(&str) -> bool,
+    ) -> usize {
+        self.invalidate_memory(key_matches);
+        remove_cache_files(file_key_matches)
+    }
+
+    /// Drop memory entries whose key matches, leaving cache files alone
+    pub fn invalidate_memory(&self, key_matches: impl Fn(&K) -> bool) {
+        let mut memory = self.memory_cache.lock().unwrap();
+        let stale: Vec<K> = memory
+            .iter()
+            .map(|(key, _)| key)
+            .filter(|key| key_matches(key))
+            .cloned()
+            .collect();
+        for key in stale {
+            memory.pop(&key);
+        }
+    }
+
     /// Get memory cache statistics
     pub fn memory_stats(&self) -> (usize, usize) {
         let cache = self.memory_cache.lock().unwrap();
         (cache.len(), self.memory_capacity)
     }
+
+    /// Memory usage and default time to live of this cache
+    pub fn info(&self) -> CacheInfo {
+        let (entries, capacity) = self.memory_stats();
+        CacheInfo {
+            name: K::NAME,
+            entries,
+            capacity,
+            ttl_secs: self.ttl_secs,
+        }
+    }
 }
 
 /// Global cache manager for the entire application
@@ -360,17 +1068,32 @@ pub struct CacheManager {
     pub league_settings: UnifiedCache<LeagueSettingsCacheKey, Value>,
     pub http_player_data: UnifiedCache<HttpPlayerDataCacheKey, Value>,
     pub roster_data: UnifiedCache<RosterDataCacheKey, Value>,
+    pub schedule_data: UnifiedCache<ScheduleCacheKey, Value>,
+    pub pro_schedule: UnifiedCache<ProScheduleCacheKey, Value>,
+    /// Held while deferred invalidations are flushed, so flushes don't overlap
+    flush_lock: Mutex<()>,
 }
 
 impl CacheManager {
     /// Create a new cache manager with reasonable defaults
+    ///
+    /// Database query caches never expire (they are invalidated when stored rows
+    /// change), and neither do settings or schedules. ESPN payloads expire: rosters
+    /// after [`ROSTER_DATA_TTL_SECS`] and player data after
+    /// [`LIVE_PLAYER_DATA_TTL_SECS`], unless the fetch put them with a longer time
+    /// to live (a completed week's players, a locked week's rosters).
     pub fn new() -> Self {
         Self {
             player_data: UnifiedCache::new(100), // Cache up to 100 player data queries
             weekly_stats: UnifiedCache::new(500), // Cache up to 500 individual player weekly stats
             league_settings: UnifiedCache::new(50), // Cache up to 50 league settings
-            http_player_data: UnifiedCache::new(100), // Cache up to 100 HTTP player data responses
-            roster_data: UnifiedCache::new(50),  // Cache up to 50 roster data responses
+            // Cache up to 100 HTTP player data responses
+            http_player_data: UnifiedCache::new(100).with_ttl(LIVE_PLAYER_DATA_TTL_SECS),
+            // Cache up to 50 roster data responses
+            roster_data: UnifiedCache::new(50).with_ttl(ROSTER_DATA_TTL_SECS),
+            schedule_data: UnifiedCache::new(20), // Cache up to 20 league schedules
+            pro_schedule: UnifiedCache::new(5),   // Cache up to 5 NFL season schedules
+            flush_lock: Mutex::new(()),
         }
     }
 
@@ -381,6 +1104,76 @@ impl CacheManager {
         self.league_settings.clear_memory();
         self.http_player_data.clear_memory();
         self.roster_data.clear_memory();
+        self.schedule_data.clear_memory();
+        self.pro_schedule.clear_memory();
+    }
+
+    /// Drop cached database query results for a week whose stored rows changed
+    ///
+    /// Memory entries go right away. Deleting the week's cache files is deferred to
+    /// [`flush_invalidations`](Self::flush_invalidations), so a backfill writing
+    /// many weeks scans the cache directory once; until then those files are
+    /// skipped on read. The database is shared by every league, so every league's
+    /// entries go.
+    pub fn invalidate_week(&self, season: Season, week: Week) {
+        let in_week = |stored: Option<(Season, Week)>| stored == Some((season, week));
+        self.player_data
+            .invalidate_memory(|key| in_week(key.stored_week()));
+        self.weekly_stats
+            .invalidate_memory(|key| in_week(key.stored_week()));
+
+        let seq = INVALIDATION_SEQ.fetch_add(1, Ordering::Relaxed);
+        PENDING_WEEKS.lock().unwrap().insert((season, week), seq);
+    }
+
+    /// Delete the cache files of every week invalidated since the last flush
+    ///
+    /// Runs once at the end of a command. Weeks stay marked as pending until their
+    /// files are gone, so a concurrent read never picks up a stale file, and a week
+    /// invalidated again mid-flush stays queued for the next one. Returns the number
+    /// of cache files removed.
+    pub fn flush_invalidations(&self) -> usize {
+        let _flushing = self.flush_lock.lock().unwrap();
+        let snapshot = PENDING_WEEKS.lock().unwrap().clone();
+        if snapshot.is_empty() || !cache_writes_enabled() {
+            return 0;
+        }
+
+        let weeks: HashSet<(u16, u16)> = snapshot
+            .keys()
+            .map(|(season, week)| (season.as_u16(), week.as_u16()))
+            .collect();
+        let removed = remove_cache_files(|file_key| {
+            stored_week_of_file_key(file_key).is_some_and(|week| weeks.contains(&week))
+        });
+
+        PENDING_WEEKS
+            .lock()
+            .unwrap()
+            .retain(|week, seq| snapshot.get(week) != Some(seq));
+        removed
+    }
+
+    /// Drop every cached ESPN player payload for a league week, whatever its filters
+    ///
+    /// Used when a refetch replaces one filter combination, so the others can't keep
+    /// serving the older payload. Returns the number of cache files removed.
+    pub fn invalidate_http_player_week(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+    ) -> usize {
+        let prefix = format!(
+            "http_player_data_l{}_s{}_w{}_",
+            league_id.as_u32(),
+            season.as_u16(),
+            week.as_u16()
+        );
+        self.http_player_data.invalidate_matching(
+            |key| key.league_id == league_id && key.season == season && key.week == week,
+            |file_key| file_key.starts_with(&prefix),
+        )
     }
 
     /// Get memory usage statistics for all caches
@@ -397,8 +1190,26 @@ impl CacheManager {
             self.http_player_data.memory_stats(),
         );
         stats.insert("roster_data".to_string(), self.roster_data.memory_stats());
+        stats.insert(
+            "schedule_data".to_string(),
+            self.schedule_data.memory_stats(),
+        );
+        stats.insert("pro_schedule".to_string(), self.pro_schedule.memory_stats());
         stats
     }
+
+    /// Memory usage and default time to live of every cache
+    pub fn cache_info(&self) -> Vec<CacheInfo> {
+        vec![
+            self.player_data.info(),
+            self.weekly_stats.info(),
+            self.league_settings.info(),
+            self.http_player_data.info(),
+            self.roster_data.info(),
+            self.schedule_data.info(),
+            self.pro_schedule.info(),
+        ]
+    }
 }
 
 impl Default for CacheManager {
@@ -408,7 +1219,6 @@ impl Default for CacheManager {
 }
 
 // Global cache instance using lazy_static pattern
-use std::sync::LazyLock;
 
 /// Global cache manager instance for use across the application
 pub static GLOBAL_CACHE: LazyLock<CacheManager> = LazyLock::new(CacheManager::new);
@@ -419,13 +1229,23 @@ mod tests {
     use std::fs;
     use tempfile::tempdir;
 
+    #[test]
+    fn test_league_settings_file_key() {
+        assert_eq!(
+            league_settings_file_key("league-settings_2023_12345"),
+            Some((2023, 12345))
+        );
+        assert_eq!(league_settings_file_key("league-settings_2023"), None);
+        assert_eq!(league_settings_file_key("player_data_l1_s2023_w1"), None);
+    }
+
     #[test]
     fn test_league_settings_path() {
         let path = league_settings_path(2023, 12345);
         let path_str = path.to_string_lossy();
 
         assert!(path_str.contains("espn-ffl"));
-        assert!(path_str.contains("league-settings_2023_12345.json"));
+        assert!(path.ends_with("12345/league-settings_2023_12345.json"));
     }
 
     #[test]
@@ -470,9 +1290,79 @@ mod tests {
         assert_eq!(content, "test content");
     }
 
+    #[test]
+    fn test_write_string_replaces_and_leaves_no_temp_files() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("output.json");
+
+        write_string(&file_path, "first").unwrap();
+        write_string(&file_path, "second").unwrap();
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), "second");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_concurrent_writers_never_expose_partial_json() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("player_data.json");
+
+        // Large payloads make a torn write likely if writes were not atomic
+        let payload = |marker: u32| {
+            let values: Vec<u32> = (0..20_000).map(|i| i + marker).collect();
+            serde_json::to_string(&serde_json::json!({ "marker": marker, "values": values }))
+                .unwrap()
+        };
+        let payloads: Vec<String> = (0..2).map(payload).collect();
+
+        std::thread::scope(|scope| {
+            for writer in 0..2 {
+                let file_path = &file_path;
+                let payloads = &payloads;
+                scope.spawn(move || {
+                    for i in 0..50 {
+                        write_string(file_path, &payloads[(writer + i) % 2]).unwrap();
+                    }
+                });
+            }
+
+            let file_path = &file_path;
+            scope.spawn(move || {
+                for _ in 0..500 {
+                    if let Some(content) = try_read_to_string(file_path) {
+                        let parsed: Result<Value, _> = serde_json::from_str(&content);
+                        assert!(parsed.is_ok(), "reader observed partial JSON");
+                    }
+                }
+            });
+        });
+
+        // Only the final cache file remains once all writers finish
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_read_cache_file_removes_corrupt_files() {
+        let temp_dir = tempdir().unwrap();
+        let file_path = temp_dir.path().join("roster_data.json");
+
+        write_string(&file_path, "{\"value\": [1, 2").unwrap();
+        assert_eq!(read_cache_file::<Value>("test", &file_path), None);
+        assert!(!file_path.exists());
+
+        // Missing is a plain miss; a rewritten file reads cleanly
+        assert_eq!(read_cache_file::<Value>("test", &file_path), None);
+        write_cache_file("test", &file_path, "{\"value\": [1, 2]}");
+        assert_eq!(
+            read_cache_file::<Value>("test", &file_path),
+            Some(serde_json::json!({"value": [1, 2]}))
+        );
+    }
+
     #[test]
     fn test_cache_key_generation() {
         let key = PlayerDataCacheKey {
+            league_id: LeagueId::new(42),
             season: Season::new(2025),
             week: Week::new(1),
             player_names: Some(vec!["Josh Allen".to_string()]),
@@ -481,28 +1371,153 @@ mod tests {
             injury_status: None,
             roster_status: None,
             fantasy_team_filter: None,
+            nfl_team: None,
         };
 
         let file_key = key.to_file_key();
         assert!(file_key.contains("player_data"));
+        assert!(file_key.contains("l42"));
         assert!(file_key.contains("s2025"));
         assert!(file_key.contains("w1"));
         assert!(file_key.contains("Josh Allen"));
         assert!(file_key.contains("actual"));
     }
 
+    #[test]
+    fn test_league_keys_live_in_league_directories() {
+        let roster = RosterDataCacheKey {
+            league_id: LeagueId::new(42),
+            season: Season::new(2025),
+            week: None,
+        };
+        assert!(roster
+            .to_file_path()
+            .ends_with("42/roster_data_l42_s2025_current.json"));
+
+        let schedule = ProScheduleCacheKey {
+            season: Season::new(2025),
+        };
+        assert_eq!(
+            schedule.to_file_path(),
+            cache_files_dir().join("pro_schedule_s2025.json")
+        );
+    }
+
+    #[test]
+    fn test_migrate_flat_cache_files() {
+        let dir = tempdir().unwrap();
+        let base = dir.path();
+        for name in [
+            "league-settings_2025_111.json",
+            "league_settings_l111_s2025.json",
+            "http_player_data_l222_s2025_w3_all_names_all_pos_all_inj_all_ros.json",
+            "roster_data_l222_s2025_current.json",
+            "schedule_data_l111_s2025.json",
+            "player_data_s2025_w3_all_names_all_pos_all_inj_all_ros_all_teams_actual.json",
+            "weekly_stats_p100_s2025_w3.json",
+            "pro_schedule_s2025.json",
+            "scoring_overrides.json",
+        ] {
+            fs::write(base.join(name), "{}").unwrap();
+        }
+        fs::write(base.join("players.db"), "").unwrap();
+        // A newer copy already in the league directory wins over the flat one
+        fs::create_dir_all(base.join("222")).unwrap();
+        fs::write(base.join("222/roster_data_l222_s2025_current.json"), "[]").unwrap();
+
+        let migration = migrate_flat_cache_files(base).unwrap();
+        assert_eq!(
+            migration,
+            CacheMigration {
+                moved: 4,
+                discarded: 3
+            }
+        );
+
+        let mut left: Vec<String> = fs::read_dir(base)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.path().is_file())
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        left.sort();
+        assert_eq!(
+            left,
+            vec![
+                "players.db",
+                "pro_schedule_s2025.json",
+                "scoring_overrides.json"
+            ]
+        );
+        assert!(base.join("111/league-settings_2025_111.json").exists());
+        assert!(base.join("111/schedule_data_l111_s2025.json").exists());
+        assert_eq!(
+            fs::read_to_string(base.join("222/roster_data_l222_s2025_current.json")).unwrap(),
+            "[]"
+        );
+
+        // Running again finds nothing to do
+        assert_eq!(
+            migrate_flat_cache_files(base).unwrap(),
+            CacheMigration::default()
+        );
+    }
+
+    #[test]
+    fn test_http_player_data_key_ignores_stat_source_and_tracks_filters() {
+        let key = HttpPlayerDataCacheKey {
+            league_id: LeagueId::new(42),
+            season: Season::new(2025),
+            week: Week::new(3),
+            player_names: None,
+            positions: None,
+            injury_status: None,
+            roster_status: None,
+            limit: None,
+        };
+        assert_eq!(
+            key.to_file_key(),
+            "http_player_data_l42_s2025_w3_all_names_all_pos_all_inj_all_ros"
+        );
+        assert_eq!(
+            key.legacy_file_keys(),
+            vec!["http_player_data_l42_s2025_w3_all_names_all_pos_actual".to_string()]
+        );
+
+        // Filtered payloads never matched the old unfiltered file
+        let filtered = HttpPlayerDataCacheKey {
+            roster_status: Some(RosterStatusFilter::FA),
+            ..key.clone()
+        };
+        assert_ne!(filtered.to_file_key(), key.to_file_key());
+        assert!(filtered.legacy_file_keys().is_empty());
+
+        // Limited payloads hold a subset, so they're kept apart too
+        let limited = HttpPlayerDataCacheKey {
+            limit: Some(50),
+            ..key.clone()
+        };
+        assert_eq!(
+            limited.to_file_key(),
+            "http_player_data_l42_s2025_w3_all_names_all_pos_all_inj_all_ros_lim50"
+        );
+        assert!(limited.legacy_file_keys().is_empty());
+    }
+
     #[test]
     fn test_unified_cache_memory_operations() {
         let cache: UnifiedCache<WeeklyStatsCacheKey, Option<String>> = UnifiedCache::new(2);
 
         // Use unique test keys to avoid cache conflicts with real data
         let key1 = WeeklyStatsCacheKey {
+            league_id: LeagueId::new(42),
             player_id: PlayerId::new(999991),
             season: Season::new(2099),
             week: Week::new(99),
         };
 
         let key2 = WeeklyStatsCacheKey {
+            league_id: LeagueId::new(42),
             player_id: PlayerId::new(999992),
             season: Season::new(2099),
             week: Week::new(99),
@@ -518,6 +1533,7 @@ mod tests {
         // Test LRU eviction
         cache.put(key2.clone(), Some("test_data2".to_string()));
         let key3 = WeeklyStatsCacheKey {
+            league_id: LeagueId::new(42),
             player_id: PlayerId::new(999993),
             season: Season::new(2099),
             week: Week::new(99),
@@ -530,6 +1546,65 @@ mod tests {
         assert_eq!(stats.1, 2); // Capacity is 2
     }
 
+    #[test]
+    fn test_expired_entries_are_misses_and_their_files_removed() {
+        let entry = |fetched_at, ttl_secs| Timestamped {
+            fetched_at,
+            ttl_secs,
+            value: (),
+        };
+        assert!(!entry(1_000, None).is_expired(u64::MAX));
+        assert!(!entry(1_000, Some(60)).is_expired(1_060));
+        assert!(entry(1_000, Some(60)).is_expired(1_061));
+
+        let cache: UnifiedCache<WeeklyStatsCacheKey, Option<String>> =
+            UnifiedCache::new(2).with_ttl(60);
+        let key = |player_id| WeeklyStatsCacheKey {
+            league_id: LeagueId::new(42),
+            player_id: PlayerId::new(player_id),
+            season: Season::new(2098),
+            week: Week::new(99),
+        };
+        let (expired, bare) = (key(999_981), key(999_982));
+
+        // An envelope fetched long ago has run out its time to live
+        write_string(
+            &expired.to_file_path(),
+            r#"{"fetched_at": 1000, "ttl_secs": 60, "value": "old"}"#,
+        )
+        .unwrap();
+        assert_eq!(cache.get(&expired), None);
+        assert!(!expired.to_file_path().exists());
+
+        // A bare value from an earlier version counts from the file's age
+        write_string(&bare.to_file_path(), r#""legacy""#).unwrap();
+        let entry = cache.get_timestamped(&bare).unwrap();
+        assert_eq!(entry.value, Some("legacy".to_string()));
+        assert_eq!(entry.ttl_secs, Some(60));
+        assert!(!entry.is_expired(unix_now()));
+        cache.invalidate_disk_cache(&bare).unwrap();
+
+        // Entries put with their own time to live keep it, in memory and on disk
+        cache.put_with_ttl(expired.clone(), Some("final".to_string()), None);
+        let written = fs::read_to_string(expired.to_file_path()).unwrap();
+        assert!(!written.contains("ttl_secs"));
+        cache.clear_memory();
+        assert_eq!(cache.get_timestamped(&expired).unwrap().ttl_secs, None);
+        cache.invalidate_disk_cache(&expired).unwrap();
+    }
+
+    #[test]
+    fn test_cache_info_lists_default_ttls() {
+        let info = CacheManager::new().cache_info();
+        let ttl = |name: &str| info.iter().find(|c| c.name == name).unwrap().ttl_secs;
+
+        assert_eq!(info.len(), 7);
+        assert_eq!(ttl("roster_data"), Some(ROSTER_DATA_TTL_SECS));
+        assert_eq!(ttl("http_player_data"), Some(LIVE_PLAYER_DATA_TTL_SECS));
+        assert_eq!(ttl("player_data"), None);
+        assert_eq!(ttl("pro_schedule"), None);
+    }
+
     #[test]
     fn test_cache_manager_creation() {
         let manager = CacheManager::new();
@@ -540,6 +1615,8 @@ mod tests {
         assert!(stats.contains_key("league_settings"));
         assert!(stats.contains_key("http_player_data"));
         assert!(stats.contains_key("roster_data"));
+        assert!(stats.contains_key("schedule_data"));
+        assert!(stats.contains_key("pro_schedule"));
 
         // All caches should start empty
         for (_, (used, _capacity)) in stats {
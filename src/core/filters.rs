@@ -12,6 +12,7 @@
 //! - **filterInjured**: Filter by injury status (works)
 //! - **filterName**: Filter by player name (works for single names)
 //! - **filterSlotIds**: Filter by position IDs (works)
+//! - **limit** / **offset**: Page through the results (works)
 //!
 //! Other filters like `filterHealthy`, `filterFreeAgent`, etc. are ignored by ESPN's API.
 
@@ -61,6 +62,13 @@ pub struct PlayersFilter {
     pub filter_injured: Option<Val<bool>>,
     // Note: filterHealthy, filterFreeAgent, filterAvailable, etc. don't seem to work as server-side filters
     // We'll handle roster filtering client-side after getting the data
+    /// Most players ESPN returns for the request (one page)
+    #[serde(rename = "limit", skip_serializing_if = "Option::is_none")]
+    pub filter_limit: Option<u32>,
+
+    /// Players to skip before the page starts
+    #[serde(rename = "offset", skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
 }
 
 /// General-purpose helper: any Serialize → JSON → HeaderValue
@@ -107,23 +115,14 @@ pub fn build_players_filter(
         f.filter_active = Some(Val { value: active });
     }
 
-    // Add injury status filters (only server-side ones that actually work)
-    if let Some(injury_filter) = injury_status_filter {
-        match injury_filter {
-            InjuryStatusFilter::Active => {
-                // Use filterActive=true to get only active players
-                f.filter_active = Some(Val { value: true });
-            }
-            InjuryStatusFilter::Injured => {
-                // Use filterInjured=true to get only injured players
-                f.filter_injured = Some(Val { value: true });
-            }
-            // For specific injury statuses (Out, Doubtful, etc.), we'll filter client-side
-            // since ESPN doesn't support granular injury status filtering
-            _ => {
-                // Don't set any server-side filter, we'll filter client-side
-            }
-        }
+    // Add injury status filters (only server-side ones that actually work).
+    // Injured is filtered client-side: ESPN's filterInjured leaves out suspended
+    // and PUP players, which count as injured here (see `InjuryStatus`). Specific
+    // statuses (Out, Doubtful, etc.) are client-side too, since ESPN doesn't
+    // support granular injury status filtering.
+    if let Some(InjuryStatusFilter::Active) = injury_status_filter {
+        // Use filterActive=true to get only active players
+        f.filter_active = Some(Val { value: true });
     }
 
     // Roster status filters don't work server-side, so we handle them client-side
@@ -149,6 +148,8 @@ mod tests {
         assert!(filter.filter_name.is_none());
         assert!(filter.filter_slot_ids.is_none());
         assert!(filter.filter_injured.is_none());
+        assert!(filter.filter_limit.is_none());
+        assert!(filter.offset.is_none());
     }
 
     #[test]
@@ -169,7 +170,7 @@ mod tests {
     fn test_build_players_filter_with_active() {
         let filter = build_players_filter(None, None, Some(true), None, None);
         assert!(filter.filter_active.is_some());
-        assert_eq!(filter.filter_active.unwrap().value, true);
+        assert!(filter.filter_active.unwrap().value);
     }
 
     #[test]
@@ -193,5 +194,17 @@ mod tests {
         assert!(json.contains("\"filterName\":{\"value\":\"Test\"}"));
         assert!(json.contains("\"filterSlotIds\":{\"value\":[0,2]}"));
         assert!(json.contains("\"filterActive\":{\"value\":true}"));
+        assert!(!json.contains("limit"));
+    }
+
+    #[test]
+    fn test_players_filter_serializes_paging() {
+        let filter = PlayersFilter {
+            filter_limit: Some(50),
+            offset: Some(100),
+            ..PlayersFilter::default()
+        };
+        let json = serde_json::to_string(&filter).unwrap();
+        assert_eq!(json, r#"{"limit":50,"offset":100}"#);
     }
 }
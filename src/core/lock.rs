@@ -0,0 +1,136 @@
+//! Advisory process lock for destructive database operations
+//!
+//! A lock file holding the owner's PID is created next to the database. Commands
+//! that wipe or rewrite stored data hold it for the duration of the change, so a
+//! cron job and an interactive run can't clear the database out from under each
+//! other. Lock files left behind by a crashed process are detected and replaced.
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{error::EspnError, storage::PlayerDatabase, Result};
+
+/// Held advisory lock; the lock file is removed on drop
+#[derive(Debug)]
+pub struct ProcessLock {
+    path: PathBuf,
+}
+
+impl ProcessLock {
+    /// Acquire the lock guarding destructive operations on the local database
+    pub fn acquire_database() -> Result<Self> {
+        let mut path = PlayerDatabase::database_path()?.into_os_string();
+        path.push(".lock");
+        let path = PathBuf::from(path);
+        Self::acquire_at(&path)
+    }
+
+    /// Acquire a lock file at `path`, failing if another live process holds it
+    pub fn acquire_at(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Second attempt only happens after removing a stale lock
+        for _ in 0..2 {
+            match OpenOptions::new().write(true).create_new(true).open(path) {
+                Ok(mut file) => {
+                    writeln!(file, "{}", std::process::id())?;
+                    return Ok(Self {
+                        path: path.to_path_buf(),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+                    let holder = read_holder_pid(path);
+                    if holder.is_some_and(|pid| !process_alive(pid)) {
+                        let _ = fs::remove_file(path);
+                        continue;
+                    }
+                    return Err(locked_error(path, holder));
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Err(locked_error(path, read_holder_pid(path)))
+    }
+
+    /// Path of the lock file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ProcessLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn read_holder_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn locked_error(path: &Path, holder: Option<u32>) -> EspnError {
+    EspnError::Locked {
+        path: path.display().to_string(),
+        holder: holder.map_or_else(|| "unknown PID".to_string(), |pid| format!("PID {}", pid)),
+    }
+}
+
+/// Whether a process with this PID is still running
+///
+/// Only Linux can check cheaply (via /proc); elsewhere the lock is assumed live and
+/// a stale file has to be removed by hand.
+fn process_alive(pid: u32) -> bool {
+    if pid == std::process::id() {
+        return true;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        Path::new("/proc").join(pid.to_string()).exists()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_lock_is_exclusive_and_released_on_drop() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("players.db.lock");
+
+        let lock = ProcessLock::acquire_at(&path).unwrap();
+        assert_eq!(read_holder_pid(&path), Some(std::process::id()));
+
+        let err = ProcessLock::acquire_at(&path).unwrap_err();
+        assert!(matches!(err, EspnError::Locked { .. }));
+
+        drop(lock);
+        assert!(!path.exists());
+        assert!(ProcessLock::acquire_at(&path).is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_stale_lock_is_replaced() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("players.db.lock");
+
+        // PIDs are capped well below u32::MAX, so this process can't exist
+        fs::write(&path, format!("{}\n", u32::MAX)).unwrap();
+
+        let _lock = ProcessLock::acquire_at(&path).unwrap();
+        assert_eq!(read_holder_pid(&path), Some(std::process::id()));
+    }
+}
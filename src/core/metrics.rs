@@ -0,0 +1,345 @@
+//! Per-run counters for `--timings` (and `--verbose` runs)
+//!
+//! A process-wide collector tallies HTTP requests, bytes and wait time per ESPN
+//! endpoint, hits, misses and errors per cache, database rows written and wall-clock time
+//! per named stage, so a slow backfill can be pinned on ESPN, parsing or SQLite.
+//! Where the OS reports it, the process's peak resident memory and its growth over
+//! the run are included, so memory regressions on big pulls are visible.
+//! Collection is off by default; while off, every recording call is a single
+//! atomic load.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::cli::render::{output_width, Align, Column, Table};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+static METRICS: Mutex<RunMetrics> = Mutex::new(RunMetrics::new());
+
+/// Requests made to one ESPN endpoint
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpStats {
+    pub requests: u64,
+    pub bytes: u64,
+    /// Time from sending the request until the whole body arrived
+    pub wait: Duration,
+}
+
+/// Lookups in one cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Failed writes and corrupt files (see [`crate::error::CacheError`])
+    pub errors: u64,
+}
+
+/// Everything recorded during a run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunMetrics {
+    pub http: BTreeMap<&'static str, HttpStats>,
+    pub caches: BTreeMap<&'static str, CacheStats>,
+    pub rows_written: u64,
+    /// Total time per stage, in the order stages first ran
+    pub stages: Vec<(&'static str, Duration)>,
+    /// Resident memory (KB) when collection started
+    pub start_rss_kb: Option<u64>,
+    /// Peak resident memory (KB) of the process
+    pub peak_rss_kb: Option<u64>,
+}
+
+impl RunMetrics {
+    pub const fn new() -> Self {
+        Self {
+            http: BTreeMap::new(),
+            caches: BTreeMap::new(),
+            rows_written: 0,
+            stages: Vec::new(),
+            start_rss_kb: None,
+            peak_rss_kb: None,
+        }
+    }
+
+    pub fn record_http(&mut self, endpoint: &'static str, bytes: u64, wait: Duration) {
+        let stats = self.http.entry(endpoint).or_default();
+        stats.requests += 1;
+        stats.bytes += bytes;
+        stats.wait += wait;
+    }
+
+    pub fn record_cache(&mut self, cache: &'static str, hit: bool) {
+        let stats = self.caches.entry(cache).or_default();
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+    }
+
+    pub fn record_cache_error(&mut self, cache: &'static str) {
+        self.caches.entry(cache).or_default().errors += 1;
+    }
+
+    /// Add a stage's time to its running total; repeated stages (one per week) accumulate
+    pub fn record_stage(&mut self, stage: &'static str, elapsed: Duration) {
+        match self.stages.iter_mut().find(|(name, _)| *name == stage) {
+            Some((_, total)) => *total += elapsed,
+            None => self.stages.push((stage, elapsed)),
+        }
+    }
+
+    pub fn total_requests(&self) -> u64 {
+        self.http.values().map(|s| s.requests).sum()
+    }
+
+    /// Summary tables, one line per entry
+    pub fn summary_lines(&self, width: Option<usize>) -> Vec<String> {
+        let mut lines = vec!["Run timings".to_string()];
+
+        let mut http = Table::new(vec![
+            Column::flex("Endpoint", 8, 24),
+            Column::fixed("Requests", Align::Right),
+            Column::fixed("KB", Align::Right),
+            Column::fixed("Wait (s)", Align::Right),
+        ]);
+        for (endpoint, stats) in &self.http {
+            http.add_row(vec![
+                endpoint.to_string(),
+                stats.requests.to_string(),
+                format!("{:.1}", stats.bytes as f64 / 1024.0),
+                format!("{:.2}", stats.wait.as_secs_f64()),
+            ]);
+        }
+        if http.is_empty() {
+            lines.push("No HTTP requests".to_string());
+        } else {
+            lines.extend(http.render(width));
+        }
+
+        if !self.caches.is_empty() {
+            let mut caches = Table::new(vec![
+                Column::flex("Cache", 8, 24),
+                Column::fixed("Hits", Align::Right),
+                Column::fixed("Misses", Align::Right),
+                Column::fixed("Errors", Align::Right),
+            ]);
+            for (cache, stats) in &self.caches {
+                caches.add_row(vec![
+                    cache.to_string(),
+                    stats.hits.to_string(),
+                    stats.misses.to_string(),
+                    stats.errors.to_string(),
+                ]);
+            }
+            lines.push(String::new());
+            lines.extend(caches.render(width));
+        }
+
+        if !self.stages.is_empty() {
+            let mut stages = Table::new(vec![
+                Column::flex("Stage", 8, 24),
+                Column::fixed("Time (s)", Align::Right),
+            ]);
+            for (stage, elapsed) in &self.stages {
+                stages.add_row(vec![
+                    stage.to_string(),
+                    format!("{:.2}", elapsed.as_secs_f64()),
+                ]);
+            }
+            lines.push(String::new());
+            lines.extend(stages.render(width));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("Database rows written: {}", self.rows_written));
+        if let Some(peak) = self.peak_rss_kb {
+            let mb = |kb: u64| kb as f64 / 1024.0;
+            lines.push(match self.start_rss_kb {
+                Some(start) => format!(
+                    "Peak memory: {:.1} MB ({:+.1} MB over start)",
+                    mb(peak),
+                    mb(peak) - mb(start)
+                ),
+                None => format!("Peak memory: {:.1} MB", mb(peak)),
+            });
+        }
+        lines
+    }
+}
+
+/// Start collecting metrics for this run
+pub fn enable_metrics() {
+    ENABLED.store(true, Ordering::Relaxed);
+    with_metrics(|m| m.start_rss_kb = process_memory_kb("VmRSS"));
+}
+
+/// Whether metrics are being collected
+pub fn metrics_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A memory figure (KB) for this process from `/proc/self/status`: `VmRSS` for the
+/// current resident size, `VmHWM` for the peak. `None` off Linux.
+fn process_memory_kb(field: &str) -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status.lines().find_map(|line| {
+        line.strip_prefix(field)?
+            .strip_prefix(':')?
+            .trim()
+            .strip_suffix("kB")?
+            .trim()
+            .parse()
+            .ok()
+    })
+}
+
+fn with_metrics(f: impl FnOnce(&mut RunMetrics)) {
+    if metrics_enabled() {
+        f(&mut METRICS.lock().unwrap_or_else(|e| e.into_inner()));
+    }
+}
+
+/// Record one completed HTTP request
+pub fn record_http(endpoint: &'static str, bytes: u64, wait: Duration) {
+    with_metrics(|m| m.record_http(endpoint, bytes, wait));
+}
+
+/// Record a cache lookup
+pub fn record_cache(cache: &'static str, hit: bool) {
+    with_metrics(|m| m.record_cache(cache, hit));
+}
+
+/// Record a cache write failure or corrupt cache file
+pub fn record_cache_error(cache: &'static str) {
+    with_metrics(|m| m.record_cache_error(cache));
+}
+
+/// Count database rows written
+pub fn record_rows_written(rows: usize) {
+    with_metrics(|m| m.rows_written += rows as u64);
+}
+
+/// Time a stage until the returned guard is dropped
+pub fn stage(name: &'static str) -> StageTimer {
+    StageTimer {
+        name,
+        started: metrics_enabled().then(Instant::now),
+    }
+}
+
+/// Guard from [`stage`]; adds the elapsed time to the stage when dropped
+pub struct StageTimer {
+    name: &'static str,
+    started: Option<Instant>,
+}
+
+impl Drop for StageTimer {
+    fn drop(&mut self) {
+        if let Some(started) = self.started {
+            let elapsed = started.elapsed();
+            with_metrics(|m| m.record_stage(self.name, elapsed));
+        }
+    }
+}
+
+/// A copy of everything recorded so far
+pub fn metrics_snapshot() -> RunMetrics {
+    METRICS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Clear everything recorded so far
+pub fn reset_metrics() {
+    *METRICS.lock().unwrap_or_else(|e| e.into_inner()) = RunMetrics::new();
+}
+
+/// Print the run summary to stderr, keeping stdout clean for `--json`
+pub fn print_metrics_summary() {
+    // tarpaulin::skip - console output
+    eprintln!();
+    let mut metrics = metrics_snapshot();
+    metrics.peak_rss_kb = process_memory_kb("VmHWM");
+    for line in metrics.summary_lines(output_width()) {
+        eprintln!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_metrics_accumulate() {
+        let mut metrics = RunMetrics::new();
+        metrics.record_http("players", 2048, Duration::from_millis(300));
+        metrics.record_http("players", 1024, Duration::from_millis(200));
+        metrics.record_http("mSettings", 10, Duration::from_millis(50));
+        metrics.record_cache("roster_data", false);
+        metrics.record_cache("roster_data", true);
+        metrics.record_cache("roster_data", true);
+        metrics.record_cache_error("roster_data");
+        metrics.record_stage("store", Duration::from_millis(5));
+        metrics.record_stage("fetch", Duration::from_millis(7));
+        metrics.record_stage("store", Duration::from_millis(5));
+
+        assert_eq!(
+            metrics.http["players"],
+            HttpStats {
+                requests: 2,
+                bytes: 3072,
+                wait: Duration::from_millis(500),
+            }
+        );
+        assert_eq!(metrics.total_requests(), 3);
+        assert_eq!(
+            metrics.caches["roster_data"],
+            CacheStats {
+                hits: 2,
+                misses: 1,
+                errors: 1,
+            }
+        );
+        // First-run order is kept and repeats add up
+        assert_eq!(
+            metrics.stages,
+            vec![
+                ("store", Duration::from_millis(10)),
+                ("fetch", Duration::from_millis(7))
+            ]
+        );
+
+        let summary = metrics.summary_lines(None).join("\n");
+        assert!(summary.contains("players"));
+        assert!(summary.contains("3.0"));
+        assert!(summary.contains("Database rows written: 0"));
+        assert!(!summary.contains("Peak memory"));
+    }
+
+    #[test]
+    fn test_summary_reports_peak_memory_growth() {
+        let mut metrics = RunMetrics::new();
+        metrics.start_rss_kb = Some(20 * 1024);
+        metrics.peak_rss_kb = Some(52 * 1024 + 512);
+
+        let summary = metrics.summary_lines(None);
+        assert_eq!(
+            summary.last().map(String::as_str),
+            Some("Peak memory: 52.5 MB (+32.5 MB over start)")
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_memory_is_read_on_linux() {
+        let current = process_memory_kb("VmRSS").unwrap();
+        let peak = process_memory_kb("VmHWM").unwrap();
+        assert!(current > 0);
+        assert!(peak >= current);
+    }
+}
@@ -4,10 +4,27 @@
 //! the application:
 //! - `cache`: File system caching utilities
 //! - `filters`: ESPN API filter structures and utilities
+//! - `lock`: Advisory process lock for destructive database operations
+//! - `metrics`: Per-run request, cache, database and stage counters (`--timings`)
 
 pub mod cache;
 pub mod filters;
+pub mod lock;
+pub mod metrics;
 
 // Re-export commonly used items for convenience
-pub use cache::{league_settings_path, try_read_to_string, write_string};
+pub use cache::{
+    cache_file_age_secs, cache_writes_enabled, disable_cache_writes, is_fresh, league_cache_dir,
+    league_settings_files, league_settings_path, read_cache_file, report_cache_error,
+    try_read_to_string, write_cache_file, write_string,
+};
 pub use filters::{build_players_filter, IntoHeaderValue, PlayersFilter, Val};
+pub use lock::ProcessLock;
+
+/// Current time as seconds since the Unix epoch (0 if the clock is set before it)
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
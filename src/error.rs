@@ -1,14 +1,61 @@
 //! Error types for the ESPN Fantasy Football CLI
 
+use std::path::PathBuf;
 use thiserror::Error;
 
 pub type Result<T> = std::result::Result<T, EspnError>;
 
+/// A failure in the on-disk cache
+///
+/// These never fail a command: write failures are shown as warnings, corrupt
+/// files are removed and refetched, and both are counted in `--timings`.
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("could not write cache file {}: {source}", path.display())]
+    Write {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("could not serialize cache entry for {}: {source}", path.display())]
+    Serialize {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("corrupt cache file {}: {source}", path.display())]
+    Corrupt {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+impl CacheError {
+    /// The cache file the error is about
+    pub fn path(&self) -> &std::path::Path {
+        match self {
+            CacheError::Write { path, .. }
+            | CacheError::Serialize { path, .. }
+            | CacheError::Corrupt { path, .. } => path,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum EspnError {
     #[error("HTTP request failed: {0}")]
     Http(#[from] reqwest::Error),
 
+    #[error("HTTP request {request_id} failed: {source}")]
+    HttpRequest {
+        request_id: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
     #[error("JSON parsing failed: {0}")]
     Json(#[from] serde_json::Error),
 
@@ -33,19 +80,124 @@ pub enum EspnError {
     #[error("Invalid position: {position}")]
     InvalidPosition { position: String },
 
+    #[error("Unknown NFL team: {team} (use an abbreviation such as BUF or LAR)")]
+    InvalidNflTeam { team: String },
+
     #[error("Player not found: {name}")]
     PlayerNotFound { name: String },
 
+    #[error("Player name {name} matches several players ({}); use the player ID instead", candidates.join(", "))]
+    AmbiguousPlayerName {
+        name: String,
+        candidates: Vec<String>,
+    },
+
+    #[error("Compare needs at least two players, got {count}")]
+    TooFewPlayers { count: usize },
+
+    #[error("--sort {sort} needs projected and actual points side by side; add --both")]
+    SortRequiresBoth { sort: String },
+
+    #[error("Note not found: {note_id}")]
+    NoteNotFound { note_id: i64 },
+
+    #[error("Unknown stat category {name}; known categories: {}", known.join(", "))]
+    UnknownStatCategory { name: String, known: Vec<String> },
+
+    #[error("Invalid scoring overrides file {path}: {message}")]
+    InvalidScoringOverrides { path: String, message: String },
+
+    #[error("Invalid credentials file {path}: {message}")]
+    InvalidCredentials { path: String, message: String },
+
+    #[error("Scoring override for stat {stat_id} doesn't match a stat the league scores; valid stat IDs: {}", valid.join(", "))]
+    UnknownOverrideStat { stat_id: u16, valid: Vec<String> },
+
     #[error("Invalid scoring configuration")]
     InvalidScoring,
 
+    #[error("Fantasy team not found: {team}")]
+    TeamNotFound { team: String },
+
+    #[error("No opponent found for team {team_id} in week {week}")]
+    NoOpponent { team_id: u32, week: u16 },
+
+    #[error("Invalid week range: week {from} must come before week {to}")]
+    InvalidWeekRange { from: u16, to: u16 },
+
+    #[error("Invalid weeks '{spec}': {reason}")]
+    InvalidWeeks { spec: String, reason: String },
+
+    #[error(
+        "Season {requested} is not available yet; the latest ESPN season is {latest_available}"
+    )]
+    InvalidSeason {
+        requested: u16,
+        latest_available: u16,
+    },
+
+    #[error("League {league_id} has no season {season}; its first season is {first_season}")]
+    LeagueSeasonNotFound {
+        league_id: u32,
+        season: u16,
+        first_season: u16,
+    },
+
+    #[error("Invalid bias strength {value}: must be a non-negative number")]
+    InvalidBiasStrength { value: f64 },
+
+    #[error("Invalid recent-form blend weight {value}: must be between 0 and 1")]
+    InvalidBlendWeight { value: f64 },
+
+    #[error("Invalid bias decay {value}: must be above 0 and at most 1")]
+    InvalidBiasDecay { value: f64 },
+
+    #[error("Invalid missing-projection share {value}: must be between 0 and 1")]
+    InvalidMissingShare { value: f64 },
+
+    #[error(
+        "{missing} of {total} players have no projection for week {week}, more than the allowed {:.0}%; \
+         not exporting (raise --max-missing to export anyway)",
+        max_share * 100.0
+    )]
+    TooManyMissingProjections {
+        missing: usize,
+        total: usize,
+        week: u16,
+        max_share: f64,
+    },
+
+    #[error("Invalid time '{value}': expected e.g. 2025-09-14T17:00Z (UTC) or Unix seconds")]
+    InvalidTimestamp { value: String },
+
+    #[error("{feature} support is not enabled; rebuild with `--features {feature}`")]
+    FeatureDisabled { feature: String },
+
+    #[error("{} week update(s) failed", failures.len())]
+    UpdateFailed {
+        failures: Vec<crate::commands::update_all_data::UpdateFailure>,
+    },
+
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
 
+    #[error("Another espn-ffl process ({holder}) is modifying the database; if it is no longer running, remove {path}")]
+    Locked { path: String, holder: String },
+
     #[error("System time error: {0}")]
     SystemTime(#[from] std::time::SystemTimeError),
 }
 
+impl EspnError {
+    /// The underlying HTTP error, whether or not it carries a request ID
+    pub fn http_error(&self) -> Option<&reqwest::Error> {
+        match self {
+            EspnError::Http(e) | EspnError::HttpRequest { source: e, .. } => Some(e),
+            _ => None,
+        }
+    }
+}
+
 impl From<Box<dyn std::error::Error + Send + Sync>> for EspnError {
     fn from(err: Box<dyn std::error::Error + Send + Sync>) -> Self {
         EspnError::Cache {
@@ -54,6 +206,14 @@ impl From<Box<dyn std::error::Error + Send + Sync>> for EspnError {
     }
 }
 
+impl From<CacheError> for EspnError {
+    fn from(err: CacheError) -> Self {
+        EspnError::Cache {
+            message: err.to_string(),
+        }
+    }
+}
+
 impl From<anyhow::Error> for EspnError {
     fn from(err: anyhow::Error) -> Self {
         EspnError::Cache {
@@ -1,30 +1,55 @@
 // src/espn/cache_settings.rs
+use std::{
+    fs,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
 use serde_json::Value;
 
-use crate::core::{league_settings_path, try_read_to_string, write_string};
+use crate::core::{
+    cache_file_age_secs, cache_writes_enabled, league_settings_files, league_settings_path,
+    read_cache_file, unix_now, write_cache_file,
+};
 use crate::espn::types::LeagueEnvelope;
 use crate::espn::{http::get_league_settings, types::LeagueSettings};
+use crate::storage::{PlayerDatabase, StoredLeagueSettings};
 use crate::{LeagueId, Result, Season};
 
-/// Try to load league settings from .cache first. If missing or `refresh == true`,
-/// fetch from ESPN (`view=mSettings`), extract the `"settings"` object, and re-write the cache.
+/// Cache name for the settings file in `--timings`
+const SETTINGS_CACHE: &str = "league_settings_file";
+
+/// Set once this process has imported the settings files into the database
+static SETTINGS_FILES_IMPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Load league settings from the database first. If missing or `refresh == true`,
+/// fetch from ESPN (`view=mSettings`), extract the `"settings"` object, and store it.
+///
+/// Settings are kept in the database once it exists. Before that (nothing stored
+/// yet) they are cached in a file as before, so looking at a league never creates
+/// the database, and the file is imported the first time the database is there.
 pub async fn load_or_fetch_league_settings(
     league_id: LeagueId,
     refresh: bool,
     season: Season,
 ) -> Result<LeagueSettings> {
+    let mut db = if PlayerDatabase::database_path()?.exists() {
+        Some(PlayerDatabase::new()?)
+    } else {
+        None
+    };
     let path = league_settings_path(season.as_u16(), league_id.as_u32());
 
-    // 1) Try cache (unless refresh)
+    // 1) Try the database, or the cache file without one (unless refresh)
     if !refresh {
-        // tarpaulin::skip - file I/O operation
-        if let Some(s) = try_read_to_string(&path) {
-            // tarpaulin::skip - JSON parsing of cached data
-            if let Ok(v) = serde_json::from_str::<Value>(&s) {
-                if let Some(parsed) = try_parse_settings_from_cached(&v) {
-                    return Ok(parsed);
-                }
-            }
+        let stored = match db.as_mut() {
+            Some(db) => stored_league_settings(db, league_id, season)?.map(|s| s.settings),
+            // tarpaulin::skip - file I/O operation
+            None => read_cache_file::<Value>(SETTINGS_CACHE, &path)
+                .as_ref()
+                .and_then(try_parse_settings_from_cached),
+        };
+        if let Some(settings) = stored {
+            return Ok(settings);
         }
     }
 
@@ -33,14 +58,72 @@ pub async fn load_or_fetch_league_settings(
     let parsed: LeagueEnvelope =
         serde_json::from_value(get_league_settings(league_id, season).await?)?;
 
-    // 3) Write cache (store the raw ESPN payload so future reads can pluck "settings")
-    if let Ok(json_str) = serde_json::to_string_pretty(&parsed.settings) {
-        let _ = write_string(&path, &json_str); // tarpaulin::skip - file I/O operation
+    // 3) Store them for future runs
+    match db.as_mut() {
+        Some(db) => db.upsert_league_settings(league_id, season, &parsed.settings, unix_now())?,
+        None => {
+            if let Ok(json_str) = serde_json::to_string_pretty(&parsed.settings) {
+                write_cache_file(SETTINGS_CACHE, &path, &json_str); // tarpaulin::skip - file I/O operation
+            }
+        }
     }
 
     Ok(parsed.settings)
 }
 
+/// Whether settings for the league and season are stored, in the database or a cache file
+pub fn has_stored_league_settings(league_id: LeagueId, season: Season) -> Result<bool> {
+    if PlayerDatabase::database_path()?.exists() {
+        let mut db = PlayerDatabase::new()?;
+        return Ok(stored_league_settings(&mut db, league_id, season)?.is_some());
+    }
+    Ok(league_settings_path(season.as_u16(), league_id.as_u32()).exists())
+}
+
+/// League settings stored for a season, importing any settings files first
+pub fn stored_league_settings(
+    db: &mut PlayerDatabase,
+    league_id: LeagueId,
+    season: Season,
+) -> Result<Option<StoredLeagueSettings>> {
+    if !SETTINGS_FILES_IMPORTED.swap(true, Ordering::Relaxed) {
+        import_league_settings_files(db)?;
+    }
+    Ok(db.get_league_settings(league_id, season)?)
+}
+
+/// Import every league settings file from the cache directories into the database
+///
+/// Settings are cached as `league-settings_{season}_{league_id}.json` files until
+/// the database exists. Each is stored with its file's age as the fetch time, unless
+/// the database already has settings for that league and season, and the file is
+/// then removed (kept while cache writes are disabled). Returns the number imported.
+pub fn import_league_settings_files(db: &mut PlayerDatabase) -> Result<usize> {
+    let mut imported = 0;
+    for (season, league_id, path) in league_settings_files() {
+        let (season, league_id) = (Season::new(season), LeagueId::new(league_id));
+        let parsed = read_cache_file::<Value>(SETTINGS_CACHE, &path)
+            .as_ref()
+            .and_then(try_parse_settings_from_cached);
+        if let Some(settings) = parsed {
+            if db.get_league_settings(league_id, season)?.is_none() {
+                let age = cache_file_age_secs(&path).unwrap_or(0);
+                db.upsert_league_settings(
+                    league_id,
+                    season,
+                    &settings,
+                    unix_now().saturating_sub(age),
+                )?;
+                imported += 1;
+            }
+        }
+        if cache_writes_enabled() {
+            let _ = fs::remove_file(&path);
+        }
+    }
+    Ok(imported)
+}
+
 /// Attempt to parse a cached JSON Value into LeagueSettings.
 ///
 /// Supported cache shapes:
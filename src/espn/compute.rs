@@ -1,12 +1,196 @@
+use serde::Serialize;
 use serde_json::Value;
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
-use crate::espn::types::ScoringItem;
+use crate::espn::{
+    stat_categories::stat_label,
+    types::{InjuryStatus, Player, ScoringItem},
+};
+
+/// One week's raw stats keyed by ESPN stat ID (as a string)
+///
+/// Implemented for the typed map on parsed players and for raw JSON, such as the
+/// stat blocks stored in the database, so both score the same way.
+pub trait StatMap {
+    /// Whether the map has any entries at all, numeric or not
+    fn has_entries(&self) -> bool;
+
+    /// Entries with a numeric value
+    fn numeric_stats(&self) -> impl Iterator<Item = (&str, f64)>;
+}
+
+impl StatMap for BTreeMap<String, f64> {
+    fn has_entries(&self) -> bool {
+        !self.is_empty()
+    }
+
+    fn numeric_stats(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.iter()
+            .map(|(stat_id, value)| (stat_id.as_str(), *value))
+    }
+}
+
+impl StatMap for Value {
+    fn has_entries(&self) -> bool {
+        self.as_object().is_some_and(|map| !map.is_empty())
+    }
+
+    fn numeric_stats(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(stat_id, value)| Some((stat_id.as_str(), value.as_f64()?)))
+    }
+}
+
+/// A player's stat blocks, one per season/week/source/split
+pub trait StatBlocks {
+    type Stats: StatMap;
+
+    /// The `stats` map of the block matching all four IDs
+    fn find_stats(&self, season: u16, week: u16, source: u8, split: u8) -> Option<&Self::Stats>;
+}
+
+impl StatBlocks for Player {
+    type Stats = BTreeMap<String, f64>;
+
+    fn find_stats(&self, season: u16, week: u16, source: u8, split: u8) -> Option<&Self::Stats> {
+        self.stats
+            .iter()
+            .find(|s| {
+                s.season_id.as_u16() == season
+                    && s.scoring_period_id.as_u16() == week
+                    && s.stat_source_id == source
+                    && s.stat_split_type_id == split
+            })
+            .map(|s| &s.stats)
+    }
+}
+
+impl StatBlocks for Value {
+    type Stats = Value;
+
+    fn find_stats(&self, season: u16, week: u16, source: u8, split: u8) -> Option<&Self::Stats> {
+        let stats = self.get("stats")?.as_array()?;
+        stats.iter().find_map(|s| {
+            let season_id = s.get("seasonId").and_then(|v| v.as_u64())? as u16;
+            let sp = s.get("scoringPeriodId").and_then(|v| v.as_u64())? as u16;
+            let src = s.get("statSourceId").and_then(|v| v.as_u64())? as u8;
+            let split_id = s.get("statSplitTypeId").and_then(|v| v.as_u64())? as u8;
+            if season_id == season && sp == week && src == source && split_id == split {
+                s.get("stats")
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Scoring slot no position override is keyed on, so only base points apply
+pub const BASE_SCORING_SLOT: u8 = u8::MAX;
+
+/// Raw points a D/ST allowed in the week
+pub const POINTS_ALLOWED_STAT: u16 = 120;
+
+/// Raw yards a D/ST allowed in the week
+pub const YARDS_ALLOWED_STAT: u16 = 127;
+
+/// ESPN's points-allowed bracket stats with each bracket's lowest value
+/// (0, 1-6, 7-13, 14-17, 18-21, 22-27, 28-34, 35-45, 46+)
+pub const POINTS_ALLOWED_BRACKETS: [(u16, f64); 9] = [
+    (89, 0.0),
+    (90, 1.0),
+    (91, 7.0),
+    (92, 14.0),
+    (121, 18.0),
+    (122, 22.0),
+    (123, 28.0),
+    (124, 35.0),
+    (125, 46.0),
+];
+
+/// ESPN's yards-allowed bracket stats with each bracket's lowest value
+/// (under 100, 100-199, 200-299, 300-349, 350-399, 400-449, 450-499, 500-549, 550+)
+pub const YARDS_ALLOWED_BRACKETS: [(u16, f64); 9] = [
+    (128, 0.0),
+    (129, 100.0),
+    (130, 200.0),
+    (131, 300.0),
+    (132, 350.0),
+    (133, 400.0),
+    (134, 450.0),
+    (135, 500.0),
+    (136, 550.0),
+];
+
+/// The bracket stat a raw points- or yards-allowed value falls in
+///
+/// Brackets run up to the next one's lowest value, so fractional projections
+/// (e.g. 21.4 points allowed) land in the bracket below the next whole number.
+pub fn allowed_bracket_stat(brackets: &[(u16, f64)], allowed: f64) -> u16 {
+    brackets
+        .iter()
+        .rev()
+        .find(|(_, lowest)| allowed >= *lowest)
+        .unwrap_or(&brackets[0])
+        .0
+}
+
+/// A week's stats as (stat ID, value) pairs, with D/ST allowed brackets filled in
+///
+/// Tiered points-allowed and yards-allowed scoring is keyed on bracket stats. When
+/// a stat block carries the raw total (stat 120 or 127) but none of that family's
+/// bracket stats, the matching bracket is added with a value of 1; brackets ESPN
+/// sent are used as they are.
+fn scored_stats(weekly_stats_obj: &impl StatMap) -> Vec<(u16, f64)> {
+    let mut stats: Vec<(u16, f64)> = weekly_stats_obj
+        .numeric_stats()
+        .filter_map(|(stat_id, value)| Some((stat_id.parse::<u16>().ok()?, value)))
+        .collect();
+    for (raw_stat, brackets) in [
+        (POINTS_ALLOWED_STAT, &POINTS_ALLOWED_BRACKETS),
+        (YARDS_ALLOWED_STAT, &YARDS_ALLOWED_BRACKETS),
+    ] {
+        let Some(allowed) = stats.iter().find(|(id, _)| *id == raw_stat).map(|s| s.1) else {
+            continue;
+        };
+        let has_bracket = stats
+            .iter()
+            .any(|(id, _)| brackets.iter().any(|(bracket, _)| bracket == id));
+        if !has_bracket {
+            stats.push((allowed_bracket_stat(brackets, allowed), 1.0));
+        }
+    }
+    stats
+}
+
+/// Set by `--strict-positions`
+static STRICT_POSITIONS: AtomicBool = AtomicBool::new(false);
+
+/// Leave players without a known position out of point computation
+pub fn enable_strict_positions() {
+    STRICT_POSITIONS.store(true, Ordering::Relaxed);
+}
+
+/// Whether `--strict-positions` is in effect
+pub fn strict_positions() -> bool {
+    STRICT_POSITIONS.load(Ordering::Relaxed)
+}
 
 pub fn build_scoring_index(items: &[ScoringItem]) -> BTreeMap<u16, (f64, BTreeMap<u8, f64>)> {
     let mut idx = BTreeMap::new();
     for it in items {
-        idx.insert(it.stat_id, (it.points, it.points_overrides.clone()));
+        // Reverse items count against the player
+        let sign = if it.is_reverse_item { -1.0 } else { 1.0 };
+        let overrides = it
+            .points_overrides
+            .iter()
+            .map(|(&slot, &points)| (slot, sign * points))
+            .collect();
+        idx.insert(it.stat_id, (sign * it.points, overrides));
     }
     idx
 }
@@ -14,46 +198,55 @@ pub fn build_scoring_index(items: &[ScoringItem]) -> BTreeMap<u16, (f64, BTreeMa
 /// Select the stat block for a specific season/week/source.
 /// `stat_source_id`: 0 = actual, 1 = projected.
 /// `stat_split_type_id`: 1 = weekly, 0 = season total.
-/// Returns the `stats` map if found.
-pub fn select_weekly_stats(
-    player: &Value,
+/// Returns the `stats` map if found. Works on parsed [`Player`]s directly, with
+/// no round-trip through JSON.
+pub fn select_weekly_stats<P: StatBlocks>(
+    player: &P,
     season: u16,
     week: u16,
     stat_source_id: u8,
-) -> Option<&Value> {
-    let stats = player.get("stats")?.as_array()?;
-    stats.iter().find_map(|s| {
-        let season_id = s.get("seasonId").and_then(|v| v.as_u64())? as u16;
-        let sp = s.get("scoringPeriodId").and_then(|v| v.as_u64())? as u16;
-        let src = s.get("statSourceId").and_then(|v| v.as_u64())? as u8;
-        let split = s.get("statSplitTypeId").and_then(|v| v.as_u64())? as u8;
-        if season_id == season && sp == week && src == stat_source_id && split == 1 {
-            s.get("stats")
-        } else {
-            None
-        }
-    })
+) -> Option<&P::Stats> {
+    player.find_stats(season, week, stat_source_id, 1)
+}
+
+/// Lineup slot whose `pointsOverrides` apply to a player with this ESPN default position
+///
+/// Overrides are keyed by lineup slot (QB 0, RB 2, WR 4, TE 6, D/ST 16, K 17), while
+/// players carry a default position ID (QB 1, RB 2, WR 3, TE 4, K 5, D/ST 16). IDs
+/// that are already slot IDs pass through unchanged.
+pub fn scoring_slot_for_position(position_id: u8) -> u8 {
+    match position_id {
+        1 => 0,
+        3 => 4,
+        4 => 6,
+        5 => 17,
+        other => other,
+    }
+}
+
+/// Scoring slot for an ESPN player
+///
+/// Players whose position can't be determined get base points only, or `None`
+/// under `--strict-positions` so they are left out entirely.
+pub fn scoring_slot_for_player(player: &Player) -> Option<u8> {
+    match player.position() {
+        Some(position) => Some(scoring_slot_for_position(position.to_u8())),
+        None if strict_positions() => None,
+        None => Some(BASE_SCORING_SLOT),
+    }
 }
 
 /// Compute fantasy points for one player's week, given their slot and a scoring index.
+///
+/// D/ST points- and yards-allowed brackets missing from the stats are derived from
+/// the raw totals (see [`POINTS_ALLOWED_BRACKETS`]).
 pub fn compute_points_for_week(
-    weekly_stats_obj: &Value,
+    weekly_stats_obj: &impl StatMap,
     player_slot_id: u8,
     scoring_index: &BTreeMap<u16, (f64, BTreeMap<u8, f64>)>,
 ) -> f64 {
-    let Some(stats_map) = weekly_stats_obj.as_object() else {
-        return 0.0;
-    };
-
     let mut total = 0.0;
-    for (stat_id_str, stat_val) in stats_map {
-        // ESPN stat keys are strings; convert to u16
-        let Ok(stat_id) = stat_id_str.parse::<u16>() else {
-            continue;
-        };
-        let Some(raw) = stat_val.as_f64() else {
-            continue;
-        };
+    for (stat_id, raw) in scored_stats(weekly_stats_obj) {
         if let Some((base_pts, overrides)) = scoring_index.get(&stat_id) {
             let per_unit = overrides.get(&player_slot_id).copied().unwrap_or(*base_pts);
             total += raw * per_unit;
@@ -61,3 +254,82 @@ pub fn compute_points_for_week(
     }
     total
 }
+
+/// Points earned from one stat in a week
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PointsContribution {
+    pub stat_id: u16,
+    /// Human-readable stat name, e.g. "FG 50+"
+    pub label: String,
+    /// The week's raw stat value, e.g. yards or field goals made
+    pub raw_value: f64,
+    pub points_per_unit: f64,
+    pub points: f64,
+    /// Local scoring overrides that changed the points per unit, e.g. "+0.5 (TE premium)"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub adjustment: Option<String>,
+}
+
+/// Break a week's points down by stat, largest contribution (by magnitude) first
+///
+/// Applies the same rules as [`compute_points_for_week`]; stats the league doesn't
+/// score and zero values are left out.
+pub fn explain_points_for_week(
+    weekly_stats_obj: &impl StatMap,
+    player_slot_id: u8,
+    scoring_index: &BTreeMap<u16, (f64, BTreeMap<u8, f64>)>,
+) -> Vec<PointsContribution> {
+    let mut contributions: Vec<PointsContribution> = scored_stats(weekly_stats_obj)
+        .into_iter()
+        .filter_map(|(stat_id, value)| {
+            let raw_value = Some(value).filter(|v| *v != 0.0)?;
+            let (base_pts, overrides) = scoring_index.get(&stat_id)?;
+            let points_per_unit = overrides.get(&player_slot_id).copied().unwrap_or(*base_pts);
+            Some(PointsContribution {
+                stat_id,
+                label: stat_label(stat_id),
+                raw_value,
+                points_per_unit,
+                points: raw_value * points_per_unit,
+                adjustment: None,
+            })
+        })
+        .collect();
+    contributions.sort_by(|a, b| {
+        b.points
+            .abs()
+            .total_cmp(&a.points.abs())
+            .then(a.stat_id.cmp(&b.stat_id))
+    });
+    contributions
+}
+
+/// Infer whether a player took part in a week's game from their actual stat block.
+///
+/// An empty stat map means the player never recorded a stat. A map of all-zero
+/// values is only a DNP when ESPN also flags the player inactive, out, on IR,
+/// PUP or NFI, or suspended; otherwise it is a genuine zero.
+pub fn infer_played(
+    weekly_stats_obj: &impl StatMap,
+    active: Option<bool>,
+    injury_status: Option<&InjuryStatus>,
+) -> bool {
+    if !weekly_stats_obj.has_entries() {
+        return false;
+    }
+
+    let recorded_stat = weekly_stats_obj.numeric_stats().any(|(_, raw)| raw != 0.0);
+    let ruled_out = active == Some(false)
+        || matches!(
+            injury_status,
+            Some(
+                InjuryStatus::Out
+                    | InjuryStatus::InjuryReserve
+                    | InjuryStatus::Suspension
+                    | InjuryStatus::PUP
+                    | InjuryStatus::NonFootballInjury
+            )
+        );
+
+    recorded_stat || !ruled_out
+}
@@ -0,0 +1,182 @@
+//! ESPN cookie credentials for private leagues.
+//!
+//! Credentials come from the `ESPN_SWID`/`ESPN_S2` environment variables or, when
+//! either is unset, from a credentials file in the config directory, e.g.:
+//!
+//! ```text
+//! SWID={1A2B3C4D-...}
+//! espn_s2=AEBxyz...
+//! ```
+
+use std::{
+    fmt,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{error::EspnError, Result};
+
+/// Environment variable holding the SWID cookie
+pub const SWID_ENV_VAR: &str = "ESPN_SWID";
+
+/// Environment variable holding the espn_s2 cookie
+pub const S2_ENV_VAR: &str = "ESPN_S2";
+
+/// File name looked up in the `espn-ffl` config directory
+pub const CREDENTIALS_FILE: &str = "credentials";
+
+/// Only the first loose-permissions warning is printed, not one per request
+static PERMISSIONS_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// The two cookies ESPN needs to read a private league
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Credentials {
+    pub swid: String,
+    pub espn_s2: String,
+}
+
+impl Credentials {
+    /// Value for the `Cookie` request header
+    pub fn cookie_header(&self) -> String {
+        format!("SWID={}; espn_s2={}", self.swid, self.espn_s2)
+    }
+
+    /// Both cookies from the environment, if both are set
+    pub fn from_env() -> Option<Self> {
+        let swid = std::env::var(SWID_ENV_VAR).ok()?;
+        let espn_s2 = std::env::var(S2_ENV_VAR).ok()?;
+        Some(Self { swid, espn_s2 })
+    }
+
+    /// Parse a credentials file's contents
+    ///
+    /// One `key=value` per line; keys are `SWID`/`ESPN_SWID` and `espn_s2`/`ESPN_S2`
+    /// in any case. Blank lines and `#` comments are skipped.
+    pub fn parse(contents: &str) -> std::result::Result<Self, String> {
+        let mut swid = None;
+        let mut espn_s2 = None;
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {} is not key=value", number + 1));
+            };
+            let value = value.trim().trim_matches('"').to_string();
+            match key.trim().to_ascii_lowercase().as_str() {
+                "swid" | "espn_swid" => swid = Some(value),
+                "espn_s2" | "s2" => espn_s2 = Some(value),
+                other => return Err(format!("unknown key {} on line {}", other, number + 1)),
+            }
+        }
+        match (swid, espn_s2) {
+            (Some(swid), Some(espn_s2)) if !swid.is_empty() && !espn_s2.is_empty() => {
+                Ok(Self { swid, espn_s2 })
+            }
+            _ => Err("both SWID and espn_s2 are required".to_string()),
+        }
+    }
+
+    /// Read a credentials file; `None` when it doesn't exist
+    pub fn from_file(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        if let Some(warning) = permissions_warning(path) {
+            if !PERMISSIONS_WARNED.swap(true, Ordering::Relaxed) {
+                eprintln!("⚠ Warning: {}", warning);
+            }
+        }
+        let contents = std::fs::read_to_string(path)?;
+        Self::parse(&contents)
+            .map(Some)
+            .map_err(|message| EspnError::InvalidCredentials {
+                path: path.display().to_string(),
+                message,
+            })
+    }
+
+    /// Write a credentials file readable only by the current user
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = format!("SWID={}\nespn_s2={}\n", self.swid, self.espn_s2);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(path)?;
+        // An existing file keeps its old mode through open, so tighten it too
+        #[cfg(unix)]
+        file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+        file.write_all(contents.as_bytes())?;
+        Ok(())
+    }
+}
+
+/// Where the credentials came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CredentialSource {
+    Env,
+    File(PathBuf),
+}
+
+impl fmt::Display for CredentialSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialSource::Env => {
+                write!(f, "{} / {} environment variables", SWID_ENV_VAR, S2_ENV_VAR)
+            }
+            CredentialSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Where the credentials file is read from and written to
+pub fn credentials_path() -> PathBuf {
+    let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("espn-ffl").join(CREDENTIALS_FILE)
+}
+
+/// Credentials for ESPN requests: the environment variables, else the credentials file
+pub fn resolve_credentials() -> Result<Option<(Credentials, CredentialSource)>> {
+    resolve_credentials_from(Credentials::from_env(), &credentials_path())
+}
+
+/// Apply the precedence rules to already-read environment credentials and a file path
+pub fn resolve_credentials_from(
+    env: Option<Credentials>,
+    file: &Path,
+) -> Result<Option<(Credentials, CredentialSource)>> {
+    if let Some(credentials) = env {
+        return Ok(Some((credentials, CredentialSource::Env)));
+    }
+    Ok(Credentials::from_file(file)?
+        .map(|credentials| (credentials, CredentialSource::File(file.to_path_buf()))))
+}
+
+/// A warning when group or other users have any access to the file
+#[cfg(unix)]
+pub fn permissions_warning(path: &Path) -> Option<String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path).ok()?.permissions().mode();
+    (mode & 0o077 != 0).then(|| {
+        format!(
+            "{} is accessible by other users (mode {:o}); run `chmod 600 {}`",
+            path.display(),
+            mode & 0o777,
+            path.display()
+        )
+    })
+}
+
+/// A warning when group or other users have any access to the file
+#[cfg(not(unix))]
+pub fn permissions_warning(_path: &Path) -> Option<String> {
+    None
+}
@@ -1,17 +1,33 @@
-use reqwest::{header::HeaderValue, Client};
+use reqwest::{header::HeaderValue, Client, RequestBuilder};
+use serde::de::DeserializeOwned;
 use serde_json::Value;
-use std::sync::LazyLock;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        LazyLock,
+    },
+    time::Instant,
+};
 
 use crate::{
     cli::types::{
         filters::{InjuryStatusFilter, RosterStatusFilter},
-        position::Position,
+        position::{FlexEligibility, Position},
+        time::utc_date,
     },
     core::{
         build_players_filter,
-        cache::{HttpPlayerDataCacheKey, LeagueSettingsCacheKey, RosterDataCacheKey, GLOBAL_CACHE},
-        IntoHeaderValue,
+        cache::{
+            HttpPlayerDataCacheKey, LeagueSettingsCacheKey, ProScheduleCacheKey,
+            RosterDataCacheKey, ScheduleCacheKey, GLOBAL_CACHE, LIVE_PLAYER_DATA_TTL_SECS,
+            ROSTER_DATA_TTL_SECS,
+        },
+        is_fresh,
+        metrics::record_http,
+        unix_now, IntoHeaderValue,
     },
+    error::EspnError,
+    espn::credentials::resolve_credentials,
     LeagueId, Result, Season, Week,
 };
 use reqwest::header::{HeaderMap, ACCEPT, COOKIE};
@@ -22,6 +38,70 @@ mod tests;
 /// Base path for ESPN Fantasy Football v3 API.
 pub const FFL_BASE_URL: &str = "https://lm-api-reads.fantasy.espn.com/apis/v3/games/ffl";
 
+/// Environment variable that sets how long cached roster data is trusted, in minutes.
+pub const ROSTER_FRESHNESS_ENV_VAR: &str = "ESPN_FFL_ROSTER_FRESHNESS_MINUTES";
+
+/// Default freshness window for cached roster data, in minutes.
+pub const DEFAULT_ROSTER_FRESHNESS_MINUTES: u64 = 60;
+
+/// Environment variable that overrides [`FFL_BASE_URL`] (e.g. a caching proxy or mock server).
+pub const BASE_URL_ENV_VAR: &str = "ESPN_FFL_BASE_URL";
+
+/// Environment variable that sets the user agent sent to ESPN.
+pub const USER_AGENT_ENV_VAR: &str = "ESPN_FFL_USER_AGENT";
+
+/// User agent used unless [`USER_AGENT_ENV_VAR`] is set; `{version}` is the crate version.
+pub const DEFAULT_USER_AGENT: &str = "espn-ffl-cli/{version}";
+
+/// Header carrying a unique ID per request, echoed in debug output and errors
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Players asked for per `/players` request; larger payloads are fetched a page at a time.
+pub const PLAYERS_PAGE_SIZE: u32 = 1000;
+
+/// Players asked for when ESPN filters by a single name, which only matches a handful.
+pub const NAME_QUERY_LIMIT: u32 = 50;
+
+/// Player limit for a query: small when ESPN filters by name (exactly one given)
+pub fn player_query_limit(player_names: Option<&[String]>) -> Option<u32> {
+    matches!(player_names, Some([_])).then_some(NAME_QUERY_LIMIT)
+}
+
+/// User agent from a custom template (or the default), with `{version}` expanded
+pub fn resolve_user_agent(custom: Option<&str>) -> String {
+    custom
+        .map(str::trim)
+        .filter(|agent| !agent.is_empty())
+        .unwrap_or(DEFAULT_USER_AGENT)
+        .replace("{version}", env!("CARGO_PKG_VERSION"))
+}
+
+/// A random (version 4) UUID identifying one request
+pub fn new_request_id() -> String {
+    // Version nibble 4 and the RFC 4122 variant bits (10xx)
+    let bits = rand::random::<u128>() & !(0xf << 76) & !(0x3 << 62) | (0x4 << 76) | (0x2 << 62);
+    let hex = format!("{:032x}", bits);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Base URL for API requests: `ESPN_FFL_BASE_URL` if set, otherwise the production URL.
+///
+/// Resolved per request so the override also applies after the client is built.
+pub fn ffl_base_url() -> String {
+    std::env::var(BASE_URL_ENV_VAR)
+        .ok()
+        .map(|url| url.trim_end_matches('/').to_string())
+        .filter(|url| !url.is_empty())
+        .unwrap_or_else(|| FFL_BASE_URL.to_string())
+}
+
 /// Parameters for player data retrieval.
 #[derive(Debug)]
 pub struct PlayerDataRequest {
@@ -34,6 +114,12 @@ pub struct PlayerDataRequest {
     pub positions: Option<Vec<Position>>,
     pub injury_status_filter: Option<InjuryStatusFilter>,
     pub roster_status_filter: Option<RosterStatusFilter>,
+    /// What a FLEX filter covers in the league
+    pub flex: FlexEligibility,
+    /// Most players to fetch; `None` fetches every page
+    pub limit: Option<u32>,
+    /// Players per request (see [`PLAYERS_PAGE_SIZE`])
+    pub page_size: u32,
 }
 
 impl PlayerDataRequest {
@@ -49,6 +135,9 @@ impl PlayerDataRequest {
             positions: None,
             injury_status_filter: None,
             roster_status_filter: None,
+            flex: FlexEligibility::default(),
+            limit: None,
+            page_size: PLAYERS_PAGE_SIZE,
         }
     }
 
@@ -87,28 +176,75 @@ impl PlayerDataRequest {
         self.roster_status_filter = Some(filter);
         self
     }
+
+    /// Fetch at most this many players.
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Ask ESPN for this many players per request.
+    pub fn with_page_size(mut self, page_size: u32) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
 }
 
+/// Shared client, built on first use with the user agent configured at that point
 static CLIENT: LazyLock<Client> = LazyLock::new(|| {
+    let custom = std::env::var(USER_AGENT_ENV_VAR).ok();
     Client::builder()
-        .user_agent("espn-ffl-cli")
+        .user_agent(resolve_user_agent(custom.as_deref()))
         .build()
         .expect("Failed to build http client")
 });
 
+/// Set once the nameless-teams warning has been printed
+static NAMELESS_TEAMS_WARNED: AtomicBool = AtomicBool::new(false);
+
+/// Send a request and parse the JSON body, recording it under `endpoint` for `--timings`
+///
+/// Each request gets a fresh [`REQUEST_ID_HEADER`], printed with `debug` and included
+/// in the error if the request fails.
+async fn send_json<T: DeserializeOwned>(
+    request: RequestBuilder,
+    endpoint: &'static str,
+    debug: bool,
+) -> Result<T> {
+    let request_id = new_request_id();
+    if debug {
+        eprintln!("Request-Id => {} ({})", request_id, endpoint); // tarpaulin::skip
+    }
+    let tagged = |source: reqwest::Error| EspnError::HttpRequest {
+        request_id: request_id.clone(),
+        source,
+    };
+
+    let started = Instant::now();
+    // tarpaulin::skip - HTTP client call
+    let body = request
+        .header(REQUEST_ID_HEADER, &request_id)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(tagged)?
+        .bytes()
+        .await
+        .map_err(tagged)?;
+    record_http(endpoint, body.len() as u64, started.elapsed());
+    Ok(serde_json::from_slice(&body)?)
+}
+
 /// Build HTTP headers for ESPN API requests.
 ///
 /// Always includes Accept: application/json header.
-/// Includes cookies if ESPN_SWID and ESPN_S2 environment variables are set.
+/// Includes cookies from ESPN_SWID and ESPN_S2, or else the credentials file.
 fn build_espn_headers() -> Result<HeaderMap> {
     let mut headers = HeaderMap::new();
     headers.insert(ACCEPT, HeaderValue::from_static("application/json"));
 
-    let swid = std::env::var("ESPN_SWID").ok();
-    let s2 = std::env::var("ESPN_S2").ok();
-    if let (Some(swid), Some(s2)) = (swid, s2) {
-        let cookie = format!("SWID={}; espn_s2={}", swid, s2);
-        headers.insert(COOKIE, HeaderValue::from_str(&cookie)?);
+    if let Some((credentials, _)) = resolve_credentials()? {
+        headers.insert(COOKIE, HeaderValue::from_str(&credentials.cookie_header())?);
     }
 
     Ok(headers)
@@ -124,7 +260,8 @@ pub async fn get_league_settings(league_id: LeagueId, season: Season) -> Result<
     // }
 
     let url = format!(
-        "{FFL_BASE_URL}/seasons/{}/segments/0/leagues/{}",
+        "{}/seasons/{}/segments/0/leagues/{}",
+        ffl_base_url(),
         season.as_u16(),
         league_id.as_u32()
     );
@@ -132,15 +269,12 @@ pub async fn get_league_settings(league_id: LeagueId, season: Season) -> Result<
     let headers = build_espn_headers()?;
 
     // tarpaulin::skip - HTTP client call
-    let res = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
-        .await?;
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "mSettings",
+        false,
+    )
+    .await?;
 
     // Cache the result
     GLOBAL_CACHE.league_settings.put(cache_key, res.clone());
@@ -149,15 +283,16 @@ pub async fn get_league_settings(league_id: LeagueId, season: Season) -> Result<
 }
 
 pub async fn get_player_data(request: PlayerDataRequest) -> Result<Value> {
-    // Create cache key - note: we need to determine if this is projected or not
-    // For now, we'll assume this is actual data (projected is handled separately)
+    // The payload holds both actual and projected stats, so one entry serves either
     let cache_key = HttpPlayerDataCacheKey {
         league_id: request.league_id,
         season: request.season,
         week: request.week,
         player_names: request.player_names.clone(),
         positions: request.positions.clone(),
-        projected: false, // This function gets actual data
+        injury_status: request.injury_status_filter.clone(),
+        roster_status: request.roster_status_filter.clone(),
+        limit: request.limit,
     };
 
     // Check cache first (but skip if debug mode or refresh flag is set)
@@ -170,10 +305,10 @@ pub async fn get_player_data(request: PlayerDataRequest) -> Result<Value> {
     // Build the filters from cli args
     let slots: Option<Vec<u8>> = request.positions.map(|ps| {
         ps.into_iter()
-            .flat_map(|p| p.get_all_position_ids())
+            .flat_map(|p| p.league_position_ids(&request.flex))
             .collect()
     });
-    let players_filter = build_players_filter(
+    let mut players_filter = build_players_filter(
         request.player_names,
         slots,
         None,
@@ -181,11 +316,12 @@ pub async fn get_player_data(request: PlayerDataRequest) -> Result<Value> {
         request.roster_status_filter.as_ref(),
     );
 
-    let mut headers = build_espn_headers()?;
-    headers.insert("x-fantasy-filter", players_filter.to_header_value()?);
-
     // URL and query params
-    let url = format!("{FFL_BASE_URL}/seasons/{}/players", request.season.as_u16());
+    let url = format!(
+        "{}/seasons/{}/players",
+        ffl_base_url(),
+        request.season.as_u16()
+    );
     let params = [
         ("forLeagueId", request.league_id.to_string()),
         ("view", "kona_player_info".to_string()),
@@ -193,37 +329,79 @@ pub async fn get_player_data(request: PlayerDataRequest) -> Result<Value> {
         ("scoringPeriodId", request.week.as_u16().to_string()),
     ];
 
-    if request.debug {
-        // tarpaulin::skip - debug output
-        eprintln!(
-            "URL => seasons/{}/players?forLeagueId={}&view=kona_player_info&scoringPeriodId={}",
-            request.season.as_u16(),
-            request.league_id,
-            request.week.as_u16()
-        );
-        for (k, v) in &headers {
-            if let Ok(s) = v.to_str() {
-                eprintln!("{}: {}", k, s); // tarpaulin::skip
+    // Page through the players, stitching the pages into one array
+    let mut players: Vec<Value> = Vec::new();
+    let players_val = loop {
+        let fetched = players.len() as u32;
+        let page_limit = match request.limit {
+            Some(limit) => request.page_size.min(limit.saturating_sub(fetched)),
+            None => request.page_size,
+        };
+        if page_limit == 0 {
+            break Value::Array(players);
+        }
+        players_filter.filter_limit = Some(page_limit);
+        players_filter.offset = (fetched > 0).then_some(fetched);
+
+        let mut headers = build_espn_headers()?;
+        headers.insert("x-fantasy-filter", players_filter.to_header_value()?);
+
+        if request.debug {
+            // tarpaulin::skip - debug output
+            eprintln!(
+                "URL => seasons/{}/players?forLeagueId={}&view=kona_player_info&scoringPeriodId={}",
+                request.season.as_u16(),
+                request.league_id,
+                request.week.as_u16()
+            );
+            for (k, v) in &headers {
+                if let Ok(s) = v.to_str() {
+                    eprintln!("{}: {}", k, s); // tarpaulin::skip
+                }
             }
         }
-    }
 
-    // tarpaulin::skip - HTTP client call
-    let players_val = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
+        // tarpaulin::skip - HTTP client call
+        let page: Value = send_json(
+            CLIENT.get(&url).headers(headers).query(&params),
+            "players",
+            request.debug,
+        )
         .await?;
+        let Value::Array(page) = page else {
+            // Not a player list (e.g. an error body): pass it on as ESPN sent it
+            break page;
+        };
+
+        let page_len = page.len() as u32;
+        // A page that starts with a player already fetched means the offset was ignored
+        let repeated = page.first().is_some_and(|first| players.contains(first));
+        if !repeated {
+            players.extend(page);
+        }
+        if repeated || page_len < page_limit {
+            break Value::Array(players);
+        }
+    };
 
     // Cache the result (but not in debug mode)
     if !request.debug {
+        // A refresh supersedes whatever other filter combinations cached for the week
+        if request.refresh {
+            GLOBAL_CACHE.invalidate_http_player_week(
+                request.league_id,
+                request.season,
+                request.week,
+            );
+        }
+        // A finished week's stats won't change; until then the payload expires
+        let week_over = get_pro_schedule(request.season)
+            .await
+            .is_ok_and(|schedule| schedule.week_is_over(request.week, unix_now()));
+        let ttl = (!week_over).then_some(LIVE_PLAYER_DATA_TTL_SECS);
         GLOBAL_CACHE
             .http_player_data
-            .put(cache_key, players_val.clone());
+            .put_with_ttl(cache_key, players_val.clone(), ttl);
     }
 
     Ok(players_val)
@@ -245,19 +423,38 @@ pub async fn get_league_rosters_with_cache_status(
     };
 
     // Check cache first (but skip if debug mode or refresh flag is set)
-    if !debug && !refresh {
-        if let Some(cached_result) = GLOBAL_CACHE.roster_data.get(&cache_key) {
-            return Ok((cached_result, CacheStatus::Hit));
-        }
-    }
-
-    let cache_status = if refresh {
+    let mut cache_status = if refresh {
         CacheStatus::Refreshed
     } else {
         CacheStatus::Miss
     };
+    if !debug && !refresh {
+        if let Some(cached) = GLOBAL_CACHE.roster_data.get_timestamped(&cache_key) {
+            // Lineups only lock once a specific week's games kick off; the current
+            // roster (no week) can change at any time
+            let last_kickoff = match week {
+                Some(w) => get_pro_schedule(season)
+                    .await
+                    .ok()
+                    .and_then(|schedule| schedule.last_kickoff(w)),
+                None => None,
+            };
+            let status = roster_cache_decision(
+                cached.fetched_at,
+                unix_now(),
+                last_kickoff,
+                roster_freshness_secs(),
+            );
+            if !matches!(status, CacheStatus::Stale { .. }) {
+                return Ok((cached.value, status));
+            }
+            cache_status = status;
+        }
+    }
+
     let url = format!(
-        "{FFL_BASE_URL}/seasons/{}/segments/0/leagues/{}",
+        "{}/seasons/{}/segments/0/leagues/{}",
+        ffl_base_url(),
         season.as_u16(),
         league_id.as_u32()
     );
@@ -278,15 +475,12 @@ pub async fn get_league_rosters_with_cache_status(
         eprintln!("Params => {:?}", params);
     }
 
-    let res = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
-        .await?;
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "mRoster",
+        debug,
+    )
+    .await?;
 
     if debug {
         eprintln!("RAW ROSTER API RESPONSE:");
@@ -297,9 +491,21 @@ pub async fn get_league_rosters_with_cache_status(
         );
     }
 
-    // Cache the result (but not in debug mode)
+    // Cache the result (but not in debug mode); a week's rosters are final once
+    // fetched after its last kickoff
     if !debug {
-        GLOBAL_CACHE.roster_data.put(cache_key, res.clone());
+        let locked = match week {
+            Some(w) => get_pro_schedule(season)
+                .await
+                .ok()
+                .and_then(|schedule| schedule.last_kickoff(w))
+                .is_some_and(|kickoff| unix_now() >= kickoff),
+            None => false,
+        };
+        let ttl = (!locked).then(|| ROSTER_DATA_TTL_SECS.max(roster_freshness_secs()));
+        GLOBAL_CACHE
+            .roster_data
+            .put_with_ttl(cache_key, res.clone(), ttl);
     }
 
     Ok((res, cache_status))
@@ -325,7 +531,7 @@ pub async fn get_player_info(
     season: Season,
     week: Week,
 ) -> Result<Value> {
-    let url = format!("{FFL_BASE_URL}/seasons/{}/players", season.as_u16());
+    let url = format!("{}/seasons/{}/players", ffl_base_url(), season.as_u16());
     let params = [
         ("forLeagueId", league_id.to_string()),
         ("view", "players_wl".to_string()), // "wl" often means "with lineup" or detailed info
@@ -339,15 +545,12 @@ pub async fn get_player_info(
         eprintln!("Params => {:?}", params);
     }
 
-    let res = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
-        .await?;
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "players_wl",
+        debug,
+    )
+    .await?;
 
     Ok(res)
 }
@@ -360,7 +563,7 @@ pub async fn get_player_data_with_view(
     week: Week,
     view: &str,
 ) -> Result<Value> {
-    let url = format!("{FFL_BASE_URL}/seasons/{}/players", season.as_u16());
+    let url = format!("{}/seasons/{}/players", ffl_base_url(), season.as_u16());
     let params = [
         ("forLeagueId", league_id.to_string()),
         ("view", view.to_string()),
@@ -374,27 +577,113 @@ pub async fn get_player_data_with_view(
         eprintln!("Params => {:?}", params);
     }
 
-    let res = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
-        .await?;
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "players (other view)",
+        debug,
+    )
+    .await?;
 
     Ok(res)
 }
 
 /// Cache status for roster data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CacheStatus {
-    Hit,
+    /// Cached roster fetched after the week's last kickoff; lineups can no longer change
+    Locked {
+        fetched_at: u64,
+    },
+    /// Cached roster fetched within the freshness window
+    Fresh {
+        fetched_at: u64,
+    },
+    /// Cached roster was older than the freshness window and has been refetched
+    Stale {
+        fetched_at: u64,
+    },
     Miss,
     Refreshed,
 }
 
+impl CacheStatus {
+    /// Status line describing where the roster data came from
+    pub fn message(&self, week: Option<Week>) -> String {
+        let subject = match week {
+            Some(w) => format!("Week {} roster", w.as_u16()),
+            None => "Current roster".to_string(),
+        };
+        match self {
+            CacheStatus::Locked { fetched_at } => format!(
+                "{} snapshot from {}, lineups locked — using cache",
+                subject,
+                format_utc_timestamp(*fetched_at)
+            ),
+            CacheStatus::Fresh { fetched_at } => format!(
+                "{} snapshot from {} is fresh — using cache",
+                subject,
+                format_utc_timestamp(*fetched_at)
+            ),
+            CacheStatus::Stale { fetched_at } => format!(
+                "{} snapshot from {} was stale — refetched",
+                subject,
+                format_utc_timestamp(*fetched_at)
+            ),
+            CacheStatus::Miss => format!("{} status fetched (cache miss)", subject),
+            CacheStatus::Refreshed => format!("{} status fetched (refreshed)", subject),
+        }
+    }
+}
+
+/// Decide whether a cached roster can still be used
+///
+/// A roster fetched after the week's last kickoff is final. Otherwise it is trusted
+/// for `freshness_secs` after it was fetched. Transactions aren't tracked, so use
+/// `--refresh` to pick up a trade or waiver claim inside the window.
+pub fn roster_cache_decision(
+    fetched_at: u64,
+    now: u64,
+    last_kickoff: Option<u64>,
+    freshness_secs: u64,
+) -> CacheStatus {
+    if last_kickoff.is_some_and(|kickoff| fetched_at >= kickoff) {
+        CacheStatus::Locked { fetched_at }
+    } else if is_fresh(now.saturating_sub(fetched_at), freshness_secs) {
+        CacheStatus::Fresh { fetched_at }
+    } else {
+        CacheStatus::Stale { fetched_at }
+    }
+}
+
+/// Roster freshness window in seconds from `ESPN_FFL_ROSTER_FRESHNESS_MINUTES`
+pub fn roster_freshness_secs() -> u64 {
+    std::env::var(ROSTER_FRESHNESS_ENV_VAR)
+        .ok()
+        .and_then(|minutes| minutes.trim().parse::<u64>().ok())
+        .unwrap_or(DEFAULT_ROSTER_FRESHNESS_MINUTES)
+        * 60
+}
+
+/// Format a Unix timestamp as e.g. "Sun 17:58 UTC"
+pub fn format_utc_timestamp(secs: u64) -> String {
+    const WEEKDAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    // 1970-01-01 was a Thursday
+    let weekday = WEEKDAYS[((secs / 86_400 + 4) % 7) as usize];
+    let seconds_of_day = secs % 86_400;
+    format!(
+        "{} {:02}:{:02} UTC",
+        weekday,
+        seconds_of_day / 3600,
+        (seconds_of_day % 3600) / 60
+    )
+}
+
+/// Format a Unix timestamp as a UTC calendar date, e.g. "2025-10-12"
+pub fn format_utc_date(secs: u64) -> String {
+    let (year, month, day) = utc_date(secs);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
 /// Get league roster data and return team information with rosters
 pub async fn get_league_roster_data(
     debug: bool,
@@ -409,6 +698,111 @@ pub async fn get_league_roster_data(
     Ok((league_data, cache_status))
 }
 
+/// Get game-level metadata (the latest available season) from the base FFL endpoint
+pub async fn get_game_metadata() -> Result<crate::espn::types::GameMetadata> {
+    let headers = build_espn_headers()?;
+
+    // tarpaulin::skip - HTTP client call
+    let res: crate::espn::types::GameMetadata =
+        send_json(CLIENT.get(ffl_base_url()).headers(headers), "game", false).await?;
+
+    Ok(res)
+}
+
+/// Get the league status (current week, previous seasons) via the mStatus view
+pub async fn get_league_status(
+    league_id: LeagueId,
+    season: Season,
+) -> Result<crate::espn::types::LeagueStatusEnvelope> {
+    let url = format!(
+        "{}/seasons/{}/segments/0/leagues/{}",
+        ffl_base_url(),
+        season.as_u16(),
+        league_id.as_u32()
+    );
+    let params = [("view", "mStatus")];
+    let headers = build_espn_headers()?;
+
+    // tarpaulin::skip - HTTP client call
+    let res: crate::espn::types::LeagueStatusEnvelope = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "mStatus",
+        false,
+    )
+    .await?;
+
+    Ok(res)
+}
+
+/// Get the league schedule with matchup scores for the whole season
+///
+/// Uses the mMatchupScore view (plus mTeam for team names). Cached per league and
+/// season; pass `refresh` to pick up newly completed matchups.
+pub async fn get_league_schedule(
+    league_id: LeagueId,
+    season: Season,
+    refresh: bool,
+) -> Result<crate::espn::types::LeagueSchedule> {
+    let cache_key = ScheduleCacheKey { league_id, season };
+
+    if !refresh {
+        if let Some(cached_result) = GLOBAL_CACHE.schedule_data.get(&cache_key) {
+            return Ok(serde_json::from_value(cached_result)?);
+        }
+    }
+
+    let url = format!(
+        "{}/seasons/{}/segments/0/leagues/{}",
+        ffl_base_url(),
+        season.as_u16(),
+        league_id.as_u32()
+    );
+    let params = [("view", "mMatchupScore"), ("view", "mTeam")];
+    let headers = build_espn_headers()?;
+
+    // tarpaulin::skip - HTTP client call
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "mMatchupScore",
+        false,
+    )
+    .await?;
+
+    let schedule = serde_json::from_value(res.clone())?;
+    GLOBAL_CACHE.schedule_data.put(cache_key, res);
+
+    Ok(schedule)
+}
+
+/// Get the NFL schedule for a season (kickoff times of every pro game)
+///
+/// Uses the proTeamSchedules_wl view, cached per season. Kickoff times rarely
+/// change, so the cache is only bypassed by clearing it.
+pub async fn get_pro_schedule(season: Season) -> Result<crate::espn::types::ProSchedule> {
+    let cache_key = ProScheduleCacheKey { season };
+
+    if let Some(cached_result) = GLOBAL_CACHE.pro_schedule.get(&cache_key) {
+        return Ok(serde_json::from_value(cached_result)?);
+    }
+
+    let url = format!("{}/seasons/{}", ffl_base_url(), season.as_u16());
+    let params = [("view", "proTeamSchedules_wl")];
+    let headers = build_espn_headers()?;
+
+    // tarpaulin::skip - HTTP client call
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "proTeamSchedules",
+        false,
+    )
+    .await?;
+
+    let schedule = serde_json::from_value(res.clone())?;
+    GLOBAL_CACHE.pro_schedule.put(cache_key, res);
+
+    Ok(schedule)
+}
+
 /// Fetch roster data and update PlayerPoints with roster information
 /// Fetch current league roster data once for efficient reuse
 ///
@@ -455,6 +849,7 @@ pub fn update_player_points_with_roster_data(
     }
 
     if let Some(league_data) = roster_data {
+        warn_nameless_teams(league_data);
         league_data.update_player_points_with_roster(player_points);
         if verbose {
             println!("✓ Roster status updated");
@@ -470,6 +865,19 @@ pub fn update_player_points_with_roster_data(
     }
 }
 
+/// Warn (once per run) about rostered teams ESPN sent without a name
+fn warn_nameless_teams(league_data: &crate::espn::types::LeagueData) {
+    let team_ids = league_data.nameless_team_ids();
+    if team_ids.is_empty() || NAMELESS_TEAMS_WARNED.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    let team_ids: Vec<String> = team_ids.iter().map(u32::to_string).collect();
+    eprintln!(
+        "⚠ Warning: ESPN sent no name for team(s) {}; showing them as \"Team <id>\" (filter them with --team-id)",
+        team_ids.join(", ")
+    );
+}
+
 /// Legacy function - kept for backward compatibility
 ///
 /// This function is less efficient as it makes a separate API call.
@@ -498,7 +906,7 @@ pub async fn get_player_data_with_custom_filter(
     week: Week,
     custom_filter_json: &str,
 ) -> Result<Value> {
-    let url = format!("{FFL_BASE_URL}/seasons/{}/players", season.as_u16());
+    let url = format!("{}/seasons/{}/players", ffl_base_url(), season.as_u16());
     let params = [
         ("forLeagueId", league_id.to_string()),
         ("view", "kona_player_info".to_string()),
@@ -517,15 +925,12 @@ pub async fn get_player_data_with_custom_filter(
         eprintln!("Custom filter => {}", custom_filter_json);
     }
 
-    let res = CLIENT
-        .get(&url)
-        .headers(headers)
-        .query(&params)
-        .send()
-        .await?
-        .error_for_status()?
-        .json::<Value>()
-        .await?;
+    let res: Value = send_json(
+        CLIENT.get(&url).headers(headers).query(&params),
+        "players (custom filter)",
+        debug,
+    )
+    .await?;
 
     Ok(res)
 }
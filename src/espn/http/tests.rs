@@ -14,6 +14,18 @@ mod http_tests {
         );
     }
 
+    #[test]
+    fn test_ffl_base_url_override() {
+        std::env::set_var(BASE_URL_ENV_VAR, "http://localhost:8080/ffl/");
+        assert_eq!(ffl_base_url(), "http://localhost:8080/ffl");
+
+        std::env::set_var(BASE_URL_ENV_VAR, "");
+        assert_eq!(ffl_base_url(), FFL_BASE_URL);
+
+        std::env::remove_var(BASE_URL_ENV_VAR);
+        assert_eq!(ffl_base_url(), FFL_BASE_URL);
+    }
+
     #[test]
     fn test_client_initialization() {
         // Test that the static CLIENT is properly initialized
@@ -22,4 +34,109 @@ mod http_tests {
         // We can't directly test the user agent, but we can verify the client exists
         assert!(std::ptr::addr_of!(*client) as usize != 0);
     }
+
+    #[test]
+    fn test_resolve_user_agent() {
+        let version = env!("CARGO_PKG_VERSION");
+        assert_eq!(
+            resolve_user_agent(None),
+            format!("espn-ffl-cli/{}", version)
+        );
+        assert_eq!(resolve_user_agent(Some("  ")), resolve_user_agent(None));
+        assert_eq!(
+            resolve_user_agent(Some("probe/{version} (throttle test)")),
+            format!("probe/{} (throttle test)", version)
+        );
+        // No placeholder, no version
+        assert_eq!(resolve_user_agent(Some("probe")), "probe");
+    }
+
+    #[test]
+    fn test_new_request_id_is_a_v4_uuid() {
+        let id = new_request_id();
+        assert_eq!(id.len(), 36);
+        let groups: Vec<&str> = id.split('-').collect();
+        assert_eq!(
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+            vec![8, 4, 4, 4, 12]
+        );
+        assert!(id.chars().all(|c| c == '-' || c.is_ascii_hexdigit()));
+        assert!(groups[2].starts_with('4'));
+        assert!(matches!(&groups[3][..1], "8" | "9" | "a" | "b"));
+        assert_ne!(id, new_request_id());
+    }
+
+    #[test]
+    fn test_roster_cache_decision() {
+        let kickoff = 1_000_000;
+        let window = 3600;
+
+        // Fetched after the last kickoff: final regardless of age
+        assert_eq!(
+            roster_cache_decision(kickoff + 10, kickoff + 100_000, Some(kickoff), window),
+            CacheStatus::Locked {
+                fetched_at: kickoff + 10
+            }
+        );
+
+        // Before kickoff (or unknown schedule): trusted within the window only
+        assert_eq!(
+            roster_cache_decision(kickoff - 600, kickoff - 60, Some(kickoff), window),
+            CacheStatus::Fresh {
+                fetched_at: kickoff - 600
+            }
+        );
+        assert_eq!(
+            roster_cache_decision(500, 500 + window, None, window),
+            CacheStatus::Fresh { fetched_at: 500 }
+        );
+        assert_eq!(
+            roster_cache_decision(500, 501 + window, None, window),
+            CacheStatus::Stale { fetched_at: 500 }
+        );
+    }
+
+    #[test]
+    fn test_format_utc_date() {
+        assert_eq!(format_utc_date(0), "1970-01-01");
+        // 2024-02-29 12:00 UTC (leap day)
+        assert_eq!(format_utc_date(1_709_208_000), "2024-02-29");
+        // 2025-12-31 23:59:59 UTC
+        assert_eq!(format_utc_date(1_767_225_599), "2025-12-31");
+    }
+
+    #[test]
+    fn test_roster_freshness_secs_env() {
+        std::env::set_var(ROSTER_FRESHNESS_ENV_VAR, "15");
+        assert_eq!(roster_freshness_secs(), 900);
+
+        std::env::set_var(ROSTER_FRESHNESS_ENV_VAR, "soon");
+        assert_eq!(
+            roster_freshness_secs(),
+            DEFAULT_ROSTER_FRESHNESS_MINUTES * 60
+        );
+
+        std::env::remove_var(ROSTER_FRESHNESS_ENV_VAR);
+        assert_eq!(
+            roster_freshness_secs(),
+            DEFAULT_ROSTER_FRESHNESS_MINUTES * 60
+        );
+    }
+
+    #[test]
+    fn test_cache_status_message() {
+        // 2025-09-14 16:58 UTC was a Sunday
+        let fetched_at = 1_757_869_080;
+        assert_eq!(format_utc_timestamp(fetched_at), "Sun 16:58 UTC");
+        assert_eq!(format_utc_timestamp(0), "Thu 00:00 UTC");
+
+        assert_eq!(
+            CacheStatus::Locked { fetched_at }.message(Some(Week::new(2))),
+            "Week 2 roster snapshot from Sun 16:58 UTC, lineups locked — using cache"
+        );
+        assert_eq!(
+            CacheStatus::Miss.message(None),
+            "Current roster status fetched (cache miss)"
+        );
+    }
 }
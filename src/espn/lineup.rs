@@ -0,0 +1,260 @@
+//! Lineup slot eligibility, optimal lineup selection and projection ranges.
+//!
+//! Projections are treated as independent normal distributions: a player's
+//! standard deviation comes from the spread of their past projection errors,
+//! or a fixed fraction of the projection when there isn't enough history.
+//! Team totals add means and variances, which ignores correlation between
+//! teammates (e.g. a QB and his receivers), so ranges are somewhat too narrow.
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::PlayerId;
+
+/// Z-score for the 10th/90th percentile used for floor and ceiling
+pub const FLOOR_CEILING_Z: f64 = 1.2816;
+
+/// Standard deviation as a share of the projection when a player has no error history
+pub const DEFAULT_STD_FRACTION: f64 = 0.4;
+
+/// Bench and injured reserve lineup slots
+const BENCH_SLOT: u8 = 20;
+const IR_SLOT: u8 = 21;
+
+/// Positions that can fill a starting lineup slot, or `None` for non-starting slots
+pub fn slot_eligible_positions(slot_id: u8) -> Option<&'static [&'static str]> {
+    let positions: &[&str] = match slot_id {
+        0 | 1 => &["QB"],
+        2 => &["RB"],
+        3 => &["RB", "WR"],
+        4 => &["WR"],
+        5 => &["WR", "TE"],
+        6 => &["TE"],
+        7 => &["QB", "RB", "WR", "TE"],
+        16 => &["D/ST"],
+        17 => &["K"],
+        23 => &["RB", "WR", "TE"],
+        _ => return None,
+    };
+    Some(positions)
+}
+
+/// Display label for a lineup slot
+pub fn slot_label(slot_id: u8) -> String {
+    match slot_id {
+        0 | 1 => "QB".to_string(),
+        2 => "RB".to_string(),
+        3 => "RB/WR".to_string(),
+        4 => "WR".to_string(),
+        5 => "WR/TE".to_string(),
+        6 => "TE".to_string(),
+        7 => "OP".to_string(),
+        16 => "D/ST".to_string(),
+        17 => "K".to_string(),
+        BENCH_SLOT => "BE".to_string(),
+        IR_SLOT => "IR".to_string(),
+        23 => "FLEX".to_string(),
+        other => format!("Slot {}", other),
+    }
+}
+
+/// A rostered player considered for a starting spot
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LineupCandidate {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    pub projection: f64,
+    pub std_dev: f64,
+    /// False for players on bye, ruled out or on IR; they never start
+    pub available: bool,
+    /// Lineup slot the player occupies on the roster right now
+    #[serde(skip)]
+    pub roster_slot_id: Option<u8>,
+    /// Their game has kicked off, so they can't leave `roster_slot_id`
+    pub locked: bool,
+}
+
+impl LineupCandidate {
+    /// 10th percentile outcome, never below zero
+    pub fn floor(&self) -> f64 {
+        (self.projection - FLOOR_CEILING_Z * self.std_dev).max(0.0)
+    }
+
+    /// 90th percentile outcome
+    pub fn ceiling(&self) -> f64 {
+        self.projection + FLOOR_CEILING_Z * self.std_dev
+    }
+}
+
+/// One starting slot and the player chosen for it
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct LineupSlot {
+    pub slot_id: u8,
+    pub slot: String,
+    /// `None` when no available player can fill the slot
+    pub player: Option<LineupCandidate>,
+}
+
+/// Best available starting lineup for a roster
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct OptimalLineup {
+    pub starters: Vec<LineupSlot>,
+    pub bench: Vec<LineupCandidate>,
+}
+
+impl OptimalLineup {
+    /// Starting slots left empty
+    pub fn holes(&self) -> impl Iterator<Item = &LineupSlot> {
+        self.starters.iter().filter(|s| s.player.is_none())
+    }
+
+    /// Sum of starter projections
+    pub fn projected_points(&self) -> f64 {
+        self.starters
+            .iter()
+            .filter_map(|s| s.player.as_ref())
+            .map(|p| p.projection)
+            .sum()
+    }
+
+    /// Variance of the lineup total, assuming independent starters
+    pub fn variance(&self) -> f64 {
+        self.starters
+            .iter()
+            .filter_map(|s| s.player.as_ref())
+            .map(|p| p.std_dev.powi(2))
+            .sum()
+    }
+
+    /// 10th percentile of the lineup total
+    pub fn floor(&self) -> f64 {
+        (self.projected_points() - FLOOR_CEILING_Z * self.variance().sqrt()).max(0.0)
+    }
+
+    /// 90th percentile of the lineup total
+    pub fn ceiling(&self) -> f64 {
+        self.projected_points() + FLOOR_CEILING_Z * self.variance().sqrt()
+    }
+}
+
+/// Pick the highest-projected starting lineup from a roster
+///
+/// `lineup_slot_counts` is the league's `lineupSlotCounts` (slot ID string to count).
+/// Slots are filled from the most restrictive (single position) to the most flexible,
+/// each taking the best remaining eligible player; this is optimal for ESPN's standard
+/// layouts where flex slots are supersets of the dedicated ones.
+///
+/// Locked players stay where they are: one in a starting slot keeps it whatever their
+/// projection, and one on the bench (or IR) can't start.
+pub fn optimal_lineup(
+    candidates: Vec<LineupCandidate>,
+    lineup_slot_counts: &HashMap<String, u32>,
+) -> OptimalLineup {
+    let mut slots: Vec<(u8, &'static [&'static str])> = lineup_slot_counts
+        .iter()
+        .filter_map(|(slot_id, count)| {
+            let slot_id = slot_id.parse::<u8>().ok()?;
+            let positions = slot_eligible_positions(slot_id)?;
+            Some(std::iter::repeat_n((slot_id, positions), *count as usize))
+        })
+        .flatten()
+        .collect();
+    slots.sort_by_key(|(slot_id, positions)| (positions.len(), *slot_id));
+
+    let (locked, mut pool): (Vec<_>, Vec<_>) = candidates.into_iter().partition(|c| c.locked);
+    pool.sort_by(|a, b| {
+        b.projection
+            .partial_cmp(&a.projection)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let mut starters = Vec::with_capacity(slots.len());
+    let mut locked_bench = Vec::new();
+    for candidate in locked {
+        let slot_index = candidate.roster_slot_id.and_then(|roster_slot| {
+            slots
+                .iter()
+                .position(|(slot_id, _)| *slot_id == roster_slot)
+        });
+        match slot_index {
+            Some(index) => {
+                let (slot_id, _) = slots.remove(index);
+                starters.push(LineupSlot {
+                    slot_id,
+                    slot: slot_label(slot_id),
+                    player: Some(candidate),
+                });
+            }
+            None => locked_bench.push(candidate),
+        }
+    }
+
+    for (slot_id, positions) in slots {
+        let pick = pool
+            .iter()
+            .position(|c| c.available && positions.contains(&c.position.as_str()))
+            .map(|index| pool.remove(index));
+        starters.push(LineupSlot {
+            slot_id,
+            slot: slot_label(slot_id),
+            player: pick,
+        });
+    }
+
+    // Present starters in the usual ESPN order
+    starters.sort_by_key(|s| display_order(s.slot_id));
+
+    pool.extend(locked_bench);
+    OptimalLineup {
+        starters,
+        bench: pool,
+    }
+}
+
+fn display_order(slot_id: u8) -> u8 {
+    match slot_id {
+        0 | 1 => 0,
+        2 => 1,
+        4 => 2,
+        6 => 3,
+        3 | 5 | 23 => 4,
+        7 => 5,
+        16 => 6,
+        17 => 7,
+        _ => 8,
+    }
+}
+
+/// Standard normal cumulative distribution function
+///
+/// Uses the Abramowitz & Stegun 7.1.26 approximation of `erf` (error below 1.5e-7).
+pub fn normal_cdf(x: f64) -> f64 {
+    let z = x / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z.abs());
+    let poly = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    let erf = if z >= 0.0 { erf } else { -erf };
+    0.5 * (1.0 + erf)
+}
+
+/// Probability that a lineup with `margin` more projected points wins
+///
+/// Treats the score difference as normal with the combined variance of both lineups.
+pub fn win_probability(margin: f64, combined_variance: f64) -> f64 {
+    if combined_variance <= 0.0 {
+        return match margin.partial_cmp(&0.0) {
+            Some(std::cmp::Ordering::Greater) => 1.0,
+            Some(std::cmp::Ordering::Less) => 0.0,
+            _ => 0.5,
+        };
+    }
+    normal_cdf(margin / combined_variance.sqrt())
+}
+
+/// Whether a lineup slot ID is the injured reserve slot
+pub fn is_ir_slot(slot_id: u8) -> bool {
+    slot_id == IR_SLOT
+}
@@ -1,4 +1,10 @@
 pub mod cache_settings;
 pub mod compute;
+pub mod credentials;
 pub mod http;
+pub mod lineup;
+pub mod playoffs;
+pub mod scoring_overrides;
+pub mod stat_categories;
+pub mod teams;
 pub mod types;
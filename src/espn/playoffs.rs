@@ -0,0 +1,306 @@
+//! Playoff odds by Monte Carlo simulation of the remaining regular season.
+//!
+//! Each team's strength is the mean and standard deviation of its completed
+//! weekly scores. Every simulated week draws a normal score for both sides of
+//! each remaining matchup, the higher score wins, and the final standings are
+//! seeded by win percentage with points for as the tiebreaker. Weekly scores are
+//! treated as independent, so streaks and roster changes are not modelled.
+
+use rand::Rng;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::espn::types::LeagueSchedule;
+
+/// Playoff spots used when the league settings don't say
+pub const DEFAULT_PLAYOFF_TEAMS: usize = 4;
+
+/// A team's weekly scoring distribution
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TeamStrength {
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Completed weeks behind the estimate
+    pub games: usize,
+}
+
+/// Current regular-season record
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TeamRecord {
+    pub team_id: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub points_for: f64,
+}
+
+impl TeamRecord {
+    fn new(team_id: u32) -> Self {
+        Self {
+            team_id,
+            wins: 0,
+            losses: 0,
+            ties: 0,
+            points_for: 0.0,
+        }
+    }
+
+    /// Wins with ties counted as half
+    pub fn win_credit(&self) -> f64 {
+        self.wins as f64 + self.ties as f64 / 2.0
+    }
+}
+
+/// An undecided regular-season matchup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct RemainingGame {
+    pub week: u16,
+    pub home: u32,
+    pub away: u32,
+}
+
+/// Everything the simulation needs, independent of where it came from
+#[derive(Debug, Clone)]
+pub struct PlayoffSimInput {
+    pub records: Vec<TeamRecord>,
+    pub strengths: HashMap<u32, TeamStrength>,
+    pub remaining: Vec<RemainingGame>,
+    pub playoff_teams: usize,
+}
+
+impl PlayoffSimInput {
+    /// Build standings, strengths and the remaining games from a league schedule
+    ///
+    /// Matchups after `regular_season_weeks` are playoff games and are ignored; with
+    /// `None` every scheduled matchup counts as regular season.
+    pub fn from_schedule(
+        schedule: &LeagueSchedule,
+        regular_season_weeks: Option<u16>,
+        playoff_teams: usize,
+    ) -> Self {
+        let mut records: HashMap<u32, TeamRecord> = schedule
+            .teams
+            .iter()
+            .map(|team| (team.id, TeamRecord::new(team.id)))
+            .collect();
+        let mut scores: HashMap<u32, Vec<f64>> = HashMap::new();
+        let mut remaining = Vec::new();
+
+        let regular_season = schedule
+            .schedule
+            .iter()
+            .filter(|m| regular_season_weeks.is_none_or(|weeks| m.matchup_period_id <= weeks));
+        for matchup in regular_season {
+            let (Some(home), Some(away)) = (&matchup.home, &matchup.away) else {
+                continue;
+            };
+            if !matchup.is_complete() {
+                remaining.push(RemainingGame {
+                    week: matchup.matchup_period_id,
+                    home: home.team_id,
+                    away: away.team_id,
+                });
+                continue;
+            }
+
+            for (side, result) in [(home, "HOME"), (away, "AWAY")] {
+                let record = records
+                    .entry(side.team_id)
+                    .or_insert_with(|| TeamRecord::new(side.team_id));
+                match matchup.winner.as_str() {
+                    "TIE" => record.ties += 1,
+                    winner if winner == result => record.wins += 1,
+                    _ => record.losses += 1,
+                }
+                if let Some(points) = side.total_points {
+                    record.points_for += points;
+                    scores.entry(side.team_id).or_default().push(points);
+                }
+            }
+        }
+
+        let mut records: Vec<TeamRecord> = records.into_values().collect();
+        records.sort_by_key(|r| r.team_id);
+        let team_ids: Vec<u32> = records.iter().map(|r| r.team_id).collect();
+
+        Self {
+            strengths: team_strengths(&team_ids, &scores),
+            records,
+            remaining,
+            playoff_teams,
+        }
+    }
+}
+
+/// Estimate each team's weekly scoring distribution from its completed weeks
+///
+/// A team with fewer than two scores takes the league-wide spread, and one with
+/// none also takes the league-wide mean. With no completed weeks at all every
+/// team is identical, so each remaining game is a coin flip.
+pub fn team_strengths(
+    team_ids: &[u32],
+    scores: &HashMap<u32, Vec<f64>>,
+) -> HashMap<u32, TeamStrength> {
+    let all_scores: Vec<f64> = scores.values().flatten().copied().collect();
+    let (league_mean, league_std) = mean_and_std(&all_scores).unwrap_or((0.0, 1.0));
+
+    team_ids
+        .iter()
+        .map(|&team_id| {
+            let team_scores = scores.get(&team_id).map(Vec::as_slice).unwrap_or(&[]);
+            let strength = match mean_and_std(team_scores) {
+                Some((mean, std_dev)) if team_scores.len() >= 2 => TeamStrength {
+                    mean,
+                    std_dev,
+                    games: team_scores.len(),
+                },
+                Some((mean, _)) => TeamStrength {
+                    mean,
+                    std_dev: league_std,
+                    games: team_scores.len(),
+                },
+                None => TeamStrength {
+                    mean: league_mean,
+                    std_dev: league_std,
+                    games: 0,
+                },
+            };
+            (team_id, strength)
+        })
+        .collect()
+}
+
+/// Sample mean and standard deviation; the deviation is 0 for a single value
+fn mean_and_std(values: &[f64]) -> Option<(f64, f64)> {
+    if values.is_empty() {
+        return None;
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return Some((mean, 0.0));
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    Some((mean, variance.sqrt()))
+}
+
+/// Simulated playoff chances for one team
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayoffOdds {
+    pub team_id: u32,
+    pub record: TeamRecord,
+    pub strength: TeamStrength,
+    /// Average regular-season wins (ties count half) across simulations
+    pub projected_wins: f64,
+    pub playoff_probability: f64,
+    /// Chance of finishing with each seed; index 0 is the first seed
+    pub seed_probabilities: Vec<f64>,
+}
+
+/// Run `iterations` simulations of the remaining schedule
+///
+/// Results are ordered by playoff probability, then by current standing. The
+/// outcome depends only on the inputs and the RNG, so a seeded RNG reproduces it.
+pub fn simulate_playoff_odds<R: Rng>(
+    input: &PlayoffSimInput,
+    iterations: u32,
+    rng: &mut R,
+) -> Vec<PlayoffOdds> {
+    let team_count = input.records.len();
+    let playoff_teams = input.playoff_teams.min(team_count);
+    let index: HashMap<u32, usize> = input
+        .records
+        .iter()
+        .enumerate()
+        .map(|(i, r)| (r.team_id, i))
+        .collect();
+    let strength = |team_id: u32| {
+        input
+            .strengths
+            .get(&team_id)
+            .copied()
+            .unwrap_or(TeamStrength {
+                mean: 0.0,
+                std_dev: 1.0,
+                games: 0,
+            })
+    };
+
+    let mut seed_counts = vec![vec![0u32; team_count]; team_count];
+    let mut total_wins = vec![0.0; team_count];
+
+    for _ in 0..iterations {
+        let mut wins: Vec<f64> = input.records.iter().map(TeamRecord::win_credit).collect();
+        let mut points: Vec<f64> = input.records.iter().map(|r| r.points_for).collect();
+
+        for game in &input.remaining {
+            let (Some(&home), Some(&away)) = (index.get(&game.home), index.get(&game.away)) else {
+                continue;
+            };
+            let home_score = sample_score(strength(game.home), rng);
+            let away_score = sample_score(strength(game.away), rng);
+            match home_score.partial_cmp(&away_score) {
+                Some(std::cmp::Ordering::Greater) => wins[home] += 1.0,
+                Some(std::cmp::Ordering::Less) => wins[away] += 1.0,
+                _ => {
+                    wins[home] += 0.5;
+                    wins[away] += 0.5;
+                }
+            }
+            points[home] += home_score;
+            points[away] += away_score;
+        }
+
+        let mut order: Vec<usize> = (0..team_count).collect();
+        order.sort_by(|&a, &b| {
+            wins[b]
+                .total_cmp(&wins[a])
+                .then(points[b].total_cmp(&points[a]))
+                .then(input.records[a].team_id.cmp(&input.records[b].team_id))
+        });
+        for (seed, &team) in order.iter().enumerate() {
+            seed_counts[team][seed] += 1;
+        }
+        for (total, team_wins) in total_wins.iter_mut().zip(&wins) {
+            *total += team_wins;
+        }
+    }
+
+    let runs = iterations.max(1) as f64;
+    let mut odds: Vec<PlayoffOdds> = input
+        .records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            let seed_probabilities: Vec<f64> = seed_counts[i][..playoff_teams]
+                .iter()
+                .map(|&count| count as f64 / runs)
+                .collect();
+            PlayoffOdds {
+                team_id: record.team_id,
+                record: record.clone(),
+                strength: strength(record.team_id),
+                projected_wins: total_wins[i] / runs,
+                playoff_probability: seed_probabilities.iter().sum(),
+                seed_probabilities,
+            }
+        })
+        .collect();
+
+    odds.sort_by(|a, b| {
+        b.playoff_probability
+            .total_cmp(&a.playoff_probability)
+            .then(b.record.win_credit().total_cmp(&a.record.win_credit()))
+            .then(b.record.points_for.total_cmp(&a.record.points_for))
+            .then(a.team_id.cmp(&b.team_id))
+    });
+    odds
+}
+
+/// Draw a weekly score from a team's normal distribution (Box-Muller), floored at 0
+fn sample_score<R: Rng>(strength: TeamStrength, rng: &mut R) -> f64 {
+    let u1: f64 = 1.0 - rng.random::<f64>();
+    let u2: f64 = rng.random();
+    let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+    (strength.mean + z * strength.std_dev).max(0.0)
+}
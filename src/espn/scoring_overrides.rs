@@ -0,0 +1,238 @@
+//! Local scoring adjustments applied on top of league scoring
+//!
+//! Some leagues score things ESPN doesn't model (divisional bonuses, a TE
+//! premium run outside ESPN). An optional JSON file lists per-unit adjustments
+//! keyed by stat ID and/or position; they are folded into the scoring index
+//! after the league settings, so every command that scores stats sees them.
+//!
+//! ```json
+//! {
+//!   "adjustments": [
+//!     { "stat_id": 53, "position": "TE", "add": 0.5, "note": "TE premium" },
+//!     { "stat_id": 4, "multiply": 1.5 }
+//!   ]
+//! }
+//! ```
+
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    cli::types::position::Position,
+    error::EspnError,
+    espn::{
+        compute::{scoring_slot_for_position, PointsContribution},
+        stat_categories::stat_label,
+    },
+    Result,
+};
+
+/// Environment variable pointing at an overrides file outside the data directory
+pub const SCORING_OVERRIDES_ENV_VAR: &str = "ESPN_FFL_SCORING_OVERRIDES";
+
+/// File name looked up in the data directory when the variable isn't set
+pub const SCORING_OVERRIDES_FILE: &str = "scoring_overrides.json";
+
+/// Set by `--no-overrides`
+static OVERRIDES_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Score with league settings only, ignoring any overrides file
+pub fn disable_scoring_overrides() {
+    OVERRIDES_DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Where the overrides file is read from
+pub fn scoring_overrides_path() -> PathBuf {
+    if let Ok(path) = std::env::var(SCORING_OVERRIDES_ENV_VAR) {
+        if !path.trim().is_empty() {
+            return PathBuf::from(path);
+        }
+    }
+    let base = dirs::cache_dir().unwrap_or_else(|| PathBuf::from("."));
+    base.join("espn-ffl").join(SCORING_OVERRIDES_FILE)
+}
+
+fn one() -> f64 {
+    1.0
+}
+
+/// One adjustment to the points per unit of a stat
+///
+/// The adjusted value is `points_per_unit * multiply + add`. Without a stat ID it
+/// applies to every stat the league scores; without a position, to every position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoringAdjustment {
+    pub stat_id: Option<u16>,
+    pub position: Option<Position>,
+    pub add: f64,
+    pub multiply: f64,
+    /// Why the adjustment exists, shown by `--explain`
+    pub note: Option<String>,
+}
+
+impl ScoringAdjustment {
+    fn matches(&self, stat_id: u16, slot: u8) -> bool {
+        self.stat_id.is_none_or(|id| id == stat_id)
+            && self
+                .position
+                .is_none_or(|position| scoring_slot_for_position(position.to_u8()) == slot)
+    }
+
+    /// Short description, e.g. "×1.5 +0.5 (TE premium)"
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if self.multiply != 1.0 {
+            parts.push(format!("×{}", self.multiply));
+        }
+        if self.add != 0.0 {
+            parts.push(format!("{:+}", self.add));
+        }
+        if let Some(note) = &self.note {
+            parts.push(format!("({})", note));
+        }
+        parts.join(" ")
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ScoringOverridesFile {
+    adjustments: Vec<AdjustmentEntry>,
+}
+
+/// An adjustment as written in the file, before its position is parsed
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AdjustmentEntry {
+    stat_id: Option<u16>,
+    position: Option<String>,
+    #[serde(default)]
+    add: f64,
+    #[serde(default = "one")]
+    multiply: f64,
+    note: Option<String>,
+}
+
+/// Adjustments read from an overrides file
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoringOverrides {
+    pub path: PathBuf,
+    pub adjustments: Vec<ScoringAdjustment>,
+}
+
+impl ScoringOverrides {
+    /// Read the overrides file, if there is one and `--no-overrides` wasn't passed
+    pub fn load() -> Result<Option<Self>> {
+        if OVERRIDES_DISABLED.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+        let path = scoring_overrides_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        Self::from_path(&path).map(Some)
+    }
+
+    /// Parse an overrides file
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let invalid = |message: String| EspnError::InvalidScoringOverrides {
+            path: path.display().to_string(),
+            message,
+        };
+        let contents = std::fs::read_to_string(path)?;
+        let file: ScoringOverridesFile =
+            serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?;
+
+        let adjustments = file
+            .adjustments
+            .into_iter()
+            .map(|entry| {
+                let position = match entry.position.as_deref().map(str::parse::<Position>) {
+                    None => None,
+                    Some(Ok(Position::FLEX | Position::BE | Position::IR)) | Some(Err(_)) => {
+                        return Err(invalid(format!(
+                            "{} is not a player position",
+                            entry.position.unwrap_or_default()
+                        )))
+                    }
+                    Some(Ok(position)) => Some(position),
+                };
+                Ok(ScoringAdjustment {
+                    stat_id: entry.stat_id,
+                    position,
+                    add: entry.add,
+                    multiply: entry.multiply,
+                    note: entry.note,
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            adjustments,
+        })
+    }
+
+    /// Reject adjustments for stats the league doesn't score
+    pub fn validate(&self, scoring_index: &BTreeMap<u16, (f64, BTreeMap<u8, f64>)>) -> Result<()> {
+        for stat_id in self.adjustments.iter().filter_map(|a| a.stat_id) {
+            if !scoring_index.contains_key(&stat_id) {
+                return Err(EspnError::UnknownOverrideStat {
+                    stat_id,
+                    valid: scoring_index
+                        .keys()
+                        .map(|id| format!("{} ({})", id, stat_label(*id)))
+                        .collect(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Fold the adjustments into a league scoring index, in file order
+    pub fn apply(&self, scoring_index: &mut BTreeMap<u16, (f64, BTreeMap<u8, f64>)>) {
+        for adjustment in &self.adjustments {
+            let adjust = |points: f64| points * adjustment.multiply + adjustment.add;
+            for (stat_id, (base, overrides)) in scoring_index.iter_mut() {
+                if adjustment.stat_id.is_some_and(|id| id != *stat_id) {
+                    continue;
+                }
+                match adjustment.position {
+                    Some(position) => {
+                        let slot = scoring_slot_for_position(position.to_u8());
+                        let current = overrides.get(&slot).copied().unwrap_or(*base);
+                        overrides.insert(slot, adjust(current));
+                    }
+                    None => {
+                        *base = adjust(*base);
+                        for points in overrides.values_mut() {
+                            *points = adjust(*points);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Adjustments that changed `stat_id` for a player in `slot`, described
+    pub fn describe(&self, stat_id: u16, slot: u8) -> Option<String> {
+        let applied: Vec<String> = self
+            .adjustments
+            .iter()
+            .filter(|a| a.matches(stat_id, slot))
+            .map(ScoringAdjustment::describe)
+            .collect();
+        (!applied.is_empty()).then(|| applied.join(", "))
+    }
+
+    /// Mark breakdown lines whose points per unit came from an adjustment
+    pub fn annotate(&self, contributions: &mut [PointsContribution], slot: u8) {
+        for contribution in contributions {
+            contribution.adjustment = self.describe(contribution.stat_id, slot);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! Names for ESPN's numeric stat IDs
+//!
+//! ESPN reports raw stats as `{"<stat id>": value}` maps with no labels. This
+//! table names the IDs worth ranking players by; IDs not listed here are still
+//! stored, they just can't be asked for by name.
+
+use crate::error::EspnError;
+
+/// A raw stat category with its ESPN stat ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatCategory {
+    pub stat_id: u16,
+    /// Kebab-case name accepted on the command line
+    pub name: &'static str,
+    /// Column header for console output
+    pub label: &'static str,
+}
+
+const fn category(stat_id: u16, name: &'static str, label: &'static str) -> StatCategory {
+    StatCategory {
+        stat_id,
+        name,
+        label,
+    }
+}
+
+/// Known stat categories, grouped passing, rushing, receiving, misc, kicking, defense
+pub const STAT_CATEGORIES: &[StatCategory] = &[
+    category(0, "passing-attempts", "Pass Att"),
+    category(1, "passing-completions", "Pass Cmp"),
+    category(3, "passing-yards", "Pass Yds"),
+    category(4, "passing-touchdowns", "Pass TD"),
+    category(19, "passing-2pt-conversions", "Pass 2PT"),
+    category(20, "passing-interceptions", "INT Thrown"),
+    category(64, "times-sacked", "Sacked"),
+    category(23, "rushing-attempts", "Rush Att"),
+    category(24, "rushing-yards", "Rush Yds"),
+    category(25, "rushing-touchdowns", "Rush TD"),
+    category(26, "rushing-2pt-conversions", "Rush 2PT"),
+    category(53, "receptions", "Rec"),
+    category(58, "receiving-targets", "Targets"),
+    category(42, "receiving-yards", "Rec Yds"),
+    category(43, "receiving-touchdowns", "Rec TD"),
+    category(44, "receiving-2pt-conversions", "Rec 2PT"),
+    category(68, "fumbles", "Fumbles"),
+    category(72, "fumbles-lost", "Fum Lost"),
+    category(83, "field-goals-made", "FG"),
+    category(84, "field-goals-attempted", "FG Att"),
+    category(85, "field-goals-missed", "FG Miss"),
+    category(80, "field-goals-made-under-40", "FG <40"),
+    category(81, "field-goals-attempted-under-40", "FG Att <40"),
+    category(82, "field-goals-missed-under-40", "FG Miss <40"),
+    category(77, "field-goals-made-40-49", "FG 40-49"),
+    category(78, "field-goals-attempted-40-49", "FG Att 40-49"),
+    category(79, "field-goals-missed-40-49", "FG Miss 40-49"),
+    category(74, "field-goals-made-50-plus", "FG 50+"),
+    category(75, "field-goals-attempted-50-plus", "FG Att 50+"),
+    category(76, "field-goals-missed-50-plus", "FG Miss 50+"),
+    category(198, "field-goals-made-50-59", "FG 50-59"),
+    category(199, "field-goals-attempted-50-59", "FG Att 50-59"),
+    category(200, "field-goals-missed-50-59", "FG Miss 50-59"),
+    category(201, "field-goals-made-60-plus", "FG 60+"),
+    category(202, "field-goals-attempted-60-plus", "FG Att 60+"),
+    category(203, "field-goals-missed-60-plus", "FG Miss 60+"),
+    category(86, "extra-points-made", "XP"),
+    category(87, "extra-points-attempted", "XP Att"),
+    category(88, "extra-points-missed", "XP Miss"),
+    category(99, "defensive-sacks", "Sacks"),
+    category(95, "defensive-interceptions", "Def INT"),
+    category(96, "defensive-fumble-recoveries", "Fum Rec"),
+    category(98, "defensive-touchdowns", "Def TD"),
+    category(120, "points-allowed", "Pts Allowed"),
+    category(89, "points-allowed-0", "PA 0"),
+    category(90, "points-allowed-1-6", "PA 1-6"),
+    category(91, "points-allowed-7-13", "PA 7-13"),
+    category(92, "points-allowed-14-17", "PA 14-17"),
+    category(121, "points-allowed-18-21", "PA 18-21"),
+    category(122, "points-allowed-22-27", "PA 22-27"),
+    category(123, "points-allowed-28-34", "PA 28-34"),
+    category(124, "points-allowed-35-45", "PA 35-45"),
+    category(125, "points-allowed-46-plus", "PA 46+"),
+    category(127, "yards-allowed", "Yds Allowed"),
+    category(128, "yards-allowed-under-100", "YA <100"),
+    category(129, "yards-allowed-100-199", "YA 100-199"),
+    category(130, "yards-allowed-200-299", "YA 200-299"),
+    category(131, "yards-allowed-300-349", "YA 300-349"),
+    category(132, "yards-allowed-350-399", "YA 350-399"),
+    category(133, "yards-allowed-400-449", "YA 400-449"),
+    category(134, "yards-allowed-450-499", "YA 450-499"),
+    category(135, "yards-allowed-500-549", "YA 500-549"),
+    category(136, "yards-allowed-550-plus", "YA 550+"),
+];
+
+/// Lowercase and drop separators so `passing-yards`, `passing_yards` and
+/// `passingYards` all match
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Look up a category by name (case and separators are ignored)
+pub fn find_stat_category(name: &str) -> Option<&'static StatCategory> {
+    let wanted = normalize(name);
+    STAT_CATEGORIES.iter().find(|c| normalize(c.name) == wanted)
+}
+
+/// Display label for a stat ID, e.g. "FG 50+"; unnamed IDs read "Stat <id>"
+pub fn stat_label(stat_id: u16) -> String {
+    STAT_CATEGORIES
+        .iter()
+        .find(|c| c.stat_id == stat_id)
+        .map(|c| c.label.to_string())
+        .unwrap_or_else(|| format!("Stat {}", stat_id))
+}
+
+/// Parse a category name, listing the known names on failure
+pub fn parse_stat_category(name: &str) -> Result<&'static StatCategory, EspnError> {
+    find_stat_category(name).ok_or_else(|| EspnError::UnknownStatCategory {
+        name: name.to_string(),
+        known: STAT_CATEGORIES.iter().map(|c| c.name.to_string()).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_stat_category_ignores_case_and_separators() {
+        for name in [
+            "passing-yards",
+            "passing_yards",
+            "passingYards",
+            "PASSING YARDS",
+        ] {
+            assert_eq!(find_stat_category(name).unwrap().stat_id, 3);
+        }
+        assert!(find_stat_category("passing-yard").is_none());
+    }
+
+    #[test]
+    fn test_stat_categories_are_unique() {
+        for (i, a) in STAT_CATEGORIES.iter().enumerate() {
+            for b in &STAT_CATEGORIES[i + 1..] {
+                assert_ne!(a.stat_id, b.stat_id, "{} and {}", a.name, b.name);
+                assert_ne!(normalize(a.name), normalize(b.name));
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_stat_category_error_lists_known_names() {
+        let err = parse_stat_category("touchdowns").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("touchdowns"));
+        assert!(message.contains("rushing-touchdowns"));
+    }
+}
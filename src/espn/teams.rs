@@ -0,0 +1,276 @@
+//! NFL teams by ESPN pro team ID, and the team defenses (D/ST) built on them.
+
+/// An NFL team as ESPN numbers it
+struct ProTeam {
+    id: u32,
+    abbrev: &'static str,
+    name: &'static str,
+}
+
+/// Every current NFL team; ESPN skips IDs 31 and 32
+const PRO_TEAMS: &[ProTeam] = &[
+    ProTeam {
+        id: 1,
+        abbrev: "ATL",
+        name: "Atlanta Falcons",
+    },
+    ProTeam {
+        id: 2,
+        abbrev: "BUF",
+        name: "Buffalo Bills",
+    },
+    ProTeam {
+        id: 3,
+        abbrev: "CHI",
+        name: "Chicago Bears",
+    },
+    ProTeam {
+        id: 4,
+        abbrev: "CIN",
+        name: "Cincinnati Bengals",
+    },
+    ProTeam {
+        id: 5,
+        abbrev: "CLE",
+        name: "Cleveland Browns",
+    },
+    ProTeam {
+        id: 6,
+        abbrev: "DAL",
+        name: "Dallas Cowboys",
+    },
+    ProTeam {
+        id: 7,
+        abbrev: "DEN",
+        name: "Denver Broncos",
+    },
+    ProTeam {
+        id: 8,
+        abbrev: "DET",
+        name: "Detroit Lions",
+    },
+    ProTeam {
+        id: 9,
+        abbrev: "GB",
+        name: "Green Bay Packers",
+    },
+    ProTeam {
+        id: 10,
+        abbrev: "TEN",
+        name: "Tennessee Titans",
+    },
+    ProTeam {
+        id: 11,
+        abbrev: "IND",
+        name: "Indianapolis Colts",
+    },
+    ProTeam {
+        id: 12,
+        abbrev: "KC",
+        name: "Kansas City Chiefs",
+    },
+    ProTeam {
+        id: 13,
+        abbrev: "LV",
+        name: "Las Vegas Raiders",
+    },
+    ProTeam {
+        id: 14,
+        abbrev: "LAR",
+        name: "Los Angeles Rams",
+    },
+    ProTeam {
+        id: 15,
+        abbrev: "MIA",
+        name: "Miami Dolphins",
+    },
+    ProTeam {
+        id: 16,
+        abbrev: "MIN",
+        name: "Minnesota Vikings",
+    },
+    ProTeam {
+        id: 17,
+        abbrev: "NE",
+        name: "New England Patriots",
+    },
+    ProTeam {
+        id: 18,
+        abbrev: "NO",
+        name: "New Orleans Saints",
+    },
+    ProTeam {
+        id: 19,
+        abbrev: "NYG",
+        name: "New York Giants",
+    },
+    ProTeam {
+        id: 20,
+        abbrev: "NYJ",
+        name: "New York Jets",
+    },
+    ProTeam {
+        id: 21,
+        abbrev: "PHI",
+        name: "Philadelphia Eagles",
+    },
+    ProTeam {
+        id: 22,
+        abbrev: "ARI",
+        name: "Arizona Cardinals",
+    },
+    ProTeam {
+        id: 23,
+        abbrev: "PIT",
+        name: "Pittsburgh Steelers",
+    },
+    ProTeam {
+        id: 24,
+        abbrev: "LAC",
+        name: "Los Angeles Chargers",
+    },
+    ProTeam {
+        id: 25,
+        abbrev: "SF",
+        name: "San Francisco 49ers",
+    },
+    ProTeam {
+        id: 26,
+        abbrev: "SEA",
+        name: "Seattle Seahawks",
+    },
+    ProTeam {
+        id: 27,
+        abbrev: "TB",
+        name: "Tampa Bay Buccaneers",
+    },
+    ProTeam {
+        id: 28,
+        abbrev: "WSH",
+        name: "Washington Commanders",
+    },
+    ProTeam {
+        id: 29,
+        abbrev: "CAR",
+        name: "Carolina Panthers",
+    },
+    ProTeam {
+        id: 30,
+        abbrev: "JAX",
+        name: "Jacksonville Jaguars",
+    },
+    ProTeam {
+        id: 33,
+        abbrev: "BAL",
+        name: "Baltimore Ravens",
+    },
+    ProTeam {
+        id: 34,
+        abbrev: "HOU",
+        name: "Houston Texans",
+    },
+];
+
+fn pro_team(pro_team_id: u32) -> Option<&'static ProTeam> {
+    PRO_TEAMS.iter().find(|team| team.id == pro_team_id)
+}
+
+/// Abbreviation for an ESPN NFL team ID
+pub fn pro_team_abbrev(pro_team_id: u32) -> Option<&'static str> {
+    pro_team(pro_team_id).map(|team| team.abbrev)
+}
+
+/// Full name for an ESPN NFL team ID, e.g. "Buffalo Bills"
+pub fn pro_team_name(pro_team_id: u32) -> Option<&'static str> {
+    pro_team(pro_team_id).map(|team| team.name)
+}
+
+/// Name for a team defense when ESPN sends none, e.g. "Buffalo Bills D/ST"
+pub fn defense_name(pro_team_id: u32) -> Option<String> {
+    pro_team_name(pro_team_id).map(|name| format!("{} D/ST", name))
+}
+
+/// Team defenses have player ID `-(DEFENSE_ID_BASE + pro team ID)`, e.g. -16002
+/// for the Bills D/ST
+pub const DEFENSE_ID_BASE: u64 = 16000;
+
+/// NFL team ID encoded in a team defense's player ID
+///
+/// Accepts the positive form older versions stored too (see `db dedupe`).
+pub fn defense_pro_team_id(player_id: i64) -> Option<u32> {
+    let pro_team_id = player_id.unsigned_abs().checked_sub(DEFENSE_ID_BASE)?;
+    u32::try_from(pro_team_id)
+        .ok()
+        .filter(|&id| pro_team_abbrev(id).is_some())
+}
+
+/// NFL team ID for a team defense's display name, e.g. "Bills D/ST"
+///
+/// Matches on the nickname, so relocated teams (Rams, Chargers, Raiders) and
+/// every name Washington has played under resolve to today's team.
+pub fn defense_pro_team_id_from_name(name: &str) -> Option<u32> {
+    let team = name
+        .trim()
+        .trim_end_matches("D/ST")
+        .trim_end_matches("DST")
+        .trim_end();
+    if team.eq_ignore_ascii_case("Washington") || team.ends_with("Football Team") {
+        return Some(28);
+    }
+    let id = match team.rsplit(' ').next()?.to_ascii_lowercase().as_str() {
+        "falcons" => 1,
+        "bills" => 2,
+        "bears" => 3,
+        "bengals" => 4,
+        "browns" => 5,
+        "cowboys" => 6,
+        "broncos" => 7,
+        "lions" => 8,
+        "packers" => 9,
+        "titans" => 10,
+        "colts" => 11,
+        "chiefs" => 12,
+        "raiders" => 13,
+        "rams" => 14,
+        "dolphins" => 15,
+        "vikings" => 16,
+        "patriots" => 17,
+        "saints" => 18,
+        "giants" => 19,
+        "jets" => 20,
+        "eagles" => 21,
+        "cardinals" => 22,
+        "steelers" => 23,
+        "chargers" => 24,
+        "49ers" => 25,
+        "seahawks" => 26,
+        "buccaneers" => 27,
+        "commanders" | "redskins" => 28,
+        "panthers" => 29,
+        "jaguars" => 30,
+        "ravens" => 33,
+        "texans" => 34,
+        _ => return None,
+    };
+    Some(id)
+}
+
+/// ESPN NFL team ID for an abbreviation, case-insensitive
+///
+/// Other sites' spellings (`WAS`, `JAC`) and pre-move abbreviations (`OAK`,
+/// `SD`, `STL`) are accepted too.
+pub fn pro_team_id_for_abbrev(abbrev: &str) -> Option<u32> {
+    let abbrev = abbrev.trim().to_ascii_uppercase();
+    let canonical = match abbrev.as_str() {
+        "WAS" => "WSH",
+        "JAC" => "JAX",
+        "OAK" => "LV",
+        "SD" => "LAC",
+        "STL" | "LA" => "LAR",
+        other => other,
+    };
+    PRO_TEAMS
+        .iter()
+        .find(|team| team.abbrev == canonical)
+        .map(|team| team.id)
+}
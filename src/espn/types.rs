@@ -1,6 +1,19 @@
-use crate::{PlayerId, Season, Week};
-use serde::{de::Error, Deserialize, Deserializer, Serialize};
-use std::collections::BTreeMap;
+use crate::{
+    cli::render::skip_none,
+    cli::types::position::{FlexEligibility, Position},
+    espn::{
+        lineup::slot_eligible_positions,
+        teams::{
+            defense_name, defense_pro_team_id, defense_pro_team_id_from_name, pro_team_abbrev,
+        },
+    },
+    PlayerId, Season, UtcTimestamp, Week,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::{LazyLock, Mutex},
+};
 
 /// Parameters for creating PlayerPoints from cached data to avoid too many function arguments.
 #[derive(Debug)]
@@ -17,31 +30,69 @@ pub struct CachedPlayerData {
     pub is_rostered: Option<bool>,
     pub team_id: Option<u32>,
     pub team_name: Option<String>,
+    pub played: Option<bool>,
+    pub opponent_team: Option<String>,
+    pub home_game: Option<bool>,
 }
 
-fn de_str_key_map_u8_f64<'de, D>(deserializer: D) -> Result<BTreeMap<u8, f64>, D::Error>
-where
-    D: Deserializer<'de>,
-{
-    let raw: BTreeMap<String, f64> = Deserialize::deserialize(deserializer)?;
-    raw.into_iter()
-        .map(|(k, v)| k.parse::<u8>().map(|kk| (kk, v)).map_err(D::Error::custom))
-        .collect()
-}
-
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "RawScoringItem")]
 pub struct ScoringItem {
     #[serde(rename = "statId")]
     pub stat_id: u16,
     /// Base points for this stat (used when no override exists for the player's slot)
     pub points: f64,
     /// Overrides by lineup slot id (keys come in as strings)
+    #[serde(rename = "pointsOverrides")]
+    pub points_overrides: BTreeMap<u8, f64>,
+    /// The stat counts against the player: its points (and overrides) are negated
+    #[serde(rename = "isReverseItem", default)]
+    pub is_reverse_item: bool,
+    /// `pointsOverrides` keys that aren't lineup slot IDs and were left out
     #[serde(
-        rename = "pointsOverrides",
-        deserialize_with = "de_str_key_map_u8_f64",
-        default
+        rename = "skippedOverrideKeys",
+        default,
+        skip_serializing_if = "Vec::is_empty"
     )]
-    pub points_overrides: BTreeMap<u8, f64>,
+    pub skipped_override_keys: Vec<String>,
+}
+
+/// A scoring item as ESPN sends it; fields we don't use (e.g. `leagueRanking`) are ignored
+#[derive(Deserialize)]
+struct RawScoringItem {
+    #[serde(rename = "statId")]
+    stat_id: u16,
+    points: f64,
+    #[serde(rename = "pointsOverrides", default)]
+    points_overrides: BTreeMap<String, f64>,
+    #[serde(rename = "isReverseItem", default)]
+    is_reverse_item: bool,
+    #[serde(rename = "skippedOverrideKeys", default)]
+    skipped_override_keys: Vec<String>,
+}
+
+impl From<RawScoringItem> for ScoringItem {
+    /// Override keys that don't parse as a lineup slot ID are skipped rather than
+    /// failing the whole settings parse
+    fn from(raw: RawScoringItem) -> Self {
+        let mut skipped_override_keys = raw.skipped_override_keys;
+        let mut points_overrides = BTreeMap::new();
+        for (key, points) in raw.points_overrides {
+            match key.parse::<u8>() {
+                Ok(slot) => {
+                    points_overrides.insert(slot, points);
+                }
+                Err(_) => skipped_override_keys.push(key),
+            }
+        }
+        Self {
+            stat_id: raw.stat_id,
+            points: raw.points,
+            points_overrides,
+            is_reverse_item: raw.is_reverse_item,
+            skipped_override_keys,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -50,6 +101,30 @@ pub struct ScoringSettings {
     pub scoring_items: Vec<ScoringItem>,
 }
 
+impl ScoringSettings {
+    /// One line per scoring item with `pointsOverrides` keys that were skipped
+    pub fn override_warnings(&self) -> Vec<String> {
+        self.scoring_items
+            .iter()
+            .filter(|item| !item.skipped_override_keys.is_empty())
+            .map(|item| {
+                format!(
+                    "stat {}: skipped pointsOverrides key(s) {} (not lineup slot IDs)",
+                    item.stat_id,
+                    item.skipped_override_keys
+                        .iter()
+                        .map(|key| format!("{:?}", key))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+            .collect()
+    }
+}
+
+/// Lineup slot ID of OP, the superflex slot (QB/RB/WR/TE)
+pub const SUPERFLEX_SLOT: u8 = 7;
+
 /// Roster settings from league configuration
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RosterSettings {
@@ -59,6 +134,57 @@ pub struct RosterSettings {
     pub position_limits: std::collections::HashMap<String, i32>,
 }
 
+/// Schedule settings from league configuration
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ScheduleSettings {
+    /// Regular-season matchup periods; later periods are playoffs
+    #[serde(rename = "matchupPeriodCount", default)]
+    pub matchup_period_count: Option<u16>,
+    #[serde(rename = "playoffTeamCount", default)]
+    pub playoff_team_count: Option<u32>,
+    /// Scoring periods (weeks) played in each matchup period; a two-week
+    /// championship maps one matchup period to two weeks
+    #[serde(
+        rename = "matchupPeriods",
+        alias = "scoringPeriodsByMatchupPeriod",
+        default
+    )]
+    pub matchup_periods: BTreeMap<u16, Vec<u16>>,
+}
+
+impl ScheduleSettings {
+    /// Weeks that make up a matchup period, in order
+    ///
+    /// Without ESPN's mapping every matchup period is the week of the same number.
+    pub fn scoring_periods(&self, matchup_period: u16) -> Vec<Week> {
+        match self.matchup_periods.get(&matchup_period) {
+            Some(weeks) if !weeks.is_empty() => {
+                let mut weeks = weeks.clone();
+                weeks.sort_unstable();
+                weeks.into_iter().map(Week::new).collect()
+            }
+            _ => vec![Week::new(matchup_period)],
+        }
+    }
+
+    /// Matchup period a week is played in
+    pub fn matchup_period_for(&self, week: Week) -> u16 {
+        self.matchup_periods
+            .iter()
+            .find(|(_, weeks)| weeks.contains(&week.as_u16()))
+            .map_or(week.as_u16(), |(period, _)| *period)
+    }
+
+    /// Matchup periods spanning more than one week, with their weeks
+    pub fn multi_week_periods(&self) -> BTreeMap<u16, Vec<Week>> {
+        self.matchup_periods
+            .keys()
+            .map(|&period| (period, self.scoring_periods(period)))
+            .filter(|(_, weeks)| weeks.len() > 1)
+            .collect()
+    }
+}
+
 /// Root we deserialize out of mSettings
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LeagueSettings {
@@ -66,6 +192,9 @@ pub struct LeagueSettings {
     pub scoring_settings: ScoringSettings,
     #[serde(rename = "rosterSettings")]
     pub roster_settings: RosterSettings,
+    /// Missing from settings cached before it was read
+    #[serde(rename = "scheduleSettings", default)]
+    pub schedule_settings: ScheduleSettings,
 }
 
 impl LeagueSettings {
@@ -110,6 +239,66 @@ impl LeagueSettings {
 
         position_ids
     }
+
+    /// Whether the lineup has an OP (superflex) slot, which QBs can fill
+    pub fn has_superflex(&self) -> bool {
+        self.roster_settings
+            .lineup_slot_counts
+            .get(&SUPERFLEX_SLOT.to_string())
+            .is_some_and(|count| *count > 0)
+    }
+
+    /// Positions that can fill lineup slot `slot_id` when it's a flex-style slot
+    /// (RB/WR, WR/TE, OP or FLEX)
+    ///
+    /// `None` for single-position and non-starting slots, and for slots this
+    /// league's lineup doesn't have.
+    pub fn flex_eligible_positions(&self, slot_id: u8) -> Option<Vec<Position>> {
+        let in_lineup = self
+            .roster_settings
+            .lineup_slot_counts
+            .get(&slot_id.to_string())
+            .is_some_and(|count| *count > 0);
+        let positions = slot_eligible_positions(slot_id).filter(|p| p.len() > 1 && in_lineup)?;
+        Some(positions.iter().filter_map(|p| p.parse().ok()).collect())
+    }
+
+    /// What a FLEX filter covers in this league: every position one of its
+    /// flex-style slots takes, or the standard RB/WR/TE without any
+    pub fn flex_eligibility(&self) -> FlexEligibility {
+        FlexEligibility::new(
+            self.roster_settings
+                .lineup_slot_counts
+                .keys()
+                .filter_map(|id| id.parse().ok())
+                .filter_map(|id| self.flex_eligible_positions(id))
+                .flatten(),
+        )
+    }
+
+    /// Number of dedicated starting lineup spots per position across the whole league
+    ///
+    /// Keys use the same position names as stored player data (e.g. "QB", "D/ST").
+    /// Flex-style slots are not attributed to any single position.
+    pub fn starters_per_position(&self, team_count: u32) -> std::collections::HashMap<String, u32> {
+        self.roster_settings
+            .lineup_slot_counts
+            .iter()
+            .filter_map(|(slot_id, count)| {
+                let position = match slot_id.parse::<u8>().ok()? {
+                    0 => "QB",
+                    2 => "RB",
+                    4 => "WR",
+                    6 => "TE",
+                    16 => "D/ST",
+                    17 => "K",
+                    _ => return None,
+                };
+                Some((position.to_string(), count * team_count))
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
 }
 
 /// Top-level envelope for mSettings
@@ -118,8 +307,214 @@ pub struct LeagueEnvelope {
     pub settings: LeagueSettings,
 }
 
+/// League status block from the mStatus view
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LeagueStatus {
+    #[serde(rename = "currentMatchupPeriod", default)]
+    pub current_matchup_period: Option<u16>,
+    #[serde(rename = "latestScoringPeriod", default)]
+    pub latest_scoring_period: Option<u16>,
+    #[serde(rename = "finalScoringPeriod", default)]
+    pub final_scoring_period: Option<u16>,
+    #[serde(rename = "isActive", default)]
+    pub is_active: Option<bool>,
+    #[serde(rename = "previousSeasons", default)]
+    pub previous_seasons: Vec<u16>,
+}
+
+/// Top-level envelope for mStatus
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LeagueStatusEnvelope {
+    #[serde(rename = "seasonId", default)]
+    pub season_id: Option<u16>,
+    /// The league's current scoring period (week)
+    #[serde(rename = "scoringPeriodId", default)]
+    pub scoring_period_id: Option<u16>,
+    #[serde(default)]
+    pub status: LeagueStatus,
+}
+
+impl LeagueStatusEnvelope {
+    /// The current week of the season, if ESPN reported one
+    pub fn current_week(&self) -> Option<Week> {
+        self.scoring_period_id
+            .or(self.status.latest_scoring_period)
+            .map(Week::new)
+    }
+
+    /// Every season the league has existed, oldest first
+    pub fn league_seasons(&self) -> Vec<u16> {
+        let mut seasons = self.status.previous_seasons.clone();
+        seasons.extend(self.season_id);
+        seasons.sort_unstable();
+        seasons.dedup();
+        seasons
+    }
+}
+
+/// Game-level metadata from the base FFL endpoint
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct GameMetadata {
+    /// Latest season ESPN has opened (the one new leagues are created in)
+    #[serde(rename = "currentSeasonId", default)]
+    pub current_season_id: Option<u16>,
+}
+
+/// A single NFL game from the proTeamSchedules_wl view
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProGame {
+    pub id: u64,
+    /// Kickoff time in milliseconds since the Unix epoch
+    pub date: u64,
+    #[serde(rename = "homeProTeamId", default)]
+    pub home_pro_team_id: Option<u32>,
+    #[serde(rename = "awayProTeamId", default)]
+    pub away_pro_team_id: Option<u32>,
+}
+
+/// An NFL team and its games keyed by scoring period
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProTeam {
+    pub id: i32,
+    #[serde(default)]
+    pub abbrev: Option<String>,
+    #[serde(rename = "proGamesByScoringPeriod", default)]
+    pub pro_games_by_scoring_period: std::collections::HashMap<String, Vec<ProGame>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProScheduleSettings {
+    #[serde(rename = "proTeams", default)]
+    pub pro_teams: Vec<ProTeam>,
+}
+
+/// NFL schedule for a season (games/ffl/seasons/{season}?view=proTeamSchedules_wl)
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProSchedule {
+    pub settings: ProScheduleSettings,
+}
+
+impl ProSchedule {
+    /// Kickoff of the week's last game in seconds since the Unix epoch, once every
+    /// lineup slot is locked
+    pub fn last_kickoff(&self, week: Week) -> Option<u64> {
+        let key = week.as_u16().to_string();
+        self.settings
+            .pro_teams
+            .iter()
+            .filter_map(|team| team.pro_games_by_scoring_period.get(&key))
+            .flatten()
+            .map(|game| game.date / 1000)
+            .max()
+    }
+
+    /// Kickoff of the week's first game in seconds since the Unix epoch
+    pub fn first_kickoff(&self, week: Week) -> Option<u64> {
+        let key = week.as_u16().to_string();
+        self.settings
+            .pro_teams
+            .iter()
+            .filter_map(|team| team.pro_games_by_scoring_period.get(&key))
+            .flatten()
+            .map(|game| game.date / 1000)
+            .min()
+    }
+
+    /// Kickoff of an NFL team's game in a week, in seconds; `None` on a bye
+    pub fn kickoff(&self, pro_team_id: u32, week: Week) -> Option<u64> {
+        let key = week.as_u16().to_string();
+        self.settings
+            .pro_teams
+            .iter()
+            .filter(|team| i64::from(team.id) == i64::from(pro_team_id))
+            .filter_map(|team| team.pro_games_by_scoring_period.get(&key))
+            .flatten()
+            .map(|game| game.date / 1000)
+            .min()
+    }
+
+    /// The NFL team an NFL team plays in a week; `None` on a bye
+    ///
+    /// Both teams list the same game ID, so the opponent is the other team with it.
+    pub fn opponent(&self, pro_team_id: u32, week: Week) -> Option<u32> {
+        let key = week.as_u16().to_string();
+        let is_team = |team: &ProTeam| i64::from(team.id) == i64::from(pro_team_id);
+        let game_ids: HashSet<u64> = self
+            .settings
+            .pro_teams
+            .iter()
+            .filter(|team| is_team(team))
+            .filter_map(|team| team.pro_games_by_scoring_period.get(&key))
+            .flatten()
+            .map(|game| game.id)
+            .collect();
+        self.settings
+            .pro_teams
+            .iter()
+            .filter(|team| !is_team(team))
+            .find(|team| {
+                team.pro_games_by_scoring_period
+                    .get(&key)
+                    .is_some_and(|games| games.iter().any(|game| game_ids.contains(&game.id)))
+            })
+            .and_then(|team| u32::try_from(team.id).ok())
+    }
+
+    /// Whether an NFL team plays at home in a week; `None` on a bye or when ESPN
+    /// didn't say which side is home
+    pub fn is_home(&self, pro_team_id: u32, week: Week) -> Option<bool> {
+        let key = week.as_u16().to_string();
+        self.settings
+            .pro_teams
+            .iter()
+            .filter(|team| i64::from(team.id) == i64::from(pro_team_id))
+            .filter_map(|team| team.pro_games_by_scoring_period.get(&key))
+            .flatten()
+            .find_map(|game| game.home_pro_team_id)
+            .map(|home| home == pro_team_id)
+    }
+
+    /// Whether every game of `week` has finished by `now` (seconds), i.e. its last
+    /// game kicked off more than [`GAME_WINDOW_SECS`] ago
+    pub fn week_is_over(&self, week: Week, now: u64) -> bool {
+        self.last_kickoff(week)
+            .is_some_and(|kickoff| now >= kickoff + GAME_WINDOW_SECS)
+    }
+
+    /// NFL teams whose game in `week` has kicked off by `now` (seconds)
+    ///
+    /// `None` once the week is over (its last game kicked off more than
+    /// [`GAME_WINDOW_SECS`] ago) or when the schedule has no games for it, since
+    /// locks only matter while lineups can still change.
+    pub fn locked_teams(&self, week: Week, now: u64) -> Option<HashSet<u32>> {
+        let last_kickoff = self.last_kickoff(week)?;
+        if now >= last_kickoff + GAME_WINDOW_SECS {
+            return None;
+        }
+        Some(
+            self.settings
+                .pro_teams
+                .iter()
+                .filter_map(|team| u32::try_from(team.id).ok())
+                .filter(|&team| {
+                    self.kickoff(team, week)
+                        .is_some_and(|kickoff| now >= kickoff)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// How long after its kickoff a game is assumed to still be in progress
+pub const GAME_WINDOW_SECS: u64 = 4 * 60 * 60;
+
 /// Player injury status
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+///
+/// How each status maps to the `--injury-status` filters: `active` only matches
+/// Active, and `injured` matches every other status. That includes Suspension,
+/// PUP and NonFootballInjury (the player is unavailable either way) and Unknown,
+/// so no player falls outside both filters.
+#[derive(Debug, Clone, Serialize, PartialEq)]
 pub enum InjuryStatus {
     #[serde(rename = "ACTIVE")]
     Active,
@@ -135,10 +530,76 @@ pub enum InjuryStatus {
     Probable,
     #[serde(rename = "DAY_TO_DAY")]
     DayToDay,
-    #[serde(other)]
+    #[serde(rename = "SUSPENSION")]
+    Suspension,
+    /// Physically unable to perform list
+    #[serde(rename = "PHYSICALLY_UNABLE_TO_PERFORM")]
+    PUP,
+    #[serde(rename = "NON_FOOTBALL_INJURY")]
+    NonFootballInjury,
     Unknown,
 }
 
+/// Raw statuses already reported as unrecognized, so each is logged once per run
+static UNRECOGNIZED_INJURY_STATUSES: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+impl InjuryStatus {
+    /// Parse a status from ESPN or the database, falling back to Unknown
+    ///
+    /// The first time an unrecognized value is seen it is printed as a warning so
+    /// new ESPN statuses get noticed.
+    pub fn parse_or_unknown(raw: &str) -> Self {
+        raw.parse().unwrap_or_else(|_| {
+            let mut seen = UNRECOGNIZED_INJURY_STATUSES
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            if seen.insert(raw.to_string()) {
+                eprintln!(
+                    "⚠ Warning: unrecognized injury status {:?}; treating it as Unknown",
+                    raw
+                );
+            }
+            InjuryStatus::Unknown
+        })
+    }
+}
+
+impl std::str::FromStr for InjuryStatus {
+    type Err = String;
+
+    /// Accepts ESPN's codes (`DAY_TO_DAY`) and the display names stored in the
+    /// database (`Day-to-Day`), ignoring case and separators
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        match normalized.as_str() {
+            "active" => Ok(InjuryStatus::Active),
+            "injuryreserve" | "ir" => Ok(InjuryStatus::InjuryReserve),
+            "out" => Ok(InjuryStatus::Out),
+            "doubtful" => Ok(InjuryStatus::Doubtful),
+            "questionable" => Ok(InjuryStatus::Questionable),
+            "probable" => Ok(InjuryStatus::Probable),
+            "daytoday" => Ok(InjuryStatus::DayToDay),
+            "suspension" | "suspended" => Ok(InjuryStatus::Suspension),
+            "physicallyunabletoperform" | "pup" => Ok(InjuryStatus::PUP),
+            "nonfootballinjury" | "nfi" => Ok(InjuryStatus::NonFootballInjury),
+            "unknown" => Ok(InjuryStatus::Unknown),
+            _ => Err(format!("unknown injury status: {}", s)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InjuryStatus {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        Ok(InjuryStatus::parse_or_unknown(&raw))
+    }
+}
+
 impl std::fmt::Display for InjuryStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -149,6 +610,9 @@ impl std::fmt::Display for InjuryStatus {
             InjuryStatus::Questionable => write!(f, "Questionable"),
             InjuryStatus::Probable => write!(f, "Probable"),
             InjuryStatus::DayToDay => write!(f, "Day-to-Day"),
+            InjuryStatus::Suspension => write!(f, "Suspension"),
+            InjuryStatus::PUP => write!(f, "PUP"),
+            InjuryStatus::NonFootballInjury => write!(f, "NFI"),
             InjuryStatus::Unknown => write!(f, "Unknown"),
         }
     }
@@ -170,6 +634,170 @@ pub struct Player {
     pub injured: Option<bool>,
     #[serde(rename = "injuryStatus", default)]
     pub injury_status: Option<InjuryStatus>,
+    /// ESPN's NFL team ID; 0 for free agents
+    #[serde(rename = "proTeamId", default)]
+    pub pro_team_id: Option<u32>,
+    /// Lineup slots the player can fill
+    #[serde(rename = "eligibleSlots", default)]
+    pub eligible_slots: Vec<u8>,
+    /// League-wide roster and start rates; absent from some responses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ownership: Option<PlayerOwnership>,
+    /// Latest news headline; ESPN's shape for this block varies, anything unreadable is `None`
+    #[serde(
+        default,
+        deserialize_with = "deserialize_player_news",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub news: Option<PlayerNews>,
+}
+
+/// Share of ESPN leagues rostering and starting a player, as percentages (0-100)
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct PlayerOwnership {
+    #[serde(rename = "percentOwned", default)]
+    pub percent_owned: Option<f64>,
+    #[serde(rename = "percentStarted", default)]
+    pub percent_started: Option<f64>,
+}
+
+/// A news headline ESPN attached to a player
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerNews {
+    pub headline: String,
+    /// Unix timestamp in seconds; `None` when ESPN leaves the date out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<u64>,
+}
+
+impl PlayerNews {
+    /// The latest headline in a news block
+    ///
+    /// ESPN sends the block as a single item, an object wrapping a `feed` array,
+    /// or a bare array of items; it may also be empty or missing. The newest
+    /// dated item wins, and items without a headline are skipped.
+    pub fn from_value(value: &serde_json::Value) -> Option<Self> {
+        use serde_json::Value;
+        match value {
+            Value::Array(items) => {
+                items
+                    .iter()
+                    .filter_map(Self::from_value)
+                    .reduce(|latest, item| {
+                        if item.published > latest.published {
+                            item
+                        } else {
+                            latest
+                        }
+                    })
+            }
+            Value::Object(fields) => {
+                if let Some(feed) = fields.get("feed") {
+                    return Self::from_value(feed);
+                }
+                let headline = fields.get("headline")?.as_str()?.trim();
+                if headline.is_empty() {
+                    return None;
+                }
+                let published = ["published", "lastModified", "date"]
+                    .iter()
+                    .find_map(|key| fields.get(*key).and_then(news_timestamp));
+                Some(Self {
+                    headline: headline.to_string(),
+                    published,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Seconds since the epoch from an ESPN news date: epoch seconds or milliseconds,
+/// or an ISO 8601 string (fractional seconds are dropped)
+fn news_timestamp(value: &serde_json::Value) -> Option<u64> {
+    const MILLIS_THRESHOLD: u64 = 100_000_000_000;
+    if let Some(number) = value.as_u64() {
+        return Some(if number >= MILLIS_THRESHOLD {
+            number / 1000
+        } else {
+            number
+        });
+    }
+    let text = value.as_str()?.trim();
+    let text = match text.split_once('.') {
+        Some((whole, _fraction)) => whole,
+        None => text.strip_suffix(['Z', 'z']).unwrap_or(text),
+    };
+    text.parse::<UtcTimestamp>().ok().map(|ts| ts.as_secs())
+}
+
+/// Read the optional news block without ever failing the player it belongs to
+fn deserialize_player_news<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<PlayerNews>, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(PlayerNews::from_value(&value))
+}
+
+impl Player {
+    /// The player's position, from `defaultPositionId` or else from the one
+    /// single-position lineup slot they are eligible for
+    pub fn position(&self) -> Option<Position> {
+        if let Ok(id) = u8::try_from(self.default_position_id) {
+            if let Ok(position) = Position::try_from(id) {
+                return Some(position);
+            }
+        }
+        let mut from_slots = self.eligible_slots.iter().filter_map(|slot| match slot {
+            0 => Some(Position::QB),
+            2 => Some(Position::RB),
+            4 => Some(Position::WR),
+            6 => Some(Position::TE),
+            16 => Some(Position::DEF),
+            17 => Some(Position::K),
+            _ => None,
+        });
+        let position = from_slots.next()?;
+        from_slots.all(|p| p == position).then_some(position)
+    }
+
+    /// ESPN's NFL team ID, when the player is on a team
+    ///
+    /// Team defenses often come without `proTeamId`, so theirs is read from the
+    /// player ID (see [`defense_pro_team_id`]) or else the name.
+    pub fn nfl_team_id(&self) -> Option<u32> {
+        match self.pro_team_id {
+            Some(id) if id != 0 => Some(id),
+            _ if self.position() == Some(Position::DEF) => {
+                defense_pro_team_id(self.id).or_else(|| {
+                    self.full_name
+                        .as_deref()
+                        .and_then(defense_pro_team_id_from_name)
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// NFL team abbreviation, e.g. "BUF", when the player is on a team
+    pub fn pro_team(&self) -> Option<&'static str> {
+        self.nfl_team_id().and_then(pro_team_abbrev)
+    }
+
+    /// ESPN's name for the player, or a stand-in when it sent none
+    ///
+    /// A team defense is named after its NFL team (e.g. "Buffalo Bills D/ST");
+    /// anyone else becomes `Player {id}`.
+    pub fn display_name(&self) -> String {
+        if let Some(name) = &self.full_name {
+            return name.clone();
+        }
+        self.position()
+            .filter(|p| *p == Position::DEF)
+            .and_then(|_| self.nfl_team_id())
+            .and_then(defense_name)
+            .unwrap_or_else(|| format!("Player {}", self.id))
+    }
 }
 
 /// Player statistics for a specific period
@@ -189,6 +817,7 @@ pub struct PlayerStats {
 
 /// Computed player points for display
 #[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub struct PlayerPoints {
     pub id: PlayerId,
     pub name: String,
@@ -196,12 +825,27 @@ pub struct PlayerPoints {
     pub week: Week,
     pub projected: bool,
     pub points: f64,
+    #[serde(skip_serializing_if = "skip_none")]
     pub active: Option<bool>,
+    #[serde(skip_serializing_if = "skip_none")]
     pub injured: Option<bool>,
+    #[serde(skip_serializing_if = "skip_none")]
     pub injury_status: Option<InjuryStatus>,
+    #[serde(skip_serializing_if = "skip_none")]
     pub is_rostered: Option<bool>,
+    #[serde(skip_serializing_if = "skip_none")]
     pub team_id: Option<u32>,
+    #[serde(skip_serializing_if = "skip_none")]
     pub team_name: Option<String>,
+    /// `Some(false)` when the player did not play (DNP) rather than scoring zero
+    #[serde(skip_serializing_if = "skip_none")]
+    pub played: Option<bool>,
+    /// NFL team the player faced that week (e.g. "KC"); `None` on a bye or when unknown
+    #[serde(skip_serializing_if = "skip_none")]
+    pub opponent_team: Option<String>,
+    /// Whether the player's team played at home
+    #[serde(skip_serializing_if = "skip_none")]
+    pub home_game: Option<bool>,
 }
 
 impl PlayerPoints {
@@ -228,8 +872,17 @@ impl PlayerPoints {
             is_rostered: Some(false),
             team_id: None,
             team_name: None,
+            played: None,
+            opponent_team: None,
+            home_game: None,
         }
     }
+
+    /// Whether this entry is a known "did not play" week
+    pub fn is_dnp(&self) -> bool {
+        self.played == Some(false)
+    }
+
     /// Create PlayerPoints from cached data with injury/roster info
     pub fn from_cached_data(params: CachedPlayerData) -> Self {
         Self {
@@ -245,6 +898,9 @@ impl PlayerPoints {
             is_rostered: params.is_rostered,
             team_id: params.team_id,
             team_name: params.team_name,
+            played: params.played,
+            opponent_team: params.opponent_team,
+            home_game: params.home_game,
         }
     }
 
@@ -266,6 +922,9 @@ impl PlayerPoints {
             is_rostered: None,   // Will be filled by update_player_points_with_roster_info
             team_id: None,       // Will be filled by update_player_points_with_roster_info
             team_name: None,     // Will be filled by update_player_points_with_roster_info
+            played: None,
+            opponent_team: None,
+            home_game: None,
         }
     }
 
@@ -280,10 +939,7 @@ impl PlayerPoints {
     ) -> Self {
         Self {
             id: player_id,
-            name: player
-                .full_name
-                .clone()
-                .unwrap_or_else(|| format!("Player {}", player.id)),
+            name: player.display_name(),
             position,
             points,
             week,
@@ -294,6 +950,9 @@ impl PlayerPoints {
             is_rostered: None, // Will be filled later
             team_id: None,     // Will be filled later
             team_name: None,   // Will be filled later
+            played: None,
+            opponent_team: None, // Will be filled from the NFL schedule
+            home_game: None,
         }
     }
 }
@@ -324,6 +983,28 @@ pub struct Team {
     pub roster: Option<TeamRoster>,
 }
 
+impl Team {
+    /// Whether ESPN sent a usable name (pre-draft and orphaned teams can lack one)
+    pub fn has_name(&self) -> bool {
+        self.name
+            .as_deref()
+            .is_some_and(|name| !name.trim().is_empty())
+    }
+
+    /// The team's name, or a "Team {id}" placeholder when ESPN sent none
+    pub fn display_name(&self) -> String {
+        team_display_name(self.id, self.name.as_deref())
+    }
+}
+
+/// A fantasy team's name, or a "Team {id}" placeholder when it has none
+pub fn team_display_name(team_id: u32, name: Option<&str>) -> String {
+    match name.filter(|name| !name.trim().is_empty()) {
+        Some(name) => name.to_string(),
+        None => format!("Team {}", team_id),
+    }
+}
+
 /// League data with teams from ESPN API
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LeagueData {
@@ -331,7 +1012,18 @@ pub struct LeagueData {
 }
 
 impl LeagueData {
+    /// IDs of rostered teams without a name, which show as "Team {id}"
+    pub fn nameless_team_ids(&self) -> Vec<u32> {
+        self.teams
+            .iter()
+            .filter(|team| team.roster.is_some() && !team.has_name())
+            .map(|team| team.id)
+            .collect()
+    }
+
     /// Create a mapping of player ID to team information
+    ///
+    /// Teams without a name get a "Team {id}" placeholder.
     pub fn create_player_roster_map(
         &self,
     ) -> std::collections::HashMap<i64, (u32, Option<String>, Option<String>)> {
@@ -342,7 +1034,7 @@ impl LeagueData {
                 for entry in &roster.entries {
                     player_to_team.insert(
                         entry.player_id,
-                        (team.id, team.name.clone(), team.abbrev.clone()),
+                        (team.id, Some(team.display_name()), team.abbrev.clone()),
                     );
                 }
             }
@@ -371,3 +1063,61 @@ impl LeagueData {
         }
     }
 }
+
+/// One side of a fantasy matchup from the mMatchupScore view
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MatchupSide {
+    #[serde(rename = "teamId")]
+    pub team_id: u32,
+    #[serde(rename = "totalPoints", default)]
+    pub total_points: Option<f64>,
+}
+
+/// Fantasy matchup from the league schedule
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Matchup {
+    pub id: u32,
+    #[serde(rename = "matchupPeriodId")]
+    pub matchup_period_id: u16,
+    pub home: Option<MatchupSide>,
+    /// Missing for bye weeks
+    #[serde(default)]
+    pub away: Option<MatchupSide>,
+    /// "HOME", "AWAY", "TIE" or "UNDECIDED" for matchups still in progress
+    #[serde(default = "default_matchup_winner")]
+    pub winner: String,
+}
+
+fn default_matchup_winner() -> String {
+    "UNDECIDED".to_string()
+}
+
+impl Matchup {
+    /// Whether ESPN has settled the result of this matchup
+    pub fn is_complete(&self) -> bool {
+        self.winner != "UNDECIDED"
+    }
+}
+
+/// League schedule with team information (mMatchupScore + mTeam views)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LeagueSchedule {
+    #[serde(default)]
+    pub teams: Vec<Team>,
+    #[serde(default)]
+    pub schedule: Vec<Matchup>,
+}
+
+impl LeagueSchedule {
+    /// Map of fantasy team ID to display name
+    pub fn team_names(&self) -> std::collections::HashMap<u32, String> {
+        self.teams
+            .iter()
+            .map(|team| {
+                let name =
+                    team_display_name(team.id, team.name.as_deref().or(team.abbrev.as_deref()));
+                (team.id, name)
+            })
+            .collect()
+    }
+}
@@ -18,12 +18,14 @@
 //! use espn_ffl::{LeagueId, Season, Week, Position, commands::{common::CommandParamsBuilder, player_data::*}};
 //!
 //! # async fn example() -> espn_ffl::Result<()> {
-//! // Get player data for current week
+//! // Get scored player data for week 1; `handle_player_data` prints it instead
 //! let params = PlayerDataParams::new(Season::default(), Week::new(1), false)
 //!     .with_league_id(LeagueId::new(123456))
 //!     .with_positions(vec![Position::QB, Position::RB]);
 //!
-//! handle_player_data(params).await?;
+//! for player in fetch_player_data(params).await? {
+//!     println!("{} {:.1}", player.name, player.points);
+//! }
 //! # Ok(())
 //! # }
 //! ```
@@ -46,9 +48,15 @@ pub mod storage;
 pub use cli::types::{
     ids::{LeagueId, PlayerId},
     position::Position,
-    time::{Season, Week},
+    time::{Season, UtcTimestamp, Week, WeekRange},
 };
 pub use error::{EspnError, Result};
 pub use espn::types::{LeagueSettings, ScoringItem, ScoringSettings};
 
 pub const LEAGUE_ID_ENV_VAR: &str = "ESPN_FFL_LEAGUE_ID";
+
+/// Set to any value other than `0`/`false` to disable disk-cache writes (`--no-cache-write`)
+pub const NO_CACHE_WRITE_ENV_VAR: &str = "ESPN_FFL_NO_CACHE_WRITE";
+
+/// Path of the local database file, instead of `players.db` in the cache directory (`--db-path`)
+pub const DB_PATH_ENV_VAR: &str = "ESPN_FFL_DB_PATH";
@@ -2,23 +2,100 @@
 
 use clap::Parser;
 use espn_ffl::{
-    cli::{Commands, ESPN},
+    cli::{
+        render::{enable_legacy_json, set_output_width},
+        types::{filters::FantasyTeamFilter, position::PositionSelector},
+        AuthCommands, Commands, DbCommands, NoteCommands, ESPN,
+    },
     commands::{
+        accuracy_report::{handle_accuracy_report, AccuracyReportParams},
+        auth::{handle_auth_set, handle_auth_status},
+        category_leaders::{handle_category_leaders, CategoryLeadersParams},
         common::CommandParamsBuilder,
+        compare::{handle_compare, CompareParams},
+        db::{
+            handle_db_aliases, handle_db_check, handle_db_coverage, handle_db_dedupe,
+            handle_db_export, handle_db_team_names,
+        },
         league_data::handle_league_data,
+        matchup_forecast::{handle_matchup_forecast, MatchupForecastParams},
+        notes::{handle_note_add, handle_note_list, handle_note_remove},
+        optimal_lineup::{handle_optimal_lineup, OptimalLineupParams},
+        pace::{handle_pace, PaceParams},
         player_data::{handle_player_data, PlayerDataParams},
+        playoff_odds::{handle_playoff_odds, PlayoffOddsParams},
+        positional_ranks::{handle_positional_ranks, PositionalRanksParams},
         projection_analysis::{handle_projection_analysis, ProjectionAnalysisParams},
+        projection_export::{ProjectionExport, DEFAULT_MAX_MISSING_PROJECTIONS},
+        roster::handle_roster,
+        roster_diff::handle_roster_diff,
+        season_records::handle_season_records,
+        stats_history::handle_stats_history,
+        streaming_recap::handle_streaming_recap,
+        team_totals::handle_team_totals,
         update_all_data::handle_update_all_data,
+        warm_cache::{handle_warm_cache, WarmCacheParams},
+        week_over_week::{handle_week_over_week, WeekOverWeekParams},
+    },
+    core::{
+        cache::GLOBAL_CACHE,
+        disable_cache_writes,
+        metrics::{enable_metrics, print_metrics_summary, stage},
     },
-    Result,
+    espn::{compute::enable_strict_positions, scoring_overrides::disable_scoring_overrides},
+    storage::set_database_path,
+    Result, WeekRange,
 };
 
 /// Run the CLI.
 #[tokio::main]
 async fn main() -> Result<()> {
     let app = ESPN::parse();
+    if app.no_cache_write {
+        disable_cache_writes();
+    }
+    if app.no_overrides {
+        disable_scoring_overrides();
+    }
+    if app.strict_positions {
+        enable_strict_positions();
+    }
+    if app.legacy_json {
+        enable_legacy_json();
+    }
+    if let Some(path) = app.db_path.clone() {
+        set_database_path(path);
+    }
+    if let Some(width) = app.width {
+        set_output_width(width as usize);
+    }
+    if let Some(threads) = app.max_threads {
+        if let Err(e) = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads as usize)
+            .build_global()
+        {
+            eprintln!("⚠ Warning: Could not limit worker threads: {}", e);
+        }
+    }
+    let timings = app.wants_timings();
+    if timings {
+        enable_metrics();
+    }
+
+    let result = run(app.command).await;
+    {
+        let _stage = stage("cache invalidation");
+        GLOBAL_CACHE.flush_invalidations();
+    }
+    if timings {
+        print_metrics_summary();
+    }
+    result
+}
 
-    match app.command {
+/// Dispatch a parsed command to its handler.
+async fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::LeagueData {
             league_id,
             refresh,
@@ -28,27 +105,61 @@ async fn main() -> Result<()> {
 
         Commands::PlayerData {
             filters,
+            weeks,
             debug,
             json,
+            json_lines,
+            csv,
             projected,
             refresh_positions,
             clear_db,
+            include_notes,
             refresh,
+            sparkline,
+            ascii,
+            both,
+            sort,
+            reverse,
+            show_notes,
+            with_news,
+            top,
+            group_by,
+            explain,
+            status_changed_since,
+            quiet,
         } => {
             let fantasy_team_filter = filters.get_fantasy_team_filter();
+            let positions = filters.get_positions();
             let mut params = PlayerDataParams::new(filters.season, filters.week, projected)
                 .with_optional_league_id(filters.league_id)
                 .with_optional_player_names(filters.player_name)
-                .with_optional_positions(filters.positions)
+                .with_optional_positions(positions)
                 .with_optional_injury_filter(filters.injury_status)
                 .with_optional_roster_filter(filters.roster_status)
                 .with_optional_fantasy_team_filter(fantasy_team_filter)
+                .with_optional_nfl_team(filters.nfl_team)
                 .with_json_output_if(json)
+                .with_json_lines_if(json_lines)
+                .with_csv_if(csv)
                 .with_refresh_if(refresh)
                 .with_debug(debug);
 
             params.refresh_positions = refresh_positions;
             params.clear_db = clear_db;
+            params.include_notes = include_notes;
+            params.sparkline = sparkline;
+            params.ascii = ascii;
+            params.both = both;
+            params.sort = sort;
+            params.reverse = reverse;
+            params.show_notes = show_notes;
+            params.with_news = with_news;
+            params.top = top;
+            params.group_by = group_by;
+            params.explain = explain;
+            params.status_changed_since = status_changed_since;
+            params.weeks = weeks;
+            params.quiet = quiet;
 
             handle_player_data(params).await?
         }
@@ -56,22 +167,67 @@ async fn main() -> Result<()> {
         Commands::ProjectionAnalysis {
             filters,
             json,
+            json_lines,
+            csv,
             refresh,
             bias_strength,
+            bias_decay,
+            group_by,
+            top,
+            sort,
+            include_dnp,
+            blend_recent,
+            show_notes,
+            show_history,
+            include_unprojected,
+            contrarian,
+            tiers,
+            as_of,
+            export_projections,
+            site,
+            max_missing,
         } => {
             // Default to 1.0 (original conservative approach) if not specified
             let bias_factor = bias_strength.unwrap_or(1.0);
             let fantasy_team_filter = filters.get_fantasy_team_filter();
+            let positions = filters.get_positions();
 
-            let params = ProjectionAnalysisParams::new(filters.season, filters.week, bias_factor)
-                .with_optional_league_id(filters.league_id)
-                .with_optional_player_names(filters.player_name)
-                .with_optional_positions(filters.positions)
-                .with_optional_injury_filter(filters.injury_status)
-                .with_optional_roster_filter(filters.roster_status)
-                .with_optional_fantasy_team_filter(fantasy_team_filter)
-                .with_json_output_if(json)
-                .with_refresh_if(refresh);
+            let mut params =
+                ProjectionAnalysisParams::new(filters.season, filters.week, bias_factor)?
+                    .with_optional_league_id(filters.league_id)
+                    .with_optional_player_names(filters.player_name)
+                    .with_optional_positions(positions)
+                    .with_optional_injury_filter(filters.injury_status)
+                    .with_optional_roster_filter(filters.roster_status)
+                    .with_optional_fantasy_team_filter(fantasy_team_filter)
+                    .with_optional_nfl_team(filters.nfl_team)
+                    .with_json_output_if(json)
+                    .with_json_lines_if(json_lines)
+                    .with_csv_if(csv)
+                    .with_refresh_if(refresh);
+            params.group_by = group_by;
+            params.top = top;
+            params.sort = sort;
+            params.include_dnp = include_dnp;
+            params.show_notes = show_notes;
+            params.show_history = show_history;
+            params.include_unprojected = include_unprojected;
+            params.contrarian = contrarian;
+            params.tiers = tiers;
+            params.as_of = as_of;
+            if let Some(weight) = blend_recent {
+                params = params.with_blend_recent(weight)?;
+            }
+            if let Some(decay) = bias_decay {
+                params = params.with_bias_decay(decay)?;
+            }
+            if let Some(path) = export_projections {
+                params.export = Some(ProjectionExport::new(
+                    path,
+                    site,
+                    max_missing.unwrap_or(DEFAULT_MAX_MISSING_PROJECTIONS),
+                )?);
+            }
 
             handle_projection_analysis(params).await?
         }
@@ -79,9 +235,337 @@ async fn main() -> Result<()> {
         Commands::UpdateAllData {
             league_id,
             season,
+            weeks,
             through_week,
             verbose,
-        } => handle_update_all_data(season, through_week, league_id, verbose).await?,
+            fail_fast,
+            yes,
+        } => {
+            // clap requires one of --weeks / --through-week
+            let weeks = weeks
+                .unwrap_or_else(|| through_week.map_or_else(WeekRange::all, WeekRange::through));
+            handle_update_all_data(season, weeks, league_id, verbose, fail_fast, yes).await?
+        }
+
+        Commands::SeasonRecords {
+            league_id,
+            season,
+            json,
+            refresh,
+        } => handle_season_records(league_id, season, refresh, json).await?,
+
+        Commands::MatchupForecast {
+            league_id,
+            season,
+            week,
+            team,
+            team_id,
+            bias_strength,
+            json,
+            refresh,
+            add,
+            remove,
+            as_of,
+        } => {
+            let team = team
+                .map(FantasyTeamFilter::Name)
+                .or(team_id.map(FantasyTeamFilter::Id))
+                .expect("clap requires --team or --team-id");
+            handle_matchup_forecast(MatchupForecastParams {
+                league_id,
+                season,
+                week,
+                team,
+                bias_strength: bias_strength.unwrap_or(1.0),
+                refresh,
+                as_json: json,
+                add,
+                remove,
+                as_of,
+            })
+            .await?
+        }
+
+        Commands::OptimalLineup {
+            league_id,
+            season,
+            week,
+            team,
+            team_id,
+            bias_strength,
+            json,
+            refresh,
+        } => {
+            let team = team
+                .map(FantasyTeamFilter::Name)
+                .or(team_id.map(FantasyTeamFilter::Id))
+                .expect("clap requires --team or --team-id");
+            handle_optimal_lineup(OptimalLineupParams {
+                league_id,
+                season,
+                week,
+                team,
+                bias_strength: bias_strength.unwrap_or(1.0),
+                refresh,
+                as_json: json,
+            })
+            .await?
+        }
+
+        Commands::Compare {
+            league_id,
+            season,
+            week,
+            players,
+            bias_strength,
+            json,
+            refresh,
+        } => {
+            handle_compare(CompareParams {
+                league_id,
+                season,
+                week,
+                players,
+                bias_strength: bias_strength.unwrap_or(1.0),
+                refresh,
+                as_json: json,
+            })
+            .await?
+        }
+
+        Commands::PlayoffOdds {
+            league_id,
+            season,
+            iterations,
+            playoff_teams,
+            seed,
+            json,
+            refresh,
+            share,
+        } => {
+            handle_playoff_odds(PlayoffOddsParams {
+                league_id,
+                season,
+                iterations,
+                playoff_teams,
+                seed,
+                refresh,
+                as_json: json,
+                share,
+            })
+            .await?
+        }
+
+        Commands::WarmCache {
+            league_id,
+            season,
+            check_coverage,
+            json,
+            refresh,
+        } => {
+            handle_warm_cache(WarmCacheParams {
+                league_id,
+                season,
+                refresh,
+                check_coverage,
+                as_json: json,
+            })
+            .await?
+        }
+
+        Commands::Roster {
+            league_id,
+            season,
+            week,
+            team,
+            team_id,
+            json,
+            refresh,
+        } => {
+            let team_filter = team
+                .map(FantasyTeamFilter::Name)
+                .or(team_id.map(FantasyTeamFilter::Id));
+            handle_roster(league_id, season, week, team_filter, refresh, json).await?
+        }
+
+        Commands::RosterDiff {
+            league_id,
+            season,
+            from,
+            to,
+            team,
+            team_id,
+            json,
+            refresh,
+        } => {
+            let team_filter = team
+                .map(FantasyTeamFilter::Name)
+                .or(team_id.map(FantasyTeamFilter::Id));
+            handle_roster_diff(league_id, season, from, to, team_filter, refresh, json).await?
+        }
+
+        Commands::TeamTotals {
+            league_id,
+            season,
+            week,
+            json,
+            refresh,
+            share,
+        } => handle_team_totals(league_id, season, week, refresh, json, share.as_deref()).await?,
+
+        Commands::StreamingRecap {
+            league_id,
+            season,
+            team,
+            team_id,
+            json,
+        } => {
+            let team = team
+                .map(FantasyTeamFilter::Name)
+                .or(team_id.map(FantasyTeamFilter::Id))
+                .expect("clap requires --team or --team-id");
+            handle_streaming_recap(league_id, season, team, json)?
+        }
+
+        Commands::CategoryLeaders {
+            category,
+            league_id,
+            season,
+            week,
+            season_totals: _,
+            positions,
+            roster_status,
+            top,
+            json,
+        } => handle_category_leaders(CategoryLeadersParams {
+            category,
+            league_id,
+            season,
+            week,
+            positions: positions.as_deref().map(PositionSelector::expand),
+            roster_status,
+            top,
+            as_json: json,
+        })?,
+
+        Commands::PositionalRanks {
+            league_id,
+            season,
+            positions,
+            min_games,
+            json,
+        } => handle_positional_ranks(PositionalRanksParams {
+            league_id,
+            season,
+            positions: positions.as_deref().map(PositionSelector::expand),
+            min_games,
+            as_json: json,
+        })?,
+
+        Commands::AccuracyReport {
+            league_id,
+            season,
+            through_week,
+            bias_strength,
+            bias_decay,
+            json,
+        } => handle_accuracy_report(AccuracyReportParams {
+            league_id,
+            season,
+            through_week,
+            bias_strength: bias_strength.unwrap_or(1.0),
+            bias_decay: bias_decay.unwrap_or(1.0),
+            as_json: json,
+        })?,
+
+        Commands::Pace {
+            league_id,
+            season,
+            positions,
+            min_games,
+            top,
+            json,
+        } => handle_pace(PaceParams {
+            league_id,
+            season,
+            positions: positions.as_deref().map(PositionSelector::expand),
+            min_games,
+            top,
+            as_json: json,
+        })?,
+
+        Commands::WeekOverWeek {
+            league_id,
+            season,
+            week,
+            from,
+            positions,
+            roster_status,
+            by,
+            top,
+            json,
+        } => handle_week_over_week(WeekOverWeekParams {
+            league_id,
+            season,
+            from,
+            to: week,
+            positions: positions.as_deref().map(PositionSelector::expand),
+            roster_status,
+            metric: by,
+            top,
+            as_json: json,
+        })?,
+
+        Commands::StatsHistory {
+            player_name,
+            league_id,
+            season,
+            ascii,
+            json,
+        } => handle_stats_history(league_id, &player_name, season, ascii, json)?,
+
+        Commands::Note { command } => match command {
+            NoteCommands::Add { player, text } => handle_note_add(&player, &text)?,
+            NoteCommands::List { player, json } => handle_note_list(player.as_deref(), json)?,
+            NoteCommands::Rm { id } => handle_note_remove(id)?,
+        },
+
+        Commands::Db { command } => match command {
+            DbCommands::Check {
+                league_id,
+                season,
+                current_week,
+                fix,
+                json,
+            } => handle_db_check(league_id, season, current_week, fix, json).await?,
+            DbCommands::Coverage {
+                league_id,
+                season,
+                weeks,
+                partial_below,
+                json,
+            } => handle_db_coverage(league_id, season, weeks, partial_below, json)?,
+            DbCommands::Export {
+                league_id,
+                season,
+                format,
+                output,
+            } => handle_db_export(league_id, season, format, output.as_deref())?,
+            DbCommands::TeamNames {
+                league_id,
+                season,
+                team_id,
+                json,
+            } => handle_db_team_names(league_id, season, team_id, json)?,
+            DbCommands::Aliases { player, json } => handle_db_aliases(player.as_deref(), json)?,
+            DbCommands::Dedupe { dry_run, json } => handle_db_dedupe(dry_run, json)?,
+        },
+        Commands::Auth { command } => match command {
+            AuthCommands::Set => handle_auth_set()?,
+            AuthCommands::Status { league_id, season } => {
+                handle_auth_status(league_id, season).await?
+            }
+        },
     }
 
     Ok(())
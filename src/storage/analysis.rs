@@ -1,18 +1,595 @@
 //! Analysis operations for projection accuracy and performance estimation
 
 use super::{models::*, schema::PlayerDatabase};
-use crate::{PlayerId, Season, Week};
+use crate::{
+    cli::types::{
+        filters::{RosterStatusFilter, WeekOverWeekMetric},
+        position::FlexEligibility,
+    },
+    espn::{
+        lineup::{DEFAULT_STD_FRACTION, FLOOR_CEILING_Z},
+        types::InjuryStatus,
+    },
+    LeagueId, PlayerId, Position, Season, Week,
+};
 use anyhow::Result;
-use rusqlite::params;
+use rusqlite::{params, OptionalExtension};
+use std::collections::{BTreeMap, HashMap};
+
+/// Fewest games with projection errors before their spread is treated as meaningful
+const MIN_GAMES_FOR_ERROR_STD: u32 = 3;
+
+/// Adjusted projections are capped at this multiple of the position's best stored week
+pub const POSITION_CEILING_FACTOR: f64 = 1.25;
+
+/// Number of most recent actual games in a recent-form estimate
+pub const RECENT_FORM_GAMES: u32 = 4;
+
+/// Confidence given to estimates for players ESPN didn't project at all
+pub const UNPROJECTED_CONFIDENCE: f64 = 0.15;
+
+/// Display order for position groups; anything else sorts alphabetically after these
+pub(crate) const POSITION_GROUP_ORDER: [&str; 6] = ["QB", "RB", "WR", "TE", "K", "D/ST"];
+
+/// Weekly positional finishes counted as top-tier ("WR1") and second-tier ("WR2")
+const TOP_TIER_RANK: u32 = 12;
+const SECOND_TIER_RANK: u32 = 24;
+
+/// Games each NFL team plays in a regular season (18 weeks, one bye)
+pub const SEASON_GAMES: u32 = 17;
+
+/// Full-season points at the current per-game rate: points so far plus the
+/// per-game average over the games left. Without a game played, just `points`.
+pub fn extrapolate_pace(points: f64, games: u32, remaining_games: u32) -> f64 {
+    if games == 0 {
+        return points;
+    }
+    points + points / games as f64 * remaining_games as f64
+}
+
+/// Team games left after `weeks_elapsed` weeks of the season
+///
+/// Every team has one bye, so once it has passed one fewer of the elapsed
+/// weeks was a game.
+pub fn remaining_team_games(weeks_elapsed: u16, bye_passed: bool) -> u32 {
+    let games_elapsed = u32::from(weeks_elapsed).saturating_sub(u32::from(bye_passed));
+    SEASON_GAMES.saturating_sub(games_elapsed)
+}
+
+/// Recency-weighted average of actual points, ordered oldest to newest.
+///
+/// Weights rise linearly with recency (1, 2, ..., n), so the latest game counts n
+/// times as much as the oldest. `None` without any games.
+pub fn recent_form_estimate(actuals: &[f64]) -> Option<f64> {
+    if actuals.is_empty() {
+        return None;
+    }
+    let (weighted, total_weight) =
+        actuals
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(sum, weights), (i, points)| {
+                let weight = (i + 1) as f64;
+                (sum + weight * points, weights + weight)
+            });
+    Some(weighted / total_weight)
+}
+
+/// Blend a bias-adjusted projection with a recent-form estimate
+///
+/// `weight` is the share given to recent form: 0 keeps the adjusted projection,
+/// 1 uses recent form alone.
+pub fn blend_with_recent_form(adjusted_projection: f64, recent_form: f64, weight: f64) -> f64 {
+    (1.0 - weight) * adjusted_projection + weight * recent_form
+}
+
+/// Sort estimates by estimated points, highest first
+fn sort_by_estimate(estimates: &mut [PerformanceEstimate]) {
+    estimates.sort_by(|a, b| {
+        b.estimated_points
+            .partial_cmp(&a.estimated_points)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Section estimates by position, ranking players within each group
+///
+/// `estimates` must already be sorted the way the flat view is; that order is kept
+/// inside each group so positional ranks use the same tie-breaking. `starters` maps a
+/// position to the number of starting spots across the league: the replacement level is
+/// the estimate of the first player past that cutoff (or the last player if the group
+/// is smaller).
+pub fn group_estimates_by_position(
+    estimates: Vec<PerformanceEstimate>,
+    starters: &HashMap<String, u32>,
+) -> Vec<PositionGroup> {
+    let mut grouped: Vec<(String, Vec<PerformanceEstimate>)> = Vec::new();
+    for estimate in estimates {
+        match grouped
+            .iter_mut()
+            .find(|(pos, _)| *pos == estimate.position)
+        {
+            Some((_, players)) => players.push(estimate),
+            None => grouped.push((estimate.position.clone(), vec![estimate])),
+        }
+    }
+
+    grouped.sort_by_key(|(position, _)| {
+        let rank = POSITION_GROUP_ORDER
+            .iter()
+            .position(|p| p == position)
+            .unwrap_or(POSITION_GROUP_ORDER.len());
+        (rank, position.clone())
+    });
+
+    grouped
+        .into_iter()
+        .map(|(position, players)| {
+            let average_estimate =
+                players.iter().map(|e| e.estimated_points).sum::<f64>() / players.len() as f64;
+
+            let cutoff = starters
+                .get(&position)
+                .map(|&count| count as usize)
+                .unwrap_or(players.len())
+                .min(players.len() - 1);
+            let replacement_level = players[cutoff].estimated_points;
+
+            let players = players
+                .into_iter()
+                .enumerate()
+                .map(|(i, estimate)| RankedEstimate {
+                    positional_rank: i as u32 + 1,
+                    points_above_replacement: estimate.estimated_points - replacement_level,
+                    estimate,
+                })
+                .collect();
+
+            PositionGroup {
+                position,
+                average_estimate,
+                replacement_level,
+                players,
+            }
+        })
+        .collect()
+}
+
+/// How past projection errors become a bias adjustment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BiasWeighting {
+    /// 0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified
+    pub strength: f64,
+    /// Each week's error is weighted by `decay^weeks_ago`; 1.0 weighs every week equally
+    pub decay: f64,
+}
+
+impl BiasWeighting {
+    /// Weight every prior week equally at the given strength
+    pub fn new(strength: f64) -> Self {
+        Self {
+            strength,
+            decay: 1.0,
+        }
+    }
+}
+
+/// Decay-weighted summary of a player's projection errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightedBias {
+    /// Weighted mean error; positive when ESPN overestimated
+    pub mean: f64,
+    /// Weighted sample standard deviation of the errors; 0.0 for a single week
+    pub std: f64,
+    /// Kish effective sample size, `(Σw)² / Σw²`; the week count when unweighted
+    pub effective_games: f64,
+}
+
+/// Average projection errors (`(week, error)` pairs) for `target_week`, weighting
+/// each by `decay^weeks_ago`
+///
+/// The spread uses reliability weights, `Σw(x - mean)² / (Σw - Σw²/Σw)`, so a decay
+/// of 1.0 gives the plain mean and sample standard deviation. `None` without errors.
+pub fn weighted_bias(
+    errors: &[(Week, f64)],
+    target_week: Week,
+    decay: f64,
+) -> Option<WeightedBias> {
+    if errors.is_empty() {
+        return None;
+    }
+    let weights: Vec<f64> = errors
+        .iter()
+        .map(|(week, _)| {
+            let weeks_ago = target_week.as_u16().saturating_sub(week.as_u16());
+            decay.powi(i32::from(weeks_ago))
+        })
+        .collect();
+    let total: f64 = weights.iter().sum();
+    let total_squared: f64 = weights.iter().map(|w| w * w).sum();
+    let mean = errors
+        .iter()
+        .zip(&weights)
+        .map(|((_, error), w)| w * error)
+        .sum::<f64>()
+        / total;
+
+    let std = if errors.len() > 1 {
+        let spread: f64 = errors
+            .iter()
+            .zip(&weights)
+            .map(|((_, error), w)| w * (error - mean).powi(2))
+            .sum();
+        (spread / (total - total_squared / total)).sqrt()
+    } else {
+        0.0
+    };
+
+    Some(WeightedBias {
+        mean,
+        std,
+        effective_games: total * total / total_squared,
+    })
+}
+
+/// Points to add to an ESPN projection to correct for a player's past bias
+///
+/// The correction shrinks toward 0 with few (effective) games and is scaled down for
+/// average biases over 10 points. A projection of 0 (bye, or not expected to play)
+/// is never adjusted.
+pub fn bias_adjustment(projection: f64, bias: &WeightedBias, strength: f64) -> f64 {
+    if projection == 0.0 {
+        return 0.0;
+    }
+    // Simple bias adjustment - trust player-specific patterns
+    let sample_factor = bias.effective_games / (bias.effective_games + 2.0);
+
+    // Only limit extreme biases
+    let bias_magnitude = bias.mean.abs();
+    let magnitude_factor = if bias_magnitude > 10.0 {
+        10.0 / bias_magnitude
+    } else {
+        1.0
+    };
+
+    -bias.mean * sample_factor * magnitude_factor * strength
+}
+
+/// Cutoffs for [`start_tier`], as multiples of the position's replacement level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TierThresholds {
+    /// Estimate a must start needs
+    pub must_start: f64,
+    /// Floor (10th percentile) a must start also needs, so boom-or-bust players drop a tier
+    pub must_start_floor: f64,
+    /// Start rate (percent) a must start needs when ESPN supplies one
+    pub must_start_min_started: Option<f64>,
+    /// Estimate a solid start needs
+    pub solid_start: f64,
+    /// Estimate worth a flex spot; anything lower is a sit
+    pub flex: f64,
+}
+
+impl Default for TierThresholds {
+    fn default() -> Self {
+        Self {
+            must_start: 1.3,
+            must_start_floor: 0.6,
+            must_start_min_started: Some(50.0),
+            solid_start: 1.1,
+            flex: 0.9,
+        }
+    }
+}
+
+/// 10th percentile outcome for an estimate, never below zero
+///
+/// Uses the spread of the player's past projection errors, or a fixed share of the
+/// estimate without enough history, as the matchup forecast does.
+pub fn estimate_floor(estimate: &PerformanceEstimate) -> f64 {
+    let std_dev = estimate
+        .error_std
+        .unwrap_or(DEFAULT_STD_FRACTION * estimate.estimated_points.max(0.0));
+    (estimate.estimated_points - FLOOR_CEILING_Z * std_dev).max(0.0)
+}
+
+/// Estimate of the first player past each position's league-wide starter pool
+///
+/// Same cutoff as [`group_estimates_by_position`], but over players ordered by
+/// estimate, so it can be taken before any display filters are applied.
+pub fn replacement_levels(
+    estimates: &[PerformanceEstimate],
+    starters: &HashMap<String, u32>,
+) -> HashMap<String, f64> {
+    let mut by_position: HashMap<&str, Vec<f64>> = HashMap::new();
+    for estimate in estimates {
+        by_position
+            .entry(estimate.position.as_str())
+            .or_default()
+            .push(estimate.estimated_points);
+    }
+    by_position
+        .into_iter()
+        .map(|(position, mut points)| {
+            points.sort_by(|a, b| b.total_cmp(a));
+            let cutoff = starters
+                .get(position)
+                .map(|&count| count as usize)
+                .unwrap_or(points.len())
+                .min(points.len() - 1);
+            (position.to_string(), points[cutoff])
+        })
+        .collect()
+}
+
+/// Tier for one estimate against its position's replacement level
+pub fn start_tier(
+    estimate: &PerformanceEstimate,
+    replacement_level: f64,
+    thresholds: &TierThresholds,
+) -> StartTier {
+    let points = estimate.estimated_points;
+    let crowd_agrees = match (thresholds.must_start_min_started, estimate.percent_started) {
+        (Some(min), Some(started)) => started >= min,
+        _ => true,
+    };
+    if points >= replacement_level * thresholds.must_start
+        && estimate_floor(estimate) >= replacement_level * thresholds.must_start_floor
+        && crowd_agrees
+    {
+        StartTier::MustStart
+    } else if points >= replacement_level * thresholds.solid_start {
+        StartTier::SolidStart
+    } else if points >= replacement_level * thresholds.flex {
+        StartTier::FlexConsideration
+    } else {
+        StartTier::Sit
+    }
+}
+
+/// Set each estimate's tier; positions without a replacement level are left untiered
+pub fn assign_start_tiers(
+    estimates: &mut [PerformanceEstimate],
+    replacement_levels: &HashMap<String, f64>,
+    thresholds: &TierThresholds,
+) {
+    for estimate in estimates {
+        estimate.tier = replacement_levels
+            .get(&estimate.position)
+            .map(|&level| start_tier(estimate, level, thresholds));
+    }
+}
+
+/// Set each estimate's value over its position's replacement level
+///
+/// Positions without a replacement level are left without one.
+pub fn assign_vorp(
+    estimates: &mut [PerformanceEstimate],
+    replacement_levels: &HashMap<String, f64>,
+) {
+    for estimate in estimates {
+        estimate.vorp = replacement_levels
+            .get(&estimate.position)
+            .map(|&level| estimate.estimated_points - level);
+    }
+}
+
+/// Sort estimates by value over replacement, highest first; estimates without one go
+/// last, by estimated points
+pub fn sort_by_vorp(estimates: &mut [PerformanceEstimate]) {
+    estimates.sort_by(|a, b| match (a.vorp, b.vorp) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => b.estimated_points.total_cmp(&a.estimated_points),
+    });
+}
+
+/// Positional ranks by which the adjusted estimate must beat the start rate for `--contrarian`
+pub const CONTRARIAN_RANK_GAP: u32 = 10;
+
+/// Keep players the adjusted estimate ranks well above where managers' start rates put them
+///
+/// Ranks are within each position, counting only players with a start rate: a
+/// player is kept when their rank by start rate is at least `min_rank_gap` places
+/// worse than their rank by estimated points. The input order is kept.
+pub fn contrarian_estimates(
+    estimates: Vec<PerformanceEstimate>,
+    min_rank_gap: u32,
+) -> Vec<PerformanceEstimate> {
+    let rank_within_position = |key: fn(&PerformanceEstimate) -> f64| {
+        let mut ordered: Vec<&PerformanceEstimate> = estimates
+            .iter()
+            .filter(|e| e.percent_started.is_some())
+            .collect();
+        ordered.sort_by(|a, b| {
+            key(b)
+                .partial_cmp(&key(a))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        ordered
+            .into_iter()
+            .map(|e| {
+                let rank = counts.entry(e.position.as_str()).or_insert(0);
+                *rank += 1;
+                (e.player_id, *rank)
+            })
+            .collect::<HashMap<PlayerId, u32>>()
+    };
+    let estimate_ranks = rank_within_position(|e| e.estimated_points);
+    let start_ranks = rank_within_position(|e| e.percent_started.unwrap_or(0.0));
+
+    let is_contrarian = |estimate: &PerformanceEstimate| match (
+        estimate_ranks.get(&estimate.player_id),
+        start_ranks.get(&estimate.player_id),
+    ) {
+        (Some(&by_estimate), Some(&by_starts)) => {
+            by_starts >= by_estimate.saturating_add(min_rank_gap)
+        }
+        _ => false,
+    };
+    let keep: Vec<bool> = estimates.iter().map(is_contrarian).collect();
+    estimates
+        .into_iter()
+        .zip(keep)
+        .filter_map(|(estimate, keep)| keep.then_some(estimate))
+        .collect()
+}
+
+/// Compare two roster snapshots team by team
+///
+/// A player is added or dropped when their (team, player) pair appears in only one
+/// snapshot, so trades show up as a drop on one team and an add on the other. Moves
+/// are only reported when the slot group (starter/bench/IR) changes. Every team in
+/// either snapshot gets an entry, ordered by team ID; points are left empty.
+pub fn diff_rosters(
+    before: &[RosterSnapshotEntry],
+    after: &[RosterSnapshotEntry],
+) -> Vec<TeamRosterDiff> {
+    let change = |entry: &RosterSnapshotEntry, from_slot, to_slot| RosterChange {
+        player_id: entry.player_id,
+        player_name: entry.player_name.clone(),
+        position: entry.position.clone(),
+        from_slot,
+        to_slot,
+        weekly_points: Vec::new(),
+        total_points: 0.0,
+    };
+
+    let mut teams: BTreeMap<u32, TeamRosterDiff> = BTreeMap::new();
+    for entry in before.iter().chain(after) {
+        let team = teams
+            .entry(entry.team_id)
+            .or_insert_with(|| TeamRosterDiff {
+                team_id: entry.team_id,
+                team_name: None,
+                added: Vec::new(),
+                dropped: Vec::new(),
+                moved: Vec::new(),
+            });
+        // Prefer the later week's name in case the team was renamed
+        if entry.team_name.is_some() {
+            team.team_name = entry.team_name.clone();
+        }
+    }
+
+    let before_slots: HashMap<(u32, PlayerId), SlotGroup> = before
+        .iter()
+        .map(|e| {
+            (
+                (e.team_id, e.player_id),
+                SlotGroup::from_lineup_slot(e.lineup_slot_id),
+            )
+        })
+        .collect();
+    let after_slots: HashMap<(u32, PlayerId), SlotGroup> = after
+        .iter()
+        .map(|e| {
+            (
+                (e.team_id, e.player_id),
+                SlotGroup::from_lineup_slot(e.lineup_slot_id),
+            )
+        })
+        .collect();
+
+    for entry in after {
+        let to_slot = after_slots[&(entry.team_id, entry.player_id)];
+        let team = teams
+            .get_mut(&entry.team_id)
+            .expect("team registered above");
+        match before_slots.get(&(entry.team_id, entry.player_id)) {
+            None => team.added.push(change(entry, None, Some(to_slot))),
+            Some(&from_slot) if from_slot != to_slot => {
+                team.moved
+                    .push(change(entry, Some(from_slot), Some(to_slot)))
+            }
+            Some(_) => {}
+        }
+    }
+
+    for entry in before {
+        if !after_slots.contains_key(&(entry.team_id, entry.player_id)) {
+            let from_slot = before_slots[&(entry.team_id, entry.player_id)];
+            let team = teams
+                .get_mut(&entry.team_id)
+                .expect("team registered above");
+            team.dropped.push(change(entry, Some(from_slot), None));
+        }
+    }
+
+    teams.into_values().collect()
+}
+
+/// The change `metric` ranks by, when both weeks have it
+pub fn week_over_week_delta(
+    change: &WeekOverWeekChange,
+    metric: WeekOverWeekMetric,
+) -> Option<f64> {
+    match metric {
+        WeekOverWeekMetric::Actual => change.actual_delta,
+        WeekOverWeekMetric::Projected => change.projected_delta,
+        WeekOverWeekMetric::Owned => change.owned_delta,
+    }
+}
+
+/// Split week-over-week changes into risers and fallers by `metric`
+///
+/// Risers are the `top` biggest increases, fallers the `top` biggest drops, each
+/// biggest first; players without the metric in both weeks, or with no change,
+/// are in neither. Ties go to the lower player ID.
+pub fn risers_and_fallers(
+    changes: &[WeekOverWeekChange],
+    metric: WeekOverWeekMetric,
+    top: usize,
+) -> (Vec<&WeekOverWeekChange>, Vec<&WeekOverWeekChange>) {
+    let mut ranked: Vec<(&WeekOverWeekChange, f64)> = changes
+        .iter()
+        .filter_map(|change| Some((change, week_over_week_delta(change, metric)?)))
+        .filter(|(_, delta)| *delta != 0.0)
+        .collect();
+    ranked.sort_by(|(a, a_delta), (b, b_delta)| {
+        b_delta
+            .total_cmp(a_delta)
+            .then(a.player_id.as_i64().cmp(&b.player_id.as_i64()))
+    });
+
+    let risers = ranked
+        .iter()
+        .filter(|(_, delta)| *delta > 0.0)
+        .take(top)
+        .map(|(change, _)| *change)
+        .collect();
+    let mut fallers: Vec<(&WeekOverWeekChange, f64)> = ranked
+        .into_iter()
+        .filter(|(_, delta)| *delta < 0.0)
+        .collect();
+    fallers.sort_by(|(a, a_delta), (b, b_delta)| {
+        a_delta
+            .total_cmp(b_delta)
+            .then(a.player_id.as_i64().cmp(&b.player_id.as_i64()))
+    });
+    let fallers = fallers
+        .into_iter()
+        .take(top)
+        .map(|(change, _)| change)
+        .collect();
+    (risers, fallers)
+}
 
 impl PlayerDatabase {
     /// Get players with the biggest projection errors (over/under estimated)
+    ///
+    /// Weeks marked as "did not play" are skipped unless `include_dnp` is set.
     pub fn get_projection_analysis(
         &self,
+        league_id: LeagueId,
         season: Season,
         week: Option<Week>,
         limit: Option<u32>,
+        include_dnp: bool,
     ) -> Result<Vec<ProjectionAnalysis>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
         let mut query = String::from(
             "SELECT p.name, p.position, p.team,
                     AVG(s.projected_points - s.actual_points) as avg_error,
@@ -20,15 +597,22 @@ impl PlayerDatabase {
              FROM players p
              JOIN player_weekly_stats s ON p.player_id = s.player_id
              WHERE s.season = ?
+               AND s.league_id = ?
                AND s.projected_points IS NOT NULL
                AND s.actual_points IS NOT NULL",
         );
 
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(season.as_u16())];
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> =
+            vec![Box::new(season.as_u16()), Box::new(league_id.as_u32())];
+
+        if !include_dnp {
+            query.push_str(" AND (s.played IS NULL OR s.played = 1)");
+        }
 
         if let Some(w) = week {
-            query.push_str(" AND s.week < ?");
-            params.push(Box::new(w.as_u16()));
+            // Only weeks before `w`; week 1 has no history (no week 0)
+            query.push_str(" AND s.week <= ?");
+            params.push(Box::new(w.prev().map_or(0, |prev| prev.as_u16())));
         }
 
         query.push_str(" GROUP BY p.player_id, p.name, p.position, p.team ORDER BY avg_error DESC");
@@ -59,20 +643,24 @@ impl PlayerDatabase {
     }
 
     /// Estimate performance for a specific week based on ESPN projections and historical bias
+    ///
+    /// Weeks marked as "did not play" are left out of the bias history unless `include_dnp` is set.
     pub fn estimate_week_performance(
         &self,
+        league_id: LeagueId,
         season: Season,
         target_week: Week,
         projected_points_data: &[(PlayerId, f64)], // ESPN projections for target week
-        limit: Option<u32>,
-        bias_strength: f64, // 0.0 = no adjustment, 1.0 = full bias correction, >1.0 = amplified
+        bias: BiasWeighting,
+        include_dnp: bool,
     ) -> Result<Vec<PerformanceEstimate>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
         let mut estimates = Vec::new();
+        // History runs through the week before the target; week 1 has none
+        let history_through = target_week.prev().map_or(0, |w| w.as_u16());
+        let position_max = self.get_position_max_points(league_id)?;
 
-        for (player_id, espn_projection) in projected_points_data
-            .iter()
-            .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
-        {
+        for (player_id, espn_projection) in projected_points_data {
             // Get player info first
             let mut player_stmt = self
                 .conn
@@ -95,105 +683,86 @@ impl PlayerDatabase {
                 Err(e) => return Err(e.into()),
             };
 
-            // Get individual bias values for this player
-            // Include all weeks with both projected and actual data
-            let mut bias_stmt = self.conn.prepare(
-                "SELECT s.projected_points, s.actual_points, (s.projected_points - s.actual_points) as bias
-                 FROM player_weekly_stats s
-                 WHERE s.player_id = ?
-                   AND s.season = ?
-                   AND s.week < ?
-                   AND s.projected_points IS NOT NULL
-                   AND s.actual_points IS NOT NULL",
-            )?;
-
-            let bias_rows = bias_stmt.query_map(
-                params![player_id.as_i64(), season.as_u16(), target_week.as_u16()],
-                |row| {
-                    Ok((
-                        row.get::<_, f64>(0)?, // projected_points
-                        row.get::<_, f64>(1)?, // actual_points
-                        row.get::<_, f64>(2)?, // bias
-                    ))
-                },
-            )?;
-
-            let mut bias_values = Vec::new();
-            for bias_result in bias_rows {
-                let (projected, actual, bias) = bias_result?;
-                // Skip weeks where both projected and actual are zero (BYE weeks, didn't play)
-                if projected == 0.0 && actual == 0.0 {
-                    continue;
-                }
-                bias_values.push(bias);
-            }
+            let history =
+                self.get_bias_history(league_id, *player_id, season, history_through, include_dnp)?;
+            let errors: Vec<(Week, f64)> = history
+                .iter()
+                .filter(|week| week.excluded.is_none())
+                .map(|week| (week.week, week.error))
+                .collect();
+            let dnp_weeks = history
+                .iter()
+                .filter(|week| {
+                    matches!(
+                        week.excluded,
+                        Some(HistoryExclusion::Out | HistoryExclusion::Dnp)
+                    )
+                })
+                .count() as u32;
 
-            let games_count = bias_values.len() as u32;
-            if games_count == 0 {
+            let games_count = errors.len() as u32;
+            let Some(weighted) = weighted_bias(&errors, target_week, bias.decay) else {
                 // No historical data, skip to fallback
                 continue;
-            }
-
-            // Simple approach: Calculate player's average bias (no recency weighting)
-            let average_bias = bias_values.iter().sum::<f64>() / bias_values.len() as f64;
+            };
+            let average_bias = weighted.mean;
 
             // Start with ESPN's projection
             let base_projection = *espn_projection;
 
-            // If ESPN projects 0 points, don't adjust - player is likely not playing or on bye
-            let (bias_adjustment, estimated_points) = if base_projection == 0.0 {
-                (0.0, 0.0)
-            } else {
-                // Simple bias adjustment - trust player-specific patterns
-                let sample_factor = games_count as f64 / (games_count as f64 + 2.0);
+            let bias_adjustment = bias_adjustment(base_projection, &weighted, bias.strength);
+            let estimated_points = base_projection + bias_adjustment;
 
-                // Only limit extreme biases
-                let bias_magnitude = average_bias.abs();
-                let magnitude_factor = if bias_magnitude > 10.0 {
-                    10.0 / bias_magnitude
-                } else {
-                    1.0
-                };
-
-                let adjustment_strength = sample_factor * magnitude_factor;
-                let bias_adjustment = -average_bias * adjustment_strength * bias_strength;
-                let estimated_points = (base_projection + bias_adjustment).max(0.0);
-                (bias_adjustment, estimated_points)
+            // Keep runaway adjustments within what the position has actually scored,
+            // but never cap below ESPN's own projection
+            let ceiling = position_max
+                .get(&position)
+                .map(|max| (max * POSITION_CEILING_FACTOR).max(base_projection));
+            let clamp_note = match ceiling {
+                Some(ceiling) if estimated_points > ceiling => Some(format!(
+                    " - capped at {:.1} pts ({:.0}% of the {} single-week high)",
+                    ceiling,
+                    POSITION_CEILING_FACTOR * 100.0,
+                    position
+                )),
+                _ if estimated_points < 0.0 => Some(" - floored at 0 pts".to_string()),
+                _ => None,
             };
+            let estimated_points = estimated_points
+                .min(ceiling.unwrap_or(f64::INFINITY))
+                .max(0.0);
 
             // Confidence based on pattern consistency
-            let bias_variance = if bias_values.len() > 1 {
-                bias_values
-                    .iter()
-                    .map(|&x| (x - average_bias).powi(2))
-                    .sum::<f64>()
-                    / (bias_values.len() - 1) as f64
-            } else {
-                0.0
-            };
-
-            let bias_std = bias_variance.sqrt();
+            let bias_std = weighted.std;
             let consistency_factor = 1.0 / (1.0 + bias_std / 3.0); // Higher std = lower confidence
             let confidence = (0.3 + 0.5 * consistency_factor).clamp(0.25, 0.85);
 
-            // Generate simple reasoning
+            // Generate simple reasoning; a decayed history also reports its effective size
+            let games = if bias.decay < 1.0 {
+                format!(
+                    "{} games, {:.1} effective",
+                    games_count, weighted.effective_games
+                )
+            } else {
+                format!("{} games", games_count)
+            };
             let reasoning = if base_projection == 0.0 {
                 "ESPN projects 0 pts - player not expected to play or on bye week".to_string()
             } else if bias_adjustment.abs() > 1.0 {
                 if average_bias > 0.0 {
                     format!(
-                        "Avg bias: ESPN overestimates by {:.1} pts ({} games, {:.1} std) - adjusted down {:.1} pts ({}% confidence)",
+                        "Avg bias: ESPN overestimates by {:.1} pts ({}, {:.1} std) - adjusted down {:.1} pts ({}% confidence)",
                         average_bias,
-                        games_count,
+                        games,
                         bias_std,
                         bias_adjustment.abs(),
                         (confidence * 100.0) as u8
                     )
                 } else {
                     format!(
-                        "Avg bias: ESPN underestimates by {:.1} pts ({} games, {:.1} std) - adjusted up {:.1} pts ({}% confidence)",
+                        "Avg bias: ESPN underestimates by {:.1} pts ({}, {:.1} std) - adjusted up {:.1} pts ({}% confidence)",
                         average_bias.abs(),
-                        games_count,
+                        games,
                         bias_std,
                         bias_adjustment,
                         (confidence * 100.0) as u8
@@ -201,12 +770,21 @@ impl PlayerDatabase {
                 }
             } else {
                 format!(
-                    "ESPN projection {:.1} pts - minimal bias detected ({} games, {}% confidence)",
+                    "ESPN projection {:.1} pts - minimal bias detected ({}, {}% confidence)",
                     base_projection,
-                    games_count,
+                    games,
                     (confidence * 100.0) as u8
                 )
             };
+            let reasoning = match clamp_note {
+                Some(note) => reasoning + &note,
+                None => reasoning,
+            };
+            let reasoning = match dnp_weeks {
+                0 => reasoning,
+                1 => reasoning + " - skipped 1 DNP week",
+                n => format!("{} - skipped {} DNP weeks", reasoning, n),
+            };
 
             estimates.push(PerformanceEstimate {
                 player_id: *player_id,
@@ -218,14 +796,19 @@ impl PlayerDatabase {
                 estimated_points,
                 confidence,
                 reasoning,
+                error_std: (games_count >= MIN_GAMES_FOR_ERROR_STD).then_some(bias_std),
+                dnp_weeks: (dnp_weeks > 0).then_some(dnp_weeks),
+                blend: None,
+                history: None,
+                percent_started: None,
+                tier: None,
+                locked: None,
+                vorp: None,
             });
         }
 
         // Add fallback for players not found in database but in ESPN data
-        for (player_id, espn_projection) in projected_points_data
-            .iter()
-            .take(limit.map(|l| l as usize).unwrap_or(usize::MAX))
-        {
+        for (player_id, espn_projection) in projected_points_data {
             // Check if we already processed this player
             if estimates.iter().any(|e| e.player_id == *player_id) {
                 continue;
@@ -256,16 +839,1531 @@ impl PlayerDatabase {
                 estimated_points: *espn_projection,
                 confidence: 0.3,
                 reasoning: "No historical data - using ESPN projection".to_string(),
+                error_std: None,
+                dnp_weeks: None,
+                blend: None,
+                history: None,
+                percent_started: None,
+                tier: None,
+                locked: None,
+                vorp: None,
             });
         }
 
         // Sort by estimated points descending
-        estimates.sort_by(|a, b| {
-            b.estimated_points
-                .partial_cmp(&a.estimated_points)
+        sort_by_estimate(&mut estimates);
+
+        Ok(estimates)
+    }
+
+    /// Last `games` actual scores before `before_week`, oldest first
+    ///
+    /// Weeks the player did not play are skipped unless `include_dnp` is set.
+    pub fn get_recent_actuals(
+        &self,
+        league_id: LeagueId,
+        player_id: PlayerId,
+        season: Season,
+        before_week: Week,
+        games: u32,
+        include_dnp: bool,
+    ) -> Result<Vec<f64>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT actual_points FROM player_weekly_stats
+             WHERE player_id = ? AND season = ? AND week < ? AND league_id = ?
+               AND actual_points IS NOT NULL
+               AND (? OR played IS NULL OR played = 1)
+             ORDER BY week DESC
+             LIMIT ?",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                player_id.as_i64(),
+                season.as_u16(),
+                before_week.as_u16(),
+                league_id.as_u32(),
+                include_dnp,
+                games
+            ],
+            |row| row.get::<_, f64>(0),
+        )?;
+
+        let mut actuals = Vec::new();
+        for row in rows {
+            actuals.push(row?);
+        }
+        actuals.reverse();
+        Ok(actuals)
+    }
+
+    /// A player's weeks with both projected and actual points, through `through_week`
+    ///
+    /// Bye weeks (both 0) are marked excluded, as are weeks the player didn't play
+    /// unless `include_dnp` is set; the rest are the errors behind the bias average.
+    pub fn get_bias_history(
+        &self,
+        league_id: LeagueId,
+        player_id: PlayerId,
+        season: Season,
+        through_week: u16,
+        include_dnp: bool,
+    ) -> Result<Vec<BiasHistoryWeek>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT week, projected_points, actual_points, played, injury_status
+             FROM player_weekly_stats
+             WHERE player_id = ?
+               AND season = ?
+               AND week <= ?
+               AND league_id = ?
+               AND projected_points IS NOT NULL
+               AND actual_points IS NOT NULL
+             ORDER BY week",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                player_id.as_i64(),
+                season.as_u16(),
+                through_week,
+                league_id.as_u32()
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, u16>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, f64>(2)?,
+                    row.get::<_, Option<bool>>(3)?,
+                    row.get::<_, Option<String>>(4)?,
+                ))
+            },
+        )?;
+
+        let mut history = Vec::new();
+        for row in rows {
+            let (week, projected, actual, played, injury_status) = row?;
+            let ruled_out = matches!(
+                injury_status.as_deref().map(InjuryStatus::parse_or_unknown),
+                Some(InjuryStatus::Out | InjuryStatus::InjuryReserve)
+            );
+            let excluded = if projected == 0.0 && actual == 0.0 {
+                Some(HistoryExclusion::Bye)
+            } else if played == Some(false) && !include_dnp {
+                Some(if ruled_out {
+                    HistoryExclusion::Out
+                } else {
+                    HistoryExclusion::Dnp
+                })
+            } else {
+                None
+            };
+            history.push(BiasHistoryWeek {
+                week: Week::new(week),
+                projected,
+                actual,
+                error: projected - actual,
+                excluded,
+            });
+        }
+        Ok(history)
+    }
+
+    /// Attach each estimate's bias history (`--show-history`)
+    pub fn attach_bias_history(
+        &self,
+        league_id: LeagueId,
+        estimates: &mut [PerformanceEstimate],
+        season: Season,
+        target_week: Week,
+        include_dnp: bool,
+    ) -> Result<()> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let through = target_week.prev().map_or(0, |w| w.as_u16());
+        for estimate in estimates.iter_mut() {
+            estimate.history = Some(self.get_bias_history(
+                league_id,
+                estimate.player_id,
+                season,
+                through,
+                include_dnp,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Blend each estimate with the player's recent form (`--blend-recent`)
+    ///
+    /// Players ESPN projects at 0 (bye, ruled out) and players with no recent games
+    /// keep their estimate. The blend is recorded on the estimate and in its reasoning,
+    /// and the estimates are re-sorted.
+    pub fn apply_recent_form_blend(
+        &self,
+        league_id: LeagueId,
+        estimates: &mut [PerformanceEstimate],
+        season: Season,
+        target_week: Week,
+        weight: f64,
+        include_dnp: bool,
+    ) -> Result<()> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        for estimate in estimates.iter_mut() {
+            if estimate.espn_projection == 0.0 {
+                continue;
+            }
+            let actuals = self.get_recent_actuals(
+                league_id,
+                estimate.player_id,
+                season,
+                target_week,
+                RECENT_FORM_GAMES,
+                include_dnp,
+            )?;
+            let Some(recent_form) = recent_form_estimate(&actuals) else {
+                continue;
+            };
+
+            let adjusted_projection = estimate.estimated_points;
+            estimate.estimated_points =
+                blend_with_recent_form(adjusted_projection, recent_form, weight);
+            estimate.reasoning.push_str(&format!(
+                " - blended {:.0}% with recent form {:.1} pts (last {} games) from {:.1} pts",
+                weight * 100.0,
+                recent_form,
+                actuals.len(),
+                adjusted_projection
+            ));
+            estimate.blend = Some(RecentFormBlend {
+                weight,
+                adjusted_projection,
+                recent_form,
+                games: actuals.len() as u32,
+            });
+        }
+
+        sort_by_estimate(estimates);
+        Ok(())
+    }
+
+    /// Add estimates for players with no ESPN projection for the target week
+    ///
+    /// ESPN often drops the projection for questionable players. They are estimated
+    /// from recent form (0 without any recent games) at low confidence, with an ESPN
+    /// projection of 0 so `--blend-recent` leaves them alone. Players without a
+    /// stored row, or already estimated, are skipped. The estimates are re-sorted.
+    pub fn add_unprojected_estimates(
+        &self,
+        league_id: LeagueId,
+        estimates: &mut Vec<PerformanceEstimate>,
+        season: Season,
+        target_week: Week,
+        player_ids: &[PlayerId],
+        include_dnp: bool,
+    ) -> Result<()> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut player_stmt = self
+            .conn
+            .prepare("SELECT name, position, team FROM players WHERE player_id = ?")?;
+
+        for player_id in player_ids {
+            if estimates.iter().any(|e| e.player_id == *player_id) {
+                continue;
+            }
+            let player_info = player_stmt.query_row(params![player_id.as_i64()], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            });
+            let (name, position, team) = match player_info {
+                Ok(info) => info,
+                Err(rusqlite::Error::QueryReturnedNoRows) => continue,
+                Err(e) => return Err(e.into()),
+            };
+
+            let actuals = self.get_recent_actuals(
+                league_id,
+                *player_id,
+                season,
+                target_week,
+                RECENT_FORM_GAMES,
+                include_dnp,
+            )?;
+            let (estimated_points, reasoning) = match recent_form_estimate(&actuals) {
+                Some(recent_form) => (
+                    recent_form,
+                    format!(
+                        "No ESPN projection — estimate from last {} games",
+                        actuals.len()
+                    ),
+                ),
+                None => (
+                    0.0,
+                    "No ESPN projection and no recent games — estimated at 0 pts".to_string(),
+                ),
+            };
+
+            estimates.push(PerformanceEstimate {
+                player_id: *player_id,
+                name,
+                position,
+                team,
+                espn_projection: 0.0,
+                bias_adjustment: 0.0,
+                estimated_points,
+                confidence: UNPROJECTED_CONFIDENCE,
+                reasoning,
+                error_std: None,
+                dnp_weeks: None,
+                blend: None,
+                history: None,
+                percent_started: None,
+                tier: None,
+                locked: None,
+                vorp: None,
+            });
+        }
+
+        sort_by_estimate(estimates);
+        Ok(())
+    }
+
+    /// Compute season-long team records from stored matchups and weekly totals
+    ///
+    /// Weeks where any matchup is undecided or a team is missing its total are
+    /// reported in `excluded_weeks` and ignored, so partial scores never become records.
+    /// "Weeks" here are matchup periods; ESPN's matchup totals already cover every
+    /// week of a multi-week period, which `multi_week_periods` lists.
+    pub fn get_season_records(&self, league_id: LeagueId, season: Season) -> Result<SeasonRecords> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT m.matchup_period, m.home_team_id, m.away_team_id, m.winner,
+                    h.points, a.points
+             FROM fantasy_matchups m
+             LEFT JOIN team_weekly_totals h
+               ON h.season = m.season AND h.week = m.matchup_period AND h.team_id = m.home_team_id
+                  AND h.league_id = m.league_id
+             LEFT JOIN team_weekly_totals a
+               ON a.season = m.season AND a.week = m.matchup_period AND a.team_id = m.away_team_id
+                  AND a.league_id = m.league_id
+             WHERE m.season = ? AND m.league_id = ?
+             ORDER BY m.matchup_period, m.matchup_id",
+        )?;
+
+        type MatchupRow = (u16, u32, Option<u32>, String, Option<f64>, Option<f64>);
+        let rows = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+            Ok((
+                row.get::<_, u16>(0)?,
+                row.get::<_, u32>(1)?,
+                row.get::<_, Option<u32>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<f64>>(4)?,
+                row.get::<_, Option<f64>>(5)?,
+            ))
+        })?;
+
+        let mut weeks: std::collections::BTreeMap<u16, Vec<MatchupRow>> =
+            std::collections::BTreeMap::new();
+        for row in rows {
+            let row = row?;
+            weeks.entry(row.0).or_default().push(row);
+        }
+
+        let mut records = SeasonRecords {
+            season,
+            included_weeks: Vec::new(),
+            excluded_weeks: Vec::new(),
+            highest_score: None,
+            lowest_score: None,
+            longest_win_streak: None,
+            biggest_blowout: None,
+            closest_game: None,
+            teams: Vec::new(),
+            multi_week_periods: self
+                .get_matchup_periods(league_id, season)?
+                .into_iter()
+                .filter(|(_, weeks)| weeks.len() > 1)
+                .collect(),
+        };
+        let mut teams: std::collections::BTreeMap<u32, TeamSeasonRecord> =
+            std::collections::BTreeMap::new();
+        // team_id -> (current streak length, streak start week)
+        let mut streaks: std::collections::HashMap<u32, (u32, Week)> =
+            std::collections::HashMap::new();
+
+        for (week_num, matchups) in weeks {
+            let week = Week::new(week_num);
+            let complete = matchups.iter().all(|(_, _, away_id, winner, home, away)| {
+                winner != "UNDECIDED" && home.is_some() && (away_id.is_none() || away.is_some())
+            });
+            if !complete {
+                records.excluded_weeks.push(week);
+                continue;
+            }
+            records.included_weeks.push(week);
+
+            for (_, home_id, away_id, winner, home_points, away_points) in matchups {
+                let home = TeamWeekScore {
+                    team_id: home_id,
+                    team_name: None,
+                    week,
+                    points: home_points.unwrap_or_default(),
+                };
+                let away = away_id.map(|team_id| TeamWeekScore {
+                    team_id,
+                    team_name: None,
+                    week,
+                    points: away_points.unwrap_or_default(),
+                });
+
+                for score in std::iter::once(&home).chain(away.as_ref()) {
+                    if records
+                        .highest_score
+                        .as_ref()
+                        .is_none_or(|best| score.points > best.points)
+                    {
+                        records.highest_score = Some(score.clone());
+                    }
+                    if records
+                        .lowest_score
+                        .as_ref()
+                        .is_none_or(|worst| score.points < worst.points)
+                    {
+                        records.lowest_score = Some(score.clone());
+                    }
+
+                    let team = teams
+                        .entry(score.team_id)
+                        .or_insert_with(|| TeamSeasonRecord {
+                            team_id: score.team_id,
+                            team_name: None,
+                            wins: 0,
+                            losses: 0,
+                            ties: 0,
+                            best_week: None,
+                            worst_week: None,
+                        });
+                    if team
+                        .best_week
+                        .as_ref()
+                        .is_none_or(|best| score.points > best.points)
+                    {
+                        team.best_week = Some(score.clone());
+                    }
+                    if team
+                        .worst_week
+                        .as_ref()
+                        .is_none_or(|worst| score.points < worst.points)
+                    {
+                        team.worst_week = Some(score.clone());
+                    }
+                }
+
+                // Bye weeks count toward scores but not toward records or streaks
+                let Some(away) = away else {
+                    continue;
+                };
+
+                let (winner_score, loser_score) = match winner.as_str() {
+                    "HOME" => (home, away),
+                    "AWAY" => (away, home),
+                    _ => {
+                        for team_id in [home.team_id, away.team_id] {
+                            if let Some(team) = teams.get_mut(&team_id) {
+                                team.ties += 1;
+                            }
+                            streaks.remove(&team_id);
+                        }
+                        continue;
+                    }
+                };
+
+                if let Some(team) = teams.get_mut(&winner_score.team_id) {
+                    team.wins += 1;
+                }
+                if let Some(team) = teams.get_mut(&loser_score.team_id) {
+                    team.losses += 1;
+                }
+                streaks.remove(&loser_score.team_id);
+
+                let streak = streaks.entry(winner_score.team_id).or_insert((0, week));
+                streak.0 += 1;
+                if records
+                    .longest_win_streak
+                    .as_ref()
+                    .is_none_or(|longest| streak.0 > longest.length)
+                {
+                    records.longest_win_streak = Some(WinStreak {
+                        team_id: winner_score.team_id,
+                        team_name: None,
+                        length: streak.0,
+                        start_week: streak.1,
+                        end_week: week,
+                    });
+                }
+
+                let margin = MatchupMargin {
+                    week,
+                    margin: winner_score.points - loser_score.points,
+                    winner: winner_score,
+                    loser: loser_score,
+                };
+                if records
+                    .biggest_blowout
+                    .as_ref()
+                    .is_none_or(|biggest| margin.margin > biggest.margin)
+                {
+                    records.biggest_blowout = Some(margin.clone());
+                }
+                if records
+                    .closest_game
+                    .as_ref()
+                    .is_none_or(|closest| margin.margin < closest.margin)
+                {
+                    records.closest_game = Some(margin);
+                }
+            }
+        }
+
+        records.teams = teams.into_values().collect();
+        Ok(records)
+    }
+
+    /// Actual points for each player over the `weeks`-long window ending at `through_week`
+    ///
+    /// Returns one entry per week in the window (oldest first), `None` where no actual
+    /// points are stored or the player did not play. Players without any stored row are omitted.
+    pub fn get_recent_actual_points(
+        &self,
+        league_id: LeagueId,
+        player_ids: &[PlayerId],
+        season: Season,
+        through_week: Week,
+        weeks: u16,
+    ) -> Result<HashMap<PlayerId, Vec<Option<f64>>>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let first_week = Week::new(
+            through_week
+                .as_u16()
+                .saturating_sub(weeks.saturating_sub(1))
+                .max(1),
+        );
+        let window: Vec<Week> = first_week.range_to(through_week).collect();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT week, CASE WHEN played = 0 THEN NULL ELSE actual_points END
+             FROM player_weekly_stats
+             WHERE player_id = ? AND season = ? AND week BETWEEN ? AND ? AND league_id = ?",
+        )?;
+
+        let mut recent = HashMap::new();
+        for player_id in player_ids {
+            let rows = stmt.query_map(
+                params![
+                    player_id.as_i64(),
+                    season.as_u16(),
+                    first_week.as_u16(),
+                    through_week.as_u16(),
+                    league_id.as_u32()
+                ],
+                |row| Ok((row.get::<_, u16>(0)?, row.get::<_, Option<f64>>(1)?)),
+            )?;
+
+            let mut by_week = HashMap::new();
+            for row in rows {
+                let (week, points) = row?;
+                by_week.insert(week, points);
+            }
+            if by_week.is_empty() {
+                continue;
+            }
+
+            let series = window
+                .iter()
+                .map(|week| by_week.get(&week.as_u16()).copied().flatten())
+                .collect();
+            recent.insert(*player_id, series);
+        }
+
+        Ok(recent)
+    }
+
+    /// Per-position minimum and maximum actual points across a season, ignoring DNP weeks
+    pub fn get_position_point_bounds(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+    ) -> Result<HashMap<String, (f64, f64)>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT p.position, MIN(s.actual_points), MAX(s.actual_points)
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.league_id = ? AND s.actual_points IS NOT NULL
+               AND (s.played IS NULL OR s.played = 1)
+             GROUP BY p.position",
+        )?;
+
+        let rows = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (row.get::<_, f64>(1)?, row.get::<_, f64>(2)?),
+            ))
+        })?;
+
+        let mut bounds = HashMap::new();
+        for row in rows {
+            let (position, range) = row?;
+            bounds.insert(position, range);
+        }
+        Ok(bounds)
+    }
+
+    /// Highest single-week actual points per position across a league's stored seasons
+    pub fn get_position_max_points(&self, league_id: LeagueId) -> Result<HashMap<String, f64>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT p.position, MAX(s.actual_points)
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.league_id = ? AND s.actual_points IS NOT NULL
+               AND (s.played IS NULL OR s.played = 1)
+             GROUP BY p.position",
+        )?;
+
+        let rows = stmt.query_map([league_id.as_u32()], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, f64>(1)?))
+        })?;
+
+        let mut max_points = HashMap::new();
+        for row in rows {
+            let (position, max) = row?;
+            max_points.insert(position, max);
+        }
+        Ok(max_points)
+    }
+
+    /// Row counts per week for each kind of stored data, weeks 1 through `weeks`
+    ///
+    /// Runs one aggregation query per data type.
+    pub fn get_data_coverage(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        weeks: u16,
+    ) -> Result<DataCoverage> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let queries = [
+            (
+                CoverageDataType::ActualPoints,
+                "SELECT week, COUNT(*) FROM player_weekly_stats
+                 WHERE season = ? AND league_id = ? AND actual_points IS NOT NULL
+                 GROUP BY week",
+            ),
+            (
+                CoverageDataType::ProjectedPoints,
+                "SELECT week, COUNT(*) FROM player_weekly_stats
+                 WHERE season = ? AND league_id = ? AND projected_points IS NOT NULL
+                 GROUP BY week",
+            ),
+            (
+                CoverageDataType::RosterSnapshots,
+                "SELECT week, COUNT(*) FROM roster_snapshots
+                 WHERE season = ? AND league_id = ? GROUP BY week",
+            ),
+            (
+                CoverageDataType::Ownership,
+                "SELECT week, COUNT(*) FROM player_weekly_stats
+                 WHERE season = ? AND league_id = ? AND is_rostered IS NOT NULL
+                 GROUP BY week",
+            ),
+        ];
+
+        let mut rows = Vec::with_capacity(queries.len());
+        for (data_type, sql) in queries {
+            let mut weekly_counts = vec![0u32; weeks as usize];
+            let mut stmt = self.conn.prepare(sql)?;
+            let counts = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+                Ok((row.get::<_, u16>(0)?, row.get::<_, u32>(1)?))
+            })?;
+            for count in counts {
+                let (week, count) = count?;
+                if let Some(slot) = week
+                    .checked_sub(1)
+                    .and_then(|index| weekly_counts.get_mut(index as usize))
+                {
+                    *slot = count;
+                }
+            }
+            rows.push(CoverageRow::new(data_type, weekly_counts));
+        }
+
+        Ok(DataCoverage {
+            season,
+            weeks,
+            rows,
+        })
+    }
+
+    /// Diff stored roster snapshots between two weeks, with each changed player's
+    /// actual points for the weeks after `from_week` through `to_week`
+    pub fn get_roster_diff(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        from_week: Week,
+        to_week: Week,
+    ) -> Result<Vec<TeamRosterDiff>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let before = self.get_roster_snapshot(league_id, season, from_week)?;
+        let after = self.get_roster_snapshot(league_id, season, to_week)?;
+        let mut diffs = diff_rosters(&before, &after);
+
+        let weeks = to_week.as_u16().saturating_sub(from_week.as_u16());
+        if weeks == 0 {
+            return Ok(diffs);
+        }
+
+        let player_ids: Vec<PlayerId> = diffs
+            .iter()
+            .flat_map(|d| d.added.iter().chain(&d.dropped).chain(&d.moved))
+            .map(|c| c.player_id)
+            .collect();
+        let recent =
+            self.get_recent_actual_points(league_id, &player_ids, season, to_week, weeks)?;
+
+        for change in diffs
+            .iter_mut()
+            .flat_map(|d| d.added.iter_mut().chain(&mut d.dropped).chain(&mut d.moved))
+        {
+            change.weekly_points = recent
+                .get(&change.player_id)
+                .cloned()
+                .unwrap_or_else(|| vec![None; weeks as usize]);
+            // Folded from 0.0 so a player with no points shows 0.0, not -0.0
+            change.total_points = change
+                .weekly_points
+                .iter()
+                .flatten()
+                .fold(0.0, |a, b| a + b);
+        }
+
+        Ok(diffs)
+    }
+
+    /// Each fantasy team's starter and bench points for a week
+    ///
+    /// Built from the stored roster snapshot and actual points, so run after the
+    /// week's actuals are stored. Teams are ordered by starter points, highest
+    /// first; a team whose starters aren't all scored lists them in
+    /// `missing_starters` rather than passing off a partial total. Team names come
+    /// from the league's current names, falling back to the snapshot's.
+    pub fn team_week_totals(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+    ) -> Result<Vec<TeamWeekTotal>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT r.team_id, r.team_name, r.player_id, p.name, r.lineup_slot_id,
+                    s.actual_points
+             FROM roster_snapshots r
+             LEFT JOIN players p ON p.player_id = r.player_id
+             LEFT JOIN player_weekly_stats s
+               ON s.player_id = r.player_id AND s.season = r.season AND s.week = r.week
+                  AND s.league_id = r.league_id
+             WHERE r.season = ? AND r.week = ? AND r.league_id = ?
+             ORDER BY r.team_id, r.lineup_slot_id, r.player_id",
+        )?;
+        let rows = stmt.query_map(
+            params![season.as_u16(), week.as_u16(), league_id.as_u32()],
+            |row| {
+                Ok((
+                    row.get::<_, u32>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    TeamWeekPlayer {
+                        player_id: PlayerId::new(row.get(2)?),
+                        player_name: row.get(3)?,
+                        lineup_slot_id: row.get(4)?,
+                        points: row.get(5)?,
+                    },
+                ))
+            },
+        )?;
+
+        let mut teams: BTreeMap<u32, (Option<String>, Vec<TeamWeekPlayer>)> = BTreeMap::new();
+        for row in rows {
+            let (team_id, team_name, player) = row?;
+            let team = teams.entry(team_id).or_insert((team_name, Vec::new()));
+            team.1.push(player);
+        }
+
+        let current_names = self.get_current_team_names(league_id, season)?;
+        let mut totals: Vec<TeamWeekTotal> = teams
+            .into_iter()
+            .map(|(team_id, (snapshot_name, players))| {
+                let mut total = TeamWeekTotal {
+                    team_id,
+                    team_name: current_names.get(&team_id).cloned().or(snapshot_name),
+                    starter_points: 0.0,
+                    bench_points: 0.0,
+                    top_scorer: None,
+                    bottom_scorer: None,
+                    missing_starters: Vec::new(),
+                };
+                let mut scored_starters = Vec::new();
+                for player in players {
+                    match (
+                        SlotGroup::from_lineup_slot(player.lineup_slot_id),
+                        player.points,
+                    ) {
+                        (SlotGroup::Starter, Some(points)) => {
+                            total.starter_points += points;
+                            scored_starters.push(player);
+                        }
+                        (SlotGroup::Starter, None) => total.missing_starters.push(player),
+                        (SlotGroup::Bench, points) => total.bench_points += points.unwrap_or(0.0),
+                        (SlotGroup::Ir, _) => {}
+                    }
+                }
+                // Ties go to the earlier lineup slot
+                let by_points = |a: &&TeamWeekPlayer, b: &&TeamWeekPlayer| {
+                    a.points
+                        .partial_cmp(&b.points)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                };
+                total.top_scorer = scored_starters.iter().rev().max_by(by_points).cloned();
+                total.bottom_scorer = scored_starters.iter().min_by(by_points).cloned();
+                total
+            })
+            .collect();
+
+        totals.sort_by(|a, b| {
+            b.starter_points
+                .partial_cmp(&a.starter_points)
                 .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.team_id.cmp(&b.team_id))
         });
+        Ok(totals)
+    }
 
-        Ok(estimates)
+    /// Every fantasy team's roster for a week, from the stored snapshot
+    ///
+    /// Each player carries the week's stored projected and actual points when there
+    /// are any. Teams are ordered by name (current names, falling back to the
+    /// snapshot's), players by starter/bench/IR and then lineup slot.
+    pub fn team_rosters(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+    ) -> Result<Vec<TeamWeekRoster>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT r.team_id, r.team_name, r.player_id, p.name, p.position, r.lineup_slot_id,
+                    s.projected_points, s.actual_points
+             FROM roster_snapshots r
+             LEFT JOIN players p ON p.player_id = r.player_id
+             LEFT JOIN player_weekly_stats s
+               ON s.player_id = r.player_id AND s.season = r.season AND s.week = r.week
+                  AND s.league_id = r.league_id
+             WHERE r.season = ? AND r.week = ? AND r.league_id = ?
+             ORDER BY r.team_id, r.lineup_slot_id, r.player_id",
+        )?;
+        let rows = stmt.query_map(
+            params![season.as_u16(), week.as_u16(), league_id.as_u32()],
+            |row| {
+                let lineup_slot_id: u8 = row.get(5)?;
+                Ok((
+                    row.get::<_, u32>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    RosterPlayer {
+                        player_id: PlayerId::new(row.get(2)?),
+                        player_name: row.get(3)?,
+                        position: row.get(4)?,
+                        lineup_slot_id,
+                        slot: SlotGroup::from_lineup_slot(lineup_slot_id),
+                        projected_points: row.get(6)?,
+                        points: row.get(7)?,
+                    },
+                ))
+            },
+        )?;
+
+        let mut teams: BTreeMap<u32, (Option<String>, Vec<RosterPlayer>)> = BTreeMap::new();
+        for row in rows {
+            let (team_id, team_name, player) = row?;
+            let team = teams.entry(team_id).or_insert((team_name, Vec::new()));
+            team.1.push(player);
+        }
+
+        let slot_order = |slot: SlotGroup| match slot {
+            SlotGroup::Starter => 0,
+            SlotGroup::Bench => 1,
+            SlotGroup::Ir => 2,
+        };
+        let current_names = self.get_current_team_names(league_id, season)?;
+        let mut rosters: Vec<TeamWeekRoster> = teams
+            .into_iter()
+            .map(|(team_id, (snapshot_name, mut players))| {
+                // The sort is stable, so slot and player order within a group is kept
+                players.sort_by_key(|player| slot_order(player.slot));
+                TeamWeekRoster {
+                    team_id,
+                    team_name: current_names.get(&team_id).cloned().or(snapshot_name),
+                    players,
+                }
+            })
+            .collect();
+
+        // Unnamed teams go last, by ID
+        rosters.sort_by_cached_key(|roster| {
+            (
+                roster.team_name.is_none(),
+                roster.team_name.as_deref().map(str::to_lowercase),
+                roster.team_id,
+            )
+        });
+        Ok(rosters)
+    }
+
+    /// A team's season at one position (`"K"` or `"D/ST"`) against streaming and holding
+    ///
+    /// Covers every week with a roster snapshot for the team. Free agents are players
+    /// with stored actuals who are on no team's snapshot that week; the season leader
+    /// is the position's top scorer over those weeks, in hindsight.
+    pub fn streaming_recap(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        team_id: u32,
+        position: &str,
+    ) -> Result<StreamingRecap> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT week FROM roster_snapshots
+             WHERE season = ? AND team_id = ? AND league_id = ?
+             ORDER BY week",
+        )?;
+        let weeks: Vec<u16> = stmt
+            .query_map(
+                params![season.as_u16(), team_id, league_id.as_u32()],
+                |row| row.get(0),
+            )?
+            .collect::<rusqlite::Result<_>>()?;
+
+        let mut recap = StreamingRecap {
+            position: position.to_string(),
+            season_leader: None,
+            weeks: Vec::new(),
+            started_total: 0.0,
+            perfect_streaming_total: 0.0,
+            holding_total: 0.0,
+            verdict: None,
+        };
+        let (Some(&first), Some(&last)) = (weeks.first(), weeks.last()) else {
+            return Ok(recap);
+        };
+
+        recap.season_leader = self
+            .conn
+            .query_row(
+                "SELECT s.player_id, p.name, SUM(s.actual_points) AS total
+                 FROM player_weekly_stats s
+                 JOIN players p ON p.player_id = s.player_id
+                 WHERE s.season = ? AND s.week BETWEEN ? AND ? AND p.position = ?
+                   AND s.league_id = ? AND s.actual_points IS NOT NULL
+                 GROUP BY s.player_id
+                 ORDER BY total DESC, s.player_id
+                 LIMIT 1",
+                params![season.as_u16(), first, last, position, league_id.as_u32()],
+                |row| {
+                    Ok(StreamingPick {
+                        player_id: PlayerId::new(row.get(0)?),
+                        player_name: row.get(1)?,
+                        points: row.get(2)?,
+                    })
+                },
+            )
+            .optional()?;
+
+        let mut started_stmt = self.conn.prepare(
+            "SELECT r.player_id, p.name, r.lineup_slot_id, s.actual_points
+             FROM roster_snapshots r
+             JOIN players p ON p.player_id = r.player_id
+             LEFT JOIN player_weekly_stats s
+               ON s.player_id = r.player_id AND s.season = r.season AND s.week = r.week
+                  AND s.league_id = r.league_id
+             WHERE r.season = ? AND r.week = ? AND r.team_id = ? AND p.position = ?
+               AND r.league_id = ?
+             ORDER BY r.lineup_slot_id, r.player_id",
+        )?;
+        let mut free_agent_stmt = self.conn.prepare(
+            "SELECT s.player_id, p.name, s.actual_points
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.week = ? AND p.position = ? AND s.league_id = ?
+               AND s.actual_points IS NOT NULL
+               AND NOT EXISTS (
+                   SELECT 1 FROM roster_snapshots r
+                   WHERE r.season = s.season AND r.week = s.week AND r.player_id = s.player_id
+                     AND r.league_id = s.league_id
+               )
+             ORDER BY s.actual_points DESC, s.player_id
+             LIMIT 1",
+        )?;
+        let mut leader_stmt = self.conn.prepare(
+            "SELECT actual_points FROM player_weekly_stats
+             WHERE player_id = ? AND season = ? AND week = ? AND league_id = ?",
+        )?;
+
+        for week in weeks {
+            let started: Vec<TeamWeekPlayer> = started_stmt
+                .query_map(
+                    params![season.as_u16(), week, team_id, position, league_id.as_u32()],
+                    |row| {
+                        Ok(TeamWeekPlayer {
+                            player_id: PlayerId::new(row.get(0)?),
+                            player_name: row.get(1)?,
+                            lineup_slot_id: row.get(2)?,
+                            points: row.get(3)?,
+                        })
+                    },
+                )?
+                .collect::<rusqlite::Result<Vec<_>>>()?
+                .into_iter()
+                .filter(|p| SlotGroup::from_lineup_slot(p.lineup_slot_id) == SlotGroup::Starter)
+                .collect();
+            let best_free_agent = free_agent_stmt
+                .query_row(
+                    params![season.as_u16(), week, position, league_id.as_u32()],
+                    |row| {
+                        Ok(StreamingPick {
+                            player_id: PlayerId::new(row.get(0)?),
+                            player_name: row.get(1)?,
+                            points: row.get(2)?,
+                        })
+                    },
+                )
+                .optional()?;
+            let season_leader_points = match &recap.season_leader {
+                Some(leader) => leader_stmt
+                    .query_row(
+                        params![
+                            leader.player_id.as_i64(),
+                            season.as_u16(),
+                            week,
+                            league_id.as_u32()
+                        ],
+                        |row| row.get::<_, Option<f64>>(0),
+                    )
+                    .optional()?
+                    .flatten()
+                    .unwrap_or(0.0),
+                None => 0.0,
+            };
+
+            let started_points = started.iter().filter_map(|p| p.points).sum();
+            recap.started_total += started_points;
+            recap.perfect_streaming_total += best_free_agent.as_ref().map_or(0.0, |p| p.points);
+            recap.holding_total += season_leader_points;
+            recap.weeks.push(StreamingWeek {
+                week: Week::new(week),
+                started,
+                started_points,
+                best_free_agent,
+                season_leader_points,
+            });
+        }
+
+        recap.verdict = Some(if recap.holding_total > recap.started_total {
+            StreamingVerdict::Hold
+        } else {
+            StreamingVerdict::Stream
+        });
+        Ok(recap)
+    }
+
+    /// Each player's positional rank in every stored week of a season, summarized
+    ///
+    /// Players are ranked by actual points against everyone at their position with
+    /// stored actuals that week; weeks they did not play are left out. Tied points
+    /// share the better rank. Players with fewer than `min_games` ranked weeks are
+    /// dropped. Sorted by position, then median rank, then player ID.
+    pub fn positional_rank_history(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        positions: Option<&[Position]>,
+        min_games: u32,
+    ) -> Result<Vec<PositionalRankHistory>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT s.week, p.position, s.player_id, p.name, p.team, s.actual_points
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.league_id = ? AND s.actual_points IS NOT NULL
+               AND (s.played IS NULL OR s.played != 0)
+             ORDER BY s.week, p.position, s.actual_points DESC, s.player_id",
+        )?;
+        type RankRow = (u16, String, i64, String, Option<String>, f64);
+        let rows = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+            Ok((
+                row.get::<_, u16>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+                row.get::<_, f64>(5)?,
+            ))
+        })?;
+
+        let wanted = |position: &str| match positions.filter(|p| !p.is_empty()) {
+            None => true,
+            Some(filter) => position.parse::<Position>().is_ok_and(|position| {
+                filter
+                    .iter()
+                    .any(|f| f.accepts(position, &FlexEligibility::default()))
+            }),
+        };
+
+        let mut players: HashMap<PlayerId, PositionalRankHistory> = HashMap::new();
+        // (week, position) being ranked, players seen in it, last points and rank
+        let mut group: Option<(u16, String)> = None;
+        let (mut seen, mut last_points, mut last_rank) = (0u32, f64::NAN, 0u32);
+        for row in rows {
+            let (week, position, player_id, name, team, points): RankRow = row?;
+            if group.as_ref() != Some(&(week, position.clone())) {
+                group = Some((week, position.clone()));
+                seen = 0;
+            }
+            seen += 1;
+            // Rows arrive highest first, so a tie keeps the previous player's rank
+            if seen == 1 || points != last_points {
+                last_rank = seen;
+            }
+            last_points = points;
+
+            if !wanted(&position) {
+                continue;
+            }
+            let player_id = PlayerId::new(player_id);
+            players
+                .entry(player_id)
+                .or_insert_with(|| PositionalRankHistory {
+                    player_id,
+                    name,
+                    position,
+                    team,
+                    games: 0,
+                    top_12: 0,
+                    top_24: 0,
+                    median_rank: 0.0,
+                    rank_variance: 0.0,
+                    weeks: Vec::new(),
+                })
+                .weeks
+                .push(WeeklyPositionRank {
+                    week: Week::new(week),
+                    points,
+                    rank: last_rank,
+                });
+        }
+
+        let mut history: Vec<PositionalRankHistory> = players
+            .into_values()
+            .filter(|player| player.weeks.len() as u32 >= min_games.max(1))
+            .map(|mut player| {
+                let mut ranks: Vec<u32> = player.weeks.iter().map(|w| w.rank).collect();
+                ranks.sort_unstable();
+                let n = ranks.len();
+                let mean = ranks.iter().map(|&r| r as f64).sum::<f64>() / n as f64;
+                player.games = n as u32;
+                player.top_12 = ranks.iter().filter(|&&r| r <= TOP_TIER_RANK).count() as u32;
+                player.top_24 = ranks.iter().filter(|&&r| r <= SECOND_TIER_RANK).count() as u32;
+                player.median_rank = if n % 2 == 1 {
+                    ranks[n / 2] as f64
+                } else {
+                    (ranks[n / 2 - 1] + ranks[n / 2]) as f64 / 2.0
+                };
+                player.rank_variance = ranks
+                    .iter()
+                    .map(|&r| (r as f64 - mean).powi(2))
+                    .sum::<f64>()
+                    / n as f64;
+                player
+            })
+            .collect();
+
+        let position_order = |position: &str| {
+            POSITION_GROUP_ORDER
+                .iter()
+                .position(|p| *p == position)
+                .unwrap_or(POSITION_GROUP_ORDER.len())
+        };
+        history.sort_by(|a, b| {
+            (position_order(&a.position), &a.position)
+                .cmp(&(position_order(&b.position), &b.position))
+                .then(a.median_rank.total_cmp(&b.median_rank))
+                .then(a.player_id.as_i64().cmp(&b.player_id.as_i64()))
+        });
+        Ok(history)
+    }
+
+    /// Each player's full-season point pace against a preseason expectation
+    ///
+    /// Weeks elapsed run through the latest week with stored actuals. Bye weeks
+    /// (projected and actual both 0) and weeks the player didn't play are left
+    /// out of their games, so the per-game average is projected over the team's
+    /// remaining games (see [`remaining_team_games`]) rather than the remaining
+    /// weeks. The expectation is the player's earliest nonzero ESPN weekly
+    /// projection over [`SEASON_GAMES`]. Players with fewer than `min_games`
+    /// games are dropped. Sorted by the size of the gap to the expectation,
+    /// biggest first, then by pace for players without one.
+    pub fn season_pace(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        positions: Option<&[Position]>,
+        min_games: u32,
+    ) -> Result<Vec<SeasonPace>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let weeks_elapsed: Option<u16> = self.conn.query_row(
+            "SELECT MAX(week) FROM player_weekly_stats
+             WHERE season = ? AND league_id = ? AND actual_points IS NOT NULL",
+            params![season.as_u16(), league_id.as_u32()],
+            |row| row.get(0),
+        )?;
+        let Some(weeks_elapsed) = weeks_elapsed else {
+            return Ok(Vec::new());
+        };
+
+        let mut stmt = self.conn.prepare(
+            "SELECT s.player_id, p.name, p.position, p.team, s.week,
+                    s.projected_points, s.actual_points, s.played
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.week <= ? AND s.league_id = ?
+             ORDER BY s.player_id, s.week",
+        )?;
+        type PaceRow = (
+            i64,
+            String,
+            String,
+            Option<String>,
+            u16,
+            Option<f64>,
+            Option<f64>,
+            Option<bool>,
+        );
+        let rows = stmt.query_map(
+            params![season.as_u16(), weeks_elapsed, league_id.as_u32()],
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                    row.get(6)?,
+                    row.get(7)?,
+                ))
+            },
+        )?;
+
+        let wanted = |position: &str| match positions.filter(|p| !p.is_empty()) {
+            None => true,
+            Some(filter) => position.parse::<Position>().is_ok_and(|position| {
+                filter
+                    .iter()
+                    .any(|f| f.accepts(position, &FlexEligibility::default()))
+            }),
+        };
+
+        // Per player: the pace row being built and whether their bye has passed
+        let mut players: Vec<(SeasonPace, bool)> = Vec::new();
+        for row in rows {
+            let (player_id, name, position, team, week, projected, actual, played): PaceRow = row?;
+            if !wanted(&position) {
+                continue;
+            }
+            let player_id = PlayerId::new(player_id);
+            if players.last().map(|(p, _)| p.player_id) != Some(player_id) {
+                players.push((
+                    SeasonPace {
+                        player_id,
+                        name,
+                        position,
+                        team,
+                        points: 0.0,
+                        games: 0,
+                        remaining_games: 0,
+                        pace: 0.0,
+                        expectation: None,
+                        expectation_week: None,
+                        vs_expectation: None,
+                    },
+                    false,
+                ));
+            }
+            let Some((pace, bye_passed)) = players.last_mut() else {
+                continue;
+            };
+
+            if pace.expectation.is_none() {
+                if let Some(projected) = projected.filter(|&p| p > 0.0) {
+                    pace.expectation = Some(projected * SEASON_GAMES as f64);
+                    pace.expectation_week = Some(Week::new(week));
+                }
+            }
+            let Some(actual) = actual else {
+                continue;
+            };
+            if projected == Some(0.0) && actual == 0.0 {
+                *bye_passed = true;
+            } else if played != Some(false) {
+                pace.points += actual;
+                pace.games += 1;
+            }
+        }
+
+        let mut paces: Vec<SeasonPace> = players
+            .into_iter()
+            .filter(|(pace, _)| pace.games >= min_games.max(1))
+            .map(|(mut pace, bye_passed)| {
+                pace.remaining_games = remaining_team_games(weeks_elapsed, bye_passed);
+                pace.pace = extrapolate_pace(pace.points, pace.games, pace.remaining_games);
+                pace.vs_expectation = pace.expectation.map(|expected| pace.pace - expected);
+                pace
+            })
+            .collect();
+        paces.sort_by(|a, b| {
+            let gap = |p: &SeasonPace| p.vs_expectation.map(f64::abs);
+            match (gap(a), gap(b)) {
+                (Some(a_gap), Some(b_gap)) => b_gap.total_cmp(&a_gap),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => b.pace.total_cmp(&a.pace),
+            }
+            .then(a.player_id.as_i64().cmp(&b.player_id.as_i64()))
+        });
+        Ok(paces)
+    }
+
+    /// How every player's stored numbers moved from week `from` to week `to`
+    ///
+    /// Covers players with a stored row in either week. Actual points, ESPN's weekly
+    /// projection and ownership get a delta when both weeks have them; the injury
+    /// designation is kept for both sides. Position and roster filters use the later
+    /// week's roster status (the earlier one's when the later week isn't stored).
+    /// Ordered by player ID; see [`risers_and_fallers`] for the ranking.
+    pub fn week_over_week(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        from: Week,
+        to: Week,
+        positions: Option<&[Position]>,
+        roster_status: Option<&RosterStatusFilter>,
+    ) -> Result<Vec<WeekOverWeekChange>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT p.player_id, p.name, p.position, p.team,
+                    CASE WHEN t.player_id IS NULL THEN f.is_rostered ELSE t.is_rostered END,
+                    CASE WHEN t.player_id IS NULL THEN f.fantasy_team_name
+                         ELSE t.fantasy_team_name END,
+                    f.actual_points, t.actual_points,
+                    f.projected_points, t.projected_points,
+                    f.percent_owned, t.percent_owned,
+                    f.injury_status, t.injury_status
+             FROM players p
+             LEFT JOIN player_weekly_stats f
+               ON f.player_id = p.player_id AND f.season = ?1 AND f.week = ?2
+                  AND f.league_id = ?4
+             LEFT JOIN player_weekly_stats t
+               ON t.player_id = p.player_id AND t.season = ?1 AND t.week = ?3
+                  AND t.league_id = ?4
+             WHERE f.player_id IS NOT NULL OR t.player_id IS NOT NULL
+             ORDER BY p.player_id",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                season.as_u16(),
+                from.as_u16(),
+                to.as_u16(),
+                league_id.as_u32()
+            ],
+            |row| {
+                let delta = |from: Option<f64>, to: Option<f64>| Some(to? - from?);
+                let (actual_from, actual_to) = (row.get(6)?, row.get(7)?);
+                let (projected_from, projected_to) = (row.get(8)?, row.get(9)?);
+                let (owned_from, owned_to) = (row.get(10)?, row.get(11)?);
+                Ok(WeekOverWeekChange {
+                    player_id: PlayerId::new(row.get(0)?),
+                    name: row.get(1)?,
+                    position: row.get(2)?,
+                    team: row.get(3)?,
+                    is_rostered: row.get(4)?,
+                    fantasy_team_name: row.get(5)?,
+                    actual_from,
+                    actual_to,
+                    actual_delta: delta(actual_from, actual_to),
+                    projected_from,
+                    projected_to,
+                    projected_delta: delta(projected_from, projected_to),
+                    owned_from,
+                    owned_to,
+                    owned_delta: delta(owned_from, owned_to),
+                    injury_status_from: row.get(12)?,
+                    injury_status_to: row.get(13)?,
+                })
+            },
+        )?;
+
+        let wanted_position = |position: &str| match positions.filter(|p| !p.is_empty()) {
+            None => true,
+            Some(filter) => position.parse::<Position>().is_ok_and(|position| {
+                filter
+                    .iter()
+                    .any(|f| f.accepts(position, &FlexEligibility::default()))
+            }),
+        };
+        let wanted_roster = |is_rostered: Option<bool>| match roster_status {
+            None => true,
+            Some(RosterStatusFilter::Rostered) => is_rostered == Some(true),
+            Some(RosterStatusFilter::FA) => is_rostered == Some(false),
+        };
+
+        let mut changes = Vec::new();
+        for row in rows {
+            let change = row?;
+            if wanted_position(&change.position) && wanted_roster(change.is_rostered) {
+                changes.push(change);
+            }
+        }
+        Ok(changes)
+    }
+
+    /// Per-position MAE and RMSE of ESPN's projections and of the bias-adjusted ones
+    ///
+    /// Covers the weeks through `through_week` (every stored week when `None`),
+    /// leaving out byes (projected and actual both 0) and weeks the player didn't
+    /// play. Each week's adjusted projection is rebuilt from the player's errors in
+    /// earlier weeks only, as [`Self::estimate_week_performance`] would have made it
+    /// then, without the position ceiling; a player's first week is ESPN's own
+    /// projection. Positions come in the usual order, followed by an `ALL` row.
+    pub fn projection_accuracy(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        through_week: Option<Week>,
+        bias: BiasWeighting,
+    ) -> Result<Vec<PositionAccuracy>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT s.player_id, p.position, s.week, s.projected_points, s.actual_points
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.week <= ? AND s.league_id = ?
+               AND s.projected_points IS NOT NULL
+               AND s.actual_points IS NOT NULL
+               AND NOT (s.projected_points = 0 AND s.actual_points = 0)
+               AND (s.played IS NULL OR s.played = 1)
+             ORDER BY s.player_id, s.week",
+        )?;
+        let rows = stmt.query_map(
+            params![
+                season.as_u16(),
+                through_week.map_or(u16::MAX, |week| week.as_u16()),
+                league_id.as_u32()
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, u16>(2)?,
+                    row.get::<_, f64>(3)?,
+                    row.get::<_, f64>(4)?,
+                ))
+            },
+        )?;
+
+        let mut by_position: HashMap<String, AccuracyTotals> = HashMap::new();
+        let mut overall = AccuracyTotals::default();
+        let mut current_player = None;
+        let mut earlier_errors: Vec<(Week, f64)> = Vec::new();
+        for row in rows {
+            let (player_id, position, week, projected, actual) = row?;
+            if current_player != Some(player_id) {
+                current_player = Some(player_id);
+                earlier_errors.clear();
+            }
+            let week = Week::new(week);
+            let adjusted = match weighted_bias(&earlier_errors, week, bias.decay) {
+                Some(weighted) => {
+                    (projected + bias_adjustment(projected, &weighted, bias.strength)).max(0.0)
+                }
+                None => projected,
+            };
+            by_position
+                .entry(position)
+                .or_default()
+                .add(projected, adjusted, actual);
+            overall.add(projected, adjusted, actual);
+            earlier_errors.push((week, projected - actual));
+        }
+
+        let mut positions: Vec<(String, AccuracyTotals)> = by_position.into_iter().collect();
+        positions.sort_by_cached_key(|(position, _)| {
+            let rank = POSITION_GROUP_ORDER
+                .iter()
+                .position(|p| p == position)
+                .unwrap_or(POSITION_GROUP_ORDER.len());
+            (rank, position.clone())
+        });
+        if overall.games > 0 {
+            positions.push(("ALL".to_string(), overall));
+        }
+        Ok(positions
+            .into_iter()
+            .map(|(position, totals)| totals.accuracy(position))
+            .collect())
+    }
+}
+
+/// Running error sums behind a [`PositionAccuracy`]
+#[derive(Debug, Default)]
+struct AccuracyTotals {
+    games: u32,
+    espn_abs: f64,
+    espn_squared: f64,
+    adjusted_abs: f64,
+    adjusted_squared: f64,
+}
+
+impl AccuracyTotals {
+    fn add(&mut self, espn: f64, adjusted: f64, actual: f64) {
+        self.games += 1;
+        self.espn_abs += (espn - actual).abs();
+        self.espn_squared += (espn - actual).powi(2);
+        self.adjusted_abs += (adjusted - actual).abs();
+        self.adjusted_squared += (adjusted - actual).powi(2);
+    }
+
+    fn accuracy(self, position: String) -> PositionAccuracy {
+        let games = f64::from(self.games);
+        let espn_mae = self.espn_abs / games;
+        let adjusted_mae = self.adjusted_abs / games;
+        PositionAccuracy {
+            position,
+            games: self.games,
+            espn_mae,
+            espn_rmse: (self.espn_squared / games).sqrt(),
+            adjusted_mae,
+            adjusted_rmse: (self.adjusted_squared / games).sqrt(),
+            improvement_pct: (espn_mae > 0.0).then(|| (espn_mae - adjusted_mae) / espn_mae * 100.0),
+        }
     }
 }
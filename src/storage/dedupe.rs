@@ -0,0 +1,327 @@
+//! Merging players stored under both signs of the same ESPN ID
+//!
+//! Older versions stored some D/ST entries under the positive form of ESPN's
+//! (negative) ID, so a database built across versions can hold the same team
+//! twice, each with part of the season. Pairs are matched on `id`/`-id` plus an
+//! exact name and position; the weekly rows are merged into one player and the
+//! other player row is removed.
+
+use super::schema::PlayerDatabase;
+use crate::PlayerId;
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+use serde::Serialize;
+
+/// Weekly stats columns merged value by value
+const MERGED_COLUMNS: [&str; 13] = [
+    "projected_points",
+    "actual_points",
+    "active",
+    "injured",
+    "injury_status",
+    "is_rostered",
+    "fantasy_team_id",
+    "fantasy_team_name",
+    "played",
+    "percent_owned",
+    "percent_started",
+    "opponent_team",
+    "home_game",
+];
+
+/// Two player rows for the same player, one under each sign of its ID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignedIdPair {
+    pub name: String,
+    pub position: String,
+    /// The ID ESPN uses today: negative for D/ST, positive otherwise
+    pub kept: PlayerId,
+    pub removed: PlayerId,
+}
+
+/// What merging one pair did
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DedupeResult {
+    #[serde(flatten)]
+    pub pair: SignedIdPair,
+    /// Weeks stored under both IDs, combined into one row
+    pub weeks_merged: usize,
+    /// Weeks stored only under the removed ID, moved to the kept one
+    pub weeks_moved: usize,
+}
+
+impl PlayerDatabase {
+    /// Players stored under both `id` and `-id` with the same name and position
+    pub fn find_signed_id_duplicates(&self) -> Result<Vec<SignedIdPair>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.player_id, a.name, a.position
+             FROM players a
+             JOIN players b
+               ON b.player_id = -a.player_id AND b.name = a.name AND b.position = a.position
+             WHERE a.player_id > 0
+             ORDER BY a.name, a.player_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut pairs = Vec::new();
+        for row in rows {
+            let (positive, name, position) = row?;
+            // ESPN only hands out negative IDs to team defenses
+            let (kept, removed) = if position == "D/ST" {
+                (-positive, positive)
+            } else {
+                (positive, -positive)
+            };
+            pairs.push(SignedIdPair {
+                name,
+                position,
+                kept: PlayerId::new(kept),
+                removed: PlayerId::new(removed),
+            });
+        }
+        Ok(pairs)
+    }
+
+    /// Merge every signed-ID duplicate into a single player
+    pub fn dedupe_signed_ids(&mut self) -> Result<Vec<DedupeResult>> {
+        let pairs = self.find_signed_id_duplicates()?;
+        let tx = self.conn.transaction()?;
+        let mut results = Vec::new();
+        for pair in pairs {
+            results.push(merge_pair(&tx, pair)?);
+        }
+        tx.commit()?;
+        Ok(results)
+    }
+}
+
+/// Fold the removed ID's rows into the kept ID
+///
+/// Overlapping weeks keep each column's non-null value, taking the more recently
+/// updated row's value when both have one. Raw stats, roster snapshots, aliases,
+/// news and injury history only fill in what the kept ID is missing; notes are
+/// repointed.
+fn merge_pair(tx: &Transaction<'_>, pair: SignedIdPair) -> Result<DedupeResult> {
+    let kept = pair.kept.as_i64();
+    let removed = pair.removed.as_i64();
+
+    let assignments: Vec<String> = MERGED_COLUMNS
+        .iter()
+        .map(|column| {
+            format!(
+                "{column} = CASE WHEN r.updated_at > k.updated_at
+                     THEN COALESCE(r.{column}, k.{column})
+                     ELSE COALESCE(k.{column}, r.{column}) END"
+            )
+        })
+        .collect();
+    let weeks_merged = tx.execute(
+        &format!(
+            "UPDATE player_weekly_stats AS k SET {},
+                 created_at = MIN(k.created_at, r.created_at),
+                 updated_at = MAX(k.updated_at, r.updated_at)
+             FROM player_weekly_stats AS r
+             WHERE k.player_id = ?1 AND r.player_id = ?2
+               AND r.season = k.season AND r.week = k.week AND r.league_id = k.league_id",
+            assignments.join(",\n                 ")
+        ),
+        params![kept, removed],
+    )?;
+
+    // Rows the kept ID has no week for move over; merged ones are dropped
+    let weeks_moved = tx.execute(
+        "UPDATE OR IGNORE player_weekly_stats SET player_id = ?1 WHERE player_id = ?2",
+        params![kept, removed],
+    )?;
+    tx.execute(
+        "DELETE FROM player_weekly_stats WHERE player_id = ?1",
+        params![removed],
+    )?;
+
+    for table in [
+        "player_weekly_raw_stats",
+        "roster_snapshots",
+        "player_aliases",
+        "player_news",
+        "injury_status_history",
+    ] {
+        tx.execute(
+            &format!("UPDATE OR IGNORE {table} SET player_id = ?1 WHERE player_id = ?2"),
+            params![kept, removed],
+        )?;
+        tx.execute(
+            &format!("DELETE FROM {table} WHERE player_id = ?1"),
+            params![removed],
+        )?;
+    }
+    tx.execute(
+        "UPDATE player_notes SET player_id = ?1 WHERE player_id = ?2",
+        params![kept, removed],
+    )?;
+    tx.execute("DELETE FROM players WHERE player_id = ?1", params![removed])?;
+
+    Ok(DedupeResult {
+        pair,
+        weeks_merged,
+        weeks_moved,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{Player, PlayerWeeklyStats},
+        LeagueId, Season, Week,
+    };
+
+    const SEASON: Season = Season(2024);
+    const LEAGUE: LeagueId = LeagueId(12345);
+
+    fn add_player(db: &mut PlayerDatabase, id: i64, name: &str, position: &str) {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: None,
+        })
+        .unwrap();
+    }
+
+    fn add_week(
+        db: &mut PlayerDatabase,
+        id: i64,
+        week: u16,
+        projected: Option<f64>,
+        actual: Option<f64>,
+        updated_at: u64,
+    ) {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(LEAGUE),
+            ..PlayerWeeklyStats::test_minimal(
+                PlayerId::new(id),
+                SEASON,
+                Week::new(week),
+                projected,
+                actual,
+            )
+        };
+        db.upsert_weekly_stats(&stats, true).unwrap();
+        db.conn
+            .execute(
+                "UPDATE player_weekly_stats SET updated_at = ? WHERE player_id = ? AND week = ?",
+                params![updated_at, id, week],
+            )
+            .unwrap();
+    }
+
+    /// Bears D/ST under both signs, each with part of the season
+    fn conflicted_db() -> PlayerDatabase {
+        let mut db = PlayerDatabase::new_in_memory().unwrap();
+        add_player(&mut db, -16003, "Bears D/ST", "D/ST");
+        add_player(&mut db, 16003, "Bears D/ST", "D/ST");
+        // Sign-related IDs with different names are different players
+        add_player(&mut db, 4242, "Some Receiver", "WR");
+        add_player(&mut db, -4242, "Other Name", "WR");
+
+        // Week 1 only under the old positive ID
+        add_week(&mut db, 16003, 1, Some(7.0), Some(9.0), 100);
+        // Week 2 under both: projection only on one side, actuals disagree
+        add_week(&mut db, 16003, 2, Some(6.5), Some(4.0), 100);
+        add_week(&mut db, -16003, 2, None, Some(5.0), 200);
+        // Week 3 only under the current negative ID
+        add_week(&mut db, -16003, 3, Some(8.0), Some(12.0), 300);
+        db.add_player_note(PlayerId::new(16003), "Home vs rookie QB")
+            .unwrap();
+        // An old name, a headline and injury history only under the positive ID
+        db.conn
+            .execute_batch(
+                "INSERT INTO player_aliases (player_id, alias, replaced_at)
+                     VALUES (16003, 'Chicago D/ST', 50);
+                 INSERT INTO player_news (player_id, headline, published, fetched_at)
+                     VALUES (16003, 'Bears defense shuts out Detroit', 90, 100);
+                 INSERT INTO injury_status_history (player_id, season, week, status, observed_at)
+                     VALUES (16003, 2024, 1, 'ACTIVE', 100);",
+            )
+            .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_signed_id_pairs_detected() {
+        let db = conflicted_db();
+        assert_eq!(
+            db.find_signed_id_duplicates().unwrap(),
+            vec![SignedIdPair {
+                name: "Bears D/ST".to_string(),
+                position: "D/ST".to_string(),
+                kept: PlayerId::new(-16003),
+                removed: PlayerId::new(16003),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_dedupe_merges_into_one_complete_history() {
+        let mut db = conflicted_db();
+        let results = db.dedupe_signed_ids().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!((results[0].weeks_merged, results[0].weeks_moved), (1, 1));
+
+        let weeks: Vec<(u16, Option<f64>, Option<f64>)> = db
+            .get_player_season_stats(LEAGUE, PlayerId::new(-16003), SEASON)
+            .unwrap()
+            .iter()
+            .map(|s| (s.week.as_u16(), s.projected_points, s.actual_points))
+            .collect();
+        assert_eq!(
+            weeks,
+            vec![
+                (1, Some(7.0), Some(9.0)),
+                // Newer actual wins, the only projection is kept
+                (2, Some(6.5), Some(5.0)),
+                (3, Some(8.0), Some(12.0)),
+            ]
+        );
+
+        assert!(db
+            .get_player_season_stats(LEAGUE, PlayerId::new(16003), SEASON)
+            .unwrap()
+            .is_empty());
+        let ids: Vec<i64> = db
+            .get_all_players()
+            .unwrap()
+            .iter()
+            .map(|p| p.player_id.as_i64())
+            .collect();
+        assert!(ids.contains(&-16003) && !ids.contains(&16003));
+        let notes = db.get_notes_for_players(&[PlayerId::new(-16003)]).unwrap();
+        assert_eq!(notes[&PlayerId::new(-16003)].len(), 1);
+
+        // The old name now finds the kept player, along with their news and history
+        let aliases = db.list_player_aliases(None).unwrap();
+        assert_eq!(aliases.len(), 1);
+        assert_eq!(aliases[0].player_id, PlayerId::new(-16003));
+        assert_eq!(aliases[0].name.as_deref(), Some("Bears D/ST"));
+        let news = db.get_player_news(&[PlayerId::new(-16003)]).unwrap();
+        assert!(news.contains_key(&PlayerId::new(-16003)));
+        let history: Vec<i64> = db
+            .conn
+            .prepare("SELECT player_id FROM injury_status_history")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(history, vec![-16003]);
+
+        // Nothing left to merge
+        assert!(db.dedupe_signed_ids().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,70 @@
+//! Parquet output for season exports
+//!
+//! Rows are written as a single record batch with typed, nullable columns so they
+//! load directly into Polars/pandas without schema inference.
+
+use super::models::WeeklyExportRow;
+use anyhow::Result;
+use arrow_array::{
+    ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray, UInt16Array,
+};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+use std::{io::Write, sync::Arc};
+
+/// Arrow schema of exported player-weeks
+pub fn weekly_export_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("player_id", DataType::Int64, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("season", DataType::UInt16, false),
+        Field::new("week", DataType::UInt16, false),
+        Field::new("projected", DataType::Float64, true),
+        Field::new("actual", DataType::Float64, true),
+        Field::new("position", DataType::Utf8, false),
+        Field::new("team", DataType::Utf8, true),
+        Field::new("injury_status", DataType::Utf8, true),
+        Field::new("is_rostered", DataType::Boolean, true),
+    ])
+}
+
+/// Write player-weeks as Parquet to a file or other writer
+pub fn write_weekly_parquet<W: Write + Send>(rows: &[WeeklyExportRow], writer: W) -> Result<()> {
+    let schema = Arc::new(weekly_export_schema());
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from_iter_values(
+            rows.iter().map(|r| r.player_id.as_i64()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.name.as_str()),
+        )),
+        Arc::new(UInt16Array::from_iter_values(
+            rows.iter().map(|r| r.season.as_u16()),
+        )),
+        Arc::new(UInt16Array::from_iter_values(
+            rows.iter().map(|r| r.week.as_u16()),
+        )),
+        Arc::new(Float64Array::from_iter(
+            rows.iter().map(|r| r.projected_points),
+        )),
+        Arc::new(Float64Array::from_iter(
+            rows.iter().map(|r| r.actual_points),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|r| r.position.as_str()),
+        )),
+        Arc::new(StringArray::from_iter(
+            rows.iter().map(|r| r.team.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter(
+            rows.iter().map(|r| r.injury_status.as_deref()),
+        )),
+        Arc::new(BooleanArray::from_iter(rows.iter().map(|r| r.is_rostered))),
+    ];
+    let batch = RecordBatch::try_new(schema.clone(), columns)?;
+
+    let mut writer = ArrowWriter::try_new(writer, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
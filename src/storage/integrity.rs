@@ -0,0 +1,484 @@
+//! Consistency checks for stored weekly stats
+//!
+//! Projected and actual fetches merge into the same `player_weekly_stats` row, so a
+//! bug in either path can leave rows that no longer make sense. Each check here is a
+//! standalone query; `check_integrity` runs them all and the safe repairs are exposed
+//! separately so callers decide when to apply them.
+
+use super::schema::PlayerDatabase;
+use crate::{PlayerId, Season, Week};
+use anyhow::Result;
+use rusqlite::params;
+use serde::Serialize;
+
+/// Maximum number of example rows kept per check
+const MAX_EXAMPLES: usize = 5;
+
+/// Kind of anomaly found in `player_weekly_stats`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrityCheck {
+    /// Rows with points whose player is missing from `players`
+    OrphanedStats,
+    /// Roster-only rows (no points) whose player is missing from `players`
+    OrphanedRosterRows,
+    /// Projected points for weeks after the current week
+    FutureProjections,
+    /// Actual points for weeks after the current week
+    FutureActuals,
+    /// Negative projected or actual points
+    NegativePoints,
+}
+
+impl IntegrityCheck {
+    /// Human-readable description for reports
+    pub fn description(&self) -> &'static str {
+        match self {
+            IntegrityCheck::OrphanedStats => "Rows with points but no player record",
+            IntegrityCheck::OrphanedRosterRows => "Roster-only rows with no player record",
+            IntegrityCheck::FutureProjections => "Projected points for future weeks",
+            IntegrityCheck::FutureActuals => "Actual points for future weeks",
+            IntegrityCheck::NegativePoints => "Negative projected or actual points",
+        }
+    }
+
+    /// Whether `--fix` can repair this anomaly without losing real data
+    pub fn is_fixable(&self) -> bool {
+        matches!(
+            self,
+            IntegrityCheck::OrphanedStats
+                | IntegrityCheck::OrphanedRosterRows
+                | IntegrityCheck::FutureActuals
+        )
+    }
+}
+
+/// A single offending `player_weekly_stats` row
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AnomalyRow {
+    pub player_id: PlayerId,
+    pub season: Season,
+    pub week: Week,
+    pub projected_points: Option<f64>,
+    pub actual_points: Option<f64>,
+}
+
+/// Result of one check: total count plus a few example rows
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityIssue {
+    pub check: IntegrityCheck,
+    pub count: usize,
+    pub examples: Vec<AnomalyRow>,
+}
+
+impl IntegrityIssue {
+    fn from_rows(check: IntegrityCheck, rows: Vec<AnomalyRow>) -> Self {
+        Self {
+            check,
+            count: rows.len(),
+            examples: rows.into_iter().take(MAX_EXAMPLES).collect(),
+        }
+    }
+}
+
+/// Distinct players stored under the same name and position
+///
+/// Not an anomaly in itself (ESPN does list different players with the same name),
+/// but anything that resolves players by name will find these ambiguous.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DuplicatePlayerName {
+    pub name: String,
+    pub position: String,
+    pub player_ids: Vec<PlayerId>,
+}
+
+/// All check results for a database
+#[derive(Debug, Clone, Serialize)]
+pub struct IntegrityReport {
+    pub season: Season,
+    /// `None` when the current week is unknown and future-week checks were skipped
+    pub current_week: Option<Week>,
+    pub issues: Vec<IntegrityIssue>,
+    /// Reported as warnings; not counted in `total_anomalies`
+    pub duplicate_names: Vec<DuplicatePlayerName>,
+}
+
+impl IntegrityReport {
+    /// Total anomalies across all checks
+    pub fn total_anomalies(&self) -> usize {
+        self.issues.iter().map(|issue| issue.count).sum()
+    }
+}
+
+/// Rows repaired by `--fix`
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IntegrityFixSummary {
+    pub orphaned_rows_deleted: usize,
+    pub future_actuals_cleared: usize,
+}
+
+const ANOMALY_COLUMNS: &str = "s.player_id, s.season, s.week, s.projected_points, s.actual_points";
+
+impl PlayerDatabase {
+    fn query_anomalies(
+        &self,
+        where_clause: &str,
+        params: &[&dyn rusqlite::ToSql],
+    ) -> Result<Vec<AnomalyRow>> {
+        let query = format!(
+            "SELECT {ANOMALY_COLUMNS} FROM player_weekly_stats s
+             WHERE {where_clause}
+             ORDER BY s.season, s.week, s.player_id"
+        );
+        let mut stmt = self.conn.prepare(&query)?;
+        let rows = stmt.query_map(params, |row| {
+            Ok(AnomalyRow {
+                player_id: PlayerId::new(row.get(0)?),
+                season: Season::new(row.get(1)?),
+                week: Week::new(row.get(2)?),
+                projected_points: row.get(3)?,
+                actual_points: row.get(4)?,
+            })
+        })?;
+
+        let mut anomalies = Vec::new();
+        for row in rows {
+            anomalies.push(row?);
+        }
+        Ok(anomalies)
+    }
+
+    /// Rows with projected or actual points whose player is missing from `players`
+    pub fn find_orphaned_stats(&self) -> Result<Vec<AnomalyRow>> {
+        self.query_anomalies(
+            "(s.projected_points IS NOT NULL OR s.actual_points IS NOT NULL)
+               AND NOT EXISTS (SELECT 1 FROM players p WHERE p.player_id = s.player_id)",
+            &[],
+        )
+    }
+
+    /// Roster-only rows (no points) whose player is missing from `players`
+    pub fn find_orphaned_roster_rows(&self) -> Result<Vec<AnomalyRow>> {
+        self.query_anomalies(
+            "s.projected_points IS NULL AND s.actual_points IS NULL
+               AND NOT EXISTS (SELECT 1 FROM players p WHERE p.player_id = s.player_id)",
+            &[],
+        )
+    }
+
+    /// Projected points stored for weeks after `current_week`
+    pub fn find_future_projections(
+        &self,
+        season: Season,
+        current_week: Week,
+    ) -> Result<Vec<AnomalyRow>> {
+        self.query_anomalies(
+            "s.season = ? AND s.week > ? AND s.projected_points IS NOT NULL",
+            &[&season.as_u16(), &current_week.as_u16()],
+        )
+    }
+
+    /// Actual points stored for weeks after `current_week`
+    pub fn find_future_actuals(
+        &self,
+        season: Season,
+        current_week: Week,
+    ) -> Result<Vec<AnomalyRow>> {
+        self.query_anomalies(
+            "s.season = ? AND s.week > ? AND s.actual_points IS NOT NULL",
+            &[&season.as_u16(), &current_week.as_u16()],
+        )
+    }
+
+    /// Rows where projected or actual points are negative
+    pub fn find_negative_points(&self) -> Result<Vec<AnomalyRow>> {
+        self.query_anomalies("s.projected_points < 0 OR s.actual_points < 0", &[])
+    }
+
+    /// Players sharing a (case-insensitive) name and position with another player ID
+    pub fn find_duplicate_player_names(&self) -> Result<Vec<DuplicatePlayerName>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT MIN(name), position, GROUP_CONCAT(player_id)
+             FROM players
+             GROUP BY LOWER(TRIM(name)), position
+             HAVING COUNT(*) > 1
+             ORDER BY LOWER(TRIM(name)), position",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut duplicates = Vec::new();
+        for row in rows {
+            let (name, position, ids) = row?;
+            let mut player_ids: Vec<PlayerId> = ids
+                .split(',')
+                .filter_map(|id| id.parse().ok())
+                .map(PlayerId::new)
+                .collect();
+            player_ids.sort_by_key(|id| id.as_i64());
+            duplicates.push(DuplicatePlayerName {
+                name,
+                position,
+                player_ids,
+            });
+        }
+        Ok(duplicates)
+    }
+
+    /// Run every check; future-week checks are skipped without a current week
+    pub fn check_integrity(
+        &self,
+        season: Season,
+        current_week: Option<Week>,
+    ) -> Result<IntegrityReport> {
+        let mut issues = vec![
+            IntegrityIssue::from_rows(IntegrityCheck::OrphanedStats, self.find_orphaned_stats()?),
+            IntegrityIssue::from_rows(
+                IntegrityCheck::OrphanedRosterRows,
+                self.find_orphaned_roster_rows()?,
+            ),
+        ];
+
+        if let Some(week) = current_week {
+            issues.push(IntegrityIssue::from_rows(
+                IntegrityCheck::FutureProjections,
+                self.find_future_projections(season, week)?,
+            ));
+            issues.push(IntegrityIssue::from_rows(
+                IntegrityCheck::FutureActuals,
+                self.find_future_actuals(season, week)?,
+            ));
+        }
+
+        issues.push(IntegrityIssue::from_rows(
+            IntegrityCheck::NegativePoints,
+            self.find_negative_points()?,
+        ));
+
+        Ok(IntegrityReport {
+            season,
+            current_week,
+            issues,
+            duplicate_names: self.find_duplicate_player_names()?,
+        })
+    }
+
+    /// Delete weekly stats rows (with or without points) whose player no longer exists
+    pub fn delete_orphaned_rows(&mut self) -> Result<usize> {
+        let deleted = self.conn.execute(
+            "DELETE FROM player_weekly_stats
+             WHERE NOT EXISTS (SELECT 1 FROM players p WHERE p.player_id = player_weekly_stats.player_id)",
+            [],
+        )?;
+        Ok(deleted)
+    }
+
+    /// Null out actual points recorded for weeks after `current_week`
+    pub fn clear_future_actuals(&mut self, season: Season, current_week: Week) -> Result<usize> {
+        let cleared = self.conn.execute(
+            "UPDATE player_weekly_stats SET actual_points = NULL
+             WHERE season = ? AND week > ? AND actual_points IS NOT NULL",
+            params![season.as_u16(), current_week.as_u16()],
+        )?;
+        Ok(cleared)
+    }
+
+    /// Apply the safe repairs: delete orphans and clear future actuals
+    pub fn fix_integrity(
+        &mut self,
+        season: Season,
+        current_week: Option<Week>,
+    ) -> Result<IntegrityFixSummary> {
+        let mut summary = IntegrityFixSummary {
+            orphaned_rows_deleted: self.delete_orphaned_rows()?,
+            ..Default::default()
+        };
+        if let Some(week) = current_week {
+            summary.future_actuals_cleared = self.clear_future_actuals(season, week)?;
+        }
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        storage::{Player, PlayerWeeklyStats},
+        LeagueId,
+    };
+
+    const LEAGUE: LeagueId = LeagueId(12345);
+
+    fn seeded_db() -> PlayerDatabase {
+        let mut db = PlayerDatabase::new_in_memory().unwrap();
+        // Orphans can only exist when foreign keys are not enforced (as on disk)
+        db.conn.execute("PRAGMA foreign_keys = OFF", []).unwrap();
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(1),
+            name: "Known Player".to_string(),
+            position: "QB".to_string(),
+            team: None,
+        })
+        .unwrap();
+        db
+    }
+
+    fn insert_stats(
+        db: &mut PlayerDatabase,
+        player_id: i64,
+        week: u16,
+        projected: Option<f64>,
+        actual: Option<f64>,
+    ) {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(LEAGUE),
+            ..PlayerWeeklyStats::test_minimal(
+                PlayerId::new(player_id),
+                Season::new(2025),
+                Week::new(week),
+                projected,
+                actual,
+            )
+        };
+        db.upsert_weekly_stats(&stats, true).unwrap();
+    }
+
+    #[test]
+    fn test_clean_database_has_no_anomalies() {
+        let mut db = seeded_db();
+        insert_stats(&mut db, 1, 1, Some(15.0), Some(17.5));
+
+        let report = db
+            .check_integrity(Season::new(2025), Some(Week::new(3)))
+            .unwrap();
+        assert_eq!(report.total_anomalies(), 0);
+        assert_eq!(report.issues.len(), 5);
+    }
+
+    #[test]
+    fn test_orphaned_stats_detected_and_fixed() {
+        let mut db = seeded_db();
+        insert_stats(&mut db, 99, 1, Some(10.0), None);
+        insert_stats(&mut db, 98, 1, None, None);
+
+        let orphans = db.find_orphaned_stats().unwrap();
+        assert_eq!(orphans.len(), 1);
+        assert_eq!(orphans[0].player_id, PlayerId::new(99));
+
+        let roster_only = db.find_orphaned_roster_rows().unwrap();
+        assert_eq!(roster_only.len(), 1);
+        assert_eq!(roster_only[0].player_id, PlayerId::new(98));
+
+        assert_eq!(db.delete_orphaned_rows().unwrap(), 2);
+        assert!(db.find_orphaned_stats().unwrap().is_empty());
+        assert!(db.find_orphaned_roster_rows().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_future_weeks_detected() {
+        let mut db = seeded_db();
+        insert_stats(&mut db, 1, 2, Some(12.0), Some(14.0));
+        insert_stats(&mut db, 1, 5, Some(12.0), None);
+        insert_stats(&mut db, 1, 6, None, Some(9.0));
+
+        let season = Season::new(2025);
+        let current = Week::new(4);
+        let projections = db.find_future_projections(season, current).unwrap();
+        assert_eq!(projections.len(), 1);
+        assert_eq!(projections[0].week, Week::new(5));
+
+        let actuals = db.find_future_actuals(season, current).unwrap();
+        assert_eq!(actuals.len(), 1);
+        assert_eq!(actuals[0].week, Week::new(6));
+
+        // Fix nulls the future actual but leaves projections and past data alone
+        let summary = db.fix_integrity(season, Some(current)).unwrap();
+        assert_eq!(summary.future_actuals_cleared, 1);
+        assert!(db.find_future_actuals(season, current).unwrap().is_empty());
+        assert_eq!(
+            db.find_future_projections(season, current).unwrap().len(),
+            1
+        );
+        let week_two = db
+            .get_weekly_stats(LEAGUE, PlayerId::new(1), season, Week::new(2))
+            .unwrap();
+        assert_eq!(week_two.unwrap().actual_points, Some(14.0));
+    }
+
+    #[test]
+    fn test_negative_points_detected() {
+        let mut db = seeded_db();
+        insert_stats(&mut db, 1, 1, Some(-1.0), Some(3.0));
+        insert_stats(&mut db, 1, 2, Some(5.0), Some(-2.5));
+
+        let negatives = db.find_negative_points().unwrap();
+        assert_eq!(negatives.len(), 2);
+        assert!(!IntegrityCheck::NegativePoints.is_fixable());
+    }
+
+    #[test]
+    fn test_future_checks_skipped_without_current_week() {
+        let mut db = seeded_db();
+        insert_stats(&mut db, 1, 17, None, Some(9.0));
+
+        let report = db.check_integrity(Season::new(2025), None).unwrap();
+        assert!(report
+            .issues
+            .iter()
+            .all(|issue| issue.check != IntegrityCheck::FutureActuals));
+        assert_eq!(report.total_anomalies(), 0);
+
+        let summary = db.fix_integrity(Season::new(2025), None).unwrap();
+        assert_eq!(summary.future_actuals_cleared, 0);
+    }
+
+    #[test]
+    fn test_examples_are_capped() {
+        let mut db = seeded_db();
+        for week in 1..=8 {
+            insert_stats(&mut db, 50, week, Some(1.0), None);
+        }
+
+        let report = db.check_integrity(Season::new(2025), None).unwrap();
+        let orphaned = &report.issues[0];
+        assert_eq!(orphaned.check, IntegrityCheck::OrphanedStats);
+        assert_eq!(orphaned.count, 8);
+        assert_eq!(orphaned.examples.len(), MAX_EXAMPLES);
+    }
+
+    #[test]
+    fn test_duplicate_player_names_reported() {
+        let mut db = seeded_db();
+        for (id, name, position) in [
+            (2, "known player", "QB"),
+            (3, "Known Player", "WR"),
+            (4, "Other Player", "QB"),
+        ] {
+            db.upsert_player(&Player {
+                player_id: PlayerId::new(id),
+                name: name.to_string(),
+                position: position.to_string(),
+                team: None,
+            })
+            .unwrap();
+        }
+
+        let report = db.check_integrity(Season::new(2025), None).unwrap();
+        assert_eq!(
+            report.duplicate_names,
+            vec![DuplicatePlayerName {
+                name: "Known Player".to_string(),
+                position: "QB".to_string(),
+                player_ids: vec![PlayerId::new(1), PlayerId::new(2)],
+            }]
+        );
+        // Warnings only, not anomalies
+        assert_eq!(report.total_anomalies(), 0);
+    }
+}
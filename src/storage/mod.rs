@@ -6,12 +6,19 @@
 //! - `schema`: Database connection and schema management
 //! - `queries`: Basic CRUD operations
 //! - `analysis`: Complex analysis and projection operations
+//! - `integrity`: Consistency checks and safe repairs for stored stats
+//! - `dedupe`: Merging players stored under both signs of an ESPN ID
+//! - `export`: Parquet output for exported rows (`parquet` feature)
 
 pub mod analysis;
+pub mod dedupe;
+#[cfg(feature = "parquet")]
+pub mod export;
+pub mod integrity;
 pub mod models;
 pub mod queries;
 pub mod schema;
 
 // Re-export the main types and database struct for easy access
 pub use models::*;
-pub use schema::PlayerDatabase;
+pub use schema::{set_database_path, PlayerDatabase};
@@ -1,6 +1,10 @@
 //! Data models for the storage layer
 
-use crate::{espn::types::InjuryStatus, PlayerId, Season, Week};
+use crate::{
+    cli::{render::skip_none, types::filters::RosterStatusFilter},
+    espn::types::{InjuryStatus, LeagueSettings},
+    LeagueId, PlayerId, Position, Season, Week,
+};
 use serde::{Deserialize, Serialize};
 
 /// Player information stored in the database
@@ -12,12 +16,30 @@ pub struct Player {
     pub team: Option<String>,
 }
 
+impl Player {
+    /// The stored form of an ESPN player
+    pub fn from_espn(player: &crate::espn::types::Player) -> Self {
+        Self {
+            player_id: PlayerId::new(player.id),
+            name: player.display_name(),
+            position: player
+                .position()
+                .map_or_else(|| "UNKNOWN".to_string(), |p| p.to_string()),
+            team: player.pro_team().map(str::to_string),
+        }
+    }
+}
+
 /// Weekly statistics for a player
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlayerWeeklyStats {
     pub player_id: PlayerId,
     pub season: Season,
     pub week: Week,
+    /// League whose scoring and rosters the row was stored with; `None` for rows
+    /// from before leagues were tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub league_id: Option<LeagueId>,
     pub projected_points: Option<f64>,
     pub actual_points: Option<f64>,
     pub active: Option<bool>,
@@ -26,6 +48,16 @@ pub struct PlayerWeeklyStats {
     pub is_rostered: Option<bool>,
     pub fantasy_team_id: Option<u32>,
     pub fantasy_team_name: Option<String>,
+    /// Whether the player took part in the game; `None` when unknown (projections, older rows)
+    pub played: Option<bool>,
+    /// Share of ESPN leagues rostering the player when the week was fetched (0-100)
+    pub percent_owned: Option<f64>,
+    /// Share of ESPN leagues starting the player when the week was fetched (0-100)
+    pub percent_started: Option<f64>,
+    /// Abbreviation of the NFL team the player faced (e.g. "KC"); `None` on a bye or unknown
+    pub opponent_team: Option<String>,
+    /// Whether the player's team played at home
+    pub home_game: Option<bool>,
     pub created_at: u64,
     pub updated_at: u64,
 }
@@ -43,6 +75,7 @@ impl PlayerWeeklyStats {
             player_id,
             season,
             week,
+            league_id: None,
             projected_points,
             actual_points,
             active: Some(true),
@@ -51,6 +84,11 @@ impl PlayerWeeklyStats {
             is_rostered: Some(false),
             fantasy_team_id: None,
             fantasy_team_name: None,
+            played: None,
+            percent_owned: None,
+            percent_started: None,
+            opponent_team: None,
+            home_game: None,
             created_at: 0,
             updated_at: 0,
         }
@@ -70,6 +108,7 @@ impl PlayerWeeklyStats {
             player_id,
             season,
             week,
+            league_id: None,
             projected_points,
             actual_points,
             active: Some(true),
@@ -78,6 +117,11 @@ impl PlayerWeeklyStats {
             is_rostered: Some(false),
             fantasy_team_id: None,
             fantasy_team_name: None,
+            played: None,
+            percent_owned: None,
+            percent_started: None,
+            opponent_team: None,
+            home_game: None,
             created_at,
             updated_at,
         }
@@ -86,9 +130,11 @@ impl PlayerWeeklyStats {
 
 /// Analysis of projection accuracy for a player
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub struct ProjectionAnalysis {
     pub name: String,
     pub position: String,
+    #[serde(default, skip_serializing_if = "skip_none")]
     pub team: Option<String>,
     pub avg_error: f64, // Positive = overestimated, Negative = underestimated
     pub games_count: u32,
@@ -96,14 +142,724 @@ pub struct ProjectionAnalysis {
 
 /// Performance estimation for next week
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub struct PerformanceEstimate {
     pub player_id: PlayerId,
     pub name: String,
     pub position: String,
+    #[serde(default, skip_serializing_if = "skip_none")]
     pub team: Option<String>,
     pub espn_projection: f64,  // Original ESPN projection
     pub bias_adjustment: f64,  // +/- adjustment applied
     pub estimated_points: f64, // Final adjusted estimate
     pub confidence: f64,       // 0.0 to 1.0
     pub reasoning: String,
+    /// Standard deviation of past projection errors; `None` with too little history
+    #[serde(default, skip_serializing_if = "skip_none")]
+    pub error_std: Option<f64>,
+    /// Weeks left out of the bias average because the player didn't play (out or DNP)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dnp_weeks: Option<u32>,
+    /// Recent-form blend applied on top of the bias adjustment (`--blend-recent`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blend: Option<RecentFormBlend>,
+    /// Weeks behind the bias average (`--show-history`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history: Option<Vec<BiasHistoryWeek>>,
+    /// Share of ESPN leagues starting the player this week; `None` when ESPN didn't say
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub percent_started: Option<f64>,
+    /// Start/sit tier (`--tiers`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tier: Option<StartTier>,
+    /// Whether the player's NFL game has kicked off; only set while the week is in progress
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    /// Estimated points above the position's replacement level (value over replacement);
+    /// `None` for positions without one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vorp: Option<f64>,
+}
+
+/// Start/sit advice from an estimate's standing against its position's replacement level
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StartTier {
+    MustStart,
+    SolidStart,
+    FlexConsideration,
+    Sit,
+}
+
+impl StartTier {
+    /// Every tier, best first
+    pub const ALL: [StartTier; 4] = [
+        StartTier::MustStart,
+        StartTier::SolidStart,
+        StartTier::FlexConsideration,
+        StartTier::Sit,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StartTier::MustStart => "Must start",
+            StartTier::SolidStart => "Solid start",
+            StartTier::FlexConsideration => "Flex consideration",
+            StartTier::Sit => "Sit",
+        }
+    }
+}
+
+/// Why a week was left out of a player's bias average
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryExclusion {
+    /// Projected and actual were both 0
+    Bye,
+    /// Ruled out (Out or IR) and didn't play
+    Out,
+    /// Didn't play for another reason
+    Dnp,
+}
+
+impl HistoryExclusion {
+    pub fn label(&self) -> &'static str {
+        match self {
+            HistoryExclusion::Bye => "bye",
+            HistoryExclusion::Out => "out",
+            HistoryExclusion::Dnp => "DNP",
+        }
+    }
+}
+
+/// One week of a player's projection history
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BiasHistoryWeek {
+    pub week: Week,
+    pub projected: f64,
+    pub actual: f64,
+    /// Projected minus actual; positive when ESPN overestimated
+    pub error: f64,
+    /// Set when the week doesn't count toward the bias average
+    pub excluded: Option<HistoryExclusion>,
+}
+
+/// How a recent-form estimate was blended into a bias-adjusted projection
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct RecentFormBlend {
+    /// Weight on recent form (0 = bias-adjusted ESPN only, 1 = recent form only)
+    pub weight: f64,
+    /// Bias-adjusted ESPN projection before blending
+    pub adjusted_projection: f64,
+    /// Recency-weighted average of the last few actual games
+    pub recent_form: f64,
+    /// Number of games in the recent-form average
+    pub games: u32,
+}
+
+/// A single fantasy team's total for one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamWeekScore {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub week: Week,
+    pub points: f64,
+}
+
+/// Result of a decided matchup, used for blowout/closest game records
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MatchupMargin {
+    pub week: Week,
+    pub winner: TeamWeekScore,
+    pub loser: TeamWeekScore,
+    pub margin: f64,
+}
+
+/// Consecutive wins for a fantasy team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WinStreak {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub length: u32,
+    pub start_week: Week,
+    pub end_week: Week,
+}
+
+/// Season summary for one fantasy team
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamSeasonRecord {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub wins: u32,
+    pub losses: u32,
+    pub ties: u32,
+    pub best_week: Option<TeamWeekScore>,
+    pub worst_week: Option<TeamWeekScore>,
+}
+
+/// Season-long records computed from completed weeks only
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonRecords {
+    pub season: Season,
+    /// Weeks where every matchup has a final result
+    pub included_weeks: Vec<Week>,
+    /// Weeks skipped because some teams are still missing actuals
+    pub excluded_weeks: Vec<Week>,
+    pub highest_score: Option<TeamWeekScore>,
+    pub lowest_score: Option<TeamWeekScore>,
+    pub longest_win_streak: Option<WinStreak>,
+    pub biggest_blowout: Option<MatchupMargin>,
+    pub closest_game: Option<MatchupMargin>,
+    pub teams: Vec<TeamSeasonRecord>,
+    /// Matchup periods played over several weeks (e.g. a two-week championship);
+    /// their scores are totals across all of those weeks
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub multi_week_periods: std::collections::BTreeMap<u16, Vec<Week>>,
+}
+
+impl SeasonRecords {
+    /// Fill in display names for every team referenced in the records
+    pub fn apply_team_names(&mut self, names: &std::collections::HashMap<u32, String>) {
+        let name_for = |team_id: u32| names.get(&team_id).cloned();
+
+        for score in [self.highest_score.as_mut(), self.lowest_score.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            score.team_name = name_for(score.team_id);
+        }
+
+        for margin in [self.biggest_blowout.as_mut(), self.closest_game.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            margin.winner.team_name = name_for(margin.winner.team_id);
+            margin.loser.team_name = name_for(margin.loser.team_id);
+        }
+
+        if let Some(streak) = self.longest_win_streak.as_mut() {
+            streak.team_name = name_for(streak.team_id);
+        }
+
+        for team in &mut self.teams {
+            team.team_name = name_for(team.team_id);
+            for score in [team.best_week.as_mut(), team.worst_week.as_mut()]
+                .into_iter()
+                .flatten()
+            {
+                score.team_name = name_for(score.team_id);
+            }
+        }
+    }
+}
+
+/// Performance estimate with its standing inside a position group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankedEstimate {
+    #[serde(flatten)]
+    pub estimate: PerformanceEstimate,
+    /// 1-based rank within the position, in the same order as the flat view
+    pub positional_rank: u32,
+    /// Estimated points minus the group's replacement level
+    pub points_above_replacement: f64,
+}
+
+/// Estimates for one position with within-group statistics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionGroup {
+    pub position: String,
+    pub average_estimate: f64,
+    /// Estimate of the first player outside the league-wide starter pool
+    pub replacement_level: f64,
+    pub players: Vec<RankedEstimate>,
+}
+
+/// Coarse lineup slot used when comparing rosters between weeks
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SlotGroup {
+    Starter,
+    Bench,
+    #[serde(rename = "IR")]
+    Ir,
+}
+
+impl SlotGroup {
+    /// Map an ESPN lineup slot ID (20 = bench, 21 = IR) to its group
+    pub fn from_lineup_slot(slot_id: u8) -> Self {
+        match slot_id {
+            20 => SlotGroup::Bench,
+            21 => SlotGroup::Ir,
+            _ => SlotGroup::Starter,
+        }
+    }
+}
+
+impl std::fmt::Display for SlotGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SlotGroup::Starter => write!(f, "starter"),
+            SlotGroup::Bench => write!(f, "bench"),
+            SlotGroup::Ir => write!(f, "IR"),
+        }
+    }
+}
+
+/// One player on a fantasy roster as of a given week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RosterSnapshotEntry {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub player_id: PlayerId,
+    pub player_name: Option<String>,
+    /// Player's position from the players table; `None` when the player isn't stored
+    pub position: Option<String>,
+    pub lineup_slot_id: u8,
+}
+
+/// A rostered player's stored actual points for one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamWeekPlayer {
+    pub player_id: PlayerId,
+    pub player_name: Option<String>,
+    pub lineup_slot_id: u8,
+    /// `None` when no actual points are stored for the week
+    pub points: Option<f64>,
+}
+
+/// One fantasy team's starter and bench points for a week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamWeekTotal {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    /// Points from players in starting slots
+    pub starter_points: f64,
+    /// Points left on the bench (IR excluded)
+    pub bench_points: f64,
+    /// Highest- and lowest-scoring starters
+    pub top_scorer: Option<TeamWeekPlayer>,
+    pub bottom_scorer: Option<TeamWeekPlayer>,
+    /// Starters without stored actual points; the totals are partial when non-empty
+    pub missing_starters: Vec<TeamWeekPlayer>,
+}
+
+impl TeamWeekTotal {
+    /// Whether every starter's points are stored
+    pub fn is_complete(&self) -> bool {
+        self.missing_starters.is_empty()
+    }
+}
+
+/// A league's scoring settings as stored in the database
+#[derive(Debug, Clone)]
+pub struct StoredLeagueSettings {
+    pub settings: LeagueSettings,
+    /// When the settings were fetched from ESPN, in Unix seconds
+    pub fetched_at: u64,
+}
+
+/// A player on a fantasy roster for a week, with the week's stored points
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RosterPlayer {
+    pub player_id: PlayerId,
+    pub player_name: Option<String>,
+    pub position: Option<String>,
+    pub lineup_slot_id: u8,
+    pub slot: SlotGroup,
+    /// `None` when no projection is stored for the week
+    pub projected_points: Option<f64>,
+    /// `None` when no actual points are stored for the week
+    pub points: Option<f64>,
+}
+
+/// One fantasy team's roster for a week: starters, then bench, then IR
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamWeekRoster {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub players: Vec<RosterPlayer>,
+}
+
+/// A player's actual points in a streaming recap
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamingPick {
+    pub player_id: PlayerId,
+    pub player_name: Option<String>,
+    pub points: f64,
+}
+
+/// One week of a team's K or D/ST results against the alternatives
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamingWeek {
+    pub week: Week,
+    /// The team's starters at the position; `points` is `None` when not stored
+    pub started: Vec<TeamWeekPlayer>,
+    pub started_points: f64,
+    /// Highest-scoring player at the position on no roster that week
+    pub best_free_agent: Option<StreamingPick>,
+    /// What the season's top scorer at the position put up that week
+    pub season_leader_points: f64,
+}
+
+/// Whether a team would have done better holding the position's top scorer
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StreamingVerdict {
+    /// The team's own starts matched or beat holding the season leader
+    Stream,
+    /// Holding the season leader every week would have scored more
+    Hold,
+}
+
+/// A fantasy team's season at K or D/ST: its actual starts, perfect streaming
+/// (the best free agent every week) and holding the season's top scorer
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StreamingRecap {
+    pub position: String,
+    /// Top scorer at the position over the recap's weeks, with their total
+    pub season_leader: Option<StreamingPick>,
+    pub weeks: Vec<StreamingWeek>,
+    pub started_total: f64,
+    pub perfect_streaming_total: f64,
+    pub holding_total: f64,
+    /// `None` when there are no weeks to compare
+    pub verdict: Option<StreamingVerdict>,
+}
+
+/// A player added, dropped or moved between two roster snapshots
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RosterChange {
+    pub player_id: PlayerId,
+    pub player_name: Option<String>,
+    pub position: Option<String>,
+    /// Slot in the earlier week; `None` for adds
+    pub from_slot: Option<SlotGroup>,
+    /// Slot in the later week; `None` for drops
+    pub to_slot: Option<SlotGroup>,
+    /// Actual points for each week after the earlier snapshot through the later one
+    pub weekly_points: Vec<Option<f64>>,
+    pub total_points: f64,
+}
+
+/// Roster churn for one fantasy team between two weeks
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TeamRosterDiff {
+    pub team_id: u32,
+    pub team_name: Option<String>,
+    pub added: Vec<RosterChange>,
+    pub dropped: Vec<RosterChange>,
+    pub moved: Vec<RosterChange>,
+}
+
+impl TeamRosterDiff {
+    /// Whether the team's roster is unchanged between the two weeks
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.dropped.is_empty() && self.moved.is_empty()
+    }
+}
+
+/// Kind of stored data counted by the coverage grid
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CoverageDataType {
+    /// Weekly stats rows with actual points
+    ActualPoints,
+    /// Weekly stats rows with projected points
+    ProjectedPoints,
+    /// Players in stored fantasy roster snapshots
+    RosterSnapshots,
+    /// Weekly stats rows with a recorded roster status (rostered or free agent)
+    Ownership,
+}
+
+impl CoverageDataType {
+    /// Row label for the coverage grid
+    pub fn label(&self) -> &'static str {
+        match self {
+            CoverageDataType::ActualPoints => "Actual points",
+            CoverageDataType::ProjectedPoints => "Projected points",
+            CoverageDataType::RosterSnapshots => "Roster snapshots",
+            CoverageDataType::Ownership => "Ownership",
+        }
+    }
+}
+
+/// How well a week is covered relative to the expected row count
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CoverageLevel {
+    Full,
+    Partial,
+    Missing,
+}
+
+impl CoverageLevel {
+    /// Grid cell symbol
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CoverageLevel::Full => "✓",
+            CoverageLevel::Partial => "◐",
+            CoverageLevel::Missing => "✗",
+        }
+    }
+}
+
+/// Weekly row counts for one data type
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoverageRow {
+    pub data_type: CoverageDataType,
+    /// Row count for each week, starting with week 1
+    pub weekly_counts: Vec<u32>,
+    /// Rows expected in a fully covered week: the best-covered week of the season
+    pub expected: u32,
+    pub total: u32,
+}
+
+impl CoverageRow {
+    /// Build a row from per-week counts, taking the busiest week as the expected count
+    pub fn new(data_type: CoverageDataType, weekly_counts: Vec<u32>) -> Self {
+        Self {
+            data_type,
+            expected: weekly_counts.iter().copied().max().unwrap_or(0),
+            total: weekly_counts.iter().sum(),
+            weekly_counts,
+        }
+    }
+
+    /// Coverage of a week (1-based); weeks with fewer than `partial_below` of the
+    /// expected rows are partial
+    pub fn level(&self, week: u16, partial_below: f64) -> CoverageLevel {
+        let count = week
+            .checked_sub(1)
+            .and_then(|index| self.weekly_counts.get(index as usize))
+            .copied()
+            .unwrap_or(0);
+        if count == 0 {
+            CoverageLevel::Missing
+        } else if (count as f64) < self.expected as f64 * partial_below.clamp(0.0, 1.0) {
+            CoverageLevel::Partial
+        } else {
+            CoverageLevel::Full
+        }
+    }
+}
+
+/// Stored data per week for a season
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DataCoverage {
+    pub season: Season,
+    pub weeks: u16,
+    pub rows: Vec<CoverageRow>,
+}
+
+/// One player-week of stored data, flattened for export
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeeklyExportRow {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub season: Season,
+    pub week: Week,
+    pub projected_points: Option<f64>,
+    pub actual_points: Option<f64>,
+    pub position: String,
+    /// NFL team abbreviation
+    pub team: Option<String>,
+    pub injury_status: Option<String>,
+    pub is_rostered: Option<bool>,
+}
+
+/// The raw stat category, weeks and players [`PlayerDatabase::get_category_leaders`] ranks
+///
+/// [`PlayerDatabase::get_category_leaders`]: super::PlayerDatabase::get_category_leaders
+#[derive(Debug, Clone, Copy)]
+pub struct CategoryLeadersQuery<'a> {
+    pub stat_id: u16,
+    pub season: Season,
+    /// Single week, or `None` for season totals
+    pub week: Option<Week>,
+    pub positions: Option<&'a [Position]>,
+    pub roster_status: Option<&'a RosterStatusFilter>,
+    pub limit: usize,
+}
+
+/// A player's total in one raw stat category, for a week or a whole season
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CategoryLeader {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    /// NFL team abbreviation
+    pub team: Option<String>,
+    pub value: f64,
+    /// Actual fantasy points over the same week(s)
+    pub fantasy_points: Option<f64>,
+    /// Weeks with a non-zero value in the category
+    pub games: u32,
+}
+
+/// A player's finish among their position in one week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeeklyPositionRank {
+    pub week: Week,
+    pub points: f64,
+    /// 1 is the week's top scorer; tied players share the better rank
+    pub rank: u32,
+}
+
+/// A player's weekly positional finishes over a season, summarized
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PositionalRankHistory {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    /// NFL team abbreviation
+    pub team: Option<String>,
+    /// Weeks played with stored actual points
+    pub games: u32,
+    pub top_12: u32,
+    pub top_24: u32,
+    pub median_rank: f64,
+    /// Population variance of the weekly ranks
+    pub rank_variance: f64,
+    /// Oldest week first
+    pub weeks: Vec<WeeklyPositionRank>,
+}
+
+/// A player's full-season point pace against a preseason expectation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SeasonPace {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    /// NFL team abbreviation
+    pub team: Option<String>,
+    /// Actual points so far
+    pub points: f64,
+    /// Games played; byes and weeks the player didn't play are left out
+    pub games: u32,
+    /// Games the player's team has left
+    pub remaining_games: u32,
+    /// Points so far plus the per-game average over the remaining games
+    pub pace: f64,
+    /// Earliest stored ESPN weekly projection over a full season; `None` without one
+    pub expectation: Option<f64>,
+    /// Week whose projection the expectation comes from
+    pub expectation_week: Option<Week>,
+    /// Pace minus the expectation
+    pub vs_expectation: Option<f64>,
+}
+
+/// How close projections came to the actual points at one position
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PositionAccuracy {
+    /// Position, or `ALL` for every position together
+    pub position: String,
+    /// Player-weeks scored; byes and weeks the player didn't play are left out
+    pub games: u32,
+    pub espn_mae: f64,
+    pub espn_rmse: f64,
+    /// Errors of the bias-adjusted projections, each built from earlier weeks only
+    pub adjusted_mae: f64,
+    pub adjusted_rmse: f64,
+    /// How much lower the adjusted MAE is than ESPN's, in percent (negative when
+    /// higher); `None` when ESPN's MAE is 0
+    pub improvement_pct: Option<f64>,
+}
+
+/// How one player's stored numbers moved between two weeks
+///
+/// Each side is `None` when nothing was stored for that week; a delta needs both.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WeekOverWeekChange {
+    pub player_id: PlayerId,
+    pub name: String,
+    pub position: String,
+    /// NFL team abbreviation
+    pub team: Option<String>,
+    /// Roster status in the later week (the earlier one if the later week isn't stored)
+    pub is_rostered: Option<bool>,
+    pub fantasy_team_name: Option<String>,
+    pub actual_from: Option<f64>,
+    pub actual_to: Option<f64>,
+    pub actual_delta: Option<f64>,
+    /// ESPN's weekly projection stored for each week
+    pub projected_from: Option<f64>,
+    pub projected_to: Option<f64>,
+    pub projected_delta: Option<f64>,
+    /// ESPN ownership percentage
+    pub owned_from: Option<f64>,
+    pub owned_to: Option<f64>,
+    pub owned_delta: Option<f64>,
+    pub injury_status_from: Option<String>,
+    pub injury_status_to: Option<String>,
+}
+
+impl WeekOverWeekChange {
+    /// Whether the injury designation is known in both weeks and differs
+    pub fn status_changed(&self) -> bool {
+        matches!(
+            (&self.injury_status_from, &self.injury_status_to),
+            (Some(from), Some(to)) if from != to
+        )
+    }
+}
+
+/// A local note attached to a player
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerNote {
+    pub note_id: i64,
+    pub player_id: PlayerId,
+    /// `None` when the player isn't in the `players` table (e.g. after `--clear-db`)
+    pub player_name: Option<String>,
+    pub note: String,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+}
+
+/// An earlier name ESPN used for a player
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerAlias {
+    pub player_id: PlayerId,
+    /// The old name
+    pub alias: String,
+    /// The player's current name; `None` when the player isn't stored
+    pub name: Option<String>,
+    /// When the rename was seen (Unix seconds)
+    pub replaced_at: u64,
+}
+
+/// A change in a player's injury designation seen between two fetches of a week
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InjuryStatusChange {
+    pub player_id: PlayerId,
+    pub season: Season,
+    pub week: Week,
+    /// The designation recorded before the change
+    pub previous_status: InjuryStatus,
+    pub status: InjuryStatus,
+    /// When the new designation was first seen (Unix seconds)
+    pub observed_at: u64,
+}
+
+/// The latest ESPN news headline stored for a player
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlayerHeadline {
+    pub player_id: PlayerId,
+    pub headline: String,
+    /// When ESPN published the item (Unix seconds); `None` when it wasn't dated
+    pub published: Option<u64>,
+    /// When the headline was fetched (Unix seconds)
+    pub fetched_at: u64,
+}
+
+/// One name a fantasy team went by during a season, with the weeks it was seen
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FantasyTeamName {
+    pub league_id: LeagueId,
+    pub season: Season,
+    pub team_id: u32,
+    pub name: String,
+    pub abbrev: Option<String>,
+    pub first_seen_week: Week,
+    pub last_seen_week: Week,
 }
@@ -1,12 +1,114 @@
 //! Basic database query operations
 
-use super::{models::*, schema::PlayerDatabase};
+use super::{
+    models::*,
+    schema::{PlayerDatabase, UNASSIGNED_LEAGUE},
+};
+use crate::cli::types::filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter};
 use crate::commands::common::CommandParams;
-use crate::{PlayerId, Position, Season, Week};
+use crate::core::metrics::record_rows_written;
+use crate::espn::{
+    teams::{defense_name, defense_pro_team_id, pro_team_abbrev},
+    types::{InjuryStatus, LeagueSettings},
+};
+use crate::{LeagueId, PlayerId, Position, Season, Week};
 use anyhow::Result;
-use rusqlite::{params, Row};
+use rusqlite::{params, OptionalExtension, Row};
+use std::collections::HashMap;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Whether `name` is a stand-in used when ESPN sends no name: `Player {id}`, or a
+/// team defense named after its NFL team
+fn is_placeholder_name(player_id: PlayerId, name: &str) -> bool {
+    name == format!("Player {}", player_id.as_i64())
+        || defense_pro_team_id(player_id.as_i64())
+            .and_then(defense_name)
+            .is_some_and(|defense| defense == name)
+}
+
+/// On a clash with the stored row, keep stored values the new row doesn't have, but
+/// always take its roster status
+const MERGE_ON_CONFLICT: &str = "ON CONFLICT (player_id, season, week, league_id) DO UPDATE SET
+     projected_points = COALESCE(excluded.projected_points, projected_points),
+     actual_points = COALESCE(excluded.actual_points, actual_points),
+     active = COALESCE(excluded.active, active),
+     injured = COALESCE(excluded.injured, injured),
+     injury_status = COALESCE(excluded.injury_status, injury_status),
+     is_rostered = excluded.is_rostered,
+     fantasy_team_id = excluded.fantasy_team_id,
+     fantasy_team_name = excluded.fantasy_team_name,
+     played = COALESCE(excluded.played, played),
+     percent_owned = COALESCE(excluded.percent_owned, percent_owned),
+     percent_started = COALESCE(excluded.percent_started, percent_started),
+     opponent_team = COALESCE(excluded.opponent_team, opponent_team),
+     home_game = COALESCE(excluded.home_game, home_game),
+     updated_at = excluded.updated_at";
+
+/// On a clash with the stored row, overwrite everything but its creation time
+const REPLACE_ON_CONFLICT: &str = "ON CONFLICT (player_id, season, week, league_id) DO UPDATE SET
+     projected_points = excluded.projected_points,
+     actual_points = excluded.actual_points,
+     active = excluded.active,
+     injured = excluded.injured,
+     injury_status = excluded.injury_status,
+     is_rostered = excluded.is_rostered,
+     fantasy_team_id = excluded.fantasy_team_id,
+     fantasy_team_name = excluded.fantasy_team_name,
+     played = excluded.played,
+     percent_owned = excluded.percent_owned,
+     percent_started = excluded.percent_started,
+     opponent_team = excluded.opponent_team,
+     home_game = excluded.home_game,
+     updated_at = excluded.updated_at";
+
+/// The stored `league_id` for a row's league
+fn league_key(league_id: Option<LeagueId>) -> u32 {
+    league_id.map_or(UNASSIGNED_LEAGUE, |id| id.as_u32())
+}
+
+/// Insert one weekly stats row on `conn` (the database or an open transaction),
+/// settling a clash with the stored row as `on_conflict` says
+fn insert_weekly_stats_on(
+    conn: &rusqlite::Connection,
+    stats: &PlayerWeeklyStats,
+    now: u64,
+    on_conflict: &str,
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        &format!(
+            "INSERT INTO player_weekly_stats
+             (player_id, season, week, league_id, projected_points, actual_points,
+              active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
+              played, percent_owned, percent_started, opponent_team, home_game,
+              created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                     ?18, ?18)
+             {}",
+            on_conflict
+        ),
+        params![
+            stats.player_id.as_i64(),
+            stats.season.as_u16(),
+            stats.week.as_u16(),
+            league_key(stats.league_id),
+            stats.projected_points,
+            stats.actual_points,
+            stats.active,
+            stats.injured,
+            stats.injury_status.as_ref().map(|s| s.to_string()),
+            stats.is_rostered,
+            stats.fantasy_team_id,
+            stats.fantasy_team_name,
+            stats.played,
+            stats.percent_owned,
+            stats.percent_started,
+            stats.opponent_team,
+            stats.home_game,
+            now
+        ],
+    )
+}
+
 /// Type alias for the complex return type of cached player data queries
 pub type CachedPlayerDataRow = (
     PlayerId,
@@ -19,11 +121,37 @@ pub type CachedPlayerDataRow = (
     Option<bool>,
     Option<u32>,
     Option<String>,
+    Option<bool>,
+    // Opponent abbreviation and whether it was a home game
+    (Option<String>, Option<bool>),
 );
 
 impl PlayerDatabase {
     /// Insert or update a player's basic information
-    pub fn upsert_player(&mut self, player: &Player) -> Result<()> {
+    ///
+    /// When the player's stored name changes, the old name is kept in
+    /// `player_aliases` so name lookups still find them, and the new alias is
+    /// returned. `Player {id}` placeholders are never kept as aliases.
+    pub fn upsert_player(&mut self, player: &Player) -> Result<Option<PlayerAlias>> {
+        let previous: Option<String> = self
+            .conn
+            .query_row(
+                "SELECT name FROM players WHERE player_id = ?",
+                [player.player_id.as_i64()],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let alias = match previous {
+            Some(previous)
+                if previous != player.name
+                    && !is_placeholder_name(player.player_id, &previous)
+                    && !is_placeholder_name(player.player_id, &player.name) =>
+            {
+                Some(self.record_player_alias(player, previous)?)
+            }
+            _ => None,
+        };
+
         self.conn.execute(
             "INSERT OR REPLACE INTO players (player_id, name, position, team)
              VALUES (?, ?, ?, ?)",
@@ -34,121 +162,374 @@ impl PlayerDatabase {
                 player.team
             ],
         )?;
-        Ok(())
+        record_rows_written(1);
+        Ok(alias)
+    }
+
+    /// Keep `previous` as an alias of the renamed player
+    ///
+    /// A player renamed back to an old name drops that name from their aliases.
+    fn record_player_alias(&mut self, player: &Player, previous: String) -> Result<PlayerAlias> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.conn.execute(
+            "INSERT OR REPLACE INTO player_aliases (player_id, alias, replaced_at)
+             VALUES (?, ?, ?)",
+            params![player.player_id.as_i64(), previous, now],
+        )?;
+        self.conn.execute(
+            "DELETE FROM player_aliases WHERE player_id = ? AND alias = ?",
+            params![player.player_id.as_i64(), player.name],
+        )?;
+        Ok(PlayerAlias {
+            player_id: player.player_id,
+            alias: previous,
+            name: Some(player.name.clone()),
+            replaced_at: now,
+        })
     }
 
     /// Update players table with ESPN player data
     /// Converts ESPN player format to database format and upserts
-    pub fn update_players_from_espn(
+    ///
+    /// Returns the aliases recorded for players ESPN renamed.
+    pub fn update_players_from_espn<'a>(
         &mut self,
-        espn_players: &[crate::espn::types::Player],
-    ) -> Result<()> {
+        espn_players: impl IntoIterator<Item = &'a crate::espn::types::Player>,
+    ) -> Result<Vec<PlayerAlias>> {
+        let mut aliases = Vec::new();
         for player in espn_players {
-            let player_id = crate::PlayerId::new(player.id);
-
-            let db_player = Player {
-                player_id,
-                name: player
-                    .full_name
-                    .clone()
-                    .unwrap_or_else(|| format!("Player {}", player.id)),
-                position: (player.default_position_id >= 0)
-                    .then(|| Position::try_from(player.default_position_id as u8).ok())
-                    .flatten()
-                    .map(|p| p.to_string())
-                    .unwrap_or_else(|| "UNKNOWN".to_string()),
-                team: None, // ESPN API doesn't provide team in this format
+            aliases.extend(self.upsert_player(&Player::from_espn(player))?);
+            if let Some(news) = &player.news {
+                self.save_player_news(PlayerId::new(player.id), news)?;
+            }
+        }
+        Ok(aliases)
+    }
+
+    /// Name team defenses stored as `Player {id}` and fill in their missing NFL team
+    ///
+    /// Both come from the defense's player ID, as for players ESPN sends without
+    /// a name. Returns the number of players updated.
+    pub fn backfill_defense_names(&mut self) -> Result<usize> {
+        let defenses: Vec<(i64, String, Option<String>)> = {
+            let mut stmt = self
+                .conn
+                .prepare("SELECT player_id, name, team FROM players WHERE position = 'D/ST'")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+            rows.collect::<rusqlite::Result<_>>()?
+        };
+
+        let tx = self.conn.transaction()?;
+        let mut updated = 0;
+        for (player_id, name, team) in defenses {
+            let Some(pro_team_id) = defense_pro_team_id(player_id) else {
+                continue;
+            };
+            let placeholder = name == format!("Player {}", player_id);
+            if !placeholder && team.is_some() {
+                continue;
+            }
+            let name = match defense_name(pro_team_id) {
+                Some(defense) if placeholder => defense,
+                _ => name,
             };
+            let team = team.or_else(|| pro_team_abbrev(pro_team_id).map(str::to_string));
+            tx.execute(
+                "UPDATE players SET name = ?, team = ? WHERE player_id = ?",
+                params![name, team, player_id],
+            )?;
+            updated += 1;
+        }
+        tx.commit()?;
+        record_rows_written(updated);
+        Ok(updated)
+    }
 
-            self.upsert_player(&db_player)?;
+    /// Old player names, newest rename first; limited to one player when `player_id` is given
+    pub fn list_player_aliases(&self, player_id: Option<PlayerId>) -> Result<Vec<PlayerAlias>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT a.player_id, a.alias, p.name, a.replaced_at
+             FROM player_aliases a
+             LEFT JOIN players p ON p.player_id = a.player_id
+             WHERE ?1 IS NULL OR a.player_id = ?1
+             ORDER BY a.replaced_at DESC, a.alias",
+        )?;
+        let rows = stmt.query_map([player_id.map(|id| id.as_i64())], |row| {
+            Ok(PlayerAlias {
+                player_id: PlayerId::new(row.get(0)?),
+                alias: row.get(1)?,
+                name: row.get(2)?,
+                replaced_at: row.get(3)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Add the current names of players whose old names match a name filter
+    ///
+    /// Name filters match on substrings, like the filters themselves, so a filter
+    /// for a player's old name also finds them under their new one.
+    pub fn expand_player_names_with_aliases(&self, names: &[String]) -> Result<Vec<String>> {
+        let mut expanded = names.to_vec();
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT p.name
+             FROM player_aliases a
+             JOIN players p ON p.player_id = a.player_id
+             WHERE a.alias LIKE ?
+             ORDER BY p.name",
+        )?;
+        for name in names {
+            let current: Vec<String> = stmt
+                .query_map([format!("%{}%", name)], |row| row.get(0))?
+                .collect::<rusqlite::Result<_>>()?;
+            for current in current {
+                if !expanded.iter().any(|n| n.eq_ignore_ascii_case(&current)) {
+                    expanded.push(current);
+                }
+            }
         }
+        Ok(expanded)
+    }
+
+    /// Append each player's injury designation to the status history when it differs
+    /// from the last one recorded for their week
+    ///
+    /// Rows without a designation are skipped. Returns the number of rows appended.
+    pub fn record_injury_statuses(
+        &mut self,
+        stats: &[PlayerWeeklyStats],
+        observed_at: u64,
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut recorded = 0;
+        {
+            let mut latest = tx.prepare(
+                "SELECT status FROM injury_status_history
+                 WHERE player_id = ? AND season = ? AND week = ?
+                 ORDER BY history_id DESC LIMIT 1",
+            )?;
+            let mut insert = tx.prepare(
+                "INSERT INTO injury_status_history (player_id, season, week, status, observed_at)
+                 VALUES (?, ?, ?, ?, ?)",
+            )?;
+            for row in stats {
+                let Some(status) = &row.injury_status else {
+                    continue;
+                };
+                let status = status.to_string();
+                let key = params![
+                    row.player_id.as_i64(),
+                    row.season.as_u16(),
+                    row.week.as_u16()
+                ];
+                let previous: Option<String> = latest.query_row(key, |r| r.get(0)).optional()?;
+                if previous.as_deref() != Some(status.as_str()) {
+                    insert.execute(params![
+                        row.player_id.as_i64(),
+                        row.season.as_u16(),
+                        row.week.as_u16(),
+                        status,
+                        observed_at
+                    ])?;
+                    recorded += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        record_rows_written(recorded);
+        Ok(recorded)
+    }
+
+    /// Injury designation changes for a week seen at or after `since` (Unix seconds)
+    ///
+    /// A player's first recorded designation for the week isn't a change. Ordered by
+    /// when the change was seen, newest first.
+    pub fn get_status_changes(
+        &self,
+        season: Season,
+        week: Week,
+        since: u64,
+    ) -> Result<Vec<InjuryStatusChange>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT player_id, status, observed_at, previous_status FROM (
+                 SELECT h.history_id, h.player_id, h.status, h.observed_at,
+                        (SELECT p.status FROM injury_status_history p
+                         WHERE p.player_id = h.player_id AND p.season = h.season
+                           AND p.week = h.week AND p.history_id < h.history_id
+                         ORDER BY p.history_id DESC LIMIT 1) AS previous_status
+                 FROM injury_status_history h
+                 WHERE h.season = ? AND h.week = ? AND h.observed_at >= ?
+             )
+             WHERE previous_status IS NOT NULL
+             ORDER BY observed_at DESC, history_id DESC",
+        )?;
+        let rows = stmt.query_map(params![season.as_u16(), week.as_u16(), since], |row| {
+            let status: String = row.get(1)?;
+            let previous_status: String = row.get(3)?;
+            Ok(InjuryStatusChange {
+                player_id: PlayerId::new(row.get(0)?),
+                season,
+                week,
+                previous_status: InjuryStatus::parse_or_unknown(&previous_status),
+                status: InjuryStatus::parse_or_unknown(&status),
+                observed_at: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Store a player's news headline, unless the stored one was published later
+    pub fn save_player_news(
+        &mut self,
+        player_id: PlayerId,
+        news: &crate::espn::types::PlayerNews,
+    ) -> Result<()> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.conn.execute(
+            "INSERT INTO player_news (player_id, headline, published, fetched_at)
+             VALUES (?, ?, ?, ?)
+             ON CONFLICT(player_id) DO UPDATE SET
+                 headline = excluded.headline,
+                 published = excluded.published,
+                 fetched_at = excluded.fetched_at
+             WHERE excluded.published IS NULL
+                OR player_news.published IS NULL
+                OR excluded.published >= player_news.published",
+            params![player_id.as_i64(), news.headline, news.published, now],
+        )?;
         Ok(())
     }
 
+    /// Store a league's settings for a season, replacing any stored copy
+    ///
+    /// `fetched_at` is when they came from ESPN, in Unix seconds.
+    pub fn upsert_league_settings(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        settings: &LeagueSettings,
+        fetched_at: u64,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO league_settings (league_id, season, settings_json, fetched_at)
+             VALUES (?, ?, ?, ?)",
+            params![
+                league_id.as_u32(),
+                season.as_u16(),
+                serde_json::to_string(settings)?,
+                fetched_at
+            ],
+        )?;
+        record_rows_written(1);
+        Ok(())
+    }
+
+    /// A league's stored settings for a season
+    ///
+    /// `None` when none are stored, or when the stored copy no longer parses, so
+    /// callers fetch them again.
+    pub fn get_league_settings(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+    ) -> Result<Option<StoredLeagueSettings>> {
+        let row: Option<(String, u64)> = self
+            .conn
+            .query_row(
+                "SELECT settings_json, fetched_at FROM league_settings
+                 WHERE league_id = ? AND season = ?",
+                params![league_id.as_u32(), season.as_u16()],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        Ok(row.and_then(|(json, fetched_at)| {
+            serde_json::from_str(&json)
+                .ok()
+                .map(|settings| StoredLeagueSettings {
+                    settings,
+                    fetched_at,
+                })
+        }))
+    }
+
+    /// Stored news headlines for the given players
+    pub fn get_player_news(
+        &self,
+        player_ids: &[PlayerId],
+    ) -> Result<HashMap<PlayerId, PlayerHeadline>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT player_id, headline, published, fetched_at FROM player_news")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(PlayerHeadline {
+                player_id: PlayerId::new(row.get(0)?),
+                headline: row.get(1)?,
+                published: row.get(2)?,
+                fetched_at: row.get(3)?,
+            })
+        })?;
+
+        let mut by_player = HashMap::new();
+        for headline in rows {
+            let headline = headline?;
+            if player_ids.contains(&headline.player_id) {
+                by_player.insert(headline.player_id, headline);
+            }
+        }
+        Ok(by_player)
+    }
+
     /// Insert or update weekly stats for a player
     /// Only updates if force_update is true or if the data doesn't exist
+    ///
+    /// Rows are kept per league (`stats.league_id`).
     pub fn upsert_weekly_stats(
         &mut self,
         stats: &PlayerWeeklyStats,
         force_update: bool,
     ) -> Result<bool> {
         let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.adopt_unassigned_rows(stats.league_id)?;
 
-        if force_update {
-            // Force update existing record
-            let rows_affected = self.conn.execute(
-                "INSERT OR REPLACE INTO player_weekly_stats
-                 (player_id, season, week, projected_points, actual_points,
-                  active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
-                  created_at, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
-                         COALESCE((SELECT created_at FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?), ?), ?)",
-                params![
-                    stats.player_id.as_i64(),
-                    stats.season.as_u16(),
-                    stats.week.as_u16(),
-                    stats.projected_points,
-                    stats.actual_points,
-                    stats.active,
-                    stats.injured,
-                    stats.injury_status.as_ref().map(|s| s.to_string()),
-                    stats.is_rostered,
-                    stats.fantasy_team_id,
-                    stats.fantasy_team_name,
-                    stats.player_id.as_i64(),
-                    stats.season.as_u16(),
-                    stats.week.as_u16(),
-                    now,
-                    now
-                ],
-            )?;
-            Ok(rows_affected > 0)
+        let on_conflict = if force_update {
+            REPLACE_ON_CONFLICT
         } else {
             // Only insert if doesn't exist
-            let rows_affected = self.conn.execute(
-                "INSERT OR IGNORE INTO player_weekly_stats
-                 (player_id, season, week, projected_points, actual_points,
-                  active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
-                  created_at, updated_at)
-                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-                params![
-                    stats.player_id.as_i64(),
-                    stats.season.as_u16(),
-                    stats.week.as_u16(),
-                    stats.projected_points,
-                    stats.actual_points,
-                    stats.active,
-                    stats.injured,
-                    stats.injury_status.as_ref().map(|s| s.to_string()),
-                    stats.is_rostered,
-                    stats.fantasy_team_id,
-                    stats.fantasy_team_name,
-                    now,
-                    now
-                ],
-            )?;
-            Ok(rows_affected > 0)
+            "ON CONFLICT DO NOTHING"
+        };
+        let rows_affected = insert_weekly_stats_on(&self.conn, stats, now, on_conflict)?;
+        if rows_affected > 0 {
+            self.invalidate_cached_week(stats.season, stats.week);
         }
+        record_rows_written(rows_affected);
+        Ok(rows_affected > 0)
     }
 
-    /// Get weekly stats for a specific player, season, and week
+    /// Get a league's weekly stats for a specific player, season, and week
     pub fn get_weekly_stats(
         &self,
+        league_id: LeagueId,
         player_id: PlayerId,
         season: Season,
         week: Week,
     ) -> Result<Option<PlayerWeeklyStats>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
         let mut stmt = self.conn.prepare(
             "SELECT player_id, season, week, projected_points, actual_points,
                     active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
-                    created_at, updated_at
+                    played, created_at, updated_at, percent_owned, percent_started,
+                    opponent_team, home_game, league_id
              FROM player_weekly_stats
-             WHERE player_id = ? AND season = ? AND week = ?",
+             WHERE player_id = ? AND season = ? AND week = ? AND league_id = ?",
         )?;
 
         let result = stmt.query_row(
-            params![player_id.as_i64(), season.as_u16(), week.as_u16()],
+            params![
+                player_id.as_i64(),
+                season.as_u16(),
+                week.as_u16(),
+                league_id.as_u32()
+            ],
             |row| self.row_to_weekly_stats(row),
         );
 
@@ -161,24 +542,28 @@ impl PlayerDatabase {
         Ok(final_result)
     }
 
-    /// Get all weekly stats for a player in a season
+    /// Get all of a league's weekly stats for a player in a season
     pub fn get_player_season_stats(
         &self,
+        league_id: LeagueId,
         player_id: PlayerId,
         season: Season,
     ) -> Result<Vec<PlayerWeeklyStats>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
         let mut stmt = self.conn.prepare(
             "SELECT player_id, season, week, projected_points, actual_points,
                     active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
-                    created_at, updated_at
+                    played, created_at, updated_at, percent_owned, percent_started,
+                    opponent_team, home_game, league_id
              FROM player_weekly_stats
-             WHERE player_id = ? AND season = ?
+             WHERE player_id = ? AND season = ? AND league_id = ?
              ORDER BY week",
         )?;
 
-        let rows = stmt.query_map(params![player_id.as_i64(), season.as_u16()], |row| {
-            self.row_to_weekly_stats(row)
-        })?;
+        let rows = stmt.query_map(
+            params![player_id.as_i64(), season.as_u16(), league_id.as_u32()],
+            |row| self.row_to_weekly_stats(row),
+        )?;
 
         let mut stats = Vec::new();
         for row in rows {
@@ -190,85 +575,62 @@ impl PlayerDatabase {
     /// Insert or merge weekly stats, preserving existing projected/actual points but updating roster info
     pub fn merge_weekly_stats(&mut self, stats: &PlayerWeeklyStats) -> Result<()> {
         let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-
-        // Use INSERT OR REPLACE with COALESCE to merge data
-        // Always update roster info but preserve existing points if available
-        self.conn.execute(
-            "INSERT OR REPLACE INTO player_weekly_stats
-             (player_id, season, week, projected_points, actual_points,
-              active, injured, injury_status, is_rostered, fantasy_team_id, fantasy_team_name,
-              created_at, updated_at)
-             VALUES (?, ?, ?,
-                     COALESCE(?, (SELECT projected_points FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?)),
-                     COALESCE(?, (SELECT actual_points FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?)),
-                     COALESCE(?, (SELECT active FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?)),
-                     COALESCE(?, (SELECT injured FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?)),
-                     COALESCE(?, (SELECT injury_status FROM player_weekly_stats
-                                  WHERE player_id = ? AND season = ? AND week = ?)),
-                     ?, ?, ?,
-                     COALESCE((SELECT created_at FROM player_weekly_stats
-                              WHERE player_id = ? AND season = ? AND week = ?), ?), ?)",
-            params![
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.projected_points,
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.actual_points,
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.active,
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.injured,
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.injury_status.as_ref().map(|s| s.to_string()),
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                stats.is_rostered,
-                stats.fantasy_team_id,
-                stats.fantasy_team_name,
-                stats.player_id.as_i64(),
-                stats.season.as_u16(),
-                stats.week.as_u16(),
-                now,
-                now
-            ],
-        )?;
+        self.adopt_unassigned_rows(stats.league_id)?;
+        insert_weekly_stats_on(&self.conn, stats, now, MERGE_ON_CONFLICT)?;
+        self.invalidate_cached_week(stats.season, stats.week);
+        record_rows_written(1);
         Ok(())
     }
 
+    /// Merge many weekly stats rows in one transaction (see [`Self::merge_weekly_stats`])
+    ///
+    /// Returns the number of rows written.
+    pub fn merge_weekly_stats_batch(&mut self, stats: &[PlayerWeeklyStats]) -> Result<usize> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if let Some(first) = stats.first() {
+            self.adopt_unassigned_rows(first.league_id)?;
+        }
+        let tx = self.conn.transaction()?;
+        for row in stats {
+            insert_weekly_stats_on(&tx, row, now, MERGE_ON_CONFLICT)?;
+        }
+        tx.commit()?;
+
+        for row in stats {
+            self.invalidate_cached_week(row.season, row.week);
+        }
+        record_rows_written(stats.len());
+        Ok(stats.len())
+    }
+
     /// Get cached player data for a specific season/week combination with filters
+    ///
+    /// Only `league_id`'s rows are read, since points and roster status depend on the league.
+    /// Name, position, injury status, roster status, fantasy team and NFL team filters
+    /// are all applied in SQL, so only matching rows are loaded.
     pub fn get_cached_player_data(
         &self,
         params: &CommandParams,
+        league_id: LeagueId,
         projected: bool,
     ) -> Result<Vec<CachedPlayerDataRow>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
         let mut query = String::from(
             "SELECT p.player_id, p.name, p.position,
                     CASE WHEN ? = 1 THEN pws.projected_points ELSE pws.actual_points END as points,
                     pws.active, pws.injured, pws.injury_status,
-                    pws.is_rostered, pws.fantasy_team_id, pws.fantasy_team_name
+                    pws.is_rostered, pws.fantasy_team_id, pws.fantasy_team_name, pws.played,
+                    pws.opponent_team, pws.home_game
              FROM players p
              JOIN player_weekly_stats pws ON p.player_id = pws.player_id
-             WHERE pws.season = ? AND pws.week = ?",
+             WHERE pws.season = ? AND pws.week = ? AND pws.league_id = ?",
         );
 
         let mut sql_params: Vec<Box<dyn rusqlite::ToSql>> = vec![
             Box::new(if projected { 1 } else { 0 }),
             Box::new(params.season.as_u16()),
             Box::new(params.week.as_u16()),
+            Box::new(league_id.as_u32()),
         ];
 
         // Add projected/actual filter
@@ -308,6 +670,42 @@ impl PlayerDatabase {
             }
         }
 
+        if let Some(filter) = &params.injury_status {
+            push_injury_filter(&mut query, &mut sql_params, filter);
+        }
+
+        if let Some(filter) = &params.roster_status {
+            query.push_str(match filter {
+                RosterStatusFilter::Rostered => " AND pws.is_rostered = 1",
+                RosterStatusFilter::FA => " AND pws.is_rostered = 0",
+            });
+        }
+
+        if let Some(filter) = &params.fantasy_team_filter {
+            match filter {
+                FantasyTeamFilter::Id(team_id) => {
+                    query.push_str(" AND pws.fantasy_team_id = ?");
+                    sql_params.push(Box::new(*team_id));
+                }
+                FantasyTeamFilter::Name(name) => {
+                    query.push_str(" AND instr(LOWER(pws.fantasy_team_name), ?) > 0");
+                    sql_params.push(Box::new(name.to_lowercase()));
+                }
+                FantasyTeamFilter::Ids(team_ids) => {
+                    let placeholders = vec!["?"; team_ids.len()].join(", ");
+                    query.push_str(&format!(" AND pws.fantasy_team_id IN ({})", placeholders));
+                    for team_id in team_ids {
+                        sql_params.push(Box::new(*team_id));
+                    }
+                }
+            }
+        }
+
+        if let Some(team) = params.nfl_team {
+            query.push_str(" AND p.team = ?");
+            sql_params.push(Box::new(team.abbrev()));
+        }
+
         query.push_str(" ORDER BY points DESC");
 
         let mut stmt = self.conn.prepare(&query)?;
@@ -316,29 +714,22 @@ impl PlayerDatabase {
             |row| {
                 let injury_status_str: Option<String> = row.get(6)?;
                 let injury_status = injury_status_str
-                    .map(|s| match s.as_str() {
-                        "Active" => Some(crate::espn::types::InjuryStatus::Active),
-                        "IR" => Some(crate::espn::types::InjuryStatus::InjuryReserve),
-                        "Out" => Some(crate::espn::types::InjuryStatus::Out),
-                        "Doubtful" => Some(crate::espn::types::InjuryStatus::Doubtful),
-                        "Questionable" => Some(crate::espn::types::InjuryStatus::Questionable),
-                        "Probable" => Some(crate::espn::types::InjuryStatus::Probable),
-                        "Day-to-Day" => Some(crate::espn::types::InjuryStatus::DayToDay),
-                        _ => Some(crate::espn::types::InjuryStatus::Unknown),
-                    })
-                    .unwrap_or(None);
+                    .as_deref()
+                    .map(InjuryStatus::parse_or_unknown);
 
                 Ok((
-                    PlayerId::new(row.get(0)?), // player_id
-                    row.get(1)?,                // name
-                    row.get(2)?,                // position
-                    row.get(3)?,                // points
-                    row.get(4)?,                // active
-                    row.get(5)?,                // injured
-                    injury_status,              // injury_status
-                    row.get(7)?,                // is_rostered
-                    row.get(8)?,                // fantasy_team_id
-                    row.get(9)?,                // fantasy_team_name
+                    PlayerId::new(row.get(0)?),   // player_id
+                    row.get(1)?,                  // name
+                    row.get(2)?,                  // position
+                    row.get(3)?,                  // points
+                    row.get(4)?,                  // active
+                    row.get(5)?,                  // injured
+                    injury_status,                // injury_status
+                    row.get(7)?,                  // is_rostered
+                    row.get(8)?,                  // fantasy_team_id
+                    row.get(9)?,                  // fantasy_team_name
+                    row.get(10)?,                 // played
+                    (row.get(11)?, row.get(12)?), // opponent_team, home_game
                 ))
             },
         )?;
@@ -351,16 +742,58 @@ impl PlayerDatabase {
         Ok(results)
     }
 
+    /// Every player-week a league has stored for a season, ordered by week then player
+    pub fn get_weekly_export(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+    ) -> Result<Vec<WeeklyExportRow>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT p.player_id, p.name, s.season, s.week, s.projected_points, s.actual_points,
+                    p.position, p.team, s.injury_status, s.is_rostered
+             FROM player_weekly_stats s
+             JOIN players p ON p.player_id = s.player_id
+             WHERE s.season = ? AND s.league_id = ?
+             ORDER BY s.week, p.player_id",
+        )?;
+
+        let rows = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+            Ok(WeeklyExportRow {
+                player_id: PlayerId::new(row.get(0)?),
+                name: row.get(1)?,
+                season: Season::new(row.get(2)?),
+                week: Week::new(row.get(3)?),
+                projected_points: row.get(4)?,
+                actual_points: row.get(5)?,
+                position: row.get(6)?,
+                team: row.get(7)?,
+                injury_status: row.get(8)?,
+                is_rostered: row.get(9)?,
+            })
+        })?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
     /// Check if we already have data for a specific season/week combination
     /// Returns true if any player data exists for the given filters
+    ///
+    /// With a `league_id`, only that league's rows count.
     pub fn has_data_for_week(
         &self,
         season: Season,
         week: Week,
+        league_id: Option<LeagueId>,
         player_names: Option<&Vec<String>>,
         positions: Option<&Vec<Position>>,
         projected: Option<bool>,
     ) -> Result<bool> {
+        self.adopt_unassigned_rows(league_id)?;
         // Build query based on filters
         let mut query = String::from(
             "SELECT COUNT(*) FROM player_weekly_stats pws
@@ -370,6 +803,11 @@ impl PlayerDatabase {
         let mut params: Vec<Box<dyn rusqlite::ToSql>> =
             vec![Box::new(season.as_u16()), Box::new(week.as_u16())];
 
+        if let Some(league_id) = league_id {
+            query.push_str(" AND pws.league_id = ?");
+            params.push(Box::new(league_id.as_u32()));
+        }
+
         // Add projected filter if provided
         if let Some(is_projected) = projected {
             if is_projected {
@@ -440,11 +878,34 @@ impl PlayerDatabase {
         Ok(players)
     }
 
+    /// NFL team abbreviations for the given players, skipping those without one
+    pub fn get_player_teams(&self, player_ids: &[PlayerId]) -> Result<HashMap<PlayerId, String>> {
+        let mut teams = HashMap::new();
+        for chunk in player_ids.chunks(500) {
+            let placeholders = vec!["?"; chunk.len()].join(", ");
+            let query = format!(
+                "SELECT player_id, team FROM players
+                 WHERE team IS NOT NULL AND player_id IN ({placeholders})"
+            );
+            let mut stmt = self.conn.prepare(&query)?;
+            let rows = stmt.query_map(
+                rusqlite::params_from_iter(chunk.iter().map(|id| id.as_i64())),
+                |row| Ok((PlayerId::new(row.get(0)?), row.get::<_, String>(1)?)),
+            )?;
+            for row in rows {
+                let (player_id, team) = row?;
+                teams.insert(player_id, team);
+            }
+        }
+        Ok(teams)
+    }
+
     /// Update roster information for ALL players based on current roster data
     /// This ensures that roster assignments are current for all players in database
     pub fn update_all_players_roster_info(
         &mut self,
         roster_data: &crate::espn::types::LeagueData,
+        league_id: LeagueId,
         season: Season,
         week: Week,
     ) -> Result<usize> {
@@ -473,6 +934,7 @@ impl PlayerDatabase {
                 player_id: player.player_id,
                 season,
                 week,
+                league_id: Some(league_id),
                 projected_points: None,
                 actual_points: None,
                 active: None,
@@ -481,6 +943,11 @@ impl PlayerDatabase {
                 is_rostered,
                 fantasy_team_id: team_id,
                 fantasy_team_name: team_name,
+                played: None,
+                percent_owned: None,
+                percent_started: None,
+                opponent_team: None,
+                home_game: None,
                 created_at: 0, // Will be set by database
                 updated_at: 0, // Will be set by database
             };
@@ -493,31 +960,653 @@ impl PlayerDatabase {
         Ok(updated_count)
     }
 
+    /// Store league schedule results and the final team totals for completed matchups
+    /// Returns the number of matchups written
+    pub fn upsert_matchups(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        matchups: &[crate::espn::types::Matchup],
+    ) -> Result<usize> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let tx = self.conn.transaction()?;
+        let mut written = 0;
+
+        for matchup in matchups {
+            let Some(home) = &matchup.home else {
+                continue;
+            };
+
+            tx.execute(
+                "INSERT OR REPLACE INTO fantasy_matchups
+                 (season, matchup_period, matchup_id, home_team_id, away_team_id, winner, updated_at,
+                  league_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    season.as_u16(),
+                    matchup.matchup_period_id,
+                    matchup.id,
+                    home.team_id,
+                    matchup.away.as_ref().map(|a| a.team_id),
+                    matchup.winner,
+                    now,
+                    league_id.as_u32()
+                ],
+            )?;
+
+            // Only settled matchups count as weekly totals; in-progress scores are partial
+            for side in [Some(home), matchup.away.as_ref()].into_iter().flatten() {
+                match side.total_points {
+                    Some(points) if matchup.is_complete() => {
+                        tx.execute(
+                            "INSERT OR REPLACE INTO team_weekly_totals
+                             (season, week, team_id, points, updated_at, league_id)
+                             VALUES (?, ?, ?, ?, ?, ?)",
+                            params![
+                                season.as_u16(),
+                                matchup.matchup_period_id,
+                                side.team_id,
+                                points,
+                                now,
+                                league_id.as_u32()
+                            ],
+                        )?;
+                    }
+                    _ => {
+                        tx.execute(
+                            "DELETE FROM team_weekly_totals
+                             WHERE season = ? AND week = ? AND team_id = ? AND league_id = ?",
+                            params![
+                                season.as_u16(),
+                                matchup.matchup_period_id,
+                                side.team_id,
+                                league_id.as_u32()
+                            ],
+                        )?;
+                    }
+                }
+            }
+            written += 1;
+        }
+
+        tx.commit()?;
+        record_rows_written(written);
+        Ok(written)
+    }
+
+    /// Replace the stored weeks of each matchup period for a season
+    ///
+    /// `matchup_periods` is the league's `matchupPeriods` setting (matchup period to
+    /// the weeks played in it). Returns the number of weeks written.
+    pub fn save_matchup_periods(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        matchup_periods: &std::collections::BTreeMap<u16, Vec<u16>>,
+    ) -> Result<usize> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM matchup_period_weeks WHERE season = ? AND league_id = ?",
+            params![season.as_u16(), league_id.as_u32()],
+        )?;
+
+        let mut written = 0;
+        for (matchup_period, weeks) in matchup_periods {
+            for week in weeks {
+                tx.execute(
+                    "INSERT OR REPLACE INTO matchup_period_weeks
+                     (season, matchup_period, week, league_id)
+                     VALUES (?, ?, ?, ?)",
+                    params![season.as_u16(), matchup_period, week, league_id.as_u32()],
+                )?;
+                written += 1;
+            }
+        }
+
+        tx.commit()?;
+        record_rows_written(written);
+        Ok(written)
+    }
+
+    /// Weeks played in each stored matchup period of a season, in order
+    ///
+    /// Empty when the league's mapping was never stored; callers then treat each
+    /// matchup period as the week of the same number.
+    pub fn get_matchup_periods(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+    ) -> Result<std::collections::BTreeMap<u16, Vec<Week>>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT matchup_period, week FROM matchup_period_weeks
+             WHERE season = ? AND league_id = ?
+             ORDER BY matchup_period, week",
+        )?;
+        let rows = stmt.query_map(params![season.as_u16(), league_id.as_u32()], |row| {
+            Ok((row.get::<_, u16>(0)?, row.get::<_, u16>(1)?))
+        })?;
+
+        let mut periods: std::collections::BTreeMap<u16, Vec<Week>> =
+            std::collections::BTreeMap::new();
+        for row in rows {
+            let (matchup_period, week) = row?;
+            periods
+                .entry(matchup_period)
+                .or_default()
+                .push(Week::new(week));
+        }
+        Ok(periods)
+    }
+
+    /// Replace the stored roster snapshot for a week with the given league rosters
+    /// Returns the number of roster entries written
+    pub fn save_roster_snapshot(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+        roster_data: &crate::espn::types::LeagueData,
+    ) -> Result<usize> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "DELETE FROM roster_snapshots WHERE season = ? AND week = ? AND league_id = ?",
+            params![season.as_u16(), week.as_u16(), league_id.as_u32()],
+        )?;
+
+        let mut written = 0;
+        for team in &roster_data.teams {
+            let Some(roster) = &team.roster else {
+                continue;
+            };
+            for entry in &roster.entries {
+                tx.execute(
+                    "INSERT OR REPLACE INTO roster_snapshots
+                     (season, week, team_id, team_name, player_id, lineup_slot_id, updated_at,
+                      league_id)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        season.as_u16(),
+                        week.as_u16(),
+                        team.id,
+                        team.name,
+                        entry.player_id,
+                        entry.lineup_slot_id,
+                        now,
+                        league_id.as_u32()
+                    ],
+                )?;
+                written += 1;
+            }
+        }
+
+        tx.commit()?;
+        record_rows_written(written);
+        Ok(written)
+    }
+
+    /// Stored raw actual stats for a week as ESPN-style `{"<stat id>": value}` maps
+    ///
+    /// Players with nothing stored are left out of the result.
+    pub fn get_raw_stats(
+        &self,
+        league_id: LeagueId,
+        player_ids: &[PlayerId],
+        season: Season,
+        week: Week,
+    ) -> Result<HashMap<PlayerId, serde_json::Value>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT stat_id, value FROM player_weekly_raw_stats
+             WHERE player_id = ? AND season = ? AND week = ? AND league_id = ?",
+        )?;
+
+        let mut raw_stats = HashMap::new();
+        for player_id in player_ids {
+            let rows = stmt.query_map(
+                params![
+                    player_id.as_i64(),
+                    season.as_u16(),
+                    week.as_u16(),
+                    league_id.as_u32()
+                ],
+                |row| Ok((row.get::<_, u16>(0)?, row.get::<_, f64>(1)?)),
+            )?;
+            let mut stats = serde_json::Map::new();
+            for row in rows {
+                let (stat_id, value) = row?;
+                stats.insert(stat_id.to_string(), value.into());
+            }
+            if !stats.is_empty() {
+                raw_stats.insert(*player_id, serde_json::Value::Object(stats));
+            }
+        }
+        Ok(raw_stats)
+    }
+
+    /// Record the fantasy team names seen in a week's roster data
+    ///
+    /// A name already on file for the team just has its week range widened, so
+    /// renaming a team appends a row instead of overwriting the old name. Teams
+    /// without a name are skipped. Returns the number of names not seen before.
+    pub fn record_team_names(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+        roster_data: &crate::espn::types::LeagueData,
+    ) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut new_names = 0;
+        {
+            let mut exists = tx.prepare(
+                "SELECT 1 FROM fantasy_teams
+                 WHERE league_id = ? AND season = ? AND team_id = ? AND name = ?",
+            )?;
+            let mut upsert = tx.prepare(
+                "INSERT INTO fantasy_teams
+                 (league_id, season, team_id, name, abbrev, first_seen_week, last_seen_week)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+                 ON CONFLICT (league_id, season, team_id, name) DO UPDATE SET
+                     abbrev = COALESCE(excluded.abbrev, abbrev),
+                     first_seen_week = MIN(first_seen_week, excluded.first_seen_week),
+                     last_seen_week = MAX(last_seen_week, excluded.last_seen_week)",
+            )?;
+            for team in &roster_data.teams {
+                let Some(name) = &team.name else {
+                    continue;
+                };
+                let key = params![league_id.as_u32(), season.as_u16(), team.id, name];
+                if !exists.exists(key)? {
+                    new_names += 1;
+                }
+                upsert.execute(params![
+                    league_id.as_u32(),
+                    season.as_u16(),
+                    team.id,
+                    name,
+                    team.abbrev,
+                    week.as_u16()
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(new_names)
+    }
+
+    /// Every recorded name per team, oldest first within each team
+    pub fn get_team_name_history(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        team_id: Option<u32>,
+    ) -> Result<Vec<FantasyTeamName>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT team_id, name, abbrev, first_seen_week, last_seen_week
+             FROM fantasy_teams
+             WHERE league_id = ? AND season = ? AND (? IS NULL OR team_id = ?)
+             ORDER BY team_id, first_seen_week, last_seen_week, name",
+        )?;
+        let rows = stmt.query_map(
+            params![league_id.as_u32(), season.as_u16(), team_id, team_id],
+            |row| {
+                Ok(FantasyTeamName {
+                    league_id,
+                    season,
+                    team_id: row.get(0)?,
+                    name: row.get(1)?,
+                    abbrev: row.get(2)?,
+                    first_seen_week: Week::new(row.get(3)?),
+                    last_seen_week: Week::new(row.get(4)?),
+                })
+            },
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// The most recently seen name for each team
+    pub fn get_current_team_names(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+    ) -> Result<HashMap<u32, String>> {
+        // History is ordered oldest first, so later names overwrite earlier ones
+        let mut current: HashMap<u32, (u16, u16, String)> = HashMap::new();
+        for entry in self.get_team_name_history(league_id, season, None)? {
+            let seen = (
+                entry.last_seen_week.as_u16(),
+                entry.first_seen_week.as_u16(),
+            );
+            match current.get(&entry.team_id) {
+                Some((last, first, _)) if (*last, *first) > seen => {}
+                _ => {
+                    current.insert(entry.team_id, (seen.0, seen.1, entry.name));
+                }
+            }
+        }
+        Ok(current
+            .into_iter()
+            .map(|(team_id, (_, _, name))| (team_id, name))
+            .collect())
+    }
+
+    /// Teams that have ever gone by a name containing `fragment`, or whose
+    /// abbreviation is exactly `fragment` (both case-insensitive)
+    pub fn find_team_ids_by_name(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        fragment: &str,
+    ) -> Result<Vec<u32>> {
+        let fragment = fragment.to_lowercase();
+        let mut stmt = self.conn.prepare(
+            "SELECT DISTINCT team_id FROM fantasy_teams
+             WHERE league_id = ? AND season = ?
+               AND (instr(LOWER(name), ?) > 0 OR LOWER(abbrev) = ?)
+             ORDER BY team_id",
+        )?;
+        let rows = stmt.query_map(
+            params![league_id.as_u32(), season.as_u16(), fragment, fragment],
+            |row| row.get(0),
+        )?;
+
+        let mut results = Vec::new();
+        for row in rows {
+            results.push(row?);
+        }
+        Ok(results)
+    }
+
+    /// Replace the stored raw actual stats for the given players in one week
+    ///
+    /// Each map is ESPN's `{"<stat id>": value}` block; zero and non-numeric values
+    /// are skipped. Returns the number of stat rows written.
+    pub fn save_raw_stats(
+        &mut self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+        player_stats: &[(PlayerId, &serde_json::Value)],
+    ) -> Result<usize> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let tx = self.conn.transaction()?;
+        let mut written = 0;
+        {
+            let mut delete = tx.prepare(
+                "DELETE FROM player_weekly_raw_stats
+                 WHERE player_id = ? AND season = ? AND week = ? AND league_id = ?",
+            )?;
+            let mut insert = tx.prepare(
+                "INSERT INTO player_weekly_raw_stats
+                 (player_id, season, week, stat_id, value, league_id)
+                 VALUES (?, ?, ?, ?, ?, ?)",
+            )?;
+
+            for (player_id, stats) in player_stats {
+                delete.execute(params![
+                    player_id.as_i64(),
+                    season.as_u16(),
+                    week.as_u16(),
+                    league_id.as_u32()
+                ])?;
+                let Some(stats_map) = stats.as_object() else {
+                    continue;
+                };
+                for (stat_id, value) in stats_map {
+                    let (Ok(stat_id), Some(value)) = (stat_id.parse::<u16>(), value.as_f64())
+                    else {
+                        continue;
+                    };
+                    if value == 0.0 {
+                        continue;
+                    }
+                    insert.execute(params![
+                        player_id.as_i64(),
+                        season.as_u16(),
+                        week.as_u16(),
+                        stat_id,
+                        value,
+                        league_id.as_u32()
+                    ])?;
+                    written += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        record_rows_written(written);
+        Ok(written)
+    }
+
+    /// Top players by one raw stat, for a single week or summed over the season
+    ///
+    /// Only rows holding the stat are read (via `idx_raw_stat_category`), so sparse
+    /// categories such as long field goals stay cheap. Fantasy points cover the same
+    /// week, or every stored week of the season. The roster filter uses the week's
+    /// roster status, or the latest stored week's for season totals.
+    pub fn get_category_leaders(
+        &self,
+        league_id: LeagueId,
+        query: &CategoryLeadersQuery,
+    ) -> Result<Vec<CategoryLeader>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let CategoryLeadersQuery {
+            stat_id,
+            season,
+            week,
+            positions,
+            roster_status,
+            limit,
+        } = *query;
+        let week = week.map(|w| w.as_u16());
+        let mut sql = String::from(
+            "SELECT * FROM (
+                SELECT p.player_id, p.name, p.position, p.team,
+                       SUM(r.value) AS total,
+                       (SELECT SUM(w.actual_points) FROM player_weekly_stats w
+                        WHERE w.player_id = p.player_id AND w.season = ?1
+                          AND (?2 IS NULL OR w.week = ?2) AND w.league_id = ?4) AS points,
+                       COUNT(*) AS games,
+                       (SELECT w.is_rostered FROM player_weekly_stats w
+                        WHERE w.player_id = p.player_id AND w.season = ?1
+                          AND (?2 IS NULL OR w.week = ?2) AND w.league_id = ?4
+                        ORDER BY w.week DESC LIMIT 1) AS is_rostered
+                FROM player_weekly_raw_stats r
+                JOIN players p ON p.player_id = r.player_id
+                WHERE r.stat_id = ?3 AND r.season = ?1 AND (?2 IS NULL OR r.week = ?2)
+                  AND r.league_id = ?4",
+        );
+        let mut sql_params: Vec<Box<dyn rusqlite::ToSql>> = vec![
+            Box::new(season.as_u16()),
+            Box::new(week),
+            Box::new(stat_id),
+            Box::new(league_id.as_u32()),
+        ];
+
+        if let Some(pos_list) = positions.filter(|p| !p.is_empty()) {
+            let placeholders: Vec<String> = pos_list
+                .iter()
+                .map(|pos| {
+                    sql_params.push(Box::new(pos.to_string()));
+                    format!("?{}", sql_params.len())
+                })
+                .collect();
+            sql.push_str(&format!(" AND p.position IN ({})", placeholders.join(", ")));
+        }
+
+        sql.push_str(" GROUP BY p.player_id) WHERE 1 = 1");
+        if let Some(filter) = roster_status {
+            sql.push_str(match filter {
+                RosterStatusFilter::Rostered => " AND is_rostered = 1",
+                RosterStatusFilter::FA => " AND is_rostered = 0",
+            });
+        }
+        sql_params.push(Box::new(limit as i64));
+        sql.push_str(&format!(
+            " ORDER BY total DESC, name LIMIT ?{}",
+            sql_params.len()
+        ));
+
+        let mut stmt = self.conn.prepare(&sql)?;
+        let rows = stmt.query_map(
+            rusqlite::params_from_iter(sql_params.iter().map(|p| p.as_ref())),
+            |row| {
+                Ok(CategoryLeader {
+                    player_id: PlayerId::new(row.get(0)?),
+                    name: row.get(1)?,
+                    position: row.get(2)?,
+                    team: row.get(3)?,
+                    value: row.get(4)?,
+                    fantasy_points: row.get(5)?,
+                    games: row.get(6)?,
+                })
+            },
+        )?;
+
+        let mut leaders = Vec::new();
+        for row in rows {
+            leaders.push(row?);
+        }
+        Ok(leaders)
+    }
+
+    /// Get the stored roster snapshot for a week, ordered by team and lineup slot
+    pub fn get_roster_snapshot(
+        &self,
+        league_id: LeagueId,
+        season: Season,
+        week: Week,
+    ) -> Result<Vec<RosterSnapshotEntry>> {
+        self.adopt_unassigned_rows(Some(league_id))?;
+        let mut stmt = self.conn.prepare(
+            "SELECT r.team_id, r.team_name, r.player_id, p.name, p.position, r.lineup_slot_id
+             FROM roster_snapshots r
+             LEFT JOIN players p ON p.player_id = r.player_id
+             WHERE r.season = ? AND r.week = ? AND r.league_id = ?
+             ORDER BY r.team_id, r.lineup_slot_id, r.player_id",
+        )?;
+
+        let rows = stmt.query_map(
+            params![season.as_u16(), week.as_u16(), league_id.as_u32()],
+            |row| {
+                Ok(RosterSnapshotEntry {
+                    team_id: row.get(0)?,
+                    team_name: row.get(1)?,
+                    player_id: PlayerId::new(row.get(2)?),
+                    player_name: row.get(3)?,
+                    position: row.get(4)?,
+                    lineup_slot_id: row.get(5)?,
+                })
+            },
+        )?;
+
+        let mut entries = Vec::new();
+        for row in rows {
+            entries.push(row?);
+        }
+        Ok(entries)
+    }
+
+    /// Attach a note to a player, returning the new note's ID
+    pub fn add_player_note(&mut self, player_id: PlayerId, note: &str) -> Result<i64> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        self.conn.execute(
+            "INSERT INTO player_notes (player_id, note, created_at) VALUES (?, ?, ?)",
+            params![player_id.as_i64(), note, now],
+        )?;
+        Ok(self.conn.last_insert_rowid())
+    }
+
+    /// Notes, newest first; limited to one player when `player_id` is given
+    pub fn list_player_notes(&self, player_id: Option<PlayerId>) -> Result<Vec<PlayerNote>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT n.note_id, n.player_id, p.name, n.note, n.created_at
+             FROM player_notes n
+             LEFT JOIN players p ON p.player_id = n.player_id
+             WHERE ?1 IS NULL OR n.player_id = ?1
+             ORDER BY n.created_at DESC, n.note_id DESC",
+        )?;
+        let rows = stmt.query_map(params![player_id.map(|id| id.as_i64())], |row| {
+            Ok(PlayerNote {
+                note_id: row.get(0)?,
+                player_id: PlayerId::new(row.get(1)?),
+                player_name: row.get(2)?,
+                note: row.get(3)?,
+                created_at: row.get(4)?,
+            })
+        })?;
+
+        let mut notes = Vec::new();
+        for row in rows {
+            notes.push(row?);
+        }
+        Ok(notes)
+    }
+
+    /// Notes for the given players, newest first per player
+    pub fn get_notes_for_players(
+        &self,
+        player_ids: &[PlayerId],
+    ) -> Result<HashMap<PlayerId, Vec<PlayerNote>>> {
+        let mut by_player: HashMap<PlayerId, Vec<PlayerNote>> = HashMap::new();
+        for note in self.list_player_notes(None)? {
+            if player_ids.contains(&note.player_id) {
+                by_player.entry(note.player_id).or_default().push(note);
+            }
+        }
+        Ok(by_player)
+    }
+
+    /// Delete a note; returns false if no note has that ID
+    pub fn delete_player_note(&mut self, note_id: i64) -> Result<bool> {
+        let deleted = self
+            .conn
+            .execute("DELETE FROM player_notes WHERE note_id = ?", [note_id])?;
+        Ok(deleted > 0)
+    }
+
+    /// Delete every player note
+    pub fn clear_player_notes(&mut self) -> Result<()> {
+        self.conn.execute("DELETE FROM player_notes", [])?;
+        Ok(())
+    }
+
     /// Clear all data from the database (useful for starting fresh)
+    ///
+    /// Player notes are user-entered rather than fetched, so they are kept; use
+    /// [`Self::clear_player_notes`] to remove them as well.
     pub fn clear_all_data(&mut self) -> Result<()> {
         // Delete all data from both tables (weekly stats first due to foreign key)
         self.conn.execute("DELETE FROM player_weekly_stats", [])?;
+        self.conn
+            .execute("DELETE FROM player_weekly_raw_stats", [])?;
         self.conn.execute("DELETE FROM players", [])?;
+        self.conn.execute("DELETE FROM fantasy_matchups", [])?;
+        self.conn.execute("DELETE FROM team_weekly_totals", [])?;
+        self.conn.execute("DELETE FROM matchup_period_weeks", [])?;
+        self.conn.execute("DELETE FROM roster_snapshots", [])?;
+        self.conn.execute("DELETE FROM fantasy_teams", [])?;
+        self.conn.execute("DELETE FROM player_news", [])?;
+        self.conn.execute("DELETE FROM injury_status_history", [])?;
+        self.conn.execute("DELETE FROM player_aliases", [])?;
+        self.conn.execute("DELETE FROM league_settings", [])?;
         Ok(())
     }
 
     /// Helper to convert database row to PlayerWeeklyStats
     pub(crate) fn row_to_weekly_stats(&self, row: &Row) -> rusqlite::Result<PlayerWeeklyStats> {
-        use crate::espn::types::InjuryStatus;
-
         let injury_status_str: Option<String> = row.get(7)?;
         let injury_status = injury_status_str
-            .map(|s| match s.as_str() {
-                "Active" => Some(InjuryStatus::Active),
-                "IR" => Some(InjuryStatus::InjuryReserve),
-                "Out" => Some(InjuryStatus::Out),
-                "Doubtful" => Some(InjuryStatus::Doubtful),
-                "Questionable" => Some(InjuryStatus::Questionable),
-                "Probable" => Some(InjuryStatus::Probable),
-                "Day-to-Day" => Some(InjuryStatus::DayToDay),
-                _ => Some(InjuryStatus::Unknown),
-            })
-            .unwrap_or(None);
+            .as_deref()
+            .map(InjuryStatus::parse_or_unknown);
 
         Ok(PlayerWeeklyStats {
             player_id: PlayerId::new(row.get(0)?),
@@ -531,8 +1620,49 @@ impl PlayerDatabase {
             is_rostered: row.get(8)?,
             fantasy_team_id: row.get(9)?,
             fantasy_team_name: row.get(10)?,
-            created_at: row.get(11)?,
-            updated_at: row.get(12)?,
+            played: row.get(11)?,
+            percent_owned: row.get(14)?,
+            percent_started: row.get(15)?,
+            opponent_team: row.get(16)?,
+            home_game: row.get(17)?,
+            league_id: Some(row.get::<_, u32>(18)?)
+                .filter(|&id| id != UNASSIGNED_LEAGUE)
+                .map(LeagueId::new),
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
         })
     }
 }
+
+/// Add the SQL predicate for an injury status filter
+///
+/// Mirrors `matches_injury_filter`: "active" also covers rows without a status that
+/// aren't flagged injured, and "injured" covers any status other than Active.
+fn push_injury_filter(
+    query: &mut String,
+    sql_params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    filter: &InjuryStatusFilter,
+) {
+    let (clause, status) = match filter {
+        InjuryStatusFilter::Active => (
+            " AND (pws.injury_status = ?
+                   OR (pws.injury_status IS NULL AND (pws.injured IS NULL OR pws.injured = 0)))",
+            InjuryStatus::Active,
+        ),
+        InjuryStatusFilter::Injured => (
+            " AND (pws.injured = 1
+                   OR (pws.injury_status IS NOT NULL AND pws.injury_status != ?))",
+            InjuryStatus::Active,
+        ),
+        InjuryStatusFilter::Out => (" AND pws.injury_status = ?", InjuryStatus::Out),
+        InjuryStatusFilter::Doubtful => (" AND pws.injury_status = ?", InjuryStatus::Doubtful),
+        InjuryStatusFilter::Questionable => {
+            (" AND pws.injury_status = ?", InjuryStatus::Questionable)
+        }
+        InjuryStatusFilter::Probable => (" AND pws.injury_status = ?", InjuryStatus::Probable),
+        InjuryStatusFilter::DayToDay => (" AND pws.injury_status = ?", InjuryStatus::DayToDay),
+        InjuryStatusFilter::IR => (" AND pws.injury_status = ?", InjuryStatus::InjuryReserve),
+    };
+    query.push_str(clause);
+    sql_params.push(Box::new(status.to_string()));
+}
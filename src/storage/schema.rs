@@ -1,43 +1,154 @@
 //! Database schema and connection management
 
-use crate::error::EspnError;
+use crate::{core::cache::GLOBAL_CACHE, error::EspnError, LeagueId, Season, Week, DB_PATH_ENV_VAR};
 use anyhow::Result;
 use dirs::cache_dir;
-use rusqlite::Connection;
-use std::path::PathBuf;
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    cell::Cell,
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+/// `league_id` of rows stored before leagues were tracked, until a league adopts them
+pub(crate) const UNASSIGNED_LEAGUE: u32 = 0;
+
+/// Tables whose rows are kept per league
+const LEAGUE_KEYED_TABLES: [&str; 6] = [
+    "player_weekly_stats",
+    "player_weekly_raw_stats",
+    "fantasy_matchups",
+    "team_weekly_totals",
+    "matchup_period_weeks",
+    "roster_snapshots",
+];
+
+/// How long to wait for another process's write lock before giving up
+const BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Database file given with `--db-path`
+static DATABASE_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use the database at `path` for the rest of the process instead of the default one
+pub fn set_database_path(path: PathBuf) {
+    let _ = DATABASE_PATH_OVERRIDE.set(path);
+}
 
 /// Database connection manager for player data
 pub struct PlayerDatabase {
     pub(crate) conn: Connection,
+    /// Weeks whose cached query results this connection has already dropped;
+    /// `None` for in-memory databases, which no cache is built from
+    invalidated_weeks: Option<HashSet<(Season, Week)>>,
+    /// Whether rows from before leagues were tracked have been handed to a league
+    unassigned_adopted: Cell<bool>,
 }
 
 impl PlayerDatabase {
     /// Create a new database connection and ensure tables exist
     pub fn new() -> Result<Self> {
-        let db_path = Self::database_path()?;
+        Self::open(&Self::database_path()?)
+    }
 
-        // Ensure the cache directory exists
-        if let Some(parent) = db_path.parent() {
+    /// Open the database file at `db_path`, creating it and its tables if needed
+    pub fn open(db_path: &Path) -> Result<Self> {
+        // Ensure the parent directory exists
+        if let Some(parent) = db_path.parent().filter(|p| !p.as_os_str().is_empty()) {
             std::fs::create_dir_all(parent)?;
         }
 
-        let conn = Connection::open(&db_path)?;
-        let mut db = Self { conn };
+        let conn = Connection::open(db_path)?;
+        // WAL lets readers proceed while another process writes; the busy timeout makes
+        // concurrent writers wait for the lock instead of failing with "database is locked"
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.query_row("PRAGMA journal_mode = WAL", [], |_| Ok(()))?;
+        let mut db = Self {
+            conn,
+            invalidated_weeks: Some(HashSet::new()),
+            unassigned_adopted: Cell::new(false),
+        };
         db.initialize_schema()?;
         Ok(db)
     }
 
+    /// Open the database for commands that only read from it
+    ///
+    /// Before anything has been stored there is no database file, and an empty
+    /// in-memory database stands in, so read-only commands report "no data"
+    /// without creating the file or its directory.
+    pub fn open_existing() -> Result<Self> {
+        if Self::database_path()?.exists() {
+            Self::new()
+        } else {
+            Self::new_in_memory()
+        }
+    }
+
     /// Create an in-memory database for testing
     pub fn new_in_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
         conn.execute("PRAGMA foreign_keys = ON", [])?;
-        let mut db = Self { conn };
+        let mut db = Self {
+            conn,
+            invalidated_weeks: None,
+            unassigned_adopted: Cell::new(false),
+        };
         db.initialize_schema()?;
         Ok(db)
     }
 
-    /// Get the path to the database file
-    fn database_path() -> Result<PathBuf> {
+    /// Drop cached query results built from a week's rows after writing to it
+    ///
+    /// Only the first write to each week does any work, and the cache files are
+    /// deleted in one batch when the command ends (see
+    /// [`CacheManager::flush_invalidations`](crate::core::cache::CacheManager::flush_invalidations)).
+    pub(crate) fn invalidate_cached_week(&mut self, season: Season, week: Week) {
+        if let Some(invalidated) = &mut self.invalidated_weeks {
+            if invalidated.insert((season, week)) {
+                GLOBAL_CACHE.invalidate_week(season, week);
+            }
+        }
+    }
+
+    /// Hand rows stored before leagues were tracked to the league being read or written
+    ///
+    /// A database only ever held one league's rows before, so the first league to
+    /// use it takes them over, rather than every query for it seeing each week
+    /// twice or reads missing the old weeks until something is stored. Rows it
+    /// already has a copy of are dropped. Runs once per connection; calls without
+    /// a league leave them alone.
+    pub(crate) fn adopt_unassigned_rows(&self, league_id: Option<LeagueId>) -> Result<()> {
+        let Some(league_id) = league_id.filter(|_| !self.unassigned_adopted.get()) else {
+            return Ok(());
+        };
+        self.unassigned_adopted.set(true);
+        // Reads only borrow the connection; no other transaction is open here
+        let tx = self.conn.unchecked_transaction()?;
+        for table in LEAGUE_KEYED_TABLES {
+            tx.execute(
+                &format!("UPDATE OR IGNORE {table} SET league_id = ? WHERE league_id = ?"),
+                params![league_id.as_u32(), UNASSIGNED_LEAGUE],
+            )?;
+            tx.execute(
+                &format!("DELETE FROM {table} WHERE league_id = ?"),
+                [UNASSIGNED_LEAGUE],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Get the path to the database file: `--db-path`, else `ESPN_FFL_DB_PATH`, else
+    /// `espn-ffl/players.db` in the cache directory
+    pub(crate) fn database_path() -> Result<PathBuf> {
+        if let Some(path) = DATABASE_PATH_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
+        if let Some(path) = std::env::var_os(DB_PATH_ENV_VAR).filter(|p| !p.is_empty()) {
+            return Ok(PathBuf::from(path));
+        }
         let cache_dir = cache_dir().ok_or_else(|| EspnError::Cache {
             message: "Could not determine cache directory".to_string(),
         })?;
@@ -46,6 +157,10 @@ impl PlayerDatabase {
 
     /// Initialize the database schema
     pub(crate) fn initialize_schema(&mut self) -> Result<()> {
+        // Bring a database from an older release up to date before creating anything,
+        // so a missing weekly stats table still means a new database
+        self.migrate()?;
+
         // Create players table
         self.conn.execute(
             "CREATE TABLE IF NOT EXISTS players (
@@ -71,39 +186,168 @@ impl PlayerDatabase {
                 is_rostered INTEGER,
                 fantasy_team_id INTEGER,
                 fantasy_team_name TEXT,
+                played INTEGER,
+                percent_owned REAL,
+                percent_started REAL,
+                opponent_team TEXT,
+                home_game INTEGER,
                 created_at INTEGER NOT NULL,
                 updated_at INTEGER NOT NULL,
-                PRIMARY KEY (player_id, season, week),
+                league_id INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (player_id, season, week, league_id),
                 FOREIGN KEY (player_id) REFERENCES players(player_id)
             )",
             [],
         )?;
 
-        // Add new columns if they don't exist (for database migration)
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN active INTEGER",
+        // Create player_weekly_raw_stats table (ESPN's actual stat map, one row per stat).
+        // Normalized rather than a JSON column so a category lookup walks only the rows
+        // that have that stat via the index below; zero values are not stored.
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS player_weekly_raw_stats (
+                player_id INTEGER NOT NULL,
+                season INTEGER NOT NULL,
+                week INTEGER NOT NULL,
+                stat_id INTEGER NOT NULL,
+                value REAL NOT NULL,
+                league_id INTEGER NOT NULL,
+                PRIMARY KEY (player_id, season, week, stat_id, league_id)
+            )",
+            [],
+        )?;
+
+        // Create fantasy_matchups table (league schedule results)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS fantasy_matchups (
+                season INTEGER NOT NULL,
+                matchup_period INTEGER NOT NULL,
+                matchup_id INTEGER NOT NULL,
+                home_team_id INTEGER NOT NULL,
+                away_team_id INTEGER,
+                winner TEXT NOT NULL,
+                updated_at INTEGER NOT NULL,
+                league_id INTEGER NOT NULL,
+                PRIMARY KEY (season, matchup_id, league_id)
+            )",
             [],
-        );
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN injured INTEGER",
+        )?;
+
+        // Create team_weekly_totals table (final team scores per matchup period)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS team_weekly_totals (
+                season INTEGER NOT NULL,
+                week INTEGER NOT NULL,
+                team_id INTEGER NOT NULL,
+                points REAL NOT NULL,
+                updated_at INTEGER NOT NULL,
+                league_id INTEGER NOT NULL,
+                PRIMARY KEY (season, week, team_id, league_id)
+            )",
             [],
-        );
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN injury_status TEXT",
+        )?;
+
+        // Create matchup_period_weeks table (weeks played in each matchup period)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS matchup_period_weeks (
+                season INTEGER NOT NULL,
+                matchup_period INTEGER NOT NULL,
+                week INTEGER NOT NULL,
+                league_id INTEGER NOT NULL,
+                PRIMARY KEY (season, week, league_id)
+            )",
             [],
-        );
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN is_rostered INTEGER",
+        )?;
+
+        // Create roster_snapshots table (fantasy rosters as of each week)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS roster_snapshots (
+                season INTEGER NOT NULL,
+                week INTEGER NOT NULL,
+                team_id INTEGER NOT NULL,
+                team_name TEXT,
+                player_id INTEGER NOT NULL,
+                lineup_slot_id INTEGER NOT NULL,
+                updated_at INTEGER NOT NULL,
+                league_id INTEGER NOT NULL,
+                PRIMARY KEY (season, week, player_id, league_id)
+            )",
             [],
-        );
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN fantasy_team_id INTEGER",
+        )?;
+
+        // Create fantasy_teams table (every name a team has gone by, per season)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS fantasy_teams (
+                league_id INTEGER NOT NULL,
+                season INTEGER NOT NULL,
+                team_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                abbrev TEXT,
+                first_seen_week INTEGER NOT NULL,
+                last_seen_week INTEGER NOT NULL,
+                PRIMARY KEY (league_id, season, team_id, name)
+            )",
             [],
-        );
-        let _ = self.conn.execute(
-            "ALTER TABLE player_weekly_stats ADD COLUMN fantasy_team_name TEXT",
+        )?;
+
+        // Create player_notes table (user annotations; kept by --clear-db).
+        // No foreign key: notes outlive the players table being cleared.
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS player_notes (
+                note_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                player_id INTEGER NOT NULL,
+                note TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            )",
             [],
-        );
+        )?;
+
+        // Create player_aliases table (names ESPN used for a player before renaming them)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS player_aliases (
+                player_id INTEGER NOT NULL,
+                alias TEXT NOT NULL,
+                replaced_at INTEGER NOT NULL,
+                PRIMARY KEY (player_id, alias)
+            )",
+            [],
+        )?;
+
+        // Create player_news table (latest ESPN headline per player)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS player_news (
+                player_id INTEGER PRIMARY KEY,
+                headline TEXT NOT NULL,
+                published INTEGER,
+                fetched_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create injury_status_history table (each injury designation seen for a player's
+        // week, appended when a fetch sees it change)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS injury_status_history (
+                history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                player_id INTEGER NOT NULL,
+                season INTEGER NOT NULL,
+                week INTEGER NOT NULL,
+                status TEXT NOT NULL,
+                observed_at INTEGER NOT NULL
+            )",
+            [],
+        )?;
+
+        // Create league_settings table (each league's scoring settings, per season)
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS league_settings (
+                league_id INTEGER NOT NULL,
+                season INTEGER NOT NULL,
+                settings_json TEXT NOT NULL,
+                fetched_at INTEGER NOT NULL,
+                PRIMARY KEY (league_id, season)
+            )",
+            [],
+        )?;
 
         // Create indexes for performance
         self.conn.execute(
@@ -119,6 +363,219 @@ impl PlayerDatabase {
             [],
         )?;
 
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_injury_status_history
+             ON injury_status_history(season, week, player_id)",
+            [],
+        )?;
+
+        self.conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_raw_stat_category
+             ON player_weekly_raw_stats(stat_id, season, week)",
+            [],
+        )?;
+
         Ok(())
     }
+
+    /// Apply the migrations newer than the database's schema version
+    ///
+    /// Databases from before versioning start at 0; their columns were added on
+    /// open, so any of the early migrations may already be in place and each one
+    /// skips what exists. A new database is created at [`SCHEMA_VERSION`]. All
+    /// pending migrations and the version bump are committed together.
+    fn migrate(&mut self) -> Result<()> {
+        self.conn.execute(
+            "CREATE TABLE IF NOT EXISTS schema_version (
+                id INTEGER PRIMARY KEY CHECK (id = 1),
+                version INTEGER NOT NULL
+            )",
+            [],
+        )?;
+        let stored: Option<u32> = self
+            .conn
+            .query_row(
+                "SELECT version FROM schema_version WHERE id = 1",
+                [],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let version = match stored {
+            Some(version) => version,
+            None if table_exists(&self.conn, "player_weekly_stats")? => 0,
+            None => SCHEMA_VERSION,
+        };
+
+        let tx = self.conn.transaction()?;
+        for migration in MIGRATIONS.iter().filter(|m| m.version > version) {
+            (migration.apply)(&tx)?;
+        }
+        tx.execute(
+            "INSERT OR REPLACE INTO schema_version (id, version) VALUES (1, ?)",
+            [version.max(SCHEMA_VERSION)],
+        )?;
+        tx.commit()?;
+        Ok(())
+    }
+}
+
+/// One change to the shape of an existing table
+struct Migration {
+    /// Schema version a database is at once this has been applied
+    version: u32,
+    apply: fn(&Connection) -> Result<()>,
+}
+
+/// Every change to an existing table since the first release, oldest first
+///
+/// New tables don't need one: `initialize_schema` creates whatever is missing.
+const MIGRATIONS: &[Migration] = &[
+    // Injury designations
+    Migration {
+        version: 1,
+        apply: |conn| {
+            add_missing_columns(
+                conn,
+                "player_weekly_stats",
+                &[
+                    ("active", "INTEGER"),
+                    ("injured", "INTEGER"),
+                    ("injury_status", "TEXT"),
+                ],
+            )
+        },
+    },
+    // Fantasy roster status
+    Migration {
+        version: 2,
+        apply: |conn| {
+            add_missing_columns(
+                conn,
+                "player_weekly_stats",
+                &[
+                    ("is_rostered", "INTEGER"),
+                    ("fantasy_team_id", "INTEGER"),
+                    ("fantasy_team_name", "TEXT"),
+                ],
+            )
+        },
+    },
+    // Rows stored before participation tracking keep played = NULL
+    Migration {
+        version: 3,
+        apply: |conn| add_missing_columns(conn, "player_weekly_stats", &[("played", "INTEGER")]),
+    },
+    // Ownership is only known for weeks fetched after it was tracked
+    Migration {
+        version: 4,
+        apply: |conn| {
+            add_missing_columns(
+                conn,
+                "player_weekly_stats",
+                &[("percent_owned", "REAL"), ("percent_started", "REAL")],
+            )
+        },
+    },
+    // The matchup is only known for weeks fetched after it was tracked
+    Migration {
+        version: 5,
+        apply: |conn| {
+            add_missing_columns(
+                conn,
+                "player_weekly_stats",
+                &[("opponent_team", "TEXT"), ("home_game", "INTEGER")],
+            )
+        },
+    },
+    // Rows are kept per league
+    Migration {
+        version: 6,
+        apply: key_weekly_stats_by_league,
+    },
+];
+
+/// Schema version of a database created by this release
+pub(crate) const SCHEMA_VERSION: u32 = MIGRATIONS[MIGRATIONS.len() - 1].version;
+
+fn table_exists(conn: &Connection, table: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type = 'table' AND name = ?",
+        [table],
+        |row| row.get(0),
+    )?)
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?",
+        [table, column],
+        |row| row.get(0),
+    )?)
+}
+
+fn add_missing_columns(conn: &Connection, table: &str, columns: &[(&str, &str)]) -> Result<()> {
+    for (column, sql_type) in columns {
+        if !has_column(conn, table, column)? {
+            conn.execute(
+                &format!("ALTER TABLE {table} ADD COLUMN {column} {sql_type}"),
+                [],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Rebuild a `player_weekly_stats` table from before leagues were tracked
+///
+/// SQLite can't change a primary key in place, so the rows are copied into a
+/// table keyed by league too. They're assigned to [`UNASSIGNED_LEAGUE`] until the
+/// first league to use the database adopts them.
+fn key_weekly_stats_by_league(conn: &Connection) -> Result<()> {
+    if has_column(conn, "player_weekly_stats", "league_id")? {
+        return Ok(());
+    }
+
+    let columns = "player_id, season, week, projected_points, actual_points,
+                   active, injured, injury_status, is_rostered, fantasy_team_id,
+                   fantasy_team_name, played, percent_owned, percent_started,
+                   opponent_team, home_game, created_at, updated_at";
+    conn.execute(
+        "CREATE TABLE player_weekly_stats_by_league (
+            player_id INTEGER,
+            season INTEGER,
+            week INTEGER,
+            projected_points REAL,
+            actual_points REAL,
+            active INTEGER,
+            injured INTEGER,
+            injury_status TEXT,
+            is_rostered INTEGER,
+            fantasy_team_id INTEGER,
+            fantasy_team_name TEXT,
+            played INTEGER,
+            percent_owned REAL,
+            percent_started REAL,
+            opponent_team TEXT,
+            home_game INTEGER,
+            created_at INTEGER NOT NULL,
+            updated_at INTEGER NOT NULL,
+            league_id INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (player_id, season, week, league_id),
+            FOREIGN KEY (player_id) REFERENCES players(player_id)
+        )",
+        [],
+    )?;
+    conn.execute(
+        &format!(
+            "INSERT INTO player_weekly_stats_by_league ({columns}, league_id)
+             SELECT {columns}, ? FROM player_weekly_stats"
+        ),
+        [UNASSIGNED_LEAGUE],
+    )?;
+    conn.execute("DROP TABLE player_weekly_stats", [])?;
+    conn.execute(
+        "ALTER TABLE player_weekly_stats_by_league RENAME TO player_weekly_stats",
+        [],
+    )?;
+    Ok(())
 }
@@ -0,0 +1,2061 @@
+//! End-to-end tests that run the `espn-ffl` binary against a mocked ESPN API
+//!
+//! Each test gets its own mock server and cache directory (which holds the
+//! database), so scenarios only need to mount the payloads they rely on.
+
+mod common;
+
+use common::{MockEspn, TEST_LEAGUE_ID, TEST_SEASON};
+use espn_ffl::{storage::PlayerDatabase, LeagueId, PlayerId, Season, Week};
+use predicates::prelude::*;
+use serde_json::Value;
+use wiremock::{
+    matchers::{header, method, path, query_param},
+    Mock, ResponseTemplate,
+};
+
+const SEASON: &str = "2025";
+
+/// Parse a command's stdout as JSON, failing with the raw output if it isn't
+fn stdout_json(output: &[u8]) -> Value {
+    let text = String::from_utf8_lossy(output);
+    serde_json::from_str(&text).unwrap_or_else(|e| panic!("not JSON ({}):\n{}", e, text))
+}
+
+/// Assert every object in a JSON array has exactly these keys
+fn assert_keys(rows: &Value, expected: &[&str]) {
+    let rows = rows.as_array().expect("JSON array");
+    assert!(!rows.is_empty());
+    for row in rows {
+        let mut keys: Vec<&str> = row
+            .as_object()
+            .expect("JSON object")
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        let mut expected = expected.to_vec();
+        expected.sort_unstable();
+        assert_eq!(keys, expected);
+    }
+}
+
+/// Assert every object in a JSON array has the `required` keys and otherwise only
+/// `optional` ones, which are left out when unknown
+fn assert_keys_within(rows: &Value, required: &[&str], optional: &[&str]) {
+    let rows = rows.as_array().expect("JSON array");
+    assert!(!rows.is_empty());
+    for row in rows {
+        let row = row.as_object().expect("JSON object");
+        for key in required {
+            assert!(row.contains_key(*key), "missing {}: {:?}", key, row);
+        }
+        for (key, value) in row {
+            assert!(
+                required.contains(&key.as_str()) || optional.contains(&key.as_str()),
+                "unexpected {}: {:?}",
+                key,
+                row
+            );
+            assert!(!value.is_null(), "{} is null: {:?}", key, row);
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_data_rerun_is_served_from_cache() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Test Quarterback"))
+        .stdout(predicate::str::contains(
+            "Found 2 players with fantasy points",
+        ));
+    assert_eq!(mock.player_fetches().await, 1);
+
+    // The same week again comes from the database without asking ESPN
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Test Runningback"));
+    assert_eq!(mock.player_fetches().await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_both_scores_projected_and_actual_from_one_fetch() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--both"])
+        .args(["--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows = stdout_json(&output.stdout);
+    assert_eq!(rows[0]["id"], 100);
+    assert_eq!(rows[0]["projected_points"], 18.0);
+    assert_eq!(rows[0]["actual_points"], 20.0);
+    assert_eq!(rows[0]["delta"], 2.0);
+    assert_eq!(mock.player_fetches().await, 1);
+
+    // Either side alone is then served from the database
+    for args in [&["--json"][..], &["--json", "--proj"][..]] {
+        mock.cli()
+            .args(["player-data", "--season", SEASON, "--week", "1"])
+            .args(args)
+            .assert()
+            .success();
+    }
+    assert_eq!(mock.player_fetches().await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sort_orders_player_data_rows() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let ids = |args: &[&str]| -> Vec<i64> {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        stdout_json(&output.stdout)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect()
+    };
+    assert_eq!(ids(&[]), vec![100, 200]);
+    assert_eq!(ids(&["--sort", "points", "--reverse"]), vec![200, 100]);
+    // The rostered QB's team comes before the free agent RB
+    assert_eq!(ids(&["--sort", "team"]), vec![100, 200]);
+    assert_eq!(ids(&["--sort", "name", "--reverse"]), vec![200, 100]);
+    // Actual minus projected: the QB beat the projection by 2, the RB fell 2 short
+    assert_eq!(
+        ids(&["--both", "--sort", "projection-diff", "--reverse"]),
+        vec![200, 100]
+    );
+
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .args(["--sort", "projection-diff"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("SortRequiresBoth"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_unplayed_week_warns_to_use_projections() {
+    let mock = MockEspn::start().await;
+    // Only projections so far: the week hasn't been played
+    let mut players = common::players_fixture(1);
+    for player in players.as_array_mut().unwrap() {
+        player["stats"]
+            .as_array_mut()
+            .unwrap()
+            .retain(|stats| stats["statSourceId"] == 1);
+    }
+    mock.mock_players(players).await;
+    mock.mock_defaults().await;
+
+    let run = |args: &[&str]| {
+        mock.cli()
+            .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+            .args(args)
+            .assert()
+            .success()
+    };
+    let warning = "Week 1 has not been played yet; use --proj for projections";
+    let output = run(&[]).stderr(predicate::str::contains(warning));
+    assert_eq!(
+        stdout_json(&output.get_output().stdout),
+        Value::Array(vec![])
+    );
+    run(&["--quiet"]).stderr(predicate::str::contains(warning).not());
+    // Projections are what's there, so no warning for them
+    let output = run(&["--proj"]).stderr(predicate::str::contains(warning).not());
+    assert_eq!(
+        stdout_json(&output.get_output().stdout)
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_data_json_schema() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let required = ["id", "name", "position", "week", "projected", "points"];
+    let optional = [
+        "active",
+        "injured",
+        "injury_status",
+        "is_rostered",
+        "team_id",
+        "team_name",
+        "played",
+        "opponent_team",
+        "home_game",
+    ];
+    let rows = stdout_json(&output.stdout);
+    assert_keys_within(&rows, &required, &optional);
+    let row = |id: i64| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["id"] == id)
+            .unwrap()
+            .clone()
+    };
+    let qb = row(100);
+    assert_eq!(qb["points"], 20.0);
+    assert_eq!(qb["position"], "QB");
+    assert_eq!(qb["team_name"], "Team Alpha");
+    // Free agents have no fantasy team, so the fields are left out
+    assert!(row(200).get("team_id").is_none());
+
+    // --legacy-json keeps every field, with nulls
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .arg("--legacy-json")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows = stdout_json(&output.stdout);
+    assert_keys(&rows, &[&required[..], &optional[..]].concat());
+    let rb = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["id"] == 200)
+        .unwrap();
+    assert!(rb["team_id"].is_null());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_data_stores_each_players_opponent() {
+    let mock = MockEspn::start().await;
+    // The QB (Buffalo) hosts the RB (Cincinnati) in week 1
+    let mut players = common::players_fixture(1);
+    players[0]["proTeamId"] = 2.into();
+    players[1]["proTeamId"] = 4.into();
+    mock.mock_players(players).await;
+    mock.mock_defaults().await;
+    let game = serde_json::json!({
+        "1": [{ "id": 7, "date": 0, "homeProTeamId": 2, "awayProTeamId": 4 }]
+    });
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}", TEST_SEASON)))
+        .and(query_param("view", "proTeamSchedules_wl"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "settings": {
+                "proTeams": [
+                    { "id": 2, "abbrev": "Buf", "proGamesByScoringPeriod": game },
+                    { "id": 4, "abbrev": "Cin", "proGamesByScoringPeriod": game }
+                ]
+            }
+        })))
+        .mount(&mock.server)
+        .await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let matchups: Vec<_> = stdout_json(&output.stdout)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| {
+            (
+                row["id"].clone(),
+                row["opponent_team"].clone(),
+                row["home_game"].clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        matchups,
+        vec![
+            (100.into(), "CIN".into(), true.into()),
+            (200.into(), "BUF".into(), false.into())
+        ]
+    );
+
+    // Served from the database, the matchup is still shown
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Opp"))
+        .stdout(predicate::str::contains("@BUF"));
+    assert_eq!(mock.player_fetches().await, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_status_changed_since_lists_players_whose_status_changed() {
+    let mock = MockEspn::start().await;
+    let mount = |status: &'static str| {
+        let mock = &mock;
+        async move {
+            mock.server.reset().await;
+            let mut players = common::players_fixture(1);
+            players[1]["injuryStatus"] = status.into();
+            mock.mock_players(players).await;
+            mock.mock_defaults().await;
+        }
+    };
+    let ids = |args: &[&str]| -> Vec<i64> {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        stdout_json(&output.stdout)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    mount("QUESTIONABLE").await;
+    ids(&[]);
+    // Nothing changed yet: the first status seen for the week isn't a change
+    assert!(ids(&["--status-changed-since", "1h"]).is_empty());
+
+    // The free-agent runningback is ruled out before kickoff
+    mount("OUT").await;
+    ids(&["--refresh"]);
+    assert_eq!(ids(&["--status-changed-since", "1h"]), vec![200]);
+    assert!(ids(&["--status-changed-since", "2100-01-01T00:00Z"]).is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_db_path_overrides_the_database_location() {
+    let mock = MockEspn::start().await;
+    mock.mock_players(common::players_fixture(1)).await;
+    mock.mock_defaults().await;
+    let db_path = mock.cache_dir.path().join("leagues").join("scratch.db");
+    let default_path = mock.cache_dir.path().join("espn-ffl").join("players.db");
+
+    mock.cli()
+        .args(["--db-path", db_path.to_str().unwrap()])
+        .args(["player-data", "--season", SEASON, "--week", "1", "--debug"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(format!(
+            "Database => {}",
+            db_path.display()
+        )));
+    assert!(db_path.exists());
+    assert!(!default_path.exists());
+
+    // The environment variable points at the same file
+    let output = mock
+        .cli()
+        .env("ESPN_FFL_DB_PATH", &db_path)
+        .args(["stats-history", "-n", "100", "--season", SEASON, "--json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(stdout_json(&output.stdout).as_array().unwrap().len(), 1);
+    assert!(!default_path.exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_stats_history_lists_a_players_stored_weeks() {
+    let mock = MockEspn::start().await;
+    mock.mock_players(common::players_fixture(1)).await;
+    mock.mock_defaults().await;
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success();
+
+    // A partial name resolves when only one player matches
+    let output = mock
+        .cli()
+        .args([
+            "stats-history",
+            "-n",
+            "quarter",
+            "--season",
+            SEASON,
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows = stdout_json(&output.stdout);
+    assert_eq!(rows.as_array().unwrap().len(), 1);
+    assert_eq!(rows[0]["player_id"], 100);
+    assert_eq!(rows[0]["week"], 1);
+
+    mock.cli()
+        .args([
+            "stats-history",
+            "-n",
+            "Test Quarterback",
+            "--season",
+            SEASON,
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Delta"))
+        .stdout(predicate::str::contains("Trend:"));
+
+    mock.cli()
+        .args(["stats-history", "-n", "Test", "--season", SEASON])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Test Runningback = 200"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_odd_scoring_items_still_load_and_score() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::odd_scoring_settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    // One interception, which the reverse item turns into -2 points
+    let mut players = common::players_fixture(1);
+    players[0]["stats"][0]["stats"]["20"] = 1.0.into();
+    mock.mock_players(players).await;
+
+    mock.cli()
+        .args(["league-data", "--season", SEASON, "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            r#"⚠ Warning: stat 4: skipped pointsOverrides key(s) "-", "ALL""#,
+        ));
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows = stdout_json(&output.stdout);
+    let qb = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["id"] == 100)
+        .unwrap();
+    // 300 yards * 0.04 + 2 TDs * 4 (the QB override) - 2 for the interception
+    assert_eq!(qb["points"], 18.0);
+}
+
+/// Player IDs `player-data -p FLEX --json` returns
+fn flex_player_ids(mock: &MockEspn) -> Vec<i64> {
+    let output = mock
+        .cli()
+        .args([
+            "player-data",
+            "--season",
+            SEASON,
+            "--week",
+            "1",
+            "-p",
+            "FLEX",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let mut ids: Vec<i64> = stdout_json(&output.stdout)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["id"].as_i64().unwrap())
+        .collect();
+    ids.sort_unstable();
+    ids
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_flex_filter_includes_qbs_in_superflex_leagues() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::superflex_settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    mock.mock_players(common::players_fixture(1)).await;
+
+    // The QB can fill the OP slot, so FLEX keeps it alongside the RB
+    assert_eq!(flex_player_ids(&mock), vec![100, 200]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_flex_filter_excludes_qbs_without_superflex() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+    assert_eq!(flex_player_ids(&mock), vec![200]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_flex_filter_follows_a_wr_te_only_flex_slot() {
+    let mock = MockEspn::start().await;
+    let mut settings = common::settings_fixture();
+    let slots = &mut settings["settings"]["rosterSettings"]["lineupSlotCounts"];
+    slots.as_object_mut().unwrap().remove("23");
+    slots["5"] = 1.into();
+    mock.mock_league_view("mSettings", settings).await;
+    mock.mock_defaults().await;
+
+    // Neither the QB nor the RB can fill a WR/TE slot
+    assert!(flex_player_ids(&mock).is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_group_by_team_subtotals_and_limits_each_team() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    // A second free agent, worth half the RB
+    let mut players = common::players_fixture(1);
+    let mut backup = players[1].clone();
+    backup["id"] = 300.into();
+    backup["fullName"] = "Backup Runningback".into();
+    for stats in backup["stats"].as_array_mut().unwrap() {
+        let yards = stats["stats"]["24"].as_f64().unwrap();
+        stats["stats"]["24"] = (yards / 2.0).into();
+    }
+    players.as_array_mut().unwrap().push(backup);
+    mock.mock_players(players).await;
+
+    let run = |args: &[&str]| {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--week", "1"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    };
+    let group_ids = |group: &Value| -> Vec<i64> {
+        group["players"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    let groups = stdout_json(&run(&["--group-by", "team", "--json"]).stdout);
+    let groups = groups.as_array().unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups[0]["team_name"], "Team Alpha");
+    assert_eq!(groups[0]["points"], 20.0);
+    assert_eq!(group_ids(&groups[0]), vec![100]);
+    // Free agents come last, best first
+    assert!(groups[1]["team_name"].is_null());
+    assert_eq!(groups[1]["points"], 15.0);
+    assert_eq!(group_ids(&groups[1]), vec![200, 300]);
+
+    // --top limits each team when grouping, and the whole list otherwise
+    let groups = stdout_json(&run(&["--group-by", "team", "--top", "1", "--json"]).stdout);
+    let sizes: Vec<usize> = groups
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|g| group_ids(g).len())
+        .collect();
+    assert_eq!(sizes, vec![1, 1]);
+    let rows = stdout_json(&run(&["--top", "1", "--json"]).stdout);
+    assert_eq!(rows.as_array().unwrap().len(), 1);
+    assert_eq!(rows[0]["id"], 100);
+
+    // --both subtotals projected and actual points per team
+    let groups = stdout_json(&run(&["--both", "--group-by", "team", "--json"]).stdout);
+    assert_eq!(groups[0]["projected_points"], 18.0);
+    assert_eq!(groups[0]["actual_points"], 20.0);
+    assert_eq!(groups[1]["projected_points"], 18.0);
+    assert_eq!(groups[1]["actual_points"], 15.0);
+
+    let stdout = String::from_utf8_lossy(&run(&["--group-by", "team"]).stdout).to_string();
+    assert!(
+        stdout.contains("== Team Alpha (1 players, 20.00 pts) =="),
+        "{}",
+        stdout
+    );
+    assert!(
+        stdout.contains("== Free Agents (2 players, 15.00 pts) =="),
+        "{}",
+        stdout
+    );
+    assert!(stdout.find("Team Alpha") < stdout.find("Free Agents"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_renamed_player_is_found_by_old_and_new_name() {
+    let mock = MockEspn::start().await;
+    let mount = |name: &'static str| {
+        let mock = &mock;
+        async move {
+            mock.server.reset().await;
+            mock.mock_league_view("mSettings", common::settings_fixture())
+                .await;
+            mock.mock_league_view("mRoster", common::roster_fixture())
+                .await;
+            let mut players = common::players_fixture(1);
+            players[0]["fullName"] = name.into();
+            mock.mock_players(players).await;
+        }
+    };
+    let run = |args: &[&str]| {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    };
+    let ids = |output: &std::process::Output| -> Vec<i64> {
+        stdout_json(&output.stdout)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect()
+    };
+
+    mount("Hollywood Brown").await;
+    run(&[]);
+    // ESPN renames the player between fetches
+    mount("Marquise Brown").await;
+    let output = run(&["--refresh"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("renamed player 100 from Hollywood Brown to Marquise Brown"),
+        "{}",
+        stderr
+    );
+
+    // Both names filter to the same player. A single name is filtered by ESPN,
+    // which the mock doesn't do; the old name is widened to both names and
+    // filtered locally.
+    assert!(ids(&run(&["-n", "Marquise"])).contains(&100));
+    assert_eq!(ids(&run(&["-n", "Hollywood"])), vec![100]);
+
+    // Exact-name lookups accept the old name too
+    mock.cli()
+        .args(["note", "add", "Hollywood Brown", "deep threat"])
+        .assert()
+        .success();
+    let output = mock
+        .cli()
+        .args(["db", "aliases", "--player", "Marquise Brown", "--json"])
+        .output()
+        .unwrap();
+    let aliases = stdout_json(&output.stdout);
+    assert_eq!(aliases.as_array().unwrap().len(), 1);
+    assert_eq!(aliases[0]["player_id"], 100);
+    assert_eq!(aliases[0]["alias"], "Hollywood Brown");
+    assert_eq!(aliases[0]["name"], "Marquise Brown");
+    mock.cli()
+        .args(["note", "list", "Marquise Brown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("deep threat"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_nfl_team_filter_includes_the_team_defense() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    let mut players = common::players_fixture(1);
+    players[0]["proTeamId"] = 2.into();
+    players[1]["proTeamId"] = 4.into();
+    // ESPN leaves proTeamId off the defense; its ID encodes the Bills
+    let mut defense = players[1].clone();
+    defense["id"] = (-16002).into();
+    defense["fullName"] = "Bills D/ST".into();
+    defense["defaultPositionId"] = 16.into();
+    defense.as_object_mut().unwrap().remove("proTeamId");
+    players.as_array_mut().unwrap().push(defense);
+    mock.mock_players(players).await;
+
+    // Every player is stored with its team
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .assert()
+        .success();
+    let db = PlayerDatabase::new().unwrap();
+    let teams = db
+        .get_player_teams(&[PlayerId::new(-16002), PlayerId::new(200)])
+        .unwrap();
+    assert_eq!(teams[&PlayerId::new(-16002)], "BUF");
+    assert_eq!(teams[&PlayerId::new(200)], "CIN");
+
+    let bills = |refresh: bool| {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--week", "1"])
+            .args(["--nfl-team", "buf", "--json"])
+            .args(refresh.then_some("--refresh"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let mut ids: Vec<i64> = stdout_json(&output.stdout)
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_i64().unwrap())
+            .collect();
+        ids.sort_unstable();
+        ids
+    };
+    // Filtered from ESPN's response, then from the database
+    assert_eq!(bills(true), vec![-16002, 100]);
+    assert_eq!(bills(false), vec![-16002, 100]);
+
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--nfl-team", "XYZ"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Unknown NFL team: XYZ"));
+}
+
+#[tokio::test]
+async fn test_with_news_shows_latest_stored_headline() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    let mut players = common::players_fixture(1);
+    players[0]["news"] = serde_json::json!({
+        "feed": [
+            {"headline": "QB limited at practice", "published": "2025-09-03T18:00:00Z"},
+            {"headline": "QB full participant", "published": "2025-09-05T18:00:00.250Z"}
+        ]
+    });
+    // An empty block is ignored without touching the player's stats
+    players[1]["news"] = serde_json::json!({});
+    mock.mock_players(players).await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .args(["--with-news", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rows = stdout_json(&output.stdout);
+    let row = |id: i64| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["id"] == id)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(row(100)["news"]["headline"], "QB full participant");
+    assert_eq!(row(100)["news"]["published"], 1_757_095_200);
+    assert!(row(200)["news"].is_null());
+    assert!(row(200)["points"].as_f64().unwrap() > 0.0);
+
+    // Served from the database, in the text table
+    mock.cli()
+        .args([
+            "player-data",
+            "--season",
+            SEASON,
+            "--week",
+            "1",
+            "--with-news",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("News"))
+        .stdout(predicate::str::contains("QB full participant (2025-09-05)"));
+
+    // Without the flag the output is unchanged
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(stdout_json(&output.stdout)[0].get("news").is_none());
+}
+
+/// Parse newline-delimited JSON, checking each line is a complete object on its own
+fn stdout_json_lines(output: &[u8]) -> Vec<Value> {
+    String::from_utf8_lossy(output)
+        .lines()
+        .map(|line| {
+            serde_json::from_str(line).unwrap_or_else(|e| panic!("not JSON ({}): {}", e, line))
+        })
+        .collect()
+}
+
+/// Run a command with `--json` and with `--json-lines`, returning both outputs
+fn json_and_json_lines(mock: &MockEspn, args: &[&str]) -> (Value, Vec<Value>) {
+    let run = |flag: &str| {
+        let output = mock.cli().args(args).arg(flag).output().unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output.stdout
+    };
+    (
+        stdout_json(&run("--json")),
+        stdout_json_lines(&run("--json-lines")),
+    )
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_json_lines_match_the_json_array() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(3).await;
+
+    let (array, lines) =
+        json_and_json_lines(&mock, &["player-data", "--season", SEASON, "--week", "1"]);
+    assert_eq!(array.as_array().unwrap().len(), 2);
+    assert_eq!(&array, &Value::Array(lines));
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .assert()
+        .success();
+    let (array, lines) = json_and_json_lines(
+        &mock,
+        &["projection-analysis", "--season", SEASON, "--week", "3"],
+    );
+    assert!(!lines.is_empty());
+    assert_eq!(&array, &Value::Array(lines));
+
+    // db export streams the same player-weeks as its JSON array
+    let export = |format: &str| {
+        let output = mock
+            .cli()
+            .args(["db", "export", "--season", SEASON, "--format", format])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        output.stdout
+    };
+    let array = stdout_json(&export("json"));
+    let lines = stdout_json_lines(&export("json-lines"));
+    assert_eq!(array.as_array().unwrap().len(), 4);
+    assert_eq!(array, Value::Array(lines));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_timings_summary_goes_to_stderr() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args([
+            "player-data",
+            "--season",
+            SEASON,
+            "--week",
+            "1",
+            "--json",
+            "--timings",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    // stdout stays valid JSON; the summary is on stderr
+    stdout_json(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Run timings"), "{}", stderr);
+    assert!(stderr.contains("players"));
+    assert!(stderr.contains("Database rows written"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_corrupt_cache_file_is_removed_and_refetched() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+    let roster_fetches = || async {
+        mock.server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.query().unwrap_or_default().contains("mRoster"))
+            .count()
+    };
+    let run = || {
+        mock.cli()
+            .args([
+                "player-data",
+                "--season",
+                SEASON,
+                "--week",
+                "1",
+                "--timings",
+            ])
+            .output()
+            .unwrap()
+    };
+
+    assert!(run().status.success());
+    assert_eq!(roster_fetches().await, 1);
+    let league_dir = mock
+        .cache_dir
+        .path()
+        .join("espn-ffl")
+        .join(TEST_LEAGUE_ID.to_string());
+    let roster_file = std::fs::read_dir(&league_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .find(|path| path.to_string_lossy().contains("roster_data"))
+        .expect("roster cache file");
+    std::fs::write(&roster_file, "{\"fetched_at\": 17").unwrap();
+
+    // The corrupt file is reported, counted, removed and replaced by a fresh fetch
+    let output = run();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("corrupt cache file"), "{}", stderr);
+    assert!(stderr.contains("Errors"), "{}", stderr);
+    assert_eq!(roster_fetches().await, 2);
+    let rewritten: Value =
+        serde_json::from_str(&std::fs::read_to_string(&roster_file).unwrap()).unwrap();
+    assert!(rewritten["value"].is_object());
+
+    // The next run reads the rewritten file cleanly
+    let output = run();
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("corrupt cache file"));
+    assert_eq!(roster_fetches().await, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_data_week_range_combines_weeks() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    // The RB outscores the QB in week 2
+    let mut week2 = common::players_fixture(2);
+    week2[1]["stats"][0]["stats"]["24"] = 300.0.into();
+    mock.mock_players_week(1, common::players_fixture(1)).await;
+    mock.mock_players_week(2, week2).await;
+
+    let run = |args: &[&str]| {
+        let output = mock
+            .cli()
+            .args(["player-data", "--season", SEASON, "--json"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        stdout_json(&output.stdout)
+    };
+    let week_and_id = |rows: &Value| -> Vec<(i64, i64)> {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .map(|row| (row["week"].as_i64().unwrap(), row["id"].as_i64().unwrap()))
+            .collect()
+    };
+
+    // One flat array, weeks in order, points descending within each week
+    let rows = run(&["--weeks", "1-2"]);
+    assert_eq!(
+        week_and_id(&rows),
+        vec![(1, 100), (1, 200), (2, 200), (2, 100)]
+    );
+    // --top applies per week; the range is served from the database on a rerun
+    let rows = run(&["--week-range", "1,2", "--top", "1"]);
+    assert_eq!(week_and_id(&rows), vec![(1, 100), (2, 200)]);
+    let rows = run(&["--weeks", "1-2", "--refresh", "--top", "1"]);
+    assert_eq!(week_and_id(&rows), vec![(1, 100), (2, 200)]);
+
+    mock.cli()
+        .args(["player-data", "--weeks", "1-2", "--both"])
+        .assert()
+        .failure();
+    mock.cli()
+        .args(["projection-analysis", "--season", SEASON, "--weeks", "1-2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("unexpected argument '--weeks'"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_player_data_and_projection_analysis_csv() {
+    let mock = MockEspn::start().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+    mock.mock_league_status(&[]).await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    let mut roster = common::roster_fixture();
+    roster["teams"][0]["name"] = "Kenny Rogers' Toasters, \"KRT\"".into();
+    mock.mock_league_view("mRoster", roster).await;
+    mock.mock_players_week(3, common::players_fixture(3)).await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "3", "--csv"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec![
+            "id,name,position,week,projected,points,injury_status,is_rostered,team_name",
+            "100,Test Quarterback,QB,3,false,20.00,Active,true,\"Kenny Rogers' Toasters, \"\"KRT\"\"\"",
+            "200,Test Runningback,RB,3,false,10.00,Active,false,",
+        ],
+        "{}",
+        stdout
+    );
+
+    let output = mock
+        .cli()
+        .args([
+            "projection-analysis",
+            "--season",
+            SEASON,
+            "--week",
+            "3",
+            "--csv",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines[0],
+        "player_id,name,position,team,espn_projection,bias_adjustment,estimated_points,confidence,error_std,percent_started,tier,reasoning"
+    );
+    assert_eq!(lines.len(), 3, "{}", stdout);
+
+    mock.cli()
+        .args(["player-data", "--csv", "--json"])
+        .assert()
+        .failure();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_full_league_pull_is_scored_across_chunks() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::roster_fixture())
+        .await;
+    // Enough free-agent RBs to fill more than one scoring chunk, with the rostered
+    // QB last so its roster status is applied in a later chunk
+    let fixture = common::players_fixture(1);
+    let mut players: Vec<Value> = (0..250)
+        .map(|i| {
+            let mut rb = fixture[1].clone();
+            rb["id"] = (1000 + i).into();
+            rb["fullName"] = format!("Depth Runningback {}", i).into();
+            rb
+        })
+        .collect();
+    players.push(fixture[0].clone());
+    mock.mock_players(Value::Array(players)).await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--json"])
+        .args(["--max-threads", "1", "--timings"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let rows = stdout_json(&output.stdout);
+    assert_eq!(rows.as_array().unwrap().len(), 251);
+    let qb = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["id"] == 100)
+        .unwrap();
+    assert_eq!(qb["team_id"], 1);
+    if cfg!(target_os = "linux") {
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Peak memory"));
+    }
+
+    let db = PlayerDatabase::new().unwrap();
+    let stored = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.is_rostered, Some(true));
+    assert_eq!(stored.fantasy_team_id, Some(1));
+    assert!(db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(1249),
+            Season::new(TEST_SEASON),
+            Week::new(1)
+        )
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_team_totals_share_report_escapes_team_names() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", common::settings_fixture())
+        .await;
+    let mut roster = common::roster_fixture();
+    roster["teams"][0]["name"] = "<script>alert('x')</script> FC".into();
+    mock.mock_league_view("mRoster", roster).await;
+    mock.mock_players(common::players_fixture(1)).await;
+
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success();
+    let share = mock.cache_dir.path().join("week1.html");
+    mock.cli()
+        .args(["team-totals", "--season", SEASON, "--week", "1", "--share"])
+        .arg(&share)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Wrote share report"));
+
+    let html = std::fs::read_to_string(&share).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("Season 2025 Week 1"));
+    assert!(html.contains("&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt; FC"));
+    assert!(!html.contains("<script"));
+    assert!(html.contains("<td class=\"num\">20.0</td>"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_roster_shows_slots_and_stored_points() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    // Before any points are stored the roster still lists the player
+    let output = mock
+        .cli()
+        .args(["roster", "--season", SEASON, "--week", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let rosters = stdout_json(&output.stdout);
+    assert_eq!(rosters[0]["team_name"], "Team Alpha");
+    assert_eq!(rosters[0]["players"][0]["player_id"], 100);
+    assert_eq!(rosters[0]["players"][0]["slot"], "starter");
+    assert!(rosters[0]["players"][0]["points"].is_null());
+
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success();
+    mock.cli()
+        .args(["roster", "--season", SEASON, "--week", "1"])
+        .args(["--team", "alpha"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Team Alpha"))
+        .stdout(predicate::str::contains("Test Quarterback"))
+        .stdout(predicate::str::contains("20.0"));
+
+    let output = mock
+        .cli()
+        .args(["roster", "--season", SEASON, "--week", "1", "--json"])
+        .args(["--team-id", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(stdout_json(&output.stdout), serde_json::json!([]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_roster_changes_lists_adds_with_positions() {
+    let mock = MockEspn::start().await;
+    // Team Alpha picks up the RB in week 5; mounted first so it wins for that week
+    Mock::given(method("GET"))
+        .and(path(mock.league_path()))
+        .and(query_param("view", "mRoster"))
+        .and(query_param("scoringPeriodId", "5"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "teams": [{
+                "id": 1,
+                "name": "Team Alpha",
+                "roster": { "entries": [
+                    { "playerId": 100, "lineupSlotId": 0 },
+                    { "playerId": 200, "lineupSlotId": 20 }
+                ] }
+            }]
+        })))
+        .mount(&mock.server)
+        .await;
+    mock.mock_defaults().await;
+    mock.cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .assert()
+        .success();
+
+    let output = mock
+        .cli()
+        .args(["roster-changes", "--season", SEASON])
+        .args(["--from-week", "4", "--to-week", "5", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let diffs = stdout_json(&output.stdout);
+    assert_eq!(diffs[0]["team_name"], "Team Alpha");
+    assert_eq!(diffs[0]["added"][0]["player_id"], 200);
+    assert_eq!(diffs[0]["added"][0]["position"], "RB");
+    assert_eq!(diffs[0]["dropped"], serde_json::json!([]));
+
+    mock.cli()
+        .args([
+            "roster-diff",
+            "--season",
+            SEASON,
+            "--from",
+            "4",
+            "--to",
+            "5",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "+ Test Runningback (RB, bench) 0.0 pts [--]",
+        ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_all_data_writes_every_week() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(3).await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "3"])
+        .assert()
+        .success();
+
+    // Both players, both sources, for each week
+    let db = PlayerDatabase::new().unwrap();
+    for player_id in [100, 200] {
+        let weeks = db
+            .get_player_season_stats(
+                LeagueId::new(TEST_LEAGUE_ID),
+                PlayerId::new(player_id),
+                Season::new(TEST_SEASON),
+            )
+            .unwrap();
+        let stored: Vec<u16> = weeks.iter().map(|s| s.week.as_u16()).collect();
+        assert_eq!(stored, vec![1, 2, 3]);
+        assert!(weeks
+            .iter()
+            .all(|s| s.actual_points.is_some() && s.projected_points.is_some()));
+    }
+    let qb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(2),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(qb.actual_points, Some(20.0));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_all_data_weeks_list() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(3).await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--weeks", "3,1"])
+        .assert()
+        .success();
+
+    let db = PlayerDatabase::new().unwrap();
+    let weeks = db
+        .get_player_season_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+        )
+        .unwrap();
+    let stored: Vec<u16> = weeks.iter().map(|s| s.week.as_u16()).collect();
+    assert_eq!(stored, vec![1, 3]);
+
+    // Reversed ranges are rejected before anything is fetched
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--weeks", "3-1"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("week 3 must come before week 1"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_all_data_prints_summary_before_fetching() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(3).await;
+
+    // Without a terminal the update starts on its own after the summary
+    let output = mock
+        .cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let summary = stdout.split("Processing Week 1").next().unwrap();
+    assert!(summary.contains("Weeks: 1-2 (2 weeks)"), "{}", summary);
+    assert!(summary.contains("Sources: actual, projected"));
+    assert!(summary.contains("Already stored: none"));
+    // Rosters and players for each week and source, plus the uncached settings
+    assert!(
+        summary.contains("Estimated ESPN requests: 9"),
+        "{}",
+        summary
+    );
+    assert!(summary.contains("Auth cookies: no"));
+    let database = mock.cache_dir.path().join("espn-ffl").join("players.db");
+    assert!(summary.contains(&format!("Database: {}", database.display())));
+
+    let weekly_requests = mock
+        .server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|r| {
+            let query = r.url.query().unwrap_or_default();
+            r.url.path().ends_with("/players")
+                || query.contains("mRoster")
+                || query.contains("mSettings")
+        })
+        .count();
+    assert_eq!(weekly_requests, 9);
+
+    // Stored weeks are listed, and --yes skips the prompt
+    mock.cli()
+        .args([
+            "update-all-data",
+            "--season",
+            SEASON,
+            "--through-week",
+            "3",
+            "--yes",
+        ])
+        .env("ESPN_SWID", "{SWID}")
+        .env("ESPN_S2", "s2")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Already stored: 1-2 (2 of 3"))
+        .stdout(predicate::str::contains("Estimated ESPN requests: 12"))
+        .stdout(predicate::str::contains(
+            "Auth cookies: yes, from ESPN_SWID",
+        ))
+        .stdout(predicate::str::contains("Update cancelled").not());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_all_data_failure_exits_nonzero() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(1).await;
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+        .and(query_param("scoringPeriodId", "2"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock.server)
+        .await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("HTTP 500"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_requests_carry_user_agent_and_request_ids() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1", "--debug"])
+        .env("ESPN_FFL_USER_AGENT", "probe/{version}")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(requests.len() > 1);
+    let expected_agent = format!("probe/{}", env!("CARGO_PKG_VERSION"));
+    for request in &requests {
+        assert_eq!(request.headers["user-agent"], expected_agent.as_str());
+    }
+    let mut ids: Vec<&str> = requests
+        .iter()
+        .map(|request| request.headers["x-request-id"].to_str().unwrap())
+        .collect();
+
+    // --debug logs the ID of the player request it describes
+    let players = requests
+        .iter()
+        .find(|request| request.url.path().ends_with("/players"))
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains(&format!(
+            "Request-Id => {}",
+            players.headers["x-request-id"].to_str().unwrap()
+        )),
+        "{}",
+        stderr
+    );
+
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), requests.len());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_failed_request_reports_its_request_id() {
+    let mock = MockEspn::start().await;
+    Mock::given(method("GET"))
+        .and(path(mock.league_path()))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock.server)
+        .await;
+
+    let output = mock
+        .cli()
+        .args(["player-data", "--season", SEASON, "--week", "1"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+
+    // The error names the request that failed
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let requests = mock.server.received_requests().await.unwrap();
+    assert!(
+        requests
+            .iter()
+            .any(|request| stderr.contains(request.headers["x-request-id"].to_str().unwrap())),
+        "{}",
+        stderr
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_projection_analysis_after_two_seeded_weeks() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(3).await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .assert()
+        .success();
+
+    let output = mock
+        .cli()
+        .args([
+            "projection-analysis",
+            "--season",
+            SEASON,
+            "--week",
+            "3",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let rows = stdout_json(&output.stdout);
+    assert_keys_within(
+        &rows,
+        &[
+            "player_id",
+            "name",
+            "position",
+            "espn_projection",
+            "bias_adjustment",
+            "estimated_points",
+            "confidence",
+            "reasoning",
+        ],
+        &["team", "error_std", "vorp"],
+    );
+    // The QB has beaten an 18-point projection by 2 in both seeded weeks
+    let qb = rows
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|row| row["player_id"] == 100)
+        .unwrap();
+    assert!((qb["espn_projection"].as_f64().unwrap() - 18.0).abs() < 1e-9);
+    assert!(qb["bias_adjustment"].as_f64().unwrap() > 0.0);
+
+    // --top applies after the roster filter, so the rostered QB doesn't use up the limit
+    let output = mock
+        .cli()
+        .args([
+            "projection-analysis",
+            "--season",
+            SEASON,
+            "--week",
+            "3",
+            "--roster-status",
+            "fa",
+            "--top",
+            "1",
+            "--json",
+        ])
+        .output()
+        .unwrap();
+    let ids: Vec<_> = stdout_json(&output.stdout)
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|row| row["player_id"].clone())
+        .collect();
+    assert_eq!(ids, vec![200]);
+
+    // Sorted by value over replacement, each position's baseline is listed first
+    mock.cli()
+        .args([
+            "projection-analysis",
+            "--season",
+            SEASON,
+            "--week",
+            "3",
+            "--sort",
+            "vorp",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Replacement baselines:"))
+        .stdout(predicate::str::contains("VORP"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_projection_analysis_marks_players_whose_game_has_started() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(2).await;
+    // The QB plays Sunday afternoon; the free-agent RB already played Thursday night
+    let mut players = common::players_fixture(3);
+    players[0]["proTeamId"] = 2.into();
+    players[1]["proTeamId"] = 4.into();
+    mock.mock_players_week(3, players).await;
+    let game = |id: u64, date: u64| serde_json::json!({ "3": [{ "id": id, "date": date }] });
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}", TEST_SEASON)))
+        .and(query_param("view", "proTeamSchedules_wl"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "settings": {
+                "proTeams": [
+                    { "id": 2, "abbrev": "Buf", "proGamesByScoringPeriod": game(1, 1_758_474_000_000) },
+                    { "id": 4, "abbrev": "Cin", "proGamesByScoringPeriod": game(2, 1_758_240_900_000) }
+                ]
+            }
+        })))
+        .mount(&mock.server)
+        .await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .assert()
+        .success();
+
+    let analysis = |extra: &[&str]| {
+        let output = mock
+            .cli()
+            .args(["projection-analysis", "--season", SEASON, "--week", "3"])
+            .args(["--as-of", "2025-09-21T12:00Z"])
+            .args(extra)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output.stdout
+    };
+
+    let rows = stdout_json(&analysis(&["--json"]));
+    let locked = |id: i64| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["player_id"] == id)
+            .unwrap()["locked"]
+            .clone()
+    };
+    assert_eq!(locked(100), false);
+    assert_eq!(locked(200), true);
+
+    let text = String::from_utf8_lossy(&analysis(&["--roster-status", "fa"])).to_string();
+    assert!(
+        text.contains("Skipping 1 free agent(s) whose games have already started"),
+        "{}",
+        text
+    );
+    assert!(!text.contains("Test Runningback"), "{}", text);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_projection_analysis_exports_site_csv() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(2).await;
+    let mut players = common::players_fixture(3);
+    players[0]["proTeamId"] = 28.into();
+    players[0]["fullName"] = "Test Quarterback Jr.".into();
+    players[1]["proTeamId"] = 30.into();
+    mock.mock_players_week(3, players).await;
+    let game = |id: u64| serde_json::json!({ "3": [{ "id": id, "date": 1_758_474_000_000u64 }] });
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}", TEST_SEASON)))
+        .and(query_param("view", "proTeamSchedules_wl"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "settings": {
+                "proTeams": [
+                    { "id": 28, "proGamesByScoringPeriod": game(1) },
+                    { "id": 30, "proGamesByScoringPeriod": game(1) }
+                ]
+            }
+        })))
+        .mount(&mock.server)
+        .await;
+
+    mock.cli()
+        .args(["update-all-data", "--season", SEASON, "--through-week", "2"])
+        .assert()
+        .success();
+
+    let csv_path = mock.cache_dir.path().join("projections.csv");
+    mock.cli()
+        .args(["projection-analysis", "--season", SEASON, "--week", "3"])
+        .arg("--export-projections")
+        .arg(&csv_path)
+        .args(["--site", "fanduel", "--as-of", "2025-09-01T00:00Z"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("✓ Wrote 2 fanduel projections"));
+
+    let csv = std::fs::read_to_string(&csv_path).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "name,position,team,opponent,projection,floor,ceiling,confidence"
+    );
+    assert_eq!(lines.len(), 3, "{}", csv);
+    let qb = lines
+        .iter()
+        .find(|line| line.starts_with("Test Quarterback,"))
+        .unwrap_or_else(|| panic!("{}", csv));
+    // FanDuel drops the suffix and abbreviates Washington and Jacksonville its own way
+    assert!(qb.starts_with("Test Quarterback,QB,WAS,JAC,"), "{}", qb);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_projection_export_refuses_when_projections_are_missing() {
+    let mock = MockEspn::start().await;
+    mock.mock_season(2).await;
+    // ESPN hasn't projected the RB yet
+    let mut players = common::players_fixture(3);
+    players[1]["stats"]
+        .as_array_mut()
+        .unwrap()
+        .retain(|stats| stats["statSourceId"] != 1);
+    mock.mock_players_week(3, players).await;
+
+    let csv_path = mock.cache_dir.path().join("projections.csv");
+    mock.cli()
+        .args(["projection-analysis", "--season", SEASON, "--week", "3"])
+        .arg("--export-projections")
+        .arg(&csv_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "TooManyMissingProjections { missing: 1, total: 2, week: 3",
+        ));
+    assert!(!csv_path.exists());
+
+    mock.cli()
+        .args(["projection-analysis", "--season", SEASON, "--week", "3"])
+        .arg("--export-projections")
+        .arg(&csv_path)
+        .args(["--max-missing", "0.5"])
+        .assert()
+        .success();
+    assert!(csv_path.exists());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_matchup_forecast_sums_a_two_week_championship() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view(
+        "mSettings",
+        common::two_week_championship_settings_fixture(),
+    )
+    .await;
+    mock.mock_league_view("mMatchupScore", common::championship_league_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::championship_league_fixture())
+        .await;
+    for week in [16, 17] {
+        mock.mock_players_week(week, common::players_fixture(week))
+            .await;
+    }
+
+    // Either week of the championship forecasts the whole matchup period
+    for week in ["16", "17"] {
+        let output = mock
+            .cli()
+            .args([
+                "matchup-forecast",
+                "--season",
+                SEASON,
+                "--week",
+                week,
+                "--team-id",
+                "1",
+                "--json",
+            ])
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+
+        let forecast = stdout_json(&output.stdout);
+        assert_eq!(forecast["matchup_period"], 16);
+        assert_eq!(forecast["scoring_periods"], serde_json::json!([16, 17]));
+        assert_eq!(forecast["opponent"]["team_id"], 2);
+        // An 18-point QB and a 12-point RB projection, in each of the two weeks
+        assert!((forecast["team"]["projected_points"].as_f64().unwrap() - 36.0).abs() < 1e-9);
+        assert!((forecast["opponent"]["projected_points"].as_f64().unwrap() - 24.0).abs() < 1e-9);
+        assert!((forecast["projected_margin"].as_f64().unwrap() - 12.0).abs() < 1e-9);
+        let other_weeks = forecast["team"]["other_weeks"].as_array().unwrap();
+        assert_eq!(other_weeks.len(), 1);
+        assert_ne!(other_weeks[0]["week"].to_string(), week);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_matchup_alias_lists_both_lineups() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view(
+        "mSettings",
+        common::two_week_championship_settings_fixture(),
+    )
+    .await;
+    mock.mock_league_view("mMatchupScore", common::championship_league_fixture())
+        .await;
+    mock.mock_league_view("mRoster", common::championship_league_fixture())
+        .await;
+    for week in [16, 17] {
+        mock.mock_players_week(week, common::players_fixture(week))
+            .await;
+    }
+
+    let output = mock
+        .cli()
+        .args(["matchup", "--season", SEASON, "--week", "16"])
+        .args(["--team-id", "1", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // Each side's starters come with their own projection
+    let forecast = stdout_json(&output.stdout);
+    assert_eq!(forecast["team"]["team_id"], 1);
+    assert_eq!(forecast["opponent"]["team_id"], 2);
+    let projections = |side: &str| -> Vec<f64> {
+        forecast[side]["lineup"]["starters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|slot| slot["player"]["projection"].as_f64())
+            .collect()
+    };
+    assert_eq!(projections("team").iter().sum::<f64>(), 18.0);
+    assert_eq!(projections("opponent").iter().sum::<f64>(), 12.0);
+    assert!(forecast["win_probability"].as_f64().unwrap() > 0.5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_optimal_lineup_flags_a_ruled_out_best_option() {
+    let mock = MockEspn::start().await;
+    // Team Alpha also has the RB, who is out this week
+    let mut players = common::players_fixture(1);
+    players[1]["injuryStatus"] = "OUT".into();
+    players[1]["injured"] = true.into();
+    mock.mock_players(players).await;
+    mock.mock_league_view(
+        "mRoster",
+        serde_json::json!({
+            "teams": [{
+                "id": 1,
+                "name": "Team Alpha",
+                "roster": { "entries": [
+                    { "playerId": 100, "lineupSlotId": 0 },
+                    { "playerId": 200, "lineupSlotId": 20 }
+                ] }
+            }]
+        }),
+    )
+    .await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args(["optimal-lineup", "--season", SEASON, "--week", "1"])
+        .args(["--team", "alpha", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let report = stdout_json(&output.stdout);
+    assert_eq!(report["team_id"], 1);
+    // Only the QB's 18-point projection starts
+    assert!((report["projected_points"].as_f64().unwrap() - 18.0).abs() < 1e-9);
+    let starters = report["lineup"]["starters"].as_array().unwrap();
+    assert_eq!(starters[0]["slot"], "QB");
+    assert_eq!(starters[0]["player"]["player_id"], 100);
+    assert_eq!(report["lineup"]["bench"][0]["player_id"], 200);
+    let warnings: Vec<&str> = report["warnings"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|w| w.as_str().unwrap())
+        .collect();
+    assert!(warnings.contains(
+        &"RB: Test Runningback (Out) would be the best option at 12.0 pts but can't start"
+    ));
+    // Flagged once, though the RB would fit the second RB slot and FLEX too
+    assert_eq!(
+        warnings
+            .iter()
+            .filter(|w| w.contains("Runningback"))
+            .count(),
+        1
+    );
+
+    mock.cli()
+        .args(["optimal-lineup", "--season", SEASON, "--week", "1"])
+        .args(["--team-id", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Optimal Lineup - Team Alpha"))
+        .stdout(predicate::str::contains("Projected total: 18.0"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_compare_recommends_the_higher_estimate() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let output = mock
+        .cli()
+        .args(["compare", "--season", SEASON, "--week", "1"])
+        .args(["-n", "runningback", "-n", "Test Quarterback", "--json"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let comparison = stdout_json(&output.stdout);
+    let players = comparison["players"].as_array().unwrap();
+    // In the order named
+    assert_eq!(players[0]["player_id"], 200);
+    assert_eq!(players[1]["player_id"], 100);
+    assert!((players[1]["espn_projection"].as_f64().unwrap() - 18.0).abs() < 1e-9);
+    assert_eq!(comparison["recommended_player_id"], 100);
+
+    mock.cli()
+        .args(["compare", "--season", SEASON, "--week", "1"])
+        .args(["-n", "runningback", "-n", "quarterback"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Start Test Quarterback"));
+
+    // "Test" is part of both names
+    mock.cli()
+        .args(["compare", "--season", SEASON, "--week", "1"])
+        .args(["-n", "Test", "-n", "runningback"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("AmbiguousPlayerName"))
+        .stderr(predicate::str::contains("= 100"))
+        .stderr(predicate::str::contains("= 200"));
+
+    mock.cli()
+        .args([
+            "compare",
+            "--season",
+            SEASON,
+            "--week",
+            "1",
+            "-n",
+            "runningback",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("TooFewPlayers"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_auth_status_uses_credentials_file() {
+    let mock = MockEspn::start().await;
+    Mock::given(method("GET"))
+        .and(path(mock.league_path()))
+        .and(header("cookie", "SWID={ABC}; espn_s2=s2-value"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(common::settings_fixture()))
+        .mount(&mock.server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path(mock.league_path()))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock.server)
+        .await;
+
+    mock.cli()
+        .args(["auth", "status", "--season", SEASON])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Credentials: none"))
+        .stdout(predicate::str::contains("✗ ESPN refused league"));
+
+    let path = mock.cache_dir.path().join("espn-ffl/credentials");
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, "SWID={ABC}\nespn_s2=s2-value\n").unwrap();
+    mock.cli()
+        .args(["auth", "status", "--season", SEASON])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(path.display().to_string()))
+        .stdout(predicate::str::contains("✓ ESPN returned league"));
+
+    // The environment variables win over the file
+    mock.cli()
+        .args(["auth", "status", "--season", SEASON])
+        .env("ESPN_SWID", "{OTHER}")
+        .env("ESPN_S2", "other")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("environment variables"))
+        .stdout(predicate::str::contains("✗ ESPN refused league"));
+}
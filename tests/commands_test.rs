@@ -107,6 +107,9 @@ fn test_player_points_serialization() {
         is_rostered: Some(true),
         team_id: Some(1),
         team_name: Some("Test Team".to_string()),
+        played: None,
+        opponent_team: None,
+        home_game: None,
     };
 
     let json = serde_json::to_string(&player_points).unwrap();
@@ -118,7 +121,7 @@ fn test_player_points_serialization() {
 
 #[test]
 fn test_player_points_ordering() {
-    let mut players = vec![
+    let mut players = [
         PlayerPoints {
             id: PlayerId::new(1),
             name: "Player 1".to_string(),
@@ -132,6 +135,9 @@ fn test_player_points_ordering() {
             is_rostered: Some(true),
             team_id: Some(1),
             team_name: Some("Team A".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         },
         PlayerPoints {
             id: PlayerId::new(2),
@@ -146,6 +152,9 @@ fn test_player_points_ordering() {
             is_rostered: Some(true),
             team_id: Some(2),
             team_name: Some("Team B".to_string()),
+            played: None,
+            opponent_team: None,
+            home_game: None,
         },
         PlayerPoints {
             id: PlayerId::new(3),
@@ -160,6 +169,9 @@ fn test_player_points_ordering() {
             is_rostered: Some(false),
             team_id: None,
             team_name: None,
+            played: None,
+            opponent_team: None,
+            home_game: None,
         },
     ];
 
@@ -214,6 +226,14 @@ fn test_performance_estimate_creation() {
         estimated_points: 18.5,
         confidence: 0.75,
         reasoning: "Based on historical data".to_string(),
+        error_std: Some(2.5),
+        dnp_weeks: None,
+        blend: None,
+        history: None,
+        percent_started: None,
+        tier: None,
+        locked: None,
+        vorp: None,
     };
 
     assert_eq!(estimate.player_id, PlayerId::new(12345));
@@ -263,6 +283,7 @@ fn test_player_weekly_stats_creation() {
         player_id: PlayerId::new(12345),
         season: Season::new(2023),
         week: Week::new(1),
+        league_id: Some(LeagueId::new(54321)),
         projected_points: Some(20.0),
         actual_points: Some(18.5),
         active: Some(true),
@@ -271,6 +292,11 @@ fn test_player_weekly_stats_creation() {
         is_rostered: Some(true),
         fantasy_team_id: Some(1),
         fantasy_team_name: Some("Test Team".to_string()),
+        played: None,
+        percent_owned: None,
+        percent_started: None,
+        opponent_team: None,
+        home_game: None,
         created_at: 1234567890,
         updated_at: 1234567890,
     };
@@ -330,6 +356,9 @@ fn test_cached_data_includes_injury_and_roster_status() {
         is_rostered: Some(true), // rostered
         team_id: Some(123),      // team_id
         team_name: Some("My Team".to_string()),
+        played: None,
+        opponent_team: None,
+        home_game: None,
     });
 
     // These assertions would fail if cached data constructor ignores status fields
@@ -385,6 +414,10 @@ fn test_cached_vs_fresh_data_status_consistency() {
             active: Some(true),
             injured: Some(false),
             injury_status: Some(InjuryStatus::Active),
+            pro_team_id: Some(2),
+            eligible_slots: vec![],
+            ownership: None,
+            news: None,
         },
         "QB".to_string(),
         25.0,
@@ -406,6 +439,9 @@ fn test_cached_vs_fresh_data_status_consistency() {
         is_rostered: Some(true),                   // rostered (example)
         team_id: Some(42),                         // team_id (example)
         team_name: Some("Test Team".to_string()),  // team_name (example)
+        played: None,
+        opponent_team: None,
+        home_game: None,
     });
 
     // Status fields should match between fresh and cached data
@@ -428,6 +464,198 @@ fn test_cached_vs_fresh_data_status_consistency() {
     assert_eq!(fresh_data.projected, cached_data.projected);
 }
 
+#[test]
+fn test_projection_analysis_params_rejects_negative_bias_strength() {
+    use espn_ffl::commands::projection_analysis::ProjectionAnalysisParams;
+
+    let result = ProjectionAnalysisParams::new(Season::new(2025), Week::new(3), -0.5);
+    assert!(matches!(
+        result,
+        Err(EspnError::InvalidBiasStrength { value }) if value == -0.5
+    ));
+    assert!(ProjectionAnalysisParams::new(Season::new(2025), Week::new(3), f64::NAN).is_err());
+
+    let params = ProjectionAnalysisParams::new(Season::new(2025), Week::new(3), 0.0).unwrap();
+    assert_eq!(params.bias_strength, 0.0);
+}
+
+#[test]
+fn test_bias_strength_warning_above_soft_cap() {
+    use espn_ffl::commands::projection_analysis::{
+        bias_strength_warning, DEFAULT_BIAS_STRENGTH_SOFT_CAP,
+    };
+
+    assert!(bias_strength_warning(1.0, DEFAULT_BIAS_STRENGTH_SOFT_CAP).is_none());
+    assert!(bias_strength_warning(3.0, DEFAULT_BIAS_STRENGTH_SOFT_CAP).is_none());
+
+    let warning = bias_strength_warning(10.0, DEFAULT_BIAS_STRENGTH_SOFT_CAP).unwrap();
+    assert!(warning.contains("bias strength 10 is above 3"));
+    assert!(bias_strength_warning(2.0, 1.5).is_some());
+}
+
+#[test]
+fn test_combine_projected_actual_and_sort() {
+    use espn_ffl::{
+        cli::types::filters::ComparisonSort,
+        commands::player_data::{combine_projected_actual, sort_comparisons},
+    };
+
+    let points = |id: i64, points: f64, projected: bool| PlayerPoints {
+        id: PlayerId::new(id),
+        name: format!("Player {}", id),
+        position: "WR".to_string(),
+        points,
+        week: Week::new(3),
+        projected,
+        active: Some(true),
+        injured: Some(false),
+        injury_status: None,
+        is_rostered: Some(!projected),
+        team_id: None,
+        team_name: None,
+        played: None,
+        opponent_team: None,
+        home_game: None,
+    };
+
+    let actual = vec![points(1, 10.0, false), points(2, 25.0, false)];
+    let projected = vec![
+        points(1, 15.0, true),
+        points(2, 18.0, true),
+        points(3, 7.0, true),
+    ];
+    let mut rows = combine_projected_actual(actual, projected);
+    assert_eq!(rows.len(), 3);
+
+    let player1 = rows.iter().find(|r| r.id == PlayerId::new(1)).unwrap();
+    assert_eq!(player1.projected_points, Some(15.0));
+    assert_eq!(player1.actual_points, Some(10.0));
+    assert_eq!(player1.delta, Some(-5.0));
+    // Status details come from the actual row
+    assert_eq!(player1.is_rostered, Some(true));
+
+    let player3 = rows.iter().find(|r| r.id == PlayerId::new(3)).unwrap();
+    assert_eq!(player3.actual_points, None);
+    assert_eq!(player3.delta, None);
+
+    let ids = |rows: &[espn_ffl::commands::player_data::PointsComparison]| {
+        rows.iter().map(|r| r.id.as_i64()).collect::<Vec<_>>()
+    };
+
+    // Points: actual when known, otherwise projected
+    sort_comparisons(&mut rows, ComparisonSort::Points, false);
+    assert_eq!(ids(&rows), vec![2, 1, 3]);
+
+    // Delta: biggest outperformance first, unknown deltas last
+    sort_comparisons(&mut rows, ComparisonSort::Delta, false);
+    assert_eq!(ids(&rows), vec![2, 1, 3]);
+    rows.swap(0, 1);
+    sort_comparisons(&mut rows, ComparisonSort::Delta, false);
+    assert_eq!(rows[0].delta, Some(7.0));
+    assert_eq!(rows[2].delta, None);
+    // Reversed, the smallest delta leads and the unknown one stays last
+    sort_comparisons(&mut rows, ComparisonSort::Delta, true);
+    assert_eq!(ids(&rows), vec![1, 2, 3]);
+}
+
+#[test]
+fn test_sort_player_rows_keeps_points_order_for_ties() {
+    use espn_ffl::{
+        cli::types::filters::ComparisonSort,
+        commands::player_data::{sort_player_rows, SortFields},
+    };
+
+    // (name, position, team, points), already in points order
+    let rows = vec![
+        ("bravo", "WR", Some("Zeta"), 30.0),
+        ("Alpha", "QB", None, 25.0),
+        ("charlie", "WR", Some("alpha"), 20.0),
+        ("Delta", "QB", Some("Zeta"), 15.0),
+        ("echo", "D/ST", None, 10.0),
+    ];
+    let sorted = |sort: ComparisonSort, reverse: bool| {
+        let mut rows = rows.clone();
+        sort_player_rows(&mut rows, sort, reverse, |row| SortFields {
+            points: Some(row.3),
+            delta: None,
+            name: row.0,
+            position: row.1,
+            team_name: row.2,
+        });
+        rows.iter().map(|row| row.0).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        sorted(ComparisonSort::Name, false),
+        vec!["Alpha", "bravo", "charlie", "Delta", "echo"]
+    );
+    assert_eq!(
+        sorted(ComparisonSort::Position, false),
+        vec!["Alpha", "Delta", "bravo", "charlie", "echo"]
+    );
+    // Reversed positions keep each position's players highest first
+    assert_eq!(
+        sorted(ComparisonSort::Position, true),
+        vec!["echo", "bravo", "charlie", "Alpha", "Delta"]
+    );
+    // Free agents last either way
+    assert_eq!(
+        sorted(ComparisonSort::Team, false),
+        vec!["charlie", "bravo", "Delta", "Alpha", "echo"]
+    );
+    assert_eq!(
+        sorted(ComparisonSort::Team, true),
+        vec!["bravo", "Delta", "charlie", "Alpha", "echo"]
+    );
+    assert_eq!(
+        sorted(ComparisonSort::Points, true),
+        vec!["echo", "Delta", "charlie", "Alpha", "bravo"]
+    );
+}
+
+#[test]
+fn test_group_by_team_puts_free_agents_last_and_limits_each_team() {
+    use espn_ffl::commands::player_data::group_by_team;
+
+    // (id, team), already in points order
+    let rows: Vec<(i64, Option<String>)> = [
+        (1, Some("zebras")),
+        (2, None),
+        (3, Some("Alpha")),
+        (4, Some("zebras")),
+        (5, None),
+        (6, Some("Alpha")),
+        (7, Some("zebras")),
+    ]
+    .into_iter()
+    .map(|(id, team)| (id, team.map(str::to_string)))
+    .collect();
+    fn team(row: &(i64, Option<String>)) -> (Option<u32>, Option<&str>) {
+        (row.1.as_ref().map(|_| 1), row.1.as_deref())
+    }
+
+    let groups = group_by_team(rows.clone(), team, None);
+    let sections: Vec<(&str, Vec<i64>)> = groups
+        .iter()
+        .map(|g| (g.label(), g.players.iter().map(|p| p.0).collect()))
+        .collect();
+    assert_eq!(
+        sections,
+        vec![
+            ("Alpha", vec![3, 6]),
+            ("zebras", vec![1, 4, 7]),
+            ("Free Agents", vec![2, 5]),
+        ]
+    );
+    assert_eq!(groups[2].team_id, None);
+
+    // --top keeps the first N of every team rather than N overall
+    let groups = group_by_team(rows, team, Some(2));
+    let sizes: Vec<usize> = groups.iter().map(|g| g.players.len()).collect();
+    assert_eq!(sizes, vec![2, 2, 2]);
+    assert_eq!(groups[1].players[1].0, 4);
+}
+
 #[cfg(test)]
 mod projection_analysis_filtering_tests {
     use super::*;
@@ -456,6 +684,9 @@ mod projection_analysis_filtering_tests {
             is_rostered,
             team_id: None,
             team_name: None,
+            played: None,
+            opponent_team: None,
+            home_game: None,
         }
     }
 
@@ -678,3 +909,233 @@ mod projection_analysis_filtering_tests {
         ));
     }
 }
+
+#[test]
+fn test_filter_keeps_team_defenses_and_names_them() {
+    use espn_ffl::{
+        cli::types::position::FlexEligibility,
+        commands::player_filters::filter_and_convert_players, espn::types::Player,
+    };
+
+    let players: Vec<Player> = serde_json::from_value(serde_json::json!([
+        // A team defense ESPN sent without a name
+        { "id": -16002, "defaultPositionId": 16 },
+        // Individual defensive players and other negative IDs are dropped
+        { "id": 4000001, "fullName": "Some Linebacker", "defaultPositionId": 11 },
+        { "id": -5, "fullName": "Not A Defense", "defaultPositionId": 1 },
+        { "id": 3918298, "fullName": "Josh Allen", "defaultPositionId": 1, "proTeamId": 2 }
+    ]))
+    .unwrap();
+
+    let mut filtered = filter_and_convert_players(
+        players,
+        None,
+        Some(vec![Position::DEF]),
+        None,
+        &FlexEligibility::default(),
+    );
+    assert_eq!(filtered.len(), 1);
+    let defense = filtered.remove(0);
+    // The negative ID is kept as-is
+    assert_eq!(defense.player_id, PlayerId::new(-16002));
+
+    let stored = espn_ffl::storage::Player::from_espn(&defense.original_player);
+    assert_eq!(stored.name, "Buffalo Bills D/ST");
+    assert_eq!(stored.position, "D/ST");
+    assert_eq!(stored.team.as_deref(), Some("BUF"));
+}
+
+#[test]
+fn test_check_season() {
+    use espn_ffl::{commands::league_data::check_season, error::EspnError};
+
+    let league_id = LeagueId::new(12345);
+    let history = [2021, 2022, 2023, 2024, 2025];
+
+    assert!(check_season(league_id, Season::new(2025), 2025, Some(&history)).is_ok());
+    assert!(check_season(league_id, Season::new(2022), 2025, Some(&history)).is_ok());
+    // Without league history only the upper bound is enforced
+    assert!(check_season(league_id, Season::new(2010), 2025, None).is_ok());
+
+    assert!(matches!(
+        check_season(league_id, Season::new(2026), 2025, Some(&history)),
+        Err(EspnError::InvalidSeason {
+            requested: 2026,
+            latest_available: 2025
+        })
+    ));
+    assert!(matches!(
+        check_season(league_id, Season::new(2020), 2025, Some(&history)),
+        Err(EspnError::LeagueSeasonNotFound {
+            league_id: 12345,
+            season: 2020,
+            first_season: 2021
+        })
+    ));
+}
+
+#[test]
+fn test_resolve_player_for_notes() {
+    use espn_ffl::commands::notes::resolve_player;
+
+    let mut db = PlayerDatabase::new_in_memory().unwrap();
+    for (id, name, position, team) in [
+        (1, "Josh Allen", "QB", Some("BUF")),
+        (2, "Josh Allen", "LB", Some("JAX")),
+        (3, "D.J. Moore", "WR", Some("CHI")),
+    ] {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: team.map(str::to_string),
+        })
+        .unwrap();
+    }
+
+    // Exact names match regardless of case and punctuation
+    assert_eq!(resolve_player(&db, "dj moore").unwrap(), PlayerId::new(3));
+    // IDs always resolve
+    assert_eq!(resolve_player(&db, "2").unwrap(), PlayerId::new(2));
+    // Substrings are not exact names
+    assert!(matches!(
+        resolve_player(&db, "Moore"),
+        Err(EspnError::PlayerNotFound { .. })
+    ));
+
+    match resolve_player(&db, "Josh Allen") {
+        Err(EspnError::AmbiguousPlayerName { candidates, .. }) => {
+            assert_eq!(
+                candidates,
+                vec!["Josh Allen (QB, BUF) = 1", "Josh Allen (LB, JAX) = 2"]
+            );
+        }
+        other => panic!("expected AmbiguousPlayerName, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_recommend_player_breaks_ties_by_confidence() {
+    use espn_ffl::commands::compare::{recommend_player, PlayerComparison};
+
+    let player = |id: i64, estimated_points: f64, confidence: f64| PlayerComparison {
+        player_id: PlayerId::new(id),
+        name: format!("Player {}", id),
+        position: "RB".to_string(),
+        team: None,
+        espn_projection: Some(estimated_points),
+        estimated_points,
+        confidence,
+        recent_average: None,
+        injury_status: None,
+    };
+
+    assert_eq!(
+        recommend_player(&[player(1, 12.0, 0.9), player(2, 14.5, 0.4)]),
+        Some(PlayerId::new(2))
+    );
+    // Equal estimates go to the more confident one, then to the first named
+    assert_eq!(
+        recommend_player(&[player(1, 12.0, 0.5), player(2, 12.0, 0.7)]),
+        Some(PlayerId::new(2))
+    );
+    assert_eq!(
+        recommend_player(&[player(1, 12.0, 0.5), player(2, 12.0, 0.5)]),
+        Some(PlayerId::new(1))
+    );
+    assert_eq!(recommend_player(&[]), None);
+}
+
+#[test]
+fn test_rows_to_json_attaches_notes() {
+    use espn_ffl::commands::notes::rows_to_json;
+    use std::collections::HashMap;
+
+    let row = ProjectionAnalysis {
+        name: "Josh Allen".to_string(),
+        position: "QB".to_string(),
+        team: Some("BUF".to_string()),
+        avg_error: 1.0,
+        games_count: 3,
+    };
+    let note = PlayerNote {
+        note_id: 7,
+        player_id: PlayerId::new(1),
+        player_name: Some("Josh Allen".to_string()),
+        note: "start every week".to_string(),
+        created_at: 0,
+    };
+    let notes = HashMap::from([(PlayerId::new(1), vec![note])]);
+
+    let plain: serde_json::Value = serde_json::from_str(
+        &rows_to_json(std::slice::from_ref(&row), |_| PlayerId::new(1), None).unwrap(),
+    )
+    .unwrap();
+    assert!(plain[0].get("notes").is_none());
+
+    let with_notes: serde_json::Value = serde_json::from_str(
+        &rows_to_json(&[row.clone(), row], |_| PlayerId::new(1), Some(&notes)).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(with_notes[0]["name"], "Josh Allen");
+    assert_eq!(with_notes[0]["notes"][0]["note"], "start every week");
+}
+
+#[test]
+fn test_projection_analysis_blend_weight_validation() {
+    use espn_ffl::commands::projection_analysis::ProjectionAnalysisParams;
+
+    let params = ProjectionAnalysisParams::new(Season::new(2023), Week::new(5), 1.0).unwrap();
+    assert_eq!(params.blend_recent, None);
+
+    let params = params.with_blend_recent(0.3).unwrap();
+    assert_eq!(params.blend_recent, Some(0.3));
+
+    for weight in [-0.1, 1.5, f64::NAN] {
+        let params = ProjectionAnalysisParams::new(Season::new(2023), Week::new(5), 1.0).unwrap();
+        assert!(matches!(
+            params.with_blend_recent(weight),
+            Err(EspnError::InvalidBlendWeight { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_projection_analysis_bias_decay_validation() {
+    use espn_ffl::commands::projection_analysis::ProjectionAnalysisParams;
+
+    let params = ProjectionAnalysisParams::new(Season::new(2023), Week::new(5), 1.0).unwrap();
+    assert_eq!(params.bias_decay, 1.0);
+    assert_eq!(params.with_bias_decay(0.8).unwrap().bias_decay, 0.8);
+
+    for decay in [0.0, -0.5, 1.2, f64::NAN] {
+        let params = ProjectionAnalysisParams::new(Season::new(2023), Week::new(5), 1.0).unwrap();
+        assert!(matches!(
+            params.with_bias_decay(decay),
+            Err(EspnError::InvalidBiasDecay { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_category_leaders_rejects_unknown_category() {
+    use espn_ffl::commands::category_leaders::{handle_category_leaders, CategoryLeadersParams};
+
+    let result = handle_category_leaders(CategoryLeadersParams {
+        category: "hang-time".to_string(),
+        league_id: Some(LeagueId::new(12345)),
+        season: Season::new(2023),
+        week: Some(Week::new(1)),
+        positions: None,
+        roster_status: None,
+        top: 10,
+        as_json: true,
+    });
+    match result {
+        Err(EspnError::UnknownStatCategory { name, known }) => {
+            assert_eq!(name, "hang-time");
+            assert!(known.iter().any(|k| k == "passing-yards"));
+        }
+        other => panic!("expected UnknownStatCategory, got {:?}", other),
+    }
+}
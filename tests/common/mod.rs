@@ -0,0 +1,323 @@
+//! Shared helpers for handler-level and CLI tests that run against a wiremock ESPN API.
+//!
+//! Handlers resolve the API base URL and cache directory from the environment,
+//! so each test takes a process-wide lock while it owns those variables. CLI tests
+//! run the built binary with the same variables passed explicitly.
+
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+use tokio::sync::{Mutex, MutexGuard};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, MockServer, ResponseTemplate,
+};
+
+pub const TEST_LEAGUE_ID: u32 = 12345;
+pub const TEST_SEASON: u16 = 2025;
+
+static ENV_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Mock ESPN server plus an isolated cache directory for a single test
+pub struct MockEspn {
+    pub server: MockServer,
+    pub cache_dir: TempDir,
+    _env_guard: MutexGuard<'static, ()>,
+}
+
+impl MockEspn {
+    /// Start a mock server and point the CLI's base URL and cache dir at it
+    pub async fn start() -> Self {
+        let env_guard = ENV_LOCK.lock().await;
+        let server = MockServer::start().await;
+        let cache_dir = TempDir::new().unwrap();
+
+        std::env::set_var("ESPN_FFL_BASE_URL", server.uri());
+        std::env::set_var("XDG_CACHE_HOME", cache_dir.path());
+        std::env::set_var("XDG_CONFIG_HOME", cache_dir.path());
+        std::env::remove_var("ESPN_SWID");
+        std::env::remove_var("ESPN_S2");
+        std::env::remove_var("ESPN_FFL_NO_CACHE_WRITE");
+
+        Self {
+            server,
+            cache_dir,
+            _env_guard: env_guard,
+        }
+    }
+
+    /// Path of the league endpoint used by the settings and roster views
+    pub fn league_path(&self) -> String {
+        format!(
+            "/seasons/{}/segments/0/leagues/{}",
+            TEST_SEASON, TEST_LEAGUE_ID
+        )
+    }
+
+    /// Mount a league endpoint response for a specific `view`
+    pub async fn mock_league_view(&self, view: &str, body: Value) {
+        Mock::given(method("GET"))
+            .and(path(self.league_path()))
+            .and(query_param("view", view))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mount the players endpoint response
+    pub async fn mock_players(&self, body: Value) {
+        Mock::given(method("GET"))
+            .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mount the players endpoint response for a single scoring period
+    pub async fn mock_players_week(&self, week: u16, body: Value) {
+        Mock::given(method("GET"))
+            .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+            .and(query_param("scoringPeriodId", week.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mount metadata, status, settings and roster, plus the default players for
+    /// weeks `1..=through_week`
+    pub async fn mock_season(&self, through_week: u16) {
+        self.mock_game_metadata(TEST_SEASON).await;
+        self.mock_league_status(&[]).await;
+        self.mock_league_view("mSettings", settings_fixture()).await;
+        self.mock_league_view("mRoster", roster_fixture()).await;
+        for week in 1..=through_week {
+            self.mock_players_week(week, players_fixture(week)).await;
+        }
+    }
+
+    /// Number of requests the players endpoint has received
+    pub async fn player_fetches(&self) -> usize {
+        let players_path = format!("/seasons/{}/players", TEST_SEASON);
+        self.server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .filter(|r| r.url.path() == players_path)
+            .count()
+    }
+
+    /// The `espn-ffl` binary pointed at this server, cache directory and test league
+    pub fn cli(&self) -> Command {
+        let mut cmd = Command::cargo_bin("espn-ffl").unwrap();
+        cmd.env("ESPN_FFL_BASE_URL", self.server.uri())
+            .env("XDG_CACHE_HOME", self.cache_dir.path())
+            .env("XDG_CONFIG_HOME", self.cache_dir.path())
+            .env("ESPN_FFL_LEAGUE_ID", TEST_LEAGUE_ID.to_string())
+            .env_remove("ESPN_SWID")
+            .env_remove("ESPN_S2")
+            .env_remove("ESPN_FFL_NO_CACHE_WRITE")
+            .env_remove("ESPN_FFL_DB_PATH")
+            .env_remove("ESPN_FFL_SCORING_OVERRIDES")
+            .env_remove("ESPN_FFL_ROSTER_FRESHNESS_MINUTES")
+            .env_remove("ESPN_FFL_USER_AGENT");
+        cmd
+    }
+
+    /// Mount the base FFL endpoint reporting `current_season` as the latest season
+    pub async fn mock_game_metadata(&self, current_season: u16) {
+        Mock::given(method("GET"))
+            .and(path("/"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "abbrev": "FFL",
+                "currentSeasonId": current_season,
+            })))
+            .mount(&self.server)
+            .await;
+    }
+
+    /// Mount the mStatus view for the test season with the league's earlier seasons
+    pub async fn mock_league_status(&self, previous_seasons: &[u16]) {
+        self.mock_league_view(
+            "mStatus",
+            json!({
+                "seasonId": TEST_SEASON,
+                "scoringPeriodId": 5,
+                "status": { "previousSeasons": previous_seasons },
+            }),
+        )
+        .await;
+    }
+
+    /// Mount settings, roster and players with the default fixtures
+    pub async fn mock_defaults(&self) {
+        self.mock_league_view("mSettings", settings_fixture()).await;
+        self.mock_league_view("mRoster", roster_fixture()).await;
+        self.mock_players(players_fixture(1)).await;
+    }
+}
+
+/// League settings: 0.04/passing yard, 4/passing TD, 0.1/rushing yard
+pub fn settings_fixture() -> Value {
+    json!({
+        "settings": {
+            "scoringSettings": {
+                "scoringItems": [
+                    { "statId": 3, "points": 0.04 },
+                    { "statId": 4, "points": 4.0 },
+                    { "statId": 24, "points": 0.1 }
+                ]
+            },
+            "rosterSettings": {
+                "lineupSlotCounts": { "0": 1, "2": 2, "4": 2, "6": 1, "16": 1, "17": 1, "20": 6, "23": 1 },
+                "positionLimits": {}
+            }
+        }
+    })
+}
+
+/// The default settings as sent by a league whose scoring items carry extra fields
+/// (`leagueRanking`, `isReverseItem`) and non-numeric `pointsOverrides` keys, plus
+/// a reverse item costing 2 points per interception (stat 20)
+pub fn odd_scoring_settings_fixture() -> Value {
+    let mut settings = settings_fixture();
+    let items = settings["settings"]["scoringSettings"]["scoringItems"]
+        .as_array_mut()
+        .unwrap();
+    items[0]["leagueRanking"] = json!(0.0);
+    items[0]["isReverseItem"] = json!(false);
+    items[1]["pointsOverrides"] = json!({ "0": 4.0, "ALL": 6.0, "-": 5.0 });
+    items.push(json!({ "statId": 20, "points": 2.0, "isReverseItem": true }));
+    settings
+}
+
+/// The default settings plus one OP (superflex) lineup slot
+pub fn superflex_settings_fixture() -> Value {
+    let mut settings = settings_fixture();
+    settings["settings"]["rosterSettings"]["lineupSlotCounts"]["7"] = json!(1);
+    settings
+}
+
+/// One team rostering the QB (player 100); the RB (player 200) is a free agent
+pub fn roster_fixture() -> Value {
+    json!({
+        "teams": [
+            {
+                "id": 1,
+                "name": "Team Alpha",
+                "abbrev": "ALP",
+                "roster": {
+                    "entries": [
+                        { "playerId": 100, "lineupSlotId": 0, "injuryStatus": "ACTIVE" }
+                    ]
+                }
+            }
+        ]
+    })
+}
+
+/// A QB worth 20 pts and an RB worth 10 pts (actual), with projections of 18/12
+pub fn players_fixture(week: u16) -> Value {
+    json!([
+        {
+            "id": 100,
+            "fullName": "Test Quarterback",
+            "defaultPositionId": 1,
+            "active": true,
+            "injured": false,
+            "injuryStatus": "ACTIVE",
+            "stats": [
+                {
+                    "seasonId": TEST_SEASON,
+                    "scoringPeriodId": week,
+                    "statSourceId": 0,
+                    "statSplitTypeId": 1,
+                    "stats": { "3": 300.0, "4": 2.0 }
+                },
+                {
+                    "seasonId": TEST_SEASON,
+                    "scoringPeriodId": week,
+                    "statSourceId": 1,
+                    "statSplitTypeId": 1,
+                    "stats": { "3": 250.0, "4": 2.0 }
+                }
+            ]
+        },
+        {
+            "id": 200,
+            "fullName": "Test Runningback",
+            "defaultPositionId": 2,
+            "active": true,
+            "injured": false,
+            "injuryStatus": "ACTIVE",
+            "stats": [
+                {
+                    "seasonId": TEST_SEASON,
+                    "scoringPeriodId": week,
+                    "statSourceId": 0,
+                    "statSplitTypeId": 1,
+                    "stats": { "24": 100.0 }
+                },
+                {
+                    "seasonId": TEST_SEASON,
+                    "scoringPeriodId": week,
+                    "statSourceId": 1,
+                    "statSplitTypeId": 1,
+                    "stats": { "24": 120.0 }
+                }
+            ]
+        }
+    ])
+}
+
+/// The default settings for a league whose championship (matchup period 16) is
+/// played over weeks 16 and 17
+pub fn two_week_championship_settings_fixture() -> Value {
+    let mut settings = settings_fixture();
+    settings["settings"]["scheduleSettings"] = json!({
+        "matchupPeriodCount": 14,
+        "playoffTeamCount": 2,
+        "matchupPeriods": { "14": [14], "15": [15], "16": [16, 17] }
+    });
+    settings
+}
+
+/// Team Alpha (QB, player 100) against Team Bravo (RB, player 200) in the
+/// two-week championship, plus the schedule's team names
+pub fn championship_league_fixture() -> Value {
+    json!({
+        "teams": [
+            {
+                "id": 1,
+                "name": "Team Alpha",
+                "abbrev": "ALP",
+                "roster": {
+                    "entries": [
+                        { "playerId": 100, "lineupSlotId": 0, "injuryStatus": "ACTIVE" }
+                    ]
+                }
+            },
+            {
+                "id": 2,
+                "name": "Team Bravo",
+                "abbrev": "BRV",
+                "roster": {
+                    "entries": [
+                        { "playerId": 200, "lineupSlotId": 2, "injuryStatus": "ACTIVE" }
+                    ]
+                }
+            }
+        ],
+        "schedule": [
+            {
+                "id": 1,
+                "matchupPeriodId": 16,
+                "home": { "teamId": 1 },
+                "away": { "teamId": 2 },
+                "winner": "UNDECIDED"
+            }
+        ]
+    })
+}
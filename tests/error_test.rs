@@ -90,6 +90,39 @@ mod espn_error_tests {
         assert!(error_string.contains("Failed to write cache"));
     }
 
+    #[test]
+    fn test_feature_disabled_error() {
+        let error = EspnError::FeatureDisabled {
+            feature: "parquet".to_string(),
+        };
+        assert_eq!(
+            error.to_string(),
+            "parquet support is not enabled; rebuild with `--features parquet`"
+        );
+    }
+
+    #[test]
+    fn test_season_errors() {
+        let error = EspnError::InvalidSeason {
+            requested: 2026,
+            latest_available: 2025,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Season 2026 is not available yet; the latest ESPN season is 2025"
+        );
+
+        let error = EspnError::LeagueSeasonNotFound {
+            league_id: 12345,
+            season: 2019,
+            first_season: 2022,
+        };
+        assert_eq!(
+            error.to_string(),
+            "League 12345 has no season 2019; its first season is 2022"
+        );
+    }
+
     #[test]
     fn test_no_data_error() {
         let error = EspnError::NoData;
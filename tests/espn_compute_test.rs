@@ -14,16 +14,22 @@ mod scoring_tests {
                 stat_id: 53,  // Passing yards
                 points: 0.04, // 1 point per 25 yards
                 points_overrides: BTreeMap::new(),
+                is_reverse_item: false,
+                skipped_override_keys: Vec::new(),
             },
             ScoringItem {
                 stat_id: 1, // Passing TDs
                 points: 4.0,
                 points_overrides: BTreeMap::new(),
+                is_reverse_item: false,
+                skipped_override_keys: Vec::new(),
             },
             ScoringItem {
                 stat_id: 20, // Interceptions
                 points: -2.0,
                 points_overrides: BTreeMap::new(),
+                is_reverse_item: false,
+                skipped_override_keys: Vec::new(),
             },
             ScoringItem {
                 stat_id: 24, // Rushing yards
@@ -34,11 +40,15 @@ mod scoring_tests {
                     map.insert(0, 0.05); // QB slot gets 0.05 per yard
                     map
                 },
+                is_reverse_item: false,
+                skipped_override_keys: Vec::new(),
             },
             ScoringItem {
                 stat_id: 25, // Rushing TDs
                 points: 6.0,
                 points_overrides: BTreeMap::new(),
+                is_reverse_item: false,
+                skipped_override_keys: Vec::new(),
             },
         ]
     }
@@ -179,6 +189,43 @@ mod scoring_tests {
         assert!(stats.is_none());
     }
 
+    #[test]
+    fn test_select_weekly_stats_typed_player_matches_json() {
+        let player_data = json!({
+            "id": 12345,
+            "defaultPositionId": 1,
+            "stats": [
+                {
+                    "seasonId": 2023,
+                    "scoringPeriodId": 1,
+                    "statSourceId": 0,
+                    "statSplitTypeId": 0,
+                    "stats": { "53": 4000.0 }
+                },
+                {
+                    "seasonId": 2023,
+                    "scoringPeriodId": 1,
+                    "statSourceId": 0,
+                    "statSplitTypeId": 1,
+                    "stats": { "53": 350.0, "1": 2.0, "24": 20.0 }
+                }
+            ]
+        });
+        let player: Player = serde_json::from_value(player_data.clone()).unwrap();
+        let index = build_scoring_index(&create_test_scoring_items());
+
+        let typed = select_weekly_stats(&player, 2023, 1, 0).unwrap();
+        let raw = select_weekly_stats(&player_data, 2023, 1, 0).unwrap();
+        assert_eq!(typed.get("53"), Some(&350.0));
+        assert_eq!(
+            compute_points_for_week(typed, 0, &index),
+            compute_points_for_week(raw, 0, &index)
+        );
+        assert_eq!(compute_points_for_week(typed, 0, &index), 23.0);
+        assert!(select_weekly_stats(&player, 2023, 1, 1).is_none());
+        assert!(select_weekly_stats(&player, 2023, 2, 0).is_none());
+    }
+
     #[test]
     fn test_compute_points_for_week_basic() {
         let items = create_test_scoring_items();
@@ -286,6 +333,8 @@ mod scoring_tests {
             stat_id: 999,
             points: -1.0, // Negative points per unit
             points_overrides: BTreeMap::new(),
+            is_reverse_item: false,
+            skipped_override_keys: Vec::new(),
         }];
         let scoring_index = build_scoring_index(&items);
 
@@ -330,4 +379,263 @@ mod scoring_tests {
         let points = compute_points_for_week(&weekly_stats, 0, &scoring_index);
         assert_eq!(points, 8.0); // Only the valid passing TD should count
     }
+
+    /// A kicker week: 2/3 from 40-49, 1/1 from 50+, 3/3 extra points
+    fn kicker_week() -> serde_json::Value {
+        json!({
+            "74": 1.0, "75": 1.0,
+            "77": 2.0, "78": 3.0, "79": 1.0,
+            "80": 0.0, "81": 0.0,
+            "83": 3.0, "84": 4.0, "85": 1.0,
+            "86": 3.0, "87": 3.0
+        })
+    }
+
+    fn scoring_item(stat_id: u16, points: f64, overrides: &[(u8, f64)]) -> ScoringItem {
+        ScoringItem {
+            stat_id,
+            points,
+            points_overrides: overrides.iter().copied().collect(),
+            is_reverse_item: false,
+            skipped_override_keys: Vec::new(),
+        }
+    }
+
+    /// ESPN's default D/ST scoring: sacks, takeaways and the allowed brackets
+    fn dst_standard_scoring() -> Vec<ScoringItem> {
+        let mut items = vec![
+            scoring_item(99, 1.0, &[]),
+            scoring_item(95, 2.0, &[]),
+            scoring_item(96, 2.0, &[]),
+        ];
+        for (stat_id, points) in [
+            (89, 5.0),
+            (90, 4.0),
+            (91, 3.0),
+            (92, 1.0),
+            (121, 0.0),
+            (122, 0.0),
+            (123, -1.0),
+            (124, -3.0),
+            (125, -5.0),
+            (128, 5.0),
+            (129, 3.0),
+            (130, 2.0),
+            (131, 0.0),
+            (132, -1.0),
+            (133, -3.0),
+            (134, -5.0),
+            (135, -6.0),
+            (136, -7.0),
+        ] {
+            items.push(scoring_item(stat_id, points, &[]));
+        }
+        items
+    }
+
+    #[test]
+    fn test_dst_points_from_allowed_totals() {
+        let index = build_scoring_index(&dst_standard_scoring());
+        let slot = scoring_slot_for_position(16);
+
+        // 4 sacks, 2 INT, 1 fumble recovery, 10 points and 250 yards allowed:
+        // 4 + 4 + 2 + 3 (PA 7-13) + 2 (YA 200-299)
+        let strong = json!({ "99": 4.0, "95": 2.0, "96": 1.0, "120": 10.0, "127": 250.0 });
+        assert_eq!(compute_points_for_week(&strong, slot, &index), 15.0);
+
+        // 1 sack, 38 points and 420 yards allowed: 1 - 3 (PA 35-45) - 3 (YA 400-449)
+        let blowout = json!({ "99": 1.0, "120": 38.0, "127": 420.0 });
+        assert_eq!(compute_points_for_week(&blowout, slot, &index), -5.0);
+
+        // Bracket edges
+        let scored =
+            |allowed: f64| compute_points_for_week(&json!({ "120": allowed }), slot, &index);
+        assert_eq!(
+            [0.0, 1.0, 6.0, 7.0, 13.0, 14.0, 17.0, 18.0, 28.0, 46.0].map(scored),
+            [5.0, 4.0, 4.0, 3.0, 3.0, 1.0, 1.0, 0.0, -1.0, -5.0]
+        );
+
+        // The breakdown names the bracket the total fell in
+        let breakdown = explain_points_for_week(&strong, slot, &index);
+        let brackets: Vec<(&str, f64)> = breakdown
+            .iter()
+            .filter(|c| c.stat_id != 99 && c.stat_id != 95 && c.stat_id != 96)
+            .map(|c| (c.label.as_str(), c.points))
+            .collect();
+        assert_eq!(brackets, vec![("PA 7-13", 3.0), ("YA 200-299", 2.0)]);
+        let total: f64 = breakdown.iter().map(|c| c.points).sum();
+        assert_eq!(total, 15.0);
+    }
+
+    #[test]
+    fn test_dst_brackets_sent_by_espn_are_not_counted_twice() {
+        let index = build_scoring_index(&dst_standard_scoring());
+        let slot = scoring_slot_for_position(16);
+
+        // ESPN flagged the 14-17 and 300-349 brackets alongside the totals
+        let stats = json!({ "99": 2.0, "120": 17.0, "92": 1.0, "127": 310.0, "131": 1.0 });
+        assert_eq!(compute_points_for_week(&stats, slot, &index), 3.0);
+    }
+
+    #[test]
+    fn test_dst_bracket_overrides_and_projections() {
+        // A shutout is worth 10 in the D/ST slot
+        let mut items = dst_standard_scoring();
+        items.retain(|item| item.stat_id != 89);
+        items.push(scoring_item(89, 5.0, &[(16, 10.0)]));
+        let index = build_scoring_index(&items);
+
+        let shutout = json!({ "99": 5.0, "120": 0.0, "127": 90.0 });
+        assert_eq!(compute_points_for_week(&shutout, 16, &index), 20.0);
+        assert_eq!(
+            compute_points_for_week(&shutout, BASE_SCORING_SLOT, &index),
+            15.0
+        );
+
+        // Fractional projections fall in the bracket below the next whole number
+        assert_eq!(allowed_bracket_stat(&POINTS_ALLOWED_BRACKETS, 21.4), 121);
+        assert_eq!(allowed_bracket_stat(&POINTS_ALLOWED_BRACKETS, 0.6), 89);
+        assert_eq!(allowed_bracket_stat(&YARDS_ALLOWED_BRACKETS, 312.6), 131);
+        assert_eq!(allowed_bracket_stat(&YARDS_ALLOWED_BRACKETS, 99.9), 128);
+        let projected = json!({ "99": 2.5, "120": 21.4, "127": 312.6 });
+        assert_eq!(compute_points_for_week(&projected, 16, &index), 2.5);
+    }
+
+    #[test]
+    fn test_reverse_item_counts_against_the_player() {
+        let mut item = scoring_item(20, 2.0, &[(0, 3.0)]);
+        item.is_reverse_item = true;
+        let index = build_scoring_index(&[item]);
+
+        let stats = json!({ "20": 2.0 });
+        assert_eq!(compute_points_for_week(&stats, 2, &index), -4.0);
+        assert_eq!(compute_points_for_week(&stats, 0, &index), -6.0);
+    }
+
+    #[test]
+    fn test_kicker_points_standard_scoring() {
+        let index = build_scoring_index(&[
+            scoring_item(74, 5.0, &[]),
+            scoring_item(77, 4.0, &[]),
+            scoring_item(80, 3.0, &[]),
+            scoring_item(85, -1.0, &[]),
+            scoring_item(86, 1.0, &[]),
+            scoring_item(88, -1.0, &[]),
+        ]);
+        let slot = scoring_slot_for_position(5);
+        assert_eq!(slot, 17);
+
+        // 5 + 2 * 4 - 1 + 3
+        assert_eq!(compute_points_for_week(&kicker_week(), slot, &index), 15.0);
+    }
+
+    #[test]
+    fn test_kicker_points_custom_scoring_with_slot_overrides() {
+        // Misses only cost points in the K slot; long field goals are worth more there
+        let index = build_scoring_index(&[
+            scoring_item(74, 5.0, &[(17, 6.0)]),
+            scoring_item(77, 4.0, &[]),
+            scoring_item(79, -1.0, &[]),
+            scoring_item(85, 0.0, &[(17, -2.0)]),
+            scoring_item(86, 1.0, &[]),
+            scoring_item(88, -1.0, &[]),
+        ]);
+
+        // 6 + 2 * 4 - 1 (missed 40-49) - 2 (missed FG) + 3
+        let slot = scoring_slot_for_position(5);
+        assert_eq!(compute_points_for_week(&kicker_week(), slot, &index), 14.0);
+        // Keyed by the default position ID instead, the overrides would be missed
+        assert_eq!(compute_points_for_week(&kicker_week(), 5, &index), 15.0);
+
+        let breakdown = explain_points_for_week(&kicker_week(), slot, &index);
+        let lines: Vec<(&str, f64, f64)> = breakdown
+            .iter()
+            .map(|c| (c.label.as_str(), c.raw_value, c.points))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                ("FG 40-49", 2.0, 8.0),
+                ("FG 50+", 1.0, 6.0),
+                ("XP", 3.0, 3.0),
+                ("FG Miss", 1.0, -2.0),
+                ("FG Miss 40-49", 1.0, -1.0),
+            ]
+        );
+        let total: f64 = breakdown.iter().map(|c| c.points).sum();
+        assert_eq!(total, 14.0);
+
+        let json = serde_json::to_value(&breakdown[0]).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "stat_id": 77,
+                "label": "FG 40-49",
+                "raw_value": 2.0,
+                "points_per_unit": 4.0,
+                "points": 8.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_scoring_slot_for_position() {
+        // QB, RB, WR, TE, K, D/ST default positions map to their lineup slots
+        let slots: Vec<u8> = [1, 2, 3, 4, 5, 16]
+            .into_iter()
+            .map(scoring_slot_for_position)
+            .collect();
+        assert_eq!(slots, vec![0, 2, 4, 6, 17, 16]);
+        // Slot IDs pass through
+        assert_eq!(scoring_slot_for_position(0), 0);
+        assert_eq!(scoring_slot_for_position(17), 17);
+    }
+
+    #[test]
+    fn test_unknown_position_scored_without_qb_overrides() {
+        let index = build_scoring_index(&create_test_scoring_items());
+        let unknown: Player = serde_json::from_value(json!({
+            "id": 4040,
+            "fullName": "Mystery Back",
+            "defaultPositionId": -1
+        }))
+        .unwrap();
+        assert_eq!(unknown.position(), None);
+
+        // 100 rushing yards at the base 0.1, not the QB slot's 0.05
+        let slot = scoring_slot_for_player(&unknown).unwrap();
+        assert_eq!(slot, BASE_SCORING_SLOT);
+        let stats = json!({"24": 100.0});
+        assert!((compute_points_for_week(&stats, slot, &index) - 10.0).abs() < 1e-9);
+
+        // eligibleSlots settles the position when the default ID doesn't
+        let rb: Player = serde_json::from_value(json!({
+            "id": 4041,
+            "defaultPositionId": -1,
+            "eligibleSlots": [2, 3, 7, 20, 21, 23]
+        }))
+        .unwrap();
+        assert_eq!(rb.position().map(|p| p.to_string()), Some("RB".into()));
+        assert_eq!(scoring_slot_for_player(&rb), Some(2));
+    }
+
+    #[test]
+    fn test_infer_played() {
+        // No stats recorded at all is a DNP
+        assert!(!infer_played(&json!({}), Some(true), None));
+        // Any non-zero stat means the player took part
+        assert!(infer_played(
+            &json!({"3": 0.0, "24": 12.0}),
+            Some(false),
+            None
+        ));
+        // All zeros is a genuine goose egg unless the player was ruled out
+        assert!(infer_played(&json!({"3": 0.0}), Some(true), None));
+        assert!(!infer_played(
+            &json!({"3": 0.0}),
+            Some(true),
+            Some(&InjuryStatus::Out)
+        ));
+        assert!(!infer_played(&json!({"3": 0.0}), Some(false), None));
+    }
 }
@@ -0,0 +1,90 @@
+//! Unit tests for ESPN credentials loading
+
+use espn_ffl::{
+    error::EspnError,
+    espn::credentials::{
+        permissions_warning, resolve_credentials_from, CredentialSource, Credentials,
+    },
+};
+use tempfile::TempDir;
+
+fn credentials(swid: &str, espn_s2: &str) -> Credentials {
+    Credentials {
+        swid: swid.to_string(),
+        espn_s2: espn_s2.to_string(),
+    }
+}
+
+#[test]
+fn test_parse_credentials_file() {
+    let parsed = Credentials::parse(
+        "# copied from the browser\n\
+         SWID={ABC-123}\n\
+         \n\
+         espn_s2 = \"AEB%2Fxyz=\"\n",
+    )
+    .unwrap();
+    assert_eq!(parsed, credentials("{ABC-123}", "AEB%2Fxyz="));
+    assert_eq!(parsed.cookie_header(), "SWID={ABC-123}; espn_s2=AEB%2Fxyz=");
+
+    // The environment variable names work as keys too
+    let parsed = Credentials::parse("ESPN_SWID={ABC}\nESPN_S2=s2\n").unwrap();
+    assert_eq!(parsed, credentials("{ABC}", "s2"));
+
+    assert!(Credentials::parse("SWID={ABC}\n").is_err());
+    assert!(Credentials::parse("SWID={ABC}\nespn_s2=\n").is_err());
+    assert!(Credentials::parse("SWID={ABC}\ncookie=1\nespn_s2=s2").is_err());
+    assert!(Credentials::parse("just a token").is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_permission_warning_for_shared_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("espn-ffl/credentials");
+    credentials("{ABC}", "s2").write(&path).unwrap();
+
+    // Written owner-only
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    assert_eq!(permissions_warning(&path), None);
+
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+    let warning = permissions_warning(&path).unwrap();
+    assert!(warning.contains("chmod 600"));
+
+    // Still readable, just warned about; rewriting tightens the mode again
+    assert!(Credentials::from_file(&path).unwrap().is_some());
+    credentials("{ABC}", "s2").write(&path).unwrap();
+    assert_eq!(permissions_warning(&path), None);
+}
+
+#[test]
+fn test_env_credentials_take_precedence_over_file() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("credentials");
+    credentials("{FILE}", "file-s2").write(&path).unwrap();
+
+    let (used, source) = resolve_credentials_from(Some(credentials("{ENV}", "env-s2")), &path)
+        .unwrap()
+        .unwrap();
+    assert_eq!(used, credentials("{ENV}", "env-s2"));
+    assert_eq!(source, CredentialSource::Env);
+
+    let (used, source) = resolve_credentials_from(None, &path).unwrap().unwrap();
+    assert_eq!(used, credentials("{FILE}", "file-s2"));
+    assert_eq!(source, CredentialSource::File(path.clone()));
+
+    // Neither source: requests go out without cookies
+    let missing = dir.path().join("missing");
+    assert!(resolve_credentials_from(None, &missing).unwrap().is_none());
+
+    // A broken file is an error rather than silently unauthenticated
+    std::fs::write(&path, "SWID={FILE}\n").unwrap();
+    assert!(matches!(
+        resolve_credentials_from(None, &path),
+        Err(EspnError::InvalidCredentials { .. })
+    ));
+}
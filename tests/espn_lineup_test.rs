@@ -0,0 +1,294 @@
+//! Unit tests for lineup selection and matchup forecast math
+
+use espn_ffl::{
+    commands::matchup_forecast::{
+        apply_roster_changes, find_opponent, ResolvedChanges, TeamForecast, WeekLineup,
+    },
+    espn::{
+        lineup::*,
+        types::{LeagueData, LeagueSchedule},
+    },
+    PlayerId, Week,
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+fn candidate(id: i64, position: &str, projection: f64, available: bool) -> LineupCandidate {
+    LineupCandidate {
+        player_id: PlayerId::new(id),
+        name: format!("Player {}", id),
+        position: position.to_string(),
+        projection,
+        std_dev: 2.0,
+        available,
+        roster_slot_id: None,
+        locked: false,
+    }
+}
+
+/// A player whose game has started, sitting in `roster_slot_id`
+fn locked_candidate(
+    id: i64,
+    position: &str,
+    projection: f64,
+    roster_slot_id: u8,
+) -> LineupCandidate {
+    LineupCandidate {
+        roster_slot_id: Some(roster_slot_id),
+        locked: true,
+        ..candidate(id, position, projection, true)
+    }
+}
+
+fn slot_counts(slots: &[(u8, u32)]) -> HashMap<String, u32> {
+    slots
+        .iter()
+        .map(|(slot, count)| (slot.to_string(), *count))
+        .collect()
+}
+
+#[test]
+fn test_optimal_lineup_fills_dedicated_slots_before_flex() {
+    // QB, 2 RB, 1 WR, FLEX, bench
+    let counts = slot_counts(&[(0, 1), (2, 2), (4, 1), (23, 1), (20, 6), (21, 1)]);
+    let candidates = vec![
+        candidate(1, "QB", 20.0, true),
+        candidate(2, "RB", 15.0, true),
+        candidate(3, "RB", 12.0, true),
+        candidate(4, "RB", 11.0, true),
+        candidate(5, "WR", 14.0, true),
+        candidate(6, "WR", 9.0, true),
+        candidate(7, "QB", 18.0, true),
+    ];
+
+    let lineup = optimal_lineup(candidates, &counts);
+    let starters: Vec<(String, i64)> = lineup
+        .starters
+        .iter()
+        .map(|s| {
+            (
+                s.slot.clone(),
+                s.player.as_ref().unwrap().player_id.as_i64(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        starters,
+        vec![
+            ("QB".to_string(), 1),
+            ("RB".to_string(), 2),
+            ("RB".to_string(), 3),
+            ("WR".to_string(), 5),
+            ("FLEX".to_string(), 4),
+        ]
+    );
+    assert_eq!(lineup.projected_points(), 72.0);
+    assert_eq!(lineup.bench.len(), 2);
+    assert_eq!(lineup.holes().count(), 0);
+    // Five independent starters with std 2.0 each
+    assert!((lineup.variance() - 20.0).abs() < 1e-9);
+    assert!(lineup.floor() < 72.0 && lineup.ceiling() > 72.0);
+}
+
+#[test]
+fn test_optimal_lineup_skips_unavailable_and_reports_holes() {
+    let counts = slot_counts(&[(0, 1), (6, 1), (17, 1)]);
+    let candidates = vec![
+        candidate(1, "QB", 25.0, false), // ruled out
+        candidate(2, "QB", 14.0, true),
+        candidate(3, "TE", 0.0, false), // on bye
+        candidate(4, "K", 8.0, true),
+    ];
+
+    let lineup = optimal_lineup(candidates, &counts);
+    let qb = lineup.starters[0].player.as_ref().unwrap();
+    assert_eq!(qb.player_id, PlayerId::new(2));
+
+    let holes: Vec<&str> = lineup.holes().map(|s| s.slot.as_str()).collect();
+    assert_eq!(holes, vec!["TE"]);
+    assert_eq!(lineup.projected_points(), 22.0);
+}
+
+#[test]
+fn test_optimal_lineup_starts_second_qb_at_op() {
+    // Superflex: QB, RB, WR, FLEX, OP
+    let counts = slot_counts(&[(0, 1), (2, 1), (4, 1), (23, 1), (7, 1), (20, 4)]);
+    let candidates = vec![
+        candidate(1, "QB", 22.0, true),
+        candidate(2, "QB", 17.0, true),
+        candidate(3, "RB", 12.0, true),
+        candidate(4, "RB", 9.0, true),
+        candidate(5, "WR", 11.0, true),
+        candidate(6, "WR", 8.0, true),
+    ];
+
+    let lineup = optimal_lineup(candidates, &counts);
+    let op = lineup.starters.iter().find(|s| s.slot == "OP").unwrap();
+    assert_eq!(op.player.as_ref().unwrap().player_id.as_i64(), 2);
+    let flex = lineup.starters.iter().find(|s| s.slot == "FLEX").unwrap();
+    assert_eq!(flex.player.as_ref().unwrap().player_id.as_i64(), 4);
+    assert_eq!(lineup.projected_points(), 71.0);
+}
+
+#[test]
+fn test_candidate_floor_never_negative() {
+    let mut player = candidate(1, "WR", 3.0, true);
+    player.std_dev = 5.0;
+    assert_eq!(player.floor(), 0.0);
+    assert!((player.ceiling() - (3.0 + FLOOR_CEILING_Z * 5.0)).abs() < 1e-9);
+}
+
+#[test]
+fn test_normal_cdf_and_win_probability() {
+    assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+    assert!((normal_cdf(1.0) - 0.841_344_7).abs() < 1e-6);
+    assert!((normal_cdf(-1.96) - 0.024_997_9).abs() < 1e-6);
+
+    // 10 point edge with a combined std of 10 is a one-sigma favorite
+    assert!((win_probability(10.0, 100.0) - 0.841_344_7).abs() < 1e-6);
+    assert!((win_probability(-10.0, 100.0) - 0.158_655_3).abs() < 1e-6);
+    assert_eq!(win_probability(5.0, 0.0), 1.0);
+    assert_eq!(win_probability(0.0, 0.0), 0.5);
+}
+
+#[test]
+fn test_optimal_lineup_keeps_locked_players_in_place() {
+    // QB, 2 RB, FLEX, bench
+    let counts = slot_counts(&[(0, 1), (2, 2), (23, 1), (20, 6)]);
+    let candidates = vec![
+        candidate(1, "QB", 20.0, true),
+        // Started at RB and already playing, despite the low projection
+        locked_candidate(2, "RB", 4.0, 2),
+        candidate(3, "RB", 15.0, true),
+        candidate(4, "RB", 12.0, true),
+        // Left on the bench before kickoff: can't be moved into the lineup
+        locked_candidate(5, "RB", 30.0, 20),
+        candidate(6, "RB", 8.0, true),
+    ];
+
+    let lineup = optimal_lineup(candidates, &counts);
+    let starter_ids = |slot: &str| -> Vec<i64> {
+        lineup
+            .starters
+            .iter()
+            .filter(|s| s.slot == slot)
+            .map(|s| s.player.as_ref().unwrap().player_id.as_i64())
+            .collect()
+    };
+    assert_eq!(starter_ids("QB"), vec![1]);
+    let mut rbs = starter_ids("RB");
+    rbs.sort_unstable();
+    assert_eq!(rbs, vec![2, 3]);
+    assert_eq!(starter_ids("FLEX"), vec![4]);
+    assert!(lineup.bench.iter().any(|p| p.player_id == PlayerId::new(5)));
+    assert_eq!(lineup.projected_points(), 51.0);
+}
+
+#[test]
+fn test_team_forecast_sums_every_week_of_the_matchup_period() {
+    let counts = slot_counts(&[(0, 1), (2, 1)]);
+    let week_16 = optimal_lineup(
+        vec![
+            candidate(1, "QB", 20.0, true),
+            candidate(2, "RB", 10.0, true),
+        ],
+        &counts,
+    );
+    let week_17 = optimal_lineup(
+        vec![
+            candidate(1, "QB", 18.0, true),
+            candidate(2, "RB", 0.0, false),
+        ],
+        &counts,
+    );
+
+    let single = TeamForecast::new(1, "Alpha".to_string(), week_16.clone(), Vec::new());
+    assert_eq!(single.projected_points, 30.0);
+    assert_eq!(single.floor, week_16.floor());
+    assert_eq!(single.ceiling, week_16.ceiling());
+
+    let forecast = TeamForecast::new(
+        1,
+        "Alpha".to_string(),
+        week_16,
+        vec![WeekLineup {
+            week: Week::new(17),
+            lineup: week_17,
+        }],
+    );
+    assert_eq!(forecast.projected_points, 48.0);
+    // Three starters with a 2-point spread each
+    assert!((forecast.variance() - 12.0).abs() < 1e-9);
+    let spread = FLOOR_CEILING_Z * 12.0_f64.sqrt();
+    assert!((forecast.floor - (48.0 - spread)).abs() < 1e-9);
+    assert!((forecast.ceiling - (48.0 + spread)).abs() < 1e-9);
+}
+
+#[test]
+fn test_find_opponent() {
+    let schedule: LeagueSchedule = serde_json::from_value(json!({
+        "teams": [],
+        "schedule": [
+            {"id": 1, "matchupPeriodId": 1, "home": {"teamId": 1}, "away": {"teamId": 2}},
+            {"id": 2, "matchupPeriodId": 2, "home": {"teamId": 3}, "away": {"teamId": 1}},
+            {"id": 3, "matchupPeriodId": 3, "home": {"teamId": 1}}
+        ]
+    }))
+    .unwrap();
+
+    assert_eq!(find_opponent(&schedule, 1, 1), Some(2));
+    assert_eq!(find_opponent(&schedule, 1, 2), Some(3));
+    // Bye week has no away side
+    assert_eq!(find_opponent(&schedule, 1, 3), None);
+    assert_eq!(find_opponent(&schedule, 4, 1), None);
+}
+
+#[test]
+fn test_apply_roster_changes() {
+    let league: LeagueData = serde_json::from_value(json!({
+        "teams": [
+            {"id": 1, "name": "Mine", "roster": {"entries": [
+                {"playerId": 10, "lineupSlotId": 0, "injuryStatus": null},
+                {"playerId": 11, "lineupSlotId": 2, "injuryStatus": null}
+            ]}},
+            {"id": 2, "name": "Rival", "roster": {"entries": [
+                {"playerId": 20, "lineupSlotId": 4, "injuryStatus": null}
+            ]}}
+        ]
+    }))
+    .unwrap();
+    let named = |id: i64| (PlayerId::new(id), format!("Player {}", id));
+    let changes = ResolvedChanges {
+        // 20 is on another roster, 30 is a free agent, 10 is already mine
+        added: vec![named(20), named(30), named(10)],
+        // 11 is mine, 40 isn't
+        removed: vec![named(11), named(40)],
+    };
+
+    let mut warnings = Vec::new();
+    let scenario = apply_roster_changes(&league, 1, &changes, &mut warnings);
+    let roster_ids = |team: usize| -> Vec<i64> {
+        scenario.teams[team]
+            .roster
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|e| e.player_id)
+            .collect()
+    };
+    assert_eq!(roster_ids(0), vec![10, 20, 30]);
+    assert_eq!(roster_ids(1), Vec::<i64>::new());
+    // Additions go to the bench
+    assert_eq!(
+        scenario.teams[0].roster.as_ref().unwrap().entries[1].lineup_slot_id,
+        20
+    );
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].contains("rostered by Rival"));
+    assert!(warnings[1].contains("already on your roster"));
+    assert!(warnings[2].contains("Player 40 is not on your roster"));
+
+    // The real rosters are untouched
+    assert_eq!(league.teams[1].roster.as_ref().unwrap().entries.len(), 1);
+}
@@ -0,0 +1,162 @@
+//! Unit tests for the playoff odds simulation
+
+use espn_ffl::espn::{playoffs::*, types::LeagueSchedule};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+fn matchup(id: u32, week: u16, home: (u32, f64), away: (u32, f64), winner: &str) -> Value {
+    json!({
+        "id": id,
+        "matchupPeriodId": week,
+        "home": { "teamId": home.0, "totalPoints": home.1 },
+        "away": { "teamId": away.0, "totalPoints": away.1 },
+        "winner": winner
+    })
+}
+
+/// Four teams, two weeks played, one regular-season week and one playoff week left
+fn schedule() -> LeagueSchedule {
+    serde_json::from_value(json!({
+        "teams": [{ "id": 1 }, { "id": 2 }, { "id": 3 }, { "id": 4 }],
+        "schedule": [
+            matchup(1, 1, (1, 120.0), (2, 100.0), "HOME"),
+            matchup(2, 1, (3, 90.0), (4, 90.0), "TIE"),
+            matchup(3, 2, (1, 110.0), (3, 95.0), "HOME"),
+            matchup(4, 2, (2, 105.0), (4, 80.0), "HOME"),
+            matchup(5, 3, (1, 0.0), (4, 0.0), "UNDECIDED"),
+            matchup(6, 3, (2, 0.0), (3, 0.0), "UNDECIDED"),
+            matchup(7, 4, (1, 0.0), (2, 0.0), "UNDECIDED"),
+        ]
+    }))
+    .unwrap()
+}
+
+fn record(team_id: u32, wins: u32, losses: u32, points_for: f64) -> TeamRecord {
+    TeamRecord {
+        team_id,
+        wins,
+        losses,
+        ties: 0,
+        points_for,
+    }
+}
+
+fn strength(mean: f64, std_dev: f64) -> TeamStrength {
+    TeamStrength {
+        mean,
+        std_dev,
+        games: 5,
+    }
+}
+
+#[test]
+fn test_from_schedule_builds_standings_and_remaining_games() {
+    let input = PlayoffSimInput::from_schedule(&schedule(), Some(3), 2);
+
+    let by_team: HashMap<u32, &TeamRecord> = input.records.iter().map(|r| (r.team_id, r)).collect();
+    assert_eq!((by_team[&1].wins, by_team[&1].losses), (2, 0));
+    assert_eq!((by_team[&3].losses, by_team[&3].ties), (1, 1));
+    assert_eq!(by_team[&4].win_credit(), 0.5);
+    assert!((by_team[&1].points_for - 230.0).abs() < 1e-9);
+
+    // Week 4 is past the regular season
+    assert_eq!(input.remaining.len(), 2);
+    assert!(input.remaining.iter().all(|g| g.week == 3));
+
+    let team_1 = input.strengths[&1];
+    assert!((team_1.mean - 115.0).abs() < 1e-9);
+    assert_eq!(team_1.games, 2);
+
+    // Without a regular-season length every undecided matchup is simulated
+    let input = PlayoffSimInput::from_schedule(&schedule(), None, 2);
+    assert_eq!(input.remaining.len(), 3);
+}
+
+#[test]
+fn test_team_strengths_fall_back_to_league_spread() {
+    let scores = HashMap::from([(1, vec![100.0, 120.0]), (2, vec![90.0])]);
+    let strengths = team_strengths(&[1, 2, 3], &scores);
+
+    assert!((strengths[&1].std_dev - 200f64.sqrt()).abs() < 1e-9);
+    // One game: own mean, league spread
+    assert!((strengths[&2].mean - 90.0).abs() < 1e-9);
+    assert!((strengths[&2].std_dev - 15.275252316519467).abs() < 1e-9);
+    // No games: league mean and spread
+    assert!((strengths[&3].mean - 310.0 / 3.0).abs() < 1e-9);
+    assert_eq!(strengths[&3].games, 0);
+}
+
+#[test]
+fn test_simulation_is_reproducible_with_seed() {
+    let input = PlayoffSimInput::from_schedule(&schedule(), Some(3), 2);
+    let run = |seed| simulate_playoff_odds(&input, 2_000, &mut StdRng::seed_from_u64(seed));
+
+    assert_eq!(run(7), run(7));
+
+    let odds = run(7);
+    let total: f64 = odds.iter().map(|o| o.playoff_probability).sum();
+    assert!((total - 2.0).abs() < 1e-9);
+    for seed in 0..2 {
+        let seed_total: f64 = odds.iter().map(|o| o.seed_probabilities[seed]).sum();
+        assert!((seed_total - 1.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_simulation_clinched_and_eliminated_teams() {
+    // Team 1 can't be caught; team 4 can't catch second place with one game left
+    let input = PlayoffSimInput {
+        records: vec![
+            record(1, 8, 0, 900.0),
+            record(2, 6, 2, 850.0),
+            record(3, 5, 3, 800.0),
+            record(4, 2, 6, 700.0),
+        ],
+        strengths: HashMap::from([
+            (1, strength(110.0, 15.0)),
+            (2, strength(100.0, 15.0)),
+            (3, strength(100.0, 15.0)),
+            (4, strength(90.0, 15.0)),
+        ]),
+        remaining: vec![
+            RemainingGame {
+                week: 9,
+                home: 1,
+                away: 4,
+            },
+            RemainingGame {
+                week: 9,
+                home: 2,
+                away: 3,
+            },
+        ],
+        playoff_teams: 2,
+    };
+    let odds = simulate_playoff_odds(&input, 5_000, &mut StdRng::seed_from_u64(1));
+    let by_team: HashMap<u32, &PlayoffOdds> = odds.iter().map(|o| (o.team_id, o)).collect();
+
+    assert_eq!(odds[0].team_id, 1);
+    assert_eq!(by_team[&1].seed_probabilities[0], 1.0);
+    assert_eq!(by_team[&4].playoff_probability, 0.0);
+    // Team 3 only passes team 2 by beating it by enough to win the points tiebreaker
+    assert!(by_team[&2].playoff_probability > by_team[&3].playoff_probability);
+    assert!(by_team[&3].playoff_probability > 0.0);
+    assert!(by_team[&1].projected_wins > 8.0 && by_team[&1].projected_wins < 9.0);
+}
+
+#[test]
+fn test_simulation_with_no_remaining_games_keeps_standings() {
+    let input = PlayoffSimInput {
+        records: vec![record(1, 3, 5, 700.0), record(2, 5, 3, 650.0)],
+        strengths: HashMap::new(),
+        remaining: Vec::new(),
+        playoff_teams: 4,
+    };
+    let odds = simulate_playoff_odds(&input, 10, &mut StdRng::seed_from_u64(3));
+
+    // Playoff spots are capped at the number of teams
+    assert_eq!(odds[0].team_id, 2);
+    assert_eq!(odds[0].seed_probabilities, vec![1.0, 0.0]);
+    assert_eq!(odds[1].seed_probabilities, vec![0.0, 1.0]);
+}
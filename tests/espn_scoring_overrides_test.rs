@@ -0,0 +1,145 @@
+//! Unit tests for local scoring overrides
+
+use espn_ffl::{
+    error::EspnError,
+    espn::{
+        compute::{build_scoring_index, compute_points_for_week, explain_points_for_week},
+        scoring_overrides::ScoringOverrides,
+        types::ScoringItem,
+    },
+};
+use serde_json::json;
+use std::{collections::BTreeMap, io::Write, path::PathBuf};
+use tempfile::NamedTempFile;
+
+const QB_SLOT: u8 = 0;
+const TE_SLOT: u8 = 6;
+
+fn scoring_index() -> BTreeMap<u16, (f64, BTreeMap<u8, f64>)> {
+    build_scoring_index(&[
+        ScoringItem {
+            stat_id: 4, // Passing TDs
+            points: 4.0,
+            points_overrides: BTreeMap::new(),
+            is_reverse_item: false,
+            skipped_override_keys: Vec::new(),
+        },
+        ScoringItem {
+            stat_id: 53, // Receptions
+            points: 1.0,
+            points_overrides: BTreeMap::from([(QB_SLOT, 0.0)]),
+            is_reverse_item: false,
+            skipped_override_keys: Vec::new(),
+        },
+    ])
+}
+
+fn overrides_file(contents: serde_json::Value) -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(file, "{}", contents).unwrap();
+    file
+}
+
+fn load(contents: serde_json::Value) -> espn_ffl::Result<ScoringOverrides> {
+    let file = overrides_file(contents);
+    ScoringOverrides::from_path(file.path())
+}
+
+#[test]
+fn test_overrides_apply_by_stat_and_position() {
+    let overrides = load(json!({
+        "adjustments": [
+            { "stat_id": 53, "position": "TE", "add": 0.5, "note": "TE premium" },
+            { "stat_id": 4, "multiply": 1.5 }
+        ]
+    }))
+    .unwrap();
+    let mut index = scoring_index();
+    overrides.validate(&index).unwrap();
+    overrides.apply(&mut index);
+
+    assert_eq!(index[&4], (6.0, BTreeMap::new()));
+    // The TE slot gets its own value; other slots keep league scoring
+    assert_eq!(
+        index[&53],
+        (1.0, BTreeMap::from([(QB_SLOT, 0.0), (TE_SLOT, 1.5)]))
+    );
+
+    let week = json!({ "4": 1.0, "53": 6.0 });
+    assert_eq!(compute_points_for_week(&week, TE_SLOT, &index), 15.0);
+    assert_eq!(compute_points_for_week(&week, 4, &index), 12.0);
+}
+
+#[test]
+fn test_position_only_override_scales_every_stat_for_that_slot() {
+    let overrides = load(json!({
+        "adjustments": [{ "position": "QB", "multiply": 2.0 }]
+    }))
+    .unwrap();
+    let mut index = scoring_index();
+    overrides.apply(&mut index);
+
+    assert_eq!(index[&4].1[&QB_SLOT], 8.0);
+    assert_eq!(index[&53].1[&QB_SLOT], 0.0);
+    assert_eq!(index[&4].0, 4.0);
+}
+
+#[test]
+fn test_explain_marks_overridden_stats() {
+    let overrides = load(json!({
+        "adjustments": [{ "stat_id": 53, "position": "TE", "add": 0.5, "note": "TE premium" }]
+    }))
+    .unwrap();
+    let mut index = scoring_index();
+    overrides.apply(&mut index);
+
+    let mut breakdown = explain_points_for_week(&json!({ "4": 1.0, "53": 6.0 }), TE_SLOT, &index);
+    overrides.annotate(&mut breakdown, TE_SLOT);
+    let adjustments: Vec<(u16, Option<&str>)> = breakdown
+        .iter()
+        .map(|c| (c.stat_id, c.adjustment.as_deref()))
+        .collect();
+    assert_eq!(
+        adjustments,
+        vec![(53, Some("+0.5 (TE premium)")), (4, None)]
+    );
+
+    // A WR's receptions are untouched
+    overrides.annotate(&mut breakdown, 4);
+    assert!(breakdown.iter().all(|c| c.adjustment.is_none()));
+}
+
+#[test]
+fn test_unknown_stat_id_lists_league_stats() {
+    let overrides = load(json!({ "adjustments": [{ "stat_id": 999, "add": 1.0 }] })).unwrap();
+    let err = overrides.validate(&scoring_index()).unwrap_err();
+
+    assert!(matches!(
+        err,
+        EspnError::UnknownOverrideStat { stat_id: 999, .. }
+    ));
+    let message = err.to_string();
+    assert!(message.contains("999"));
+    assert!(message.contains("4 (Pass TD), 53 (Rec)"), "{}", message);
+}
+
+#[test]
+fn test_invalid_overrides_files_are_rejected() {
+    for contents in [
+        json!({ "adjustments": [{ "position": "FLEX", "add": 1.0 }] }),
+        json!({ "adjustments": [{ "position": "XX", "add": 1.0 }] }),
+        json!({ "adjustments": [{ "stat_id": 4, "bonus": 1.0 }] }),
+        json!({ "rules": [] }),
+    ] {
+        let err = load(contents.clone()).unwrap_err();
+        assert!(
+            matches!(err, EspnError::InvalidScoringOverrides { .. }),
+            "{}: {}",
+            contents,
+            err
+        );
+    }
+
+    let missing = ScoringOverrides::from_path(&PathBuf::from("/nonexistent/overrides.json"));
+    assert!(missing.is_err());
+}
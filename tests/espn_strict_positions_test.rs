@@ -0,0 +1,34 @@
+//! Tests for `--strict-positions`
+//!
+//! The flag is process-wide and can't be turned off again, so these run in their
+//! own test binary rather than alongside the other scoring tests.
+
+use espn_ffl::espn::{compute::*, types::*};
+use serde_json::json;
+
+#[test]
+fn test_strict_positions_leaves_unknown_position_out() {
+    let unknown: Player = serde_json::from_value(json!({
+        "id": 4040,
+        "fullName": "Mystery Back",
+        "defaultPositionId": -1
+    }))
+    .unwrap();
+    let rb: Player = serde_json::from_value(json!({
+        "id": 4041,
+        "fullName": "Known Back",
+        "defaultPositionId": 2
+    }))
+    .unwrap();
+
+    // Lenient by default: the unknown player is kept at base points
+    assert!(!strict_positions());
+    assert_eq!(scoring_slot_for_player(&unknown), Some(BASE_SCORING_SLOT));
+    assert_eq!(scoring_slot_for_player(&rb), Some(2));
+
+    // Strict mode excludes them, while known positions are scored as before
+    enable_strict_positions();
+    assert!(strict_positions());
+    assert_eq!(scoring_slot_for_player(&unknown), None);
+    assert_eq!(scoring_slot_for_player(&rb), Some(2));
+}
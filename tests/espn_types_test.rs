@@ -1,6 +1,8 @@
 //! Unit tests for ESPN types and data structures
 
-use espn_ffl::{espn::types::*, PlayerId, Week};
+use espn_ffl::{
+    cli::types::position::FlexEligibility, espn::teams::*, espn::types::*, PlayerId, Position, Week,
+};
 use serde_json::json;
 use std::collections::BTreeMap;
 
@@ -39,6 +41,254 @@ mod types_tests {
         assert!(item.points_overrides.is_empty());
     }
 
+    #[test]
+    fn test_player_pro_team() {
+        let player: Player = serde_json::from_value(json!({
+            "id": 3918298,
+            "fullName": "Josh Allen",
+            "defaultPositionId": 1,
+            "proTeamId": 2
+        }))
+        .unwrap();
+        assert_eq!(player.pro_team_id, Some(2));
+        assert_eq!(player.pro_team(), Some("BUF"));
+
+        // Free agents use team 0; older payloads omit the field
+        assert_eq!(pro_team_abbrev(0), None);
+        let player: Player = serde_json::from_value(json!({
+            "id": 1,
+            "defaultPositionId": 1
+        }))
+        .unwrap();
+        assert_eq!(player.pro_team(), None);
+    }
+
+    #[test]
+    fn test_defense_pro_team_from_id() {
+        let team = |id: i64| defense_pro_team_id(id).and_then(pro_team_abbrev);
+        assert_eq!(team(-16002), Some("BUF"));
+        assert_eq!(team(-16028), Some("WSH"));
+        assert_eq!(team(-16014), Some("LAR"));
+        assert_eq!(team(-16024), Some("LAC"));
+        assert_eq!(team(-16013), Some("LV"));
+        assert_eq!(team(-16033), Some("BAL"));
+        assert_eq!(team(-16034), Some("HOU"));
+        // The positive form older versions stored
+        assert_eq!(team(16030), Some("JAX"));
+        // IDs 31 and 32 were never used, and regular players aren't defenses
+        assert_eq!(team(-16031), None);
+        assert_eq!(team(-16000), None);
+        assert_eq!(team(3918298), None);
+    }
+
+    #[test]
+    fn test_defense_pro_team_from_name() {
+        let team = |name: &str| defense_pro_team_id_from_name(name).and_then(pro_team_abbrev);
+        assert_eq!(team("Bills D/ST"), Some("BUF"));
+        assert_eq!(team("49ers D/ST"), Some("SF"));
+        // Every name Washington has played under
+        assert_eq!(team("Commanders D/ST"), Some("WSH"));
+        assert_eq!(team("Redskins D/ST"), Some("WSH"));
+        assert_eq!(team("Washington Football Team D/ST"), Some("WSH"));
+        assert_eq!(team("Washington D/ST"), Some("WSH"));
+        // Relocated teams keep their nickname
+        assert_eq!(team("St. Louis Rams D/ST"), Some("LAR"));
+        assert_eq!(team("San Diego Chargers DST"), Some("LAC"));
+        assert_eq!(team("Oakland Raiders D/ST"), Some("LV"));
+        assert_eq!(team("Nobody D/ST"), None);
+    }
+
+    #[test]
+    fn test_defense_player_gets_its_pro_team() {
+        // No proTeamId: the ID decides
+        let player: Player = serde_json::from_value(json!({
+            "id": -16014,
+            "fullName": "Rams D/ST",
+            "defaultPositionId": 16,
+            "proTeamId": 0
+        }))
+        .unwrap();
+        assert_eq!(player.nfl_team_id(), Some(14));
+        assert_eq!(player.pro_team(), Some("LAR"));
+
+        // An ID without a team falls back to the name
+        let player: Player = serde_json::from_value(json!({
+            "id": -1,
+            "fullName": "Chargers D/ST",
+            "defaultPositionId": 16
+        }))
+        .unwrap();
+        assert_eq!(player.pro_team(), Some("LAC"));
+
+        // Only defenses are derived; a free agent stays without a team
+        let player: Player = serde_json::from_value(json!({
+            "id": 16002,
+            "fullName": "Bills Fan",
+            "defaultPositionId": 1,
+            "proTeamId": 0
+        }))
+        .unwrap();
+        assert_eq!(player.pro_team(), None);
+    }
+
+    #[test]
+    fn test_nameless_defense_is_named_after_its_team() {
+        assert_eq!(pro_team_name(2), Some("Buffalo Bills"));
+        assert_eq!(
+            defense_name(28),
+            Some("Washington Commanders D/ST".to_string())
+        );
+        assert_eq!(defense_name(31), None);
+
+        let player: Player = serde_json::from_value(json!({
+            "id": -16002,
+            "defaultPositionId": 16
+        }))
+        .unwrap();
+        assert_eq!(player.display_name(), "Buffalo Bills D/ST");
+        assert_eq!(player.pro_team(), Some("BUF"));
+
+        // ESPN's own name wins, and other nameless players keep the placeholder
+        let player: Player = serde_json::from_value(json!({
+            "id": -16002,
+            "fullName": "Bills D/ST",
+            "defaultPositionId": 16
+        }))
+        .unwrap();
+        assert_eq!(player.display_name(), "Bills D/ST");
+        let player: Player = serde_json::from_value(json!({
+            "id": 42,
+            "defaultPositionId": 1
+        }))
+        .unwrap();
+        assert_eq!(player.display_name(), "Player 42");
+    }
+
+    /// A player payload with one stat line and the given news block (`None` leaves it out)
+    fn player_with_news(news: Option<serde_json::Value>) -> Player {
+        let mut payload = json!({
+            "id": 3139477,
+            "fullName": "Patrick Mahomes",
+            "defaultPositionId": 1,
+            "stats": [{
+                "seasonId": 2025,
+                "scoringPeriodId": 3,
+                "statSourceId": 0,
+                "statSplitTypeId": 1,
+                "stats": {"3": 250.0}
+            }]
+        });
+        if let Some(news) = news {
+            payload["news"] = news;
+        }
+        serde_json::from_value(payload).unwrap()
+    }
+
+    #[test]
+    fn test_player_news_tolerates_empty_shapes() {
+        for news in [
+            None,
+            Some(json!(null)),
+            Some(json!([])),
+            Some(json!({})),
+            Some(json!({"feed": []})),
+            Some(json!("Limited in practice")),
+            Some(json!([{"headline": "   "}, {"type": "Story"}])),
+        ] {
+            let player = player_with_news(news.clone());
+            assert_eq!(player.news, None, "{:?}", news);
+            assert_eq!(player.stats.len(), 1, "{:?}", news);
+            assert_eq!(player.stats[0].stats.get("3"), Some(&250.0));
+        }
+    }
+
+    #[test]
+    fn test_player_news_from_single_item() {
+        let player = player_with_news(Some(json!({
+            "headline": "Mahomes (ankle) limited at Wednesday practice",
+            "published": "2025-09-17T21:14:08.123Z"
+        })));
+        assert_eq!(
+            player.news,
+            Some(PlayerNews {
+                headline: "Mahomes (ankle) limited at Wednesday practice".to_string(),
+                published: Some(1_758_143_648),
+            })
+        );
+        assert_eq!(player.stats.len(), 1);
+    }
+
+    #[test]
+    fn test_player_news_picks_latest_item() {
+        let feed = json!([
+            {"headline": "Older", "lastModified": 1_758_000_000_000u64},
+            {"headline": "Newest", "lastModified": 1_758_143_648_000u64},
+            {"headline": "Undated"}
+        ]);
+        let player = player_with_news(Some(feed.clone()));
+        let news = player.news.unwrap();
+        assert_eq!(news.headline, "Newest");
+        assert_eq!(news.published, Some(1_758_143_648));
+
+        // The same items wrapped in a feed object
+        let player = player_with_news(Some(json!({"feed": feed})));
+        assert_eq!(player.news.unwrap().headline, "Newest");
+
+        // Undated items are still used when nothing is dated
+        let player = player_with_news(Some(json!([{"headline": "Only one"}])));
+        assert_eq!(
+            player.news,
+            Some(PlayerNews {
+                headline: "Only one".to_string(),
+                published: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pro_team_id_for_abbrev() {
+        assert_eq!(pro_team_id_for_abbrev("BUF"), Some(2));
+        assert_eq!(pro_team_id_for_abbrev("buf"), Some(2));
+        assert_eq!(pro_team_id_for_abbrev("WSH"), Some(28));
+        assert_eq!(pro_team_id_for_abbrev("WAS"), Some(28));
+        assert_eq!(pro_team_id_for_abbrev("JAC"), Some(30));
+        assert_eq!(pro_team_id_for_abbrev("LA"), Some(14));
+        assert_eq!(pro_team_id_for_abbrev("STL"), Some(14));
+        assert_eq!(pro_team_id_for_abbrev("SD"), Some(24));
+        assert_eq!(pro_team_id_for_abbrev("OAK"), Some(13));
+        assert_eq!(pro_team_id_for_abbrev("XYZ"), None);
+    }
+
+    #[test]
+    fn test_scoring_item_tolerates_odd_overrides_and_extra_fields() {
+        let json = json!({
+            "statId": 4,
+            "points": 4.0,
+            "isReverseItem": true,
+            "leagueRanking": 0.0,
+            "leagueTotal": 12.0,
+            "pointsOverrides": { "0": 6.0, "ALL": 5.0, "300": 1.0 }
+        });
+
+        let item: ScoringItem = serde_json::from_value(json).unwrap();
+        assert!(item.is_reverse_item);
+        assert_eq!(item.points_overrides, BTreeMap::from([(0, 6.0)]));
+        assert_eq!(item.skipped_override_keys, vec!["300", "ALL"]);
+
+        let settings = ScoringSettings {
+            scoring_items: vec![item.clone()],
+        };
+        assert_eq!(
+            settings.override_warnings(),
+            vec![r#"stat 4: skipped pointsOverrides key(s) "300", "ALL" (not lineup slot IDs)"#]
+        );
+
+        // The skipped keys survive a round trip through the settings cache
+        let cached: ScoringItem =
+            serde_json::from_value(serde_json::to_value(&item).unwrap()).unwrap();
+        assert_eq!(cached, item);
+    }
+
     #[test]
     fn test_scoring_item_serialization() {
         let mut overrides = BTreeMap::new();
@@ -49,6 +299,8 @@ mod types_tests {
             stat_id: 24,
             points: 0.1,
             points_overrides: overrides,
+            is_reverse_item: false,
+            skipped_override_keys: Vec::new(),
         };
 
         let json = serde_json::to_value(&item).unwrap();
@@ -109,6 +361,89 @@ mod types_tests {
         assert_eq!(settings.scoring_settings.scoring_items.len(), 1);
         assert_eq!(settings.scoring_settings.scoring_items[0].stat_id, 20);
         assert_eq!(settings.scoring_settings.scoring_items[0].points, -2.0);
+        // Settings cached before schedule settings were read have none
+        assert_eq!(settings.schedule_settings.playoff_team_count, None);
+    }
+
+    #[test]
+    fn test_flex_eligibility_follows_the_lineup_slots() {
+        let settings = |slots: serde_json::Value| -> LeagueSettings {
+            serde_json::from_value(json!({
+                "scoringSettings": { "scoringItems": [] },
+                "rosterSettings": { "lineupSlotCounts": slots, "positionLimits": {} }
+            }))
+            .unwrap()
+        };
+
+        // WR/TE flex only: no RBs
+        let wr_te = settings(json!({ "2": 2, "4": 2, "5": 1, "23": 0 }));
+        assert_eq!(
+            wr_te.flex_eligible_positions(5),
+            Some(vec![Position::WR, Position::TE])
+        );
+        assert_eq!(wr_te.flex_eligible_positions(23), None);
+        assert_eq!(wr_te.flex_eligible_positions(2), None);
+        assert_eq!(
+            wr_te.flex_eligibility().positions(),
+            &[Position::WR, Position::TE]
+        );
+
+        // FLEX plus OP covers QBs too
+        let superflex = settings(json!({ "0": 1, "7": 1, "23": 1 }));
+        assert_eq!(
+            superflex.flex_eligibility().positions(),
+            &[Position::QB, Position::RB, Position::WR, Position::TE]
+        );
+
+        // No flex-style slot at all: the standard RB/WR/TE
+        let no_flex = settings(json!({ "0": 1, "2": 2 }));
+        assert_eq!(no_flex.flex_eligibility(), FlexEligibility::default());
+    }
+
+    #[test]
+    fn test_league_settings_schedule_settings() {
+        let json = json!({
+            "scoringSettings": { "scoringItems": [] },
+            "rosterSettings": { "lineupSlotCounts": {}, "positionLimits": {} },
+            "scheduleSettings": { "matchupPeriodCount": 14, "playoffTeamCount": 6 }
+        });
+
+        let settings: LeagueSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(settings.schedule_settings.matchup_period_count, Some(14));
+        assert_eq!(settings.schedule_settings.playoff_team_count, Some(6));
+        // No mapping: each matchup period is the week of the same number
+        let schedule = &settings.schedule_settings;
+        assert_eq!(schedule.scoring_periods(3), vec![Week::new(3)]);
+        assert_eq!(schedule.matchup_period_for(Week::new(3)), 3);
+        assert!(schedule.multi_week_periods().is_empty());
+    }
+
+    #[test]
+    fn test_schedule_settings_two_week_championship() {
+        let json = json!({
+            "matchupPeriodCount": 14,
+            "matchupPeriods": { "14": [14], "15": [15], "16": [17, 16] }
+        });
+
+        let schedule: ScheduleSettings = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            schedule.scoring_periods(16),
+            vec![Week::new(16), Week::new(17)]
+        );
+        assert_eq!(schedule.scoring_periods(15), vec![Week::new(15)]);
+        assert_eq!(schedule.matchup_period_for(Week::new(16)), 16);
+        assert_eq!(schedule.matchup_period_for(Week::new(17)), 16);
+        assert_eq!(
+            schedule.multi_week_periods(),
+            BTreeMap::from([(16, vec![Week::new(16), Week::new(17)])])
+        );
+
+        // The name used by the league status view is accepted too
+        let aliased: ScheduleSettings = serde_json::from_value(json!({
+            "scoringPeriodsByMatchupPeriod": { "16": [16, 17] }
+        }))
+        .unwrap();
+        assert_eq!(aliased.matchup_period_for(Week::new(17)), 16);
     }
 
     #[test]
@@ -181,6 +516,51 @@ mod types_tests {
         assert!(player.stats.is_empty());
     }
 
+    #[test]
+    fn test_injury_status_parsing() {
+        let statuses: Vec<InjuryStatus> = serde_json::from_value(json!([
+            "ACTIVE",
+            "SUSPENSION",
+            "PHYSICALLY_UNABLE_TO_PERFORM",
+            "NON_FOOTBALL_INJURY",
+            "SOMETHING_NEW"
+        ]))
+        .unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                InjuryStatus::Active,
+                InjuryStatus::Suspension,
+                InjuryStatus::PUP,
+                InjuryStatus::NonFootballInjury,
+                InjuryStatus::Unknown,
+            ]
+        );
+
+        // Stored display names and ESPN codes parse to the same status
+        for status in [
+            InjuryStatus::Active,
+            InjuryStatus::InjuryReserve,
+            InjuryStatus::Out,
+            InjuryStatus::Doubtful,
+            InjuryStatus::Questionable,
+            InjuryStatus::Probable,
+            InjuryStatus::DayToDay,
+            InjuryStatus::Suspension,
+            InjuryStatus::PUP,
+            InjuryStatus::NonFootballInjury,
+            InjuryStatus::Unknown,
+        ] {
+            assert_eq!(
+                status.to_string().parse::<InjuryStatus>(),
+                Ok(status.clone())
+            );
+            let code = serde_json::to_value(&status).unwrap();
+            assert_eq!(code.as_str().unwrap().parse::<InjuryStatus>(), Ok(status));
+        }
+        assert!("SOMETHING_NEW".parse::<InjuryStatus>().is_err());
+    }
+
     #[test]
     fn test_player_stats_deserialization() {
         let json = json!({
@@ -231,6 +611,10 @@ mod types_tests {
             active: None,
             injured: None,
             injury_status: None,
+            pro_team_id: None,
+            eligible_slots: vec![],
+            ownership: None,
+            news: None,
         };
         let player_points = PlayerPoints::from_espn_player(
             PlayerId::new(456789),
@@ -266,6 +650,9 @@ mod types_tests {
             is_rostered: Some(true),                  // rostered
             team_id: Some(42),                        // team_id
             team_name: Some("Test Team".to_string()), // team_name
+            played: None,
+            opponent_team: None,
+            home_game: None,
         });
 
         // Verify all fields are set correctly
@@ -274,7 +661,7 @@ mod types_tests {
         assert_eq!(player_points.position, "QB");
         assert_eq!(player_points.points, 29.5);
         assert_eq!(player_points.week, Week::new(1));
-        assert_eq!(player_points.projected, false);
+        assert!(!player_points.projected);
         assert_eq!(player_points.active, Some(true));
         assert_eq!(player_points.injured, Some(false));
         assert_eq!(player_points.injury_status, Some(InjuryStatus::Active));
@@ -283,6 +670,61 @@ mod types_tests {
         assert_eq!(player_points.team_name, Some("Test Team".to_string()));
     }
 
+    #[test]
+    fn test_player_points_json_fields() {
+        let cached = |status: bool| CachedPlayerData {
+            player_id: PlayerId::new(12345),
+            name: "Josh Allen".to_string(),
+            position: "QB".to_string(),
+            points: 29.5,
+            week: Week::new(1),
+            projected: false,
+            active: status.then_some(true),
+            injured: status.then_some(false),
+            injury_status: status.then_some(InjuryStatus::Active),
+            is_rostered: status.then_some(true),
+            team_id: status.then_some(42),
+            team_name: status.then(|| "Test Team".to_string()),
+            played: status.then_some(true),
+            opponent_team: None,
+            home_game: None,
+        };
+
+        let known = serde_json::to_value(PlayerPoints::from_cached_data(cached(true))).unwrap();
+        assert_eq!(
+            known,
+            json!({
+                "id": 12345,
+                "name": "Josh Allen",
+                "position": "QB",
+                "week": 1,
+                "projected": false,
+                "points": 29.5,
+                "active": true,
+                "injured": false,
+                "injury_status": "ACTIVE",
+                "is_rostered": true,
+                "team_id": 42,
+                "team_name": "Test Team",
+                "played": true
+            })
+        );
+
+        // Unknown status fields are left out rather than written as null
+        let unknown = serde_json::to_value(PlayerPoints::from_cached_data(cached(false))).unwrap();
+        assert_eq!(
+            unknown,
+            json!({
+                "id": 12345,
+                "name": "Josh Allen",
+                "position": "QB",
+                "week": 1,
+                "projected": false,
+                "points": 29.5
+            })
+        );
+    }
+
     #[test]
     fn test_player_points_from_cached_data_with_injured_status() {
         use espn_ffl::{PlayerId, Week};
@@ -301,6 +743,9 @@ mod types_tests {
             is_rostered: Some(false), // not rostered (free agent)
             team_id: None,            // no team_id
             team_name: None,          // no team_name
+            played: None,
+            opponent_team: None,
+            home_game: None,
         });
 
         assert_eq!(player_points.active, Some(false));
@@ -345,9 +790,10 @@ mod types_tests {
             }
         });
 
-        // This should fail during deserialization due to invalid key
-        let result = serde_json::from_value::<ScoringItem>(json);
-        assert!(result.is_err());
+        // The invalid key is skipped instead of failing the whole item
+        let item = serde_json::from_value::<ScoringItem>(json).unwrap();
+        assert_eq!(item.points_overrides, BTreeMap::from([(2, 0.05)]));
+        assert_eq!(item.skipped_override_keys, vec!["invalid"]);
     }
 
     #[test]
@@ -413,11 +859,15 @@ mod types_tests {
                             map.insert(2, 0.06);
                             map
                         },
+                        is_reverse_item: false,
+                        skipped_override_keys: Vec::new(),
                     },
                     ScoringItem {
                         stat_id: 1,
                         points: 4.0,
                         points_overrides: BTreeMap::new(),
+                        is_reverse_item: false,
+                        skipped_override_keys: Vec::new(),
                     },
                 ],
             },
@@ -425,6 +875,7 @@ mod types_tests {
                 lineup_slot_counts: std::collections::HashMap::new(),
                 position_limits: std::collections::HashMap::new(),
             },
+            schedule_settings: Default::default(),
         };
 
         let json = serde_json::to_value(&original).unwrap();
@@ -457,4 +908,258 @@ mod types_tests {
         let result: Result<BTreeMap<u8, f64>, _> = serde_json::from_value(json);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_starters_per_position() {
+        let settings = LeagueSettings {
+            scoring_settings: ScoringSettings {
+                scoring_items: vec![],
+            },
+            roster_settings: RosterSettings {
+                lineup_slot_counts: [
+                    ("0", 1),
+                    ("2", 2),
+                    ("4", 2),
+                    ("6", 1),
+                    ("17", 1),
+                    ("20", 7),
+                    ("23", 1),
+                    ("16", 0),
+                ]
+                .into_iter()
+                .map(|(slot, count)| (slot.to_string(), count))
+                .collect(),
+                position_limits: std::collections::HashMap::new(),
+            },
+            schedule_settings: Default::default(),
+        };
+
+        let starters = settings.starters_per_position(10);
+        assert_eq!(starters.get("QB"), Some(&10));
+        assert_eq!(starters.get("RB"), Some(&20));
+        assert_eq!(starters.get("WR"), Some(&20));
+        assert_eq!(starters.get("TE"), Some(&10));
+        assert_eq!(starters.get("K"), Some(&10));
+        // Bench, flex and empty slots are not attributed to a position
+        assert_eq!(starters.get("D/ST"), None);
+        assert_eq!(starters.len(), 5);
+    }
+
+    #[test]
+    fn test_pro_schedule_last_kickoff() {
+        let schedule: ProSchedule = serde_json::from_value(json!({
+            "settings": {
+                "proTeams": [
+                    {
+                        "id": 1,
+                        "abbrev": "Atl",
+                        "proGamesByScoringPeriod": {
+                            "1": [{"id": 10, "date": 1757278800000u64}],
+                            "2": [{"id": 20, "date": 1757970000000u64}]
+                        }
+                    },
+                    {
+                        "id": 2,
+                        "abbrev": "Buf",
+                        "proGamesByScoringPeriod": {
+                            "1": [{"id": 11, "date": 1757379600000u64}]
+                        }
+                    },
+                    {"id": 0, "abbrev": "FA"}
+                ]
+            }
+        }))
+        .unwrap();
+
+        // Latest kickoff of the week, in seconds
+        assert_eq!(schedule.last_kickoff(Week::new(1)), Some(1757379600));
+        assert_eq!(schedule.last_kickoff(Week::new(2)), Some(1757970000));
+        assert_eq!(schedule.last_kickoff(Week::new(3)), None);
+    }
+
+    /// Week 2 of 2025: Thursday night, Sunday 1 PM and 4:25 PM ET, Monday night and a bye
+    fn sunday_slate() -> ProSchedule {
+        let game = |id: u64, date: u64| json!({"2": [{"id": id, "date": date * 1000}]});
+        serde_json::from_value(json!({
+            "settings": {
+                "proTeams": [
+                    {"id": 1, "abbrev": "Thu", "proGamesByScoringPeriod": game(1, 1_757_636_100)},
+                    {"id": 2, "abbrev": "Sun", "proGamesByScoringPeriod": game(2, 1_757_869_200)},
+                    {"id": 3, "abbrev": "Lte", "proGamesByScoringPeriod": game(3, 1_757_881_500)},
+                    {"id": 4, "abbrev": "Mon", "proGamesByScoringPeriod": game(4, 1_757_978_100)},
+                    {"id": 5, "abbrev": "Bye"}
+                ]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_pro_schedule_locked_teams_through_a_sunday_slate() {
+        let schedule = sunday_slate();
+        let week = Week::new(2);
+        let locked_at = |time: &str| {
+            let now: espn_ffl::UtcTimestamp = time.parse().unwrap();
+            schedule.locked_teams(week, now.as_secs()).map(|teams| {
+                let mut teams: Vec<u32> = teams.into_iter().collect();
+                teams.sort_unstable();
+                teams
+            })
+        };
+
+        assert_eq!(schedule.kickoff(2, week), Some(1_757_869_200));
+        assert_eq!(schedule.kickoff(5, week), None);
+
+        // Before Thursday night nobody is locked, but the week is still live
+        assert_eq!(locked_at("2025-09-11T12:00Z"), Some(vec![]));
+        assert_eq!(locked_at("2025-09-14T16:59:59Z"), Some(vec![1]));
+        // The 1 PM ET kickoff is 17:00 UTC
+        assert_eq!(locked_at("2025-09-14T17:00Z"), Some(vec![1, 2]));
+        assert_eq!(locked_at("2025-09-14T20:30Z"), Some(vec![1, 2, 3]));
+        assert_eq!(locked_at("2025-09-16T03:14:59Z"), Some(vec![1, 2, 3, 4]));
+        // Four hours after Monday's kickoff the week is over
+        assert_eq!(locked_at("2025-09-16T03:15Z"), None);
+        assert_eq!(schedule.locked_teams(Week::new(3), 0), None);
+    }
+
+    #[test]
+    fn test_week_is_unplayed_until_its_first_kickoff() {
+        use espn_ffl::commands::player_data::week_not_played;
+
+        let schedule = sunday_slate();
+        let week = Week::new(2);
+        assert_eq!(schedule.first_kickoff(week), Some(1_757_636_100));
+        // The schedule wins over the payload when it has the week
+        assert!(week_not_played(Some(&schedule), week, true, 1_757_636_099));
+        assert!(!week_not_played(
+            Some(&schedule),
+            week,
+            false,
+            1_757_636_100
+        ));
+        // Without it, only a payload with no actual stats means unplayed
+        assert!(week_not_played(Some(&schedule), Week::new(3), false, 0));
+        assert!(!week_not_played(None, week, true, 0));
+    }
+
+    #[test]
+    fn test_pro_schedule_opponent() {
+        let schedule: ProSchedule = serde_json::from_value(json!({
+            "settings": {
+                "proTeams": [
+                    {"id": 2, "proGamesByScoringPeriod": {"3": [{"id": 7, "date": 0}]}},
+                    {"id": 4, "proGamesByScoringPeriod": {"3": [{"id": 8, "date": 0}]}},
+                    {"id": 15, "proGamesByScoringPeriod": {"3": [{"id": 7, "date": 0}]}},
+                    {"id": 30, "proGamesByScoringPeriod": {"4": [{"id": 9, "date": 0}]}}
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(schedule.opponent(2, Week::new(3)), Some(15));
+        assert_eq!(schedule.opponent(15, Week::new(3)), Some(2));
+        // Nobody else lists team 4's game, and team 30 is on bye
+        assert_eq!(schedule.opponent(4, Week::new(3)), None);
+        assert_eq!(schedule.opponent(30, Week::new(3)), None);
+    }
+
+    #[test]
+    fn test_pro_schedule_is_home() {
+        let game = |home: Option<u32>| json!({"3": [{"id": 7, "date": 0, "homeProTeamId": home, "awayProTeamId": 15}]});
+        let schedule: ProSchedule = serde_json::from_value(json!({
+            "settings": {
+                "proTeams": [
+                    {"id": 2, "proGamesByScoringPeriod": game(Some(2))},
+                    {"id": 15, "proGamesByScoringPeriod": game(Some(2))},
+                    {"id": 4, "proGamesByScoringPeriod": game(None)}
+                ]
+            }
+        }))
+        .unwrap();
+        assert_eq!(schedule.is_home(2, Week::new(3)), Some(true));
+        assert_eq!(schedule.is_home(15, Week::new(3)), Some(false));
+        // Unknown without a home team, and on a bye
+        assert_eq!(schedule.is_home(4, Week::new(3)), None);
+        assert_eq!(schedule.is_home(2, Week::new(4)), None);
+    }
+
+    #[test]
+    fn test_roster_with_nameless_team_uses_placeholder() {
+        use espn_ffl::{
+            cli::types::filters::FantasyTeamFilter,
+            commands::player_filters::matches_fantasy_team_filter,
+        };
+
+        // A pre-draft/orphaned team comes back with an ID and roster but no name
+        let league: LeagueData = serde_json::from_value(json!({
+            "teams": [
+                {
+                    "id": 1,
+                    "name": "Team Alpha",
+                    "abbrev": "ALP",
+                    "roster": {"entries": [{"playerId": 100, "lineupSlotId": 0}]}
+                },
+                {
+                    "id": 7,
+                    "roster": {"entries": [{"playerId": 200, "lineupSlotId": 2}]}
+                },
+                {"id": 8, "name": "  ", "roster": {"entries": []}},
+                {"id": 9}
+            ]
+        }))
+        .unwrap();
+        // Team 9 has no roster, so there is nothing to show under a placeholder
+        assert_eq!(league.nameless_team_ids(), vec![7, 8]);
+
+        let player = |id: i64| Player {
+            id,
+            full_name: Some(format!("Player {}", id)),
+            default_position_id: 2,
+            stats: vec![],
+            active: None,
+            injured: None,
+            injury_status: None,
+            pro_team_id: None,
+            eligible_slots: vec![],
+            ownership: None,
+            news: None,
+        };
+        let mut points: Vec<PlayerPoints> = [100, 200]
+            .into_iter()
+            .map(|id| {
+                PlayerPoints::from_espn_player(
+                    PlayerId::new(id),
+                    &player(id),
+                    "RB".to_string(),
+                    10.0,
+                    Week::new(1),
+                    false,
+                )
+            })
+            .collect();
+        league.update_player_points_with_roster(&mut points);
+
+        let orphan = &points[1];
+        assert_eq!(orphan.is_rostered, Some(true));
+        assert_eq!(orphan.team_id, Some(7));
+        assert_eq!(orphan.team_name.as_deref(), Some("Team 7"));
+
+        // Filtering by ID still finds the team; another team's name doesn't match it
+        assert!(matches_fantasy_team_filter(
+            orphan,
+            &FantasyTeamFilter::Id(7)
+        ));
+        let alpha = FantasyTeamFilter::Name("alpha".to_string());
+        assert!(!matches_fantasy_team_filter(orphan, &alpha));
+        assert!(matches_fantasy_team_filter(&points[0], &alpha));
+
+        // Stored team rows without a name get the same placeholder and filtering
+        assert_eq!(team_display_name(7, None), "Team 7");
+        assert_eq!(team_display_name(8, Some("  ")), "Team 8");
+        assert_eq!(team_display_name(1, Some("Team Alpha")), "Team Alpha");
+        assert!(alpha.matches(1, Some("Team Alpha")));
+        assert!(!alpha.matches(7, None));
+        assert!(FantasyTeamFilter::Id(7).matches(7, None));
+        assert!(FantasyTeamFilter::Ids(vec![3, 7]).matches(7, None));
+        assert!(!FantasyTeamFilter::Ids(vec![3]).matches(7, Some("Team Alpha")));
+    }
 }
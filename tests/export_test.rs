@@ -0,0 +1,112 @@
+//! Round-trip tests for Parquet season exports
+
+#![cfg(feature = "parquet")]
+
+use arrow_array::{Array, BooleanArray, Float64Array, Int64Array, StringArray, UInt16Array};
+use espn_ffl::{
+    espn::types::InjuryStatus,
+    storage::{export::write_weekly_parquet, *},
+    LeagueId, PlayerId, Season, Week,
+};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use std::fs::File;
+
+const LEAGUE: LeagueId = LeagueId(12345);
+
+fn column<'a, T: 'static>(batch: &'a arrow_array::RecordBatch, name: &str) -> &'a T {
+    batch
+        .column_by_name(name)
+        .unwrap()
+        .as_any()
+        .downcast_ref::<T>()
+        .unwrap()
+}
+
+#[test]
+fn test_weekly_export_parquet_round_trip() {
+    let mut db = PlayerDatabase::new_in_memory().unwrap();
+    for (id, name, position) in [(101, "Josh Allen", "QB"), (202, "Puka Nacua", "WR")] {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: Some("BUF".to_string()),
+        })
+        .unwrap();
+    }
+    for week in 1..=3 {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(LEAGUE),
+            injury_status: Some(InjuryStatus::Active),
+            is_rostered: Some(true),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(101),
+                Season::new(2024),
+                Week::new(week),
+                Some(20.0),
+                Some(20.0 + week as f64),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    // Projection only, roster status unknown: those columns stay null
+    let stats = PlayerWeeklyStats {
+        league_id: Some(LEAGUE),
+        is_rostered: None,
+        ..PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(202),
+            Season::new(2024),
+            Week::new(3),
+            Some(14.5),
+            None,
+            0,
+            0,
+        )
+    };
+    db.upsert_weekly_stats(&stats, false).unwrap();
+
+    let rows = db.get_weekly_export(LEAGUE, Season::new(2024)).unwrap();
+    assert_eq!(rows.len(), 4);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("season.parquet");
+    write_weekly_parquet(&rows, File::create(&path).unwrap()).unwrap();
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap();
+    let batches: Vec<_> = reader.map(|batch| batch.unwrap()).collect();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+
+    let batch = &batches[0];
+    let player_ids = column::<Int64Array>(batch, "player_id");
+    let seasons = column::<UInt16Array>(batch, "season");
+    let weeks = column::<UInt16Array>(batch, "week");
+    let projected = column::<Float64Array>(batch, "projected");
+    let actual = column::<Float64Array>(batch, "actual");
+    let positions = column::<StringArray>(batch, "position");
+    let injury_status = column::<StringArray>(batch, "injury_status");
+    let is_rostered = column::<BooleanArray>(batch, "is_rostered");
+
+    // Rows are ordered by week then player, matching the database query
+    for (i, row) in rows.iter().enumerate() {
+        assert_eq!(player_ids.value(i), row.player_id.as_i64());
+        assert_eq!(seasons.value(i), 2024);
+        assert_eq!(weeks.value(i), row.week.as_u16());
+        assert_eq!(positions.value(i), row.position);
+    }
+
+    assert_eq!(actual.value(0), 21.0);
+    assert_eq!(injury_status.value(0), "Active");
+    assert!(is_rostered.value(0));
+
+    // Week 3 projection-only row keeps nulls for missing values
+    assert_eq!(player_ids.value(3), 202);
+    assert_eq!(projected.value(3), 14.5);
+    assert!(actual.is_null(3));
+    assert!(injury_status.is_null(3));
+    assert!(is_rostered.is_null(3));
+}
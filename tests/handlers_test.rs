@@ -0,0 +1,1034 @@
+//! End-to-end handler tests against a mocked ESPN API
+
+mod common;
+
+use common::{
+    players_fixture, roster_fixture, settings_fixture, MockEspn, TEST_LEAGUE_ID, TEST_SEASON,
+};
+use espn_ffl::{
+    cli::types::filters::RosterStatusFilter,
+    commands::{
+        category_leaders::{handle_category_leaders, CategoryLeadersParams},
+        common::CommandParamsBuilder,
+        db::{handle_db_coverage, handle_db_team_names},
+        league_data::{handle_league_data, validate_season},
+        notes::handle_note_list,
+        player_data::{
+            fetch_player_data, fetch_points_comparison, handle_player_data, PlayerDataParams,
+        },
+        projection_analysis::{analyze_projections, ProjectionAnalysisParams},
+        update_all_data::handle_update_all_data,
+        warm_cache::{warm_cache, WarmCacheParams, WarmStatus},
+    },
+    core::{
+        cache::GLOBAL_CACHE,
+        metrics::{enable_metrics, metrics_snapshot, reset_metrics},
+    },
+    error::EspnError,
+    espn::http::{get_player_data, PlayerDataRequest},
+    storage::{CategoryLeadersQuery, PlayerDatabase},
+    LeagueId, PlayerId, Season, Week, WeekRange,
+};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use wiremock::{
+    matchers::{method, path, query_param},
+    Mock, ResponseTemplate,
+};
+
+#[tokio::test]
+async fn test_player_data_run_records_metrics() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+    // Earlier tests in this process may have left the same week in memory
+    GLOBAL_CACHE.clear_all_memory();
+    enable_metrics();
+    reset_metrics();
+
+    let params = || {
+        PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+            .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+    };
+    handle_player_data(params()).await.unwrap();
+
+    let metrics = metrics_snapshot();
+    let players = metrics.http["players"];
+    assert_eq!(players.requests, 1);
+    assert!(players.bytes > 0);
+    assert!(metrics.http.contains_key("mSettings"));
+    assert!(metrics.caches["http_player_data"].misses >= 1);
+    // Both fixture players plus their weekly rows
+    assert!(metrics.rows_written >= 4);
+    let stages: Vec<&str> = metrics.stages.iter().map(|(name, _)| *name).collect();
+    for stage in ["fetch players", "parse and score", "store"] {
+        assert!(stages.contains(&stage), "missing stage {stage}");
+    }
+
+    // A rerun is served locally: no new player request, and the lookups count as hits
+    handle_player_data(params()).await.unwrap();
+    let rerun = metrics_snapshot();
+    assert_eq!(rerun.http["players"].requests, 1);
+    let hits = |m: &espn_ffl::core::metrics::RunMetrics| -> u64 {
+        m.caches.values().map(|c| c.hits).sum()
+    };
+    assert!(hits(&rerun) > hits(&metrics));
+    reset_metrics();
+}
+
+#[tokio::test]
+async fn test_handle_player_data_against_mock_api() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let params = PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+        .with_refresh();
+    handle_player_data(params).await.unwrap();
+
+    // Results land in the database under the mocked cache directory
+    let db = PlayerDatabase::new().unwrap();
+    assert!(mock.cache_dir.path().join("espn-ffl/players.db").exists());
+
+    let qb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(qb.actual_points, Some(20.0));
+    assert_eq!(qb.is_rostered, Some(true));
+    assert_eq!(qb.fantasy_team_id, Some(1));
+    assert_eq!(qb.fantasy_team_name.as_deref(), Some("Team Alpha"));
+
+    let rb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(200),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert!((rb.actual_points.unwrap() - 10.0).abs() < 1e-9);
+    assert_eq!(rb.is_rostered, Some(false));
+
+    // Raw actual stats are kept for category queries (3 = passing yards)
+    let leaders = db
+        .get_category_leaders(
+            LeagueId::new(TEST_LEAGUE_ID),
+            &CategoryLeadersQuery {
+                stat_id: 3,
+                season: Season::new(TEST_SEASON),
+                week: Some(Week::new(1)),
+                positions: None,
+                roster_status: None,
+                limit: 5,
+            },
+        )
+        .unwrap();
+    assert_eq!(leaders.len(), 1);
+    assert_eq!(leaders[0].player_id, PlayerId::new(100));
+    assert_eq!(leaders[0].value, 300.0);
+    assert_eq!(leaders[0].fantasy_points, Some(20.0));
+
+    // League settings were stored from the mocked endpoint
+    assert!(db
+        .get_league_settings(LeagueId::new(TEST_LEAGUE_ID), Season::new(TEST_SEASON))
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_handle_player_data_both_stores_projected_and_actual() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let mut params = PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+        .with_refresh();
+    params.both = true;
+    handle_player_data(params).await.unwrap();
+
+    // Each source is merged into the same row without overwriting the other
+    let db = PlayerDatabase::new().unwrap();
+    let qb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(qb.actual_points, Some(20.0));
+    assert!((qb.projected_points.unwrap() - 18.0).abs() < 1e-9);
+    assert_eq!(qb.fantasy_team_id, Some(1));
+
+    // A second run serves both sources from the database
+    let mut params = PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID));
+    params.both = true;
+    handle_player_data(params).await.unwrap();
+
+    let qb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(qb.actual_points, Some(20.0));
+    assert!((qb.projected_points.unwrap() - 18.0).abs() < 1e-9);
+}
+
+#[tokio::test]
+async fn test_handle_player_data_stores_both_sources_from_one_fetch() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    let params = PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+        .with_refresh();
+    handle_player_data(params).await.unwrap();
+
+    // The actual run also scored and kept the projections from the same payload
+    let db = PlayerDatabase::new().unwrap();
+    let qb = db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(qb.actual_points, Some(20.0));
+    assert!((qb.projected_points.unwrap() - 18.0).abs() < 1e-9);
+
+    // --proj is then served without asking ESPN for the players again
+    let params = PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), true)
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID));
+    handle_player_data(params).await.unwrap();
+
+    let players_path = format!("/seasons/{}/players", TEST_SEASON);
+    let player_fetches = mock
+        .server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == players_path)
+        .count();
+    assert_eq!(player_fetches, 1);
+}
+
+#[tokio::test]
+async fn test_fetch_functions_return_the_filtered_rows() {
+    let mock = MockEspn::start().await;
+    mock.mock_defaults().await;
+
+    // Only the QB is on a fantasy team in the roster fixture
+    let params = || {
+        PlayerDataParams::new(Season::new(TEST_SEASON), Week::new(1), false)
+            .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+            .with_roster_filter(RosterStatusFilter::Rostered)
+    };
+    let rows = fetch_player_data(params()).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].id, PlayerId::new(100));
+    assert_eq!(rows[0].points, 20.0);
+    assert_eq!(rows[0].team_name.as_deref(), Some("Team Alpha"));
+
+    let rows = fetch_points_comparison(params()).await.unwrap();
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].actual_points, Some(20.0));
+    assert!((rows[0].delta.unwrap() - 2.0).abs() < 1e-9);
+
+    // Without history the estimates are ESPN's projections, highest first
+    let params = ProjectionAnalysisParams::new(Season::new(TEST_SEASON), Week::new(1), 1.0)
+        .unwrap()
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID));
+    let estimates = analyze_projections(params).await.unwrap();
+    let ids: Vec<PlayerId> = estimates.iter().map(|e| e.player_id).collect();
+    assert_eq!(ids, vec![PlayerId::new(100), PlayerId::new(200)]);
+    assert!((estimates[0].espn_projection - 18.0).abs() < 1e-9);
+
+    let params = ProjectionAnalysisParams::new(Season::new(TEST_SEASON), Week::new(1), 1.0)
+        .unwrap()
+        .with_league_id(LeagueId::new(TEST_LEAGUE_ID))
+        .with_roster_filter(RosterStatusFilter::FA);
+    let estimates = analyze_projections(params).await.unwrap();
+    let ids: Vec<PlayerId> = estimates.iter().map(|e| e.player_id).collect();
+    assert_eq!(ids, vec![PlayerId::new(200)]);
+}
+
+#[tokio::test]
+async fn test_get_player_data_migrates_legacy_cache_file() {
+    let mock = MockEspn::start().await;
+    let week = Week::new(3);
+    let cache_dir = mock.cache_dir.path().join("espn-ffl");
+    let legacy = cache_dir.join(format!(
+        "http_player_data_l{}_s{}_w3_all_names_all_pos_actual.json",
+        TEST_LEAGUE_ID, TEST_SEASON
+    ));
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    std::fs::write(&legacy, players_fixture(3).to_string()).unwrap();
+
+    // No players endpoint is mocked, so this can only be answered from the old file
+    let players = get_player_data(PlayerDataRequest::new(
+        LeagueId::new(TEST_LEAGUE_ID),
+        Season::new(TEST_SEASON),
+        week,
+    ))
+    .await
+    .unwrap();
+    assert_eq!(players, players_fixture(3));
+
+    assert!(!legacy.exists());
+    assert!(cache_dir
+        .join(TEST_LEAGUE_ID.to_string())
+        .join(format!(
+            "http_player_data_l{}_s{}_w3_all_names_all_pos_all_inj_all_ros.json",
+            TEST_LEAGUE_ID, TEST_SEASON
+        ))
+        .exists());
+    assert!(mock.server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_league_settings_files_are_imported_into_the_database() {
+    use espn_ffl::espn::cache_settings::{
+        import_league_settings_files, load_or_fetch_league_settings,
+    };
+
+    let mock = MockEspn::start().await;
+    let league_id = LeagueId::new(TEST_LEAGUE_ID);
+    let season = Season::new(TEST_SEASON);
+    let league_dir = mock
+        .cache_dir
+        .path()
+        .join("espn-ffl")
+        .join(TEST_LEAGUE_ID.to_string());
+    let file = league_dir.join(format!(
+        "league-settings_{}_{}.json",
+        TEST_SEASON, TEST_LEAGUE_ID
+    ));
+    std::fs::create_dir_all(&league_dir).unwrap();
+    std::fs::write(
+        &file,
+        common::superflex_settings_fixture()["settings"].to_string(),
+    )
+    .unwrap();
+
+    let mut db = PlayerDatabase::new().unwrap();
+    assert_eq!(import_league_settings_files(&mut db).unwrap(), 1);
+    assert!(!file.exists());
+    let stored = db.get_league_settings(league_id, season).unwrap().unwrap();
+    assert!(stored.settings.has_superflex());
+
+    // No settings endpoint is mocked, so this can only come from the database
+    let settings = load_or_fetch_league_settings(league_id, false, season)
+        .await
+        .unwrap();
+    assert!(settings.has_superflex());
+    assert!(mock.server.received_requests().await.unwrap().is_empty());
+
+    // --clear-db clears them too
+    db.clear_all_data().unwrap();
+    assert!(db.get_league_settings(league_id, season).unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_two_leagues_share_a_cache_dir_without_mixing() {
+    use espn_ffl::espn::{
+        cache_settings::load_or_fetch_league_settings, http::get_league_roster_data,
+    };
+
+    let mock = MockEspn::start().await;
+    GLOBAL_CACHE.clear_all_memory();
+    let league_a = LeagueId::new(TEST_LEAGUE_ID);
+    let league_b = LeagueId::new(67890);
+    let season = Season::new(TEST_SEASON);
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+    mock.mock_league_view("mRoster", roster_fixture()).await;
+    let league_b_path = format!("/seasons/{}/segments/0/leagues/67890", TEST_SEASON);
+    for (view, body) in [
+        ("mSettings", common::superflex_settings_fixture()),
+        (
+            "mRoster",
+            json!({"teams": [{"id": 7, "name": "Team Bravo", "abbrev": "BRV", "roster": {"entries": []}}]}),
+        ),
+    ] {
+        Mock::given(method("GET"))
+            .and(path(league_b_path.as_str()))
+            .and(query_param("view", view))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&mock.server)
+            .await;
+    }
+
+    let load = || async {
+        let settings_a = load_or_fetch_league_settings(league_a, false, season)
+            .await
+            .unwrap();
+        let settings_b = load_or_fetch_league_settings(league_b, false, season)
+            .await
+            .unwrap();
+        let (rosters_a, _) = get_league_roster_data(false, league_a, season, None, false)
+            .await
+            .unwrap();
+        let (rosters_b, _) = get_league_roster_data(false, league_b, season, None, false)
+            .await
+            .unwrap();
+        assert!(!settings_a.has_superflex());
+        assert!(settings_b.has_superflex());
+        assert_eq!(rosters_a.teams[0].name.as_deref(), Some("Team Alpha"));
+        assert_eq!(rosters_b.teams[0].name.as_deref(), Some("Team Bravo"));
+    };
+    load().await;
+
+    // Each league's files sit in its own directory
+    let cache_dir = mock.cache_dir.path().join("espn-ffl");
+    let files = |league: u32| -> Vec<String> {
+        let mut names: Vec<String> = std::fs::read_dir(cache_dir.join(league.to_string()))
+            .unwrap()
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    };
+    for league in [TEST_LEAGUE_ID, 67890] {
+        let names = files(league);
+        assert!(names.iter().any(|name| name.starts_with("roster_data")));
+        // No database yet, so settings are cached in a file
+        assert!(names.iter().any(|name| name.starts_with("league-settings")));
+        assert!(names
+            .iter()
+            .all(|name| name.contains(&format!("_{}", league))
+                || name.contains(&format!("_l{}", league))));
+    }
+
+    // Served from disk alone, each league still gets its own data
+    GLOBAL_CACHE.clear_all_memory();
+    mock.server.reset().await;
+    load().await;
+    assert!(mock.server.received_requests().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_refresh_drops_cached_payloads_for_other_filters() {
+    let mock = MockEspn::start().await;
+    mock.mock_players(players_fixture(3)).await;
+    GLOBAL_CACHE.clear_all_memory();
+    let cache_dir = mock
+        .cache_dir
+        .path()
+        .join(format!("espn-ffl/{}", TEST_LEAGUE_ID));
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    let cached = |week: u16, names: &str| {
+        cache_dir.join(format!(
+            "http_player_data_l{}_s{}_w{}_{}_all_pos_all_inj_all_ros.json",
+            TEST_LEAGUE_ID, TEST_SEASON, week, names
+        ))
+    };
+    for path in [cached(3, "names_Josh Allen"), cached(13, "all_names")] {
+        std::fs::write(path, "[]").unwrap();
+    }
+
+    let mut request = PlayerDataRequest::new(
+        LeagueId::new(TEST_LEAGUE_ID),
+        Season::new(TEST_SEASON),
+        Week::new(3),
+    );
+    request.refresh = true;
+    get_player_data(request).await.unwrap();
+
+    // The name-filtered payload predates the refresh; other weeks are untouched
+    assert!(!cached(3, "names_Josh Allen").exists());
+    assert!(cached(3, "all_names").exists());
+    assert!(cached(13, "all_names").exists());
+}
+
+#[tokio::test]
+async fn test_name_query_asks_espn_for_a_limited_page() {
+    use espn_ffl::espn::http::{player_query_limit, NAME_QUERY_LIMIT};
+
+    let mock = MockEspn::start().await;
+    mock.mock_players(players_fixture(4)).await;
+    GLOBAL_CACHE.clear_all_memory();
+
+    let names = vec!["Test Quarterback".to_string()];
+    let limit = player_query_limit(Some(&names)).unwrap();
+    assert_eq!(limit, NAME_QUERY_LIMIT);
+    let request = PlayerDataRequest::new(
+        LeagueId::new(TEST_LEAGUE_ID),
+        Season::new(TEST_SEASON),
+        Week::new(4),
+    )
+    .with_player_names(names)
+    .with_limit(limit);
+    get_player_data(request).await.unwrap();
+
+    // The NFL schedule is also asked for, to tell how long to cache the payload
+    let players_path = format!("/seasons/{}/players", TEST_SEASON);
+    let requests: Vec<_> = mock
+        .server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.url.path() == players_path)
+        .collect();
+    assert_eq!(requests.len(), 1);
+    let filter: serde_json::Value =
+        serde_json::from_slice(requests[0].headers["x-fantasy-filter"].as_bytes()).unwrap();
+    assert_eq!(
+        filter,
+        json!({ "filterName": { "value": "Test Quarterback" }, "limit": NAME_QUERY_LIMIT })
+    );
+}
+
+#[tokio::test]
+async fn test_player_pages_are_stitched_together() {
+    use wiremock::matchers::header_regex;
+
+    let mock = MockEspn::start().await;
+    GLOBAL_CACHE.clear_all_memory();
+    let players = players_fixture(5);
+    let page = |index: usize| json!([players[index].clone()]);
+    // Offsets are only sent after the first page; mocks mounted first take priority
+    for (offset, body) in [(1, page(1)), (2, json!([]))] {
+        Mock::given(method("GET"))
+            .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+            .and(header_regex(
+                "x-fantasy-filter",
+                &format!(r#""offset":{}\b"#, offset),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(body))
+            .mount(&mock.server)
+            .await;
+    }
+    mock.mock_players(page(0)).await;
+
+    let stitched = get_player_data(
+        PlayerDataRequest::new(
+            LeagueId::new(TEST_LEAGUE_ID),
+            Season::new(TEST_SEASON),
+            Week::new(5),
+        )
+        .with_page_size(1),
+    )
+    .await
+    .unwrap();
+    assert_eq!(stitched, players);
+    let players_path = format!("/seasons/{}/players", TEST_SEASON);
+    let page_fetches = mock
+        .server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .filter(|r| r.url.path() == players_path)
+        .count();
+    assert_eq!(page_fetches, 3);
+}
+
+#[tokio::test]
+async fn test_stat_correction_invalidates_cached_week() {
+    use espn_ffl::{
+        commands::common::CommandParams,
+        core::cache::{CacheKey, PlayerDataCacheKey},
+        storage::{Player, PlayerWeeklyStats},
+    };
+
+    let _mock = MockEspn::start().await;
+    GLOBAL_CACHE.clear_all_memory();
+    let season = Season::new(TEST_SEASON);
+    let player_id = PlayerId::new(3918298);
+    let stats = |week: u16, actual: f64| PlayerWeeklyStats {
+        league_id: Some(LeagueId::new(TEST_LEAGUE_ID)),
+        ..PlayerWeeklyStats::test_minimal(player_id, season, Week::new(week), None, Some(actual))
+    };
+    let key = |week: u16| PlayerDataCacheKey {
+        league_id: LeagueId::new(TEST_LEAGUE_ID),
+        season,
+        week: Week::new(week),
+        player_names: None,
+        positions: None,
+        projected: false,
+        injury_status: None,
+        roster_status: None,
+        fantasy_team_filter: None,
+        nfl_team: None,
+    };
+    // Cache-first read, as a caller of the query cache would do it
+    let read = |db: &PlayerDatabase, week: u16| {
+        GLOBAL_CACHE.player_data.get(&key(week)).unwrap_or_else(|| {
+            let rows = db
+                .get_cached_player_data(
+                    &CommandParams::new(season, Week::new(week)),
+                    LeagueId::new(TEST_LEAGUE_ID),
+                    false,
+                )
+                .unwrap();
+            GLOBAL_CACHE.player_data.put(key(week), rows.clone());
+            rows
+        })
+    };
+
+    {
+        let mut db = PlayerDatabase::new().unwrap();
+        db.upsert_player(&Player {
+            player_id,
+            name: "Josh Allen".to_string(),
+            position: "QB".to_string(),
+            team: None,
+        })
+        .unwrap();
+        db.upsert_weekly_stats(&stats(2, 21.4), true).unwrap();
+        db.upsert_weekly_stats(&stats(12, 30.0), true).unwrap();
+        // The weeks just written are cached in memory only until the run ends
+        assert_eq!(read(&db, 2)[0].3, 21.4);
+        assert!(!key(2).to_file_path().exists());
+        GLOBAL_CACHE.flush_invalidations();
+    }
+    // A read-only run caches them on disk
+    GLOBAL_CACHE.clear_all_memory();
+    let db = PlayerDatabase::new().unwrap();
+    assert_eq!(read(&db, 2)[0].3, 21.4);
+    assert_eq!(read(&db, 12)[0].3, 30.0);
+    assert!(key(2).to_file_path().exists());
+
+    // A later run picks up ESPN's stat correction for week 2
+    let mut db = PlayerDatabase::new().unwrap();
+    db.upsert_weekly_stats(&stats(2, 23.4), true).unwrap();
+
+    // The stale file stays until the run ends, but isn't read
+    assert!(key(2).to_file_path().exists());
+    assert_eq!(read(&db, 2)[0].3, 23.4);
+    GLOBAL_CACHE.flush_invalidations();
+    assert!(!key(2).to_file_path().exists());
+    assert!(key(12).to_file_path().exists());
+    assert_eq!(GLOBAL_CACHE.player_data.get(&key(12)).unwrap()[0].3, 30.0);
+}
+
+/// Most a full backfill may spend deleting stale cache files
+const INVALIDATION_BUDGET: Duration = Duration::from_secs(2);
+
+#[tokio::test]
+async fn test_backfill_invalidation_is_batched_and_within_budget() {
+    use espn_ffl::{
+        core::cache::{CacheKey, PlayerDataCacheKey, WeeklyStatsCacheKey},
+        storage::{Player, PlayerWeeklyStats},
+    };
+
+    let _mock = MockEspn::start().await;
+    GLOBAL_CACHE.flush_invalidations();
+    let league_id = LeagueId::new(TEST_LEAGUE_ID);
+    let season = Season::new(TEST_SEASON);
+    let seed = |path: std::path::PathBuf| {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "null").unwrap();
+    };
+
+    // A large cache: weekly stats for 500 players over 18 weeks of this season and
+    // the last, plus a few player-data queries per week
+    let mut stale = Vec::new();
+    let mut kept = Vec::new();
+    for season in [season, Season::new(TEST_SEASON - 1)] {
+        for week in 1..=18 {
+            let week = Week::new(week);
+            let mut paths: Vec<_> = (0..500)
+                .map(|player| {
+                    WeeklyStatsCacheKey {
+                        league_id,
+                        player_id: PlayerId::new(player),
+                        season,
+                        week,
+                    }
+                    .to_file_path()
+                })
+                .collect();
+            paths.extend([false, true].map(|projected| {
+                PlayerDataCacheKey {
+                    league_id,
+                    season,
+                    week,
+                    player_names: None,
+                    positions: None,
+                    projected,
+                    injury_status: None,
+                    roster_status: None,
+                    fantasy_team_filter: None,
+                    nfl_team: None,
+                }
+                .to_file_path()
+            }));
+            paths.iter().cloned().for_each(seed);
+            if season.as_u16() == TEST_SEASON {
+                stale.extend(paths);
+            } else {
+                kept.extend(paths);
+            }
+        }
+    }
+
+    // update-all-data's write pattern: a connection per week and source
+    for week in 1..=18 {
+        for projected in [false, true] {
+            let mut db = PlayerDatabase::new().unwrap();
+            for player in 0..50 {
+                db.upsert_player(&Player {
+                    player_id: PlayerId::new(player),
+                    name: format!("Player {}", player),
+                    position: "WR".to_string(),
+                    team: None,
+                })
+                .unwrap();
+                let points = Some(player as f64);
+                let (projected, actual) = if projected {
+                    (points, None)
+                } else {
+                    (None, points)
+                };
+                let stats = PlayerWeeklyStats::test_minimal(
+                    PlayerId::new(player),
+                    season,
+                    Week::new(week),
+                    projected,
+                    actual,
+                );
+                db.upsert_weekly_stats(&stats, true).unwrap();
+            }
+        }
+    }
+    // Writing touched no files; they all go in one pass at the end
+    assert!(stale.iter().all(|path| path.exists()));
+
+    let started = Instant::now();
+    let removed = GLOBAL_CACHE.flush_invalidations();
+    let elapsed = started.elapsed();
+    assert_eq!(removed, stale.len());
+    assert!(
+        elapsed < INVALIDATION_BUDGET,
+        "flushing took {:?} for {} files",
+        elapsed,
+        removed
+    );
+    assert!(stale.iter().all(|path| !path.exists()));
+    assert!(kept.iter().all(|path| path.exists()));
+    assert_eq!(GLOBAL_CACHE.flush_invalidations(), 0);
+}
+
+#[tokio::test]
+async fn test_no_cache_write_leaves_read_only_cache_dir_untouched() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+    let cache_dir = mock.cache_dir.path();
+    std::fs::set_permissions(cache_dir, std::fs::Permissions::from_mode(0o555)).unwrap();
+    std::env::set_var("ESPN_FFL_NO_CACHE_WRITE", "1");
+
+    let result = handle_league_data(
+        Some(LeagueId::new(TEST_LEAGUE_ID)),
+        true,
+        Season::new(TEST_SEASON),
+        true,
+    )
+    .await;
+
+    std::env::remove_var("ESPN_FFL_NO_CACHE_WRITE");
+    std::fs::set_permissions(cache_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+    result.unwrap();
+    assert_eq!(std::fs::read_dir(cache_dir).unwrap().count(), 0);
+}
+
+#[tokio::test]
+async fn test_cache_write_failure_is_not_fatal() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+
+    // A regular file where the cache directory should be makes every write fail
+    let not_a_dir = mock.cache_dir.path().join("cache-file");
+    std::fs::write(&not_a_dir, "").unwrap();
+    std::env::set_var("XDG_CACHE_HOME", &not_a_dir);
+
+    handle_league_data(
+        Some(LeagueId::new(TEST_LEAGUE_ID)),
+        true,
+        Season::new(TEST_SEASON),
+        false,
+    )
+    .await
+    .unwrap();
+    assert_eq!(std::fs::read_to_string(&not_a_dir).unwrap(), "");
+}
+
+#[tokio::test]
+async fn test_commands_without_stored_data_create_no_database() {
+    let mock = MockEspn::start().await;
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+    let season = Season::new(TEST_SEASON);
+    let league_id = LeagueId::new(TEST_LEAGUE_ID);
+
+    handle_league_data(Some(league_id), false, season, false)
+        .await
+        .unwrap();
+    // Second run is served from the settings cache
+    handle_league_data(Some(league_id), false, season, true)
+        .await
+        .unwrap();
+    handle_category_leaders(CategoryLeadersParams {
+        category: "receptions".to_string(),
+        league_id: Some(league_id),
+        season,
+        week: None,
+        positions: None,
+        roster_status: None,
+        top: 10,
+        as_json: true,
+    })
+    .unwrap();
+    handle_db_coverage(Some(league_id), season, 18, 0.9, true).unwrap();
+    handle_db_team_names(Some(league_id), season, None, true).unwrap();
+    handle_note_list(None, true).unwrap();
+
+    let db_path = mock.cache_dir.path().join("espn-ffl").join("players.db");
+    assert!(!db_path.exists());
+}
+
+#[tokio::test]
+async fn test_validate_season_against_mocked_metadata() {
+    let mock = MockEspn::start().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+    mock.mock_league_status(&[2022, 2023, 2024]).await;
+    let league_id = LeagueId::new(TEST_LEAGUE_ID);
+
+    // A season the league existed in
+    validate_season(league_id, Season::new(2023)).await.unwrap();
+
+    // Past ESPN's latest season
+    match validate_season(league_id, Season::new(2026)).await {
+        Err(EspnError::InvalidSeason {
+            requested,
+            latest_available,
+        }) => {
+            assert_eq!(requested, 2026);
+            assert_eq!(latest_available, TEST_SEASON);
+        }
+        other => panic!("expected InvalidSeason, got {:?}", other),
+    }
+
+    // Before the league was created
+    match validate_season(league_id, Season::new(2019)).await {
+        Err(EspnError::LeagueSeasonNotFound {
+            season,
+            first_season,
+            ..
+        }) => {
+            assert_eq!(season, 2019);
+            assert_eq!(first_season, 2022);
+        }
+        other => panic!("expected LeagueSeasonNotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_update_all_data_fails_fast_on_future_season() {
+    let mock = MockEspn::start().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+
+    let result = handle_update_all_data(
+        Season::new(2027),
+        WeekRange::all(),
+        Some(LeagueId::new(TEST_LEAGUE_ID)),
+        false,
+        false,
+        true,
+    )
+    .await;
+    assert!(matches!(result, Err(EspnError::InvalidSeason { .. })));
+
+    // Only the metadata endpoint was hit; no weekly fetches were attempted
+    let requests = mock.server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].url.path(), "/");
+}
+
+#[tokio::test]
+async fn test_update_all_data_continues_past_failed_week() {
+    let mock = MockEspn::start().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+    mock.mock_league_status(&[]).await;
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+    mock.mock_league_view("mRoster", roster_fixture()).await;
+    for week in [1, 3] {
+        Mock::given(method("GET"))
+            .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+            .and(query_param("scoringPeriodId", week.to_string()))
+            .respond_with(ResponseTemplate::new(200).set_body_json(players_fixture(week)))
+            .mount(&mock.server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+        .and(query_param("scoringPeriodId", "2"))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock.server)
+        .await;
+
+    let result = handle_update_all_data(
+        Season::new(TEST_SEASON),
+        WeekRange::through(Week::new(3)),
+        Some(LeagueId::new(TEST_LEAGUE_ID)),
+        false,
+        false,
+        true,
+    )
+    .await;
+
+    let failures = match result {
+        Err(EspnError::UpdateFailed { failures }) => failures,
+        other => panic!("expected UpdateFailed, got {:?}", other),
+    };
+    let failed: Vec<(u16, &str)> = failures
+        .iter()
+        .map(|f| (f.week.as_u16(), f.source))
+        .collect();
+    assert_eq!(failed, vec![(2, "actual"), (2, "projected")]);
+    assert!(failures.iter().all(|f| f.kind == "HTTP 500"));
+
+    // The weeks around the failure were still written
+    let db = PlayerDatabase::new().unwrap();
+    for week in [1, 3] {
+        let qb = db
+            .get_weekly_stats(
+                LeagueId::new(TEST_LEAGUE_ID),
+                PlayerId::new(100),
+                Season::new(TEST_SEASON),
+                Week::new(week),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(qb.actual_points, Some(20.0));
+        assert!(qb.projected_points.is_some());
+    }
+    assert!(db
+        .get_weekly_stats(
+            LeagueId::new(TEST_LEAGUE_ID),
+            PlayerId::new(100),
+            Season::new(TEST_SEASON),
+            Week::new(2)
+        )
+        .unwrap()
+        .is_none());
+}
+
+#[tokio::test]
+async fn test_update_all_data_fail_fast_stops_at_first_failure() {
+    let mock = MockEspn::start().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+    mock.mock_league_status(&[]).await;
+    mock.mock_league_view("mSettings", settings_fixture()).await;
+    mock.mock_league_view("mRoster", roster_fixture()).await;
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}/players", TEST_SEASON)))
+        .respond_with(ResponseTemplate::new(500))
+        .mount(&mock.server)
+        .await;
+
+    let result = handle_update_all_data(
+        Season::new(TEST_SEASON),
+        WeekRange::through(Week::new(3)),
+        Some(LeagueId::new(TEST_LEAGUE_ID)),
+        false,
+        true,
+        true,
+    )
+    .await;
+    assert!(matches!(result, Err(EspnError::HttpRequest { .. })));
+
+    let player_requests = mock
+        .server
+        .received_requests()
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|r| r.url.path().ends_with("/players"))
+        .count();
+    assert_eq!(player_requests, 1);
+}
+
+#[tokio::test]
+async fn test_warm_cache_fetches_once_then_reports_fresh() {
+    let mock = MockEspn::start().await;
+    GLOBAL_CACHE.clear_all_memory();
+    mock.mock_defaults().await;
+    mock.mock_game_metadata(TEST_SEASON).await;
+    mock.mock_league_status(&[]).await;
+    mock.mock_league_view("mMatchupScore", json!({ "teams": [], "schedule": [] }))
+        .await;
+    Mock::given(method("GET"))
+        .and(path(format!("/seasons/{}", TEST_SEASON)))
+        .and(query_param("view", "proTeamSchedules_wl"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(json!({ "settings": { "proTeams": [] } })),
+        )
+        .mount(&mock.server)
+        .await;
+
+    let params = WarmCacheParams {
+        league_id: Some(LeagueId::new(TEST_LEAGUE_ID)),
+        season: Season::new(TEST_SEASON),
+        refresh: false,
+        check_coverage: true,
+        as_json: true,
+    };
+    let statuses = |report: &espn_ffl::commands::warm_cache::WarmCacheReport| {
+        report
+            .items
+            .iter()
+            .map(|item| (item.item, item.status))
+            .collect::<Vec<_>>()
+    };
+
+    let first = warm_cache(&params).await.unwrap();
+    assert_eq!(first.week, Week::new(5));
+    assert_eq!(first.failed(), 0);
+    assert!(
+        first
+            .items
+            .iter()
+            .all(|item| item.status == WarmStatus::Fetched),
+        "{:?}",
+        statuses(&first)
+    );
+
+    // Nothing is stored yet, so every earlier week is a gap with a follow-up command
+    assert_eq!(first.coverage_gaps.as_ref().unwrap().len(), 8);
+    assert_eq!(first.follow_up.len(), 4);
+    assert_eq!(
+        first.follow_up[0],
+        format!(
+            "espn-ffl player-data --league-id {} --season {} --week 1 --both --refresh",
+            TEST_LEAGUE_ID, TEST_SEASON
+        )
+    );
+
+    let second = warm_cache(&params).await.unwrap();
+    assert!(
+        second
+            .items
+            .iter()
+            .all(|item| item.status == WarmStatus::Fresh),
+        "{:?}",
+        statuses(&second)
+    );
+}
@@ -1,6 +1,12 @@
 //! Unit tests for storage functionality
 
-use espn_ffl::{storage::*, PlayerId, Season, Week};
+use espn_ffl::{
+    storage::{analysis::BiasWeighting, *},
+    LeagueId, PlayerId, Season, Week,
+};
+
+/// League the cached player data tests store their rows for
+const TEST_LEAGUE: LeagueId = LeagueId(12345);
 
 fn create_test_db() -> PlayerDatabase {
     PlayerDatabase::new_in_memory().unwrap()
@@ -168,7 +174,12 @@ fn test_get_weekly_stats_existing() {
     db.upsert_weekly_stats(&stats, false).unwrap();
 
     let retrieved = db
-        .get_weekly_stats(PlayerId::new(12346), Season::new(2023), Week::new(2))
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12346),
+            Season::new(2023),
+            Week::new(2),
+        )
         .unwrap();
 
     assert!(retrieved.is_some());
@@ -185,7 +196,12 @@ fn test_get_weekly_stats_nonexistent() {
     let db = create_test_db();
 
     let result = db
-        .get_weekly_stats(PlayerId::new(99999), Season::new(2023), Week::new(1))
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(99999),
+            Season::new(2023),
+            Week::new(1),
+        )
         .unwrap();
 
     assert!(result.is_none());
@@ -210,7 +226,7 @@ fn test_get_player_season_stats() {
     }
 
     let season_stats = db
-        .get_player_season_stats(PlayerId::new(12345), Season::new(2023))
+        .get_player_season_stats(TEST_LEAGUE, PlayerId::new(12345), Season::new(2023))
         .unwrap();
 
     assert_eq!(season_stats.len(), 5);
@@ -226,7 +242,7 @@ fn test_get_projection_analysis_no_data() {
     let db = create_test_db();
 
     let analysis = db
-        .get_projection_analysis(Season::new(2023), None, Some(10))
+        .get_projection_analysis(TEST_LEAGUE, Season::new(2023), None, Some(10), false)
         .unwrap();
 
     assert!(analysis.is_empty());
@@ -247,20 +263,23 @@ fn test_get_projection_analysis_with_data() {
 
     // Insert some weekly stats with projection errors
     for week in 1..=5 {
-        let stats = PlayerWeeklyStats::test_with_fields(
-            PlayerId::new(12345),
-            Season::new(2023),
-            Week::new(week),
-            Some(20.0), // Consistently overestimated
-            Some(15.0),
-            0,
-            0,
-        );
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(week),
+                Some(20.0), // Consistently overestimated
+                Some(15.0),
+                0,
+                0,
+            )
+        };
         db.upsert_weekly_stats(&stats, false).unwrap();
     }
 
     let analysis = db
-        .get_projection_analysis(Season::new(2023), None, Some(10))
+        .get_projection_analysis(TEST_LEAGUE, Season::new(2023), None, Some(10), false)
         .unwrap();
 
     assert_eq!(analysis.len(), 1);
@@ -271,6 +290,62 @@ fn test_get_projection_analysis_with_data() {
     assert!((player_analysis.avg_error - 5.0).abs() < 0.01); // 20.0 - 15.0 = 5.0 error
 }
 
+#[test]
+fn test_projection_analysis_is_kept_per_league() {
+    let mut db = create_test_db_with_player();
+    let other_league = LeagueId::new(67890);
+
+    // Same player and weeks, scored differently in each league
+    for (league_id, actual) in [(TEST_LEAGUE, 15.0), (other_league, 25.0)] {
+        for week in 1..=4 {
+            let stats = PlayerWeeklyStats {
+                league_id: Some(league_id),
+                ..PlayerWeeklyStats::test_with_fields(
+                    PlayerId::new(12345),
+                    Season::new(2023),
+                    Week::new(week),
+                    Some(20.0),
+                    Some(actual),
+                    0,
+                    0,
+                )
+            };
+            db.upsert_weekly_stats(&stats, false).unwrap();
+        }
+    }
+
+    for (league_id, avg_error) in [(TEST_LEAGUE, 5.0), (other_league, -5.0)] {
+        let analysis = db
+            .get_projection_analysis(league_id, Season::new(2023), None, None, false)
+            .unwrap();
+        assert_eq!(analysis.len(), 1);
+        assert_eq!(analysis[0].games_count, 4);
+        assert!((analysis[0].avg_error - avg_error).abs() < 0.01);
+    }
+
+    let projected_data = vec![(PlayerId::new(12345), 20.0)];
+    let estimate_in = |league_id| {
+        db.estimate_week_performance(
+            league_id,
+            Season::new(2023),
+            Week::new(5),
+            &projected_data,
+            BiasWeighting::new(1.0),
+            false,
+        )
+        .unwrap()
+        .remove(0)
+    };
+    assert!(estimate_in(TEST_LEAGUE).estimated_points < 20.0);
+    assert!(estimate_in(other_league).estimated_points > 20.0);
+
+    // A league with nothing stored gets no analysis at all
+    assert!(db
+        .get_projection_analysis(LeagueId::new(1), Season::new(2023), None, None, false)
+        .unwrap()
+        .is_empty());
+}
+
 #[test]
 fn test_estimate_week_performance_no_data() {
     let db = create_test_db();
@@ -279,11 +354,12 @@ fn test_estimate_week_performance_no_data() {
 
     let estimates = db
         .estimate_week_performance(
+            TEST_LEAGUE,
             Season::new(2023),
             Week::new(5),
             &projected_data,
-            Some(10),
-            1.0,
+            BiasWeighting::new(1.0),
+            false,
         )
         .unwrap();
 
@@ -310,15 +386,18 @@ fn test_estimate_week_performance_with_bias() {
 
     // Insert historical data showing consistent overestimation
     for week in 1..=4 {
-        let stats = PlayerWeeklyStats::test_with_fields(
-            PlayerId::new(12345),
-            Season::new(2023),
-            Week::new(week),
-            Some(20.0), // ESPN consistently projects 20
-            Some(15.0), // Player consistently scores 15
-            0,
-            0,
-        );
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(week),
+                Some(20.0), // ESPN consistently projects 20
+                Some(15.0), // Player consistently scores 15
+                0,
+                0,
+            )
+        };
         db.upsert_weekly_stats(&stats, false).unwrap();
     }
 
@@ -328,11 +407,12 @@ fn test_estimate_week_performance_with_bias() {
 
     let estimates = db
         .estimate_week_performance(
+            TEST_LEAGUE,
             Season::new(2023),
             Week::new(5), // Estimate for week 5 based on weeks 1-4
             &projected_data,
-            Some(10),
-            1.0,
+            BiasWeighting::new(1.0),
+            false,
         )
         .unwrap();
 
@@ -346,6 +426,210 @@ fn test_estimate_week_performance_with_bias() {
     assert!(estimate.reasoning.contains("overestimates"));
 }
 
+#[test]
+fn test_estimate_week_performance_clamps_runaway_adjustment() {
+    let mut db = create_test_db();
+
+    let kicker = Player {
+        player_id: PlayerId::new(3001),
+        name: "Test Kicker".to_string(),
+        position: "K".to_string(),
+        team: None,
+    };
+    db.upsert_player(&kicker).unwrap();
+    let qb = Player {
+        player_id: PlayerId::new(3002),
+        name: "Test QB".to_string(),
+        position: "QB".to_string(),
+        team: None,
+    };
+    db.upsert_player(&qb).unwrap();
+
+    for week in 1..=4 {
+        // Kicker consistently underestimated by 8, QB overestimated by 8
+        let kicker_stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(3001),
+                Season::new(2023),
+                Week::new(week),
+                Some(8.0),
+                Some(16.0),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&kicker_stats, false).unwrap();
+        let qb_stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(3002),
+                Season::new(2023),
+                Week::new(week),
+                Some(20.0),
+                Some(12.0),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&qb_stats, false).unwrap();
+    }
+
+    let projected_data = vec![(PlayerId::new(3001), 8.0), (PlayerId::new(3002), 20.0)];
+    let estimates = db
+        .estimate_week_performance(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(5),
+            &projected_data,
+            BiasWeighting::new(20.0),
+            false,
+        )
+        .unwrap();
+
+    // 16 pts is the best kicker week on record, so the ceiling is 16 * 1.25
+    let kicker = estimates
+        .iter()
+        .find(|e| e.player_id == PlayerId::new(3001))
+        .unwrap();
+    assert!((kicker.estimated_points - 20.0).abs() < 0.01);
+    assert!(kicker.reasoning.contains("capped at 20.0 pts"));
+
+    let qb = estimates
+        .iter()
+        .find(|e| e.player_id == PlayerId::new(3002))
+        .unwrap();
+    assert_eq!(qb.estimated_points, 0.0);
+    assert!(qb.reasoning.contains("floored at 0 pts"));
+}
+
+#[test]
+fn test_merge_weekly_stats_keeps_other_source() {
+    let mut db = create_test_db_with_player();
+
+    let actual = PlayerWeeklyStats::test_with_fields(
+        PlayerId::new(12345),
+        Season::new(2023),
+        Week::new(2),
+        None,
+        Some(21.0),
+        0,
+        0,
+    );
+    db.merge_weekly_stats(&actual).unwrap();
+
+    let projected = PlayerWeeklyStats::test_with_fields(
+        PlayerId::new(12345),
+        Season::new(2023),
+        Week::new(2),
+        Some(17.5),
+        None,
+        0,
+        0,
+    );
+    db.merge_weekly_stats(&projected).unwrap();
+
+    let stored = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(2),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.projected_points, Some(17.5));
+    assert_eq!(stored.actual_points, Some(21.0));
+}
+
+#[test]
+fn test_merge_weekly_stats_batch_matches_single_merges() {
+    let mut db = create_test_db_with_player();
+    let week_stats = |week: u16, projected: Option<f64>, actual: Option<f64>| {
+        PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(week),
+            projected,
+            actual,
+            0,
+            0,
+        )
+    };
+    db.merge_weekly_stats(&week_stats(4, Some(12.0), None))
+        .unwrap();
+
+    let written = db
+        .merge_weekly_stats_batch(&[
+            week_stats(4, None, Some(9.5)),
+            week_stats(5, Some(14.0), None),
+        ])
+        .unwrap();
+    assert_eq!(written, 2);
+
+    let stored = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(4),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.projected_points, Some(12.0));
+    assert_eq!(stored.actual_points, Some(9.5));
+    let stored = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(5),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.projected_points, Some(14.0));
+}
+
+#[test]
+fn test_ownership_survives_roster_merge() {
+    let mut db = create_test_db_with_player();
+
+    let mut fetched = PlayerWeeklyStats::test_minimal(
+        PlayerId::new(12345),
+        Season::new(2023),
+        Week::new(3),
+        Some(15.0),
+        None,
+    );
+    fetched.percent_owned = Some(97.5);
+    fetched.percent_started = Some(81.2);
+    db.upsert_weekly_stats(&fetched, true).unwrap();
+
+    // A roster-only update carries no ownership and must not erase it
+    let mut roster_update = PlayerWeeklyStats::test_minimal(
+        PlayerId::new(12345),
+        Season::new(2023),
+        Week::new(3),
+        None,
+        None,
+    );
+    roster_update.is_rostered = Some(true);
+    db.merge_weekly_stats(&roster_update).unwrap();
+
+    let stored = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(3),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.percent_owned, Some(97.5));
+    assert_eq!(stored.percent_started, Some(81.2));
+    assert_eq!(stored.is_rostered, Some(true));
+}
+
 // Note: test_clear_all_data was removed because with the unified caching system,
 // clearing the database doesn't clear the cache. This behavior is by design
 // since the cache provides persistence and performance benefits.
@@ -370,6 +654,7 @@ fn test_get_cached_player_data_with_injury_and_roster_status() {
         player_id: PlayerId::new(12348), // Use unique ID for cached_player_data test
         season: Season::new(2023),
         week: Week::new(4), // Use unique week for cached_player_data test
+        league_id: Some(TEST_LEAGUE),
         projected_points: None,
         actual_points: Some(25.5),
         active: Some(false),
@@ -378,6 +663,11 @@ fn test_get_cached_player_data_with_injury_and_roster_status() {
         is_rostered: Some(true),
         fantasy_team_id: Some(42),
         fantasy_team_name: Some("Test Team".to_string()),
+        played: Some(true),
+        percent_owned: None,
+        percent_started: None,
+        opponent_team: Some("KC".to_string()),
+        home_game: Some(false),
         created_at: 1234567890,
         updated_at: 1234567890,
     };
@@ -385,7 +675,9 @@ fn test_get_cached_player_data_with_injury_and_roster_status() {
 
     // Get cached data
     let params = espn_ffl::commands::common::CommandParams::new(Season::new(2023), Week::new(4));
-    let cached_data = db.get_cached_player_data(&params, false).unwrap();
+    let cached_data = db
+        .get_cached_player_data(&params, TEST_LEAGUE, false)
+        .unwrap();
 
     // Should have one result
     assert_eq!(cached_data.len(), 1);
@@ -401,6 +693,8 @@ fn test_get_cached_player_data_with_injury_and_roster_status() {
         is_rostered,
         team_id,
         team_name,
+        played,
+        matchup,
     ) = &cached_data[0];
 
     // Verify all fields are correctly returned
@@ -414,6 +708,124 @@ fn test_get_cached_player_data_with_injury_and_roster_status() {
     assert_eq!(*is_rostered, Some(true));
     assert_eq!(*team_id, Some(42));
     assert_eq!(team_name, &Some("Test Team".to_string()));
+    assert_eq!(*played, Some(true));
+    assert_eq!(matchup, &(Some("KC".to_string()), Some(false)));
+}
+
+#[test]
+fn test_get_cached_player_data_status_filters_in_sql() {
+    use espn_ffl::{
+        cli::types::filters::{FantasyTeamFilter, InjuryStatusFilter, RosterStatusFilter},
+        commands::common::CommandParams,
+        espn::types::InjuryStatus,
+    };
+
+    let mut db = create_test_db();
+
+    // (id, injured, injury status, rostered, fantasy team)
+    let rows = [
+        (
+            5001,
+            Some(true),
+            Some(InjuryStatus::Questionable),
+            Some(true),
+            Some((1, "Alpha Squad")),
+        ),
+        (
+            5002,
+            Some(false),
+            Some(InjuryStatus::Active),
+            Some(false),
+            None,
+        ),
+        (5003, Some(false), None, Some(false), None),
+        (5004, None, Some(InjuryStatus::Out), None, None),
+    ];
+    for (id, injured, injury_status, is_rostered, team) in rows {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: format!("Player {}", id),
+            position: "RB".to_string(),
+            team: None,
+        })
+        .unwrap();
+        let stats = PlayerWeeklyStats {
+            injured,
+            injury_status,
+            is_rostered,
+            fantasy_team_id: team.map(|(team_id, _)| team_id),
+            fantasy_team_name: team.map(|(_, name)| name.to_string()),
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(id),
+                Season::new(2023),
+                Week::new(6),
+                None,
+                Some(10.0),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let query_ids = |configure: &dyn Fn(&mut CommandParams)| {
+        let mut params = CommandParams::new(Season::new(2023), Week::new(6));
+        configure(&mut params);
+        let mut ids: Vec<i64> = db
+            .get_cached_player_data(&params, TEST_LEAGUE, false)
+            .unwrap()
+            .into_iter()
+            .map(|row| row.0.as_i64())
+            .collect();
+        ids.sort_unstable();
+        ids
+    };
+
+    // Free agents only; rows with unknown roster status are left out
+    assert_eq!(
+        query_ids(&|p| p.roster_status = Some(RosterStatusFilter::FA)),
+        vec![5002, 5003]
+    );
+    assert_eq!(
+        query_ids(&|p| p.roster_status = Some(RosterStatusFilter::Rostered)),
+        vec![5001]
+    );
+
+    assert_eq!(
+        query_ids(&|p| p.injury_status = Some(InjuryStatusFilter::Active)),
+        vec![5002, 5003]
+    );
+    assert_eq!(
+        query_ids(&|p| p.injury_status = Some(InjuryStatusFilter::Injured)),
+        vec![5001, 5004]
+    );
+    assert_eq!(
+        query_ids(&|p| p.injury_status = Some(InjuryStatusFilter::Out)),
+        vec![5004]
+    );
+
+    assert_eq!(
+        query_ids(&|p| p.fantasy_team_filter = Some(FantasyTeamFilter::Name("alpha".to_string()))),
+        vec![5001]
+    );
+    assert_eq!(
+        query_ids(&|p| p.fantasy_team_filter = Some(FantasyTeamFilter::Id(2))),
+        Vec::<i64>::new()
+    );
+    assert_eq!(
+        query_ids(&|p| p.fantasy_team_filter = Some(FantasyTeamFilter::Ids(vec![1, 2]))),
+        vec![5001]
+    );
+
+    // Filters combine
+    assert_eq!(
+        query_ids(&|p| {
+            p.roster_status = Some(RosterStatusFilter::FA);
+            p.injury_status = Some(InjuryStatusFilter::Active);
+        }),
+        vec![5002, 5003]
+    );
 }
 
 #[test]
@@ -421,40 +833,2734 @@ fn test_get_cached_player_data_filters_by_projected() {
     let mut db = create_test_db_with_player();
 
     // Add both projected and actual stats
-    let projected_stats = PlayerWeeklyStats::test_with_fields(
-        PlayerId::new(12345),
-        Season::new(2023),
-        Week::new(1),
-        Some(20.0), // projected
-        None,       // no actual
-        0,
-        0,
-    );
+    let projected_stats = PlayerWeeklyStats {
+        league_id: Some(TEST_LEAGUE),
+        ..PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(1),
+            Some(20.0), // projected
+            None,       // no actual
+            0,
+            0,
+        )
+    };
     db.upsert_weekly_stats(&projected_stats, false).unwrap();
 
-    let actual_stats = PlayerWeeklyStats::test_with_fields(
-        PlayerId::new(12345),
-        Season::new(2023),
-        Week::new(1),
-        Some(20.0), // keep projected
-        Some(18.5), // add actual
-        0,
-        0,
-    );
+    let actual_stats = PlayerWeeklyStats {
+        league_id: Some(TEST_LEAGUE),
+        ..PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(1),
+            Some(20.0), // keep projected
+            Some(18.5), // add actual
+            0,
+            0,
+        )
+    };
     db.upsert_weekly_stats(&actual_stats, true).unwrap(); // force update
 
     // Test projected filter
     let params = espn_ffl::commands::common::CommandParams::new(Season::new(2023), Week::new(1));
     let projected_data = db
-        .get_cached_player_data(&params, true) // projected = true
+        .get_cached_player_data(&params, TEST_LEAGUE, true) // projected = true
         .unwrap();
     assert_eq!(projected_data.len(), 1);
     assert_eq!(projected_data[0].3, 20.0); // Should return projected points
 
     // Test actual filter
     let actual_data = db
-        .get_cached_player_data(&params, false) // projected = false
+        .get_cached_player_data(&params, TEST_LEAGUE, false) // projected = false
         .unwrap();
     assert_eq!(actual_data.len(), 1);
     assert_eq!(actual_data[0].3, 18.5); // Should return actual points
 }
+
+#[test]
+fn test_weekly_stats_are_kept_per_league() {
+    let mut db = create_test_db_with_player();
+    let other_league = LeagueId::new(999);
+    // The same player-week, scored and rostered differently in two leagues
+    for (league_id, actual, is_rostered) in [(TEST_LEAGUE, 18.5, true), (other_league, 19.0, false)]
+    {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(league_id),
+            is_rostered: Some(is_rostered),
+            fantasy_team_id: is_rostered.then_some(3),
+            fantasy_team_name: is_rostered.then(|| "Team Alpha".to_string()),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(1),
+                Some(20.0),
+                Some(actual),
+                0,
+                0,
+            )
+        };
+        db.merge_weekly_stats(&stats).unwrap();
+    }
+
+    let params = espn_ffl::commands::common::CommandParams::new(Season::new(2023), Week::new(1));
+    let ours = db
+        .get_cached_player_data(&params, TEST_LEAGUE, false)
+        .unwrap();
+    assert_eq!(ours.len(), 1);
+    assert_eq!(ours[0].3, 18.5);
+    assert_eq!(ours[0].7, Some(true));
+    assert_eq!(ours[0].9.as_deref(), Some("Team Alpha"));
+    let theirs = db
+        .get_cached_player_data(&params, other_league, false)
+        .unwrap();
+    assert_eq!(theirs[0].3, 19.0);
+    assert_eq!(theirs[0].7, Some(false));
+
+    // Single-player lookups and the export see only the league asked for
+    for (league_id, actual) in [(TEST_LEAGUE, 18.5), (other_league, 19.0)] {
+        let week = db
+            .get_weekly_stats(
+                league_id,
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(1),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(week.actual_points, Some(actual));
+        let season = db
+            .get_player_season_stats(league_id, PlayerId::new(12345), Season::new(2023))
+            .unwrap();
+        assert_eq!(season.len(), 1);
+        let export = db.get_weekly_export(league_id, Season::new(2023)).unwrap();
+        assert_eq!(export.len(), 1);
+        assert_eq!(export[0].actual_points, Some(actual));
+    }
+
+    let has_data = |league_id| {
+        db.has_data_for_week(
+            Season::new(2023),
+            Week::new(1),
+            league_id,
+            None,
+            None,
+            Some(false),
+        )
+        .unwrap()
+    };
+    assert!(has_data(Some(other_league)));
+    assert!(!has_data(Some(LeagueId::new(777))));
+    assert!(has_data(None));
+}
+
+#[test]
+fn test_weekly_stats_from_before_leagues_are_migrated_and_adopted() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("players.db");
+    {
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE players (
+                 player_id INTEGER PRIMARY KEY, name TEXT NOT NULL,
+                 position TEXT NOT NULL, team TEXT
+             );
+             INSERT INTO players VALUES (12345, 'Test Player', 'QB', 'TEST');
+             CREATE TABLE player_weekly_stats (
+                 player_id INTEGER, season INTEGER, week INTEGER,
+                 projected_points REAL, actual_points REAL,
+                 created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL,
+                 PRIMARY KEY (player_id, season, week)
+             );
+             INSERT INTO player_weekly_stats VALUES
+                 (12345, 2023, 1, 20.0, 18.5, 1, 1),
+                 (12345, 2023, 2, 20.0, 25.0, 1, 1);",
+        )
+        .unwrap();
+    }
+
+    // The rows keep their points but no league
+    let mut db = PlayerDatabase::open(&path).unwrap();
+    let unassigned: u32 = rusqlite::Connection::open(&path)
+        .unwrap()
+        .query_row(
+            "SELECT COUNT(*) FROM player_weekly_stats WHERE league_id = 0",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap();
+    assert_eq!(unassigned, 2);
+
+    // The first league to read them takes them over
+    let week_1 = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(week_1.actual_points, Some(18.5));
+    assert_eq!(week_1.league_id, Some(TEST_LEAGUE));
+
+    // and merges into them when it stores the same week
+    let roster_update = PlayerWeeklyStats {
+        league_id: Some(TEST_LEAGUE),
+        is_rostered: Some(true),
+        ..PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(2),
+            None,
+            None,
+            0,
+            0,
+        )
+    };
+    db.merge_weekly_stats(&roster_update).unwrap();
+    let season = db
+        .get_player_season_stats(TEST_LEAGUE, PlayerId::new(12345), Season::new(2023))
+        .unwrap();
+    assert_eq!(season.len(), 2);
+    assert!(season.iter().all(|s| s.league_id == Some(TEST_LEAGUE)));
+    assert_eq!(season[1].actual_points, Some(25.0));
+    assert_eq!(season[1].is_rostered, Some(true));
+    drop(db);
+
+    // Reopening leaves the rebuilt table alone
+    let db = PlayerDatabase::open(&path).unwrap();
+    assert_eq!(
+        db.get_player_season_stats(TEST_LEAGUE, PlayerId::new(12345), Season::new(2023))
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_database_from_an_old_release_is_migrated_in_place() {
+    let dir = tempfile::TempDir::new().unwrap();
+    let path = dir.path().join("players.db");
+    {
+        // The weekly stats table as the first release left it, with no schema version
+        let conn = rusqlite::Connection::open(&path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE players (
+                 player_id INTEGER PRIMARY KEY, name TEXT NOT NULL,
+                 position TEXT NOT NULL, team TEXT
+             );
+             INSERT INTO players VALUES (12345, 'Test Player', 'QB', 'TEST');
+             CREATE TABLE player_weekly_stats (
+                 player_id INTEGER, season INTEGER, week INTEGER,
+                 projected_points REAL, actual_points REAL,
+                 created_at INTEGER NOT NULL, updated_at INTEGER NOT NULL,
+                 PRIMARY KEY (player_id, season, week),
+                 FOREIGN KEY (player_id) REFERENCES players(player_id)
+             );
+             INSERT INTO player_weekly_stats VALUES (12345, 2023, 1, 20.0, 18.5, 1, 2);",
+        )
+        .unwrap();
+    }
+
+    let schema_version = |path: &std::path::Path| -> u32 {
+        rusqlite::Connection::open(path)
+            .unwrap()
+            .query_row("SELECT version FROM schema_version", [], |row| row.get(0))
+            .unwrap()
+    };
+    let db = PlayerDatabase::open(&path).unwrap();
+    let stats = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(1),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stats.projected_points, Some(20.0));
+    assert_eq!(stats.actual_points, Some(18.5));
+    assert_eq!(stats.created_at, 1);
+    // Columns added since are unknown for the old row
+    assert_eq!(stats.injury_status, None);
+    assert_eq!(stats.is_rostered, None);
+    assert_eq!(stats.played, None);
+    assert_eq!(stats.percent_owned, None);
+    assert_eq!(stats.opponent_team, None);
+    assert_eq!(stats.league_id, Some(TEST_LEAGUE));
+    drop(db);
+    let migrated = schema_version(&path);
+    assert!(migrated > 0);
+
+    // A current database is left alone
+    let db = PlayerDatabase::open(&path).unwrap();
+    assert_eq!(schema_version(&path), migrated);
+    let analysis = db
+        .get_projection_analysis(TEST_LEAGUE, Season::new(2023), None, None, false)
+        .unwrap();
+    assert_eq!(analysis.len(), 1);
+    drop(db);
+
+    // and a new one starts at the same version
+    let new_path = dir.path().join("new.db");
+    PlayerDatabase::open(&new_path).unwrap();
+    assert_eq!(schema_version(&new_path), migrated);
+}
+
+fn create_matchup(
+    id: u32,
+    week: u16,
+    home: (u32, f64),
+    away: Option<(u32, f64)>,
+    winner: &str,
+) -> espn_ffl::espn::types::Matchup {
+    use espn_ffl::espn::types::{Matchup, MatchupSide};
+
+    Matchup {
+        id,
+        matchup_period_id: week,
+        home: Some(MatchupSide {
+            team_id: home.0,
+            total_points: Some(home.1),
+        }),
+        away: away.map(|(team_id, points)| MatchupSide {
+            team_id,
+            total_points: Some(points),
+        }),
+        winner: winner.to_string(),
+    }
+}
+
+#[test]
+fn test_backfill_defense_names() {
+    let mut db = create_test_db();
+    let stored = [
+        (-16002, "Player -16002", None),
+        (-16014, "Rams D/ST", None),
+        (-16033, "Ravens D/ST", Some("BAL")),
+    ];
+    for (id, name, team) in stored {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: "D/ST".to_string(),
+            team: team.map(str::to_string),
+        })
+        .unwrap();
+    }
+
+    assert_eq!(db.backfill_defense_names().unwrap(), 2);
+    let players = db.get_all_players().unwrap();
+    let find = |id: i64| {
+        players
+            .iter()
+            .find(|p| p.player_id == PlayerId::new(id))
+            .map(|p| (p.name.as_str(), p.team.as_deref()))
+    };
+    assert_eq!(find(-16002), Some(("Buffalo Bills D/ST", Some("BUF"))));
+    // A real name is kept; only the missing team is filled in
+    assert_eq!(find(-16014), Some(("Rams D/ST", Some("LAR"))));
+    assert_eq!(find(-16033), Some(("Ravens D/ST", Some("BAL"))));
+
+    // Nothing left to fix
+    assert_eq!(db.backfill_defense_names().unwrap(), 0);
+}
+
+#[test]
+fn test_get_season_records() {
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+
+    let matchups = vec![
+        create_matchup(1, 1, (1, 120.0), Some((2, 100.0)), "HOME"),
+        create_matchup(2, 1, (3, 90.0), Some((4, 150.5)), "AWAY"),
+        create_matchup(3, 2, (1, 110.0), Some((3, 109.5)), "HOME"),
+        create_matchup(4, 2, (2, 80.0), Some((4, 140.0)), "AWAY"),
+        // Week 3 is still in progress and must not produce records
+        create_matchup(5, 3, (1, 200.0), Some((4, 10.0)), "UNDECIDED"),
+        create_matchup(6, 3, (2, 0.0), Some((3, 0.0)), "UNDECIDED"),
+    ];
+    assert_eq!(
+        db.upsert_matchups(TEST_LEAGUE, season, &matchups).unwrap(),
+        6
+    );
+
+    let records = db.get_season_records(TEST_LEAGUE, season).unwrap();
+
+    assert_eq!(records.included_weeks, vec![Week::new(1), Week::new(2)]);
+    assert_eq!(records.excluded_weeks, vec![Week::new(3)]);
+
+    let highest = records.highest_score.unwrap();
+    assert_eq!((highest.team_id, highest.points), (4, 150.5));
+    let lowest = records.lowest_score.unwrap();
+    assert_eq!((lowest.team_id, lowest.points), (2, 80.0));
+
+    let blowout = records.biggest_blowout.unwrap();
+    assert_eq!(blowout.winner.team_id, 4);
+    assert_eq!(blowout.margin, 60.5);
+    let closest = records.closest_game.unwrap();
+    assert_eq!(closest.winner.team_id, 1);
+    assert_eq!(closest.margin, 0.5);
+
+    let streak = records.longest_win_streak.unwrap();
+    assert_eq!(streak.length, 2);
+    assert_eq!(streak.start_week, Week::new(1));
+    assert_eq!(streak.end_week, Week::new(2));
+
+    let team_one = records.teams.iter().find(|t| t.team_id == 1).unwrap();
+    assert_eq!((team_one.wins, team_one.losses, team_one.ties), (2, 0, 0));
+    assert_eq!(team_one.best_week.as_ref().unwrap().points, 120.0);
+    assert_eq!(team_one.worst_week.as_ref().unwrap().points, 110.0);
+}
+
+#[test]
+fn test_get_season_records_bye_and_tie() {
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+
+    let matchups = vec![
+        create_matchup(1, 1, (1, 100.0), Some((2, 100.0)), "TIE"),
+        create_matchup(2, 1, (3, 75.0), None, "HOME"),
+    ];
+    db.upsert_matchups(TEST_LEAGUE, season, &matchups).unwrap();
+
+    let records = db.get_season_records(TEST_LEAGUE, season).unwrap();
+    assert_eq!(records.included_weeks, vec![Week::new(1)]);
+    assert!(records.longest_win_streak.is_none());
+    assert!(records.biggest_blowout.is_none());
+
+    // Bye week scores still count toward high/low marks
+    assert_eq!(records.lowest_score.unwrap().team_id, 3);
+
+    let team_three = records.teams.iter().find(|t| t.team_id == 3).unwrap();
+    assert_eq!(
+        (team_three.wins, team_three.losses, team_three.ties),
+        (0, 0, 0)
+    );
+    let team_one = records.teams.iter().find(|t| t.team_id == 1).unwrap();
+    assert_eq!(team_one.ties, 1);
+}
+
+#[test]
+fn test_season_records_apply_team_names() {
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    db.upsert_matchups(
+        TEST_LEAGUE,
+        season,
+        &[create_matchup(1, 1, (1, 120.0), Some((2, 100.0)), "HOME")],
+    )
+    .unwrap();
+
+    let mut records = db.get_season_records(TEST_LEAGUE, season).unwrap();
+    let names =
+        std::collections::HashMap::from([(1, "Alpha".to_string()), (2, "Bravo".to_string())]);
+    records.apply_team_names(&names);
+
+    assert_eq!(
+        records.highest_score.unwrap().team_name.as_deref(),
+        Some("Alpha")
+    );
+    assert_eq!(
+        records.biggest_blowout.unwrap().loser.team_name.as_deref(),
+        Some("Bravo")
+    );
+    assert!(records.teams.iter().all(|t| t.team_name.is_some()));
+}
+
+#[test]
+fn test_season_records_with_two_week_championship() {
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let periods = std::collections::BTreeMap::from([(15, vec![15]), (16, vec![16, 17])]);
+    assert_eq!(
+        db.save_matchup_periods(TEST_LEAGUE, season, &periods)
+            .unwrap(),
+        3
+    );
+
+    // ESPN's championship totals cover both weeks
+    db.upsert_matchups(
+        TEST_LEAGUE,
+        season,
+        &[
+            create_matchup(1, 15, (1, 120.0), Some((2, 100.0)), "HOME"),
+            create_matchup(2, 16, (1, 230.5), Some((2, 241.0)), "AWAY"),
+        ],
+    )
+    .unwrap();
+
+    let records = db.get_season_records(TEST_LEAGUE, season).unwrap();
+    assert_eq!(records.included_weeks, vec![Week::new(15), Week::new(16)]);
+    assert_eq!(
+        records.multi_week_periods,
+        std::collections::BTreeMap::from([(16, vec![Week::new(16), Week::new(17)])])
+    );
+    let highest = records.highest_score.unwrap();
+    assert_eq!((highest.team_id, highest.points), (2, 241.0));
+    let team_two = records.teams.iter().find(|t| t.team_id == 2).unwrap();
+    assert_eq!((team_two.wins, team_two.losses), (1, 1));
+
+    // Saving again replaces the season's mapping
+    db.save_matchup_periods(TEST_LEAGUE, season, &std::collections::BTreeMap::new())
+        .unwrap();
+    assert!(db
+        .get_matchup_periods(TEST_LEAGUE, season)
+        .unwrap()
+        .is_empty());
+}
+
+fn create_estimate(id: i64, position: &str, estimated_points: f64) -> PerformanceEstimate {
+    PerformanceEstimate {
+        player_id: PlayerId::new(id),
+        name: format!("Player {}", id),
+        position: position.to_string(),
+        team: None,
+        espn_projection: estimated_points,
+        bias_adjustment: 0.0,
+        estimated_points,
+        confidence: 0.5,
+        reasoning: String::new(),
+        error_std: None,
+        dnp_weeks: None,
+        blend: None,
+        history: None,
+        percent_started: None,
+        tier: None,
+        locked: None,
+        vorp: None,
+    }
+}
+
+#[test]
+fn test_contrarian_estimates_compare_positional_ranks() {
+    use espn_ffl::storage::analysis::contrarian_estimates;
+
+    let with_starts = |id, position, points, started| PerformanceEstimate {
+        percent_started: started,
+        ..create_estimate(id, position, points)
+    };
+    let estimates = vec![
+        with_starts(1, "WR", 20.0, Some(95.0)),
+        with_starts(2, "WR", 18.0, Some(10.0)),
+        with_starts(3, "WR", 16.0, Some(80.0)),
+        with_starts(4, "WR", 14.0, Some(70.0)),
+        with_starts(5, "WR", 12.0, None),
+        with_starts(6, "QB", 25.0, Some(5.0)),
+        with_starts(7, "QB", 20.0, Some(90.0)),
+    ];
+
+    // Player 2 is 2nd by estimate but 4th of 4 by start rate; player 6 is 1st vs 2nd
+    let kept: Vec<i64> = contrarian_estimates(estimates.clone(), 2)
+        .iter()
+        .map(|e| e.player_id.as_i64())
+        .collect();
+    assert_eq!(kept, vec![2]);
+
+    let kept: Vec<i64> = contrarian_estimates(estimates.clone(), 1)
+        .iter()
+        .map(|e| e.player_id.as_i64())
+        .collect();
+    assert_eq!(kept, vec![2, 6]);
+
+    // No start rates at all: nothing to compare against
+    let no_data: Vec<_> = estimates
+        .into_iter()
+        .map(|e| PerformanceEstimate {
+            percent_started: None,
+            ..e
+        })
+        .collect();
+    assert!(contrarian_estimates(no_data, 1).is_empty());
+}
+
+#[test]
+fn test_performance_estimate_json_fields() {
+    let mut estimate = create_estimate(7, "WR", 12.5);
+    let json = serde_json::to_value(&estimate).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "player_id": 7,
+            "name": "Player 7",
+            "position": "WR",
+            "espn_projection": 12.5,
+            "bias_adjustment": 0.0,
+            "estimated_points": 12.5,
+            "confidence": 0.5,
+            "reasoning": ""
+        })
+    );
+
+    estimate.team = Some("BUF".to_string());
+    estimate.error_std = Some(3.0);
+    estimate.locked = Some(false);
+    let json = serde_json::to_value(&estimate).unwrap();
+    let mut keys: Vec<&str> = json
+        .as_object()
+        .unwrap()
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort_unstable();
+    assert_eq!(
+        keys,
+        vec![
+            "bias_adjustment",
+            "confidence",
+            "error_std",
+            "espn_projection",
+            "estimated_points",
+            "locked",
+            "name",
+            "player_id",
+            "position",
+            "reasoning",
+            "team",
+        ]
+    );
+
+    // Entries without the optional fields still read back
+    let read: PerformanceEstimate = serde_json::from_value(serde_json::json!({
+        "player_id": 7,
+        "name": "Player 7",
+        "position": "WR",
+        "espn_projection": 12.5,
+        "bias_adjustment": 0.0,
+        "estimated_points": 12.5,
+        "confidence": 0.5,
+        "reasoning": ""
+    }))
+    .unwrap();
+    assert_eq!(read.team, None);
+    assert_eq!(read.error_std, None);
+}
+
+#[test]
+fn test_group_estimates_by_position() {
+    use espn_ffl::storage::analysis::group_estimates_by_position;
+
+    // Already sorted like the flat view, including a tie between players 2 and 3
+    let estimates = vec![
+        create_estimate(1, "RB", 20.0),
+        create_estimate(2, "QB", 18.0),
+        create_estimate(3, "QB", 18.0),
+        create_estimate(4, "RB", 12.0),
+        create_estimate(5, "K", 8.0),
+        create_estimate(6, "QB", 10.0),
+    ];
+    let starters = std::collections::HashMap::from([("QB".to_string(), 2), ("RB".to_string(), 4)]);
+
+    let groups = group_estimates_by_position(estimates, &starters);
+    let positions: Vec<&str> = groups.iter().map(|g| g.position.as_str()).collect();
+    assert_eq!(positions, vec!["QB", "RB", "K"]);
+
+    let qb = &groups[0];
+    assert_eq!(qb.players.len(), 3);
+    assert!((qb.average_estimate - 46.0 / 3.0).abs() < 1e-9);
+    // Two starters, so the third QB is the replacement level
+    assert_eq!(qb.replacement_level, 10.0);
+    let ranked: Vec<(i64, u32)> = qb
+        .players
+        .iter()
+        .map(|p| (p.estimate.player_id.as_i64(), p.positional_rank))
+        .collect();
+    assert_eq!(ranked, vec![(2, 1), (3, 2), (6, 3)]);
+    assert_eq!(qb.players[0].points_above_replacement, 8.0);
+
+    // Fewer players than starting spots falls back to the last player
+    let rb = &groups[1];
+    assert_eq!(rb.replacement_level, 12.0);
+    assert_eq!(rb.players[0].points_above_replacement, 8.0);
+
+    // Unknown starter count uses the last player as well
+    let k = &groups[2];
+    assert_eq!(k.replacement_level, 8.0);
+    assert_eq!(k.players[0].positional_rank, 1);
+}
+
+#[test]
+fn test_replacement_levels_use_starter_cutoff() {
+    use espn_ffl::storage::analysis::replacement_levels;
+
+    // Unsorted on purpose: the cutoff is taken over players ordered by estimate
+    let estimates = vec![
+        create_estimate(1, "QB", 10.0),
+        create_estimate(2, "QB", 25.0),
+        create_estimate(3, "QB", 18.0),
+        create_estimate(4, "RB", 12.0),
+        create_estimate(5, "K", 8.0),
+        create_estimate(6, "K", 6.0),
+    ];
+    let starters = std::collections::HashMap::from([("QB".to_string(), 2), ("RB".to_string(), 4)]);
+
+    let levels = replacement_levels(&estimates, &starters);
+    assert_eq!(levels["QB"], 10.0);
+    // Too few players, or no starter count: the last player
+    assert_eq!(levels["RB"], 12.0);
+    assert_eq!(levels["K"], 6.0);
+}
+
+#[test]
+fn test_vorp_against_replacement_baselines() {
+    use espn_ffl::storage::analysis::{assign_vorp, replacement_levels, sort_by_vorp};
+
+    // A 1-QB, 2-RB league of two teams: QBs score more, but RBs are scarcer
+    let mut estimates = vec![
+        create_estimate(1, "QB", 24.0),
+        create_estimate(2, "QB", 22.0),
+        create_estimate(3, "QB", 20.0),
+        create_estimate(4, "RB", 18.0),
+        create_estimate(5, "RB", 15.0),
+        create_estimate(6, "RB", 12.0),
+        create_estimate(7, "RB", 10.0),
+        create_estimate(8, "RB", 6.0),
+        create_estimate(9, "P", 4.0),
+    ];
+    let starters = std::collections::HashMap::from([("QB".to_string(), 2), ("RB".to_string(), 4)]);
+
+    let levels = replacement_levels(&estimates, &starters);
+    assert_eq!(levels["QB"], 20.0);
+    assert_eq!(levels["RB"], 6.0);
+
+    assign_vorp(&mut estimates, &levels);
+    assert_eq!(estimates[0].vorp, Some(4.0));
+    assert_eq!(estimates[3].vorp, Some(12.0));
+    // The lone punter is its own baseline
+    assert_eq!(estimates[8].vorp, Some(0.0));
+
+    sort_by_vorp(&mut estimates);
+    let order: Vec<i64> = estimates.iter().map(|e| e.player_id.as_i64()).collect();
+    assert_eq!(order, vec![4, 5, 6, 1, 7, 2, 3, 8, 9]);
+    assert_eq!(serde_json::to_value(&estimates[0]).unwrap()["vorp"], 12.0);
+
+    // Estimates without a baseline go last
+    let mut estimates = vec![create_estimate(1, "K", 9.0), create_estimate(2, "WR", 5.0)];
+    assign_vorp(
+        &mut estimates,
+        &std::collections::HashMap::from([("WR".to_string(), 4.0)]),
+    );
+    sort_by_vorp(&mut estimates);
+    assert_eq!(estimates[0].player_id, PlayerId::new(2));
+    assert_eq!(estimates[1].vorp, None);
+}
+
+#[test]
+fn test_start_tiers_against_replacement_level() {
+    use espn_ffl::storage::analysis::{
+        assign_start_tiers, estimate_floor, start_tier, TierThresholds,
+    };
+
+    let thresholds = TierThresholds::default();
+    let tier = |estimate: &PerformanceEstimate| start_tier(estimate, 10.0, &thresholds);
+    let with_std = |points, std: f64| PerformanceEstimate {
+        error_std: Some(std),
+        ..create_estimate(1, "WR", points)
+    };
+
+    // 1.3x replacement with a steady floor
+    assert_eq!(tier(&with_std(14.0, 2.0)), StartTier::MustStart);
+    // Same estimate, but boom-or-bust: the floor drags it down a tier
+    assert!(estimate_floor(&with_std(14.0, 8.0)) < 6.0);
+    assert_eq!(tier(&with_std(14.0, 8.0)), StartTier::SolidStart);
+    assert_eq!(tier(&with_std(11.0, 1.0)), StartTier::SolidStart);
+    assert_eq!(tier(&with_std(9.5, 1.0)), StartTier::FlexConsideration);
+    assert_eq!(tier(&with_std(8.0, 1.0)), StartTier::Sit);
+
+    // Without error history the floor uses a share of the estimate
+    assert_eq!(tier(&create_estimate(1, "WR", 20.0)), StartTier::MustStart);
+
+    // The crowd can hold back a must start, but only when ESPN supplied a start rate
+    let benched_by_crowd = PerformanceEstimate {
+        percent_started: Some(20.0),
+        ..with_std(14.0, 2.0)
+    };
+    assert_eq!(tier(&benched_by_crowd), StartTier::SolidStart);
+    let no_crowd_check = TierThresholds {
+        must_start_min_started: None,
+        ..thresholds
+    };
+    assert_eq!(
+        start_tier(&benched_by_crowd, 10.0, &no_crowd_check),
+        StartTier::MustStart
+    );
+
+    // Positions without a replacement level stay untiered
+    let mut estimates = vec![create_estimate(1, "WR", 14.0), create_estimate(2, "P", 5.0)];
+    let levels = std::collections::HashMap::from([("WR".to_string(), 12.0)]);
+    assign_start_tiers(&mut estimates, &levels, &thresholds);
+    assert_eq!(estimates[0].tier, Some(StartTier::SolidStart));
+    assert_eq!(estimates[1].tier, None);
+
+    let json = serde_json::to_value(&estimates[0]).unwrap();
+    assert_eq!(json["tier"], "solid_start");
+    assert!(serde_json::to_value(&estimates[1])
+        .unwrap()
+        .get("tier")
+        .is_none());
+}
+
+#[test]
+fn test_get_recent_actual_points_and_position_bounds() {
+    let mut db = create_test_db_with_player();
+
+    // Weeks 2-4 have actuals, week 5 only a projection
+    for (week, actual) in [(2, Some(12.0)), (3, Some(25.0)), (4, Some(8.0)), (5, None)] {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(week),
+                Some(15.0),
+                actual,
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let recent = db
+        .get_recent_actual_points(
+            TEST_LEAGUE,
+            &[PlayerId::new(12345), PlayerId::new(99999)],
+            Season::new(2023),
+            Week::new(5),
+            4,
+        )
+        .unwrap();
+
+    // Window is weeks 2-5, oldest first; players without rows are omitted
+    assert_eq!(recent.len(), 1);
+    assert_eq!(
+        recent[&PlayerId::new(12345)],
+        vec![Some(12.0), Some(25.0), Some(8.0), None]
+    );
+
+    // Window is clamped to week 1
+    let early = db
+        .get_recent_actual_points(
+            TEST_LEAGUE,
+            &[PlayerId::new(12345)],
+            Season::new(2023),
+            Week::new(2),
+            8,
+        )
+        .unwrap();
+    assert_eq!(early[&PlayerId::new(12345)], vec![None, Some(12.0)]);
+
+    let bounds = db
+        .get_position_point_bounds(TEST_LEAGUE, Season::new(2023))
+        .unwrap();
+    assert_eq!(bounds.get("QB"), Some(&(8.0, 25.0)));
+    assert!(db
+        .get_position_point_bounds(TEST_LEAGUE, Season::new(2024))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_weighted_bias_decays_older_weeks() {
+    use espn_ffl::storage::analysis::weighted_bias;
+
+    // Errors 4, 2, -2, 6 in weeks 1-4, estimated for week 5
+    let errors: Vec<(Week, f64)> = [4.0, 2.0, -2.0, 6.0]
+        .into_iter()
+        .enumerate()
+        .map(|(i, error)| (Week::new(i as u16 + 1), error))
+        .collect();
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    // Uniform: plain mean and sample standard deviation
+    let uniform = weighted_bias(&errors, Week::new(5), 1.0).unwrap();
+    assert_eq!(uniform.mean, 2.5);
+    assert!(close(uniform.std, (35.0_f64 / 3.0).sqrt()));
+    assert_eq!(uniform.effective_games, 4.0);
+
+    // Weights 1/16, 1/8, 1/4, 1/2: mean 3.0 / 0.9375 = 3.2, effective size
+    // 0.9375² / 0.33203125 = 45/17, spread 10.9 / (0.9375 - 0.33203125 / 0.9375)
+    let decayed = weighted_bias(&errors, Week::new(5), 0.5).unwrap();
+    assert!(close(decayed.mean, 3.2));
+    assert!(close(decayed.effective_games, 45.0 / 17.0));
+    assert!(close(decayed.std, (10.9_f64 * 12.0 / 7.0).sqrt()));
+
+    assert_eq!(weighted_bias(&[], Week::new(5), 0.5), None);
+}
+
+#[test]
+fn test_bias_decay_weights_recent_weeks_in_estimates() {
+    let mut db = create_test_db_with_player();
+    for (week, actual) in [(1, 16.0), (2, 18.0), (3, 22.0), (4, 14.0)] {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(week),
+                Some(20.0),
+                Some(actual),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    let estimate = |decay: f64| {
+        db.estimate_week_performance(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(5),
+            &[(PlayerId::new(12345), 20.0)],
+            BiasWeighting {
+                strength: 1.0,
+                decay,
+            },
+            false,
+        )
+        .unwrap()
+        .remove(0)
+    };
+
+    // Uniform: 2.5 bias scaled by 4 / (4 + 2)
+    let uniform = estimate(1.0);
+    assert!((uniform.bias_adjustment + 2.5 * 4.0 / 6.0).abs() < 1e-9);
+    assert!(uniform.reasoning.contains("(4 games, 3.4 std)"));
+
+    // Decayed: 3.2 bias scaled by (45/17) / (45/17 + 2) = 45/79
+    let decayed = estimate(0.5);
+    assert!((decayed.bias_adjustment + 3.2 * 45.0 / 79.0).abs() < 1e-9);
+    assert!(
+        decayed
+            .reasoning
+            .contains("(4 games, 2.6 effective, 4.3 std)"),
+        "{}",
+        decayed.reasoning
+    );
+}
+
+#[test]
+fn test_projection_accuracy_replays_bias_week_by_week() {
+    let mut db = create_test_db_with_player();
+    db.upsert_player(&Player {
+        player_id: PlayerId::new(2),
+        name: "Test Back".to_string(),
+        position: "RB".to_string(),
+        team: None,
+    })
+    .unwrap();
+    let rows = [
+        (12345, 1, 20.0, 16.0, None),
+        (12345, 2, 20.0, 18.0, None),
+        (12345, 3, 20.0, 14.0, None),
+        // Bye and a week the QB sat out are left out
+        (12345, 4, 0.0, 0.0, None),
+        (12345, 5, 20.0, 0.0, Some(false)),
+        (2, 1, 10.0, 15.0, None),
+        (2, 2, 10.0, 5.0, Some(true)),
+    ];
+    for (player_id, week, projected, actual, played) in rows {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(player_id),
+            Season::new(2023),
+            Week::new(week),
+            Some(projected),
+            Some(actual),
+            0,
+            0,
+        );
+        stats.league_id = Some(TEST_LEAGUE);
+        stats.played = played;
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-9;
+
+    let accuracy = db
+        .projection_accuracy(
+            TEST_LEAGUE,
+            Season::new(2023),
+            None,
+            BiasWeighting::new(1.0),
+        )
+        .unwrap();
+    let positions: Vec<&str> = accuracy.iter().map(|a| a.position.as_str()).collect();
+    assert_eq!(positions, ["QB", "RB", "ALL"]);
+
+    // QB errors 4, 2, 6; adjusted from earlier weeks only: 20 (no history),
+    // 20 - 4/3 and 20 - 3 * 2/4
+    let qb = &accuracy[0];
+    assert_eq!(qb.games, 3);
+    assert!(close(qb.espn_mae, 4.0));
+    assert!(close(qb.espn_rmse, (56.0_f64 / 3.0).sqrt()));
+    let qb_adjusted_errors = [4.0, 2.0 / 3.0, 4.5];
+    assert!(close(
+        qb.adjusted_mae,
+        qb_adjusted_errors.iter().sum::<f64>() / 3.0
+    ));
+    assert!(close(
+        qb.improvement_pct.unwrap(),
+        (4.0 - qb.adjusted_mae) / 4.0 * 100.0
+    ));
+    assert!(qb.improvement_pct.unwrap() > 0.0);
+
+    // A bias that flips sign makes the adjusted projection worse
+    let rb = &accuracy[1];
+    assert_eq!(rb.games, 2);
+    assert!(close(rb.espn_mae, 5.0));
+    assert!(close(rb.adjusted_mae, (5.0 + 20.0 / 3.0) / 2.0));
+    assert!(rb.improvement_pct.unwrap() < 0.0);
+
+    let all = &accuracy[2];
+    assert_eq!(all.games, 5);
+    assert!(close(all.espn_mae, 22.0 / 5.0));
+
+    // Later weeks don't count, or feed earlier weeks' adjustments
+    let through_week_1 = db
+        .projection_accuracy(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Some(Week::new(1)),
+            BiasWeighting::new(1.0),
+        )
+        .unwrap();
+    assert_eq!(through_week_1[0].games, 1);
+    assert!(close(through_week_1[0].adjusted_mae, 4.0));
+    assert_eq!(through_week_1[0].improvement_pct, Some(0.0));
+
+    assert!(db
+        .projection_accuracy(
+            TEST_LEAGUE,
+            Season::new(2024),
+            None,
+            BiasWeighting::new(1.0)
+        )
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_dnp_weeks_excluded_from_analysis_by_default() {
+    let mut db = create_test_db_with_player();
+
+    // Weeks 1-3 projected 20 and scored 15; week 4 was a DNP recorded as zero
+    for week in 1..=4 {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(week),
+            Some(20.0),
+            Some(if week == 4 { 0.0 } else { 15.0 }),
+            0,
+            0,
+        );
+        stats.played = Some(week != 4);
+        stats.league_id = Some(TEST_LEAGUE);
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let analysis = db
+        .get_projection_analysis(TEST_LEAGUE, Season::new(2023), None, None, false)
+        .unwrap();
+    assert_eq!(analysis[0].games_count, 3);
+    assert_eq!(analysis[0].avg_error, 5.0);
+
+    let with_dnp = db
+        .get_projection_analysis(TEST_LEAGUE, Season::new(2023), None, None, true)
+        .unwrap();
+    assert_eq!(with_dnp[0].games_count, 4);
+
+    let projected_data = vec![(PlayerId::new(12345), 20.0)];
+    let estimate = &db
+        .estimate_week_performance(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(5),
+            &projected_data,
+            BiasWeighting::new(1.0),
+            false,
+        )
+        .unwrap()[0];
+    assert!(estimate.reasoning.contains("3 games"));
+    assert!(estimate.reasoning.ends_with(" - skipped 1 DNP week"));
+    assert_eq!(estimate.dnp_weeks, Some(1));
+
+    // Counted as games, nothing is skipped
+    let estimate = &db
+        .estimate_week_performance(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(5),
+            &projected_data,
+            BiasWeighting::new(1.0),
+            true,
+        )
+        .unwrap()[0];
+    assert!(estimate.reasoning.contains("4 games"));
+    assert_eq!(estimate.dnp_weeks, None);
+
+    // DNP weeks render as gaps in recent trends
+    let recent = db
+        .get_recent_actual_points(
+            TEST_LEAGUE,
+            &[PlayerId::new(12345)],
+            Season::new(2023),
+            Week::new(4),
+            2,
+        )
+        .unwrap();
+    assert_eq!(recent[&PlayerId::new(12345)], vec![Some(15.0), None]);
+
+    // Roster merges keep the stored participation flag
+    let mut roster_update = PlayerWeeklyStats::test_minimal(
+        PlayerId::new(12345),
+        Season::new(2023),
+        Week::new(4),
+        None,
+        None,
+    );
+    roster_update.is_rostered = Some(true);
+    roster_update.league_id = Some(TEST_LEAGUE);
+    db.merge_weekly_stats(&roster_update).unwrap();
+    let stored = db
+        .get_weekly_stats(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(4),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(stored.played, Some(false));
+}
+
+#[test]
+fn test_bias_history_marks_excluded_weeks() {
+    use espn_ffl::espn::types::InjuryStatus;
+
+    let mut db = create_test_db_with_player();
+
+    // Week 1 counts, week 2 is a bye, week 3 a DNP while Out, week 4 a DNP otherwise
+    let weeks = [
+        (1, 20.0, 15.0, true, None),
+        (2, 0.0, 0.0, true, None),
+        (3, 18.0, 0.0, false, Some(InjuryStatus::Out)),
+        (4, 18.0, 0.0, false, Some(InjuryStatus::Active)),
+    ];
+    for (week, projected, actual, played, status) in weeks {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(12345),
+            Season::new(2023),
+            Week::new(week),
+            Some(projected),
+            Some(actual),
+            0,
+            0,
+        );
+        stats.league_id = Some(TEST_LEAGUE);
+        stats.played = Some(played);
+        stats.injury_status = status;
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let history = db
+        .get_bias_history(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            4,
+            false,
+        )
+        .unwrap();
+    let excluded: Vec<_> = history.iter().map(|w| w.excluded).collect();
+    assert_eq!(
+        excluded,
+        vec![
+            None,
+            Some(HistoryExclusion::Bye),
+            Some(HistoryExclusion::Out),
+            Some(HistoryExclusion::Dnp),
+        ]
+    );
+    assert_eq!(history[0].error, 5.0);
+
+    // With --include-dnp only the bye stays excluded
+    let history = db
+        .get_bias_history(
+            TEST_LEAGUE,
+            PlayerId::new(12345),
+            Season::new(2023),
+            4,
+            true,
+        )
+        .unwrap();
+    assert_eq!(history.iter().filter(|w| w.excluded.is_some()).count(), 1);
+
+    // Estimates only carry history once attached, and it matches the weeks used
+    let mut estimates = db
+        .estimate_week_performance(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(5),
+            &[(PlayerId::new(12345), 20.0)],
+            BiasWeighting::new(1.0),
+            false,
+        )
+        .unwrap();
+    assert!(estimates[0].reasoning.contains("1 games"));
+    assert!(estimates[0].history.is_none());
+    assert!(!serde_json::to_string(&estimates[0])
+        .unwrap()
+        .contains("history"));
+
+    db.attach_bias_history(
+        TEST_LEAGUE,
+        &mut estimates,
+        Season::new(2023),
+        Week::new(5),
+        false,
+    )
+    .unwrap();
+    assert_eq!(estimates[0].history.as_ref().unwrap().len(), 4);
+}
+
+fn roster_entry(team_id: u32, player_id: i64, lineup_slot_id: u8) -> RosterSnapshotEntry {
+    RosterSnapshotEntry {
+        team_id,
+        team_name: Some(format!("Team {}", team_id)),
+        player_id: PlayerId::new(player_id),
+        player_name: Some(format!("Player {}", player_id)),
+        position: None,
+        lineup_slot_id,
+    }
+}
+
+#[test]
+fn test_diff_rosters_adds_drops_and_moves() {
+    use espn_ffl::storage::analysis::diff_rosters;
+
+    let before = vec![
+        roster_entry(1, 10, 0),  // QB starter, unchanged
+        roster_entry(1, 11, 2),  // RB starter -> bench
+        roster_entry(1, 12, 20), // bench, dropped
+        roster_entry(1, 13, 4),  // WR -> FLEX is still a starter
+        roster_entry(2, 20, 21), // IR -> starter
+        roster_entry(2, 21, 6),  // traded to team 1
+    ];
+    let after = vec![
+        roster_entry(1, 10, 0),
+        roster_entry(1, 11, 20),
+        roster_entry(1, 13, 23),
+        roster_entry(1, 14, 20), // new pickup
+        roster_entry(1, 21, 6),
+        roster_entry(2, 20, 2),
+    ];
+
+    let diffs = diff_rosters(&before, &after);
+    assert_eq!(diffs.len(), 2);
+
+    let ids = |changes: &[RosterChange]| -> Vec<i64> {
+        changes.iter().map(|c| c.player_id.as_i64()).collect()
+    };
+
+    let team1 = &diffs[0];
+    assert_eq!(team1.team_id, 1);
+    assert_eq!(ids(&team1.added), vec![14, 21]);
+    assert_eq!(team1.added[0].to_slot, Some(SlotGroup::Bench));
+    assert_eq!(team1.added[0].from_slot, None);
+    assert_eq!(ids(&team1.dropped), vec![12]);
+    assert_eq!(team1.dropped[0].from_slot, Some(SlotGroup::Bench));
+    assert_eq!(ids(&team1.moved), vec![11]);
+    assert_eq!(team1.moved[0].from_slot, Some(SlotGroup::Starter));
+    assert_eq!(team1.moved[0].to_slot, Some(SlotGroup::Bench));
+
+    let team2 = &diffs[1];
+    assert!(team2.added.is_empty());
+    assert_eq!(ids(&team2.dropped), vec![21]);
+    assert_eq!(ids(&team2.moved), vec![20]);
+    assert_eq!(team2.moved[0].from_slot, Some(SlotGroup::Ir));
+
+    // Identical snapshots produce empty diffs per team
+    let unchanged = diff_rosters(&before, &before);
+    assert!(unchanged.iter().all(|d| d.is_empty()));
+}
+
+#[test]
+fn test_get_roster_diff_with_points() {
+    use espn_ffl::espn::types::{LeagueData, RosterEntry, Team, TeamRoster};
+
+    let mut db = create_test_db_with_player();
+    let roster = |entries: Vec<(i64, u8)>| LeagueData {
+        teams: vec![Team {
+            id: 1,
+            name: Some("Team Alpha".to_string()),
+            abbrev: None,
+            roster: Some(TeamRoster {
+                entries: entries
+                    .into_iter()
+                    .map(|(player_id, lineup_slot_id)| RosterEntry {
+                        player_id,
+                        lineup_slot_id,
+                        injury_status: None,
+                    })
+                    .collect(),
+            }),
+        }],
+    };
+
+    assert_eq!(
+        db.save_roster_snapshot(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(1),
+            &roster(vec![(99, 0)])
+        )
+        .unwrap(),
+        1
+    );
+    db.save_roster_snapshot(
+        TEST_LEAGUE,
+        Season::new(2023),
+        Week::new(3),
+        &roster(vec![(12345, 0)]),
+    )
+    .unwrap();
+
+    for (week, actual) in [(2, 18.0), (3, 22.0)] {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(12345),
+                Season::new(2023),
+                Week::new(week),
+                Some(20.0),
+                Some(actual),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let diffs = db
+        .get_roster_diff(TEST_LEAGUE, Season::new(2023), Week::new(1), Week::new(3))
+        .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].team_name.as_deref(), Some("Team Alpha"));
+
+    let added = &diffs[0].added[0];
+    assert_eq!(added.player_name.as_deref(), Some("Test Player"));
+    assert_eq!(added.position.as_deref(), Some("QB"));
+    assert_eq!(added.weekly_points, vec![Some(18.0), Some(22.0)]);
+    assert_eq!(added.total_points, 40.0);
+
+    // Dropped player has no stored stats
+    let dropped = &diffs[0].dropped[0];
+    assert_eq!(dropped.player_name, None);
+    assert_eq!(dropped.position, None);
+    assert_eq!(dropped.weekly_points, vec![None, None]);
+
+    // Saving a week again replaces its snapshot
+    db.save_roster_snapshot(
+        TEST_LEAGUE,
+        Season::new(2023),
+        Week::new(3),
+        &roster(vec![(99, 0)]),
+    )
+    .unwrap();
+    let diffs = db
+        .get_roster_diff(TEST_LEAGUE, Season::new(2023), Week::new(1), Week::new(3))
+        .unwrap();
+    assert!(diffs[0].is_empty());
+
+    // Another league's snapshot of the same week is kept apart
+    let other_league = LeagueId::new(67890);
+    db.save_roster_snapshot(
+        other_league,
+        Season::new(2023),
+        Week::new(3),
+        &roster(vec![(12345, 0)]),
+    )
+    .unwrap();
+    let snapshot = |league_id| {
+        db.get_roster_snapshot(league_id, Season::new(2023), Week::new(3))
+            .unwrap()
+            .iter()
+            .map(|entry| entry.player_id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(snapshot(TEST_LEAGUE), [PlayerId::new(99)]);
+    assert_eq!(snapshot(other_league), [PlayerId::new(12345)]);
+}
+
+#[test]
+fn test_get_data_coverage() {
+    use espn_ffl::espn::types::{LeagueData, RosterEntry, Team, TeamRoster};
+
+    let mut db = create_test_db();
+
+    // Week 1: 10 players with actuals and projections; week 2: 5 projections only
+    for player in 0..10 {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(100 + player),
+            name: format!("Player {}", player),
+            position: "WR".to_string(),
+            team: None,
+        })
+        .unwrap();
+        let stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(100 + player),
+            Season::new(2023),
+            Week::new(1),
+            Some(10.0),
+            Some(12.0),
+            0,
+            0,
+        );
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    for player in 0..5 {
+        let stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(100 + player),
+            Season::new(2023),
+            Week::new(2),
+            Some(10.0),
+            None,
+            0,
+            0,
+        );
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    let roster = LeagueData {
+        teams: vec![Team {
+            id: 1,
+            name: Some("Team Alpha".to_string()),
+            abbrev: None,
+            roster: Some(TeamRoster {
+                entries: vec![RosterEntry {
+                    player_id: 100,
+                    lineup_slot_id: 0,
+                    injury_status: None,
+                }],
+            }),
+        }],
+    };
+    db.save_roster_snapshot(TEST_LEAGUE, Season::new(2023), Week::new(2), &roster)
+        .unwrap();
+
+    let coverage = db
+        .get_data_coverage(TEST_LEAGUE, Season::new(2023), 18)
+        .unwrap();
+    assert_eq!(coverage.rows.len(), 4);
+
+    let row = |data_type| {
+        coverage
+            .rows
+            .iter()
+            .find(|r| r.data_type == data_type)
+            .unwrap()
+    };
+
+    let actual = row(CoverageDataType::ActualPoints);
+    assert_eq!(actual.weekly_counts.len(), 18);
+    assert_eq!(&actual.weekly_counts[..3], &[10, 0, 0]);
+    assert_eq!(actual.total, 10);
+    assert_eq!(actual.level(1, 0.9), CoverageLevel::Full);
+    assert_eq!(actual.level(2, 0.9), CoverageLevel::Missing);
+
+    let projected = row(CoverageDataType::ProjectedPoints);
+    assert_eq!(projected.expected, 10);
+    assert_eq!(projected.total, 15);
+    assert_eq!(projected.level(2, 0.9), CoverageLevel::Partial);
+    // A looser threshold counts half a week as complete
+    assert_eq!(projected.level(2, 0.5), CoverageLevel::Full);
+
+    let snapshots = row(CoverageDataType::RosterSnapshots);
+    assert_eq!(&snapshots.weekly_counts[..2], &[0, 1]);
+    assert_eq!(snapshots.level(2, 0.9), CoverageLevel::Full);
+
+    // Out-of-range weeks are simply missing
+    assert_eq!(actual.level(0, 0.9), CoverageLevel::Missing);
+    assert_eq!(actual.level(19, 0.9), CoverageLevel::Missing);
+}
+
+#[test]
+fn test_get_raw_stats_round_trip() {
+    let mut db = create_test_db();
+    let stats = serde_json::json!({ "74": 1.0, "77": 2.0, "80": 0.0 });
+    db.save_raw_stats(
+        TEST_LEAGUE,
+        Season::new(2023),
+        Week::new(5),
+        &[(PlayerId::new(3001), &stats)],
+    )
+    .unwrap();
+
+    let raw = db
+        .get_raw_stats(
+            TEST_LEAGUE,
+            &[PlayerId::new(3001), PlayerId::new(3002)],
+            Season::new(2023),
+            Week::new(5),
+        )
+        .unwrap();
+    // Zeros aren't stored, and players without stats are left out
+    assert_eq!(raw.len(), 1);
+    assert_eq!(
+        raw[&PlayerId::new(3001)],
+        serde_json::json!({ "74": 1.0, "77": 2.0 })
+    );
+}
+
+#[test]
+fn test_team_name_history_tracks_renames() {
+    use espn_ffl::{
+        espn::types::{LeagueData, Team},
+        LeagueId,
+    };
+
+    let mut db = create_test_db();
+    let league_id = LeagueId::new(12345);
+    let season = Season::new(2023);
+    let teams = |names: [(&str, Option<&str>); 2]| LeagueData {
+        teams: names
+            .iter()
+            .enumerate()
+            .map(|(i, (name, abbrev))| Team {
+                id: i as u32 + 1,
+                name: Some(name.to_string()),
+                abbrev: abbrev.map(str::to_string),
+                roster: None,
+            })
+            .collect(),
+    };
+
+    let original = teams([("Kenny's Crew", Some("KC")), ("Alpha Squad", None)]);
+    let renamed = teams([("Powers Play", Some("PWR")), ("Alpha Squad", None)]);
+    assert_eq!(
+        db.record_team_names(league_id, season, Week::new(1), &original)
+            .unwrap(),
+        2
+    );
+    db.record_team_names(league_id, season, Week::new(3), &original)
+        .unwrap();
+    assert_eq!(
+        db.record_team_names(league_id, season, Week::new(4), &renamed)
+            .unwrap(),
+        1
+    );
+    // Backfilling an earlier week widens the range instead of adding a row
+    assert_eq!(
+        db.record_team_names(league_id, season, Week::new(2), &original)
+            .unwrap(),
+        0
+    );
+
+    let history = db
+        .get_team_name_history(league_id, season, Some(1))
+        .unwrap();
+    let summary: Vec<(&str, u16, u16)> = history
+        .iter()
+        .map(|h| {
+            (
+                h.name.as_str(),
+                h.first_seen_week.as_u16(),
+                h.last_seen_week.as_u16(),
+            )
+        })
+        .collect();
+    assert_eq!(summary, vec![("Kenny's Crew", 1, 3), ("Powers Play", 4, 4)]);
+    assert_eq!(history[1].abbrev.as_deref(), Some("PWR"));
+
+    let current = db.get_current_team_names(league_id, season).unwrap();
+    assert_eq!(current[&1], "Powers Play");
+    assert_eq!(current[&2], "Alpha Squad");
+
+    // Old names, current names and exact abbreviations all resolve to the team
+    assert_eq!(
+        db.find_team_ids_by_name(league_id, season, "kenny")
+            .unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        db.find_team_ids_by_name(league_id, season, "POWERS")
+            .unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        db.find_team_ids_by_name(league_id, season, "kc").unwrap(),
+        vec![1]
+    );
+    assert_eq!(
+        db.find_team_ids_by_name(league_id, season, "a").unwrap(),
+        vec![1, 2]
+    );
+    assert!(db
+        .find_team_ids_by_name(league_id, Season::new(2024), "kenny")
+        .unwrap()
+        .is_empty());
+    assert!(db
+        .find_team_ids_by_name(LeagueId::new(999), season, "kenny")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_update_players_from_espn_stores_pro_team() {
+    use espn_ffl::espn::types::Player as EspnPlayer;
+
+    let mut db = create_test_db();
+    let player = |id: i64, pro_team_id: Option<u32>| EspnPlayer {
+        id,
+        full_name: Some("Josh Allen".to_string()),
+        default_position_id: 0,
+        stats: vec![],
+        active: None,
+        injured: None,
+        injury_status: None,
+        pro_team_id,
+        eligible_slots: vec![],
+        ownership: None,
+        news: None,
+    };
+    db.update_players_from_espn(&[player(1, Some(2)), player(2, Some(0)), player(3, None)])
+        .unwrap();
+
+    let teams = db
+        .get_player_teams(&[PlayerId::new(1), PlayerId::new(2), PlayerId::new(3)])
+        .unwrap();
+    assert_eq!(teams.len(), 1);
+    assert_eq!(teams[&PlayerId::new(1)], "BUF");
+
+    // Free agents and unknown teams still store the player
+    assert_eq!(db.get_all_players().unwrap().len(), 3);
+}
+
+#[test]
+fn test_renamed_player_keeps_old_name_as_alias() {
+    let mut db = create_test_db();
+    let player_id = PlayerId::new(4241372);
+    let player = |name: &str| Player {
+        player_id,
+        name: name.to_string(),
+        position: "WR".to_string(),
+        team: Some("ARI".to_string()),
+    };
+
+    // Placeholders from nameless payloads are never kept
+    assert_eq!(db.upsert_player(&player("Player 4241372")).unwrap(), None);
+    assert_eq!(db.upsert_player(&player("Hollywood Brown")).unwrap(), None);
+    assert_eq!(db.upsert_player(&player("Hollywood Brown")).unwrap(), None);
+
+    let alias = db
+        .upsert_player(&player("Marquise Brown"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(alias.alias, "Hollywood Brown");
+    assert_eq!(alias.name.as_deref(), Some("Marquise Brown"));
+    assert_eq!(db.upsert_player(&player("Player 4241372")).unwrap(), None);
+    db.upsert_player(&player("Marquise Brown")).unwrap();
+
+    let aliases = db.list_player_aliases(Some(player_id)).unwrap();
+    assert_eq!(aliases.len(), 1);
+    assert_eq!(aliases[0].alias, "Hollywood Brown");
+    assert!(db
+        .list_player_aliases(Some(PlayerId::new(1)))
+        .unwrap()
+        .is_empty());
+
+    // Old names widen name filters to the current name
+    assert_eq!(
+        db.expand_player_names_with_aliases(&["hollywood".to_string()])
+            .unwrap(),
+        vec!["hollywood".to_string(), "Marquise Brown".to_string()]
+    );
+    assert_eq!(
+        db.expand_player_names_with_aliases(&["Marquise".to_string()])
+            .unwrap(),
+        vec!["Marquise".to_string()]
+    );
+
+    // Going back to an old name drops it from the aliases
+    db.upsert_player(&player("Hollywood Brown")).unwrap();
+    let aliases = db.list_player_aliases(None).unwrap();
+    assert_eq!(
+        aliases.iter().map(|a| a.alias.as_str()).collect::<Vec<_>>(),
+        vec!["Marquise Brown"]
+    );
+
+    db.clear_all_data().unwrap();
+    assert!(db.list_player_aliases(None).unwrap().is_empty());
+}
+
+#[test]
+fn test_player_news_keeps_latest_headline() {
+    use espn_ffl::espn::types::PlayerNews;
+
+    let mut db = create_test_db_with_player();
+    let player_id = PlayerId::new(12345);
+    let news = |headline: &str, published: Option<u64>| PlayerNews {
+        headline: headline.to_string(),
+        published,
+    };
+
+    db.save_player_news(player_id, &news("Limited Wednesday", Some(200)))
+        .unwrap();
+    // An older item doesn't replace a newer one
+    db.save_player_news(player_id, &news("Missed last week", Some(100)))
+        .unwrap();
+    let stored = db.get_player_news(&[player_id]).unwrap();
+    assert_eq!(stored[&player_id].headline, "Limited Wednesday");
+    assert_eq!(stored[&player_id].published, Some(200));
+    assert!(stored[&player_id].fetched_at > 0);
+
+    db.save_player_news(player_id, &news("Full participant Thursday", Some(300)))
+        .unwrap();
+    let stored = db
+        .get_player_news(&[player_id, PlayerId::new(999)])
+        .unwrap();
+    assert_eq!(stored.len(), 1);
+    assert_eq!(stored[&player_id].headline, "Full participant Thursday");
+
+    db.clear_all_data().unwrap();
+    assert!(db.get_player_news(&[player_id]).unwrap().is_empty());
+}
+
+#[test]
+fn test_player_notes_survive_clear_all_data() {
+    let mut db = create_test_db_with_player();
+    let player_id = PlayerId::new(12345);
+
+    let first = db.add_player_note(player_id, "handcuff for X").unwrap();
+    let second = db
+        .add_player_note(player_id, "sell high after week 9")
+        .unwrap();
+    db.add_player_note(PlayerId::new(999), "unknown player")
+        .unwrap();
+
+    // Newest first, with the stored player's name when known
+    let notes = db.list_player_notes(Some(player_id)).unwrap();
+    assert_eq!(
+        notes.iter().map(|n| n.note_id).collect::<Vec<_>>(),
+        vec![second, first]
+    );
+    assert_eq!(notes[0].player_name.as_deref(), Some("Test Player"));
+
+    let by_player = db.get_notes_for_players(&[player_id]).unwrap();
+    assert_eq!(by_player.len(), 1);
+    assert_eq!(by_player[&player_id][0].note, "sell high after week 9");
+
+    db.clear_all_data().unwrap();
+    let notes = db.list_player_notes(None).unwrap();
+    assert_eq!(notes.len(), 3);
+    assert!(notes.iter().all(|n| n.player_name.is_none()));
+
+    assert!(db.delete_player_note(first).unwrap());
+    assert!(!db.delete_player_note(first).unwrap());
+
+    db.clear_player_notes().unwrap();
+    assert!(db.list_player_notes(None).unwrap().is_empty());
+}
+
+#[test]
+fn test_recent_form_estimate_weights_latest_games() {
+    use espn_ffl::storage::analysis::{blend_with_recent_form, recent_form_estimate};
+
+    assert_eq!(recent_form_estimate(&[]), None);
+    assert_eq!(recent_form_estimate(&[12.0]), Some(12.0));
+    // Weights 1, 2, 3, 4 from oldest to newest
+    let form = recent_form_estimate(&[5.0, 4.0, 22.0, 24.0]).unwrap();
+    assert!((form - 17.5).abs() < 1e-9);
+
+    assert_eq!(blend_with_recent_form(10.0, 20.0, 0.0), 10.0);
+    assert_eq!(blend_with_recent_form(10.0, 20.0, 1.0), 20.0);
+    assert!((blend_with_recent_form(10.0, 20.0, 0.25) - 12.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_recent_form_blend_catches_breakout_player() {
+    let mut db = create_test_db();
+    let player_id = PlayerId::new(777);
+    db.upsert_player(&Player {
+        player_id,
+        name: "Backup Back".to_string(),
+        position: "RB".to_string(),
+        team: None,
+    })
+    .unwrap();
+
+    // Projected at 6 every week; thrust into the starting role in week 3
+    for (week, actual) in [(1, 5.0), (2, 4.0), (3, 22.0), (4, 24.0)] {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                player_id,
+                Season::new(2023),
+                Week::new(week),
+                Some(6.0),
+                Some(actual),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let estimate = |weight: Option<f64>| {
+        let mut estimates = db
+            .estimate_week_performance(
+                TEST_LEAGUE,
+                Season::new(2023),
+                Week::new(5),
+                &[(player_id, 6.0)],
+                BiasWeighting::new(1.0),
+                false,
+            )
+            .unwrap();
+        if let Some(weight) = weight {
+            db.apply_recent_form_blend(
+                TEST_LEAGUE,
+                &mut estimates,
+                Season::new(2023),
+                Week::new(5),
+                weight,
+                false,
+            )
+            .unwrap();
+        }
+        estimates.remove(0)
+    };
+
+    let adjusted = estimate(None);
+    assert!(adjusted.blend.is_none());
+
+    let blended = estimate(Some(0.5));
+    let blend = blended.blend.unwrap();
+    assert!((blend.recent_form - 17.5).abs() < 1e-9);
+    assert_eq!(blend.games, 4);
+    assert_eq!(blend.weight, 0.5);
+    assert!((blend.adjusted_projection - adjusted.estimated_points).abs() < 1e-9);
+    assert!((blended.estimated_points - (adjusted.estimated_points + 17.5) / 2.0).abs() < 1e-9);
+    assert!(blended
+        .reasoning
+        .contains("recent form 17.5 pts (last 4 games)"));
+
+    // The endpoints reproduce each component
+    assert!((estimate(Some(0.0)).estimated_points - adjusted.estimated_points).abs() < 1e-9);
+    assert!((estimate(Some(1.0)).estimated_points - 17.5).abs() < 1e-9);
+}
+
+#[test]
+fn test_unprojected_players_estimated_from_recent_form() {
+    use espn_ffl::storage::analysis::UNPROJECTED_CONFIDENCE;
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let (questionable, rookie, starter) =
+        (PlayerId::new(801), PlayerId::new(802), PlayerId::new(803));
+    for (player_id, name) in [
+        (questionable, "Questionable Wideout"),
+        (rookie, "Unused Rookie"),
+        (starter, "Every Week Starter"),
+    ] {
+        db.upsert_player(&Player {
+            player_id,
+            name: name.to_string(),
+            position: "WR".to_string(),
+            team: None,
+        })
+        .unwrap();
+    }
+    for (week, actual) in [(1, 10.0), (2, 16.0)] {
+        let stats = PlayerWeeklyStats {
+            league_id: Some(TEST_LEAGUE),
+            ..PlayerWeeklyStats::test_with_fields(
+                questionable,
+                season,
+                Week::new(week),
+                Some(12.0),
+                Some(actual),
+                0,
+                0,
+            )
+        };
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let mut estimates = db
+        .estimate_week_performance(
+            TEST_LEAGUE,
+            season,
+            Week::new(3),
+            &[(starter, 8.0)],
+            BiasWeighting::new(1.0),
+            false,
+        )
+        .unwrap();
+    db.add_unprojected_estimates(
+        TEST_LEAGUE,
+        &mut estimates,
+        season,
+        Week::new(3), // The starter already has an estimate; 999 isn't stored
+        &[rookie, questionable, starter, PlayerId::new(999)],
+        false,
+    )
+    .unwrap();
+
+    let ids: Vec<PlayerId> = estimates.iter().map(|e| e.player_id).collect();
+    assert_eq!(ids, vec![questionable, starter, rookie]);
+
+    // Weights 1, 2 over the two games
+    let estimate = &estimates[0];
+    assert!((estimate.estimated_points - 14.0).abs() < 1e-9);
+    assert_eq!(estimate.espn_projection, 0.0);
+    assert_eq!(estimate.confidence, UNPROJECTED_CONFIDENCE);
+    assert_eq!(
+        estimate.reasoning,
+        "No ESPN projection — estimate from last 2 games"
+    );
+
+    assert_eq!(estimates[2].estimated_points, 0.0);
+    assert!(estimates[2].reasoning.contains("no recent games"));
+}
+
+#[test]
+fn test_category_leaders_from_raw_stats() {
+    use espn_ffl::cli::types::filters::RosterStatusFilter;
+    use espn_ffl::Position;
+    use serde_json::json;
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let players = [
+        (1, "Big Arm", "QB", true),
+        (2, "Workhorse Back", "RB", false),
+        (3, "Strong Leg", "K", true),
+    ];
+    for (id, name, position, rostered) in players {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: None,
+        })
+        .unwrap();
+        for week in 1..=2 {
+            let mut stats = PlayerWeeklyStats::test_with_fields(
+                PlayerId::new(id),
+                season,
+                Week::new(week),
+                None,
+                Some(10.0 * week as f64),
+                0,
+                0,
+            );
+            stats.is_rostered = Some(rostered);
+            db.upsert_weekly_stats(&stats, false).unwrap();
+        }
+    }
+
+    // 3 = passing yards, 24 = rushing yards, 201 = 60+ yard field goals
+    let week1_qb = json!({ "3": 310.0, "24": 12.0, "201": 0.0 });
+    let week1_rb = json!({ "24": 95.0 });
+    let week1_k = json!({ "201": 1.0 });
+    let written = db
+        .save_raw_stats(
+            TEST_LEAGUE,
+            season,
+            Week::new(1),
+            &[
+                (PlayerId::new(1), &week1_qb),
+                (PlayerId::new(2), &week1_rb),
+                (PlayerId::new(3), &week1_k),
+            ],
+        )
+        .unwrap();
+    // The zero-valued 60+ yard entry is not stored
+    assert_eq!(written, 4);
+    let week2_rb = json!({ "24": 40.0 });
+    db.save_raw_stats(
+        TEST_LEAGUE,
+        season,
+        Week::new(2),
+        &[(PlayerId::new(2), &week2_rb)],
+    )
+    .unwrap();
+
+    let leaders = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: Some(Week::new(1)),
+                positions: None,
+                roster_status: None,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    let names: Vec<&str> = leaders.iter().map(|l| l.name.as_str()).collect();
+    assert_eq!(names, ["Workhorse Back", "Big Arm"]);
+    assert_eq!(leaders[0].value, 95.0);
+    assert_eq!(leaders[0].fantasy_points, Some(10.0));
+    assert_eq!(leaders[0].games, 1);
+
+    // Season totals sum the stat and fantasy points across stored weeks
+    let totals = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: None,
+                positions: None,
+                roster_status: None,
+                limit: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(totals.len(), 1);
+    assert_eq!(totals[0].value, 135.0);
+    assert_eq!(totals[0].fantasy_points, Some(30.0));
+    assert_eq!(totals[0].games, 2);
+
+    // Position and roster filters narrow the result
+    let qbs = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: None,
+                positions: Some(&[Position::QB]),
+                roster_status: None,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(qbs.len(), 1);
+    assert_eq!(qbs[0].player_id, PlayerId::new(1));
+    let free_agents = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: None,
+                positions: None,
+                roster_status: Some(&RosterStatusFilter::FA),
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(free_agents.len(), 1);
+    assert_eq!(free_agents[0].player_id, PlayerId::new(2));
+
+    // Sparse categories only return the players who recorded them
+    let long_kicks = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 201,
+                season,
+                week: None,
+                positions: None,
+                roster_status: None,
+                limit: 10,
+            },
+        )
+        .unwrap();
+    assert_eq!(long_kicks.len(), 1);
+    assert_eq!(long_kicks[0].name, "Strong Leg");
+
+    // Re-saving a week replaces that player's stats
+    let corrected = json!({ "24": 80.0 });
+    db.save_raw_stats(
+        TEST_LEAGUE,
+        season,
+        Week::new(1),
+        &[(PlayerId::new(2), &corrected)],
+    )
+    .unwrap();
+    let leaders = db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: Some(Week::new(1)),
+                positions: None,
+                roster_status: None,
+                limit: 1,
+            },
+        )
+        .unwrap();
+    assert_eq!(leaders[0].value, 80.0);
+
+    db.clear_all_data().unwrap();
+    assert!(db
+        .get_category_leaders(
+            TEST_LEAGUE,
+            &CategoryLeadersQuery {
+                stat_id: 24,
+                season,
+                week: None,
+                positions: None,
+                roster_status: None,
+                limit: 10,
+            }
+        )
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_team_week_totals_split_starters_and_bench() {
+    use espn_ffl::{
+        espn::types::{LeagueData, RosterEntry, Team, TeamRoster},
+        LeagueId,
+    };
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let week = Week::new(4);
+    // (player, lineup slot, actual points); player 6 has nothing stored
+    let players = [
+        (1, 0, Some(24.5)),
+        (2, 2, Some(8.0)),
+        (3, 4, Some(15.0)),
+        (4, 20, Some(11.0)),
+        (5, 21, Some(30.0)),
+        (6, 4, None),
+        (7, 0, Some(12.0)),
+        (8, 20, None),
+    ];
+    for (id, _, actual) in players {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: format!("Player {}", id),
+            position: "WR".to_string(),
+            team: None,
+        })
+        .unwrap();
+        if let Some(actual) = actual {
+            let stats = PlayerWeeklyStats::test_minimal(
+                PlayerId::new(id),
+                season,
+                week,
+                None,
+                Some(actual),
+            );
+            db.upsert_weekly_stats(&stats, false).unwrap();
+        }
+    }
+    let team = |id: u32, name: &str, player_ids: &[i64]| Team {
+        id,
+        name: Some(name.to_string()),
+        abbrev: None,
+        roster: Some(TeamRoster {
+            entries: players
+                .iter()
+                .filter(|(player, _, _)| player_ids.contains(player))
+                .map(|&(player_id, lineup_slot_id, _)| RosterEntry {
+                    player_id,
+                    lineup_slot_id,
+                    injury_status: None,
+                })
+                .collect(),
+        }),
+    };
+    let rosters = LeagueData {
+        teams: vec![
+            team(1, "Team Alpha", &[1, 2, 3, 4, 5]),
+            team(2, "Team Beta", &[6, 7, 8]),
+        ],
+    };
+    db.save_roster_snapshot(TEST_LEAGUE, season, week, &rosters)
+        .unwrap();
+
+    let totals = db
+        .team_week_totals(LeagueId::new(12345), season, week)
+        .unwrap();
+    assert_eq!(totals.len(), 2);
+
+    let alpha = &totals[0];
+    assert_eq!(alpha.team_name.as_deref(), Some("Team Alpha"));
+    assert!((alpha.starter_points - 47.5).abs() < 1e-9);
+    // IR points don't count as bench points
+    assert_eq!(alpha.bench_points, 11.0);
+    assert_eq!(
+        alpha.top_scorer.as_ref().unwrap().player_id,
+        PlayerId::new(1)
+    );
+    assert_eq!(
+        alpha.bottom_scorer.as_ref().unwrap().player_id,
+        PlayerId::new(2)
+    );
+    assert!(alpha.is_complete());
+
+    // A starter without stored points flags the total as partial
+    let beta = &totals[1];
+    assert_eq!(beta.starter_points, 12.0);
+    assert_eq!(beta.bench_points, 0.0);
+    assert!(!beta.is_complete());
+    assert_eq!(beta.missing_starters.len(), 1);
+    assert_eq!(beta.missing_starters[0].player_id, PlayerId::new(6));
+
+    assert!(db
+        .team_week_totals(LeagueId::new(12345), season, Week::new(5))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_team_rosters_order_teams_by_name_and_starters_first() {
+    use espn_ffl::{
+        espn::types::{LeagueData, RosterEntry, Team, TeamRoster},
+        storage::SlotGroup,
+        LeagueId,
+    };
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let week = Week::new(4);
+    // (player, lineup slot); player 3 has no stats stored
+    let players = [(1, 20), (2, 23), (3, 0), (4, 21), (5, 2)];
+    for (id, _) in players {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: format!("Player {}", id),
+            position: "RB".to_string(),
+            team: None,
+        })
+        .unwrap();
+        if id != 3 {
+            let stats = PlayerWeeklyStats::test_minimal(
+                PlayerId::new(id),
+                season,
+                week,
+                Some(10.0),
+                Some(id as f64),
+            );
+            db.upsert_weekly_stats(&stats, false).unwrap();
+        }
+    }
+    let team = |id: u32, name: &str, player_ids: &[i64]| Team {
+        id,
+        name: Some(name.to_string()),
+        abbrev: None,
+        roster: Some(TeamRoster {
+            entries: players
+                .iter()
+                .filter(|(player, _)| player_ids.contains(player))
+                .map(|&(player_id, lineup_slot_id)| RosterEntry {
+                    player_id,
+                    lineup_slot_id,
+                    injury_status: None,
+                })
+                .collect(),
+        }),
+    };
+    let rosters = LeagueData {
+        teams: vec![team(1, "Zebras", &[1, 2, 3, 4]), team(2, "Aardvarks", &[5])],
+    };
+    db.save_roster_snapshot(TEST_LEAGUE, season, week, &rosters)
+        .unwrap();
+
+    let rosters = db.team_rosters(LeagueId::new(12345), season, week).unwrap();
+    let names: Vec<_> = rosters.iter().map(|r| r.team_name.as_deref()).collect();
+    assert_eq!(names, vec![Some("Aardvarks"), Some("Zebras")]);
+
+    // Starters by slot (QB before FLEX), then bench, then IR
+    let zebras = &rosters[1];
+    let order: Vec<(i64, SlotGroup)> = zebras
+        .players
+        .iter()
+        .map(|p| (p.player_id.as_i64(), p.slot))
+        .collect();
+    assert_eq!(
+        order,
+        vec![
+            (3, SlotGroup::Starter),
+            (2, SlotGroup::Starter),
+            (1, SlotGroup::Bench),
+            (4, SlotGroup::Ir),
+        ]
+    );
+    assert_eq!(zebras.players[0].points, None);
+    assert_eq!(zebras.players[1].points, Some(2.0));
+    assert_eq!(zebras.players[1].projected_points, Some(10.0));
+    assert_eq!(zebras.players[1].position.as_deref(), Some("RB"));
+
+    assert!(db
+        .team_rosters(LeagueId::new(12345), season, Week::new(5))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_streaming_recap_compares_starts_free_agents_and_leader() {
+    use espn_ffl::espn::types::{LeagueData, RosterEntry, Team, TeamRoster};
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    // (kicker, [week 1, week 2] actual points)
+    let kickers = [
+        (10, [5.0, 7.0]),   // Team 1's starter
+        (11, [12.0, 11.0]), // Team 2's starter, the season leader
+        (12, [9.0, 3.0]),   // Free agent
+        (13, [2.0, 8.0]),   // Free agent
+        (14, [20.0, 0.0]),  // Team 1's bench: rostered, so never a free agent
+    ];
+    for (id, weekly) in kickers {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: format!("Kicker {}", id),
+            position: "K".to_string(),
+            team: None,
+        })
+        .unwrap();
+        for (week, points) in [1, 2].into_iter().zip(weekly) {
+            let stats = PlayerWeeklyStats::test_minimal(
+                PlayerId::new(id),
+                season,
+                Week::new(week),
+                None,
+                Some(points),
+            );
+            db.upsert_weekly_stats(&stats, false).unwrap();
+        }
+    }
+    let team = |id: u32, entries: &[(i64, u8)]| Team {
+        id,
+        name: Some(format!("Team {}", id)),
+        abbrev: None,
+        roster: Some(TeamRoster {
+            entries: entries
+                .iter()
+                .map(|&(player_id, lineup_slot_id)| RosterEntry {
+                    player_id,
+                    lineup_slot_id,
+                    injury_status: None,
+                })
+                .collect(),
+        }),
+    };
+    let rosters = LeagueData {
+        teams: vec![team(1, &[(10, 17), (14, 20)]), team(2, &[(11, 17)])],
+    };
+    for week in [1, 2] {
+        db.save_roster_snapshot(TEST_LEAGUE, season, Week::new(week), &rosters)
+            .unwrap();
+    }
+
+    let recap = db.streaming_recap(TEST_LEAGUE, season, 1, "K").unwrap();
+    let leader = recap.season_leader.as_ref().unwrap();
+    assert_eq!((leader.player_id, leader.points), (PlayerId::new(11), 23.0));
+    assert_eq!(recap.weeks.len(), 2);
+
+    let week1 = &recap.weeks[0];
+    assert_eq!(week1.started.len(), 1);
+    assert_eq!(week1.started[0].player_id, PlayerId::new(10));
+    assert_eq!(week1.started_points, 5.0);
+    assert_eq!(
+        week1.best_free_agent.as_ref().unwrap().player_id,
+        PlayerId::new(12)
+    );
+    assert_eq!(week1.season_leader_points, 12.0);
+    assert_eq!(
+        recap.weeks[1].best_free_agent.as_ref().unwrap().player_id,
+        PlayerId::new(13)
+    );
+
+    assert_eq!(recap.started_total, 12.0);
+    assert_eq!(recap.perfect_streaming_total, 17.0);
+    assert_eq!(recap.holding_total, 23.0);
+    assert_eq!(recap.verdict, Some(StreamingVerdict::Hold));
+
+    // No D/ST stored at all: empty lines, nothing to gain from holding
+    let defense = db.streaming_recap(TEST_LEAGUE, season, 1, "D/ST").unwrap();
+    assert!(defense.season_leader.is_none());
+    assert_eq!(defense.weeks.len(), 2);
+    assert!(defense.weeks.iter().all(|w| w.started.is_empty()));
+    assert_eq!(defense.verdict, Some(StreamingVerdict::Stream));
+
+    // A team with no snapshots has no weeks and no verdict
+    let unknown = db.streaming_recap(TEST_LEAGUE, season, 9, "K").unwrap();
+    assert!(unknown.weeks.is_empty() && unknown.verdict.is_none());
+}
+
+#[test]
+fn test_positional_rank_history_ranks_within_position_each_week() {
+    use espn_ffl::Position;
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    let players = [
+        (1, "Alpha Receiver", "WR"),
+        (2, "Bravo Receiver", "WR"),
+        (3, "Charlie Receiver", "WR"),
+        (4, "Delta Back", "RB"),
+    ];
+    for (id, name, position) in players {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: None,
+        })
+        .unwrap();
+    }
+    // (player, week, points, played)
+    let weeks = [
+        (1, 1, 20.0, true),
+        (2, 1, 20.0, true),
+        (3, 1, 10.0, true),
+        (4, 1, 5.0, true),
+        (1, 2, 8.0, true),
+        (2, 2, 15.0, true),
+        (3, 2, 0.0, false),
+        (1, 3, 30.0, true),
+        (2, 3, 12.0, true),
+        (3, 3, 12.0, true),
+    ];
+    for (id, week, points, played) in weeks {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(id),
+            season,
+            Week::new(week),
+            None,
+            Some(points),
+            0,
+            0,
+        );
+        stats.league_id = Some(TEST_LEAGUE);
+        stats.played = Some(played);
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let history = db
+        .positional_rank_history(TEST_LEAGUE, season, None, 1)
+        .unwrap();
+    let ids: Vec<i64> = history.iter().map(|h| h.player_id.as_i64()).collect();
+    // Position order first, then median rank, then player ID
+    assert_eq!(ids, [4, 1, 2, 3]);
+
+    // Ties share the better rank
+    let alpha = &history[1];
+    let ranks: Vec<u32> = alpha.weeks.iter().map(|w| w.rank).collect();
+    assert_eq!(ranks, [1, 2, 1]);
+    assert_eq!(alpha.games, 3);
+    assert_eq!(alpha.top_12, 3);
+    assert_eq!(alpha.median_rank, 1.0);
+    assert!((alpha.rank_variance - 2.0 / 9.0).abs() < 1e-9);
+    let bravo: Vec<u32> = history[2].weeks.iter().map(|w| w.rank).collect();
+    assert_eq!(bravo, [1, 1, 2]);
+
+    // The DNP week is left out rather than ranked last
+    let charlie = &history[3];
+    let weeks: Vec<(u16, u32)> = charlie
+        .weeks
+        .iter()
+        .map(|w| (w.week.as_u16(), w.rank))
+        .collect();
+    assert_eq!(weeks, [(1, 3), (3, 2)]);
+    assert_eq!(charlie.median_rank, 2.5);
+
+    // Position filters and the games minimum narrow the list, not the ranks
+    let receivers = db
+        .positional_rank_history(TEST_LEAGUE, season, Some(&[Position::WR]), 3)
+        .unwrap();
+    let ids: Vec<i64> = receivers.iter().map(|h| h.player_id.as_i64()).collect();
+    assert_eq!(ids, [1, 2]);
+    assert_eq!(receivers[0].weeks, alpha.weeks);
+    let flex = db
+        .positional_rank_history(TEST_LEAGUE, season, Some(&[Position::FLEX]), 1)
+        .unwrap();
+    assert_eq!(flex.len(), 4);
+
+    assert!(db
+        .positional_rank_history(TEST_LEAGUE, Season::new(2022), None, 1)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_pace_extrapolation_counts_team_games_left() {
+    use espn_ffl::storage::analysis::{extrapolate_pace, remaining_team_games, SEASON_GAMES};
+
+    assert_eq!(remaining_team_games(0, false), SEASON_GAMES);
+    // Four weeks in: four games played, or three once the bye has passed
+    assert_eq!(remaining_team_games(4, false), 13);
+    assert_eq!(remaining_team_games(4, true), 14);
+    assert_eq!(remaining_team_games(18, true), 0);
+    assert_eq!(remaining_team_games(18, false), 0);
+
+    assert_eq!(extrapolate_pace(36.0, 3, 14), 204.0);
+    assert_eq!(extrapolate_pace(0.0, 0, 14), 0.0);
+}
+
+#[test]
+fn test_season_pace_skips_byes_and_dnp_weeks() {
+    use espn_ffl::Position;
+
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    for (id, name, position) in [
+        (1, "Alpha Receiver", "WR"),
+        (2, "Bravo Back", "RB"),
+        (3, "Charlie Receiver", "WR"),
+    ] {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: None,
+        })
+        .unwrap();
+    }
+    // (player, week, projected, actual, played)
+    let weeks = [
+        // Bye in week 3
+        (1, 1, Some(10.0), 12.0, true),
+        (1, 2, Some(10.0), 12.0, true),
+        (1, 3, Some(0.0), 0.0, false),
+        (1, 4, Some(10.0), 12.0, true),
+        // Missed week 2; bye still to come
+        (2, 1, Some(15.0), 10.0, true),
+        (2, 2, Some(15.0), 0.0, false),
+        (2, 3, Some(15.0), 10.0, true),
+        (2, 4, Some(15.0), 10.0, true),
+        // Never projected
+        (3, 1, None, 5.0, true),
+    ];
+    for (id, week, projected, actual, played) in weeks {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(id),
+            season,
+            Week::new(week),
+            projected,
+            Some(actual),
+            0,
+            0,
+        );
+        stats.league_id = Some(TEST_LEAGUE);
+        stats.played = Some(played);
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+
+    let paces = db.season_pace(TEST_LEAGUE, season, None, 1).unwrap();
+    let ids: Vec<i64> = paces.iter().map(|p| p.player_id.as_i64()).collect();
+    // Biggest gap to the expectation first; no expectation last
+    assert_eq!(ids, [2, 1, 3]);
+
+    let bravo = &paces[0];
+    assert_eq!((bravo.games, bravo.remaining_games), (3, 13));
+    assert_eq!(bravo.pace, 30.0 + 10.0 * 13.0);
+    assert_eq!(bravo.expectation, Some(15.0 * 17.0));
+    assert_eq!(bravo.vs_expectation, Some(160.0 - 255.0));
+
+    let alpha = &paces[1];
+    assert_eq!((alpha.games, alpha.remaining_games), (3, 14));
+    assert_eq!(alpha.pace, 204.0);
+    assert_eq!(alpha.expectation_week, Some(Week::new(1)));
+    assert_eq!(alpha.vs_expectation, Some(34.0));
+
+    let charlie = &paces[2];
+    assert_eq!((charlie.games, charlie.remaining_games), (1, 13));
+    assert_eq!(charlie.pace, 70.0);
+    assert_eq!(charlie.expectation, None);
+
+    let receivers = db
+        .season_pace(TEST_LEAGUE, season, Some(&[Position::WR]), 2)
+        .unwrap();
+    let ids: Vec<i64> = receivers.iter().map(|p| p.player_id.as_i64()).collect();
+    assert_eq!(ids, [1]);
+
+    assert!(db
+        .season_pace(TEST_LEAGUE, Season::new(2022), None, 1)
+        .unwrap()
+        .is_empty());
+}
+
+/// Two stored weeks: a breakout receiver, a back who got hurt, a free agent
+/// whose ownership jumped and a kicker only stored in the later week
+fn seed_week_over_week_db() -> PlayerDatabase {
+    let mut db = create_test_db();
+    let season = Season::new(2023);
+    for (id, name, position) in [
+        (1, "Alpha Receiver", "WR"),
+        (2, "Bravo Back", "RB"),
+        (3, "Charlie Receiver", "WR"),
+        (4, "Delta Kicker", "K"),
+    ] {
+        db.upsert_player(&Player {
+            player_id: PlayerId::new(id),
+            name: name.to_string(),
+            position: position.to_string(),
+            team: None,
+        })
+        .unwrap();
+    }
+    // (player, week, projected, actual, owned, status, rostered)
+    let weeks = [
+        (1, 4, 9.0, 6.0, 40.0, "Active", true),
+        (1, 5, 14.0, 25.0, 85.0, "Active", true),
+        (2, 4, 16.0, 20.0, 99.0, "Active", true),
+        (2, 5, 8.0, 3.0, 98.0, "Questionable", true),
+        (3, 4, 4.0, 2.0, 3.0, "Active", false),
+        (3, 5, 7.0, 12.0, 31.0, "Active", false),
+        (4, 5, 8.0, 8.0, 60.0, "Active", true),
+    ];
+    for (id, week, projected, actual, owned, status, rostered) in weeks {
+        let mut stats = PlayerWeeklyStats::test_with_fields(
+            PlayerId::new(id),
+            season,
+            Week::new(week),
+            Some(projected),
+            Some(actual),
+            0,
+            0,
+        );
+        stats.league_id = Some(TEST_LEAGUE);
+        stats.percent_owned = Some(owned);
+        stats.injury_status = Some(status.parse().unwrap());
+        stats.is_rostered = Some(rostered);
+        stats.fantasy_team_name = rostered.then(|| "Team Alpha".to_string());
+        db.upsert_weekly_stats(&stats, false).unwrap();
+    }
+    db
+}
+
+#[test]
+fn test_week_over_week_compares_stored_weeks() {
+    use espn_ffl::cli::types::filters::RosterStatusFilter;
+    use espn_ffl::Position;
+
+    let db = seed_week_over_week_db();
+    let (season, from, to) = (Season::new(2023), Week::new(4), Week::new(5));
+
+    let changes = db
+        .week_over_week(TEST_LEAGUE, season, from, to, None, None)
+        .unwrap();
+    let ids: Vec<i64> = changes.iter().map(|c| c.player_id.as_i64()).collect();
+    assert_eq!(ids, [1, 2, 3, 4]);
+
+    let alpha = &changes[0];
+    assert_eq!(alpha.actual_delta, Some(19.0));
+    assert_eq!(alpha.projected_delta, Some(5.0));
+    assert_eq!(alpha.owned_delta, Some(45.0));
+    assert!(!alpha.status_changed());
+
+    let bravo = &changes[1];
+    assert_eq!(bravo.actual_delta, Some(-17.0));
+    assert_eq!(bravo.injury_status_from.as_deref(), Some("Active"));
+    assert_eq!(bravo.injury_status_to.as_deref(), Some("Questionable"));
+    assert!(bravo.status_changed());
+
+    // Only stored in the later week: no deltas, no status change
+    let delta = &changes[3];
+    assert_eq!(delta.actual_from, None);
+    assert_eq!(delta.actual_to, Some(8.0));
+    assert_eq!(delta.actual_delta, None);
+    assert!(!delta.status_changed());
+
+    let receivers = db
+        .week_over_week(TEST_LEAGUE, season, from, to, Some(&[Position::WR]), None)
+        .unwrap();
+    let ids: Vec<i64> = receivers.iter().map(|c| c.player_id.as_i64()).collect();
+    assert_eq!(ids, [1, 3]);
+    let free_agents = db
+        .week_over_week(
+            TEST_LEAGUE,
+            season,
+            from,
+            to,
+            None,
+            Some(&RosterStatusFilter::FA),
+        )
+        .unwrap();
+    let ids: Vec<i64> = free_agents.iter().map(|c| c.player_id.as_i64()).collect();
+    assert_eq!(ids, [3]);
+
+    assert!(db
+        .week_over_week(TEST_LEAGUE, Season::new(2022), from, to, None, None)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_risers_and_fallers_rank_by_metric() {
+    use espn_ffl::cli::types::filters::WeekOverWeekMetric;
+    use espn_ffl::storage::analysis::risers_and_fallers;
+
+    let db = seed_week_over_week_db();
+    let changes = db
+        .week_over_week(
+            TEST_LEAGUE,
+            Season::new(2023),
+            Week::new(4),
+            Week::new(5),
+            None,
+            None,
+        )
+        .unwrap();
+    let ids = |changes: &[&WeekOverWeekChange]| -> Vec<i64> {
+        changes.iter().map(|c| c.player_id.as_i64()).collect()
+    };
+
+    let (risers, fallers) = risers_and_fallers(&changes, WeekOverWeekMetric::Actual, 10);
+    assert_eq!(ids(&risers), [1, 3]);
+    assert_eq!(ids(&fallers), [2]);
+
+    let (risers, fallers) = risers_and_fallers(&changes, WeekOverWeekMetric::Owned, 1);
+    assert_eq!(ids(&risers), [1]);
+    assert_eq!(ids(&fallers), [2]);
+
+    let (risers, fallers) = risers_and_fallers(&changes, WeekOverWeekMetric::Projected, 10);
+    assert_eq!(ids(&risers), [1, 3]);
+    assert_eq!(ids(&fallers), [2]);
+}
+
+#[test]
+fn test_injury_status_changes_are_recorded_per_week() {
+    use espn_ffl::espn::types::InjuryStatus;
+
+    let mut db = create_test_db_with_player();
+    let (season, week) = (Season::new(2025), Week::new(5));
+    let with_status = |status: InjuryStatus| {
+        let mut stats =
+            PlayerWeeklyStats::test_minimal(PlayerId::new(12345), season, week, None, None);
+        stats.injury_status = Some(status);
+        vec![stats]
+    };
+
+    // The first designation and an unchanged one aren't changes
+    assert_eq!(
+        db.record_injury_statuses(&with_status(InjuryStatus::Questionable), 100)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        db.record_injury_statuses(&with_status(InjuryStatus::Questionable), 200)
+            .unwrap(),
+        0
+    );
+    assert!(db.get_status_changes(season, week, 0).unwrap().is_empty());
+
+    db.record_injury_statuses(&with_status(InjuryStatus::Out), 300)
+        .unwrap();
+    let changes = db.get_status_changes(season, week, 250).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].player_id, PlayerId::new(12345));
+    assert_eq!(changes[0].previous_status, InjuryStatus::Questionable);
+    assert_eq!(changes[0].status, InjuryStatus::Out);
+    assert_eq!(changes[0].observed_at, 300);
+
+    // Too early for the window, or another week
+    assert!(db.get_status_changes(season, week, 301).unwrap().is_empty());
+    assert!(db
+        .get_status_changes(season, Week::new(6), 0)
+        .unwrap()
+        .is_empty());
+}